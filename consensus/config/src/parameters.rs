@@ -83,6 +83,14 @@ pub struct Parameters {
     #[serde(default = "Parameters::default_commit_sync_batches_ahead")]
     pub commit_sync_batches_ahead: usize,
 
+    /// Strategy for electing the leaders of each round.
+    ///
+    /// NOTE: unlike other fields, this must be set identically on every authority of the
+    /// committee. Authorities disagreeing on leaders cannot commit consistently, so non-default
+    /// strategies are only meant for benchmarks and test networks.
+    #[serde(default = "LeaderElectionStrategy::default")]
+    pub leader_election: LeaderElectionStrategy,
+
     /// Anemo network settings.
     #[serde(default = "AnemoParameters::default")]
     pub anemo: AnemoParameters,
@@ -206,12 +214,34 @@ impl Default for Parameters {
             commit_sync_parallel_fetches: Parameters::default_commit_sync_parallel_fetches(),
             commit_sync_batch_size: Parameters::default_commit_sync_batch_size(),
             commit_sync_batches_ahead: Parameters::default_commit_sync_batches_ahead(),
+            leader_election: LeaderElectionStrategy::default(),
             anemo: AnemoParameters::default(),
             tonic: TonicParameters::default(),
         }
     }
 }
 
+/// Strategies for electing the leaders of each round, and adapting the schedule of leaders to the
+/// reputation scores computed periodically from committed blocks.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum LeaderElectionStrategy {
+    /// Leaders are elected at random weighted by stake, and the authorities with the lowest
+    /// reputation scores are swapped out for those with the highest scores.
+    #[default]
+    ReputationSwap,
+    /// Leaders rotate in a fixed order where each authority appears in proportion to its stake.
+    /// Reputation scores are ignored.
+    StakeWeightedRoundRobin,
+    /// Like `ReputationSwap`, with the scores of previous schedules carried over to the next one
+    /// after decaying by `decay_percent` per schedule, so a single bad period is not forgotten
+    /// immediately.
+    DecayingReputation { decay_percent: u8 },
+    /// Like `ReputationSwap`, with scores discounted by how many rounds it takes on average for
+    /// blocks of each authority to get committed, which grows with their propagation latency.
+    LatencyAware,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AnemoParameters {
     /// Size in bytes above which network messages are considered excessively large. Excessively
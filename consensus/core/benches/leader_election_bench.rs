@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use consensus_config::{AuthorityIndex, LeaderElectionStrategy};
+use consensus_core::measure_commit_latency;
+use criterion::*;
+
+const NUM_AUTHORITIES: usize = 4;
+const NUM_ROUNDS: u32 = 200;
+
+const STRATEGIES: [LeaderElectionStrategy; 4] = [
+    LeaderElectionStrategy::ReputationSwap,
+    LeaderElectionStrategy::StakeWeightedRoundRobin,
+    LeaderElectionStrategy::DecayingReputation { decay_percent: 50 },
+    LeaderElectionStrategy::LatencyAware,
+];
+
+fn leader_election_benchmark(c: &mut Criterion) {
+    let slow_authorities = [AuthorityIndex::new_for_test(3)];
+
+    let mut group = c.benchmark_group("leader_election");
+    group.sample_size(10);
+    for strategy in STRATEGIES {
+        let report =
+            measure_commit_latency(strategy, NUM_AUTHORITIES, NUM_ROUNDS, &slow_authorities);
+        println!("{report:?}");
+
+        group.bench_function(format!("{strategy:?}"), |b| {
+            b.iter(|| {
+                measure_commit_latency(strategy, NUM_AUTHORITIES, NUM_ROUNDS, &slow_authorities)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, leader_election_benchmark);
+criterion_main!(benches);
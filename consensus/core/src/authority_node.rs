@@ -10,6 +10,8 @@ use prometheus::Registry;
 use sui_protocol_config::{ConsensusNetwork, ProtocolConfig};
 use tracing::{info, warn};
 
+#[cfg(any(test, feature = "simulator"))]
+use crate::network::simulated_network::{SimulatedManager, SimulatedNetwork};
 use crate::{
     authority_service::AuthorityService,
     block_manager::BlockManager,
@@ -43,6 +45,8 @@ use crate::{
 pub enum ConsensusAuthority {
     WithAnemo(AuthorityNode<AnemoManager>),
     WithTonic(AuthorityNode<TonicManager>),
+    #[cfg(any(test, feature = "simulator"))]
+    WithSimulated(AuthorityNode<SimulatedManager>),
 }
 
 impl ConsensusAuthority {
@@ -99,10 +103,43 @@ impl ConsensusAuthority {
         }
     }
 
+    /// Starts an authority connected to the other authorities of a simulation through
+    /// `network`, which delivers, delays or drops their messages.
+    #[cfg(any(test, feature = "simulator"))]
+    pub(crate) async fn start_simulated(
+        network: Arc<SimulatedNetwork>,
+        own_index: AuthorityIndex,
+        committee: Committee,
+        parameters: Parameters,
+        protocol_config: ProtocolConfig,
+        protocol_keypair: ProtocolKeyPair,
+        transaction_verifier: Arc<dyn TransactionVerifier>,
+        commit_consumer: CommitConsumer,
+        registry: Registry,
+        boot_counter: u64,
+    ) -> Self {
+        let authority = AuthorityNode::start_with_network_manager(
+            own_index,
+            committee,
+            parameters,
+            protocol_config,
+            protocol_keypair,
+            |context| SimulatedManager::new_simulated(context, network),
+            transaction_verifier,
+            commit_consumer,
+            registry,
+            boot_counter,
+        )
+        .await;
+        Self::WithSimulated(authority)
+    }
+
     pub async fn stop(self) {
         match self {
             Self::WithAnemo(authority) => authority.stop().await,
             Self::WithTonic(authority) => authority.stop().await,
+            #[cfg(any(test, feature = "simulator"))]
+            Self::WithSimulated(authority) => authority.stop().await,
         }
     }
 
@@ -110,6 +147,8 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => authority.transaction_client(),
             Self::WithTonic(authority) => authority.transaction_client(),
+            #[cfg(any(test, feature = "simulator"))]
+            Self::WithSimulated(authority) => authority.transaction_client(),
         }
     }
 
@@ -117,6 +156,8 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => authority.replay_complete().await,
             Self::WithTonic(authority) => authority.replay_complete().await,
+            #[cfg(any(test, feature = "simulator"))]
+            Self::WithSimulated(authority) => authority.replay_complete().await,
         }
     }
 
@@ -125,6 +166,8 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => &authority.context,
             Self::WithTonic(authority) => &authority.context,
+            #[cfg(any(test, feature = "simulator"))]
+            Self::WithSimulated(authority) => &authority.context,
         }
     }
 
@@ -133,6 +176,8 @@ impl ConsensusAuthority {
         match self {
             Self::WithAnemo(authority) => authority.sync_last_known_own_block,
             Self::WithTonic(authority) => authority.sync_last_known_own_block,
+            #[cfg(any(test, feature = "simulator"))]
+            Self::WithSimulated(authority) => authority.sync_last_known_own_block,
         }
     }
 }
@@ -176,6 +221,34 @@ where
         commit_consumer: CommitConsumer,
         registry: Registry,
         boot_counter: u64,
+    ) -> Self {
+        Self::start_with_network_manager(
+            own_index,
+            committee,
+            parameters,
+            protocol_config,
+            protocol_keypair,
+            |context| N::new(context, network_keypair),
+            transaction_verifier,
+            commit_consumer,
+            registry,
+            boot_counter,
+        )
+        .await
+    }
+
+    /// Starts the authority with the network manager returned by `new_network_manager`.
+    async fn start_with_network_manager(
+        own_index: AuthorityIndex,
+        committee: Committee,
+        parameters: Parameters,
+        protocol_config: ProtocolConfig,
+        protocol_keypair: ProtocolKeyPair,
+        new_network_manager: impl FnOnce(Arc<Context>) -> N,
+        transaction_verifier: Arc<dyn TransactionVerifier>,
+        commit_consumer: CommitConsumer,
+        registry: Registry,
+        boot_counter: u64,
     ) -> Self {
         assert!(
             committee.is_valid_index(own_index),
@@ -211,7 +284,7 @@ where
 
         let (core_signals, signals_receivers) = CoreSignals::new(context.clone());
 
-        let mut network_manager = N::new(context.clone(), network_keypair);
+        let mut network_manager = new_network_manager(context.clone());
         let network_client = network_manager.client();
 
         // REQUIRED: Broadcaster must be created before Core, to start listening on the
@@ -111,6 +111,17 @@ impl<C: CoreThreadDispatcher> NetworkService for AuthorityService<C> {
                 .with_label_values(&[peer_hostname, "handle_send_block", e.clone().name()])
                 .inc();
             info!("Invalid block from {}: {}", peer, e);
+            // A correctly signed block that fails verification proves its author signed an invalid
+            // block, which any other authority can check. Failures before the signature is checked
+            // are not attributable to the author.
+            if signed_block.epoch() == self.context.committee.epoch()
+                && signed_block.round() != GENESIS_ROUND
+                && signed_block.verify_signature(&self.context).is_ok()
+            {
+                self.dag_state
+                    .write()
+                    .report_invalid_block(&signed_block, serialized_block.block.clone());
+            }
             return Err(e);
         }
         let verified_block = VerifiedBlock::new_verified(signed_block, serialized_block.block);
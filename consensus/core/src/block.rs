@@ -723,11 +723,22 @@ impl TestBlock {
         self
     }
 
+    pub fn set_misbehavior_reports(mut self, misbehavior_reports: Vec<MisbehaviorReport>) -> Self {
+        self.block.misbehavior_reports = misbehavior_reports;
+        self
+    }
+
     pub fn build(self) -> Block {
         Block::V1(self.block)
     }
 }
 
+/// Maximum number of misbehavior reports a block can attach.
+pub(crate) const MAX_MISBEHAVIOR_REPORTS_PER_BLOCK: usize = 10;
+
+/// Maximum total serialized size of the misbehavior reports a block can attach.
+pub(crate) const MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK: usize = 2 << 20;
+
 /// A block can attach reports of misbehavior by other authorities.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct MisbehaviorReport {
@@ -738,7 +749,23 @@ pub struct MisbehaviorReport {
 /// Proof of misbehavior are usually signed block(s) from the misbehaving authority.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum MisbehaviorProof {
-    InvalidBlock(BlockRef),
+    /// A serialized `SignedBlock` with a valid signature from the authority, that fails
+    /// `BlockVerifier::verify`. Only checks that do not depend on local state are covered, so every
+    /// authority can verify the proof independently.
+    InvalidBlock(Bytes),
+    /// Two serialized `SignedBlock`s from the same slot, with valid signatures from the authority
+    /// but different digests.
+    Equivocation(Bytes, Bytes),
+}
+
+impl MisbehaviorProof {
+    /// Returns the name of the proof type, for metrics and logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MisbehaviorProof::InvalidBlock(_) => "invalid_block",
+            MisbehaviorProof::Equivocation(_, _) => "equivocation",
+        }
+    }
 }
 
 // TODO: add basic verification for BlockRef and BlockDigest.
@@ -5,8 +5,9 @@ use std::{collections::BTreeSet, sync::Arc};
 
 use crate::{
     block::{
-        genesis_blocks, BlockAPI, BlockRef, BlockTimestampMs, SignedBlock, VerifiedBlock,
-        GENESIS_ROUND,
+        genesis_blocks, BlockAPI, BlockRef, BlockTimestampMs, MisbehaviorProof, SignedBlock,
+        VerifiedBlock, GENESIS_ROUND, MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK,
+        MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
     },
     context::Context,
     error::{ConsensusError, ConsensusResult},
@@ -95,11 +96,11 @@ impl SignedBlockVerifier {
         }
         Ok(())
     }
-}
 
-// All block verification logic are implemented below.
-impl BlockVerifier for SignedBlockVerifier {
-    fn verify(&self, block: &SignedBlock) -> ConsensusResult<()> {
+    /// Verifies a block's metadata, transactions and misbehavior reports. Proof blocks inside
+    /// the reports are verified with `verify_proof_blocks` unset, so a proof is never checked
+    /// against proofs nested inside it.
+    fn verify_block(&self, block: &SignedBlock, verify_proof_blocks: bool) -> ConsensusResult<()> {
         let committee = &self.context.committee;
         // The block must belong to the current epoch and have valid authority index,
         // before having its signature verified.
@@ -187,7 +188,120 @@ impl BlockVerifier for SignedBlockVerifier {
 
         self.transaction_verifier
             .verify_batch(&batch)
-            .map_err(|e| ConsensusError::InvalidTransaction(format!("{e:?}")))
+            .map_err(|e| ConsensusError::InvalidTransaction(format!("{e:?}")))?;
+
+        self.check_misbehavior_reports_impl(block, verify_proof_blocks)
+    }
+
+    /// Verifies the misbehavior reports attached to a block. Every report carries the signed
+    /// block(s) proving the misbehavior, which are checked in full.
+    pub(crate) fn check_misbehavior_reports(&self, block: &SignedBlock) -> ConsensusResult<()> {
+        self.check_misbehavior_reports_impl(block, true)
+    }
+
+    /// Without `verify_proof_blocks`, reports are checked for their limits, targets and
+    /// signatures, but the blocks offered as proof of an invalid block are not verified. This
+    /// keeps verification to one level of nesting however deeply proofs are nested.
+    fn check_misbehavior_reports_impl(
+        &self,
+        block: &SignedBlock,
+        verify_proof_blocks: bool,
+    ) -> ConsensusResult<()> {
+        let committee = &self.context.committee;
+        let reports = block.misbehavior_reports();
+        let max_reports = if self.context.protocol_config.consensus_misbehavior_reports() {
+            MAX_MISBEHAVIOR_REPORTS_PER_BLOCK
+        } else {
+            0
+        };
+        if reports.len() > max_reports {
+            return Err(ConsensusError::TooManyMisbehaviorReports(
+                reports.len(),
+                max_reports,
+            ));
+        }
+        let mut reports_bytes = 0;
+        for report in reports {
+            reports_bytes +=
+                bcs::serialized_size(report).map_err(ConsensusError::SerializationFailure)?;
+        }
+        if reports_bytes > MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK {
+            return Err(ConsensusError::TooManyMisbehaviorReportBytes(
+                reports_bytes,
+                MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK,
+            ));
+        }
+
+        for report in reports {
+            let target = report.target;
+            let invalid = |reason: &str| ConsensusError::InvalidMisbehaviorReport {
+                target,
+                reason: reason.to_string(),
+            };
+            if !committee.is_valid_index(target) {
+                return Err(ConsensusError::InvalidAuthorityIndex {
+                    index: target,
+                    max: committee.size() - 1,
+                });
+            }
+            if target == block.author() {
+                return Err(invalid("authority reported itself"));
+            }
+
+            match &report.proof {
+                MisbehaviorProof::InvalidBlock(serialized) => {
+                    let invalid_block: SignedBlock =
+                        bcs::from_bytes(serialized).map_err(ConsensusError::MalformedBlock)?;
+                    if invalid_block.epoch() != committee.epoch() {
+                        return Err(invalid("block is from a different epoch"));
+                    }
+                    if invalid_block.author() != target {
+                        return Err(invalid("block is from a different authority"));
+                    }
+                    if invalid_block.round() == GENESIS_ROUND {
+                        return Err(invalid("genesis blocks cannot be invalid"));
+                    }
+                    // Only a block signed by the target proves it misbehaved. Once the signature is
+                    // valid, any other verification failure is the target's.
+                    invalid_block.verify_signature(&self.context)?;
+                    if verify_proof_blocks && self.verify_block(&invalid_block, false).is_ok() {
+                        return Err(invalid("block is valid"));
+                    }
+                }
+                MisbehaviorProof::Equivocation(first, second) => {
+                    let first_block: SignedBlock =
+                        bcs::from_bytes(first).map_err(ConsensusError::MalformedBlock)?;
+                    let second_block: SignedBlock =
+                        bcs::from_bytes(second).map_err(ConsensusError::MalformedBlock)?;
+                    for equivocating_block in [&first_block, &second_block] {
+                        if equivocating_block.epoch() != committee.epoch() {
+                            return Err(invalid("block is from a different epoch"));
+                        }
+                        if equivocating_block.author() != target {
+                            return Err(invalid("block is from a different authority"));
+                        }
+                    }
+                    if first_block.round() != second_block.round() {
+                        return Err(invalid("blocks are from different rounds"));
+                    }
+                    if VerifiedBlock::compute_digest(first) == VerifiedBlock::compute_digest(second)
+                    {
+                        return Err(invalid("blocks are identical"));
+                    }
+                    first_block.verify_signature(&self.context)?;
+                    second_block.verify_signature(&self.context)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// All block verification logic are implemented below.
+impl BlockVerifier for SignedBlockVerifier {
+    fn verify(&self, block: &SignedBlock) -> ConsensusResult<()> {
+        self.verify_block(block, true)
     }
 
     fn check_ancestors(
@@ -262,7 +376,9 @@ mod test {
 
     use super::*;
     use crate::{
-        block::{BlockDigest, BlockRef, TestBlock, Transaction, TransactionIndex},
+        block::{
+            BlockDigest, BlockRef, MisbehaviorReport, TestBlock, Transaction, TransactionIndex,
+        },
         context::Context,
         transaction::{TransactionVerifier, ValidationError},
     };
@@ -689,4 +805,247 @@ mod test {
             ));
         }
     }
+
+    #[tokio::test]
+    async fn test_check_misbehavior_reports() {
+        let (mut context, keypairs) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_consensus_misbehavior_reports_for_testing(true);
+        let context = Arc::new(context);
+        let verifier = SignedBlockVerifier::new(context.clone(), Arc::new(TxnSizeVerifier {}));
+
+        // Two different blocks from authority 1 at the same slot.
+        let equivocating_block = |timestamp_ms| {
+            let block = TestBlock::new(10, 1).set_timestamp_ms(timestamp_ms).build();
+            SignedBlock::new(block, &keypairs[1].1)
+                .unwrap()
+                .serialize()
+                .unwrap()
+        };
+        let first = equivocating_block(1000);
+        let second = equivocating_block(1001);
+
+        let report_by_authority_2 = |report: MisbehaviorReport| {
+            let block = TestBlock::new(11, 2)
+                .set_misbehavior_reports(vec![report])
+                .build();
+            SignedBlock::new(block, &keypairs[2].1).unwrap()
+        };
+
+        // Valid equivocation report.
+        {
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::Equivocation(first.clone(), second.clone()),
+            });
+            verifier.check_misbehavior_reports(&block).unwrap();
+        }
+
+        // Valid invalid block report: the block from authority 1 has no ancestors.
+        let signed_by_authority_1 = |block: TestBlock| {
+            SignedBlock::new(block.build(), &keypairs[1].1)
+                .unwrap()
+                .serialize()
+                .unwrap()
+        };
+        {
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(first.clone()),
+            });
+            verifier.check_misbehavior_reports(&block).unwrap();
+        }
+
+        // A valid block is not evidence of misbehavior.
+        {
+            let valid_block = TestBlock::new(10, 1).set_ancestors(vec![
+                BlockRef::new(9, AuthorityIndex::new_for_test(1), BlockDigest::MIN),
+                BlockRef::new(9, AuthorityIndex::new_for_test(0), BlockDigest::MIN),
+                BlockRef::new(9, AuthorityIndex::new_for_test(2), BlockDigest::MIN),
+            ]);
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(signed_by_authority_1(valid_block)),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::InvalidMisbehaviorReport { .. })
+            ));
+        }
+
+        // Proofs nested inside a proof block are not verified. Authority 1's block is valid apart
+        // from reporting a valid block of authority 3, so it is not evidence of misbehavior.
+        {
+            let valid_block_by_authority_3 = SignedBlock::new(
+                TestBlock::new(9, 3)
+                    .set_ancestors(vec![
+                        BlockRef::new(8, AuthorityIndex::new_for_test(3), BlockDigest::MIN),
+                        BlockRef::new(8, AuthorityIndex::new_for_test(0), BlockDigest::MIN),
+                        BlockRef::new(8, AuthorityIndex::new_for_test(1), BlockDigest::MIN),
+                    ])
+                    .build(),
+                &keypairs[3].1,
+            )
+            .unwrap()
+            .serialize()
+            .unwrap();
+            let proof_block = TestBlock::new(10, 1)
+                .set_ancestors(vec![
+                    BlockRef::new(9, AuthorityIndex::new_for_test(1), BlockDigest::MIN),
+                    BlockRef::new(9, AuthorityIndex::new_for_test(0), BlockDigest::MIN),
+                    BlockRef::new(9, AuthorityIndex::new_for_test(2), BlockDigest::MIN),
+                ])
+                .set_misbehavior_reports(vec![MisbehaviorReport {
+                    target: AuthorityIndex::new_for_test(3),
+                    proof: MisbehaviorProof::InvalidBlock(valid_block_by_authority_3),
+                }]);
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(signed_by_authority_1(proof_block)),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::InvalidMisbehaviorReport { .. })
+            ));
+        }
+
+        // Deeply nested proofs, alternating between authorities 1 and 3, are only verified one
+        // level deep. Every nested block is otherwise valid, so the outermost proof block passes
+        // verification without its nested proofs being examined.
+        {
+            let mut proof = signed_by_authority_1(TestBlock::new(10, 1));
+            for depth in 0..64 {
+                let (author, target) = if depth % 2 == 0 { (3, 1) } else { (1, 3) };
+                let block = TestBlock::new(10, author)
+                    .set_ancestors(
+                        [author, 0, 2]
+                            .into_iter()
+                            .map(|a| {
+                                BlockRef::new(9, AuthorityIndex::new_for_test(a), BlockDigest::MIN)
+                            })
+                            .collect(),
+                    )
+                    .set_misbehavior_reports(vec![MisbehaviorReport {
+                        target: AuthorityIndex::new_for_test(target),
+                        proof: MisbehaviorProof::InvalidBlock(proof),
+                    }])
+                    .build();
+                proof = SignedBlock::new(block, &keypairs[author as usize].1)
+                    .unwrap()
+                    .serialize()
+                    .unwrap();
+            }
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(proof),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::InvalidMisbehaviorReport { .. })
+            ));
+        }
+
+        // An invalid block without a valid signature from the target is not evidence either.
+        {
+            let block = TestBlock::new(10, 1).build();
+            let signed_by_authority_3 = SignedBlock::new(block, &keypairs[3].1)
+                .unwrap()
+                .serialize()
+                .unwrap();
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(signed_by_authority_3),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::SignatureVerificationFailure(_))
+            ));
+        }
+
+        // Reports over the size limit.
+        {
+            let oversized_block = TestBlock::new(10, 1).set_transactions(vec![Transaction::new(
+                vec![4; MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK],
+            )]);
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(signed_by_authority_1(oversized_block)),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::TooManyMisbehaviorReportBytes(..))
+            ));
+        }
+
+        // Too many reports.
+        {
+            let report = MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::InvalidBlock(first.clone()),
+            };
+            let block = TestBlock::new(11, 2)
+                .set_misbehavior_reports(vec![report; MAX_MISBEHAVIOR_REPORTS_PER_BLOCK + 1])
+                .build();
+            let block = SignedBlock::new(block, &keypairs[2].1).unwrap();
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::TooManyMisbehaviorReports(..))
+            ));
+        }
+
+        // Identical blocks are not an equivocation.
+        {
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::Equivocation(first.clone(), first.clone()),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::InvalidMisbehaviorReport { .. })
+            ));
+        }
+
+        // Blocks from an authority other than the target.
+        {
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(3),
+                proof: MisbehaviorProof::Equivocation(first.clone(), second.clone()),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::InvalidMisbehaviorReport { .. })
+            ));
+        }
+
+        // Authorities cannot report themselves.
+        {
+            let block = TestBlock::new(11, 1)
+                .set_misbehavior_reports(vec![MisbehaviorReport {
+                    target: AuthorityIndex::new_for_test(1),
+                    proof: MisbehaviorProof::Equivocation(first.clone(), second.clone()),
+                }])
+                .build();
+            let block = SignedBlock::new(block, &keypairs[1].1).unwrap();
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::InvalidMisbehaviorReport { .. })
+            ));
+        }
+
+        // Reports are rejected while the feature is disabled.
+        {
+            let (context, _) = Context::new_for_test(4);
+            let verifier =
+                SignedBlockVerifier::new(Arc::new(context), Arc::new(TxnSizeVerifier {}));
+            let block = report_by_authority_2(MisbehaviorReport {
+                target: AuthorityIndex::new_for_test(1),
+                proof: MisbehaviorProof::Equivocation(first.clone(), second.clone()),
+            });
+            assert!(matches!(
+                verifier.check_misbehavior_reports(&block),
+                Err(ConsensusError::TooManyMisbehaviorReports(1, 0))
+            ));
+        }
+    }
 }
@@ -163,7 +163,7 @@ impl TrustedCommit {
         }
     }
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "bench"))]
     pub(crate) fn new_for_test(
         index: CommitIndex,
         previous_digest: CommitDigest,
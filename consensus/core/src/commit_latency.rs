@@ -0,0 +1,187 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the commit latency of leader election strategies, over DAGs built with `DagBuilder`
+//! where some authorities are slow to propagate their blocks.
+//!
+//! This backs the `leader_election_bench` criterion benchmark, and is exported for it with the
+//! `bench` feature.
+
+use std::sync::Arc;
+
+use consensus_config::{
+    local_committee_and_keys, AuthorityIndex, LeaderElectionStrategy, Parameters,
+};
+use parking_lot::RwLock;
+use prometheus::Registry;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, Round, Slot},
+    context::{Clock, Context},
+    dag_state::DagState,
+    leader_schedule::{LeaderSchedule, LeaderSwapTable},
+    linearizer::Linearizer,
+    metrics::initialise_metrics,
+    storage::mem_store::MemStore,
+    test_dag_builder::DagBuilder,
+    universal_committer::universal_committer_builder::UniversalCommitterBuilder,
+};
+
+/// Number of commits between leader schedule updates.
+const COMMITS_PER_SCHEDULE: u64 = 10;
+
+/// Commit latency observed with a leader election strategy.
+#[derive(Debug)]
+pub struct LatencyReport {
+    pub strategy: LeaderElectionStrategy,
+    pub committed_leaders: usize,
+    pub skipped_leaders: usize,
+    /// Average number of rounds between the round of a block and the round of the DAG when its
+    /// commit was decided.
+    pub average_latency_rounds: f64,
+}
+
+/// Builds a DAG of `num_rounds` rounds over a committee of `num_authorities` with equal stake, one
+/// round at a time, committing with `strategy` after each round as `Core` does, with leader
+/// schedule updates every `COMMITS_PER_SCHEDULE` commits.
+///
+/// Blocks of `slow_authorities` reach the other authorities a round late, so they are only linked
+/// two rounds after they are proposed. Slow authorities receive blocks in time.
+pub fn measure_commit_latency(
+    strategy: LeaderElectionStrategy,
+    num_authorities: usize,
+    num_rounds: Round,
+    slow_authorities: &[AuthorityIndex],
+) -> LatencyReport {
+    let (committee, _) = local_committee_and_keys(0, vec![1; num_authorities]);
+    let parameters = Parameters {
+        leader_election: strategy,
+        ..Default::default()
+    };
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_consensus_bad_nodes_stake_threshold_for_testing(33);
+    let context = Arc::new(Context::new(
+        AuthorityIndex::ZERO,
+        committee,
+        parameters,
+        protocol_config,
+        initialise_metrics(Registry::new()),
+        Arc::new(Clock::new()),
+    ));
+
+    let dag_state = Arc::new(RwLock::new(DagState::new(
+        context.clone(),
+        Arc::new(MemStore::new()),
+    )));
+    let leader_schedule = Arc::new(
+        LeaderSchedule::new(context.clone(), LeaderSwapTable::default())
+            .with_num_commits_per_schedule(COMMITS_PER_SCHEDULE),
+    );
+    let committer =
+        UniversalCommitterBuilder::new(context.clone(), leader_schedule.clone(), dag_state.clone())
+            .with_pipeline(true)
+            .build();
+    let mut linearizer =
+        Linearizer::new(context.clone(), dag_state.clone(), leader_schedule.clone());
+    let vote_scoring = context
+        .protocol_config
+        .consensus_distributed_vote_scoring_strategy();
+
+    let mut dag_builder = DagBuilder::new(context.clone());
+    let mut previous_round = dag_builder.genesis_block_refs();
+    let mut last_decided = Slot::new(0, AuthorityIndex::ZERO);
+    let mut report = LatencyReport {
+        strategy,
+        committed_leaders: 0,
+        skipped_leaders: 0,
+        average_latency_rounds: 0.0,
+    };
+    let mut total_latency = 0;
+    let mut committed_blocks = 0;
+
+    for round in 1..=num_rounds {
+        let late: Vec<BlockRef> = if round > 2 {
+            dag_builder
+                .blocks(round - 2..=round - 2)
+                .iter()
+                .map(|b| b.reference())
+                .filter(|r| slow_authorities.contains(&r.author))
+                .collect()
+        } else {
+            vec![]
+        };
+        let connections = context
+            .committee
+            .authorities()
+            .map(|(authority, _)| {
+                let ancestors = if slow_authorities.contains(&authority) {
+                    previous_round.clone()
+                } else {
+                    previous_round
+                        .iter()
+                        .filter(|r| !slow_authorities.contains(&r.author))
+                        .chain(&late)
+                        .copied()
+                        .collect()
+                };
+                (authority, ancestors)
+            })
+            .collect();
+        dag_builder.layer_with_connections(connections, round);
+        let blocks = dag_builder.blocks(round..=round);
+        previous_round = blocks.iter().map(|b| b.reference()).collect();
+        dag_state.write().accept_blocks(blocks);
+
+        loop {
+            if leader_schedule.commits_until_leader_schedule_update(dag_state.clone()) == 0 {
+                if vote_scoring {
+                    leader_schedule.update_leader_schedule_v2(&dag_state);
+                } else {
+                    leader_schedule.update_leader_schedule_v1(&dag_state);
+                }
+            }
+            let commits_until_update =
+                leader_schedule.commits_until_leader_schedule_update(dag_state.clone());
+
+            let decided_leaders = committer.try_decide(last_decided);
+            let Some(last) = decided_leaders.last().map(|l| l.slot()) else {
+                break;
+            };
+
+            // Leaders decided past the schedule update are decided again with the new schedule.
+            let mut sequenced_leaders = vec![];
+            for leader in decided_leaders {
+                if sequenced_leaders.len() == commits_until_update {
+                    break;
+                }
+                last_decided = leader.slot();
+                match leader.into_committed_block() {
+                    Some(block) => sequenced_leaders.push(block),
+                    None => report.skipped_leaders += 1,
+                }
+            }
+            if sequenced_leaders.len() < commits_until_update {
+                last_decided = last;
+            }
+            if sequenced_leaders.is_empty() {
+                break;
+            }
+
+            report.committed_leaders += sequenced_leaders.len();
+            let subdags = linearizer.handle_commit(sequenced_leaders);
+            for block in subdags.iter().flat_map(|s| &s.blocks) {
+                total_latency += (round - block.round()) as u64;
+                committed_blocks += 1;
+            }
+            if vote_scoring {
+                dag_state.write().add_scoring_subdags(subdags);
+            } else {
+                dag_state.write().add_unscored_committed_subdags(subdags);
+            }
+        }
+    }
+
+    report.average_latency_rounds = total_latency as f64 / committed_blocks.max(1) as f64;
+    report
+}
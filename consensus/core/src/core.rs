@@ -22,7 +22,8 @@ use crate::{
     ancestor::{AncestorState, AncestorStateManager},
     block::{
         Block, BlockAPI, BlockRef, BlockTimestampMs, BlockV1, ExtendedBlock, Round, SignedBlock,
-        Slot, VerifiedBlock, GENESIS_ROUND,
+        Slot, VerifiedBlock, GENESIS_ROUND, MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK,
+        MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
     },
     block_manager::BlockManager,
     commit::CommittedSubDag,
@@ -547,6 +548,12 @@ impl Core {
             .write()
             .take_commit_votes(MAX_COMMIT_VOTES_PER_BLOCK);
 
+        // Consume the misbehavior reports to be included.
+        let misbehavior_reports = self.dag_state.write().take_misbehavior_reports(
+            MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
+            MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK,
+        );
+
         // Create the block and insert to storage.
         let block = Block::V1(BlockV1::new(
             self.context.committee.epoch(),
@@ -556,7 +563,7 @@ impl Core {
             ancestors.iter().map(|b| b.reference()).collect(),
             transactions,
             commit_votes,
-            vec![],
+            misbehavior_reports,
         ));
         let signed_block =
             SignedBlock::new(block, &self.block_signer).expect("Block signing failed.");
@@ -0,0 +1,365 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    ops::RangeInclusive,
+    path::Path,
+};
+
+use anyhow::ensure;
+use consensus_config::AuthorityIndex;
+use tracing::warn;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, BlockTimestampMs, Round, VerifiedBlock},
+    commit::{CommitAPI as _, CommitDigest, CommitIndex, CommitRange},
+    storage::{rocksdb_store::RocksDBStore, Store as _},
+};
+
+/// The largest committee the DAG text format can express, as authorities are named by a single
+/// letter.
+const MAX_EXPORTED_AUTHORITIES: usize = 26;
+
+/// A commit read from the consensus store.
+#[derive(Clone, Debug)]
+pub struct CommitRecord {
+    pub index: CommitIndex,
+    pub digest: CommitDigest,
+    pub timestamp_ms: BlockTimestampMs,
+    pub leader: BlockRef,
+    pub blocks: Vec<BlockRef>,
+}
+
+/// Read-only access to the consensus store of a validator, for offline debugging.
+///
+/// The store is opened as a RocksDB secondary instance, so it can be inspected while the validator
+/// that owns it is still running.
+pub struct DagInspector {
+    store: RocksDBStore,
+}
+
+impl DagInspector {
+    /// Opens the consensus store at `db_path`. `secondary_path` is a scratch directory for the
+    /// files of the secondary instance.
+    pub fn open(db_path: &Path, secondary_path: &Path) -> anyhow::Result<Self> {
+        Ok(Self {
+            store: RocksDBStore::new_secondary(db_path, secondary_path)?,
+        })
+    }
+
+    /// The highest round with any block in the store.
+    pub fn last_round(&self) -> anyhow::Result<Option<Round>> {
+        Ok(self.store.read_last_round()?)
+    }
+
+    /// The index of the last commit in the store.
+    pub fn last_commit_index(&self) -> anyhow::Result<Option<CommitIndex>> {
+        Ok(self.store.read_last_commit()?.map(|commit| commit.index()))
+    }
+
+    /// Blocks from the rounds in `rounds`, ordered by round and then author.
+    pub fn blocks(&self, rounds: RangeInclusive<Round>) -> anyhow::Result<Vec<VerifiedBlock>> {
+        Ok(self.store.scan_blocks_by_round(rounds)?)
+    }
+
+    /// Blocks proposed by `author` in the rounds in `rounds`, ordered by round.
+    pub fn blocks_by_author(
+        &self,
+        author: u32,
+        rounds: RangeInclusive<Round>,
+    ) -> anyhow::Result<Vec<VerifiedBlock>> {
+        Ok(self
+            .store
+            .scan_blocks_by_author_in_rounds(authority_index(author)?, rounds)?)
+    }
+
+    /// The blocks proposed by `author` at `round`, and their causal history down to `depth`
+    /// rounds below, ordered by round and then author. Ancestors that are no longer in the store
+    /// are skipped.
+    pub fn ancestors(
+        &self,
+        round: Round,
+        author: u32,
+        depth: Round,
+    ) -> anyhow::Result<Vec<VerifiedBlock>> {
+        let min_round = round.saturating_sub(depth).max(1);
+
+        let mut found = BTreeMap::new();
+        let mut frontier = self.blocks_by_author(author, round..=round)?;
+        while !frontier.is_empty() {
+            let mut refs = BTreeSet::new();
+            for block in frontier.drain(..) {
+                refs.extend(
+                    block
+                        .ancestors()
+                        .iter()
+                        .filter(|a| a.round >= min_round && !found.contains_key(*a))
+                        .copied(),
+                );
+                found.insert(block.reference(), block);
+            }
+
+            let refs: Vec<_> = refs.into_iter().collect();
+            frontier = self
+                .store
+                .read_blocks(&refs)?
+                .into_iter()
+                .flatten()
+                .collect();
+        }
+
+        Ok(found.into_values().collect())
+    }
+
+    /// Commits with indices in `indices`, in order.
+    pub fn commits(
+        &self,
+        indices: RangeInclusive<CommitIndex>,
+    ) -> anyhow::Result<Vec<CommitRecord>> {
+        Ok(self
+            .store
+            .scan_commits(CommitRange::new(indices))?
+            .into_iter()
+            .map(|commit| CommitRecord {
+                index: commit.index(),
+                digest: commit.digest(),
+                timestamp_ms: commit.timestamp_ms(),
+                leader: commit.leader(),
+                blocks: commit.blocks().to_vec(),
+            })
+            .collect())
+    }
+
+    /// Exports the blocks from the rounds in `rounds` in the text format read by `parse_dag`, so
+    /// the DAG can be reproduced in tests.
+    ///
+    /// The format only captures which slots each block links to, so timestamps, transactions and
+    /// all but the first block of an equivocating slot are left out. When the range starts after
+    /// round 1, everything before it is collapsed into a fully connected round 1, and the exported
+    /// rounds are renumbered to follow it.
+    ///
+    /// The committee size is taken from the blocks when `committee_size` is not given.
+    pub fn export_dag(
+        &self,
+        rounds: RangeInclusive<Round>,
+        committee_size: Option<usize>,
+    ) -> anyhow::Result<String> {
+        let start = (*rounds.start()).max(1);
+        let end = (*rounds.end()).min(self.last_round()?.unwrap_or(0));
+        let blocks = self.blocks(start..=end)?;
+
+        let committee_size = committee_size.unwrap_or_else(|| {
+            blocks
+                .iter()
+                .flat_map(|b| b.ancestors().iter().map(|a| a.author).chain([b.author()]))
+                .map(|author| author.value() + 1)
+                .max()
+                .unwrap_or(1)
+        });
+        ensure!(
+            committee_size <= MAX_EXPORTED_AUTHORITIES,
+            "Cannot export a committee of {committee_size} authorities (at most {MAX_EXPORTED_AUTHORITIES})"
+        );
+        for block in &blocks {
+            for author in block
+                .ancestors()
+                .iter()
+                .map(|a| a.author)
+                .chain([block.author()])
+            {
+                ensure!(
+                    author.value() < committee_size,
+                    "Block {block} refers to authority {author}, outside a committee of {committee_size} authorities"
+                );
+            }
+        }
+
+        // Round 1 stands in for all rounds before the exported range.
+        let collapsed = start > 1;
+        let export_round = |round: Round| {
+            if !collapsed {
+                round
+            } else if round < start {
+                1
+            } else {
+                round - start + 2
+            }
+        };
+
+        let mut by_round: BTreeMap<Round, Vec<VerifiedBlock>> = BTreeMap::new();
+        for block in blocks {
+            by_round.entry(block.round()).or_default().push(block);
+        }
+
+        let mut dag = format!("DAG {{\n    Round 0 : {{ {committee_size} }},\n");
+        if collapsed {
+            dag.push_str("    Round 1 : { * },\n");
+        }
+        for round in start..=end {
+            writeln!(dag, "    Round {} : {{", export_round(round))?;
+            let mut authors = BTreeSet::new();
+            for block in by_round.remove(&round).unwrap_or_default() {
+                if !authors.insert(block.author()) {
+                    warn!("Skipping equivocating block {block} in export");
+                    continue;
+                }
+
+                let mut slots = BTreeSet::new();
+                let ancestors: Vec<_> = block
+                    .ancestors()
+                    .iter()
+                    .map(|a| (export_round(a.round), a.author))
+                    .filter(|slot| slots.insert(*slot))
+                    .map(|(round, author)| format!("{}{round}", authority_name(author)))
+                    .collect();
+                writeln!(
+                    dag,
+                    "        {} -> [{}],",
+                    authority_name(block.author()),
+                    ancestors.join(", ")
+                )?;
+            }
+            dag.push_str("    },\n");
+        }
+        dag.push_str("}\n");
+
+        Ok(dag)
+    }
+}
+
+/// `AuthorityIndex` is normally only created from a committee, which the store does not have. The
+/// index is only used as a key to scan the store here, so it is decoded from its serialized form.
+fn authority_index(author: u32) -> anyhow::Result<AuthorityIndex> {
+    Ok(bcs::from_bytes(&bcs::to_bytes(&author)?)?)
+}
+
+/// The name of an authority in the DAG text format. Callers check the authority is within
+/// `MAX_EXPORTED_AUTHORITIES`.
+fn authority_name(authority: AuthorityIndex) -> char {
+    (b'A' + authority.value() as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        commit::TrustedCommit, context::Context, storage::WriteBatch, test_dag_builder::DagBuilder,
+        test_dag_parser::parse_dag,
+    };
+
+    /// Writes the blocks of `dag_builder` and `commits` to a new store, and opens an inspector on
+    /// it.
+    fn inspect(dir: &Path, dag_builder: &DagBuilder, commits: Vec<TrustedCommit>) -> DagInspector {
+        let db_path = dir.join("consensus");
+        {
+            let store = RocksDBStore::new(db_path.to_str().unwrap());
+            store
+                .write(
+                    WriteBatch::default()
+                        .blocks(dag_builder.blocks.values().cloned().collect())
+                        .commits(commits),
+                )
+                .unwrap();
+        }
+        DagInspector::open(&db_path, &dir.join("secondary")).unwrap()
+    }
+
+    /// The (round, author) slots each block in `dag_builder` links to, by slot.
+    fn links(dag_builder: &DagBuilder) -> BTreeMap<(Round, usize), BTreeSet<(Round, usize)>> {
+        dag_builder
+            .blocks
+            .values()
+            .map(|b| {
+                let ancestors = b
+                    .ancestors()
+                    .iter()
+                    .map(|a| (a.round, a.author.value()))
+                    .collect();
+                ((b.round(), b.author().value()), ancestors)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_round_trip() {
+        let dag_str = "DAG {
+            Round 0 : { 4 },
+            Round 1 : { * },
+            Round 2 : {
+                A -> [*],
+                B -> [-D1],
+                C -> [A1, B1, C1],
+            },
+            Round 3 : { * },
+            Round 4 : {
+                A -> [A3, B3, C3],
+                D -> [-A3],
+            },
+        }";
+        let (_, dag_builder) = parse_dag(dag_str).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let inspector = inspect(dir.path(), &dag_builder, vec![]);
+
+        assert_eq!(inspector.last_round().unwrap(), Some(4));
+        assert_eq!(inspector.blocks(2..=2).unwrap().len(), 3);
+        assert_eq!(inspector.blocks_by_author(3, 1..=4).unwrap().len(), 3);
+
+        // Parsing the exported DAG builds the same blocks, linked to the same slots.
+        let exported = inspector.export_dag(0..=4, None).unwrap();
+        let (_, exported_builder) = parse_dag(&exported).unwrap();
+        assert_eq!(links(&exported_builder), links(&dag_builder));
+
+        // Authorities outside the given committee size cannot be named.
+        assert!(inspector.export_dag(0..=4, Some(3)).is_err());
+
+        // A later range is renumbered after a collapsed round 1.
+        let exported = inspector.export_dag(3..=4, None).unwrap();
+        let (_, exported_builder) = parse_dag(&exported).unwrap();
+        let rounds: BTreeSet<_> = exported_builder.blocks.keys().map(|b| b.round).collect();
+        assert_eq!(rounds, BTreeSet::from([1, 2, 3]));
+        let d3 = exported_builder
+            .blocks
+            .values()
+            .find(|b| b.round() == 3 && b.author().value() == 3)
+            .unwrap();
+        assert_eq!(d3.ancestors().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ancestors_and_commits() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context);
+        dag_builder.layers(1..=5).build();
+
+        let leader = dag_builder
+            .blocks
+            .values()
+            .find(|b| b.round() == 3)
+            .unwrap()
+            .reference();
+        let commit = TrustedCommit::new_for_test(
+            1,
+            CommitDigest::MIN,
+            3000,
+            leader,
+            dag_builder.blocks.keys().copied().collect(),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let inspector = inspect(dir.path(), &dag_builder, vec![commit]);
+
+        // One block at round 5, and all blocks of the two rounds below it.
+        let ancestors = inspector.ancestors(5, 0, 2).unwrap();
+        assert_eq!(ancestors.len(), 9);
+        assert!(ancestors.iter().all(|b| b.round() >= 3));
+
+        assert_eq!(inspector.last_commit_index().unwrap(), Some(1));
+        let commits = inspector.commits(1..=10).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].leader, leader);
+    }
+}
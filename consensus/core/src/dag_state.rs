@@ -10,22 +10,24 @@ use std::{
     vec,
 };
 
+use bytes::Bytes;
 use consensus_config::AuthorityIndex;
 use itertools::Itertools as _;
 use tokio::time::Instant;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     block::{
-        genesis_blocks, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, Round, Slot,
-        VerifiedBlock, GENESIS_ROUND,
+        genesis_blocks, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, MisbehaviorProof,
+        MisbehaviorReport, Round, SignedBlock, Slot, VerifiedBlock, GENESIS_ROUND,
+        MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK,
     },
     commit::{
         load_committed_subdag_from_store, CommitAPI as _, CommitDigest, CommitIndex, CommitInfo,
         CommitRef, CommitVote, TrustedCommit, GENESIS_COMMIT_INDEX,
     },
     context::Context,
-    leader_scoring::{ReputationScores, ScoringSubdag},
+    leader_scoring::{PropagationDelays, ReputationScores, ScoringSubdag},
     storage::{Store, WriteBatch},
     threshold_clock::ThresholdClock,
     CommittedSubDag,
@@ -91,6 +93,13 @@ pub(crate) struct DagState {
     // TODO: limit to 1st commit per round with multi-leader.
     pending_commit_votes: VecDeque<CommitVote>,
 
+    // Misbehavior reports pending to be included in new blocks.
+    pending_misbehavior_reports: VecDeque<MisbehaviorReport>,
+
+    // Slots (round, authority) for which misbehavior has already been reported, so each is
+    // reported at most once. Pruned along with evicted blocks.
+    reported_slots: BTreeSet<(Round, AuthorityIndex)>,
+
     // Data to be flushed to storage.
     blocks_to_write: Vec<VerifiedBlock>,
     commits_to_write: Vec<TrustedCommit>,
@@ -180,6 +189,8 @@ impl DagState {
             last_commit_round_advancement_time: None,
             last_committed_rounds: last_committed_rounds.clone(),
             pending_commit_votes: VecDeque::new(),
+            pending_misbehavior_reports: VecDeque::new(),
+            reported_slots: BTreeSet::new(),
             blocks_to_write: vec![],
             commits_to_write: vec![],
             commit_info_to_write: vec![],
@@ -319,6 +330,19 @@ impl DagState {
                 "Block Rejected! Attempted to add block {block:#?} to own slot where \
                 block(s) {existing_blocks:#?} already exists."
             );
+        } else if let Some(existing_block) = self
+            .get_uncommitted_blocks_at_slot(block_ref.into())
+            .into_iter()
+            .next()
+        {
+            // Another block from the same slot is evidence of equivocation.
+            self.report_misbehavior(
+                block_ref.into(),
+                MisbehaviorProof::Equivocation(
+                    existing_block.serialized().clone(),
+                    block.serialized().clone(),
+                ),
+            );
         }
         self.update_block_metadata(&block);
         self.blocks_to_write.push(block);
@@ -852,6 +876,80 @@ impl DagState {
         votes
     }
 
+    /// Records that `block` has failed `BlockVerifier::verify` after its signature has been
+    /// verified, so that its author is reported. `serialized` is the block as it was received.
+    pub(crate) fn report_invalid_block(&mut self, block: &SignedBlock, serialized: Bytes) {
+        let slot = Slot::new(block.round(), block.author());
+        self.report_misbehavior(slot, MisbehaviorProof::InvalidBlock(serialized));
+    }
+
+    /// Records misbehavior by the authority of `slot`, to be reported in the next proposed blocks
+    /// when misbehavior reports are enabled. Each slot is reported at most once.
+    fn report_misbehavior(&mut self, slot: Slot, proof: MisbehaviorProof) {
+        if !self.reported_slots.insert((slot.round, slot.authority)) {
+            return;
+        }
+
+        let hostname = &self.context.committee.authority(slot.authority).hostname;
+        warn!(
+            "Detected misbehavior ({}) by authority {} at slot {}",
+            proof.name(),
+            hostname,
+            slot
+        );
+        self.context
+            .metrics
+            .node_metrics
+            .misbehavior_reports
+            .with_label_values(&[hostname, proof.name()])
+            .inc();
+
+        if !self.context.protocol_config.consensus_misbehavior_reports() {
+            return;
+        }
+        let report = MisbehaviorReport {
+            target: slot.authority,
+            proof,
+        };
+        // Reports that can never fit in a block are only logged.
+        let size = bcs::serialized_size(&report).expect("Serialization should not fail");
+        if size > MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK {
+            warn!(
+                "Misbehavior report against {hostname} at slot {slot} is too large: {size} bytes"
+            );
+            return;
+        }
+        self.pending_misbehavior_reports.push_back(report);
+    }
+
+    /// Takes up to `limit` pending misbehavior reports, in the order they were detected, with a
+    /// total serialized size of at most `max_bytes`.
+    pub(crate) fn take_misbehavior_reports(
+        &mut self,
+        limit: usize,
+        max_bytes: usize,
+    ) -> Vec<MisbehaviorReport> {
+        // Misbehavior can no longer be detected in slots whose blocks have been evicted.
+        let evicted_rounds = &self.evicted_rounds;
+        self.reported_slots
+            .retain(|(round, authority)| *round > evicted_rounds[*authority]);
+
+        let mut reports = vec![];
+        let mut total_bytes = 0;
+        while reports.len() < limit {
+            let Some(report) = self.pending_misbehavior_reports.front() else {
+                break;
+            };
+            let size = bcs::serialized_size(report).expect("Serialization should not fail");
+            if total_bytes + size > max_bytes {
+                break;
+            }
+            total_bytes += size;
+            reports.extend(self.pending_misbehavior_reports.pop_front());
+        }
+        reports
+    }
+
     /// Index of the last commit.
     pub(crate) fn last_commit_index(&self) -> CommitIndex {
         match &self.last_commit {
@@ -1034,6 +1132,10 @@ impl DagState {
         self.scoring_subdag.calculate_distributed_vote_scores()
     }
 
+    pub(crate) fn scoring_subdag_propagation_delays(&self) -> PropagationDelays {
+        self.scoring_subdag.propagation_delays.clone()
+    }
+
     pub(crate) fn scoring_subdag_commit_range(&self) -> CommitIndex {
         self.scoring_subdag
             .commit_range
@@ -1525,6 +1627,56 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_equivocation_is_reported() {
+        let (mut context, _) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_consensus_misbehavior_reports_for_testing(true);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        // Three different blocks from authority 1 in the same slot.
+        for timestamp_ms in 1..=3 {
+            let block = VerifiedBlock::new_for_test(
+                TestBlock::new(5, 1).set_timestamp_ms(timestamp_ms).build(),
+            );
+            dag_state.accept_block(block);
+        }
+
+        // Reports are taken only if they fit in the size limit.
+        assert!(dag_state.take_misbehavior_reports(4, 1).is_empty());
+
+        // A slot is reported only once, however many blocks it has.
+        let reports =
+            dag_state.take_misbehavior_reports(4, MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].target, AuthorityIndex::new_for_test(1));
+        let MisbehaviorProof::Equivocation(first, second) = &reports[0].proof else {
+            panic!("Unexpected proof {:?}", reports[0].proof);
+        };
+        assert_ne!(first, second);
+
+        // Reports are taken only once.
+        assert!(dag_state
+            .take_misbehavior_reports(4, MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK)
+            .is_empty());
+
+        // Without the feature, misbehavior is detected but not queued for reporting.
+        let (context, _) = Context::new_for_test(4);
+        let mut dag_state = DagState::new(Arc::new(context), store);
+        for timestamp_ms in 1..=2 {
+            let block = VerifiedBlock::new_for_test(
+                TestBlock::new(5, 2).set_timestamp_ms(timestamp_ms).build(),
+            );
+            dag_state.accept_block(block);
+        }
+        assert!(dag_state
+            .take_misbehavior_reports(4, MAX_MISBEHAVIOR_REPORTS_BYTES_PER_BLOCK)
+            .is_empty());
+    }
+
     #[tokio::test]
     #[should_panic(
         expected = "Attempted to check for slot [0]8 that is <= the last evicted round 8"
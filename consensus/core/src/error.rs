@@ -126,6 +126,18 @@ pub(crate) enum ConsensusError {
         block_timestamp_ms: u64,
     },
 
+    #[error("Too many misbehavior reports in the block: {0} > {1}")]
+    TooManyMisbehaviorReports(usize, usize),
+
+    #[error("Too many misbehavior report bytes in the block: {0} > {1}")]
+    TooManyMisbehaviorReportBytes(usize, usize),
+
+    #[error("Invalid misbehavior report against authority {target}: {reason}")]
+    InvalidMisbehaviorReport {
+        target: AuthorityIndex,
+        reason: String,
+    },
+
     #[error("Received no commit from peer {peer}")]
     NoCommitReceived { peer: AuthorityIndex },
 
@@ -164,6 +176,9 @@ pub(crate) enum ConsensusError {
     #[error("RocksDB failure: {0}")]
     RocksDBFailure(#[from] TypedStoreError),
 
+    #[error("Corrupted consensus store: {0}")]
+    CorruptedStore(String),
+
     #[error("Unknown network peer: {0}")]
     UnknownNetworkPeer(String),
 
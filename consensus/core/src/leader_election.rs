@@ -0,0 +1,441 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use consensus_config::{AuthorityIndex, Committee, LeaderElectionStrategy, Stake};
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+
+use crate::{
+    context::Context,
+    leader_schedule::LeaderSwapTable,
+    leader_scoring::{PropagationDelays, ReputationScores},
+    Round,
+};
+
+/// Decides the leader of each slot, and the scores the `LeaderSwapTable` is built from whenever
+/// the leader schedule is updated.
+///
+/// All authorities must elect the same leaders, so implementations can only depend on their
+/// inputs, which are derived from committed sub dags.
+pub(crate) trait LeaderElection: Send + Sync {
+    /// Elects the leader of the slot at `round` and `offset`, under the swap table of the current
+    /// leader schedule.
+    fn elect_leader(&self, table: &LeaderSwapTable, round: Round, offset: u32) -> AuthorityIndex;
+
+    /// Returns the scores to build the next swap table from. `scores` and `propagation_delays` are
+    /// computed over the commits since the last schedule update, and `current` are the scores the
+    /// current swap table was built from.
+    fn schedule_scores(
+        &self,
+        scores: ReputationScores,
+        _propagation_delays: &PropagationDelays,
+        _current: &ReputationScores,
+    ) -> ReputationScores {
+        scores
+    }
+}
+
+/// Creates the leader election configured in the parameters of `context`.
+pub(crate) fn leader_election(context: Arc<Context>) -> Arc<dyn LeaderElection> {
+    match context.parameters.leader_election {
+        LeaderElectionStrategy::ReputationSwap => {
+            cfg_if::cfg_if! {
+                // TODO: we need to differentiate the leader strategy in tests, so for
+                // some type of testing (ex sim tests) we can use the staked approach.
+                if #[cfg(test)] {
+                    Arc::new(ReputationSwap::new_round_robin_for_test(context))
+                } else {
+                    Arc::new(ReputationSwap::new(context))
+                }
+            }
+        }
+        LeaderElectionStrategy::StakeWeightedRoundRobin => {
+            Arc::new(StakeWeightedRoundRobin::new(&context.committee))
+        }
+        LeaderElectionStrategy::DecayingReputation { decay_percent } => {
+            Arc::new(DecayingReputation {
+                inner: ReputationSwap::new(context),
+                decay_percent: decay_percent.min(100) as u64,
+            })
+        }
+        LeaderElectionStrategy::LatencyAware => Arc::new(LatencyAware {
+            inner: ReputationSwap::new(context),
+        }),
+    }
+}
+
+/// Elects leaders at random weighted by stake, then swaps the leaders with the lowest reputation
+/// scores for those with the highest scores.
+pub(crate) struct ReputationSwap {
+    context: Arc<Context>,
+    // Elects leaders in round robin instead, so unit tests can predict them. Only the default
+    // strategy is created this way in tests, and `ReputationSwap::new` is tested on its own.
+    round_robin: bool,
+}
+
+impl ReputationSwap {
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        Self {
+            context,
+            round_robin: false,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_round_robin_for_test(context: Arc<Context>) -> Self {
+        Self {
+            context,
+            round_robin: true,
+        }
+    }
+}
+
+impl LeaderElection for ReputationSwap {
+    fn elect_leader(&self, table: &LeaderSwapTable, round: Round, offset: u32) -> AuthorityIndex {
+        let committee = &self.context.committee;
+        let leader = if self.round_robin {
+            AuthorityIndex::new_for_test((round + offset) % committee.size() as u32)
+        } else {
+            stake_weighted_leader(committee, round, offset)
+        };
+        table.swap(leader, round, offset).unwrap_or(leader)
+    }
+}
+
+/// Rotates leaders so that over every `total_stake` consecutive rounds, each authority leads in
+/// proportion to its stake. Reputation scores are ignored.
+///
+/// Each unit of stake owns a position in `0..total_stake`, and the leader of a round is the owner
+/// of the position `round * stride`. The stride is coprime with the total stake, so every position
+/// is visited once per cycle, and close to the golden ratio of it, so consecutive rounds land far
+/// apart and the turns of each authority are spread out.
+pub(crate) struct StakeWeightedRoundRobin {
+    // Authorities with non-zero stake, in committee order.
+    candidates: Vec<AuthorityIndex>,
+    // The end of the positions owned by each candidate: candidate `i` owns positions
+    // `stake_ends[i - 1]..stake_ends[i]`.
+    stake_ends: Vec<u64>,
+    stride: u64,
+}
+
+impl StakeWeightedRoundRobin {
+    pub(crate) fn new(committee: &Committee) -> Self {
+        // Scale stakes down by their greatest common divisor to keep the cycle short.
+        let divisor = committee
+            .authorities()
+            .fold(0, |divisor, (_, authority)| gcd(divisor, authority.stake))
+            .max(1);
+
+        let mut candidates = vec![];
+        let mut stake_ends = vec![];
+        let mut total = 0;
+        for (index, authority) in committee.authorities() {
+            if authority.stake == 0 {
+                continue;
+            }
+            total += authority.stake / divisor;
+            candidates.push(index);
+            stake_ends.push(total);
+        }
+
+        let mut stride = (total * 618 / 1000).max(1);
+        while gcd(stride, total) != 1 {
+            stride -= 1;
+        }
+
+        Self {
+            candidates,
+            stake_ends,
+            stride,
+        }
+    }
+}
+
+impl LeaderElection for StakeWeightedRoundRobin {
+    fn elect_leader(&self, _table: &LeaderSwapTable, round: Round, offset: u32) -> AuthorityIndex {
+        assert!((offset as usize) < self.candidates.len());
+
+        let total = *self.stake_ends.last().expect("Committee has stake");
+        let position = (round as u64 * self.stride) % total;
+        let owner = self.stake_ends.partition_point(|end| *end <= position);
+
+        // Leaders of the same round at different offsets are the next distinct candidates.
+        self.candidates[(owner + offset as usize) % self.candidates.len()]
+    }
+}
+
+/// `ReputationSwap` with the scores of previous schedules carried over, decayed by
+/// `decay_percent` at each update.
+pub(crate) struct DecayingReputation {
+    inner: ReputationSwap,
+    decay_percent: u64,
+}
+
+impl LeaderElection for DecayingReputation {
+    fn elect_leader(&self, table: &LeaderSwapTable, round: Round, offset: u32) -> AuthorityIndex {
+        self.inner.elect_leader(table, round, offset)
+    }
+
+    fn schedule_scores(
+        &self,
+        scores: ReputationScores,
+        _propagation_delays: &PropagationDelays,
+        current: &ReputationScores,
+    ) -> ReputationScores {
+        // There is nothing to carry over at genesis.
+        if current.scores_per_authority.len() != scores.scores_per_authority.len() {
+            return scores;
+        }
+        let keep_percent = 100 - self.decay_percent;
+        let scores_per_authority = scores
+            .scores_per_authority
+            .iter()
+            .zip(&current.scores_per_authority)
+            .map(|(new, old)| new + old * keep_percent / 100)
+            .collect();
+        ReputationScores::new(scores.commit_range, scores_per_authority)
+    }
+}
+
+/// `ReputationSwap` with the score of each authority divided by one plus the propagation delay of
+/// its blocks in rounds, as measured by `PropagationDelays`.
+pub(crate) struct LatencyAware {
+    inner: ReputationSwap,
+}
+
+impl LeaderElection for LatencyAware {
+    fn elect_leader(&self, table: &LeaderSwapTable, round: Round, offset: u32) -> AuthorityIndex {
+        self.inner.elect_leader(table, round, offset)
+    }
+
+    fn schedule_scores(
+        &self,
+        scores: ReputationScores,
+        propagation_delays: &PropagationDelays,
+        _current: &ReputationScores,
+    ) -> ReputationScores {
+        let committee = &self.inner.context.committee;
+        let scores_per_authority = committee
+            .authorities()
+            .zip(&scores.scores_per_authority)
+            .map(
+                |((authority, _), score)| match propagation_delays.delay(committee, authority) {
+                    Some(delay) => score / (1 + delay as u64),
+                    None => *score,
+                },
+            )
+            .collect();
+        ReputationScores::new(scores.commit_range, scores_per_authority)
+    }
+}
+
+/// Elects leaders at random weighted by stake, using the round as the seed. Leaders of the same
+/// round at different offsets are distinct.
+pub(crate) fn stake_weighted_leader(
+    committee: &Committee,
+    round: Round,
+    offset: u32,
+) -> AuthorityIndex {
+    assert!((offset as usize) < committee.size());
+
+    // To ensure that we elect different leaders for the same round (using
+    // different offset) we are using the round number as seed to shuffle in
+    // a weighted way the results, but skip based on the offset.
+    // TODO: use a cache in case this proves to be computationally expensive
+    let mut seed_bytes = [0u8; 32];
+    seed_bytes[32 - 4..].copy_from_slice(&(round).to_le_bytes());
+    let mut rng = StdRng::from_seed(seed_bytes);
+
+    let choices = committee
+        .authorities()
+        .map(|(index, authority)| (index, authority.stake as f32))
+        .collect::<Vec<_>>();
+
+    let leader_index = *choices
+        .choose_multiple_weighted(&mut rng, committee.size(), |item| item.1)
+        .expect("Weighted choice error: stake values incorrect!")
+        .skip(offset as usize)
+        .map(|(index, _)| index)
+        .next()
+        .unwrap();
+
+    leader_index
+}
+
+fn gcd(a: Stake, b: Stake) -> Stake {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use consensus_config::local_committee_and_keys;
+
+    use super::*;
+    use crate::commit::CommitRange;
+
+    fn context_with(strategy: LeaderElectionStrategy) -> Arc<Context> {
+        let (mut context, _) = Context::new_for_test(4);
+        context.parameters.leader_election = strategy;
+        Arc::new(context)
+    }
+
+    #[tokio::test]
+    async fn test_stake_weighted_round_robin() {
+        let (committee, _) = local_committee_and_keys(0, vec![2, 1, 1, 4]);
+        let election = StakeWeightedRoundRobin::new(&committee);
+        let table = LeaderSwapTable::default();
+
+        // Over a full cycle, each authority leads in proportion to its stake.
+        let mut counts = [0; 4];
+        for round in 0..8 {
+            counts[election.elect_leader(&table, round, 0).value()] += 1;
+        }
+        assert_eq!(counts, [2, 1, 1, 4]);
+
+        // The heaviest authority does not lead consecutive rounds more than needed.
+        let leaders: Vec<_> = (0..8)
+            .map(|round| election.elect_leader(&table, round, 0).value())
+            .collect();
+        assert!(leaders.windows(3).all(|w| w.iter().any(|a| *a != 3)));
+
+        // Leaders of the same round are distinct.
+        for round in 0..8 {
+            let leaders: Vec<_> = (0..4)
+                .map(|offset| election.elect_leader(&table, round, offset))
+                .collect();
+            let mut distinct = leaders.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), 4);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decaying_reputation_scores() {
+        let context =
+            context_with(LeaderElectionStrategy::DecayingReputation { decay_percent: 50 });
+        let election = leader_election(context.clone());
+        let propagation_delays = PropagationDelays::new(4);
+
+        // Nothing is carried over at genesis.
+        let first = ReputationScores::new(CommitRange::new(1..=10), vec![10, 20, 30, 40]);
+        let scores = election.schedule_scores(
+            first.clone(),
+            &propagation_delays,
+            &ReputationScores::default(),
+        );
+        assert_eq!(scores, first);
+
+        let second = ReputationScores::new(CommitRange::new(11..=20), vec![40, 30, 20, 10]);
+        let scores = election.schedule_scores(second, &propagation_delays, &first);
+        assert_eq!(scores.scores_per_authority, vec![45, 40, 35, 30]);
+        assert_eq!(scores.commit_range, CommitRange::new(11..=20));
+    }
+
+    #[tokio::test]
+    async fn test_reputation_swap() {
+        let (committee, _) = local_committee_and_keys(0, vec![3, 3, 3, 1]);
+        let (mut context, _) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_consensus_bad_nodes_stake_threshold_for_testing(33);
+        let context = Arc::new(context.with_committee(committee.clone()));
+
+        // Tests elect leaders in round robin by default, so the stake weighted election of
+        // production is checked on its own.
+        let election = ReputationSwap::new(context.clone());
+        let table = LeaderSwapTable::default();
+        for round in 1..100 {
+            for offset in 0..4 {
+                assert_eq!(
+                    election.elect_leader(&table, round, offset),
+                    stake_weighted_leader(&committee, round, offset)
+                );
+            }
+        }
+
+        // The lowest scoring authority is swapped out for the highest scoring one.
+        let scores = ReputationScores::new(CommitRange::new(1..=10), vec![40, 30, 20, 1]);
+        let table = LeaderSwapTable::new(context.clone(), 10, scores);
+        for round in 1..100 {
+            let expected = match stake_weighted_leader(&committee, round, 0).value() {
+                3 => AuthorityIndex::new_for_test(0),
+                _ => stake_weighted_leader(&committee, round, 0),
+            };
+            assert_eq!(election.elect_leader(&table, round, 0), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_latency_aware_scores() {
+        use crate::{
+            block::{BlockDigest, BlockRef, TestBlock, VerifiedBlock},
+            commit::{CommitDigest, CommitRef, CommittedSubDag},
+        };
+
+        let context = context_with(LeaderElectionStrategy::LatencyAware);
+        let election = leader_election(context.clone());
+
+        // At round 10, the others still link the block of authority 3 from round 7, while all
+        // blocks from round 9 are linked by everyone.
+        let block_ref = |round, author| {
+            BlockRef::new(
+                round,
+                AuthorityIndex::new_for_test(author),
+                BlockDigest::MIN,
+            )
+        };
+        let blocks: Vec<_> = (0..4)
+            .map(|author| {
+                // Own block first, as block verification requires.
+                let ancestors = std::iter::once(author)
+                    .chain((0..4).filter(|other| *other != author))
+                    .map(|other| {
+                        let round = if other == 3 && author != 3 { 7 } else { 9 };
+                        block_ref(round, other)
+                    })
+                    .collect();
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(10, author).set_ancestors(ancestors).build(),
+                )
+            })
+            .collect();
+        let subdag = CommittedSubDag::new(
+            blocks[0].reference(),
+            blocks,
+            vec![],
+            0,
+            CommitRef::new(1, CommitDigest::MIN),
+            vec![],
+        );
+        let mut propagation_delays = PropagationDelays::new(4);
+        propagation_delays.add_subdag(&subdag);
+        assert_eq!(
+            propagation_delays.delay(&context.committee, AuthorityIndex::new_for_test(0)),
+            Some(1)
+        );
+        assert_eq!(
+            propagation_delays.delay(&context.committee, AuthorityIndex::new_for_test(3)),
+            Some(3)
+        );
+
+        let scores = ReputationScores::new(CommitRange::new(1..=1), vec![100, 100, 100, 100]);
+        let scores =
+            election.schedule_scores(scores, &propagation_delays, &ReputationScores::default());
+        assert_eq!(scores.scores_per_authority, vec![50, 50, 50, 25]);
+
+        // Authorities without committed blocks keep their scores.
+        let scores = ReputationScores::new(CommitRange::new(1..=1), vec![100, 100, 100, 100]);
+        let scores = election.schedule_scores(
+            scores,
+            &PropagationDelays::new(4),
+            &ReputationScores::default(),
+        );
+        assert_eq!(scores.scores_per_authority, vec![100, 100, 100, 100]);
+    }
+}
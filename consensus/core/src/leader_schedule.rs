@@ -15,17 +15,20 @@ use crate::{
     commit::CommitRange,
     context::Context,
     dag_state::DagState,
-    leader_scoring::{ReputationScoreCalculator, ReputationScores},
+    leader_election::{leader_election, stake_weighted_leader, LeaderElection},
+    leader_scoring::{PropagationDelays, ReputationScoreCalculator, ReputationScores},
     CommitIndex, Round,
 };
 
 /// The `LeaderSchedule` is responsible for producing the leader schedule across
 /// an epoch. The leader schedule is subject to change periodically based on
-/// calculated `ReputationScores` of the authorities.
+/// calculated `ReputationScores` of the authorities, in the way decided by the configured
+/// `LeaderElection`.
 #[derive(Clone)]
 pub(crate) struct LeaderSchedule {
     pub leader_swap_table: Arc<RwLock<LeaderSwapTable>>,
     context: Arc<Context>,
+    election: Arc<dyn LeaderElection>,
     num_commits_per_schedule: u64,
 }
 
@@ -40,13 +43,14 @@ impl LeaderSchedule {
 
     pub(crate) fn new(context: Arc<Context>, leader_swap_table: LeaderSwapTable) -> Self {
         Self {
+            election: leader_election(context.clone()),
             context,
             num_commits_per_schedule: Self::CONSENSUS_COMMITS_PER_SCHEDULE,
             leader_swap_table: Arc::new(RwLock::new(leader_swap_table)),
         }
     }
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "bench"))]
     pub(crate) fn with_num_commits_per_schedule(mut self, num_commits_per_schedule: u64) -> Self {
         self.num_commits_per_schedule = num_commits_per_schedule;
         self
@@ -138,7 +142,11 @@ impl LeaderSchedule {
 
         let (reputation_scores, last_commit_index) = {
             let dag_state = dag_state.read();
-            let reputation_scores = dag_state.calculate_scoring_subdag_scores();
+            let reputation_scores = self.election.schedule_scores(
+                dag_state.calculate_scoring_subdag_scores(),
+                &dag_state.scoring_subdag_propagation_delays(),
+                &self.leader_swap_table.read().reputation_scores,
+            );
 
             let last_commit_index = dag_state.scoring_subdag_commit_range();
 
@@ -192,6 +200,16 @@ impl LeaderSchedule {
             ReputationScoreCalculator::new(self.context.clone(), &unscored_subdags).calculate();
         drop(score_calculation_timer);
 
+        let mut propagation_delays = PropagationDelays::new(self.context.committee.size());
+        for subdag in &unscored_subdags {
+            propagation_delays.add_subdag(subdag);
+        }
+        let reputation_scores = self.election.schedule_scores(
+            reputation_scores,
+            &propagation_delays,
+            &self.leader_swap_table.read().reputation_scores,
+        );
+
         reputation_scores.update_metrics(self.context.clone());
 
         let last_commit_index = unscored_subdags.last().unwrap().commit_ref.index;
@@ -212,48 +230,12 @@ impl LeaderSchedule {
     }
 
     pub(crate) fn elect_leader(&self, round: u32, leader_offset: u32) -> AuthorityIndex {
-        cfg_if::cfg_if! {
-            // TODO: we need to differentiate the leader strategy in tests, so for
-            // some type of testing (ex sim tests) we can use the staked approach.
-            if #[cfg(test)] {
-                let leader = AuthorityIndex::new_for_test((round + leader_offset) % self.context.committee.size() as u32);
-                let table = self.leader_swap_table.read();
-                table.swap(leader, round, leader_offset).unwrap_or(leader)
-            } else {
-                let leader = self.elect_leader_stake_based(round, leader_offset);
-                let table = self.leader_swap_table.read();
-                table.swap(leader, round, leader_offset).unwrap_or(leader)
-            }
-        }
+        let table = self.leader_swap_table.read();
+        self.election.elect_leader(&table, round, leader_offset)
     }
 
     pub(crate) fn elect_leader_stake_based(&self, round: u32, offset: u32) -> AuthorityIndex {
-        assert!((offset as usize) < self.context.committee.size());
-
-        // To ensure that we elect different leaders for the same round (using
-        // different offset) we are using the round number as seed to shuffle in
-        // a weighted way the results, but skip based on the offset.
-        // TODO: use a cache in case this proves to be computationally expensive
-        let mut seed_bytes = [0u8; 32];
-        seed_bytes[32 - 4..].copy_from_slice(&(round).to_le_bytes());
-        let mut rng = StdRng::from_seed(seed_bytes);
-
-        let choices = self
-            .context
-            .committee
-            .authorities()
-            .map(|(index, authority)| (index, authority.stake as f32))
-            .collect::<Vec<_>>();
-
-        let leader_index = *choices
-            .choose_multiple_weighted(&mut rng, self.context.committee.size(), |item| item.1)
-            .expect("Weighted choice error: stake values incorrect!")
-            .skip(offset as usize)
-            .map(|(index, _)| index)
-            .next()
-            .unwrap();
-
-        leader_index
+        stake_weighted_leader(&self.context.committee, round, offset)
     }
 
     /// Atomically updates the `LeaderSwapTable` with the new provided one. Any
@@ -8,13 +8,14 @@ use std::{
     sync::Arc,
 };
 
-use consensus_config::AuthorityIndex;
+use consensus_config::{AuthorityIndex, Committee};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     block::{BlockAPI, BlockDigest, BlockRef, Slot},
     commit::{CommitRange, CommittedSubDag},
     context::Context,
+    round_prober::compute_quorum_round,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
     Round, VerifiedBlock,
 };
@@ -169,6 +170,57 @@ impl ReputationScores {
     }
 }
 
+/// Propagation delays of the blocks of each authority, measured the way `RoundProber` measures
+/// them: the delay of an authority is how far its own highest round is ahead of the low quorum
+/// round computed by `compute_quorum_round` over the rounds other authorities have received from
+/// it.
+///
+/// `RoundProber` gets the received rounds by probing peers, so its measurements differ across
+/// authorities. Here they are the highest rounds linked as ancestors by the committed blocks of
+/// each authority instead, so every authority computes the same delays from the same commits.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub(crate) struct PropagationDelays {
+    // highest_linked_rounds[linker][authority] is the highest round of the blocks of `authority`
+    // linked by committed blocks of `linker`. The diagonal is the highest committed round of each
+    // authority.
+    highest_linked_rounds: Vec<Vec<Round>>,
+}
+
+impl PropagationDelays {
+    pub(crate) fn new(num_authorities: usize) -> Self {
+        Self {
+            highest_linked_rounds: vec![vec![0; num_authorities]; num_authorities],
+        }
+    }
+
+    pub(crate) fn add_subdag(&mut self, subdag: &CommittedSubDag) {
+        for block in &subdag.blocks {
+            let rounds = &mut self.highest_linked_rounds[block.author()];
+            rounds[block.author()] = rounds[block.author()].max(block.round());
+            for ancestor in block.ancestors() {
+                rounds[ancestor.author] = rounds[ancestor.author].max(ancestor.round);
+            }
+        }
+    }
+
+    /// The propagation delay of the blocks of `authority`, in rounds. `None` when none of its
+    /// blocks have been committed.
+    pub(crate) fn delay(&self, committee: &Committee, authority: AuthorityIndex) -> Option<Round> {
+        let own_round = self.highest_linked_rounds.get(authority.value())?[authority];
+        if own_round == 0 {
+            return None;
+        }
+        let (low, _high) = compute_quorum_round(committee, authority, &self.highest_linked_rounds);
+        Some(own_round.saturating_sub(low))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for rounds in &mut self.highest_linked_rounds {
+            rounds.iter_mut().for_each(|round| *round = 0);
+        }
+    }
+}
+
 /// ScoringSubdag represents the scoring votes in a collection of subdags across
 /// multiple commits.
 /// These subdags are "scoring" for the purposes of leader schedule change. As
@@ -185,15 +237,19 @@ pub(crate) struct ScoringSubdag {
     // Note: Including stake aggregator so that we can quickly check if it exceeds
     // quourum threshold and only include those scores for certain scoring strategies.
     pub(crate) votes: BTreeMap<BlockRef, StakeAggregator<QuorumThreshold>>,
+    // Propagation delays measured from the scoring subdags, for latency aware leader election.
+    pub(crate) propagation_delays: PropagationDelays,
 }
 
 impl ScoringSubdag {
     pub(crate) fn new(context: Arc<Context>) -> Self {
+        let propagation_delays = PropagationDelays::new(context.committee.size());
         Self {
             context,
             commit_range: None,
             leaders: HashSet::new(),
             votes: BTreeMap::new(),
+            propagation_delays,
         }
     }
 
@@ -217,6 +273,8 @@ impl ScoringSubdag {
                 commit_range.extend_to(subdag.commit_ref.index);
             }
 
+            self.propagation_delays.add_subdag(&subdag);
+
             // Add the committed leader to the list of leaders we will be scoring.
             tracing::trace!("Adding new committed leader {} for scoring", subdag.leader);
             self.leaders.insert(subdag.leader);
@@ -318,6 +376,7 @@ impl ScoringSubdag {
     pub(crate) fn clear(&mut self) {
         self.leaders.clear();
         self.votes.clear();
+        self.propagation_delays.clear();
         self.commit_range = None;
     }
 }
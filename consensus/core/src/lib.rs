@@ -11,14 +11,18 @@ mod block_verifier;
 mod broadcaster;
 mod commit;
 mod commit_consumer;
+#[cfg(any(test, feature = "bench"))]
+mod commit_latency;
 mod commit_observer;
 mod commit_syncer;
 mod commit_vote_monitor;
 mod context;
 mod core;
 mod core_thread;
+mod dag_inspector;
 mod dag_state;
 mod error;
+mod leader_election;
 mod leader_schedule;
 mod leader_scoring;
 mod leader_timeout;
@@ -33,28 +37,45 @@ mod threshold_clock;
 mod transaction;
 mod universal_committer;
 
+#[cfg(test)]
+#[path = "tests/leader_election_tests.rs"]
+mod leader_election_tests;
 #[cfg(test)]
 #[path = "tests/randomized_tests.rs"]
 mod randomized_tests;
 mod round_prober;
+#[cfg(any(test, feature = "simulator"))]
+mod simulator;
 #[cfg(test)]
-mod test_dag;
+#[path = "tests/simulator_tests.rs"]
+mod simulator_tests;
 #[cfg(test)]
+mod test_dag;
+#[cfg(any(test, feature = "bench"))]
+#[cfg_attr(not(test), allow(dead_code))]
 mod test_dag_builder;
 #[cfg(test)]
 mod test_dag_parser;
 
 /// Exported consensus API.
 pub use authority_node::ConsensusAuthority;
-pub use block::{BlockAPI, BlockRef, Round, TransactionIndex};
+pub use block::{BlockAPI, BlockRef, MisbehaviorProof, MisbehaviorReport, Round, TransactionIndex};
 /// Exported API for testing.
 pub use block::{TestBlock, Transaction, VerifiedBlock};
 pub use commit::{CommitDigest, CommitIndex, CommitRef, CommittedSubDag};
 pub use commit_consumer::{CommitConsumer, CommitConsumerMonitor};
+/// Exported API for benchmarking leader election strategies.
+#[cfg(feature = "bench")]
+pub use commit_latency::{measure_commit_latency, LatencyReport};
+/// Exported API for offline inspection of the consensus store.
+pub use dag_inspector::{CommitRecord, DagInspector};
 pub use network::{
     connection_monitor::{AnemoConnectionMonitor, ConnectionMonitorHandle, ConnectionStatus},
     metrics::{MetricsMakeCallbackHandler, NetworkRouteMetrics, QuinnConnectionMetrics},
 };
+/// Exported API for simulating a committee under network faults.
+#[cfg(feature = "simulator")]
+pub use simulator::{Fault, SimulatedCommit, Simulation, SimulationConfig};
 pub use transaction::{
     BlockStatus, ClientError, TransactionClient, TransactionVerifier, ValidationError,
 };
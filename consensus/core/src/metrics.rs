@@ -133,6 +133,7 @@ pub(crate) struct NodeMetrics {
     pub(crate) network_excluded_ancestors_sent_to_fetch: IntCounterVec,
     pub(crate) network_excluded_ancestors_count_by_authority: IntCounterVec,
     pub(crate) invalid_blocks: IntCounterVec,
+    pub(crate) misbehavior_reports: IntCounterVec,
     pub(crate) rejected_blocks: IntCounterVec,
     pub(crate) rejected_future_blocks: IntCounterVec,
     pub(crate) subscribed_blocks: IntCounterVec,
@@ -420,6 +421,12 @@ impl NodeMetrics {
                 &["authority", "source", "error"],
                 registry,
             ).unwrap(),
+            misbehavior_reports: register_int_counter_vec_with_registry!(
+                "misbehavior_reports",
+                "Number of misbehaviors detected locally per authority, by type of proof",
+                &["authority", "type"],
+                registry,
+            ).unwrap(),
             rejected_blocks: register_int_counter_vec_with_registry!(
                 "rejected_blocks",
                 "Number of blocks rejected before verifications",
@@ -49,6 +49,8 @@ pub(crate) mod metrics;
 mod metrics_layer;
 #[cfg(all(test, not(msim)))]
 mod network_tests;
+#[cfg(any(test, feature = "simulator"))]
+pub(crate) mod simulated_network;
 #[cfg(test)]
 pub(crate) mod test_network;
 pub(crate) mod tonic_network;
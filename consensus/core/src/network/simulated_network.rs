@@ -0,0 +1,280 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process network connecting the authorities of a simulation. Requests are handed directly
+//! to the peer's `NetworkService`, after a latency drawn from a seeded RNG. Faults active at the
+//! time a request or response is sent delay or drop it.
+
+use std::{
+    future::Future,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use consensus_config::{AuthorityIndex, NetworkKeyPair};
+use parking_lot::{Mutex, RwLock};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::time::Instant;
+
+use crate::{
+    block::{BlockRef, VerifiedBlock},
+    commit::CommitRange,
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+    network::{
+        BlockStream, ExtendedSerializedBlock, NetworkClient, NetworkManager, NetworkService,
+    },
+    simulator::Fault,
+    Round,
+};
+
+/// The links between the authorities of a simulation, and the services they serve.
+pub(crate) struct SimulatedNetwork {
+    start: Instant,
+    /// Range of one-way latencies, in milliseconds, of links without faults.
+    latency_ms: RangeInclusive<u64>,
+    rng: Mutex<StdRng>,
+    faults: RwLock<Vec<(Range<Duration>, Fault)>>,
+    /// The service of each authority, while it is running.
+    services: RwLock<Vec<Option<Arc<dyn NetworkService>>>>,
+}
+
+impl SimulatedNetwork {
+    pub(crate) fn new(
+        committee_size: usize,
+        seed: u64,
+        latency_ms: RangeInclusive<u64>,
+        start: Instant,
+    ) -> Self {
+        Self {
+            start,
+            latency_ms,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            faults: RwLock::new(vec![]),
+            services: RwLock::new(vec![None; committee_size]),
+        }
+    }
+
+    /// Injects `fault` into the links from `window.start` until `window.end`, both measured from
+    /// the start of the simulation.
+    pub(crate) fn add_fault(&self, window: Range<Duration>, fault: Fault) {
+        self.faults.write().push((window, fault));
+    }
+
+    fn service(&self, authority: AuthorityIndex) -> Option<Arc<dyn NetworkService>> {
+        self.services.read()[authority].clone()
+    }
+
+    /// Returns the latency of a message sent now from `from` to `to`, or `None` if the message is
+    /// lost.
+    fn latency(&self, from: AuthorityIndex, to: AuthorityIndex) -> Option<Duration> {
+        let now = Instant::now() - self.start;
+        let (from, to) = (from.value(), to.value());
+        let mut delay = Duration::ZERO;
+        let mut drop_probability: f64 = 0.0;
+        for (window, fault) in self.faults.read().iter() {
+            if !window.contains(&now) {
+                continue;
+            }
+            match fault {
+                Fault::Partition(groups) => {
+                    let group_of = |a| groups.iter().position(|g| g.contains(&a));
+                    if group_of(from).is_none() || group_of(from) != group_of(to) {
+                        return None;
+                    }
+                }
+                Fault::Delay {
+                    authority,
+                    delay: d,
+                } if *authority == from => delay += *d,
+                Fault::Drop { probability } => {
+                    drop_probability = drop_probability.max(*probability)
+                }
+                _ => {}
+            }
+        }
+        let mut rng = self.rng.lock();
+        if drop_probability > 0.0 && rng.gen_bool(drop_probability) {
+            return None;
+        }
+        let latency = rng.gen_range(self.latency_ms.clone());
+        Some(Duration::from_millis(latency) + delay)
+    }
+}
+
+pub(crate) struct SimulatedClient {
+    own_index: AuthorityIndex,
+    network: Arc<SimulatedNetwork>,
+}
+
+impl SimulatedClient {
+    /// Sends a request to `peer`, which `handle` serves, and waits for the response. A lost
+    /// request or response surfaces as a timeout, as it would over a real network.
+    async fn call<T, F, Fut>(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+        handle: F,
+    ) -> ConsensusResult<T>
+    where
+        F: FnOnce(Arc<dyn NetworkService>) -> Fut,
+        Fut: Future<Output = ConsensusResult<T>>,
+    {
+        let exchange = async {
+            let Some(latency) = self.network.latency(self.own_index, peer) else {
+                return std::future::pending().await;
+            };
+            tokio::time::sleep(latency).await;
+            let Some(service) = self.network.service(peer) else {
+                return Err(ConsensusError::PeerDisconnected(format!("{peer}")));
+            };
+            let response = handle(service).await?;
+            let Some(latency) = self.network.latency(peer, self.own_index) else {
+                return std::future::pending().await;
+            };
+            tokio::time::sleep(latency).await;
+            Ok(response)
+        };
+        tokio::time::timeout(timeout, exchange)
+            .await
+            .map_err(|e| ConsensusError::NetworkRequestTimeout(format!("{peer}: {e:?}")))?
+    }
+}
+
+#[async_trait]
+impl NetworkClient for SimulatedClient {
+    const SUPPORT_STREAMING: bool = false;
+
+    async fn send_block(
+        &self,
+        peer: AuthorityIndex,
+        block: &VerifiedBlock,
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        let block = ExtendedSerializedBlock {
+            block: block.serialized().clone(),
+            excluded_ancestors: vec![],
+        };
+        self.call(peer, timeout, |service| async move {
+            service.handle_send_block(self.own_index, block).await
+        })
+        .await
+    }
+
+    async fn subscribe_blocks(
+        &self,
+        _peer: AuthorityIndex,
+        _last_received: Round,
+        _timeout: Duration,
+    ) -> ConsensusResult<BlockStream> {
+        unimplemented!("Unimplemented")
+    }
+
+    async fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        highest_accepted_rounds: Vec<Round>,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        self.call(peer, timeout, |service| async move {
+            service
+                .handle_fetch_blocks(self.own_index, block_refs, highest_accepted_rounds)
+                .await
+        })
+        .await
+    }
+
+    async fn fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        commit_range: CommitRange,
+        timeout: Duration,
+    ) -> ConsensusResult<(Vec<Bytes>, Vec<Bytes>)> {
+        self.call(peer, timeout, |service| async move {
+            let (commits, certifier_blocks) = service
+                .handle_fetch_commits(self.own_index, commit_range)
+                .await?;
+            let commits = commits
+                .into_iter()
+                .map(|c| c.serialized().clone())
+                .collect();
+            let certifier_blocks = certifier_blocks
+                .into_iter()
+                .map(|b| b.serialized().clone())
+                .collect();
+            Ok((commits, certifier_blocks))
+        })
+        .await
+    }
+
+    async fn fetch_latest_blocks(
+        &self,
+        peer: AuthorityIndex,
+        authorities: Vec<AuthorityIndex>,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        self.call(peer, timeout, |service| async move {
+            service
+                .handle_fetch_latest_blocks(self.own_index, authorities)
+                .await
+        })
+        .await
+    }
+
+    async fn get_latest_rounds(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<(Vec<Round>, Vec<Round>)> {
+        self.call(peer, timeout, |service| async move {
+            service.handle_get_latest_rounds(self.own_index).await
+        })
+        .await
+    }
+}
+
+/// Connects an authority to a `SimulatedNetwork`. Its service is reachable by peers from when it is
+/// installed until the authority stops.
+pub(crate) struct SimulatedManager {
+    own_index: AuthorityIndex,
+    network: Arc<SimulatedNetwork>,
+    client: Arc<SimulatedClient>,
+}
+
+impl SimulatedManager {
+    pub(crate) fn new_simulated(context: Arc<Context>, network: Arc<SimulatedNetwork>) -> Self {
+        let own_index = context.own_index;
+        Self {
+            own_index,
+            client: Arc::new(SimulatedClient {
+                own_index,
+                network: network.clone(),
+            }),
+            network,
+        }
+    }
+}
+
+impl<S: NetworkService> NetworkManager<S> for SimulatedManager {
+    type Client = SimulatedClient;
+
+    fn new(_context: Arc<Context>, _network_keypair: NetworkKeyPair) -> Self {
+        unimplemented!("Simulated networks are shared, use SimulatedManager::new_simulated")
+    }
+
+    fn client(&self) -> Arc<Self::Client> {
+        self.client.clone()
+    }
+
+    async fn install_service(&mut self, service: Arc<S>) {
+        self.network.services.write()[self.own_index] = Some(service);
+    }
+
+    async fn stop(&mut self) {
+        self.network.services.write()[self.own_index] = None;
+    }
+}
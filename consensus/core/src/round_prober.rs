@@ -311,7 +311,7 @@ impl<C: NetworkClient> RoundProber<C> {
 }
 
 /// For the peer specified with target_index, compute and return its [`QuorumRound`].
-fn compute_quorum_round(
+pub(crate) fn compute_quorum_round(
     committee: &Committee,
     target_index: AuthorityIndex,
     highest_received_rounds: &[Vec<Round>],
@@ -0,0 +1,294 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A simulation of a committee running consensus over a network with injected faults.
+//!
+//! Each simulated authority is a full `ConsensusAuthority`, with its own store on disk, connected to
+//! its peers through a `SimulatedNetwork`. Faults are applied to the network links: partitions,
+//! delays and random drops. Crashes stop an authority and restart it from its store. Link
+//! latencies and drops come from a single seeded RNG, but tasks of different authorities
+//! interleave freely, so runs with the same seed are not guaranteed to be identical.
+//!
+//! Simulations must run on a tokio runtime with paused time, e.g. with
+//! `#[tokio::test(flavor = "current_thread", start_paused = true)]`.
+
+use std::{
+    collections::BTreeMap,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+    time::Duration,
+};
+
+use consensus_config::{
+    local_committee_and_keys, AuthorityIndex, Committee, NetworkKeyPair, Parameters,
+    ProtocolKeyPair, Stake,
+};
+use parking_lot::Mutex;
+use prometheus::Registry;
+use sui_protocol_config::ProtocolConfig;
+use tempfile::TempDir;
+use tokio::time::Instant;
+use tracing::info;
+
+use crate::{
+    block::BlockRef, commit::CommitRef, network::simulated_network::SimulatedNetwork,
+    transaction::NoopTransactionVerifier, CommitConsumer, CommitIndex, ConsensusAuthority,
+};
+
+/// Configuration of a simulation.
+#[derive(Clone)]
+pub struct SimulationConfig {
+    /// Seed of the link latencies and message drops.
+    pub seed: u64,
+    /// Stake of each authority.
+    pub stakes: Vec<Stake>,
+    /// Parameters of every authority. `db_path` is replaced by a directory per authority.
+    pub parameters: Parameters,
+    pub protocol_config: ProtocolConfig,
+    /// Range of one-way latencies, in milliseconds, of links without faults.
+    pub latency_ms: RangeInclusive<u64>,
+}
+
+impl SimulationConfig {
+    pub fn new(seed: u64, num_authorities: usize) -> Self {
+        Self {
+            seed,
+            stakes: vec![1; num_authorities],
+            parameters: Parameters {
+                // Authorities start together from empty stores, so there is no own block to
+                // recover from peers.
+                sync_last_known_own_block_timeout: Duration::ZERO,
+                ..Default::default()
+            },
+            protocol_config: ProtocolConfig::get_for_max_version_UNSAFE(),
+            latency_ms: 10..=50,
+        }
+    }
+}
+
+/// A fault injected into the simulation for a window of time.
+#[derive(Clone, Debug)]
+pub enum Fault {
+    /// Messages between authorities in different groups are dropped. Authorities that are not in
+    /// any group are isolated.
+    Partition(Vec<Vec<usize>>),
+    /// Messages sent by the authority are delayed, in addition to the link latency.
+    Delay { authority: usize, delay: Duration },
+    /// Each message is dropped with the given probability.
+    Drop { probability: f64 },
+    /// The authority is stopped at the start of the window and restarted from its store at the
+    /// end.
+    Crash(usize),
+}
+
+/// A commit observed by an authority.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedCommit {
+    pub reference: CommitRef,
+    pub leader: BlockRef,
+    /// Time since the start of the simulation when the commit was observed.
+    pub observed_at: Duration,
+}
+
+enum Event {
+    Crash(usize),
+    Restart(usize),
+}
+
+struct SimulatedNode {
+    /// Survives crashes.
+    db_dir: TempDir,
+    authority: Option<ConsensusAuthority>,
+    boot_counter: u64,
+    commits: Arc<Mutex<Vec<SimulatedCommit>>>,
+}
+
+pub struct Simulation {
+    config: SimulationConfig,
+    committee: Committee,
+    keypairs: Vec<(NetworkKeyPair, ProtocolKeyPair)>,
+    network: Arc<SimulatedNetwork>,
+    start: Instant,
+    nodes: Vec<SimulatedNode>,
+    /// Pending crashes and restarts by time, with a sequence number to order events at the same
+    /// time.
+    events: BTreeMap<(Duration, u64), Event>,
+    next_sequence: u64,
+}
+
+impl Simulation {
+    /// Creates and starts the authorities of a simulation.
+    pub async fn new(config: SimulationConfig) -> Self {
+        let (committee, keypairs) = local_committee_and_keys(0, config.stakes.clone());
+        let start = Instant::now();
+        let network = Arc::new(SimulatedNetwork::new(
+            committee.size(),
+            config.seed,
+            config.latency_ms.clone(),
+            start,
+        ));
+        let nodes = (0..committee.size())
+            .map(|_| SimulatedNode {
+                db_dir: TempDir::new().unwrap(),
+                authority: None,
+                boot_counter: 0,
+                commits: Arc::new(Mutex::new(vec![])),
+            })
+            .collect();
+
+        let mut simulation = Self {
+            config,
+            committee,
+            keypairs,
+            network,
+            start,
+            nodes,
+            events: BTreeMap::new(),
+            next_sequence: 0,
+        };
+        for authority in 0..simulation.nodes.len() {
+            simulation.start_node(authority).await;
+        }
+        simulation
+    }
+
+    /// Injects `fault` from `start` until `end`, both measured from the start of the simulation.
+    pub fn add_fault(&mut self, start: Duration, end: Duration, fault: Fault) {
+        if let Fault::Crash(authority) = fault {
+            self.schedule(start, Event::Crash(authority));
+            self.schedule(end, Event::Restart(authority));
+        }
+        self.network.add_fault(start..end, fault);
+    }
+
+    /// Time elapsed since the start of the simulation.
+    pub fn now(&self) -> Duration {
+        Instant::now() - self.start
+    }
+
+    /// Runs the simulation until `end`, measured from the start of the simulation.
+    pub async fn run_until(&mut self, end: Duration) {
+        while let Some(entry) = self.events.first_entry() {
+            let (time, _) = *entry.key();
+            if time > end {
+                break;
+            }
+            let event = entry.remove();
+            tokio::time::sleep_until(self.start + time).await;
+            self.handle(event).await;
+        }
+        tokio::time::sleep_until(self.start + end).await;
+    }
+
+    /// Stops all authorities.
+    pub async fn stop(mut self) {
+        for node in &mut self.nodes {
+            if let Some(authority) = node.authority.take() {
+                authority.stop().await;
+            }
+        }
+    }
+
+    /// Commits observed by `authority`, in order.
+    pub fn commits(&self, authority: usize) -> Vec<SimulatedCommit> {
+        self.nodes[authority].commits.lock().clone()
+    }
+
+    /// Number of commits observed by `authority` during `window`.
+    pub fn commits_during(&self, authority: usize, window: Range<Duration>) -> usize {
+        self.nodes[authority]
+            .commits
+            .lock()
+            .iter()
+            .filter(|c| window.contains(&c.observed_at))
+            .count()
+    }
+
+    /// Whether `authority` is running, i.e. it has not crashed or it has restarted.
+    pub fn is_running(&self, authority: usize) -> bool {
+        self.nodes[authority].authority.is_some()
+    }
+
+    /// Checks that every authority observed the same sequence of commits, up to the length of its
+    /// own sequence.
+    pub fn assert_safety(&self) {
+        let commits: Vec<_> = (0..self.nodes.len()).map(|a| self.commits(a)).collect();
+        let longest = commits.iter().max_by_key(|c| c.len()).unwrap();
+        for (index, commit) in longest.iter().enumerate() {
+            assert_eq!(commit.reference.index, index as CommitIndex + 1);
+        }
+        for (authority, commits) in commits.iter().enumerate() {
+            for (commit, expected) in commits.iter().zip(longest) {
+                assert_eq!(
+                    (commit.reference, commit.leader),
+                    (expected.reference, expected.leader),
+                    "Authority {authority} diverged at commit {}",
+                    expected.reference.index
+                );
+            }
+        }
+    }
+
+    fn schedule(&mut self, time: Duration, event: Event) {
+        self.events.insert((time, self.next_sequence), event);
+        self.next_sequence += 1;
+    }
+
+    async fn handle(&mut self, event: Event) {
+        match event {
+            Event::Crash(authority) => {
+                info!("Authority {authority} crashed at {:?}", self.now());
+                if let Some(node) = self.nodes[authority].authority.take() {
+                    node.stop().await;
+                }
+            }
+            Event::Restart(authority) => {
+                info!("Authority {authority} restarted at {:?}", self.now());
+                self.nodes[authority].boot_counter += 1;
+                self.start_node(authority).await;
+            }
+        }
+    }
+
+    /// Starts an authority from the contents of its store, and records the commits it outputs.
+    async fn start_node(&mut self, authority: usize) {
+        let index = AuthorityIndex::new_for_test(authority as u32);
+        let node = &mut self.nodes[authority];
+        let parameters = Parameters {
+            db_path: node.db_dir.path().to_path_buf(),
+            ..self.config.parameters.clone()
+        };
+
+        // Commits already observed are not sent again after a restart.
+        let last_observed = node.commits.lock().len() as CommitIndex;
+        let (commit_consumer, mut commit_receiver, _) = CommitConsumer::new(last_observed);
+        let monitor = commit_consumer.monitor();
+        let commits = node.commits.clone();
+        let start = self.start;
+        tokio::spawn(async move {
+            while let Some(commit) = commit_receiver.recv().await {
+                monitor.set_highest_handled_commit(commit.commit_ref.index);
+                commits.lock().push(SimulatedCommit {
+                    reference: commit.commit_ref,
+                    leader: commit.leader,
+                    observed_at: Instant::now() - start,
+                });
+            }
+        });
+
+        let consensus_authority = ConsensusAuthority::start_simulated(
+            self.network.clone(),
+            index,
+            self.committee.clone(),
+            parameters,
+            self.config.protocol_config.clone(),
+            self.keypairs[authority].1.clone(),
+            Arc::new(NoopTransactionVerifier {}),
+            commit_consumer,
+            Registry::new(),
+            node.boot_counter,
+        )
+        .await;
+        node.authority = Some(consensus_authority);
+    }
+}
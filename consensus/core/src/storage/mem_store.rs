@@ -35,7 +35,7 @@ struct Inner {
 }
 
 impl MemStore {
-    #[cfg(test)]
+    #[cfg(any(test, feature = "bench"))]
     pub(crate) fn new() -> Self {
         MemStore {
             inner: RwLock::new(Inner {
@@ -1,7 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::VecDeque, ops::Bound::Included, time::Duration};
+use std::{
+    collections::VecDeque,
+    ops::{Bound::Included, RangeInclusive},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 
 use bytes::Bytes;
 use consensus_config::AuthorityIndex;
@@ -9,8 +15,11 @@ use sui_macros::fail_point;
 use typed_store::{
     metrics::SamplingInterval,
     reopen,
-    rocks::{default_db_options, open_cf_opts, DBMap, MetricConf, ReadWriteOptions},
-    Map as _,
+    rocks::{
+        default_db_options, open_cf_opts, open_cf_opts_secondary, DBMap, MetricConf,
+        ReadWriteOptions, RocksDB,
+    },
+    rocksdb, Map as _,
 };
 
 use super::{CommitInfo, Store, WriteBatch};
@@ -47,10 +56,39 @@ impl RocksDBStore {
         // Consensus data has high write throughput (all transactions) and is rarely read
         // (only during recovery and when helping peers catch up).
         let db_options = default_db_options().optimize_db_for_write_throughput(2);
+        let rocksdb = open_cf_opts(
+            path,
+            Some(db_options.options),
+            Self::metric_conf(),
+            &Self::column_family_options(),
+        )
+        .expect("Cannot open database");
+        Self::reopen_tables(&rocksdb)
+    }
+
+    /// Opens the storage at `path` as a read-only secondary instance, which can be done while the
+    /// primary instance is still open in another process. RocksDB keeps the secondary instance's
+    /// own files in `secondary_path`.
+    pub(crate) fn new_secondary(path: &Path, secondary_path: &Path) -> ConsensusResult<Self> {
+        let rocksdb = open_cf_opts_secondary(
+            path,
+            Some(secondary_path),
+            None,
+            Self::metric_conf(),
+            &Self::column_family_options(),
+        )?;
+        Ok(Self::reopen_tables(&rocksdb))
+    }
+
+    fn metric_conf() -> MetricConf {
         let mut metrics_conf = MetricConf::new("consensus");
         metrics_conf.read_sample_interval = SamplingInterval::new(Duration::from_secs(60), 0);
+        metrics_conf
+    }
+
+    fn column_family_options() -> Vec<(&'static str, rocksdb::Options)> {
         let cf_options = default_db_options().optimize_for_write_throughput().options;
-        let column_family_options = vec![
+        vec![
             (
                 Self::BLOCKS_CF,
                 default_db_options()
@@ -63,16 +101,11 @@ impl RocksDBStore {
             (Self::COMMITS_CF, cf_options.clone()),
             (Self::COMMIT_VOTES_CF, cf_options.clone()),
             (Self::COMMIT_INFO_CF, cf_options.clone()),
-        ];
-        let rocksdb = open_cf_opts(
-            path,
-            Some(db_options.options),
-            metrics_conf,
-            &column_family_options,
-        )
-        .expect("Cannot open database");
+        ]
+    }
 
-        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(&rocksdb,
+    fn reopen_tables(rocksdb: &Arc<RocksDB>) -> Self {
+        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(rocksdb,
             Self::BLOCKS_CF;<(Round, AuthorityIndex, BlockDigest), bytes::Bytes>,
             Self::DIGESTS_BY_AUTHORITIES_CF;<(AuthorityIndex, Round, BlockDigest), ()>,
             Self::COMMITS_CF;<(CommitIndex, CommitDigest), Bytes>,
@@ -88,6 +121,79 @@ impl RocksDBStore {
             commit_info,
         }
     }
+
+    /// Reads all blocks from the rounds in `rounds`, ordered by round and then author.
+    ///
+    /// Unlike the `Store` methods, which panic on a corrupted store, this returns an error, since
+    /// it is used to inspect stores offline.
+    pub(crate) fn scan_blocks_by_round(
+        &self,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut blocks = vec![];
+        for kv in self.blocks.safe_range_iter((
+            Included((*rounds.start(), AuthorityIndex::MIN, BlockDigest::MIN)),
+            Included((*rounds.end(), AuthorityIndex::MAX, BlockDigest::MAX)),
+        )) {
+            let ((round, author, digest), serialized) = kv?;
+            blocks.push(Self::checked_block(
+                BlockRef::new(round, author, digest),
+                serialized,
+            )?);
+        }
+        Ok(blocks)
+    }
+
+    /// Reads the blocks proposed by `author` in the rounds in `rounds`, ordered by round, through
+    /// the index of digests by authority. Returns an error on a corrupted store, like
+    /// `scan_blocks_by_round`.
+    pub(crate) fn scan_blocks_by_author_in_rounds(
+        &self,
+        author: AuthorityIndex,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut refs = vec![];
+        for kv in self.digests_by_authorities.safe_range_iter((
+            Included((author, *rounds.start(), BlockDigest::MIN)),
+            Included((author, *rounds.end(), BlockDigest::MAX)),
+        )) {
+            let ((author, round, digest), _) = kv?;
+            refs.push(BlockRef::new(round, author, digest));
+        }
+
+        let keys = refs.iter().map(|r| (r.round, r.author, r.digest));
+        let mut blocks = Vec::with_capacity(refs.len());
+        for (block_ref, serialized) in refs.iter().zip(self.blocks.multi_get(keys)?) {
+            let serialized = serialized.ok_or_else(|| {
+                ConsensusError::CorruptedStore(format!("block {block_ref} is indexed but missing"))
+            })?;
+            blocks.push(Self::checked_block(*block_ref, serialized)?);
+        }
+        Ok(blocks)
+    }
+
+    /// Deserializes a block read from the store, checking it matches the key it was stored under.
+    fn checked_block(expected: BlockRef, serialized: Bytes) -> ConsensusResult<VerifiedBlock> {
+        let signed_block: SignedBlock =
+            bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+        let block = VerifiedBlock::new_verified(signed_block, serialized);
+        if block.reference() != expected {
+            return Err(ConsensusError::CorruptedStore(format!(
+                "block {} is stored under {expected}",
+                block.reference()
+            )));
+        }
+        Ok(block)
+    }
+
+    /// Reads the highest round with any block in the store.
+    pub(crate) fn read_last_round(&self) -> ConsensusResult<Option<Round>> {
+        let Some(result) = self.blocks.safe_iter().skip_to_last().next() else {
+            return Ok(None);
+        };
+        let ((round, _, _), _) = result?;
+        Ok(Some(round))
+    }
 }
 
 impl Store for RocksDBStore {
@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks the commit latency of leader election strategies, over DAGs where some authorities are
+//! slow to propagate their blocks. The `leader_election_bench` benchmark compares them further.
+
+use consensus_config::{AuthorityIndex, LeaderElectionStrategy};
+
+use crate::{block::Round, commit_latency::measure_commit_latency};
+
+const NUM_AUTHORITIES: usize = 4;
+const NUM_ROUNDS: Round = 200;
+
+const STRATEGIES: [LeaderElectionStrategy; 4] = [
+    LeaderElectionStrategy::ReputationSwap,
+    LeaderElectionStrategy::StakeWeightedRoundRobin,
+    LeaderElectionStrategy::DecayingReputation { decay_percent: 50 },
+    LeaderElectionStrategy::LatencyAware,
+];
+
+#[tokio::test]
+async fn test_compare_strategies_without_slow_authorities() {
+    telemetry_subscribers::init_for_testing();
+
+    for strategy in STRATEGIES {
+        let report = measure_commit_latency(strategy, NUM_AUTHORITIES, NUM_ROUNDS, &[]);
+        tracing::info!("{report:?}");
+        assert_eq!(report.skipped_leaders, 0, "{report:?}");
+        assert!(
+            report.committed_leaders as Round >= NUM_ROUNDS - 3,
+            "{report:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_compare_strategies_with_slow_authority() {
+    telemetry_subscribers::init_for_testing();
+
+    let slow = [AuthorityIndex::new_for_test(3)];
+    let reports: Vec<_> = STRATEGIES
+        .into_iter()
+        .map(|strategy| measure_commit_latency(strategy, NUM_AUTHORITIES, NUM_ROUNDS, &slow))
+        .collect();
+    for report in &reports {
+        tracing::info!("{report:?}");
+        assert!(report.committed_leaders > 0, "{report:?}");
+    }
+
+    // Round robin keeps electing the slow authority, whose leader slots cannot get votes. The
+    // reputation based strategies swap it out after the first schedule update.
+    let round_robin = &reports[1];
+    for report in [&reports[0], &reports[2], &reports[3]] {
+        assert!(
+            report.skipped_leaders < round_robin.skipped_leaders,
+            "{report:?} vs {round_robin:?}"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use crate::simulator::{Fault, Simulation, SimulationConfig};
+
+const NUM_AUTHORITIES: usize = 4;
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+async fn simulation(seed: u64) -> Simulation {
+    telemetry_subscribers::init_for_testing();
+    Simulation::new(SimulationConfig::new(seed, NUM_AUTHORITIES)).await
+}
+
+/// Checks that `authority` is within a few commits of authority 0, allowing for commits in flight.
+fn assert_caught_up(simulation: &Simulation, authority: usize) {
+    let lag = simulation
+        .commits(0)
+        .len()
+        .saturating_sub(simulation.commits(authority).len());
+    assert!(lag <= 3, "Authority {authority} is {lag} commits behind");
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_simulation_without_faults() {
+    let mut simulation = simulation(1).await;
+    simulation.run_until(secs(10)).await;
+
+    simulation.assert_safety();
+    for authority in 0..NUM_AUTHORITIES {
+        assert!(
+            simulation.commits(authority).len() > 20,
+            "Authority {authority} made little progress"
+        );
+    }
+    simulation.stop().await;
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_simulation_minority_partition() {
+    let mut simulation = simulation(2).await;
+    simulation.add_fault(
+        secs(3),
+        secs(8),
+        Fault::Partition(vec![vec![0, 1, 2], vec![3]]),
+    );
+    simulation.run_until(secs(15)).await;
+
+    simulation.assert_safety();
+    // The majority keeps committing while the minority is cut off.
+    assert!(simulation.commits_during(0, secs(4)..secs(8)) > 0);
+    assert_eq!(simulation.commits_during(3, secs(4)..secs(8)), 0);
+    // The minority catches up once the partition heals.
+    assert_caught_up(&simulation, 3);
+    simulation.stop().await;
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_simulation_no_quorum_partition() {
+    let mut simulation = simulation(3).await;
+    simulation.add_fault(
+        secs(3),
+        secs(8),
+        Fault::Partition(vec![vec![0, 1], vec![2, 3]]),
+    );
+    simulation.run_until(secs(15)).await;
+
+    simulation.assert_safety();
+    for authority in 0..NUM_AUTHORITIES {
+        // Nothing commits without a quorum, and commits resume after the partition heals.
+        assert_eq!(simulation.commits_during(authority, secs(4)..secs(8)), 0);
+        assert!(simulation.commits_during(authority, secs(9)..secs(15)) > 0);
+    }
+    simulation.stop().await;
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_simulation_crash_restart() {
+    let mut simulation = simulation(4).await;
+    simulation.add_fault(secs(3), secs(6), Fault::Crash(3));
+    simulation.run_until(secs(4)).await;
+    assert!(!simulation.is_running(3));
+
+    simulation.run_until(secs(15)).await;
+    simulation.assert_safety();
+    assert!(simulation.commits_during(0, secs(3)..secs(6)) > 0);
+    // The restarted authority recovers its commits from its store, and catches up on the rest.
+    assert_caught_up(&simulation, 3);
+    // It proposes again: some of the later leaders are its blocks.
+    assert!(simulation
+        .commits(0)
+        .iter()
+        .any(|c| c.observed_at > secs(7) && c.leader.author.value() == 3));
+    simulation.stop().await;
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_simulation_slow_authority_and_drops() {
+    let mut simulation = simulation(5).await;
+    simulation.add_fault(
+        secs(0),
+        secs(15),
+        Fault::Delay {
+            authority: 2,
+            delay: Duration::from_millis(400),
+        },
+    );
+    simulation.add_fault(secs(0), secs(15), Fault::Drop { probability: 0.1 });
+    simulation.run_until(secs(15)).await;
+
+    simulation.assert_safety();
+    for authority in 0..NUM_AUTHORITIES {
+        assert!(simulation.commits_during(authority, secs(5)..secs(15)) > 0);
+    }
+    simulation.stop().await;
+}
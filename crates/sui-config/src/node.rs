@@ -995,6 +995,11 @@ pub struct StateSnapshotConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object_store_config: Option<ObjectStoreConfig>,
     pub concurrency: usize,
+    /// If set to more than one, a full state snapshot is only written every that many epochs, and
+    /// the snapshots of the epochs in between are incremental snapshots on top of the snapshot of
+    /// the previous epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_snapshot_interval_epochs: Option<u64>,
 }
 
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
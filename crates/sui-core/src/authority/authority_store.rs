@@ -10,7 +10,8 @@ use crate::authority::authority_store_pruner::{
     AuthorityStorePruner, AuthorityStorePruningMetrics, EPOCH_DURATION_MS_FOR_TESTING,
 };
 use crate::authority::authority_store_types::{
-    get_store_object_pair, ObjectContentDigest, StoreObject, StoreObjectPair, StoreObjectWrapper,
+    get_store_object_pair, ObjectContentDigest, StoreData, StoreObject, StoreObjectPair,
+    StoreObjectWrapper,
 };
 use crate::authority::epoch_start_configuration::{EpochFlag, EpochStartConfiguration};
 use crate::rpc_index::RpcIndexStore;
@@ -762,42 +763,12 @@ impl AuthorityStore {
         let mut batch = perpetual_db.objects.batch();
         for object in live_objects {
             hasher.update(object.object_reference().2.inner());
-            match object {
-                LiveObject::Normal(object) => {
-                    let StoreObjectPair(store_object_wrapper, indirect_object) =
-                        get_store_object_pair(object.clone(), indirect_objects_threshold);
-                    batch.insert_batch(
-                        &perpetual_db.objects,
-                        std::iter::once((
-                            ObjectKey::from(object.compute_object_reference()),
-                            store_object_wrapper,
-                        )),
-                    )?;
-                    if let Some(indirect_object) = indirect_object {
-                        batch.merge_batch(
-                            &perpetual_db.indirect_move_objects,
-                            iter::once((indirect_object.inner().digest(), indirect_object)),
-                        )?;
-                    }
-                    if !object.is_child_object() {
-                        Self::initialize_live_object_markers(
-                            &perpetual_db.live_owned_object_markers,
-                            &mut batch,
-                            &[object.compute_object_reference()],
-                            false, // is_force_reset
-                        )?;
-                    }
-                }
-                LiveObject::Wrapped(object_key) => {
-                    batch.insert_batch(
-                        &perpetual_db.objects,
-                        std::iter::once::<(ObjectKey, StoreObjectWrapper)>((
-                            object_key,
-                            StoreObject::Wrapped.into(),
-                        )),
-                    )?;
-                }
-            }
+            Self::insert_live_object_batch(
+                perpetual_db,
+                &mut batch,
+                object,
+                indirect_objects_threshold,
+            )?;
         }
         let sha3_digest = hasher.finalize().digest;
         if *expected_sha3_digest != sha3_digest {
@@ -811,6 +782,106 @@ impl AuthorityStore {
         Ok(())
     }
 
+    /// Inserts live objects which have already been verified by the caller, e.g. a filtered
+    /// subset of the objects restored from a state snapshot.
+    pub fn insert_verified_live_objects(
+        perpetual_db: &AuthorityPerpetualTables,
+        live_objects: impl Iterator<Item = LiveObject>,
+        indirect_objects_threshold: usize,
+    ) -> SuiResult<()> {
+        let mut batch = perpetual_db.objects.batch();
+        for object in live_objects {
+            Self::insert_live_object_batch(
+                perpetual_db,
+                &mut batch,
+                object,
+                indirect_objects_threshold,
+            )?;
+        }
+        batch.write()?;
+        Ok(())
+    }
+
+    fn insert_live_object_batch(
+        perpetual_db: &AuthorityPerpetualTables,
+        batch: &mut DBBatch,
+        object: LiveObject,
+        indirect_objects_threshold: usize,
+    ) -> SuiResult<()> {
+        match object {
+            LiveObject::Normal(object) => {
+                let StoreObjectPair(store_object_wrapper, indirect_object) =
+                    get_store_object_pair(object.clone(), indirect_objects_threshold);
+                batch.insert_batch(
+                    &perpetual_db.objects,
+                    std::iter::once((
+                        ObjectKey::from(object.compute_object_reference()),
+                        store_object_wrapper,
+                    )),
+                )?;
+                if let Some(indirect_object) = indirect_object {
+                    batch.merge_batch(
+                        &perpetual_db.indirect_move_objects,
+                        iter::once((indirect_object.inner().digest(), indirect_object)),
+                    )?;
+                }
+                if !object.is_child_object() {
+                    Self::initialize_live_object_markers(
+                        &perpetual_db.live_owned_object_markers,
+                        batch,
+                        &[object.compute_object_reference()],
+                        false, // is_force_reset
+                    )?;
+                }
+            }
+            LiveObject::Wrapped(object_key) => {
+                batch.insert_batch(
+                    &perpetual_db.objects,
+                    std::iter::once::<(ObjectKey, StoreObjectWrapper)>((
+                        object_key,
+                        StoreObject::Wrapped.into(),
+                    )),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes objects restored by `bulk_insert_live_objects` which are no longer live, e.g. when
+    /// applying an incremental state snapshot on top of its base.
+    pub fn bulk_remove_live_objects(
+        perpetual_db: &AuthorityPerpetualTables,
+        object_refs: impl Iterator<Item = ObjectRef>,
+        indirect_objects_threshold: usize,
+    ) -> SuiResult<()> {
+        let object_refs: Vec<_> = object_refs.collect();
+        let object_keys: Vec<_> = object_refs.iter().map(|r| ObjectKey::from(*r)).collect();
+        let mut batch = perpetual_db.objects.batch();
+        if indirect_objects_threshold > 0 && indirect_objects_threshold < usize::MAX {
+            let mut indirect_objects: HashMap<_, i64> = HashMap::new();
+            for object in perpetual_db
+                .objects
+                .multi_get(object_keys.iter())?
+                .into_iter()
+                .flatten()
+            {
+                if let StoreObject::Value(obj) = object.into_inner() {
+                    if let StoreData::IndirectObject(indirect_object) = obj.data {
+                        *indirect_objects.entry(indirect_object.digest).or_default() -= 1;
+                    }
+                }
+            }
+            let ref_count_update = indirect_objects
+                .iter()
+                .map(|(digest, delta)| (digest, delta.to_le_bytes()));
+            batch.partial_merge_batch(&perpetual_db.indirect_move_objects, ref_count_update)?;
+        }
+        batch.delete_batch(&perpetual_db.objects, object_keys)?;
+        batch.delete_batch(&perpetual_db.live_owned_object_markers, object_refs)?;
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn set_epoch_start_configuration(
         &self,
         epoch_start_configuration: &EpochStartConfiguration,
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     hash::Hash,
     num::NonZeroUsize,
     sync::Arc,
@@ -143,6 +143,11 @@ pub struct ConsensusHandler<C> {
     low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
     /// The consensus committee used to do stake computations for deciding set of low scoring authorities
     committee: ConsensusCommittee,
+    /// Authorities proven to have misbehaved by reports in committed blocks during this epoch.
+    /// This is kept in memory only, like `low_scoring_authorities` which it feeds: after a restart
+    /// it only includes reports from commits handled since then. It steers transaction submission
+    /// locally and is not part of any consensus output, so losing it is safe.
+    misbehaving_authorities: BTreeSet<AuthorityIndex>,
     // TODO: ConsensusHandler doesn't really share metrics with AuthorityState. We could define
     // a new metrics type here if we want to.
     metrics: Arc<AuthorityMetrics>,
@@ -187,6 +192,7 @@ impl<C> ConsensusHandler<C> {
             cache_reader,
             low_scoring_authorities,
             committee,
+            misbehaving_authorities: BTreeSet::new(),
             metrics,
             processed_cache: LruCache::new(NonZeroUsize::new(PROCESSED_CACHE_CAP).unwrap()),
             transaction_manager_sender,
@@ -300,11 +306,18 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             ));
         }
 
+        for authority in consensus_commit.misbehaving_authorities() {
+            if self.misbehaving_authorities.insert(authority) {
+                warn!("Authority {authority} was reported for misbehavior in consensus");
+            }
+        }
+
         update_low_scoring_authorities(
             self.low_scoring_authorities.clone(),
             self.epoch_store.committee(),
             &self.committee,
             consensus_commit.reputation_score_sorted_desc(),
+            &self.misbehaving_authorities,
             &self.metrics,
             self.epoch_store
                 .protocol_config()
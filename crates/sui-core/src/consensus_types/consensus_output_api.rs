@@ -32,6 +32,10 @@ pub(crate) trait ConsensusCommitAPI: Display {
     /// Returns all accepted and rejected transactions per block in the commit in deterministic order.
     fn transactions(&self) -> Vec<(AuthorityIndex, Vec<ParsedTransaction>)>;
 
+    /// Returns the authorities proven to have misbehaved by reports in the commit. Every report
+    /// carries its proof, which is verified along with the block that includes it.
+    fn misbehaving_authorities(&self) -> Vec<AuthorityIndex>;
+
     /// Returns the digest of consensus output.
     fn consensus_digest(&self, protocol_config: &ProtocolConfig) -> ConsensusCommitDigest;
 }
@@ -80,6 +84,14 @@ impl ConsensusCommitAPI for consensus_core::CommittedSubDag {
             .collect()
     }
 
+    fn misbehaving_authorities(&self) -> Vec<AuthorityIndex> {
+        self.blocks
+            .iter()
+            .flat_map(|block| block.misbehavior_reports())
+            .map(|report| report.target.value() as AuthorityIndex)
+            .collect()
+    }
+
     fn consensus_digest(&self, protocol_config: &ProtocolConfig) -> ConsensusCommitDigest {
         if protocol_config.mysticeti_use_committed_subdag_digest() {
             // We port CommitDigest, a consensus space object, into ConsensusCommitDigest, a sui-core space object.
@@ -7,6 +7,7 @@ use crate::checkpoints::CheckpointStore;
 use crate::par_index_live_object_set::LiveObjectIndexer;
 use crate::par_index_live_object_set::ParMakeLiveObjectIndexer;
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use serde::Deserialize;
@@ -31,16 +32,19 @@ use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::BackingPackageStore;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
 use tracing::{debug, info};
-use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::rocks::{default_db_options, DBMap, DBOptions, MetricConf};
+use typed_store::rocksdb::compaction_filter::Decision;
+use typed_store::rocksdb::MergeOperands;
 use typed_store::traits::Map;
 use typed_store::traits::{TableSummary, TypedStoreDebug};
 use typed_store::DBMapUtils;
 use typed_store::TypedStoreError;
 
-const CURRENT_DB_VERSION: u64 = 0;
+const CURRENT_DB_VERSION: u64 = 2;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 struct MetadataInfo {
@@ -103,6 +107,91 @@ impl CoinIndexInfo {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BalanceIndexKey {
+    pub owner: SuiAddress,
+    pub coin_type: TypeTag,
+}
+
+impl BalanceIndexKey {
+    fn new(owner: SuiAddress, coin_type: TypeTag) -> Self {
+        Self { owner, coin_type }
+    }
+}
+
+/// A change to the balance and number of coins of a coin type owned by an account.
+///
+/// Deltas are merged into the balance table by `balance_delta_merge_operator`, so each row holds
+/// the sum of every delta written to it, which is the account's current balance.
+#[derive(Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct BalanceIndexInfo {
+    pub balance_delta: i128,
+    pub coin_object_count_delta: i64,
+}
+
+impl BalanceIndexInfo {
+    fn merge_delta(&mut self, other: &Self) {
+        self.balance_delta += other.balance_delta;
+        self.coin_object_count_delta += other.coin_object_count_delta;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.balance_delta == 0 && self.coin_object_count_delta == 0
+    }
+}
+
+impl From<BalanceIndexInfo> for BalanceInfo {
+    fn from(info: BalanceIndexInfo) -> Self {
+        // Once every delta has been merged a row can't be negative, unless the index is
+        // inconsistent.
+        Self {
+            balance: info.balance_delta.clamp(0, u64::MAX as i128) as u64,
+            coin_object_count: info.coin_object_count_delta.max(0) as u64,
+        }
+    }
+}
+
+/// Balance deltas accumulated over a set of object changes, to be merged into the balance table.
+#[derive(Default)]
+struct BalanceDeltas(HashMap<BalanceIndexKey, BalanceIndexInfo>);
+
+impl BalanceDeltas {
+    /// Record that `owner` received `object`, if it is a coin.
+    fn add(&mut self, owner: SuiAddress, object: &Object) {
+        self.record(owner, object, 1);
+    }
+
+    /// Record that `owner` no longer owns `object`, if it is a coin.
+    fn remove(&mut self, owner: SuiAddress, object: &Object) {
+        self.record(owner, object, -1);
+    }
+
+    fn record(&mut self, owner: SuiAddress, object: &Object, sign: i64) {
+        let Some(coin_type) = object.coin_type_maybe() else {
+            return;
+        };
+
+        let delta = BalanceIndexInfo {
+            balance_delta: object.get_coin_value_unsafe() as i128 * sign as i128,
+            coin_object_count_delta: sign,
+        };
+
+        self.0
+            .entry(BalanceIndexKey::new(owner, coin_type))
+            .or_default()
+            .merge_delta(&delta);
+    }
+
+    fn write(
+        &mut self,
+        batch: &mut typed_store::rocks::DBBatch,
+        table: &DBMap<BalanceIndexKey, BalanceIndexInfo>,
+    ) -> Result<(), TypedStoreError> {
+        batch.merge_batch(table, self.0.drain().filter(|(_, delta)| !delta.is_empty()))?;
+        Ok(())
+    }
+}
+
 /// RocksDB tables for the RpcIndexStore
 ///
 /// Anytime a new table is added, or and existing one has it's schema changed, make sure to also
@@ -144,6 +233,13 @@ struct IndexStoreTables {
     /// Allows looking up information related to published Coins, like the ObjectID of its
     /// coorisponding CoinMetadata.
     coin: DBMap<CoinIndexKey, CoinIndexInfo>,
+
+    /// An index of the balance of each coin type owned by each account.
+    ///
+    /// Rows are only ever updated by merging in `BalanceIndexInfo` deltas, which allows a balance
+    /// to be read without loading, or iterating over, the coins that make it up.
+    #[default_options_override_fn = "balance_table_default_config"]
+    balance: DBMap<BalanceIndexKey, BalanceIndexInfo>,
     // NOTE: Authors and Reviewers before adding any new tables ensure that they are either:
     // - bounded in size by the live object set
     // - are prune-able and have corresponding logic in the `prune` function
@@ -299,6 +395,7 @@ impl IndexStoreTables {
         // object indexes
         {
             let mut coin_index = HashMap::new();
+            let mut balance_deltas = BalanceDeltas::default();
 
             for tx in &checkpoint.transactions {
                 // determine changes from removed objects
@@ -307,6 +404,7 @@ impl IndexStoreTables {
                         Owner::AddressOwner(address) => {
                             let owner_key = OwnerIndexKey::new(*address, removed_object.id());
                             batch.delete_batch(&self.owner, [owner_key])?;
+                            balance_deltas.remove(*address, removed_object);
                         }
                         Owner::ObjectOwner(object_id) => {
                             batch.delete_batch(
@@ -323,6 +421,12 @@ impl IndexStoreTables {
                 // determine changes from changed objects
                 for (object, old_object) in tx.changed_objects() {
                     if let Some(old_object) = old_object {
+                        // The previous value of a coin no longer counts towards its owner's
+                        // balance, even if the owner didn't change.
+                        if let Owner::AddressOwner(address) = old_object.owner() {
+                            balance_deltas.remove(*address, old_object);
+                        }
+
                         if old_object.owner() != object.owner() {
                             match old_object.owner() {
                                 Owner::AddressOwner(address) => {
@@ -349,6 +453,7 @@ impl IndexStoreTables {
                             let owner_key = OwnerIndexKey::new(*owner, object.id());
                             let owner_info = OwnerIndexInfo::new(object);
                             batch.insert_batch(&self.owner, [(owner_key, owner_info)])?;
+                            balance_deltas.add(*owner, object);
                         }
                         Owner::ObjectOwner(parent) => {
                             if let Some(field_info) =
@@ -390,6 +495,7 @@ impl IndexStoreTables {
             }
 
             batch.insert_batch(&self.coin, coin_index)?;
+            balance_deltas.write(&mut batch, &self.balance)?;
         }
 
         debug!(
@@ -453,6 +559,36 @@ impl IndexStoreTables {
         };
         self.coin.get(&key)
     }
+
+    fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Option<BalanceIndexInfo>, TypedStoreError> {
+        self.balance.get(&BalanceIndexKey::new(owner, coin_type))
+    }
+
+    fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<impl Iterator<Item = (BalanceIndexKey, BalanceIndexInfo)> + '_, TypedStoreError>
+    {
+        // `TypeTag::Bool` and `TypeTag::U256` are the first and last variants of `TypeTag` by
+        // their serialized representation, so every coin type owned by `owner` falls between them.
+        let lower_bound = BalanceIndexKey::new(owner, TypeTag::Bool);
+        let upper_bound = BalanceIndexKey::new(owner, TypeTag::U256);
+        let mut iter = self
+            .balance
+            .iter_with_bounds(Some(lower_bound), Some(upper_bound));
+
+        if let Some(cursor) = cursor {
+            iter = iter.skip_to(&BalanceIndexKey::new(owner, cursor))?;
+        }
+
+        // Rows whose coins have all been spent remain until they are compacted away.
+        Ok(iter.filter(|(_, info)| !info.is_empty()))
+    }
 }
 
 pub struct RpcIndexStore {
@@ -597,6 +733,32 @@ impl RpcIndexStore {
     ) -> Result<Option<CoinIndexInfo>, TypedStoreError> {
         self.tables.get_coin_info(coin_type)
     }
+
+    /// The total balance and number of coins of type `coin_type` owned by `owner`.
+    pub fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<BalanceInfo, TypedStoreError> {
+        let balance = self
+            .tables
+            .get_balance(owner, coin_type)?
+            .unwrap_or_default();
+
+        Ok(balance.into())
+    }
+
+    /// Iterate over the balances of every coin type owned by `owner`, in order of coin type,
+    /// starting from `cursor` (inclusive) if one is provided.
+    pub fn balance_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<impl Iterator<Item = (TypeTag, BalanceInfo)> + '_, TypedStoreError> {
+        let iter = self.tables.balance_iter(owner, cursor)?;
+
+        Ok(iter.map(|(key, info)| (key.coin_type, info.into())))
+    }
 }
 
 fn try_create_dynamic_field_info(
@@ -641,6 +803,47 @@ fn try_create_dynamic_field_info(
     }))
 }
 
+fn balance_table_default_config() -> DBOptions {
+    let mut options = default_db_options();
+    options.options.set_merge_operator(
+        "balance delta operator",
+        balance_delta_merge_operator,
+        balance_delta_merge_operator,
+    );
+    options
+        .options
+        .set_compaction_filter("empty balance filter", empty_balance_compaction_filter);
+    options
+}
+
+/// Sums `BalanceIndexInfo` deltas. Summing is associative, so the same operator is used for both
+/// full and partial merges.
+fn balance_delta_merge_operator(
+    _key: &[u8],
+    stored_value: Option<&[u8]>,
+    operands: &MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut balance = match stored_value {
+        Some(value) => bcs::from_bytes::<BalanceIndexInfo>(value).ok()?,
+        None => BalanceIndexInfo::default(),
+    };
+
+    for operand in operands {
+        balance.merge_delta(&bcs::from_bytes(operand).ok()?);
+    }
+
+    bcs::to_bytes(&balance).ok()
+}
+
+/// Drops balances whose coins have all been spent. A missing row reads the same as an empty one,
+/// so any deltas merged in later are still applied correctly.
+fn empty_balance_compaction_filter(_level: u32, _key: &[u8], value: &[u8]) -> Decision {
+    match bcs::from_bytes::<BalanceIndexInfo>(value) {
+        Ok(balance) if balance.is_empty() => Decision::Remove,
+        _ => Decision::Keep,
+    }
+}
+
 fn try_create_coin_index_info(object: &Object) -> Option<(CoinIndexKey, CoinIndexInfo)> {
     use sui_types::coin::CoinMetadata;
     use sui_types::coin::TreasuryCap;
@@ -687,6 +890,7 @@ struct RpcLiveObjectIndexer<'a> {
     tables: &'a IndexStoreTables,
     batch: typed_store::rocks::DBBatch,
     coin_index: &'a Mutex<HashMap<CoinIndexKey, CoinIndexInfo>>,
+    balance_deltas: BalanceDeltas,
     resolver: Box<dyn LayoutResolver + 'a>,
 }
 
@@ -698,6 +902,7 @@ impl<'a> ParMakeLiveObjectIndexer for RpcParLiveObjectSetIndexer<'a> {
             tables: self.tables,
             batch: self.tables.owner.batch(),
             coin_index: self.coin_index,
+            balance_deltas: BalanceDeltas::default(),
             resolver: self
                 .epoch_store
                 .executor()
@@ -715,6 +920,9 @@ impl<'a> LiveObjectIndexer for RpcLiveObjectIndexer<'a> {
                 let owner_info = OwnerIndexInfo::new(&object);
                 self.batch
                     .insert_batch(&self.tables.owner, [(owner_key, owner_info)])?;
+
+                // Balance Index
+                self.balance_deltas.add(owner, &object);
             }
 
             // Dynamic Field Index
@@ -753,14 +961,119 @@ impl<'a> LiveObjectIndexer for RpcLiveObjectIndexer<'a> {
         // If the batch size grows to greater that 128MB then write out to the DB so that the
         // data we need to hold in memory doesn't grown unbounded.
         if self.batch.size_in_bytes() >= 1 << 27 {
+            self.balance_deltas
+                .write(&mut self.batch, &self.tables.balance)?;
             std::mem::replace(&mut self.batch, self.tables.owner.batch()).write()?;
         }
 
         Ok(())
     }
 
-    fn finish(self) -> Result<(), StorageError> {
+    fn finish(mut self) -> Result<(), StorageError> {
+        self.balance_deltas
+            .write(&mut self.batch, &self.tables.balance)?;
         self.batch.write()?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::BalanceDeltas;
+    use super::IndexStoreTables;
+    use sui_types::base_types::ObjectID;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::gas_coin::GAS;
+    use sui_types::object::Object;
+    use sui_types::storage::BalanceInfo;
+
+    fn coin(id: ObjectID, owner: SuiAddress, value: u64) -> Object {
+        Object::with_id_owner_gas_for_testing(id, owner, value)
+    }
+
+    // Merge the balance changes of coins leaving and entering accounts into the balance table,
+    // the same way the changes made by a checkpoint are indexed.
+    fn apply(
+        tables: &IndexStoreTables,
+        removed: &[(SuiAddress, Object)],
+        added: &[(SuiAddress, Object)],
+    ) {
+        let mut deltas = BalanceDeltas::default();
+        for (owner, object) in removed {
+            deltas.remove(*owner, object);
+        }
+        for (owner, object) in added {
+            deltas.add(*owner, object);
+        }
+
+        let mut batch = tables.balance.batch();
+        deltas.write(&mut batch, &tables.balance).unwrap();
+        batch.write().unwrap();
+    }
+
+    fn balance(tables: &IndexStoreTables, owner: SuiAddress) -> BalanceInfo {
+        tables
+            .get_balance(owner, GAS::type_tag())
+            .unwrap()
+            .unwrap_or_default()
+            .into()
+    }
+
+    fn expected(balance: u64, coin_object_count: u64) -> BalanceInfo {
+        BalanceInfo {
+            balance,
+            coin_object_count,
+        }
+    }
+
+    #[test]
+    fn balance_index_tracks_coin_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let tables = IndexStoreTables::open(dir.path());
+
+        let a = SuiAddress::random_for_testing_only();
+        let b = SuiAddress::random_for_testing_only();
+        let (coin_1, coin_2, coin_3) = (ObjectID::random(), ObjectID::random(), ObjectID::random());
+
+        apply(
+            &tables,
+            &[],
+            &[(a, coin(coin_1, a, 100)), (a, coin(coin_2, a, 50))],
+        );
+        assert_eq!(balance(&tables, a), expected(150, 2));
+
+        // Split
+        apply(
+            &tables,
+            &[(a, coin(coin_1, a, 100))],
+            &[(a, coin(coin_1, a, 70)), (a, coin(coin_3, a, 30))],
+        );
+        assert_eq!(balance(&tables, a), expected(150, 3));
+
+        // Merge
+        apply(
+            &tables,
+            &[(a, coin(coin_2, a, 50)), (a, coin(coin_3, a, 30))],
+            &[(a, coin(coin_2, a, 80))],
+        );
+        assert_eq!(balance(&tables, a), expected(150, 2));
+
+        // Transfer
+        apply(
+            &tables,
+            &[(a, coin(coin_2, a, 80))],
+            &[(b, coin(coin_2, b, 80))],
+        );
+        assert_eq!(balance(&tables, a), expected(70, 1));
+        assert_eq!(balance(&tables, b), expected(80, 1));
+
+        // Delete
+        apply(&tables, &[(a, coin(coin_1, a, 70))], &[]);
+        assert_eq!(balance(&tables, a), expected(0, 0));
+        assert_eq!(balance(&tables, b), expected(80, 1));
+
+        // Accounts whose coins have all been spent have no balances to list
+        assert_eq!(tables.balance_iter(a, None).unwrap().count(), 0);
+        assert_eq!(tables.balance_iter(b, None).unwrap().count(), 1);
+    }
+}
@@ -1,6 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
 
 use arc_swap::ArcSwap;
 use consensus_config::Committee as ConsensusCommittee;
@@ -18,11 +21,14 @@ use crate::authority::AuthorityMetrics;
 /// submission side with the consensus leader election schedule. Practically we don't want to submit
 /// transactions for sequencing to validators that have low scores and are not part of the leader
 /// schedule since the chances of getting them sequenced are lower.
+/// Authorities that have been proven to misbehave in consensus are always flagged, regardless of
+/// their score.
 pub(crate) fn update_low_scoring_authorities(
     low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
     sui_committee: &Committee,
     consensus_committee: &ConsensusCommittee,
     reputation_score_sorted_desc: Option<Vec<(AuthorityIndex, u64)>>,
+    misbehaving_authorities: &BTreeSet<AuthorityIndex>,
     metrics: &Arc<AuthorityMetrics>,
     consensus_bad_nodes_stake_threshold: u64,
 ) {
@@ -53,6 +59,7 @@ pub(crate) fn update_low_scoring_authorities(
 
         let included = if total_stake
             <= consensus_bad_nodes_stake_threshold * consensus_committee.total_stake() / 100
+            || misbehaving_authorities.contains(&index)
         {
             final_low_scoring_map.insert(*authority_name, score);
             true
@@ -82,7 +89,10 @@ pub(crate) fn update_low_scoring_authorities(
 #[cfg(test)]
 mod tests {
     #![allow(clippy::mutable_key_type)]
-    use std::{collections::HashMap, sync::Arc};
+    use std::{
+        collections::{BTreeSet, HashMap},
+        sync::Arc,
+    };
 
     use arc_swap::ArcSwap;
     use consensus_config::{local_committee_and_keys, Committee as ConsensusCommittee};
@@ -121,6 +131,7 @@ mod tests {
             &sui_committee,
             &consensus_committee,
             Some(authorities_by_score_desc.clone()),
+            &BTreeSet::new(),
             &metrics,
             consensus_bad_nodes_stake_threshold,
         );
@@ -151,6 +162,7 @@ mod tests {
             &sui_committee,
             &consensus_committee,
             Some(authorities_by_score_desc.clone()),
+            &BTreeSet::new(),
             &metrics,
             consensus_bad_nodes_stake_threshold,
         );
@@ -164,6 +176,27 @@ mod tests {
                 .unwrap(),
             0
         );
+
+        // WHEN an authority with a high score has equivocated
+        update_low_scoring_authorities(
+            low_scoring.clone(),
+            &sui_committee,
+            &consensus_committee,
+            Some(authorities_by_score_desc.clone()),
+            &BTreeSet::from([1]),
+            &metrics,
+            consensus_bad_nodes_stake_threshold,
+        );
+
+        // THEN it is flagged along with the lowest scoring authority
+        assert_eq!(low_scoring.load().len(), 2);
+        assert_eq!(
+            *low_scoring
+                .load()
+                .get(sui_committee.authority_by_index(1).unwrap())
+                .unwrap(),
+            390
+        );
     }
 
     /// Generate a pair of Sui and consensus committees for the given size.
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use sui_types::base_types::ObjectID;
@@ -22,6 +23,7 @@ use sui_types::object::Object;
 use sui_types::storage::error::Error as StorageError;
 use sui_types::storage::error::Result;
 use sui_types::storage::AccountOwnedObjectInfo;
+use sui_types::storage::BalanceInfo;
 use sui_types::storage::CoinInfo;
 use sui_types::storage::DynamicFieldIndexInfo;
 use sui_types::storage::DynamicFieldKey;
@@ -528,4 +530,24 @@ impl RpcIndexes for RpcIndexStore {
             )
             .pipe(Ok)
     }
+
+    fn get_balance(
+        &self,
+        owner: &SuiAddress,
+        coin_type: &TypeTag,
+    ) -> sui_types::storage::error::Result<BalanceInfo> {
+        self.get_balance(*owner, coin_type.to_owned())
+            .map_err(StorageError::custom)
+    }
+
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> sui_types::storage::error::Result<Box<dyn Iterator<Item = (TypeTag, BalanceInfo)> + '_>>
+    {
+        let iter = self.balance_iter(*owner, cursor)?;
+
+        Ok(Box::new(iter) as _)
+    }
 }
@@ -0,0 +1,250 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use sui_macros::sim_test;
+use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::v2::GetBalanceRequest;
+use sui_rpc_api::proto::node::v2::ListBalancesRequest;
+use sui_rpc_api::proto::node::v2::ListOwnedObjectsRequest;
+use sui_types::coin::COIN_MODULE_NAME;
+use sui_types::coin::COIN_STRUCT_NAME;
+use sui_types::gas_coin::GAS;
+use sui_types::parse_sui_type_tag;
+use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
+use sui_types::sui_sdk_types_conversions::type_tag_core_to_sdk;
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+use test_cluster::TestClusterBuilder;
+
+#[sim_test]
+async fn list_owned_objects() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let address = test_cluster.get_address_0();
+    let owner = sui_sdk_types::Address::from(address);
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let gas_coins = test_cluster
+        .wallet
+        .get_gas_objects_owned_by_address(address, None)
+        .await
+        .unwrap();
+
+    // Page through all of the coins owned by the address one at a time, filtering by
+    // `0x2::coin::Coin` without any type parameters which should match every coin type.
+    let coin_type = struct_tag_core_to_sdk(move_core_types::language_storage::StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: COIN_MODULE_NAME.to_owned(),
+        name: COIN_STRUCT_NAME.to_owned(),
+        type_params: vec![],
+    })
+    .unwrap();
+
+    let mut objects = Vec::new();
+    let mut page_token = None;
+    loop {
+        let response = grpc_client
+            .list_owned_objects(ListOwnedObjectsRequest {
+                owner: Some(owner.into()),
+                object_type: Some(coin_type.clone().into()),
+                page_size: Some(1),
+                page_token: page_token.take(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(response.objects.len() <= 1);
+        objects.extend(response.objects);
+
+        match response.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    let object_ids = objects
+        .iter()
+        .map(|object| {
+            assert_eq!(object.owner, Some(owner.into()));
+            sui_sdk_types::ObjectId::try_from(object.object_id.as_ref().unwrap()).unwrap()
+        })
+        .collect::<HashSet<_>>();
+    let expected = gas_coins
+        .iter()
+        .map(|coin| sui_sdk_types::ObjectId::from(coin.0))
+        .collect::<HashSet<_>>();
+    assert_eq!(objects.len(), object_ids.len());
+    assert_eq!(object_ids, expected);
+
+    // A page token is only valid for the request that produced it
+    let page_token = grpc_client
+        .list_owned_objects(ListOwnedObjectsRequest {
+            owner: Some(owner.into()),
+            object_type: None,
+            page_size: Some(1),
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .next_page_token;
+    assert!(page_token.is_some());
+
+    grpc_client
+        .list_owned_objects(ListOwnedObjectsRequest {
+            owner: Some(owner.into()),
+            object_type: Some(coin_type.into()),
+            page_size: Some(1),
+            page_token,
+        })
+        .await
+        .unwrap_err();
+}
+
+#[sim_test]
+async fn get_and_list_balances() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let address = test_cluster.get_address_0();
+    let owner = sui_sdk_types::Address::from(address);
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let expected = test_cluster
+        .sui_client()
+        .coin_read_api()
+        .get_balance(address, None)
+        .await
+        .unwrap();
+    let sui_type = type_tag_core_to_sdk(GAS::type_tag()).unwrap();
+
+    // The coin type defaults to SUI
+    let balance = grpc_client
+        .get_balance(GetBalanceRequest {
+            owner: Some(owner.into()),
+            coin_type: None,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .balance
+        .unwrap();
+
+    assert_eq!(balance.coin_type, Some(sui_type.clone().into()));
+    assert_eq!(balance.balance, Some(expected.total_balance as u64));
+    assert_eq!(
+        balance.coin_object_count,
+        Some(expected.coin_object_count as u64)
+    );
+
+    let response = grpc_client
+        .list_balances(ListBalancesRequest {
+            owner: Some(owner.into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.balances, vec![balance]);
+    assert!(response.next_page_token.is_none());
+}
+
+#[sim_test]
+async fn list_balances_of_multiple_coin_types() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let address = test_cluster.get_address_0();
+    let owner = sui_sdk_types::Address::from(address);
+
+    // Publishing the package mints a REGULATED_COIN to its sender
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/move_test_code");
+    let tx_data = test_cluster
+        .test_transaction_builder_with_sender(address)
+        .await
+        .publish(path)
+        .build();
+    test_cluster.sign_and_execute_transaction(&tx_data).await;
+
+    let expected = test_cluster
+        .sui_client()
+        .coin_read_api()
+        .get_all_balances(address)
+        .await
+        .unwrap();
+    assert_eq!(expected.len(), 2);
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // Page through the balances one coin type at a time
+    let mut balances = Vec::new();
+    let mut page_token = None;
+    loop {
+        let response = grpc_client
+            .list_balances(ListBalancesRequest {
+                owner: Some(owner.into()),
+                page_size: Some(1),
+                page_token: page_token.take(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert!(response.balances.len() <= 1);
+        balances.extend(response.balances);
+
+        match response.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    assert_eq!(balances.len(), expected.len());
+    for expected in expected {
+        let coin_type =
+            type_tag_core_to_sdk(parse_sui_type_tag(&expected.coin_type).unwrap()).unwrap();
+        let balance = balances
+            .iter()
+            .find(|balance| balance.coin_type == Some(coin_type.clone().into()))
+            .unwrap();
+
+        assert_eq!(balance.balance, Some(expected.total_balance as u64));
+        assert_eq!(
+            balance.coin_object_count,
+            Some(expected.coin_object_count as u64)
+        );
+
+        // Each balance can also be read on its own
+        let single = grpc_client
+            .get_balance(GetBalanceRequest {
+                owner: Some(owner.into()),
+                coin_type: Some(coin_type.into()),
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .balance
+            .unwrap();
+        assert_eq!(&single, balance);
+    }
+
+    // All of the balances fit on a single default sized page
+    let response = grpc_client
+        .list_balances(ListBalancesRequest {
+            owner: Some(owner.into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.balances, balances);
+    assert!(response.next_page_token.is_none());
+}
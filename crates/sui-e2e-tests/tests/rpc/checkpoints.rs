@@ -463,3 +463,162 @@ async fn subscribe_checkpoint() {
 
     assert!(count >= 50);
 }
+
+#[sim_test]
+async fn subscribe_transactions() {
+    use sui_rpc_api::proto::node::v2alpha::subscription_service_client::SubscriptionServiceClient;
+    use sui_rpc_api::proto::node::v2alpha::{SubscribeTransactionsRequest, TransactionFilter};
+    use tokio_stream::StreamExt;
+
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let _transaction_digest = transfer_coin(&test_cluster.wallet).await;
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+
+    let mut client = SubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // Resuming from genesis replays already executed checkpoints from the store, and only the
+    // staking transaction calls into `0x3::sui_system`.
+    let mut stream = client
+        .subscribe_transactions(SubscribeTransactionsRequest {
+            filter: Some(TransactionFilter {
+                package: Some(
+                    sui_sdk_types::ObjectId::from(sui_types::SUI_SYSTEM_PACKAGE_ID).into(),
+                ),
+                module: Some(sui_sdk_types::Identifier::new("sui_system").unwrap().into()),
+                ..Default::default()
+            }),
+            start_checkpoint: Some(0),
+            options: Some(GetFullCheckpointOptions {
+                effects: Some(true),
+                ..Default::default()
+            }),
+            start_transaction_index: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let response = stream.next().await.unwrap().unwrap();
+    let cursor = response.cursor.unwrap();
+    let transaction_index = response.transaction_index.unwrap();
+
+    let FullCheckpointTransaction {
+        digest,
+        transaction,
+        effects,
+        ..
+    } = response.transaction.unwrap();
+    assert_eq!(digest, Some(transaction_digest.into()));
+    assert!(transaction.is_none());
+    assert!(effects.is_some());
+
+    // Resuming right after the delivered transaction doesn't deliver it again.
+    let mut stream = client
+        .subscribe_transactions(SubscribeTransactionsRequest {
+            filter: None,
+            start_checkpoint: Some(cursor),
+            options: None,
+            start_transaction_index: Some(transaction_index + 1),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let response = stream.next().await.unwrap().unwrap();
+    assert!(
+        (
+            response.cursor.unwrap(),
+            response.transaction_index.unwrap()
+        ) > (cursor, transaction_index)
+    );
+    assert_ne!(
+        response.transaction.unwrap().digest,
+        Some(transaction_digest.into())
+    );
+
+    // A transaction index requires a start checkpoint.
+    client
+        .subscribe_transactions(SubscribeTransactionsRequest {
+            filter: None,
+            start_checkpoint: None,
+            options: None,
+            start_transaction_index: Some(0),
+        })
+        .await
+        .unwrap_err();
+}
+
+#[sim_test]
+async fn subscribe_events() {
+    use sui_rpc_api::proto::node::v2alpha::subscription_service_client::SubscriptionServiceClient;
+    use sui_rpc_api::proto::node::v2alpha::{EventFilter, SubscribeEventsRequest};
+    use tokio_stream::StreamExt;
+
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let transaction_digest = stake_with_validator(&test_cluster).await;
+
+    let mut client = SubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let mut stream = client
+        .subscribe_events(SubscribeEventsRequest {
+            filter: Some(EventFilter {
+                package: Some(
+                    sui_sdk_types::ObjectId::from(sui_types::SUI_SYSTEM_PACKAGE_ID).into(),
+                ),
+                module: Some(sui_sdk_types::Identifier::new("sui_system").unwrap().into()),
+                ..Default::default()
+            }),
+            start_checkpoint: Some(0),
+            start_transaction_index: None,
+            start_event_index: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let response = stream.next().await.unwrap().unwrap();
+    let cursor = response.cursor.unwrap();
+    let transaction_index = response.transaction_index.unwrap();
+    let event_index = response.event_index.unwrap();
+    assert_eq!(response.transaction_digest, Some(transaction_digest.into()));
+
+    let event = sui_sdk_types::Event::try_from(&response.event.unwrap()).unwrap();
+    assert_eq!(event.type_.name.as_str(), "StakingRequestEvent");
+
+    // Resuming from the delivered event, inclusive, delivers it again.
+    let mut stream = client
+        .subscribe_events(SubscribeEventsRequest {
+            filter: None,
+            start_checkpoint: Some(cursor),
+            start_transaction_index: Some(transaction_index),
+            start_event_index: Some(event_index),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.cursor, Some(cursor));
+    assert_eq!(response.transaction_index, Some(transaction_index));
+    assert_eq!(response.event_index, Some(event_index));
+
+    // A `module` filter without a `package` is rejected.
+    client
+        .subscribe_events(SubscribeEventsRequest {
+            filter: Some(EventFilter {
+                module: Some(sui_sdk_types::Identifier::new("sui_system").unwrap().into()),
+                ..Default::default()
+            }),
+            start_checkpoint: None,
+            start_transaction_index: None,
+            start_event_index: None,
+        })
+        .await
+        .unwrap_err();
+}
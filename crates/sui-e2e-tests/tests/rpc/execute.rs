@@ -55,3 +55,58 @@ async fn execute_transaction_transfer() {
 
     assert_eq!(actual, expected);
 }
+
+#[sim_test]
+async fn simulate_transaction_transfer() {
+    use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
+    use sui_rpc_api::proto::node::v2::{SimulateTransactionOptions, SimulateTransactionRequest};
+    use sui_rpc_api::proto::types::Bcs;
+
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let address = SuiAddress::random_for_testing_only();
+    let amount = 9;
+
+    let txn =
+        make_transfer_sui_transaction(&test_cluster.wallet, Some(address), Some(amount)).await;
+
+    let response = grpc_client
+        .simulate_transaction(SimulateTransactionRequest {
+            transaction: None,
+            transaction_bcs: Some(Bcs::serialize(txn.transaction_data()).unwrap()),
+            options: Some(SimulateTransactionOptions {
+                effects_bcs: Some(true),
+                balance_changes: Some(true),
+                ..Default::default()
+            }),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Only the requested fields are populated
+    assert!(response.effects.is_none());
+    assert!(response.events.is_none());
+
+    let effects: sui_types::effects::TransactionEffects =
+        response.effects_bcs.unwrap().deserialize().unwrap();
+    assert!(effects.status().is_ok());
+
+    let recommended_gas_budget = response.recommended_gas_budget.unwrap();
+    assert!(recommended_gas_budget >= effects.gas_cost_summary().computation_cost);
+
+    // Both the sender and the recipient observe a balance change
+    assert_eq!(response.balance_changes.unwrap().balance_changes.len(), 2);
+
+    // Simulating doesn't execute the transaction
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+    let options = ExecuteTransactionOptions::default();
+    let executed = client.execute_transaction(&options, &txn).await.unwrap();
+    assert_eq!(
+        executed.effects.transaction_digest(),
+        effects.transaction_digest()
+    );
+}
@@ -1,11 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod accounts;
 mod checkpoints;
 mod committee;
 mod execute;
 mod node_info;
 mod objects;
+mod packages;
 mod resolve;
 mod transactions;
 
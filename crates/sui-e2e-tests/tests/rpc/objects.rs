@@ -8,6 +8,7 @@ use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
 use sui_rpc_api::proto::node::v2::GetObjectOptions;
 use sui_rpc_api::proto::node::v2::GetObjectRequest;
 use sui_rpc_api::proto::node::v2::GetObjectResponse;
+use sui_rpc_api::proto::node::v2::ListDynamicFieldsRequest;
 use sui_sdk_types::ObjectId;
 use test_cluster::TestClusterBuilder;
 
@@ -97,3 +98,36 @@ async fn get_object() {
     // ensure we can convert proto ObjectResponse type to rust ObjectResponse
     sui_rpc_api::types::ObjectResponse::try_from(&response).unwrap();
 }
+
+#[sim_test]
+async fn list_dynamic_fields() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    // The SuiSystemState object stores its inner state in a dynamic field
+    let parent: ObjectId = "0x5".parse().unwrap();
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let response = grpc_client
+        .list_dynamic_fields(ListDynamicFieldsRequest {
+            parent: Some(parent.into()),
+            page_size: None,
+            page_token: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(!response.dynamic_fields.is_empty());
+    for field in &response.dynamic_fields {
+        assert_eq!(field.parent, Some(parent.into()));
+        assert!(field.field_id.is_some());
+        assert!(field.name_type.is_some());
+        assert!(field.name_value.is_some());
+    }
+
+    // ensure we can convert proto ListDynamicFieldsResponse type to rust ListDynamicFieldsResponse
+    sui_rpc_api::types::ListDynamicFieldsResponse::try_from(&response).unwrap();
+}
@@ -0,0 +1,159 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::proto::node::v2::node_service_client::NodeServiceClient;
+use sui_rpc_api::proto::node::v2::Ability;
+use sui_rpc_api::proto::node::v2::DatatypeKind;
+use sui_rpc_api::proto::node::v2::GetDatatypeRequest;
+use sui_rpc_api::proto::node::v2::GetFunctionRequest;
+use sui_rpc_api::proto::node::v2::GetPackageRequest;
+use sui_rpc_api::proto::node::v2::OpenSignatureBodyType;
+use sui_rpc_api::proto::node::v2::Reference;
+use sui_rpc_api::proto::node::v2::Visibility;
+use sui_sdk_types::ObjectId;
+use test_cluster::TestClusterBuilder;
+
+const COIN_TYPE_NAME: &str =
+    "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin";
+
+#[sim_test]
+async fn get_package() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let package_id: ObjectId = "0x2".parse().unwrap();
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let package = grpc_client
+        .get_package(GetPackageRequest {
+            package_id: Some(package_id.into()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .package
+        .unwrap();
+
+    assert_eq!(package.storage_id, Some(package_id.into()));
+    assert_eq!(package.original_id, Some(package_id.into()));
+    let coin = package
+        .modules
+        .iter()
+        .find(|module| module.name.as_deref() == Some("coin"))
+        .unwrap();
+    assert!(coin
+        .datatypes
+        .iter()
+        .any(|datatype| datatype.name.as_deref() == Some("Coin")));
+    assert!(coin
+        .functions
+        .iter()
+        .any(|function| function.name.as_deref() == Some("value")));
+
+    // Requesting a package which doesn't exist fails
+    grpc_client
+        .get_package(GetPackageRequest {
+            package_id: Some("0x1234".parse::<ObjectId>().unwrap().into()),
+        })
+        .await
+        .unwrap_err();
+}
+
+#[sim_test]
+async fn get_datatype() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let package_id: ObjectId = "0x2".parse().unwrap();
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let datatype = grpc_client
+        .get_datatype(GetDatatypeRequest {
+            package_id: Some(package_id.into()),
+            module_name: Some("coin".to_owned()),
+            name: Some("Coin".to_owned()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .datatype
+        .unwrap();
+
+    assert_eq!(datatype.type_name.as_deref(), Some(COIN_TYPE_NAME));
+    assert_eq!(datatype.kind(), DatatypeKind::Struct);
+    assert_eq!(datatype.module.as_deref(), Some("coin"));
+    assert_eq!(datatype.name.as_deref(), Some("Coin"));
+    assert_eq!(
+        datatype.abilities().collect::<Vec<_>>(),
+        vec![Ability::Store, Ability::Key]
+    );
+    assert_eq!(datatype.type_parameters.len(), 1);
+    assert_eq!(datatype.type_parameters[0].is_phantom, Some(true));
+
+    let fields = datatype
+        .fields
+        .iter()
+        .map(|field| field.name.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["id", "balance"]);
+    assert!(datatype.variants.is_empty());
+}
+
+#[sim_test]
+async fn get_function() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let package_id: ObjectId = "0x2".parse().unwrap();
+
+    let mut grpc_client = NodeServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    // public fun value<T>(self: &Coin<T>): u64
+    let function = grpc_client
+        .get_function(GetFunctionRequest {
+            package_id: Some(package_id.into()),
+            module_name: Some("coin".to_owned()),
+            name: Some("value".to_owned()),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .function
+        .unwrap();
+
+    assert_eq!(function.visibility(), Visibility::Public);
+    assert_eq!(function.is_entry, Some(false));
+    assert_eq!(function.type_parameters.len(), 1);
+    assert_eq!(function.type_parameters[0].is_phantom, None);
+
+    assert_eq!(function.parameters.len(), 1);
+    let parameter = &function.parameters[0];
+    assert_eq!(parameter.reference(), Reference::Immutable);
+    let body = parameter.body.as_ref().unwrap();
+    assert_eq!(body.r#type(), OpenSignatureBodyType::Datatype);
+    assert_eq!(body.type_name.as_deref(), Some(COIN_TYPE_NAME));
+    assert_eq!(
+        body.type_parameter_instantiation[0].r#type(),
+        OpenSignatureBodyType::TypeParameter
+    );
+
+    assert_eq!(function.returns.len(), 1);
+    assert!(function.returns[0].reference.is_none());
+    assert_eq!(
+        function.returns[0].body.as_ref().unwrap().r#type(),
+        OpenSignatureBodyType::U64
+    );
+
+    // Requesting a function which doesn't exist fails
+    grpc_client
+        .get_function(GetFunctionRequest {
+            package_id: Some(package_id.into()),
+            module_name: Some("coin".to_owned()),
+            name: Some("does_not_exist".to_owned()),
+        })
+        .await
+        .unwrap_err();
+}
@@ -11,6 +11,7 @@ mod write_ahead_log;
 pub use self::simple_faucet::SimpleFaucet;
 use clap::Parser;
 use std::{net::Ipv4Addr, path::PathBuf, sync::Arc};
+use sui_transaction_builder::coin_selection::CoinSelectionStrategy;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FaucetReceipt {
@@ -151,6 +152,13 @@ pub struct FaucetConfig {
     /// used for authenticated mode.
     #[clap(long, default_value_t = 60)]
     pub rate_limiter_cleanup_interval_secs: u64,
+
+    /// The order in which the faucet's coins are added to its gas pools, one of `first-fit`,
+    /// `largest-first`, `smallest-first`, `exact-match` (preferring coins that hold exactly
+    /// `amount * num_coins`) or `avoid-locked`. Coins still in flight from a previous run are
+    /// never added, whatever the strategy.
+    #[clap(long, default_value_t = CoinSelectionStrategy::FirstFit)]
+    pub coin_selection: CoinSelectionStrategy,
 }
 
 impl Default for FaucetConfig {
@@ -174,6 +182,7 @@ impl Default for FaucetConfig {
             replenish_quota_interval_ms: 10,
             reset_time_interval_secs: 3600 * 12,
             rate_limiter_cleanup_interval_secs: 60,
+            coin_selection: CoinSelectionStrategy::FirstFit,
         }
     }
 }
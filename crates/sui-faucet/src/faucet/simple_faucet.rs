@@ -26,6 +26,7 @@ use sui_json_rpc_types::{
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::wallet_context::WalletContext;
+use sui_transaction_builder::coin_selection::{AvoidLocked, CoinCandidate, CoinSelector};
 use sui_types::object::Owner;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::{
@@ -107,25 +108,53 @@ impl SimpleFaucet {
             .map_err(|err| FaucetError::Wallet(err.to_string()))?;
         info!("SimpleFaucet::new with active address: {active_address}");
 
-        let coins = wallet
+        let amount_per_request = config.amount * config.num_coins as u64;
+        let candidates = wallet
             .gas_objects(active_address)
             .await
             .map_err(|e| FaucetError::Wallet(e.to_string()))?
             .iter()
-            // Ok to unwrap() since `get_gas_objects` guarantees gas
-            .map(|q| GasCoin::try_from(&q.1).unwrap())
-            .filter(|coin| coin.0.balance.value() >= (config.amount * config.num_coins as u64))
-            .collect::<Vec<GasCoin>>();
+            .map(|(balance, object)| CoinCandidate::new(object.object_ref(), *balance))
+            .filter(|coin| coin.balance >= amount_per_request)
+            .collect::<Vec<_>>();
+        let num_coins = candidates.len();
         let metrics = FaucetMetrics::new(prometheus_registry);
         // set initial balance when faucet starts
-        let balance = coins.iter().map(|coin| coin.0.balance.value()).sum::<u64>();
+        let balance = candidates.iter().map(|coin| coin.balance).sum::<u64>();
         metrics.balance.set(balance as i64);
 
         let wal = WriteAheadLog::open(wal_path);
         let mut pending = vec![];
 
-        let (producer, consumer) = mpsc::channel(coins.len());
-        let (batch_producer, batch_consumer) = mpsc::channel(coins.len());
+        // Coins that are still reserved by a transaction from a previous run are retried from the
+        // WAL, so they are locked and never handed out to the gas pools.
+        let mut in_flight = vec![];
+        for coin in &candidates {
+            let coin_id = coin.object_id();
+            if let Some(write_ahead_log::Entry {
+                uuid,
+                recipient,
+                tx,
+                retry_count: _,
+                in_flight: _,
+            }) = wal.reclaim(coin_id).map_err(FaucetError::internal)?
+            {
+                let uuid = Uuid::from_bytes(uuid);
+                info!(?uuid, ?recipient, ?coin_id, "Retrying txn from WAL.");
+                pending.push((uuid, recipient, coin_id, tx));
+                in_flight.push(coin_id);
+            }
+        }
+
+        // The selected strategy decides which coins end up in which pool, and in what order they
+        // are handed out.
+        let coins = AvoidLocked::new(config.coin_selection.selector(in_flight.clone()), in_flight)
+            .prioritize(candidates, amount_per_request);
+
+        // Retried coins are recycled into the pools once their transactions finish, so the pools
+        // need room for every coin.
+        let (producer, consumer) = mpsc::channel(num_coins);
+        let (batch_producer, batch_consumer) = mpsc::channel(num_coins);
 
         let (sender, mut receiver) =
             mpsc::channel::<(Uuid, SuiAddress, Vec<u64>)>(config.max_request_queue_length as usize);
@@ -139,19 +168,8 @@ impl SimpleFaucet {
         // Put half of the coins in the old faucet impl queue, and put half in the other queue for batch coins.
         // In the test cases we create an account with 5 coins so we just let this run with a minimum of 5 coins
         for (coins_processed, coin) in coins.iter().enumerate() {
-            let coin_id = *coin.id();
-            if let Some(write_ahead_log::Entry {
-                uuid,
-                recipient,
-                tx,
-                retry_count: _,
-                in_flight: _,
-            }) = wal.reclaim(coin_id).map_err(FaucetError::internal)?
-            {
-                let uuid = Uuid::from_bytes(uuid);
-                info!(?uuid, ?recipient, ?coin_id, "Retrying txn from WAL.");
-                pending.push((uuid, recipient, coin_id, tx));
-            } else if coins_processed < split_point {
+            let coin_id = coin.object_id();
+            if coins_processed < split_point {
                 producer
                     .send(coin_id)
                     .await
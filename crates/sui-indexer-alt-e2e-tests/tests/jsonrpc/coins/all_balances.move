@@ -0,0 +1,73 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. Balances of all coin types owned by A, ordered by coin type
+// 2. Balance of a single coin type
+// 3. Balances after coins are merged and transferred
+
+//# programmable --sender A --inputs 12 34 @A
+//> 0: SplitCoins(Gas, [Input(0), Input(1)]);
+//> 1: TransferObjects([NestedResult(0,0), NestedResult(0,1)], Input(2))
+
+//# publish --sender A
+module test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext) {
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c1 = coin::mint(&mut treasury_cap, 100, ctx);
+        let c2 = coin::mint(&mut treasury_cap, 200, ctx);
+        let c3 = coin::mint(&mut treasury_cap, 300, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c1, tx_context::sender(ctx));
+        transfer::public_transfer(c2, tx_context::sender(ctx));
+        transfer::public_transfer(c3, tx_context::sender(ctx));
+    }
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllBalances",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getBalance",
+  "params": ["@{A}", "@{test}::fake::FAKE"]
+}
+
+//# programmable --sender A --inputs object(1,0) object(1,1) @B
+//> 0: MergeCoins(Input(0), [Input(1)]);
+//> 1: TransferObjects([Input(0)], Input(2))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllBalances",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getAllBalances",
+  "params": ["@{B}"]
+}
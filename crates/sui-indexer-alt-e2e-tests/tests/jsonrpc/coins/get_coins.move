@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses test=0x0 --simulator
+
+// 1. All SUI coins owned by A, larger balances first
+// 2. Setting a limit
+// 3. Setting a limit and a cursor
+// 4. Coins of another type
+// 5. Coins of a type A does not own
+
+//# programmable --sender A --inputs 12 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 34 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 5600 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# publish --sender A
+module test::fake {
+    use sui::coin;
+
+    public struct FAKE has drop {}
+
+    fun init(witness: FAKE, ctx: &mut TxContext) {
+        let (mut treasury_cap, metadata) = coin::create_currency(
+            witness,
+            2,
+            b"FAKE",
+            b"",
+            b"",
+            option::none(),
+            ctx,
+        );
+
+        let c1 = coin::mint(&mut treasury_cap, 1, ctx);
+        let c2 = coin::mint(&mut treasury_cap, 200, ctx);
+
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx));
+        transfer::public_transfer(c1, tx_context::sender(ctx));
+        transfer::public_transfer(c2, tx_context::sender(ctx));
+    }
+}
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", null, null, 2]
+}
+
+//# run-jsonrpc --cursors [3,"@{obj_3_0}"]
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", null, "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", "@{test}::fake::FAKE"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getCoins",
+  "params": ["@{A}", "0x2::coin::Coin<0x2::sui::SUI>"]
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses test=0x0 --simulator
+
+// 1. All the dynamic fields of an object, including a dynamic object field
+// 2. Setting a limit
+// 3. Fetching a dynamic field's value
+// 4. Fetching a dynamic object field's value
+// 5. Removed fields are no longer returned
+
+//# publish
+module test::fields {
+    use sui::dynamic_field as df;
+    use sui::dynamic_object_field as dof;
+
+    public struct Parent has key, store {
+        id: UID,
+    }
+
+    public struct Child has key, store {
+        id: UID,
+        value: u64,
+    }
+
+    public fun new(ctx: &mut TxContext): Parent {
+        Parent { id: object::new(ctx) }
+    }
+
+    public fun add_fields(parent: &mut Parent, ctx: &mut TxContext) {
+        df::add(&mut parent.id, 1u64, 10u64);
+        df::add(&mut parent.id, 2u64, 20u64);
+        dof::add(&mut parent.id, 3u64, Child { id: object::new(ctx), value: 30 });
+    }
+
+    public fun remove_field(parent: &mut Parent) {
+        let _: u64 = df::remove(&mut parent.id, 1u64);
+    }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: test::fields::new();
+//> 1: TransferObjects([Result(0)], Input(0))
+
+//# run test::fields::add_fields --sender A --args object(2,0)
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}", null, 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFieldObject",
+  "params": ["@{obj_2_0}", { "type": "u64", "value": "2" }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFieldObject",
+  "params": ["@{obj_2_0}", { "type": "u64", "value": "3" }]
+}
+
+//# run test::fields::remove_field --sender A --args object(2,0)
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getDynamicFields",
+  "params": ["@{obj_2_0}"]
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A --addresses test=0x0 --simulator
+
+// Each of transactions 2, 3 and 4 emits three events.
+//
+// 1. All events from the module, oldest first
+// 2. Setting a limit
+// 3. A cursor pointing into the middle of a transaction's events
+// 4. A cursor pointing at the last event of a transaction, so the page starts in the next one
+// 5. Changing the order
+// 6. Setting the order, cursor and limit
+// 7. Filtering by event type, with a cursor and limit
+
+//# publish
+module test::events {
+    use sui::event;
+
+    public struct E has copy, drop {
+        value: u64,
+    }
+
+    public fun emit(start: u64) {
+        event::emit(E { value: start });
+        event::emit(E { value: start + 1 });
+        event::emit(E { value: start + 2 });
+    }
+}
+
+//# run test::events::emit --sender A --args 0
+
+//# run test::events::emit --sender A --args 10
+
+//# run test::events::emit --sender A --args 20
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{test}", "module": "events" } }]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [{ "MoveModule": { "package": "@{test}", "module": "events" } }, null, 2]
+}
+
+//# run-jsonrpc --cursors [2,1]
+{
+  "method": "suix_queryEvents",
+  "params": [
+    { "MoveModule": { "package": "@{test}", "module": "events" } },
+    "@{cursor_0}",
+    2
+  ]
+}
+
+//# run-jsonrpc --cursors [2,2]
+{
+  "method": "suix_queryEvents",
+  "params": [
+    { "MoveModule": { "package": "@{test}", "module": "events" } },
+    "@{cursor_0}",
+    3
+  ]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_queryEvents",
+  "params": [
+    { "MoveModule": { "package": "@{test}", "module": "events" } },
+    null,
+    2,
+    true
+  ]
+}
+
+//# run-jsonrpc --cursors [3,1]
+{
+  "method": "suix_queryEvents",
+  "params": [
+    { "MoveModule": { "package": "@{test}", "module": "events" } },
+    "@{cursor_0}",
+    4,
+    true
+  ]
+}
+
+//# run-jsonrpc --cursors [3,0]
+{
+  "method": "suix_queryEvents",
+  "params": [
+    { "MoveEventType": "@{test}::events::E" },
+    "@{cursor_0}",
+    2
+  ]
+}
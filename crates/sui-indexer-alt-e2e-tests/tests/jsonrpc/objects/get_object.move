@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. Fetching an object with the default options
+// 2. Fetching an object with all its contents
+// 3. Fetching the latest version of a modified object
+// 4. Fetching several objects at once, including one that does not exist
+
+//# programmable --sender A --inputs 42 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 43 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": ["@{obj_1_0}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": [
+    "@{obj_1_0}",
+    {
+      "showType": true,
+      "showOwner": true,
+      "showPreviousTransaction": true,
+      "showContent": true,
+      "showBcs": true,
+      "showStorageRebate": true
+    }
+  ]
+}
+
+//# transfer-object 1,0 --sender A --recipient B
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_getObject",
+  "params": ["@{obj_1_0}", { "showOwner": true }]
+}
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetObjects",
+  "params": [
+    [
+      "@{obj_1_0}",
+      "0x0000000000000000000000000000000000000000000000000000000000000042",
+      "@{obj_2_0}"
+    ],
+    { "showOwner": true }
+  ]
+}
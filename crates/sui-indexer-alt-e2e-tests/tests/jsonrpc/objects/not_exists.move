@@ -0,0 +1,14 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --addresses test=0x0 --simulator
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "sui_multiGetObjects",
+  "params": [
+    ["0x0000000000000000000000000000000000000000000000000000000000000042"]
+  ]
+}
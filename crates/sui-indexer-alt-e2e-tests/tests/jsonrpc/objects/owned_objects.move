@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 70 --accounts A B --addresses test=0x0 --simulator
+
+// 1. All objects owned by A, ordered by ID
+// 2. Setting a limit
+// 3. Setting a limit and a cursor
+// 4. Filtering by type
+// 5. Objects that are transferred away are no longer returned
+
+//# programmable --sender A --inputs 12 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# programmable --sender A --inputs 34 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> 1: TransferObjects([Result(0)], Input(1))
+
+//# publish --sender A
+module test::mod {
+    public struct Foo has key, store {
+        id: UID,
+    }
+
+    public fun new(ctx: &mut TxContext): Foo {
+        Foo { id: object::new(ctx) }
+    }
+}
+
+//# programmable --sender A --inputs @A
+//> 0: test::mod::new();
+//> 1: TransferObjects([Result(0)], Input(0))
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{A}"]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{A}", null, null, 2]
+}
+
+//# run-jsonrpc --cursors "@{obj_1_0}"
+{
+  "method": "suix_getOwnedObjects",
+  "params": ["@{A}", null, "@{cursor_0}", 2]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": [
+    "@{A}",
+    {
+      "filter": { "StructType": "@{test}::mod::Foo" },
+      "options": { "showType": true }
+    }
+  ]
+}
+
+//# transfer-object 4,0 --sender A --recipient B
+
+//# create-checkpoint
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": [
+    "@{A}",
+    {
+      "filter": { "StructType": "@{test}::mod::Foo" }
+    }
+  ]
+}
+
+//# run-jsonrpc
+{
+  "method": "suix_getOwnedObjects",
+  "params": [
+    "@{B}",
+    {
+      "options": { "showType": true, "showOwner": true }
+    }
+  ]
+}
@@ -83,7 +83,7 @@ impl OffchainCluster {
 
         let client_args = ClientArgs {
             local_ingestion_path: Some(config.data_ingestion_path.clone()),
-            remote_store_url: None,
+            ..Default::default()
         };
 
         // The example config includes every pipeline, and we configure its consistent range using
@@ -194,7 +194,11 @@ impl OffchainReader {
         // FIXME: It's not ideal that we have to enumerate pruners here -- if we forget to add one,
         // tests will hang indefinitely. Hopefully, by moving these over to the framework's pruning
         // support, we can avoid this complication.
-        const PRUNERS: &[&str] = &["coin_balance_buckets_pruner", "obj_info_pruner"];
+        const PRUNERS: &[&str] = &[
+            "coin_balance_buckets_pruner",
+            "obj_dynamic_fields_pruner",
+            "obj_info_pruner",
+        ];
 
         let latest: Option<i64> = watermarks::table
             .select(dsl::min(watermarks::checkpoint_hi_inclusive))
@@ -0,0 +1,27 @@
+-- Tracks the progress of a concurrent pipeline through ranges of checkpoints that are being
+-- indexed in parallel, ahead of its contiguous watermark in the `watermarks` table. Each range is
+-- merged into the pipeline's watermark once that watermark catches up with the start of the range,
+-- at which point the range's row is deleted.
+CREATE TABLE IF NOT EXISTS backfill_watermarks
+(
+    -- The pipeline this range belongs to.
+    pipeline                    TEXT          NOT NULL,
+    -- Inclusive lower checkpoint bound of the range.
+    range_lo                    BIGINT        NOT NULL,
+    -- Exclusive upper checkpoint bound of the range. The range that follows
+    -- the tip of the network is unbounded, and uses the maximum BIGINT.
+    range_hi                    BIGINT        NOT NULL,
+    -- Inclusive upper epoch bound for data written in this range.
+    epoch_hi_inclusive          BIGINT        NOT NULL,
+    -- Inclusive upper checkpoint bound for data written in this range. All
+    -- data between `range_lo` and this checkpoint has been written. Starts at
+    -- `range_lo - 1` when no data has been written yet.
+    checkpoint_hi_inclusive     BIGINT        NOT NULL,
+    -- Exclusive upper transaction sequence number bound for data written in
+    -- this range.
+    tx_hi                       BIGINT        NOT NULL,
+    -- Inclusive upper timestamp bound (in milliseconds) for data written in
+    -- this range.
+    timestamp_ms_hi_inclusive   BIGINT        NOT NULL,
+    PRIMARY KEY (pipeline, range_lo)
+);
@@ -0,0 +1,16 @@
+-- Checkpoints that a pipeline failed to index, and skipped over, because its
+-- failure policy allows it to advance its watermark past them. Rows remain
+-- until the checkpoint is successfully replayed.
+CREATE TABLE IF NOT EXISTS dead_letters
+(
+    pipeline            TEXT          NOT NULL,
+    -- The checkpoint that could not be indexed.
+    checkpoint          BIGINT        NOT NULL,
+    -- The stage of the pipeline that failed ('process' or 'commit').
+    stage               TEXT          NOT NULL,
+    -- The error from the last attempt at indexing the checkpoint.
+    error               TEXT          NOT NULL,
+    -- When the checkpoint was last dead-lettered.
+    recorded_at         TIMESTAMP     NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (pipeline, checkpoint)
+);
@@ -0,0 +1,299 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{future, iter, sync::Arc};
+
+use futures::stream::{self, StreamExt};
+use sui_types::full_checkpoint_content::CheckpointData;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::info;
+
+use crate::{
+    models::watermarks::{BackfillWatermark, CommitterWatermark},
+    store::Connection,
+};
+
+/// Describes how a concurrent pipeline splits its work between following the tip of the network
+/// and backfilling the checkpoints between its watermark and the point where it started following
+/// the tip.
+#[derive(Debug)]
+pub(crate) struct BackfillPlan {
+    /// Ranges that need to be backfilled, in order. Each range is served by its own ingestion
+    /// service.
+    pub backfill: Vec<BackfillWatermark<'static>>,
+
+    /// The range that follows the tip of the network, which is served by the indexer's main
+    /// ingestion service.
+    pub tip: BackfillWatermark<'static>,
+}
+
+impl BackfillPlan {
+    /// All the ranges in the plan (backfill ranges followed by the tip range), in order.
+    pub(crate) fn ranges(&self) -> Vec<BackfillWatermark<'static>> {
+        let mut ranges = self.backfill.clone();
+        ranges.push(self.tip.clone());
+        ranges
+    }
+}
+
+/// Figure out whether the pipeline `pipeline`, whose contiguous watermark is `watermark`, should
+/// backfill in parallel with following the tip, and if so, how. Any ranges that are newly planned
+/// are recorded in the database so that they can be resumed after a restart.
+///
+/// See [plan_ranges] for how the ranges are chosen.
+pub(crate) async fn plan<C: Connection>(
+    conn: &mut C,
+    pipeline: &'static str,
+    watermark: Option<&CommitterWatermark<'_>>,
+    first_checkpoint: Option<u64>,
+    backfill_tasks: usize,
+) -> anyhow::Result<Option<BackfillPlan>> {
+    let next_checkpoint = watermark.map_or(0, |w| w.checkpoint_hi_inclusive as u64 + 1);
+    let existing = conn.backfill_watermarks(pipeline).await?;
+
+    let Some(plan) = plan_ranges(
+        pipeline,
+        next_checkpoint,
+        existing,
+        first_checkpoint,
+        backfill_tasks,
+    ) else {
+        return Ok(None);
+    };
+
+    conn.insert_backfill_watermarks(&plan.ranges()).await?;
+
+    info!(
+        pipeline,
+        next_checkpoint,
+        tip = plan.tip.next_checkpoint(),
+        ranges = ?plan
+            .backfill
+            .iter()
+            .map(|r| (r.next_checkpoint(), r.hi()))
+            .collect::<Vec<_>>(),
+        "Backfilling in parallel with following the tip",
+    );
+
+    Ok(Some(plan))
+}
+
+/// Pure planning logic behind [plan].
+///
+/// - If ranges have already been recorded for this pipeline (`existing`), they are resumed,
+///   regardless of the other parameters. Ranges that the pipeline's watermark has already passed
+///   are dropped, and any gaps between the remaining ranges (and the pipeline's `next_checkpoint`)
+///   are filled with new ranges, so that the watermark can always make progress.
+///
+/// - Otherwise, if backfill tasks have been requested, and `first_checkpoint` is ahead of the
+///   pipeline's `next_checkpoint`, the pipeline starts following the tip from `first_checkpoint`,
+///   and the checkpoints in between are split evenly into `backfill_tasks` ranges.
+///
+/// - Otherwise no backfill is necessary.
+pub(crate) fn plan_ranges(
+    pipeline: &'static str,
+    next_checkpoint: u64,
+    existing: Vec<BackfillWatermark<'static>>,
+    first_checkpoint: Option<u64>,
+    backfill_tasks: usize,
+) -> Option<BackfillPlan> {
+    let mut existing: Vec<_> = existing
+        .into_iter()
+        .filter(|r| r.hi() > next_checkpoint)
+        .collect();
+
+    existing.sort_by_key(|r| r.range_lo);
+
+    // Only the last range can be the one following the tip.
+    let tip = if existing.last().is_some_and(|r| r.hi() == u64::MAX) {
+        existing.pop().unwrap()
+    } else if let Some(last) = existing.last() {
+        // The tip range has been merged into the pipeline's watermark, but some ranges after the
+        // watermark remain (this can only happen if ranges were removed manually). Follow the tip
+        // from the end of the last remaining range.
+        BackfillWatermark::initial(pipeline.into(), last.hi(), u64::MAX)
+    } else {
+        // Nothing to resume, so plan a fresh backfill, if one was requested.
+        let first = first_checkpoint.filter(|cp| backfill_tasks > 0 && *cp > next_checkpoint)?;
+        let tip = BackfillWatermark::initial(pipeline.into(), first, u64::MAX);
+        let backfill = split_range(pipeline, next_checkpoint, first, backfill_tasks);
+        return Some(BackfillPlan { backfill, tip });
+    };
+
+    // Fill any gaps between the pipeline's watermark and the ranges being resumed, as well as
+    // between the ranges themselves.
+    let mut backfill = vec![];
+    let mut cursor = next_checkpoint;
+    for range in existing {
+        if cursor < range.lo() {
+            backfill.push(BackfillWatermark::initial(
+                pipeline.into(),
+                cursor,
+                range.lo(),
+            ));
+        }
+
+        cursor = cursor.max(range.hi());
+        backfill.push(range);
+    }
+
+    if cursor < tip.lo() {
+        backfill.push(BackfillWatermark::initial(
+            pipeline.into(),
+            cursor,
+            tip.lo(),
+        ));
+    }
+
+    Some(BackfillPlan { backfill, tip })
+}
+
+/// Split the checkpoints from `lo` (inclusive) to `hi` (exclusive) into `tasks` contiguous ranges
+/// of roughly equal size. Returns fewer ranges if there are fewer checkpoints than tasks.
+fn split_range(
+    pipeline: &'static str,
+    lo: u64,
+    hi: u64,
+    tasks: usize,
+) -> Vec<BackfillWatermark<'static>> {
+    let total = hi.saturating_sub(lo);
+    let tasks = (tasks as u64).clamp(1, total.max(1));
+    let size = total.div_ceil(tasks);
+
+    (0..tasks)
+        .map(|i| (lo + i * size, (lo + (i + 1) * size).min(hi)))
+        .filter(|(lo, hi)| lo < hi)
+        .map(|(lo, hi)| BackfillWatermark::initial(pipeline.into(), lo, hi))
+        .collect()
+}
+
+/// Merge the checkpoints flowing out of the main ingestion service (`tip_rx`) and the backfill
+/// ingestion services (`backfill_rx`) into a single channel for the pipeline, and return the
+/// receiving end of that channel.
+///
+/// Checkpoints from the main ingestion service that come before `tip_lo` are dropped: They are
+/// the responsibility of the backfill ranges, and ingestion may start earlier than this point
+/// because of other pipelines.
+///
+/// The returned channel closes once all of the input channels have closed.
+pub(crate) fn merge(
+    tip_lo: u64,
+    tip_rx: mpsc::Receiver<Arc<CheckpointData>>,
+    backfill_rx: Vec<mpsc::Receiver<Arc<CheckpointData>>>,
+    buffer_size: usize,
+) -> mpsc::Receiver<Arc<CheckpointData>> {
+    let (tx, rx) = mpsc::channel(buffer_size);
+
+    let tip = ReceiverStream::new(tip_rx)
+        .filter(move |cp| future::ready(cp.checkpoint_summary.sequence_number >= tip_lo))
+        .boxed();
+
+    let backfill = backfill_rx
+        .into_iter()
+        .map(|rx| ReceiverStream::new(rx).boxed());
+
+    let mut inputs = stream::select_all(iter::once(tip).chain(backfill));
+
+    tokio::spawn(async move {
+        while let Some(checkpoint) = inputs.next().await {
+            if tx.send(checkpoint).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(plan: &BackfillPlan) -> Vec<(u64, u64, u64)> {
+        plan.ranges()
+            .iter()
+            .map(|r| (r.lo(), r.hi(), r.next_checkpoint()))
+            .collect()
+    }
+
+    #[test]
+    fn no_backfill_without_tasks() {
+        assert!(plan_ranges("p", 10, vec![], Some(100), 0).is_none());
+    }
+
+    #[test]
+    fn no_backfill_without_gap() {
+        assert!(plan_ranges("p", 10, vec![], Some(10), 4).is_none());
+        assert!(plan_ranges("p", 10, vec![], Some(5), 4).is_none());
+        assert!(plan_ranges("p", 10, vec![], None, 4).is_none());
+    }
+
+    #[test]
+    fn split_evenly() {
+        let plan = plan_ranges("p", 10, vec![], Some(110), 4).unwrap();
+        assert_eq!(
+            ranges(&plan),
+            vec![
+                (10, 35, 10),
+                (35, 60, 35),
+                (60, 85, 60),
+                (85, 110, 85),
+                (110, u64::MAX, 110),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_unevenly() {
+        let plan = plan_ranges("p", 0, vec![], Some(10), 3).unwrap();
+        assert_eq!(
+            ranges(&plan),
+            vec![(0, 4, 0), (4, 8, 4), (8, 10, 8), (10, u64::MAX, 10)]
+        );
+    }
+
+    #[test]
+    fn more_tasks_than_checkpoints() {
+        let plan = plan_ranges("p", 0, vec![], Some(2), 8).unwrap();
+        assert_eq!(ranges(&plan), vec![(0, 1, 0), (1, 2, 1), (2, u64::MAX, 2)]);
+    }
+
+    #[test]
+    fn resume_existing_ranges() {
+        let mut r0 = BackfillWatermark::initial("p".into(), 10, 20);
+        r0.checkpoint_hi_inclusive = 14;
+        let r1 = BackfillWatermark::initial("p".into(), 20, 30);
+        let mut tip = BackfillWatermark::initial("p".into(), 30, u64::MAX);
+        tip.checkpoint_hi_inclusive = 50;
+
+        // The first checkpoint and number of tasks are ignored when resuming.
+        let plan = plan_ranges("p", 10, vec![tip, r1, r0], Some(1000), 1).unwrap();
+        assert_eq!(
+            ranges(&plan),
+            vec![(10, 20, 15), (20, 30, 20), (30, u64::MAX, 51)]
+        );
+    }
+
+    #[test]
+    fn resume_fills_gaps() {
+        // The range from 10 to 20 has already been merged, but the watermark was not updated.
+        let r1 = BackfillWatermark::initial("p".into(), 20, 30);
+        let tip = BackfillWatermark::initial("p".into(), 40, u64::MAX);
+
+        let plan = plan_ranges("p", 5, vec![r1, tip], None, 0).unwrap();
+        assert_eq!(
+            ranges(&plan),
+            vec![(5, 20, 5), (20, 30, 20), (30, 40, 30), (40, u64::MAX, 40)]
+        );
+    }
+
+    #[test]
+    fn resume_drops_passed_ranges() {
+        let r0 = BackfillWatermark::initial("p".into(), 0, 10);
+        let tip = BackfillWatermark::initial("p".into(), 10, u64::MAX);
+
+        let plan = plan_ranges("p", 10, vec![r0, tip], None, 0).unwrap();
+        assert_eq!(ranges(&plan), vec![(10, u64::MAX, 10)]);
+    }
+}
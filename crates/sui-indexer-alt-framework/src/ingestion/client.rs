@@ -3,6 +3,9 @@
 
 use crate::ingestion::local_client::LocalIngestionClient;
 use crate::ingestion::remote_client::RemoteIngestionClient;
+use crate::ingestion::rpc_client::RpcIngestionClient;
+use crate::ingestion::store_client::StoreIngestionClient;
+use crate::ingestion::ClientArgs;
 use crate::ingestion::Error as IngestionError;
 use crate::ingestion::Result as IngestionResult;
 use crate::metrics::CheckpointLagMetricReporter;
@@ -10,9 +13,11 @@ use crate::metrics::IndexerMetrics;
 use backoff::backoff::Constant;
 use backoff::Error as BE;
 use backoff::ExponentialBackoff;
+use object_store::DynObjectStore;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_storage::blob::Blob;
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio_util::bytes::Bytes;
@@ -23,8 +28,16 @@ use url::Url;
 /// Wait at most this long between retries for transient errors.
 const MAX_TRANSIENT_RETRY_INTERVAL: Duration = Duration::from_secs(60);
 
+/// A source of checkpoint data for the ingestion service. Implementations are only responsible for
+/// fetching a single checkpoint, and classifying any failure as transient or permanent --
+/// retries, deserialization and metrics are handled by [IngestionClient].
 #[async_trait::async_trait]
-pub(crate) trait IngestionClientTrait: Send + Sync {
+pub trait IngestionClientTrait: Send + Sync {
+    /// Start any background work the client relies on (e.g. maintaining a subscription). Called
+    /// once by the ingestion service before it starts fetching checkpoints. Background tasks
+    /// should stop when `cancel` is triggered.
+    fn start(&self, _cancel: CancellationToken) {}
+
     async fn fetch(&self, checkpoint: u64) -> FetchResult;
 }
 
@@ -42,7 +55,19 @@ pub enum FetchError {
     },
 }
 
-pub type FetchResult = Result<Bytes, FetchError>;
+pub type FetchResult = Result<FetchData, FetchError>;
+
+/// The payload returned by an [IngestionClientTrait] implementation.
+#[allow(clippy::large_enum_variant)]
+pub enum FetchData {
+    /// Checkpoint data encoded as a [Blob], as found in checkpoint buckets and local ingestion
+    /// directories.
+    Raw(Bytes),
+
+    /// Checkpoint data that has already been decoded by the client (e.g. because it was received
+    /// over a typed RPC).
+    CheckpointData(CheckpointData),
+}
 
 #[derive(Clone)]
 pub struct IngestionClient {
@@ -53,6 +78,34 @@ pub struct IngestionClient {
 }
 
 impl IngestionClient {
+    /// Create a client for the ingestion source described by `args`.
+    pub(crate) fn new(args: ClientArgs, metrics: Arc<IndexerMetrics>) -> IngestionResult<Self> {
+        // TODO: Potentially support a hybrid mode where we can fetch from both local and remote.
+        let ClientArgs {
+            remote_store_url,
+            local_ingestion_path,
+            remote_store_s3,
+            remote_store_gcs,
+            rpc_api_url,
+            rpc_username,
+            rpc_password,
+        } = args;
+
+        Ok(if let Some(url) = remote_store_url {
+            Self::new_remote(url, metrics)?
+        } else if let Some(path) = local_ingestion_path {
+            Self::new_local(path, metrics)
+        } else if let Some(bucket) = remote_store_s3 {
+            Self::new_store_from_config(ObjectStoreType::S3, bucket, metrics)?
+        } else if let Some(bucket) = remote_store_gcs {
+            Self::new_store_from_config(ObjectStoreType::GCS, bucket, metrics)?
+        } else if let Some(url) = rpc_api_url {
+            Self::new_rpc(url, rpc_username, rpc_password, metrics)?
+        } else {
+            return Err(IngestionError::NoSource);
+        })
+    }
+
     pub(crate) fn new_remote(url: Url, metrics: Arc<IndexerMetrics>) -> IngestionResult<Self> {
         let client = Arc::new(RemoteIngestionClient::new(url)?);
         Ok(Self::new_impl(client, metrics))
@@ -63,6 +116,51 @@ impl IngestionClient {
         Self::new_impl(client, metrics)
     }
 
+    /// Fetch checkpoints from an object store (S3, GCS, Azure, or a local file system), where
+    /// checkpoints are stored at the root of the store as `{sequence_number}.chk`.
+    pub(crate) fn new_store(store: Arc<DynObjectStore>, metrics: Arc<IndexerMetrics>) -> Self {
+        let client = Arc::new(StoreIngestionClient::new(store));
+        Self::new_impl(client, metrics)
+    }
+
+    /// Fetch checkpoints from a full node's RPC API, subscribing to its checkpoint stream to
+    /// receive checkpoints as soon as they are executed.
+    pub(crate) fn new_rpc(
+        url: Url,
+        username: Option<String>,
+        password: Option<String>,
+        metrics: Arc<IndexerMetrics>,
+    ) -> IngestionResult<Self> {
+        let client = Arc::new(RpcIngestionClient::new(url, username, password)?);
+        Ok(Self::new_impl(client, metrics))
+    }
+
+    /// Fetch checkpoints using a custom implementation of [IngestionClientTrait].
+    pub(crate) fn new_custom(
+        client: Arc<dyn IngestionClientTrait>,
+        metrics: Arc<IndexerMetrics>,
+    ) -> Self {
+        Self::new_impl(client, metrics)
+    }
+
+    fn new_store_from_config(
+        object_store: ObjectStoreType,
+        bucket: String,
+        metrics: Arc<IndexerMetrics>,
+    ) -> IngestionResult<Self> {
+        let store = ObjectStoreConfig {
+            object_store: Some(object_store),
+            bucket: Some(bucket),
+            object_store_connection_limit: 20,
+            no_sign_request: false,
+            ..Default::default()
+        }
+        .make()
+        .map_err(IngestionError::ObjectStoreError)?;
+
+        Ok(Self::new_store(store, metrics))
+    }
+
     fn new_impl(client: Arc<dyn IngestionClientTrait>, metrics: Arc<IndexerMetrics>) -> Self {
         let checkpoint_lag_reporter = CheckpointLagMetricReporter::new(
             metrics.ingested_checkpoint_timestamp_lag.clone(),
@@ -76,6 +174,11 @@ impl IngestionClient {
         }
     }
 
+    /// Start the client's background work, if it has any (see [IngestionClientTrait::start]).
+    pub(crate) fn start(&self, cancel: CancellationToken) {
+        self.client.start(cancel);
+    }
+
     /// Fetch checkpoint data by sequence number.
     ///
     /// This function behaves like `IngestionClient::fetch`, but will repeatedly retry the fetch if
@@ -133,7 +236,7 @@ impl IngestionClient {
                     return Err(BE::permanent(IngestionError::Cancelled));
                 }
 
-                let bytes = client.fetch(checkpoint).await.map_err(|err| match err {
+                let fetched = client.fetch(checkpoint).await.map_err(|err| match err {
                     FetchError::NotFound => BE::permanent(IngestionError::NotFound(checkpoint)),
                     FetchError::Permanent(error) => {
                         BE::permanent(IngestionError::FetchError(checkpoint, error))
@@ -145,14 +248,20 @@ impl IngestionClient {
                     ),
                 })?;
 
-                self.metrics.total_ingested_bytes.inc_by(bytes.len() as u64);
-                let data: CheckpointData = Blob::from_bytes(&bytes).map_err(|e| {
-                    self.metrics.inc_retry(
-                        checkpoint,
-                        "deserialization",
-                        IngestionError::DeserializationError(checkpoint, e),
-                    )
-                })?;
+                let data: CheckpointData = match fetched {
+                    FetchData::Raw(bytes) => {
+                        self.metrics.total_ingested_bytes.inc_by(bytes.len() as u64);
+                        Blob::from_bytes(&bytes).map_err(|e| {
+                            self.metrics.inc_retry(
+                                checkpoint,
+                                "deserialization",
+                                IngestionError::DeserializationError(checkpoint, e),
+                            )
+                        })?
+                    }
+
+                    FetchData::CheckpointData(data) => data,
+                };
 
                 Ok(data)
             }
@@ -17,6 +17,17 @@ pub enum Error {
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
 
+    #[error("Failed to create object store client: {0}")]
+    ObjectStoreError(#[source] anyhow::Error),
+
+    #[error(transparent)]
+    RpcClientError(#[from] tonic::Status),
+
+    #[error(
+        "No ingestion source provided: one of the ingestion sources in ClientArgs is required"
+    )]
+    NoSource,
+
     #[error("No subscribers for ingestion service")]
     NoSubscribers,
 
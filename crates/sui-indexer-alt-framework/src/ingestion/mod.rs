@@ -18,7 +18,6 @@ use crate::ingestion::broadcaster::broadcaster;
 use crate::ingestion::client::IngestionClient;
 use crate::ingestion::error::{Error, Result};
 use crate::ingestion::regulator::regulator;
-use crate::metrics::IndexerMetrics;
 
 mod broadcaster;
 pub mod client;
@@ -26,10 +25,12 @@ pub mod error;
 mod local_client;
 mod regulator;
 mod remote_client;
+mod rpc_client;
+mod store_client;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 
-#[derive(clap::Args, Clone, Debug)]
+#[derive(clap::Args, Clone, Debug, Default)]
 pub struct ClientArgs {
     /// Remote Store to fetch checkpoints from.
     #[clap(long, required = true, group = "source")]
@@ -39,6 +40,29 @@ pub struct ClientArgs {
     /// If both remote_store_url and local_ingestion_path are provided, remote_store_url will be used.
     #[clap(long, required = true, group = "source")]
     pub local_ingestion_path: Option<PathBuf>,
+
+    /// Name of an S3 bucket to fetch checkpoints from. Credentials are picked up from the
+    /// environment or the instance metadata service.
+    #[clap(long, required = true, group = "source")]
+    pub remote_store_s3: Option<String>,
+
+    /// Name of a GCS bucket to fetch checkpoints from. Credentials are picked up from the
+    /// environment.
+    #[clap(long, required = true, group = "source")]
+    pub remote_store_gcs: Option<String>,
+
+    /// URL of a full node's RPC API to stream checkpoints from. Checkpoints are pushed to the
+    /// indexer as soon as the node executes them, rather than being polled for.
+    #[clap(long, required = true, group = "source")]
+    pub rpc_api_url: Option<Url>,
+
+    /// Username for basic authentication against the RPC API, if required.
+    #[clap(long, requires = "rpc_api_url")]
+    pub rpc_username: Option<String>,
+
+    /// Password for basic authentication against the RPC API, if required.
+    #[clap(long, requires = "rpc_username")]
+    pub rpc_password: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -69,33 +93,25 @@ impl IngestionConfig {
 }
 
 impl IngestionService {
+    /// Create an ingestion service that fetches checkpoints using `client`.
+    ///
     /// TODO: If we want to expose this as part of the framework, so people can run just an
     /// ingestion service, we will need to split `IngestionMetrics` out from `IndexerMetrics`.
     pub(crate) fn new(
-        args: ClientArgs,
+        client: IngestionClient,
         config: IngestionConfig,
-        metrics: Arc<IndexerMetrics>,
         cancel: CancellationToken,
-    ) -> Result<Self> {
-        // TODO: Potentially support a hybrid mode where we can fetch from both local and remote.
-        let client = if let Some(url) = args.remote_store_url.as_ref() {
-            IngestionClient::new_remote(url.clone(), metrics.clone())?
-        } else if let Some(path) = args.local_ingestion_path.as_ref() {
-            IngestionClient::new_local(path.clone(), metrics.clone())
-        } else {
-            panic!("Either remote_store_url or local_ingestion_path must be provided");
-        };
-
+    ) -> Self {
         let subscribers = Vec::new();
         let (ingest_hi_tx, ingest_hi_rx) = mpsc::unbounded_channel();
-        Ok(Self {
+        Self {
             config,
             client,
             ingest_hi_tx,
             ingest_hi_rx,
             subscribers,
             cancel,
-        })
+        }
     }
 
     /// The client this service uses to fetch checkpoints.
@@ -155,6 +171,8 @@ impl IngestionService {
             return Err(Error::NoSubscribers);
         }
 
+        client.start(cancel.clone());
+
         let (checkpoint_tx, checkpoint_rx) = mpsc::channel(config.ingest_concurrency);
 
         let regulator = regulator(
@@ -201,19 +219,14 @@ mod tests {
         cancel: CancellationToken,
     ) -> IngestionService {
         IngestionService::new(
-            ClientArgs {
-                remote_store_url: Some(Url::parse(&uri).unwrap()),
-                local_ingestion_path: None,
-            },
+            IngestionClient::new_remote(Url::parse(&uri).unwrap(), test_metrics()).unwrap(),
             IngestionConfig {
                 checkpoint_buffer_size,
                 ingest_concurrency,
                 ..Default::default()
             },
-            test_metrics(),
             cancel,
         )
-        .unwrap()
     }
 
     async fn test_subscriber(
@@ -255,6 +268,13 @@ mod tests {
         assert!(matches!(err, Error::NoSubscribers));
     }
 
+    /// Creating a client without any ingestion source is an error, rather than a panic.
+    #[test]
+    fn fail_on_no_source() {
+        let client = IngestionClient::new(ClientArgs::default(), test_metrics());
+        assert!(matches!(client, Err(Error::NoSource)));
+    }
+
     /// The subscriber has no effective limit, and the mock server will always return checkpoint
     /// information, but the ingestion service can still be stopped using the cancellation token.
     #[tokio::test]
@@ -0,0 +1,209 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use sui_rpc_api::client::{AuthInterceptor, Client};
+use sui_types::full_checkpoint_content::CheckpointData;
+use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::ingestion::client::{FetchData, FetchError, FetchResult, IngestionClientTrait};
+use crate::ingestion::Result as IngestionResult;
+
+/// Maximum number of checkpoints received from the subscription that are held on to while waiting
+/// to be fetched. If the buffer fills up (because ingestion is lagging behind the node), the
+/// oldest checkpoints are dropped, and will be fetched by sequence number instead.
+const MAX_BUFFERED_CHECKPOINTS: usize = 1000;
+
+/// Time to wait before re-subscribing to the checkpoint stream after it fails or closes.
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(1);
+
+type Buffer = Mutex<BTreeMap<u64, CheckpointData>>;
+
+/// Ingests checkpoints from a full node's RPC API.
+///
+/// Once started, the client subscribes to the node's checkpoint stream in a background task, and
+/// buffers checkpoints as they are pushed to it, so that requests for checkpoints at the tip of
+/// the network are served as soon as the node has executed them, without waiting for the next
+/// polling interval. Requests for checkpoints that are not in the buffer fall back to fetching the
+/// checkpoint from the node by its sequence number.
+pub(crate) struct RpcIngestionClient {
+    client: Client,
+    buffer: Arc<Buffer>,
+}
+
+impl RpcIngestionClient {
+    pub(crate) fn new(
+        url: Url,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> IngestionResult<Self> {
+        let mut client = Client::new(url.as_str())?;
+        if let Some(username) = username {
+            client = client.with_auth(AuthInterceptor::basic(username, password));
+        }
+
+        Ok(Self {
+            client,
+            buffer: Arc::new(Mutex::new(BTreeMap::new())),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl IngestionClientTrait for RpcIngestionClient {
+    /// Subscribe to the node's checkpoint stream in a background task, which runs until `cancel`
+    /// is triggered or the client is dropped.
+    fn start(&self, cancel: CancellationToken) {
+        tokio::spawn(subscribe(
+            self.client.clone(),
+            Arc::downgrade(&self.buffer),
+            cancel,
+        ));
+    }
+
+    /// Fetch a checkpoint, from the subscription buffer if it has already been pushed to us, or
+    /// from the node otherwise.
+    ///
+    /// The node reporting that it does not have the checkpoint is treated as
+    /// [FetchError::NotFound] (it has not been executed yet, or it has been pruned), and all other
+    /// RPC failures are treated as transient.
+    async fn fetch(&self, checkpoint: u64) -> FetchResult {
+        let buffered = self.buffer.lock().unwrap().remove(&checkpoint);
+        if let Some(data) = buffered {
+            return Ok(FetchData::CheckpointData(data));
+        }
+
+        match self.client.get_full_checkpoint(checkpoint).await {
+            Ok(data) => Ok(FetchData::CheckpointData(data)),
+
+            Err(status) if status.code() == tonic::Code::NotFound => {
+                debug!(checkpoint, "Checkpoint not found");
+                Err(FetchError::NotFound)
+            }
+
+            Err(status) => Err(FetchError::Transient {
+                reason: "rpc_error",
+                error: status.into(),
+            }),
+        }
+    }
+}
+
+/// Keep the subscription to the node's checkpoint stream alive, adding checkpoints to `buffer` as
+/// they arrive. The task exits once `cancel` is triggered, or the client that owns the buffer has
+/// been dropped (noticed the next time a checkpoint arrives, or the subscription needs to be
+/// re-established).
+async fn subscribe(client: Client, buffer: Weak<Buffer>, cancel: CancellationToken) {
+    loop {
+        let subscription = tokio::select! {
+            _ = cancel.cancelled() => return,
+            subscription = client.subscribe_checkpoints() => subscription,
+        };
+
+        match subscription {
+            Ok(stream) => {
+                tokio::pin!(stream);
+                loop {
+                    let item = tokio::select! {
+                        _ = cancel.cancelled() => return,
+                        item = stream.next() => item,
+                    };
+
+                    let Some(item) = item else {
+                        break;
+                    };
+
+                    let Some(buffer) = buffer.upgrade() else {
+                        return;
+                    };
+
+                    match item {
+                        Ok((cursor, data)) => {
+                            let mut buffer = buffer.lock().unwrap();
+                            buffer.insert(cursor, data);
+                            while buffer.len() > MAX_BUFFERED_CHECKPOINTS {
+                                buffer.pop_first();
+                            }
+                        }
+
+                        Err(status) => {
+                            warn!(%status, "Checkpoint subscription failed, re-subscribing");
+                            break;
+                        }
+                    }
+                }
+            }
+
+            Err(status) => {
+                warn!(%status, "Failed to subscribe to checkpoints");
+            }
+        }
+
+        if buffer.strong_count() == 0 {
+            return;
+        }
+
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(RESUBSCRIBE_INTERVAL) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_storage::blob::Blob;
+
+    use crate::ingestion::client::IngestionClient;
+    use crate::ingestion::test_utils::test_checkpoint_data;
+    use crate::metrics::tests::test_metrics;
+
+    use super::*;
+
+    /// Checkpoints that have been pushed to the client by its subscription are served from its
+    /// buffer, without needing to reach out to the node.
+    #[tokio::test]
+    async fn fetch_from_subscription_buffer() {
+        // Nothing is listening on this port, so any request to the node would fail.
+        let rpc =
+            RpcIngestionClient::new(Url::parse("http://localhost:1").unwrap(), None, None).unwrap();
+
+        let data: CheckpointData = Blob::from_bytes(&test_checkpoint_data(42)).unwrap();
+        rpc.buffer.lock().unwrap().insert(42, data);
+
+        let client = IngestionClient::new_custom(Arc::new(rpc), test_metrics());
+        let checkpoint = client.fetch(42, &CancellationToken::new()).await.unwrap();
+        assert_eq!(42, checkpoint.checkpoint_summary.sequence_number);
+    }
+
+    /// Creating the client is pure -- the subscription task is only spawned once it is started,
+    /// and stops when it is cancelled.
+    #[tokio::test]
+    async fn subscription_starts_and_stops() {
+        let rpc =
+            RpcIngestionClient::new(Url::parse("http://localhost:1").unwrap(), None, None).unwrap();
+
+        // Only the client holds on to its buffer until the subscription is started.
+        assert_eq!(1, Arc::strong_count(&rpc.buffer));
+        assert_eq!(0, Arc::weak_count(&rpc.buffer));
+
+        let cancel = CancellationToken::new();
+        rpc.start(cancel.clone());
+        assert_eq!(1, Arc::weak_count(&rpc.buffer));
+
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while Arc::weak_count(&rpc.buffer) > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Subscription task did not stop after cancellation");
+    }
+}
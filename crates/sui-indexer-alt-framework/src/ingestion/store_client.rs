@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use object_store::{path::Path, DynObjectStore, Error as ObjectStoreError};
+use tracing::{debug, error};
+
+use crate::ingestion::client::{FetchData, FetchError, FetchResult, IngestionClientTrait};
+
+pub(crate) struct StoreIngestionClient {
+    store: Arc<DynObjectStore>,
+}
+
+impl StoreIngestionClient {
+    pub(crate) fn new(store: Arc<DynObjectStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IngestionClientTrait for StoreIngestionClient {
+    /// Fetch a checkpoint from the object store.
+    ///
+    /// Missing objects are reported as [FetchError::NotFound], errors that indicate the store is
+    /// misconfigured (unsupported operations, invalid paths or configuration) are treated as
+    /// permanent, and all other errors are assumed to be transient (network issues, throttling,
+    /// server errors, etc).
+    async fn fetch(&self, checkpoint: u64) -> FetchResult {
+        let path = Path::from(format!("{checkpoint}.chk"));
+
+        let response = match self.store.get(&path).await {
+            Ok(response) => response,
+
+            Err(ObjectStoreError::NotFound { .. }) => {
+                debug!(checkpoint, "Checkpoint not found");
+                return Err(FetchError::NotFound);
+            }
+
+            Err(
+                e @ (ObjectStoreError::InvalidPath { .. }
+                | ObjectStoreError::NotSupported { .. }
+                | ObjectStoreError::NotImplemented
+                | ObjectStoreError::UnknownConfigurationKey { .. }),
+            ) => {
+                error!(checkpoint, "Permanent error, giving up!");
+                return Err(FetchError::Permanent(e.into()));
+            }
+
+            Err(e) => {
+                return Err(FetchError::Transient {
+                    reason: "object_store",
+                    error: e.into(),
+                })
+            }
+        };
+
+        // Failure to read the whole payload is treated as transient, in the same way as it is for
+        // the remote (HTTP) client.
+        let bytes = response.bytes().await.map_err(|e| FetchError::Transient {
+            reason: "bytes",
+            error: e.into(),
+        })?;
+
+        Ok(FetchData::Raw(bytes))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use object_store::local::LocalFileSystem;
+    use sui_storage::blob::{Blob, BlobEncoding};
+    use tokio_util::sync::CancellationToken;
+
+    use crate::ingestion::client::IngestionClient;
+    use crate::ingestion::error::Error;
+    use crate::ingestion::test_utils::test_checkpoint_data;
+    use crate::metrics::tests::test_metrics;
+
+    fn store_test_client(path: &std::path::Path) -> IngestionClient {
+        let store = LocalFileSystem::new_with_prefix(path).unwrap();
+        IngestionClient::new_store(std::sync::Arc::new(store), test_metrics())
+    }
+
+    #[tokio::test]
+    async fn store_test_fetch() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let test_checkpoint = test_checkpoint_data(1);
+        tokio::fs::write(tempdir.path().join("1.chk"), &test_checkpoint)
+            .await
+            .unwrap();
+
+        let client = store_test_client(tempdir.path());
+        let checkpoint = client.fetch(1, &CancellationToken::new()).await.unwrap();
+
+        assert_eq!(
+            Blob::encode(&*checkpoint, BlobEncoding::Bcs)
+                .unwrap()
+                .to_bytes(),
+            test_checkpoint
+        );
+    }
+
+    #[tokio::test]
+    async fn store_fail_on_not_found() {
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let client = store_test_client(tempdir.path());
+        let error = client
+            .fetch(42, &CancellationToken::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::NotFound(42)));
+    }
+}
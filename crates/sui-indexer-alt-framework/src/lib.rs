@@ -1,23 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeSet, sync::Arc};
+use std::{collections::BTreeSet, ops::Range, sync::Arc};
 
 use anyhow::{ensure, Context, Result};
+use backfill::BackfillPlan;
 use diesel::{
     migration::{self, Migration, MigrationSource},
     pg::Pg,
 };
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
-use ingestion::{client::IngestionClient, ClientArgs, IngestionConfig, IngestionService};
+use ingestion::{
+    client::{IngestionClient, IngestionClientTrait},
+    ClientArgs, IngestionConfig, IngestionService,
+};
 use metrics::IndexerMetrics;
-use models::watermarks::{CommitterWatermark, PrunerWatermark};
+use models::watermarks::CommitterWatermark;
 use pipeline::{
     concurrent::{self, ConcurrentConfig},
     sequential::{self, SequentialConfig},
     Processor,
 };
 use prometheus::Registry;
+use store::{Connection, Store};
 use sui_indexer_alt_metrics::db::DbConnectionStatsCollector;
 use sui_pg_db::{temp::TempDb, Db, DbArgs};
 use task::graceful_shutdown;
@@ -26,12 +31,14 @@ use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
+mod backfill;
 pub mod handlers;
 pub mod ingestion;
 pub(crate) mod metrics;
 pub mod models;
 pub mod pipeline;
 pub mod schema;
+pub mod store;
 pub mod task;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
@@ -58,11 +65,21 @@ pub struct IndexerArgs {
     /// Don't write to the watermark tables for concurrent pipelines.
     #[arg(long)]
     pub skip_watermark: bool,
+
+    /// Number of ranges to backfill in parallel, for concurrent pipelines whose watermark is
+    /// behind `--first-checkpoint`. Instead of rejecting the override, these pipelines will follow
+    /// the tip of the network from `--first-checkpoint`, while the checkpoints between their
+    /// watermark and that point are split into this many ranges, and each range is ingested and
+    /// committed concurrently. The pipeline's watermark advances as ranges complete, and progress
+    /// through each range survives restarts.
+    #[arg(long, default_value_t = 0)]
+    pub backfill_tasks: usize,
 }
 
-pub struct Indexer {
-    /// Connection pool to the database.
-    db: Db,
+pub struct Indexer<S: Store = Db> {
+    /// The store that pipelines write their data and watermarks to (by default, a connection pool
+    /// to a Postgres database).
+    store: S,
 
     /// Prometheus Metrics.
     metrics: Arc<IndexerMetrics>,
@@ -79,6 +96,17 @@ pub struct Indexer {
     /// Don't write to the watermark tables for concurrent pipelines.
     skip_watermark: bool,
 
+    /// Number of ranges that concurrent pipelines can split a backfill into.
+    backfill_tasks: usize,
+
+    /// Configuration for the ingestion service, also used to configure the ingestion services for
+    /// backfill ranges.
+    ingestion_config: IngestionConfig,
+
+    /// Ingestion services for ranges that are being backfilled, along with the range of
+    /// checkpoints they are responsible for. These are started when the indexer is run.
+    backfills: Vec<(IngestionService, Range<u64>)>,
+
     /// Optional filter for pipelines to run. If `None`, all pipelines added to the indexer will
     /// run. Any pipelines that are present in this filter but not added to the indexer will yield
     /// a warning when the indexer is run.
@@ -100,7 +128,7 @@ pub struct Indexer {
     handles: Vec<JoinHandle<()>>,
 }
 
-impl Indexer {
+impl Indexer<Db> {
     /// Create a new instance of the indexer framework. `db_args`, `indexer_args,`, `client_args`,
     /// and `ingestion_config` contain configurations for the following, respectively:
     ///
@@ -125,13 +153,55 @@ impl Indexer {
         registry: &Registry,
         cancel: CancellationToken,
     ) -> Result<Self> {
-        let IndexerArgs {
-            first_checkpoint,
-            last_checkpoint,
-            pipeline,
-            skip_watermark,
-        } = indexer_args;
+        Self::new_impl(
+            db_args,
+            indexer_args,
+            ingestion_config,
+            migrations,
+            registry,
+            cancel,
+            |metrics| IngestionClient::new(client_args, metrics),
+        )
+        .await
+    }
 
+    /// Like [Self::new], but instead of configuring where to download checkpoints from using
+    /// `ClientArgs`, checkpoints are fetched from a custom ingestion source, `client`. Retries,
+    /// deserialization and metrics are handled by the framework, as they are for the built-in
+    /// sources.
+    pub async fn new_with_ingestion_client(
+        db_args: DbArgs,
+        indexer_args: IndexerArgs,
+        client: Arc<dyn IngestionClientTrait>,
+        ingestion_config: IngestionConfig,
+        migrations: &'static EmbeddedMigrations,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        Self::new_impl(
+            db_args,
+            indexer_args,
+            ingestion_config,
+            migrations,
+            registry,
+            cancel,
+            |metrics| Ok(IngestionClient::new_custom(client, metrics)),
+        )
+        .await
+    }
+
+    async fn new_impl<F>(
+        db_args: DbArgs,
+        indexer_args: IndexerArgs,
+        ingestion_config: IngestionConfig,
+        migrations: &'static EmbeddedMigrations,
+        registry: &Registry,
+        cancel: CancellationToken,
+        ingestion_client: F,
+    ) -> Result<Self>
+    where
+        F: FnOnce(Arc<IndexerMetrics>) -> ingestion::error::Result<IngestionClient>,
+    {
         let db = Db::for_write(db_args)
             .await
             .context("Failed to connect to database")?;
@@ -141,36 +211,19 @@ impl Indexer {
             .await
             .context("Failed to run pending migrations")?;
 
-        let metrics = IndexerMetrics::new(registry);
         registry.register(Box::new(DbConnectionStatsCollector::new(
             Some("indexer_db"),
             db.clone(),
         )))?;
 
-        let ingestion_service = IngestionService::new(
-            client_args,
-            ingestion_config,
-            metrics.clone(),
-            cancel.clone(),
-        )?;
-
-        Ok(Self {
+        Self::with_store(
             db,
-            metrics,
-            ingestion_service,
-            first_checkpoint,
-            last_checkpoint,
-            skip_watermark,
-            enabled_pipelines: if pipeline.is_empty() {
-                None
-            } else {
-                Some(pipeline.into_iter().collect())
-            },
-            added_pipelines: BTreeSet::new(),
+            indexer_args,
+            ingestion_config,
+            registry,
             cancel,
-            first_checkpoint_from_watermark: u64::MAX,
-            handles: vec![],
-        })
+            ingestion_client,
+        )
     }
 
     pub async fn new_for_testing(migrations: &'static EmbeddedMigrations) -> (Self, TempDb) {
@@ -180,8 +233,8 @@ impl Indexer {
             db_args,
             IndexerArgs::default(),
             ClientArgs {
-                remote_store_url: None,
                 local_ingestion_path: Some(tempdir().unwrap().into_path()),
+                ..Default::default()
             },
             IngestionConfig::default(),
             migrations,
@@ -195,7 +248,106 @@ impl Indexer {
 
     /// The database connection pool used by the indexer.
     pub fn db(&self) -> &Db {
-        &self.db
+        &self.store
+    }
+
+    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
+    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
+    /// the database's schema is up-to-date for both the indexer framework and the specific
+    /// indexer.
+    pub fn migrations(
+        migrations: &'static EmbeddedMigrations,
+    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
+        struct Migrations(&'static EmbeddedMigrations);
+        impl MigrationSource<Pg> for Migrations {
+            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
+                let mut migrations = MIGRATIONS.migrations()?;
+                migrations.extend(self.0.migrations()?);
+                Ok(migrations)
+            }
+        }
+
+        Migrations(migrations)
+    }
+}
+
+impl<S: Store> Indexer<S> {
+    /// Create a new instance of the indexer framework that writes to `store`, rather than a
+    /// Postgres database. `indexer_args`, `client_args` and `ingestion_config` are interpreted in
+    /// the same way as for [Indexer::new].
+    ///
+    /// Unlike [Indexer::new], no migrations are run: the store is expected to be ready to accept
+    /// writes from any pipelines that are added to the indexer.
+    pub fn new_with_store(
+        store: S,
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        registry: &Registry,
+        cancel: CancellationToken,
+    ) -> Result<Self> {
+        Self::with_store(
+            store,
+            indexer_args,
+            ingestion_config,
+            registry,
+            cancel,
+            |metrics| IngestionClient::new(client_args, metrics),
+        )
+    }
+
+    fn with_store<F>(
+        store: S,
+        indexer_args: IndexerArgs,
+        ingestion_config: IngestionConfig,
+        registry: &Registry,
+        cancel: CancellationToken,
+        ingestion_client: F,
+    ) -> Result<Self>
+    where
+        F: FnOnce(Arc<IndexerMetrics>) -> ingestion::error::Result<IngestionClient>,
+    {
+        let IndexerArgs {
+            first_checkpoint,
+            last_checkpoint,
+            pipeline,
+            skip_watermark,
+            backfill_tasks,
+        } = indexer_args;
+
+        let metrics = IndexerMetrics::new(registry);
+
+        let ingestion_service = IngestionService::new(
+            ingestion_client(metrics.clone())?,
+            ingestion_config.clone(),
+            cancel.clone(),
+        );
+
+        Ok(Self {
+            store,
+            metrics,
+            ingestion_service,
+            first_checkpoint,
+            last_checkpoint,
+            skip_watermark,
+            backfill_tasks,
+            ingestion_config,
+            backfills: vec![],
+            enabled_pipelines: if pipeline.is_empty() {
+                None
+            } else {
+                Some(pipeline.into_iter().collect())
+            },
+            added_pipelines: BTreeSet::new(),
+            cancel,
+            first_checkpoint_from_watermark: u64::MAX,
+            handles: vec![],
+        })
+    }
+
+    /// The store that pipelines added to this indexer write to.
+    pub fn store(&self) -> &S {
+        &self.store
     }
 
     /// The ingestion client used by the indexer to fetch checkpoints.
@@ -209,30 +361,72 @@ impl Indexer {
     /// Concurrent pipelines commit checkpoint data out-of-order to maximise throughput, and they
     /// keep the watermark table up-to-date with the highest point they can guarantee all data
     /// exists for, for their pipeline.
-    pub async fn concurrent_pipeline<H: concurrent::Handler + Send + Sync + 'static>(
+    ///
+    /// If the pipeline needs to backfill (see [IndexerArgs::backfill_tasks]), it is also fed by
+    /// additional ingestion services, one per backfill range, in parallel with the main ingestion
+    /// service that follows the tip.
+    pub async fn concurrent_pipeline<H>(
         &mut self,
         handler: H,
         config: ConcurrentConfig,
-    ) -> Result<()> {
+    ) -> Result<()>
+    where
+        H: concurrent::Handler<Store = S> + Send + Sync + 'static,
+    {
         let start_from_pruner_watermark = H::PRUNING_REQUIRES_PROCESSED_VALUES;
-        let Some(watermark) = self.add_pipeline::<H>(start_from_pruner_watermark).await? else {
+        let Some((watermark, plan)) = self
+            .add_pipeline::<H>(start_from_pruner_watermark, true)
+            .await?
+        else {
             return Ok(());
         };
 
         // For a concurrent pipeline, if skip_watermark is set, we don't really care about the
         // watermark consistency. first_checkpoint can be anything since we don't update watermark,
-        // and writes should be idempotent.
-        if !self.skip_watermark {
+        // and writes should be idempotent. If the pipeline is backfilling, the gap between its
+        // watermark and the first checkpoint is covered by the backfill.
+        if !self.skip_watermark && plan.is_none() {
             self.check_first_checkpoint_consistency::<H>(&watermark)?;
         }
 
+        let (checkpoint_rx, ranges) = if let Some(plan) = plan {
+            let mut backfill_rx = vec![];
+            for range in &plan.backfill {
+                let checkpoints = range.next_checkpoint()..range.hi();
+                if checkpoints.is_empty() {
+                    continue;
+                }
+
+                let mut service = IngestionService::new(
+                    self.ingestion_client().clone(),
+                    self.ingestion_config.clone(),
+                    self.cancel.clone(),
+                );
+
+                backfill_rx.push(service.subscribe().0);
+                self.backfills.push((service, checkpoints));
+            }
+
+            let checkpoint_rx = backfill::merge(
+                plan.tip.lo(),
+                self.ingestion_service.subscribe().0,
+                backfill_rx,
+                self.ingestion_config.checkpoint_buffer_size,
+            );
+
+            (checkpoint_rx, plan.ranges())
+        } else {
+            (self.ingestion_service.subscribe().0, vec![])
+        };
+
         self.handles.push(concurrent::pipeline(
             handler,
             watermark,
+            ranges,
             config,
             self.skip_watermark,
-            self.db.clone(),
-            self.ingestion_service.subscribe().0,
+            self.store.clone(),
+            checkpoint_rx,
             self.metrics.clone(),
             self.cancel.clone(),
         ));
@@ -250,12 +444,15 @@ impl Indexer {
     ///
     /// The pipeline can optionally be configured to lag behind the ingestion service by a fixed
     /// number of checkpoints (configured by `checkpoint_lag`).
-    pub async fn sequential_pipeline<H: sequential::Handler + Send + Sync + 'static>(
+    pub async fn sequential_pipeline<H>(
         &mut self,
         handler: H,
         config: SequentialConfig,
-    ) -> Result<()> {
-        let Some(watermark) = self.add_pipeline::<H>(false).await? else {
+    ) -> Result<()>
+    where
+        H: sequential::Handler<Store = S> + Send + Sync + 'static,
+    {
+        let Some((watermark, _)) = self.add_pipeline::<H>(false, false).await? else {
             return Ok(());
         };
 
@@ -276,7 +473,7 @@ impl Indexer {
             handler,
             watermark,
             config,
-            self.db.clone(),
+            self.store.clone(),
             checkpoint_rx,
             watermark_tx,
             self.metrics.clone(),
@@ -308,6 +505,43 @@ impl Indexer {
         Ok(())
     }
 
+    /// Index `checkpoints` again for the concurrent pipeline served by `handler`, independently of
+    /// the pipeline's watermarks. This is useful for replaying checkpoints that the pipeline
+    /// recorded as dead letters (see [pipeline::FailurePolicy::DeadLetter]), once the issue that
+    /// caused them to fail has been fixed. Each checkpoint's dead letter is cleared once its data
+    /// has been written.
+    ///
+    /// Checkpoints are fetched using the indexer's ingestion client, and replayed one at a time,
+    /// in the order they are given. Replay stops at the first checkpoint that fails.
+    pub async fn replay<H>(&self, handler: &H, checkpoints: &[u64]) -> Result<()>
+    where
+        H: concurrent::Handler<Store = S> + Send + Sync + 'static,
+    {
+        for &checkpoint in checkpoints {
+            let data = self
+                .ingestion_client()
+                .fetch(checkpoint, &self.cancel)
+                .await
+                .with_context(|| format!("Failed to fetch checkpoint {checkpoint}"))?;
+
+            let affected = concurrent::replay(handler, &data, &self.store)
+                .await
+                .with_context(|| {
+                    format!("Failed to replay checkpoint {checkpoint} for {}", H::NAME)
+                })?;
+
+            let mut conn = self.store.connect().await?;
+            let cleared = conn.delete_dead_letter(H::NAME, checkpoint).await?;
+
+            info!(
+                pipeline = H::NAME,
+                checkpoint, affected, cleared, "Replayed checkpoint"
+            );
+        }
+
+        Ok(())
+    }
+
     /// Start ingesting checkpoints. Ingestion either starts from the configured
     /// `first_checkpoint`, or it is calculated based on the watermarks of all active pipelines.
     /// Ingestion will stop after consuming the configured `last_checkpoint`, if one is provided,
@@ -340,47 +574,49 @@ impl Indexer {
         self.handles.push(regulator_handle);
         self.handles.push(broadcaster_handle);
 
+        // Backfill ingestion services are expected to finish before the rest of the indexer, so
+        // they are not part of the graceful shutdown. The pipelines they feed will not finish
+        // until they do.
+        let mut backfill_handles = vec![];
+        for (service, checkpoints) in std::mem::take(&mut self.backfills) {
+            info!(?checkpoints, "Backfill ingestion range");
+            let (regulator_handle, broadcaster_handle) = service
+                .run(checkpoints)
+                .await
+                .context("Failed to start backfill ingestion service")?;
+
+            backfill_handles.push(regulator_handle);
+            backfill_handles.push(broadcaster_handle);
+        }
+
         Ok(tokio::spawn(async move {
             // Wait for the ingestion service and all its related tasks to wind down gracefully:
             // If ingestion has been configured to only handle a specific range of checkpoints, we
             // want to make sure that tasks are allowed to run to completion before shutting them
             // down.
             graceful_shutdown(self.handles, self.cancel).await;
+            futures::future::join_all(backfill_handles).await;
             info!("Indexing pipeline gracefully shut down");
         }))
     }
 
-    /// Combine the provided `migrations` with the migrations necessary to set up the indexer
-    /// framework. The returned migration source can be passed to [Db::run_migrations] to ensure
-    /// the database's schema is up-to-date for both the indexer framework and the specific
-    /// indexer.
-    pub fn migrations(
-        migrations: &'static EmbeddedMigrations,
-    ) -> impl MigrationSource<Pg> + Send + Sync + 'static {
-        struct Migrations(&'static EmbeddedMigrations);
-        impl MigrationSource<Pg> for Migrations {
-            fn migrations(&self) -> migration::Result<Vec<Box<dyn Migration<Pg>>>> {
-                let mut migrations = MIGRATIONS.migrations()?;
-                migrations.extend(self.0.migrations()?);
-                Ok(migrations)
-            }
-        }
-
-        Migrations(migrations)
-    }
-
     /// Update the indexer's first checkpoint based on the watermark for the pipeline by adding for
     /// handler `H` (as long as it's enabled). Returns `Ok(None)` if the pipeline is disabled,
-    /// `Ok(Some(None))` if the pipeline is enabled but its watermark is not found, and
-    /// `Ok(Some(Some(watermark)))` if the pipeline is enabled and the watermark is found.
+    /// `Ok(Some((None, _)))` if the pipeline is enabled but its watermark is not found, and
+    /// `Ok(Some((Some(watermark), _)))` if the pipeline is enabled and the watermark is found.
     ///
     /// If `start_from_pruner_watermark` is true, the indexer will start ingestion from just after
     /// the pruner watermark, so that the pruner have access to the processed values for any
     /// unpruned checkpoints.
+    ///
+    /// If `allow_backfill` is true, the pipeline may backfill in parallel with following the tip,
+    /// in which case its backfill plan is also returned, and ingestion only needs to start from
+    /// where the pipeline left off following the tip.
     async fn add_pipeline<P: Processor + 'static>(
         &mut self,
         start_from_pruner_watermark: bool,
-    ) -> Result<Option<Option<CommitterWatermark<'static>>>> {
+        allow_backfill: bool,
+    ) -> Result<Option<(Option<CommitterWatermark<'static>>, Option<BackfillPlan>)>> {
         ensure!(
             self.added_pipelines.insert(P::NAME),
             "Pipeline {:?} already added",
@@ -394,17 +630,69 @@ impl Indexer {
             }
         }
 
-        let mut conn = self.db.connect().await.context("Failed DB connection")?;
+        let mut conn = self
+            .store
+            .connect()
+            .await
+            .context("Failed to connect to store")?;
 
-        let watermark = CommitterWatermark::get(&mut conn, P::NAME)
+        let watermark = conn
+            .committer_watermark(P::NAME)
             .await
             .with_context(|| format!("Failed to get watermark for {}", P::NAME))?;
 
-        let expected_first_checkpoint = if start_from_pruner_watermark {
+        let plan = if allow_backfill {
+            // Only plan a fresh backfill for pipelines that are able to perform one, but still pick
+            // up any ranges that were recorded previously, so that they can be rejected below.
+            let backfill_tasks = if self.skip_watermark || start_from_pruner_watermark {
+                0
+            } else {
+                self.backfill_tasks
+            };
+
+            backfill::plan(
+                &mut conn,
+                P::NAME,
+                watermark.as_ref(),
+                self.first_checkpoint,
+                backfill_tasks,
+            )
+            .await
+            .with_context(|| format!("Failed to plan backfill for {}", P::NAME))?
+        } else {
+            None
+        };
+
+        if plan.is_some() {
+            ensure!(
+                !self.skip_watermark,
+                "Pipeline {} has backfill ranges in progress, which cannot be resumed when \
+                 --skip-watermark is set",
+                P::NAME,
+            );
+
+            ensure!(
+                !start_from_pruner_watermark,
+                "Pipeline {} has backfill ranges in progress, but cannot backfill in parallel \
+                 because its pruner requires processed values",
+                P::NAME,
+            );
+        }
+
+        let expected_first_checkpoint = if let Some(plan) = &plan {
+            // Backfill ranges are ingested separately, so the main ingestion service only needs to
+            // start from where the pipeline left off following the tip.
+            let next_checkpoint = watermark
+                .as_ref()
+                .map(|w| w.checkpoint_hi_inclusive as u64 + 1)
+                .unwrap_or_default();
+
+            plan.tip.next_checkpoint().max(next_checkpoint)
+        } else if start_from_pruner_watermark {
             // If the pruner of this pipeline requires processed values in order to prune,
             // we must start ingestion from just after the pruner watermark,
             // so that we can process all values needed by the pruner.
-            PrunerWatermark::get(&mut conn, P::NAME, Default::default())
+            conn.pruner_watermark(P::NAME, Default::default())
                 .await
                 .with_context(|| format!("Failed to get pruner watermark for {}", P::NAME))?
                 .map(|w| w.pruner_hi as u64)
@@ -419,7 +707,7 @@ impl Indexer {
         self.first_checkpoint_from_watermark =
             expected_first_checkpoint.min(self.first_checkpoint_from_watermark);
 
-        Ok(Some(watermark))
+        Ok(Some((watermark, plan)))
     }
 }
 
@@ -430,6 +718,8 @@ mod tests {
     use sui_pg_db as db;
     use sui_types::full_checkpoint_content::CheckpointData;
 
+    use crate::models::watermarks::PrunerWatermark;
+
     use super::*;
 
     #[derive(FieldCount)]
@@ -456,6 +746,8 @@ mod tests {
 
             #[async_trait]
             impl concurrent::Handler for $name {
+                type Store = Db;
+
                 const PRUNING_REQUIRES_PROCESSED_VALUES: bool = $pruning_requires_processed_values;
                 async fn commit(
                     _values: &[Self::Value],
@@ -82,6 +82,7 @@ pub(crate) struct IndexerMetrics {
     pub total_committer_rows_committed: IntCounterVec,
     pub total_committer_rows_affected: IntCounterVec,
     pub total_watermarks_out_of_order: IntCounterVec,
+    pub total_dead_letters: IntCounterVec,
     pub total_pruner_chunks_attempted: IntCounterVec,
     pub total_pruner_chunks_deleted: IntCounterVec,
     pub total_pruner_rows_deleted: IntCounterVec,
@@ -342,6 +343,13 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            total_dead_letters: register_int_counter_vec_with_registry!(
+                "indexer_dead_letters",
+                "Number of checkpoints this pipeline gave up on and recorded as dead letters",
+                &["pipeline", "stage"],
+                registry,
+            )
+            .unwrap(),
             total_pruner_chunks_attempted: register_int_counter_vec_with_registry!(
                 "indexer_pruner_chunks_attempted",
                 "Number of chunks this pruner attempted to delete",
@@ -0,0 +1,90 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use chrono::naive::NaiveDateTime;
+use diesel::{prelude::*, upsert::excluded};
+use diesel_async::RunQueryDsl;
+use sui_pg_db::Connection;
+
+use crate::schema::dead_letters;
+
+/// A checkpoint that a pipeline gave up on indexing, and skipped over, as allowed by its failure
+/// policy.
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = dead_letters)]
+pub struct DeadLetter<'p> {
+    /// The pipeline that failed to index the checkpoint.
+    pub pipeline: Cow<'p, str>,
+
+    /// The checkpoint that could not be indexed.
+    pub checkpoint: i64,
+
+    /// The stage of the pipeline that failed.
+    pub stage: Cow<'p, str>,
+
+    /// The error from the last attempt at indexing the checkpoint.
+    pub error: String,
+}
+
+/// A dead letter, as recorded in the database.
+#[derive(Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = dead_letters)]
+pub struct StoredDeadLetter {
+    pub pipeline: String,
+    pub checkpoint: i64,
+    pub stage: String,
+    pub error: String,
+    pub recorded_at: NaiveDateTime,
+}
+
+impl DeadLetter<'_> {
+    /// Record the dead letter, replacing any existing dead letter for the same checkpoint and
+    /// pipeline (e.g. if the checkpoint failed again after being replayed).
+    pub(crate) async fn insert(&self, conn: &mut Connection<'_>) -> QueryResult<usize> {
+        diesel::insert_into(dead_letters::table)
+            .values(self)
+            .on_conflict((dead_letters::pipeline, dead_letters::checkpoint))
+            .do_update()
+            .set((
+                dead_letters::stage.eq(excluded(dead_letters::stage)),
+                dead_letters::error.eq(excluded(dead_letters::error)),
+                dead_letters::recorded_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)
+            .await
+    }
+
+    /// Forget the dead letter for `checkpoint` in `pipeline`, once it has been successfully
+    /// replayed. Returns whether there was a dead letter to forget.
+    pub(crate) async fn delete(
+        conn: &mut Connection<'_>,
+        pipeline: &str,
+        checkpoint: u64,
+    ) -> QueryResult<bool> {
+        Ok(diesel::delete(dead_letters::table)
+            .filter(dead_letters::pipeline.eq(pipeline))
+            .filter(dead_letters::checkpoint.eq(checkpoint as i64))
+            .execute(conn)
+            .await?
+            > 0)
+    }
+}
+
+impl StoredDeadLetter {
+    /// All dead letters, optionally limited to those for `pipeline`, ordered by pipeline and then
+    /// checkpoint.
+    pub async fn list(conn: &mut Connection<'_>, pipeline: Option<&str>) -> QueryResult<Vec<Self>> {
+        let mut query = dead_letters::table
+            .select(StoredDeadLetter::as_select())
+            .order_by((dead_letters::pipeline, dead_letters::checkpoint))
+            .into_boxed();
+
+        if let Some(pipeline) = pipeline {
+            query = query.filter(dead_letters::pipeline.eq(pipeline));
+        }
+
+        query.load(conn).await
+    }
+}
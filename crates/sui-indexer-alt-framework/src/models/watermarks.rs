@@ -9,7 +9,7 @@ use diesel_async::RunQueryDsl;
 use sui_field_count::FieldCount;
 use sui_pg_db::Connection;
 
-use crate::schema::watermarks;
+use crate::schema::{backfill_watermarks, watermarks};
 
 #[derive(Insertable, Selectable, Queryable, Debug, Clone, FieldCount)]
 #[diesel(table_name = watermarks)]
@@ -27,7 +27,7 @@ pub(crate) struct StoredWatermark {
 /// Fields that the committer is responsible for setting.
 #[derive(AsChangeset, Selectable, Queryable, Debug, Clone, FieldCount)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct CommitterWatermark<'p> {
+pub struct CommitterWatermark<'p> {
     pub pipeline: Cow<'p, str>,
     pub epoch_hi_inclusive: i64,
     pub checkpoint_hi_inclusive: i64,
@@ -35,16 +35,32 @@ pub(crate) struct CommitterWatermark<'p> {
     pub timestamp_ms_hi_inclusive: i64,
 }
 
+/// Progress through a range of checkpoints that a concurrent pipeline is indexing ahead of its
+/// contiguous watermark (e.g. while backfilling in parallel with following the tip of the network).
+#[derive(Insertable, AsChangeset, Selectable, Queryable, Debug, Clone, FieldCount)]
+#[diesel(table_name = backfill_watermarks)]
+pub struct BackfillWatermark<'p> {
+    pub pipeline: Cow<'p, str>,
+    pub range_lo: i64,
+    pub range_hi: i64,
+    pub epoch_hi_inclusive: i64,
+    pub checkpoint_hi_inclusive: i64,
+    pub tx_hi: i64,
+    pub timestamp_ms_hi_inclusive: i64,
+}
+
+/// Fields that the reader watermark task is responsible for setting.
 #[derive(AsChangeset, Selectable, Queryable, Debug, Clone, FieldCount)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct ReaderWatermark<'p> {
+pub struct ReaderWatermark<'p> {
     pub pipeline: Cow<'p, str>,
     pub reader_lo: i64,
 }
 
+/// Fields that the pruner is responsible for reading and setting.
 #[derive(Queryable, Debug, Clone, FieldCount, PartialEq, Eq)]
 #[diesel(table_name = watermarks)]
-pub(crate) struct PrunerWatermark<'p> {
+pub struct PrunerWatermark<'p> {
     /// The pipeline in question
     pub pipeline: Cow<'p, str>,
 
@@ -60,20 +76,6 @@ pub(crate) struct PrunerWatermark<'p> {
     pub pruner_hi: i64,
 }
 
-impl StoredWatermark {
-    pub(crate) async fn get(
-        conn: &mut Connection<'_>,
-        pipeline: &'static str,
-    ) -> QueryResult<Option<Self>> {
-        watermarks::table
-            .select(StoredWatermark::as_select())
-            .filter(watermarks::pipeline.eq(pipeline))
-            .first(conn)
-            .await
-            .optional()
-    }
-}
-
 impl CommitterWatermark<'static> {
     /// Get the current high watermark for the pipeline.
     pub(crate) async fn get(
@@ -135,6 +137,139 @@ impl<'p> CommitterWatermark<'p> {
     }
 }
 
+impl BackfillWatermark<'static> {
+    /// Get all the ranges being tracked for the pipeline, ordered by their lower bound.
+    pub(crate) async fn get_all(
+        conn: &mut Connection<'_>,
+        pipeline: &'static str,
+    ) -> QueryResult<Vec<Self>> {
+        backfill_watermarks::table
+            .select(BackfillWatermark::as_select())
+            .filter(backfill_watermarks::pipeline.eq(pipeline))
+            .order_by(backfill_watermarks::range_lo)
+            .load(conn)
+            .await
+    }
+}
+
+impl<'p> BackfillWatermark<'p> {
+    /// A new watermark for the range of checkpoints from `lo` (inclusive) to `hi` (exclusive),
+    /// indicating zero progress.
+    pub(crate) fn initial(pipeline: Cow<'p, str>, lo: u64, hi: u64) -> Self {
+        BackfillWatermark {
+            pipeline,
+            range_lo: lo as i64,
+            range_hi: hi.min(i64::MAX as u64) as i64,
+            epoch_hi_inclusive: 0,
+            // A range that starts at genesis has no checkpoint before it.
+            checkpoint_hi_inclusive: lo.checked_sub(1).map_or(-1, |hi| hi as i64),
+            tx_hi: 0,
+            timestamp_ms_hi_inclusive: 0,
+        }
+    }
+
+    /// The first checkpoint in the range (inclusive).
+    pub(crate) fn lo(&self) -> u64 {
+        self.range_lo as u64
+    }
+
+    /// The end of the range (exclusive). The range that follows the tip of the network does not
+    /// end, and returns `u64::MAX`.
+    pub(crate) fn hi(&self) -> u64 {
+        if self.range_hi == i64::MAX {
+            u64::MAX
+        } else {
+            self.range_hi as u64
+        }
+    }
+
+    /// The next checkpoint that needs to be written for this range to make progress.
+    pub(crate) fn next_checkpoint(&self) -> u64 {
+        (self.checkpoint_hi_inclusive + 1) as u64
+    }
+
+    /// Whether any data has been written in this range yet.
+    pub(crate) fn has_progress(&self) -> bool {
+        self.checkpoint_hi_inclusive >= self.range_lo
+    }
+
+    /// Whether `checkpoint` belongs to this range.
+    pub(crate) fn contains(&self, checkpoint: u64) -> bool {
+        self.lo() <= checkpoint && checkpoint < self.hi()
+    }
+
+    /// Record that all data up to and including `watermark`'s checkpoint has been written in this
+    /// range.
+    pub(crate) fn advance(&mut self, watermark: &CommitterWatermark<'_>) {
+        self.epoch_hi_inclusive = watermark.epoch_hi_inclusive;
+        self.checkpoint_hi_inclusive = watermark.checkpoint_hi_inclusive;
+        self.tx_hi = watermark.tx_hi;
+        self.timestamp_ms_hi_inclusive = watermark.timestamp_ms_hi_inclusive;
+    }
+
+    /// The progress through this range, as a watermark for the whole pipeline (only meaningful
+    /// once the pipeline's watermark has caught up with the start of this range).
+    pub(crate) fn committer_watermark(&self) -> CommitterWatermark<'p> {
+        CommitterWatermark {
+            pipeline: self.pipeline.clone(),
+            epoch_hi_inclusive: self.epoch_hi_inclusive,
+            checkpoint_hi_inclusive: self.checkpoint_hi_inclusive,
+            tx_hi: self.tx_hi,
+            timestamp_ms_hi_inclusive: self.timestamp_ms_hi_inclusive,
+        }
+    }
+
+    /// Record a batch of new ranges, ignoring any range that already exists.
+    pub(crate) async fn insert_all(
+        ranges: &[BackfillWatermark<'p>],
+        conn: &mut Connection<'_>,
+    ) -> QueryResult<usize> {
+        diesel::insert_into(backfill_watermarks::table)
+            .values(ranges)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await
+    }
+
+    /// Update the progress through this range, as long as it raises the watermark. Returns a
+    /// boolean indicating whether the watermark was actually updated or not.
+    pub(crate) async fn update(&self, conn: &mut Connection<'_>) -> QueryResult<bool> {
+        Ok(diesel::update(backfill_watermarks::table)
+            .set(self)
+            .filter(backfill_watermarks::pipeline.eq(&self.pipeline))
+            .filter(backfill_watermarks::range_lo.eq(self.range_lo))
+            .filter(backfill_watermarks::checkpoint_hi_inclusive.lt(self.checkpoint_hi_inclusive))
+            .execute(conn)
+            .await?
+            > 0)
+    }
+
+    /// Stop tracking this range, once it has been merged into the pipeline's watermark.
+    pub(crate) async fn delete(&self, conn: &mut Connection<'_>) -> QueryResult<bool> {
+        Ok(diesel::delete(backfill_watermarks::table)
+            .filter(backfill_watermarks::pipeline.eq(&self.pipeline))
+            .filter(backfill_watermarks::range_lo.eq(self.range_lo))
+            .execute(conn)
+            .await?
+            > 0)
+    }
+}
+
+impl ReaderWatermark<'static> {
+    /// Get the current low watermark for the pipeline.
+    pub(crate) async fn get(
+        conn: &mut Connection<'_>,
+        pipeline: &'static str,
+    ) -> QueryResult<Option<Self>> {
+        watermarks::table
+            .select(ReaderWatermark::as_select())
+            .filter(watermarks::pipeline.eq(pipeline))
+            .first(conn)
+            .await
+            .optional()
+    }
+}
+
 impl<'p> ReaderWatermark<'p> {
     pub(crate) fn new(pipeline: impl Into<Cow<'p, str>>, reader_lo: u64) -> Self {
         ReaderWatermark {
@@ -230,6 +230,8 @@ mod tests {
 
     #[async_trait::async_trait]
     impl Handler for TestHandler {
+        type Store = db::Db;
+
         const MAX_PENDING_ROWS: usize = 10000;
         async fn commit(
             _values: &[Self::Value],
@@ -3,11 +3,10 @@
 
 use std::{
     cmp::Ordering,
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
     sync::Arc,
 };
 
-use sui_pg_db::Db;
 use tokio::{
     sync::mpsc,
     task::JoinHandle,
@@ -18,14 +17,15 @@ use tracing::{debug, error, info, warn};
 
 use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
-    models::watermarks::CommitterWatermark,
+    models::watermarks::{BackfillWatermark, CommitterWatermark},
     pipeline::{logging::WatermarkLogger, CommitterConfig, WatermarkPart, WARN_PENDING_WATERMARKS},
+    store::{Connection, Store},
 };
 
 use super::Handler;
 
 /// The watermark task is responsible for keeping track of a pipeline's out-of-order commits and
-/// updating its row in the `watermarks` table when a continuous run of checkpoints have landed
+/// updating its watermark in the store when a continuous run of checkpoints have landed
 /// since the last watermark update.
 ///
 /// It receives watermark "parts" that detail the proportion of each checkpoint's data that has
@@ -34,6 +34,11 @@ use super::Handler;
 /// is one or more complete (all data for that checkpoint written out) watermarks spanning
 /// contiguously from the current high watermark into the future.
 ///
+/// The task can also track progress through `ranges` of checkpoints that are being written ahead of
+/// the pipeline's watermark (e.g. when backfilling in parallel with following the tip). Each
+/// range's progress is tracked in the store alongside the pipeline's watermark, and once the pipeline's
+/// watermark reaches the start of a range, the range is merged into it.
+///
 /// If it detects that more than [WARN_PENDING_WATERMARKS] watermarks have built up, it will issue
 /// a warning, as this could be the indication of a memory leak, and the caller probably intended
 /// to run the indexer with watermarking disabled (e.g. if they are running a backfill).
@@ -46,10 +51,11 @@ use super::Handler;
 /// immediately.
 pub(super) fn commit_watermark<H: Handler + 'static>(
     initial_watermark: Option<CommitterWatermark<'static>>,
+    mut ranges: Vec<BackfillWatermark<'static>>,
     config: CommitterConfig,
     skip_watermark: bool,
     mut rx: mpsc::Receiver<Vec<WatermarkPart>>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -68,8 +74,12 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
         // watermark as much as possible without going over any holes in the sequence of
         // checkpoints (entirely missing watermarks, or incomplete watermarks).
         let mut precommitted: BTreeMap<u64, WatermarkPart> = BTreeMap::new();
+
+        // Ranges that have been merged into the pipeline's watermark, and whose rows can be
+        // removed once the pipeline's watermark has been written.
+        let mut merged: Vec<BackfillWatermark<'static>> = vec![];
         let (mut watermark, mut next_checkpoint) = if let Some(watermark) = initial_watermark {
-            let next = watermark.checkpoint_hi_inclusive + 1;
+            let next = watermark.checkpoint_hi_inclusive as u64 + 1;
             (watermark, next)
         } else {
             (CommitterWatermark::initial(H::NAME.into()), 0)
@@ -85,7 +95,12 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
             &metrics.watermark_checkpoint_in_db,
         );
 
-        info!(pipeline = H::NAME, ?watermark, "Starting commit watermark");
+        info!(
+            pipeline = H::NAME,
+            ?watermark,
+            ranges = ranges.len(),
+            "Starting commit watermark",
+        );
 
         loop {
             tokio::select! {
@@ -103,7 +118,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                         );
                     }
 
-                    let Ok(mut conn) = db.connect().await else {
+                    let Ok(mut conn) = store.connect().await else {
                         warn!(pipeline = H::NAME, "Commit watermark task failed to get connection for DB");
                         continue;
                     };
@@ -114,42 +129,25 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                         .with_label_values(&[H::NAME])
                         .start_timer();
 
-                    let mut watermark_needs_update = false;
-                    while let Some(pending) = precommitted.first_entry() {
-                        let part = pending.get();
-
-                        // Some rows from the next watermark have not landed yet.
-                        if !part.is_complete() {
-                            break;
-                        }
-
-                        match next_checkpoint.cmp(&part.watermark.checkpoint_hi_inclusive) {
-                            // Next pending checkpoint is from the future.
-                            Ordering::Less => break,
-
-                            // This is the next checkpoint -- include it.
-                            Ordering::Equal => {
-                                watermark = pending.remove().watermark;
-                                watermark_needs_update = true;
-                                next_checkpoint += 1;
-                            }
-
-                            // Next pending checkpoint is in the past. Out of order watermarks can
-                            // be encountered when a pipeline is starting up, because ingestion
-                            // must start at the lowest checkpoint across all pipelines, or because
-                            // of a backfill, where the initial checkpoint has been overridden.
-                            Ordering::Greater => {
-                                // Track how many we see to make sure it doesn't grow without
-                                // bound.
-                                metrics
-                                    .total_watermarks_out_of_order
-                                    .with_label_values(&[H::NAME])
-                                    .inc();
+                    let Gathered {
+                        watermark_updated: watermark_needs_update,
+                        ranges_updated: ranges_to_update,
+                        out_of_order,
+                    } = gather(
+                        H::NAME,
+                        &mut precommitted,
+                        &mut watermark,
+                        &mut next_checkpoint,
+                        &mut ranges,
+                        &mut merged,
+                    );
 
-                                pending.remove();
-                            }
-                        }
-                    }
+                    // Track how many out of order watermarks we see to make sure it doesn't grow
+                    // without bound.
+                    metrics
+                        .total_watermarks_out_of_order
+                        .with_label_values(&[H::NAME])
+                        .inc_by(out_of_order);
 
                     let elapsed = guard.stop_and_record();
 
@@ -182,6 +180,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                         "Gathered watermarks",
                     );
 
+                    let mut watermark_written = false;
                     if watermark_needs_update {
                         let guard = metrics
                             .watermark_commit_latency
@@ -190,7 +189,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
 
                         // TODO: If initial_watermark is empty, when we update watermark
                         // for the first time, we should also update the low watermark.
-                        match watermark.update(&mut conn).await {
+                        match conn.set_committer_watermark(&watermark).await {
                             // If there's an issue updating the watermark, log it but keep going,
                             // it's OK for the watermark to lag from a correctness perspective.
                             Err(e) => {
@@ -204,6 +203,7 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                             }
 
                             Ok(true) => {
+                                watermark_written = true;
                                 let elapsed = guard.stop_and_record();
 
                                 logger.log::<H>(&watermark, elapsed);
@@ -228,7 +228,34 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
                                     .with_label_values(&[H::NAME])
                                     .set(watermark.timestamp_ms_hi_inclusive);
                             }
-                            Ok(false) => {}
+                            Ok(false) => watermark_written = true,
+                        }
+                    }
+
+                    // Only stop tracking merged ranges once the watermark that covers them has
+                    // been written.
+                    if !watermark_needs_update || watermark_written {
+                        for range in merged.drain(..) {
+                            if let Err(e) = conn.delete_backfill_watermark(&range).await {
+                                error!(
+                                    pipeline = H::NAME,
+                                    lo = range.lo(),
+                                    "Error removing merged range: {e}",
+                                );
+                            }
+                        }
+                    }
+
+                    for range in ranges.iter().filter(|r| ranges_to_update.contains(&r.range_lo)) {
+                        // If there's an issue updating the range's watermark, log it but keep
+                        // going, like the pipeline's watermark, it can lag safely.
+                        if let Err(e) = conn.set_backfill_watermark(range).await {
+                            error!(
+                                pipeline = H::NAME,
+                                lo = range.lo(),
+                                checkpoint = range.checkpoint_hi_inclusive,
+                                "Error updating range watermark: {e}",
+                            );
                         }
                     }
 
@@ -261,3 +288,273 @@ pub(super) fn commit_watermark<H: Handler + 'static>(
         );
     })
 }
+
+/// The outcome of gathering complete watermark parts.
+#[derive(Default, Debug)]
+struct Gathered {
+    /// Whether the pipeline's watermark moved forward.
+    watermark_updated: bool,
+
+    /// Lower bounds of the ranges whose progress moved forward.
+    ranges_updated: BTreeSet<i64>,
+
+    /// How many parts were dropped because their checkpoint had already been accounted for.
+    out_of_order: u64,
+}
+
+/// Consume complete parts from `precommitted` that extend either the pipeline's `watermark`, or
+/// the progress through one of its `ranges`, without going over any holes. `next_checkpoint` is the
+/// checkpoint after the pipeline's watermark.
+///
+/// Once the pipeline's watermark reaches the start of a range, the range is merged into it (the
+/// watermark jumps to the range's progress, if it is ahead), and the range is moved to `merged`.
+fn gather(
+    pipeline: &str,
+    precommitted: &mut BTreeMap<u64, WatermarkPart>,
+    watermark: &mut CommitterWatermark<'static>,
+    next_checkpoint: &mut u64,
+    ranges: &mut Vec<BackfillWatermark<'static>>,
+    merged: &mut Vec<BackfillWatermark<'static>>,
+) -> Gathered {
+    let mut gathered = Gathered::default();
+
+    loop {
+        // Merge any ranges that the pipeline's watermark has caught up with.
+        while ranges.first().is_some_and(|r| r.lo() <= *next_checkpoint) {
+            let range = ranges.remove(0);
+            gathered.ranges_updated.remove(&range.range_lo);
+
+            if range.next_checkpoint() > *next_checkpoint {
+                *watermark = range.committer_watermark();
+                gathered.watermark_updated = true;
+                *next_checkpoint = range.next_checkpoint();
+            }
+
+            info!(
+                pipeline,
+                lo = range.lo(),
+                watermark = watermark.checkpoint_hi_inclusive,
+                "Merged range into watermark",
+            );
+
+            merged.push(range);
+        }
+
+        precommitted.retain(|checkpoint, part| {
+            // Some rows from this watermark have not landed yet.
+            if !part.is_complete() {
+                return true;
+            }
+
+            // This is the next checkpoint for the pipeline -- include it.
+            if *checkpoint == *next_checkpoint {
+                *watermark = part.watermark.clone();
+                gathered.watermark_updated = true;
+                *next_checkpoint += 1;
+                return false;
+            }
+
+            let range = ranges.iter_mut().find(|r| r.contains(*checkpoint));
+            let next = range
+                .as_ref()
+                .map_or(*next_checkpoint, |r| r.next_checkpoint());
+
+            match next.cmp(checkpoint) {
+                // Checkpoint is from the future.
+                Ordering::Less => true,
+
+                // This is the next checkpoint in its range -- include it.
+                Ordering::Equal => {
+                    // SAFETY: `next` can only equal `checkpoint` through a range, because the
+                    // pipeline's next checkpoint was checked above.
+                    let range = range.unwrap();
+                    range.advance(&part.watermark);
+                    gathered.ranges_updated.insert(range.range_lo);
+                    false
+                }
+
+                // Checkpoint is in the past. Out of order watermarks can be encountered when a
+                // pipeline is starting up, because ingestion must start at the lowest checkpoint
+                // across all pipelines, or because of a backfill, where the initial checkpoint has
+                // been overridden.
+                Ordering::Greater => {
+                    gathered.out_of_order += 1;
+                    false
+                }
+            }
+        });
+
+        // Progress may have brought the pipeline's watermark up to the next range, in which case
+        // it needs to be merged, and the parts that follow it gathered.
+        if !ranges.first().is_some_and(|r| r.lo() <= *next_checkpoint) {
+            return gathered;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIPELINE: &str = "test";
+
+    fn part(checkpoint: u64, batch_rows: usize, total_rows: usize) -> WatermarkPart {
+        WatermarkPart {
+            watermark: CommitterWatermark::new_for_testing(PIPELINE, checkpoint),
+            batch_rows,
+            total_rows,
+        }
+    }
+
+    fn precommitted(
+        parts: impl IntoIterator<Item = WatermarkPart>,
+    ) -> BTreeMap<u64, WatermarkPart> {
+        parts.into_iter().map(|p| (p.checkpoint(), p)).collect()
+    }
+
+    fn range(lo: u64, hi: u64) -> BackfillWatermark<'static> {
+        BackfillWatermark::initial(PIPELINE.into(), lo, hi)
+    }
+
+    struct State {
+        precommitted: BTreeMap<u64, WatermarkPart>,
+        watermark: CommitterWatermark<'static>,
+        next_checkpoint: u64,
+        ranges: Vec<BackfillWatermark<'static>>,
+        merged: Vec<BackfillWatermark<'static>>,
+    }
+
+    impl State {
+        /// A pipeline whose watermark is at `checkpoint_hi_inclusive`.
+        fn new(checkpoint_hi_inclusive: u64, ranges: Vec<BackfillWatermark<'static>>) -> Self {
+            Self {
+                precommitted: BTreeMap::new(),
+                watermark: CommitterWatermark::new_for_testing(PIPELINE, checkpoint_hi_inclusive),
+                next_checkpoint: checkpoint_hi_inclusive + 1,
+                ranges,
+                merged: vec![],
+            }
+        }
+
+        fn gather(&mut self) -> Gathered {
+            gather(
+                PIPELINE,
+                &mut self.precommitted,
+                &mut self.watermark,
+                &mut self.next_checkpoint,
+                &mut self.ranges,
+                &mut self.merged,
+            )
+        }
+    }
+
+    #[test]
+    fn contiguous_parts_advance_watermark() {
+        let mut state = State::new(9, vec![]);
+        state.precommitted = precommitted([
+            part(10, 1, 1),
+            part(11, 2, 2),
+            part(12, 1, 2),
+            part(13, 1, 1),
+        ]);
+
+        let gathered = state.gather();
+        assert!(gathered.watermark_updated);
+        assert_eq!(state.watermark.checkpoint_hi_inclusive, 11);
+        assert_eq!(state.next_checkpoint, 12);
+
+        // The incomplete part, and everything after it, is left behind.
+        assert_eq!(
+            state.precommitted.keys().copied().collect::<Vec<_>>(),
+            vec![12, 13]
+        );
+    }
+
+    #[test]
+    fn parts_in_range_advance_range() {
+        let mut state = State::new(9, vec![range(100, 200)]);
+        state.precommitted = precommitted([part(100, 1, 1), part(101, 1, 1), part(103, 1, 1)]);
+
+        let gathered = state.gather();
+        assert!(!gathered.watermark_updated);
+        assert_eq!(gathered.ranges_updated, BTreeSet::from([100]));
+        assert_eq!(state.watermark.checkpoint_hi_inclusive, 9);
+        assert_eq!(state.ranges[0].next_checkpoint(), 102);
+
+        // 103 is blocked by a hole at 102.
+        assert_eq!(
+            state.precommitted.keys().copied().collect::<Vec<_>>(),
+            vec![103]
+        );
+        assert!(state.merged.is_empty());
+    }
+
+    #[test]
+    fn watermark_reaching_range_merges_it() {
+        let mut state = State::new(97, vec![range(100, 200), range(300, 400)]);
+        state.precommitted = precommitted([part(100, 1, 1), part(101, 1, 1)]);
+
+        // The range makes progress, but the pipeline has a hole at 98 and 99.
+        let gathered = state.gather();
+        assert!(!gathered.watermark_updated);
+        assert_eq!(state.ranges.len(), 2);
+
+        // Filling the hole brings the pipeline up to the start of the range, which is merged, and
+        // parts that land after the range's progress are gathered in the same pass.
+        state.precommitted = precommitted([part(98, 1, 1), part(99, 1, 1), part(102, 1, 1)]);
+        let gathered = state.gather();
+        assert!(gathered.watermark_updated);
+        assert!(gathered.ranges_updated.is_empty());
+        assert_eq!(state.watermark.checkpoint_hi_inclusive, 102);
+        assert_eq!(state.next_checkpoint, 103);
+        assert!(state.precommitted.is_empty());
+
+        assert_eq!(state.merged.len(), 1);
+        assert_eq!(state.merged[0].lo(), 100);
+        assert_eq!(state.ranges.len(), 1);
+        assert_eq!(state.ranges[0].lo(), 300);
+    }
+
+    #[test]
+    fn merge_range_without_progress() {
+        let mut state = State::new(99, vec![range(100, 200)]);
+
+        let gathered = state.gather();
+        assert!(!gathered.watermark_updated);
+        assert_eq!(state.watermark.checkpoint_hi_inclusive, 99);
+        assert_eq!(state.next_checkpoint, 100);
+        assert_eq!(state.merged.len(), 1);
+        assert!(state.ranges.is_empty());
+    }
+
+    #[test]
+    fn range_from_genesis() {
+        // A range starting at genesis has not written any checkpoints yet.
+        let range = range(0, 100);
+        assert_eq!(range.next_checkpoint(), 0);
+        assert!(!range.has_progress());
+
+        let mut state = State::new(0, vec![]);
+        state.next_checkpoint = 0;
+        state.ranges = vec![range];
+        state.precommitted = precommitted([part(0, 1, 1), part(1, 1, 1)]);
+
+        let gathered = state.gather();
+        assert!(gathered.watermark_updated);
+        assert_eq!(state.watermark.checkpoint_hi_inclusive, 1);
+        assert_eq!(state.merged.len(), 1);
+    }
+
+    #[test]
+    fn out_of_order_parts_are_dropped() {
+        let mut state = State::new(9, vec![range(100, 200)]);
+        state.ranges[0].advance(&CommitterWatermark::new_for_testing(PIPELINE, 104));
+        state.precommitted = precommitted([part(5, 1, 1), part(103, 1, 1), part(105, 1, 1)]);
+
+        let gathered = state.gather();
+        assert_eq!(gathered.out_of_order, 2);
+        assert_eq!(gathered.ranges_updated, BTreeSet::from([100]));
+        assert_eq!(state.ranges[0].next_checkpoint(), 106);
+        assert!(state.precommitted.is_empty());
+    }
+}
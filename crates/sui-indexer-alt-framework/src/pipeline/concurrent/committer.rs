@@ -1,10 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::BTreeSet, sync::Arc, time::Duration};
 
 use backoff::ExponentialBackoff;
-use sui_pg_db::Db;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
@@ -12,7 +11,8 @@ use tracing::{debug, error, info, warn};
 
 use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
-    pipeline::{Break, CommitterConfig, WatermarkPart},
+    pipeline::{failure::record_dead_letter, Break, CommitterConfig, WatermarkPart},
+    store::Store,
     task::TrySpawnStreamExt,
 };
 
@@ -24,13 +24,18 @@ const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
 /// If the committer needs to retry a commit, it will wait at most this long between retries.
 const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
-/// The committer task is responsible for writing batches of rows to the database. It receives
-/// batches on `rx` and writes them out to the `db` concurrently (`config.write_concurrency`
+/// The committer task is responsible for writing batches of rows to the store. It receives
+/// batches on `rx` and writes them out to the `store` concurrently (`config.write_concurrency`
 /// controls the degree of fan-out).
 ///
 /// The writing of each batch will be repeatedly retried on an exponential back-off until it
-/// succeeds. Once the write succeeds, the [WatermarkPart]s for that batch are sent on `tx` to the
-/// watermark task, as long as `skip_watermark` is not true.
+/// succeeds, or the pipeline's failure policy runs out of attempts. Once the write succeeds, the
+/// [WatermarkPart]s for that batch are sent on `tx` to the watermark task, as long as
+/// `skip_watermark` is not true.
+///
+/// If the policy gives up on a batch, either the checkpoints it contains rows for are recorded as
+/// dead letters, and the batch's [WatermarkPart]s are sent on as though the write had succeeded,
+/// or the task shuts down.
 ///
 /// This task will shutdown via its `cancel`lation token, or if its receiver or sender channels are
 /// closed.
@@ -39,7 +44,7 @@ pub(super) fn committer<H: Handler + 'static>(
     skip_watermark: bool,
     rx: mpsc::Receiver<BatchedRows<H>>,
     tx: mpsc::Sender<Vec<WatermarkPart>>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -57,7 +62,7 @@ pub(super) fn committer<H: Handler + 'static>(
                 |BatchedRows { values, watermark }| {
                     let values = Arc::new(values);
                     let tx = tx.clone();
-                    let db = db.clone();
+                    let store = store.clone();
                     let metrics = metrics.clone();
                     let cancel = cancel.clone();
                     let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
@@ -77,10 +82,18 @@ pub(super) fn committer<H: Handler + 'static>(
                     let highest_checkpoint_timestamp =
                         watermark.iter().map(|w| w.timestamp_ms()).max();
 
+                    // Kept aside in case the batch needs to be recorded as dead letters.
+                    let policy = config.failure_policy;
+                    let dead_letter_store = store.clone();
+                    let dead_letter_metrics = metrics.clone();
+
                     use backoff::Error as BE;
+                    let mut attempt = 0;
                     let commit = move || {
+                        attempt += 1;
+                        let attempt = attempt;
                         let values = values.clone();
-                        let db = db.clone();
+                        let store = store.clone();
                         let metrics = metrics.clone();
                         let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
                         async move {
@@ -98,7 +111,7 @@ pub(super) fn committer<H: Handler + 'static>(
                                 .with_label_values(&[H::NAME])
                                 .start_timer();
 
-                            let mut conn = db.connect().await.map_err(|e| {
+                            let mut conn = store.connect().await.map_err(|e| {
                                 warn!(
                                     pipeline = H::NAME,
                                     "Committed failed to get connection for DB"
@@ -158,6 +171,7 @@ pub(super) fn committer<H: Handler + 'static>(
                                     warn!(
                                         pipeline = H::NAME,
                                         elapsed_ms = elapsed * 1000.0,
+                                        attempt,
                                         committed = values.len(),
                                         "Error writing batch: {e}",
                                     );
@@ -167,26 +181,52 @@ pub(super) fn committer<H: Handler + 'static>(
                                         .with_label_values(&[H::NAME])
                                         .inc();
 
-                                    Err(BE::transient(Break::Err(e)))
+                                    if policy.commit_attempts().is_some_and(|max| attempt >= max) {
+                                        Err(BE::permanent(Break::Err(e)))
+                                    } else {
+                                        Err(BE::transient(Break::Err(e)))
+                                    }
                                 }
                             }
                         }
                     };
 
                     async move {
-                        tokio::select! {
+                        let commit = tokio::select! {
                             _ = cancel.cancelled() => {
                                 return Err(Break::Cancel);
                             }
 
-                            // Double check that the commit actually went through, (this backoff should
-                            // not produce any permanent errors, but if it does, we need to shutdown
-                            // the pipeline).
-                            commit = backoff::future::retry(backoff, commit) => {
-                                let () = commit?;
-                            }
+                            commit = backoff::future::retry(backoff, commit) => commit,
                         };
 
+                        match commit {
+                            Ok(()) => {}
+
+                            // The failure policy has given up on this batch, but allows the
+                            // pipeline to skip over it, as long as every checkpoint that it has
+                            // rows for is recorded as a dead letter.
+                            Err(Break::Err(e)) if policy.is_dead_letter() => {
+                                let checkpoints: BTreeSet<_> =
+                                    watermark.iter().map(|w| w.checkpoint()).collect();
+
+                                for checkpoint in checkpoints {
+                                    record_dead_letter(
+                                        &dead_letter_store,
+                                        H::NAME,
+                                        checkpoint,
+                                        "commit",
+                                        &e,
+                                        &dead_letter_metrics,
+                                        &cancel,
+                                    )
+                                    .await?;
+                                }
+                            }
+
+                            Err(e) => return Err(e),
+                        }
+
                         if !skip_watermark && tx.send(watermark).await.is_err() {
                             info!(pipeline = H::NAME, "Watermark closed channel");
                             return Err(Break::Cancel);
@@ -5,13 +5,16 @@ use std::{sync::Arc, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use sui_field_count::FieldCount;
-use sui_pg_db::{self as db, Db};
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 
-use crate::{metrics::IndexerMetrics, models::watermarks::CommitterWatermark};
+use crate::{
+    metrics::IndexerMetrics,
+    models::watermarks::{BackfillWatermark, CommitterWatermark},
+    store::Store,
+};
 
 use super::{processor::processor, CommitterConfig, Processor, WatermarkPart, PIPELINE_BUFFER};
 
@@ -32,7 +35,8 @@ mod reader_watermark;
 const MAX_WATERMARK_UPDATES: usize = 10_000;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
-/// implementing [Processor]) into rows for their table, and how to write those rows to the database.
+/// implementing [Processor]) into rows for their table, and how to write those rows to the store
+/// (usually a database).
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values). Reasonable defaults have been chosen to balance concurrency with memory
@@ -52,6 +56,9 @@ const MAX_WATERMARK_UPDATES: usize = 10_000;
 /// back to the ingestion service.
 #[async_trait::async_trait]
 pub trait Handler: Processor<Value: FieldCount> {
+    /// The storage backend that this pipeline writes its rows and watermarks to.
+    type Store: Store;
+
     /// If at least this many rows are pending, the committer will commit them eagerly.
     const MIN_EAGER_ROWS: usize = 50;
 
@@ -70,18 +77,21 @@ pub trait Handler: Processor<Value: FieldCount> {
     // 2. The name is a bit abstract.
     const PRUNING_REQUIRES_PROCESSED_VALUES: bool = false;
 
-    /// Take a chunk of values and commit them to the database, returning the number of rows
+    /// Take a chunk of values and commit them to the store, returning the number of rows
     /// affected.
-    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>)
-        -> anyhow::Result<usize>;
-
-    /// Clean up data between checkpoints `_from` and `_to_exclusive` (exclusive) in the database, returning
-    /// the number of rows affected. This function is optional, and defaults to not pruning at all.
-    async fn prune(
+    async fn commit<'a>(
+        values: &[Self::Value],
+        conn: &mut <Self::Store as Store>::Connection<'a>,
+    ) -> anyhow::Result<usize>;
+
+    /// Clean up data between checkpoints `_from` and `_to_exclusive` (exclusive) in the store,
+    /// returning the number of rows affected. This function is optional, and defaults to not
+    /// pruning at all.
+    async fn prune<'a>(
         &self,
         _from: u64,
         _to_exclusive: u64,
-        _conn: &mut db::Connection<'_>,
+        _conn: &mut <Self::Store as Store>::Connection<'a>,
     ) -> anyhow::Result<usize> {
         Ok(0)
     }
@@ -182,9 +192,10 @@ impl Default for PrunerConfig {
 /// either because it received the checkpoints out-of-order or because of variance in processing
 /// time.
 ///
-/// The pipeline also maintains a row in the `watermarks` table for the pipeline which tracks the
-/// watermark below which all data has been committed (modulo pruning), as long as `skip_watermark`
-/// is not true.
+/// The pipeline also maintains a watermark in the `store` for the pipeline which tracks the
+/// checkpoint below which all data has been committed (modulo pruning), as long as `skip_watermark`
+/// is not true. If the pipeline is also writing `ranges` of checkpoints ahead of its watermark, it
+/// tracks progress through each range, and merges them into its watermark as it catches up.
 ///
 /// Checkpoint data is fed into the pipeline through the `checkpoint_rx` channel, and internal
 /// channels are created to communicate between its various components. The pipeline can be
@@ -193,9 +204,10 @@ impl Default for PrunerConfig {
 pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     handler: H,
     initial_commit_watermark: Option<CommitterWatermark<'static>>,
+    ranges: Vec<BackfillWatermark<'static>>,
     config: ConcurrentConfig,
     skip_watermark: bool,
-    db: Db,
+    store: H::Store,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
@@ -224,8 +236,10 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 
     let processor = processor(
         handler.clone(),
+        committer_config.failure_policy,
         checkpoint_rx,
         processor_tx,
+        store.clone(),
         metrics.clone(),
         cancel.clone(),
     );
@@ -243,29 +257,36 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
         skip_watermark,
         committer_rx,
         committer_tx,
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         cancel.clone(),
     );
 
     let commit_watermark = commit_watermark::<H>(
         initial_commit_watermark,
+        ranges,
         committer_config,
         skip_watermark,
         watermark_rx,
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         cancel,
     );
 
     let reader_watermark = reader_watermark::<H>(
         pruner_config.clone(),
-        db.clone(),
+        store.clone(),
         metrics.clone(),
         pruner_cancel.clone(),
     );
 
-    let pruner = pruner(handler, pruner_config, db, metrics, pruner_cancel.clone());
+    let pruner = pruner(
+        handler,
+        pruner_config,
+        store,
+        metrics,
+        pruner_cancel.clone(),
+    );
 
     tokio::spawn(async move {
         let (_, _, _, _) = futures::join!(processor, collector, committer, commit_watermark);
@@ -275,6 +296,25 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     })
 }
 
+/// Process `checkpoint` with `handler` and write all the resulting rows to the `store`, in chunks,
+/// without involving the rest of the pipeline, or updating its watermarks. Returns the number of
+/// rows affected.
+pub(crate) async fn replay<H: Handler>(
+    handler: &H,
+    checkpoint: &Arc<CheckpointData>,
+    store: &H::Store,
+) -> anyhow::Result<usize> {
+    let values = handler.process(checkpoint)?;
+
+    let mut conn = store.connect().await?;
+    let mut affected = 0;
+    for chunk in values.chunks(max_chunk_rows::<H>()) {
+        affected += H::commit(chunk, &mut conn).await?;
+    }
+
+    Ok(affected)
+}
+
 const fn max_chunk_rows<H: Handler>() -> usize {
     if H::Value::FIELD_COUNT == 0 {
         i16::MAX as usize
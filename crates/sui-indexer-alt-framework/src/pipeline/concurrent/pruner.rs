@@ -5,7 +5,6 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
-use sui_pg_db::Db;
 use tokio::{
     sync::Semaphore,
     task::JoinHandle,
@@ -16,8 +15,8 @@ use tracing::{debug, error, info, warn};
 
 use crate::{
     metrics::IndexerMetrics,
-    models::watermarks::PrunerWatermark,
     pipeline::logging::{LoggerWatermark, WatermarkLogger},
+    store::{Connection, Store},
 };
 
 use super::{Handler, PrunerConfig};
@@ -79,11 +78,11 @@ impl PendingRanges {
     }
 }
 
-/// The pruner task is responsible for deleting old data from the database. It will periodically
-/// check the `watermarks` table to see if there is any data that should be pruned between the
+/// The pruner task is responsible for deleting old data from the store. It will periodically
+/// check the pipeline's watermark to see if there is any data that should be pruned between the
 /// `pruner_hi` (inclusive), and `reader_lo` (exclusive) checkpoints. This task will also provide a
 /// mapping of the pruned checkpoints to their corresponding epoch and tx, which the handler can
-/// then use to delete the corresponding data from the database.
+/// then use to delete the corresponding data from the store.
 ///
 /// To ensure that the pruner does not interfere with reads that are still in flight, it respects
 /// the watermark's `pruner_timestamp`, which records the time that `reader_lo` was last updated.
@@ -98,7 +97,7 @@ impl PendingRanges {
 pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
     handler: Arc<H>,
     config: Option<PrunerConfig>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -142,12 +141,12 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
                         .with_label_values(&[H::NAME])
                         .start_timer();
 
-                    let Ok(mut conn) = db.connect().await else {
+                    let Ok(mut conn) = store.connect().await else {
                         warn!(pipeline = H::NAME, "Pruner failed to connect, while fetching watermark");
                         continue;
                     };
 
-                    match PrunerWatermark::get(&mut conn, H::NAME, config.delay()).await {
+                    match conn.pruner_watermark(H::NAME, config.delay()).await {
                         Ok(Some(current)) => {
                             guard.stop_and_record();
                             current
@@ -205,7 +204,7 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
             for (from, to_exclusive) in pending_prune_ranges.iter() {
                 let semaphore = semaphore.clone();
                 let cancel = cancel.child_token();
-                let db = db.clone();
+                let store = store.clone();
                 let metrics = metrics.clone();
                 let handler = handler.clone();
 
@@ -218,7 +217,7 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
                             return ((from, to_exclusive), Err(anyhow::anyhow!("Cancelled")));
                         }
                     };
-                    let result = prune_task_impl(metrics, db, handler, from, to_exclusive).await;
+                    let result = prune_task_impl(metrics, store, handler, from, to_exclusive).await;
                     ((from, to_exclusive), result)
                 }));
             }
@@ -253,7 +252,7 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
                 .with_label_values(&[H::NAME])
                 .start_timer();
 
-            let Ok(mut conn) = db.connect().await else {
+            let Ok(mut conn) = store.connect().await else {
                 warn!(
                     pipeline = H::NAME,
                     "Pruner failed to connect, while updating watermark"
@@ -261,7 +260,7 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
                 continue;
             };
 
-            match db_watermark.update(&mut conn).await {
+            match conn.set_pruner_watermark(&db_watermark).await {
                 Err(e) => {
                     let elapsed = guard.stop_and_record();
                     error!(
@@ -290,7 +289,7 @@ pub(super) fn pruner<H: Handler + Send + Sync + 'static>(
 
 async fn prune_task_impl<H: Handler + Send + Sync + 'static>(
     metrics: Arc<IndexerMetrics>,
-    db: Db,
+    store: H::Store,
     handler: Arc<H>,
     from: u64,
     to_exclusive: u64,
@@ -305,7 +304,7 @@ async fn prune_task_impl<H: Handler + Send + Sync + 'static>(
         .with_label_values(&[H::NAME])
         .start_timer();
 
-    let mut conn = db.connect().await?;
+    let mut conn = store.connect().await?;
 
     debug!(pipeline = H::NAME, "Pruning from {from} to {to_exclusive}");
 
@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use backoff::ExponentialBackoff;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, warn};
+
+use crate::{
+    metrics::IndexerMetrics,
+    models::dead_letters::DeadLetter,
+    pipeline::Break,
+    store::{Connection, Store},
+};
+
+/// If recording a dead letter fails, it is retried after waiting this long initially.
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// If recording a dead letter fails, it is retried after waiting at most this long.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a pipeline does when it repeatedly fails to process or commit data for a checkpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(
+    tag = "kind",
+    rename_all = "kebab-case",
+    rename_all_fields = "kebab-case"
+)]
+pub enum FailurePolicy {
+    /// Stop the pipeline as soon as processing a checkpoint fails, and retry failed commits
+    /// indefinitely.
+    #[default]
+    Halt,
+
+    /// Retry processing or committing a checkpoint up to `max_attempts` times in total, and stop
+    /// the pipeline if it still fails.
+    Retry { max_attempts: usize },
+
+    /// Retry processing or committing a checkpoint up to `max_attempts` times in total, and if it
+    /// still fails, record it in the dead letter table and carry on as though it had been indexed,
+    /// so that the pipeline's watermark can advance past it. Dead-lettered checkpoints can be
+    /// replayed later, once the issue has been fixed.
+    ///
+    /// Sequential pipelines cannot skip checkpoints, so they treat this policy like
+    /// [FailurePolicy::Retry].
+    DeadLetter { max_attempts: usize },
+}
+
+impl FailurePolicy {
+    /// The number of times to try processing a checkpoint before giving up on it.
+    pub(crate) fn process_attempts(&self) -> usize {
+        match self {
+            FailurePolicy::Halt => 1,
+            FailurePolicy::Retry { max_attempts } | FailurePolicy::DeadLetter { max_attempts } => {
+                (*max_attempts).max(1)
+            }
+        }
+    }
+
+    /// The number of times to try committing a batch before giving up on it, or `None` if the
+    /// pipeline should never give up.
+    pub(crate) fn commit_attempts(&self) -> Option<usize> {
+        match self {
+            FailurePolicy::Halt => None,
+            FailurePolicy::Retry { max_attempts } | FailurePolicy::DeadLetter { max_attempts } => {
+                Some((*max_attempts).max(1))
+            }
+        }
+    }
+
+    /// Whether checkpoints that the pipeline gives up on should be skipped over, rather than
+    /// stopping the pipeline.
+    pub(crate) fn is_dead_letter(&self) -> bool {
+        matches!(self, FailurePolicy::DeadLetter { .. })
+    }
+
+    /// The policy to use for a pipeline that cannot skip checkpoints.
+    pub(crate) fn without_dead_letters(self) -> Self {
+        match self {
+            FailurePolicy::DeadLetter { max_attempts } => FailurePolicy::Retry { max_attempts },
+            policy => policy,
+        }
+    }
+}
+
+/// Record that `pipeline` gave up on `checkpoint` at `stage` because of `error`. Writing to the
+/// store is retried with an exponential back-off until it succeeds, because the pipeline must not
+/// advance past the checkpoint without a record of it. Returns early if the pipeline is cancelled
+/// in the meantime.
+pub(super) async fn record_dead_letter<S: Store>(
+    store: &S,
+    pipeline: &'static str,
+    checkpoint: u64,
+    stage: &'static str,
+    error: &anyhow::Error,
+    metrics: &IndexerMetrics,
+    cancel: &CancellationToken,
+) -> Result<(), Break> {
+    error!(
+        pipeline,
+        checkpoint, stage, "Giving up on checkpoint: {error:#}"
+    );
+
+    let letter = DeadLetter {
+        pipeline: pipeline.into(),
+        checkpoint: checkpoint as i64,
+        stage: stage.into(),
+        error: format!("{error:#}"),
+    };
+
+    let backoff = ExponentialBackoff {
+        initial_interval: INITIAL_RETRY_INTERVAL,
+        current_interval: INITIAL_RETRY_INTERVAL,
+        max_interval: MAX_RETRY_INTERVAL,
+        max_elapsed_time: None,
+        ..Default::default()
+    };
+
+    let record = || async {
+        let recorded = match store.connect().await {
+            Ok(mut conn) => conn.record_dead_letter(&letter).await,
+            Err(e) => Err(e),
+        };
+
+        recorded.map_err(|e| {
+            warn!(pipeline, checkpoint, "Failed to record dead letter: {e}");
+            backoff::Error::transient(e)
+        })
+    };
+
+    tokio::select! {
+        _ = cancel.cancelled() => return Err(Break::Cancel),
+        recorded = backoff::future::retry(backoff, record) => recorded?,
+    }
+
+    metrics
+        .total_dead_letters
+        .with_label_values(&[pipeline, stage])
+        .inc();
+
+    Ok(())
+}
@@ -3,12 +3,14 @@
 
 use std::time::Duration;
 
+pub use failure::FailurePolicy;
 pub use processor::Processor;
 use serde::{Deserialize, Serialize};
 
 use crate::models::watermarks::CommitterWatermark;
 
 pub mod concurrent;
+mod failure;
 mod logging;
 mod processor;
 pub mod sequential;
@@ -36,6 +38,9 @@ pub struct CommitterConfig {
 
     /// Watermark task will check for pending watermarks this often, in milliseconds.
     pub watermark_interval_ms: u64,
+
+    /// What to do when processing or committing a checkpoint keeps failing.
+    pub failure_policy: FailurePolicy,
 }
 
 /// Processed values associated with a single checkpoint. This is an internal type used to
@@ -152,6 +157,7 @@ impl Default for CommitterConfig {
             write_concurrency: 5,
             collect_interval_ms: 500,
             watermark_interval_ms: 500,
+            failure_policy: FailurePolicy::Halt,
         }
     }
 }
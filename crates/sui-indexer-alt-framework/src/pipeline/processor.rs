@@ -1,21 +1,32 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
+use backoff::ExponentialBackoff;
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     metrics::{CheckpointLagMetricReporter, IndexerMetrics},
     pipeline::Break,
+    store::Store,
     task::TrySpawnStreamExt,
 };
 
-use super::IndexedCheckpoint;
+use super::{
+    failure::{record_dead_letter, FailurePolicy},
+    IndexedCheckpoint,
+};
+
+/// If processing a checkpoint needs to be retried, wait this long initially.
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// If processing a checkpoint needs to be retried, wait at most this long between retries.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Implementors of this trait are responsible for transforming checkpoint into rows for their
 /// table. The `FANOUT` associated value controls how many concurrent workers will be used to
@@ -41,12 +52,19 @@ pub trait Processor {
 /// Each worker processes a checkpoint into rows and sends them on to the committer using the `tx`
 /// channel.
 ///
-/// The task will shutdown if the `cancel` token is cancelled, or if any of the workers encounters
-/// an error -- there is no retry logic at this level.
-pub(super) fn processor<P: Processor + Send + Sync + 'static>(
+/// How failures to process a checkpoint are handled depends on the `policy`: Processing can be
+/// retried a limited number of times, after which the checkpoint is either recorded as a dead
+/// letter in the `store` (and sent on with no rows, so that the pipeline can make progress past
+/// it), or the task shuts down.
+///
+/// The task will shutdown if the `cancel` token is cancelled, or if any of the workers gives up on
+/// a checkpoint without recording it as a dead letter.
+pub(super) fn processor<P: Processor + Send + Sync + 'static, S: Store>(
     processor: Arc<P>,
+    policy: FailurePolicy,
     rx: mpsc::Receiver<Arc<CheckpointData>>,
     tx: mpsc::Sender<IndexedCheckpoint<P>>,
+    store: S,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -65,6 +83,7 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                 let cancel = cancel.clone();
                 let checkpoint_lag_reporter = checkpoint_lag_reporter.clone();
                 let processor = processor.clone();
+                let store = store.clone();
 
                 async move {
                     if cancel.is_cancelled() {
@@ -81,11 +100,70 @@ pub(super) fn processor<P: Processor + Send + Sync + 'static>(
                         .with_label_values(&[P::NAME])
                         .start_timer();
 
-                    let values = processor.process(&checkpoint)?;
-                    let elapsed = guard.stop_and_record();
-
                     let epoch = checkpoint.checkpoint_summary.epoch;
                     let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
+
+                    let backoff = ExponentialBackoff {
+                        initial_interval: INITIAL_RETRY_INTERVAL,
+                        current_interval: INITIAL_RETRY_INTERVAL,
+                        max_interval: MAX_RETRY_INTERVAL,
+                        max_elapsed_time: None,
+                        ..Default::default()
+                    };
+
+                    let mut attempt = 0;
+                    let process = || {
+                        attempt += 1;
+                        let attempt = attempt;
+                        let processor = processor.clone();
+                        let checkpoint = checkpoint.clone();
+                        async move {
+                            processor.process(&checkpoint).map_err(|e| {
+                                if attempt >= policy.process_attempts() {
+                                    return backoff::Error::permanent(e);
+                                }
+
+                                warn!(
+                                    pipeline = P::NAME,
+                                    checkpoint = cp_sequence_number,
+                                    attempt,
+                                    "Error processing checkpoint: {e}",
+                                );
+
+                                backoff::Error::transient(e)
+                            })
+                        }
+                    };
+
+                    let processed = tokio::select! {
+                        _ = cancel.cancelled() => return Err(Break::Cancel),
+                        processed = backoff::future::retry(backoff, process) => processed,
+                    };
+
+                    let elapsed = guard.stop_and_record();
+
+                    let values = match processed {
+                        Ok(values) => values,
+
+                        // Give up on the checkpoint, but send it on with no rows, so that the
+                        // pipeline's watermark can advance past it.
+                        Err(e) if policy.is_dead_letter() => {
+                            record_dead_letter(
+                                &store,
+                                P::NAME,
+                                cp_sequence_number,
+                                "process",
+                                &e,
+                                &metrics,
+                                &cancel,
+                            )
+                            .await?;
+
+                            vec![]
+                        }
+
+                        Err(e) => return Err(Break::Err(e)),
+                    };
                     let tx_hi = checkpoint.checkpoint_summary.network_total_transactions;
                     let timestamp_ms = checkpoint.checkpoint_summary.timestamp_ms;
 
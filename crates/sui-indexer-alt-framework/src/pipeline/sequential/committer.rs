@@ -3,25 +3,25 @@
 
 use std::{cmp::Ordering, collections::BTreeMap, sync::Arc};
 
-use diesel_async::{scoped_futures::ScopedFutureExt, AsyncConnection};
-use sui_pg_db::Db;
+use diesel_async::scoped_futures::ScopedFutureExt;
 use tokio::{
     sync::mpsc,
     task::JoinHandle,
     time::{interval, MissedTickBehavior},
 };
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     metrics::IndexerMetrics,
     models::watermarks::CommitterWatermark,
     pipeline::{logging::WatermarkLogger, IndexedCheckpoint, WARN_PENDING_WATERMARKS},
+    store::{Connection, TransactionalStore},
 };
 
 use super::{Handler, SequentialConfig};
 
-/// The committer task gathers rows into batches and writes them to the database.
+/// The committer task gathers rows into batches and writes them to the store.
 ///
 /// Data arrives out of order, grouped by checkpoint, on `rx`. The task orders them and waits to
 /// write them until either a configural polling interval has passed (controlled by
@@ -30,13 +30,14 @@ use super::{Handler, SequentialConfig};
 ///
 /// Writes are performed on checkpoint boundaries (more than one checkpoint can be present in a
 /// single write), in a single transaction that includes all row updates and an update to the
-/// watermark table.
+/// pipeline's watermark.
 ///
 /// The committer can be configured to lag behind the ingestion service by a fixed number of
 /// checkpoints (configured by `checkpoint_lag`). A value of `0` means no lag.
 ///
 /// Upon successful write, the task sends its new watermark back to the ingestion service, to
-/// unblock its regulator.
+/// unblock its regulator. Failed writes are retried, until they succeed or the pipeline's failure
+/// policy runs out of attempts, in which case the whole indexer is shut down.
 ///
 /// The task can be shutdown using its `cancel` token or if either of its channels are closed.
 pub(super) fn committer<H: Handler + 'static>(
@@ -44,7 +45,7 @@ pub(super) fn committer<H: Handler + 'static>(
     watermark: Option<CommitterWatermark<'static>>,
     mut rx: mpsc::Receiver<IndexedCheckpoint<H>>,
     tx: mpsc::UnboundedSender<(&'static str, u64)>,
-    db: Db,
+    store: H::Store,
     metrics: Arc<IndexerMetrics>,
     cancel: CancellationToken,
 ) -> JoinHandle<()> {
@@ -217,29 +218,16 @@ pub(super) fn committer<H: Handler + 'static>(
                         .with_label_values(&[H::NAME])
                         .start_timer();
 
-                    let Ok(mut conn) = db.connect().await else {
-                        warn!(pipeline = H::NAME, "Failed to get connection for DB");
-                        metrics
-                            .total_committer_batches_failed
-                            .with_label_values(&[H::NAME])
-                            .inc();
-                        continue;
-                    };
-
                     // Write all the object updates out along with the watermark update, in a
                     // single transaction. The handler's `commit` implementation is responsible for
                     // chunking up the writes into a manageable size.
-                    let affected = conn.transaction::<_, anyhow::Error, _>(|conn| async {
+                    let affected = store.transaction(|conn| async {
                         // TODO: If initial_watermark is empty, when we update watermark
                         // for the first time, we should also update the low watermark.
-                        watermark.update(conn).await?;
+                        conn.set_committer_watermark(&watermark).await?;
                         H::commit(&batch, conn).await
                     }.scope_boxed()).await;
 
-                    // Drop the connection eagerly to avoid it holding on to references borrowed by
-                    // the transaction closure.
-                    drop(conn);
-
                     let elapsed = guard.stop_and_record();
 
                     let affected = match affected {
@@ -261,6 +249,22 @@ pub(super) fn committer<H: Handler + 'static>(
                                 .inc();
 
                             attempt += 1;
+                            if config
+                                .committer
+                                .failure_policy
+                                .commit_attempts()
+                                .is_some_and(|max| attempt >= max)
+                            {
+                                error!(
+                                    pipeline = H::NAME,
+                                    attempt,
+                                    "Giving up on writing batch, stopping committer",
+                                );
+
+                                cancel.cancel();
+                                break;
+                            }
+
                             continue;
                         }
                     };
@@ -4,14 +4,18 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use sui_pg_db::{self as db, Db};
 use sui_types::full_checkpoint_content::CheckpointData;
 use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 use super::{processor::processor, CommitterConfig, Processor, PIPELINE_BUFFER};
 
-use crate::{metrics::IndexerMetrics, models::watermarks::CommitterWatermark};
+use crate::{
+    metrics::IndexerMetrics,
+    models::watermarks::CommitterWatermark,
+    store::{Store, TransactionalStore},
+};
 
 use self::committer::committer;
 
@@ -19,7 +23,7 @@ mod committer;
 
 /// Handlers implement the logic for a given indexing pipeline: How to process checkpoint data (by
 /// implementing [Processor]) into rows for their table, how to combine multiple rows into a single
-/// DB operation, and then how to write those rows atomically to the database.
+/// DB operation, and then how to write those rows atomically to the store.
 ///
 /// The handler is also responsible for tuning the various parameters of the pipeline (provided as
 /// associated values).
@@ -36,6 +40,10 @@ mod committer;
 /// checkpoints that can be received before the next checkpoint.
 #[async_trait::async_trait]
 pub trait Handler: Processor {
+    /// The storage backend that this pipeline writes its rows and watermark to. Writes to the
+    /// store must be transactional, so that the watermark can be updated atomically with the data.
+    type Store: TransactionalStore;
+
     /// If at least this many rows are pending, the committer will commit them eagerly.
     const MIN_EAGER_ROWS: usize = 50;
 
@@ -53,9 +61,12 @@ pub trait Handler: Processor {
     /// guaranteed to be presented to the batch in checkpoint order.
     fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>);
 
-    /// Take a batch of values and commit them to the database, returning the number of rows
+    /// Take a batch of values and commit them to the store, returning the number of rows
     /// affected.
-    async fn commit(batch: &Self::Batch, conn: &mut db::Connection<'_>) -> anyhow::Result<usize>;
+    async fn commit<'a>(
+        batch: &Self::Batch,
+        conn: &mut <Self::Store as Store>::Connection<'a>,
+    ) -> anyhow::Result<usize>;
 }
 
 /// Configuration for a sequential pipeline
@@ -96,8 +107,8 @@ pub struct SequentialConfig {
 pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
     handler: H,
     initial_watermark: Option<CommitterWatermark<'static>>,
-    config: SequentialConfig,
-    db: Db,
+    mut config: SequentialConfig,
+    store: H::Store,
     checkpoint_rx: mpsc::Receiver<Arc<CheckpointData>>,
     watermark_tx: mpsc::UnboundedSender<(&'static str, u64)>,
     metrics: Arc<IndexerMetrics>,
@@ -105,10 +116,24 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
 ) -> JoinHandle<()> {
     let (processor_tx, committer_rx) = mpsc::channel(H::FANOUT + PIPELINE_BUFFER);
 
+    // Sequential pipelines write checkpoints in order, so they cannot skip over a checkpoint that
+    // they failed to index.
+    let policy = config.committer.failure_policy;
+    if policy.is_dead_letter() {
+        warn!(
+            pipeline = H::NAME,
+            "Sequential pipelines cannot dead-letter checkpoints, retrying failures instead",
+        );
+    }
+
+    config.committer.failure_policy = policy.without_dead_letters();
+
     let processor = processor(
         Arc::new(handler),
+        config.committer.failure_policy,
         checkpoint_rx,
         processor_tx,
+        store.clone(),
         metrics.clone(),
         cancel.clone(),
     );
@@ -118,7 +143,7 @@ pub(crate) fn pipeline<H: Handler + Send + Sync + 'static>(
         initial_watermark,
         committer_rx,
         watermark_tx,
-        db.clone(),
+        store,
         metrics.clone(),
         cancel.clone(),
     );
@@ -2,6 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    backfill_watermarks (pipeline, range_lo) {
+        pipeline -> Text,
+        range_lo -> Int8,
+        range_hi -> Int8,
+        epoch_hi_inclusive -> Int8,
+        checkpoint_hi_inclusive -> Int8,
+        tx_hi -> Int8,
+        timestamp_ms_hi_inclusive -> Int8,
+    }
+}
+
 diesel::table! {
     cp_sequence_numbers (cp_sequence_number) {
         cp_sequence_number -> Int8,
@@ -10,6 +22,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    dead_letters (pipeline, checkpoint) {
+        pipeline -> Text,
+        checkpoint -> Int8,
+        stage -> Text,
+        error -> Text,
+        recorded_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     watermarks (pipeline) {
         pipeline -> Text,
@@ -23,4 +45,9 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(cp_sequence_numbers, watermarks,);
+diesel::allow_tables_to_appear_in_same_query!(
+    backfill_watermarks,
+    cp_sequence_numbers,
+    dead_letters,
+    watermarks,
+);
@@ -0,0 +1,411 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use anyhow::bail;
+use diesel_async::scoped_futures::ScopedBoxFuture;
+
+use crate::models::{
+    dead_letters::DeadLetter,
+    watermarks::{BackfillWatermark, CommitterWatermark, PrunerWatermark, ReaderWatermark},
+};
+
+mod pg;
+
+/// A storage backend that pipelines write their data and watermarks to. The indexer framework
+/// only interacts with the store through this trait and [Connection], so the same processing,
+/// batching, watermarking and pruning machinery can be used to write to sinks other than Postgres
+/// (e.g. an embedded key-value store, or files on disk), by implementing these traits.
+///
+/// The Postgres connection pool, [sui_pg_db::Db], is a [Store].
+#[async_trait::async_trait]
+pub trait Store: Send + Sync + Clone + 'static {
+    /// A connection to the store, which handlers write their data through, and which the
+    /// framework uses to read and write watermarks.
+    type Connection<'c>: Connection
+    where
+        Self: 'c;
+
+    /// Get a connection to the store. Connections are expected to be short-lived: they are
+    /// acquired for each write, and dropped after.
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>>;
+}
+
+/// A [Store] that supports atomically applying multiple writes. Sequential pipelines require a
+/// transactional store, because they update their watermark atomically with the data they write.
+#[async_trait::async_trait]
+pub trait TransactionalStore: Store {
+    /// Run `f` against a connection to the store, so that either all the writes it performs
+    /// through that connection are applied (if it succeeds), or none of them are (if it fails).
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>;
+}
+
+/// Operations on watermarks that the indexer framework needs every [Store] to support, through
+/// its connections. Watermarks are tracked per pipeline, and each pipeline owns a single row (or
+/// equivalent) that holds its committer, reader and pruner watermarks.
+#[async_trait::async_trait]
+pub trait Connection: Send {
+    /// The high watermark for `pipeline`: the checkpoint up to which all its data has been
+    /// written, or `None` if the pipeline has not written any data yet.
+    async fn committer_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<CommitterWatermark<'static>>>;
+
+    /// The low watermark for `pipeline`: the checkpoint from which readers are guaranteed to find
+    /// its data, or `None` if the pipeline has no watermark yet.
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<ReaderWatermark<'static>>>;
+
+    /// The region that the pruner for `pipeline` still has to prune, along with how long it needs
+    /// to wait before it can act on that information, given that it must wait `delay` after the
+    /// reader watermark was last updated. Returns `None` if the pipeline has no watermark yet.
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<PrunerWatermark<'static>>>;
+
+    /// Set the high watermark for the watermark's pipeline, as long as this raises the existing
+    /// watermark (or there is no existing watermark). Returns whether the watermark was updated.
+    async fn set_committer_watermark(
+        &mut self,
+        watermark: &CommitterWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Set the low watermark for an existing pipeline, as long as this raises the watermark, and
+    /// record the time at which this update happened (according to the store). Returns whether the
+    /// watermark was updated.
+    async fn set_reader_watermark(
+        &mut self,
+        watermark: &ReaderWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// Set how far the pruner has pruned for an existing pipeline. Returns whether the watermark
+    /// was updated.
+    async fn set_pruner_watermark(
+        &mut self,
+        watermark: &PrunerWatermark<'_>,
+    ) -> anyhow::Result<bool>;
+
+    /// All the ranges that `pipeline` is indexing ahead of its high watermark, ordered by their
+    /// lower bound. Stores that do not support parallel backfills have no ranges.
+    async fn backfill_watermarks(
+        &mut self,
+        _pipeline: &'static str,
+    ) -> anyhow::Result<Vec<BackfillWatermark<'static>>> {
+        Ok(vec![])
+    }
+
+    /// Start tracking new `ranges`, ignoring any that are already being tracked. Returns the
+    /// number of ranges that were added.
+    async fn insert_backfill_watermarks(
+        &mut self,
+        _ranges: &[BackfillWatermark<'_>],
+    ) -> anyhow::Result<usize> {
+        bail!("Store does not support backfilling in parallel")
+    }
+
+    /// Update the progress through an existing range, as long as it raises its watermark. Returns
+    /// whether the watermark was updated.
+    async fn set_backfill_watermark(
+        &mut self,
+        _range: &BackfillWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        bail!("Store does not support backfilling in parallel")
+    }
+
+    /// Stop tracking `range`. Returns whether the range was being tracked.
+    async fn delete_backfill_watermark(
+        &mut self,
+        _range: &BackfillWatermark<'_>,
+    ) -> anyhow::Result<bool> {
+        bail!("Store does not support backfilling in parallel")
+    }
+
+    /// Record that a pipeline gave up on indexing a checkpoint, replacing any existing record for
+    /// the same pipeline and checkpoint.
+    async fn record_dead_letter(&mut self, _letter: &DeadLetter<'_>) -> anyhow::Result<()> {
+        bail!("Store does not support dead letters")
+    }
+
+    /// Forget that `pipeline` gave up on indexing `checkpoint`, once it has been replayed.
+    /// Returns whether there was a record to forget.
+    async fn delete_dead_letter(
+        &mut self,
+        _pipeline: &'static str,
+        _checkpoint: u64,
+    ) -> anyhow::Result<bool> {
+        bail!("Store does not support dead letters")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        sync::{Arc, Mutex},
+    };
+
+    use sui_field_count::FieldCount;
+    use sui_storage::blob::Blob;
+    use sui_types::full_checkpoint_content::CheckpointData;
+    use tokio::sync::mpsc;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{
+        ingestion::test_utils::test_checkpoint_data,
+        metrics::tests::test_metrics,
+        pipeline::{
+            concurrent::{self, ConcurrentConfig},
+            CommitterConfig, FailurePolicy, Processor,
+        },
+    };
+
+    use super::*;
+
+    /// An in-memory store, that only remembers which checkpoints it has seen rows for, the
+    /// pipeline's committer watermark, and the checkpoints it gave up on.
+    #[derive(Clone, Default)]
+    struct MockStore(Arc<Mutex<MockData>>);
+
+    #[derive(Default)]
+    struct MockData {
+        checkpoints: BTreeSet<u64>,
+        watermark: Option<CommitterWatermark<'static>>,
+        dead_letters: BTreeSet<(u64, String)>,
+    }
+
+    struct MockConnection<'c>(&'c MockStore);
+
+    #[derive(FieldCount)]
+    struct Row {
+        checkpoint: u64,
+    }
+
+    struct Checkpoints;
+
+    /// Like [Checkpoints], but fails to process one checkpoint.
+    struct FailsOn(u64);
+
+    #[async_trait::async_trait]
+    impl Store for MockStore {
+        type Connection<'c> = MockConnection<'c>;
+
+        async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+            Ok(MockConnection(self))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Connection for MockConnection<'_> {
+        async fn committer_watermark(
+            &mut self,
+            _pipeline: &'static str,
+        ) -> anyhow::Result<Option<CommitterWatermark<'static>>> {
+            Ok(self.0 .0.lock().unwrap().watermark.clone())
+        }
+
+        async fn reader_watermark(
+            &mut self,
+            _pipeline: &'static str,
+        ) -> anyhow::Result<Option<ReaderWatermark<'static>>> {
+            Ok(None)
+        }
+
+        async fn pruner_watermark(
+            &mut self,
+            _pipeline: &'static str,
+            _delay: Duration,
+        ) -> anyhow::Result<Option<PrunerWatermark<'static>>> {
+            Ok(None)
+        }
+
+        async fn set_committer_watermark(
+            &mut self,
+            watermark: &CommitterWatermark<'_>,
+        ) -> anyhow::Result<bool> {
+            let mut data = self.0 .0.lock().unwrap();
+            if data
+                .watermark
+                .as_ref()
+                .is_some_and(|w| w.checkpoint_hi_inclusive >= watermark.checkpoint_hi_inclusive)
+            {
+                return Ok(false);
+            }
+
+            data.watermark = Some(CommitterWatermark {
+                pipeline: watermark.pipeline.clone().into_owned().into(),
+                ..*watermark
+            });
+
+            Ok(true)
+        }
+
+        async fn set_reader_watermark(
+            &mut self,
+            _watermark: &ReaderWatermark<'_>,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn set_pruner_watermark(
+            &mut self,
+            _watermark: &PrunerWatermark<'_>,
+        ) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn record_dead_letter(&mut self, letter: &DeadLetter<'_>) -> anyhow::Result<()> {
+            let mut data = self.0 .0.lock().unwrap();
+            data.dead_letters
+                .insert((letter.checkpoint as u64, letter.stage.to_string()));
+            Ok(())
+        }
+    }
+
+    impl Processor for Checkpoints {
+        const NAME: &'static str = "checkpoints";
+
+        type Value = Row;
+
+        fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Row>> {
+            Ok(vec![Row {
+                checkpoint: checkpoint.checkpoint_summary.sequence_number,
+            }])
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl concurrent::Handler for Checkpoints {
+        type Store = MockStore;
+
+        async fn commit(values: &[Row], conn: &mut MockConnection<'_>) -> anyhow::Result<usize> {
+            let mut data = conn.0 .0.lock().unwrap();
+            data.checkpoints
+                .extend(values.iter().map(|row| row.checkpoint));
+            Ok(values.len())
+        }
+    }
+
+    impl Processor for FailsOn {
+        const NAME: &'static str = "fails_on";
+
+        type Value = Row;
+
+        fn process(&self, checkpoint: &Arc<CheckpointData>) -> anyhow::Result<Vec<Row>> {
+            let checkpoint = checkpoint.checkpoint_summary.sequence_number;
+            anyhow::ensure!(checkpoint != self.0, "Failed to process {checkpoint}");
+            Ok(vec![Row { checkpoint }])
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl concurrent::Handler for FailsOn {
+        type Store = MockStore;
+
+        async fn commit(values: &[Row], conn: &mut MockConnection<'_>) -> anyhow::Result<usize> {
+            <Checkpoints as concurrent::Handler>::commit(values, conn).await
+        }
+    }
+
+    /// A concurrent pipeline can write its data and its watermark to a store that is not backed by
+    /// Postgres.
+    #[tokio::test]
+    async fn concurrent_pipeline_with_custom_store() {
+        let store = MockStore::default();
+        let (tx, rx) = mpsc::channel(10);
+
+        let config = ConcurrentConfig {
+            committer: CommitterConfig {
+                collect_interval_ms: 10,
+                watermark_interval_ms: 10,
+                ..Default::default()
+            },
+            pruner: None,
+        };
+
+        let pipeline = concurrent::pipeline(
+            Checkpoints,
+            None,
+            vec![],
+            config,
+            false,
+            store.clone(),
+            rx,
+            test_metrics(),
+            CancellationToken::new(),
+        );
+
+        for cp in 0..5 {
+            let checkpoint: CheckpointData = Blob::from_bytes(&test_checkpoint_data(cp)).unwrap();
+            tx.send(Arc::new(checkpoint)).await.unwrap();
+        }
+
+        // The pipeline winds down once it has written everything it was sent.
+        drop(tx);
+        tokio::time::timeout(Duration::from_secs(10), pipeline)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let data = store.0.lock().unwrap();
+        assert_eq!(data.checkpoints, (0..5).collect());
+        assert_eq!(data.watermark.as_ref().unwrap().checkpoint_hi_inclusive, 4);
+    }
+
+    /// A pipeline that is allowed to dead-letter checkpoints records the checkpoint it could not
+    /// process, and its watermark advances past it.
+    #[tokio::test]
+    async fn concurrent_pipeline_dead_letters_failing_checkpoint() {
+        let store = MockStore::default();
+        let (tx, rx) = mpsc::channel(10);
+
+        let config = ConcurrentConfig {
+            committer: CommitterConfig {
+                collect_interval_ms: 10,
+                watermark_interval_ms: 10,
+                failure_policy: FailurePolicy::DeadLetter { max_attempts: 2 },
+                ..Default::default()
+            },
+            pruner: None,
+        };
+
+        let pipeline = concurrent::pipeline(
+            FailsOn(2),
+            None,
+            vec![],
+            config,
+            false,
+            store.clone(),
+            rx,
+            test_metrics(),
+            CancellationToken::new(),
+        );
+
+        for cp in 0..5 {
+            let checkpoint: CheckpointData = Blob::from_bytes(&test_checkpoint_data(cp)).unwrap();
+            tx.send(Arc::new(checkpoint)).await.unwrap();
+        }
+
+        drop(tx);
+        tokio::time::timeout(Duration::from_secs(10), pipeline)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let data = store.0.lock().unwrap();
+        assert_eq!(data.checkpoints, [0, 1, 3, 4].into());
+        assert_eq!(data.dead_letters, [(2, "process".to_owned())].into());
+        assert_eq!(data.watermark.as_ref().unwrap().checkpoint_hi_inclusive, 4);
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use diesel::{
+    dsl::{exists, not},
+    BoolExpressionMethods, ExpressionMethods, QueryDsl,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sui_indexer_alt_schema::{objects::StoredCoinOwnerKind, schema::coin_balance_buckets};
+use sui_json_rpc_types::{Balance, Coin, Page};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    gas_coin::GAS,
+    object::Object,
+    parse_sui_type_tag, TypeTag,
+};
+
+use crate::{
+    context::Context,
+    error::{internal_error, invalid_params, InternalContext, RpcError},
+    paginate::{Cursor, Page as PageParams},
+};
+
+use super::{objects::response::latest_objects, rpc_module::RpcModule};
+
+#[open_rpc(namespace = "suix", tag = "Coins API")]
+#[rpc(server, namespace = "suix")]
+trait CoinsApi {
+    /// Return the coins of a given type owned by an address, as a paginated list. Coins with larger
+    /// balances are returned first, although coins whose balances are within the same order of
+    /// magnitude are ordered by their object ID.
+    #[method(name = "getCoins")]
+    async fn get_coins(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+        /// The type of coin to return, e.g. `0x2::sui::SUI` (the default if not specified).
+        coin_type: Option<String>,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of coins to return per page.
+        limit: Option<usize>,
+    ) -> RpcResult<Page<Coin, String>>;
+
+    /// Return the total balance of coins of a given type owned by an address.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+        /// The type of coin to sum, e.g. `0x2::sui::SUI` (the default if not specified).
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance>;
+
+    /// Return the total balances of all coin types owned by an address, ordered by coin type.
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(
+        &self,
+        /// The owner's Sui address.
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<Balance>>;
+}
+
+pub(crate) struct Coins(pub Context, pub CoinsConfig);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoinsConfig {
+    /// The default page size limit when querying coins, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying coins. Requesting a page larger than this is
+    /// a user error.
+    pub max_page_size: usize,
+
+    /// The number of coins whose latest versions are loaded at a time when summing balances.
+    pub balances_page_size: usize,
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+
+    #[error("Invalid coin type {0:?}: {1}")]
+    BadCoinType(String, anyhow::Error),
+}
+
+#[async_trait::async_trait]
+impl CoinsApiServer for Coins {
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<Page<Coin, String>> {
+        let Self(ctx, config) = self;
+        let coin_type = parse_coin_type(coin_type)?;
+        Ok(coins_response(ctx, config, owner, coin_type, cursor, limit)
+            .await
+            .with_internal_context(|| format!("Failed to get coins owned by {owner}"))?)
+    }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let Self(ctx, config) = self;
+        let coin_type = parse_coin_type(coin_type)?;
+        let mut balances = balances_response(ctx, config, owner, Some(&coin_type))
+            .await
+            .with_internal_context(|| format!("Failed to get balance for {owner}"))?;
+
+        Ok(balances.pop().unwrap_or_else(|| {
+            Balance::zero(coin_type.to_canonical_string(/* with_prefix */ true))
+        }))
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        let Self(ctx, config) = self;
+        Ok(balances_response(ctx, config, owner, None)
+            .await
+            .with_internal_context(|| format!("Failed to get balances for {owner}"))?)
+    }
+}
+
+impl RpcModule for Coins {
+    fn schema(&self) -> Module {
+        CoinsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl Default for CoinsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+            balances_page_size: 1000,
+        }
+    }
+}
+
+/// Interpret the coin type parameter, defaulting to SUI if none is provided.
+fn parse_coin_type(coin_type: Option<String>) -> Result<TypeTag, RpcError<Error>> {
+    let Some(coin_type) = coin_type else {
+        return Ok(GAS::type_tag());
+    };
+
+    parse_sui_type_tag(&coin_type).map_err(|e| invalid_params(Error::BadCoinType(coin_type, e)))
+}
+
+/// Load data and generate response for `getCoins`.
+async fn coins_response(
+    ctx: &Context,
+    config: &CoinsConfig,
+    owner: SuiAddress,
+    coin_type: TypeTag,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<Page<Coin, String>, RpcError<Error>> {
+    use coin_balance_buckets::dsl as c;
+
+    // Coins are paginated by their balance bucket and ID.
+    let page: PageParams<(i16, ObjectID)> = PageParams::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    let serialized_type = bcs::to_bytes(&coin_type).context("Failed to serialize coin type")?;
+
+    let newer = diesel::alias!(coin_balance_buckets as newer);
+    let mut query = c::coin_balance_buckets
+        .select((c::object_id, c::coin_balance_bucket))
+        .filter(c::owner_kind.eq(StoredCoinOwnerKind::Fastpath))
+        .filter(c::owner_id.eq(owner.to_vec()))
+        .filter(c::coin_type.eq(serialized_type))
+        .filter(not(exists(
+            newer
+                .filter(newer.field(c::object_id).eq(c::object_id))
+                .filter(newer.field(c::cp_sequence_number).gt(c::cp_sequence_number)),
+        )))
+        .order((c::coin_balance_bucket.desc(), c::object_id.asc()))
+        .limit(page.limit + 1)
+        .into_boxed();
+
+    if let Some(Cursor((bucket, id))) = page.cursor {
+        query = query.filter(
+            c::coin_balance_bucket.lt(bucket).or(c::coin_balance_bucket
+                .eq(bucket)
+                .and(c::object_id.gt(id.to_vec()))),
+        );
+    }
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let mut rows: Vec<(Vec<u8>, Option<i16>)> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch matching coins")?;
+
+    let has_next_page = rows.len() > page.limit as usize;
+    if has_next_page {
+        rows.truncate(page.limit as usize);
+    }
+
+    let rows = rows
+        .into_iter()
+        .map(|(id, bucket)| {
+            let id = ObjectID::from_bytes(id).context("Failed to deserialize coin ID")?;
+            let bucket = bucket.with_context(|| format!("No balance bucket for coin {id}"))?;
+            Ok((id, bucket))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let next_cursor = rows
+        .last()
+        .map(|last| Cursor(*last).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let objects = latest_objects::<Error>(ctx, rows.iter().map(|(id, _)| *id)).await?;
+    let coin_type = coin_type.to_canonical_string(/* with_prefix */ true);
+
+    let data = rows
+        .iter()
+        .map(|(id, _)| {
+            let object = objects
+                .get(id)
+                .ok_or_else(|| internal_error!("Failed to find latest version of coin {id}"))?;
+
+            let coin = object
+                .as_coin_maybe()
+                .ok_or_else(|| internal_error!("Object {id} is not a coin"))?;
+
+            let (coin_object_id, version, digest) = object.compute_object_reference();
+            Ok(Coin {
+                coin_type: coin_type.clone(),
+                coin_object_id,
+                version,
+                digest,
+                balance: coin.balance.value(),
+                previous_transaction: object.previous_transaction,
+            })
+        })
+        .collect::<Result<Vec<_>, RpcError<Error>>>()?;
+
+    Ok(Page {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Load data and generate response for `getBalance` (if `coin_type` is provided) or
+/// `getAllBalances` (otherwise). Balances are summed over the latest versions of all the coins
+/// `owner` currently owns, and are ordered by coin type. Coins are scanned in pages of
+/// `config.balances_page_size` coins, ordered by ID, so that only one page of coin objects is
+/// loaded at a time.
+async fn balances_response(
+    ctx: &Context,
+    config: &CoinsConfig,
+    owner: SuiAddress,
+    coin_type: Option<&TypeTag>,
+) -> Result<Vec<Balance>, RpcError<Error>> {
+    use coin_balance_buckets::dsl as c;
+
+    let serialized_type = coin_type
+        .map(bcs::to_bytes)
+        .transpose()
+        .context("Failed to serialize coin type")?;
+
+    let mut balances = BTreeMap::new();
+    let mut after: Option<ObjectID> = None;
+    loop {
+        let newer = diesel::alias!(coin_balance_buckets as newer);
+        let mut query = c::coin_balance_buckets
+            .select((c::object_id, c::coin_type))
+            .filter(c::owner_kind.eq(StoredCoinOwnerKind::Fastpath))
+            .filter(c::owner_id.eq(owner.to_vec()))
+            .filter(not(exists(
+                newer
+                    .filter(newer.field(c::object_id).eq(c::object_id))
+                    .filter(newer.field(c::cp_sequence_number).gt(c::cp_sequence_number)),
+            )))
+            .order(c::object_id.asc())
+            .limit(config.balances_page_size as i64)
+            .into_boxed();
+
+        if let Some(serialized_type) = &serialized_type {
+            query = query.filter(c::coin_type.eq(serialized_type.clone()));
+        }
+
+        if let Some(after) = after {
+            query = query.filter(c::object_id.gt(after.to_vec()));
+        }
+
+        let mut conn = ctx
+            .reader()
+            .connect()
+            .await
+            .context("Failed to connect to database")?;
+
+        let rows: Vec<(Vec<u8>, Option<Vec<u8>>)> = conn
+            .results(query)
+            .await
+            .context("Failed to fetch owned coins")?;
+
+        let is_last_page = rows.len() < config.balances_page_size;
+        let rows = rows
+            .into_iter()
+            .map(|(id, coin_type)| {
+                let id = ObjectID::from_bytes(id).context("Failed to deserialize coin ID")?;
+                let coin_type = coin_type.with_context(|| format!("No coin type for coin {id}"))?;
+                Ok((id, coin_type))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let objects = latest_objects::<Error>(ctx, rows.iter().map(|(id, _)| *id)).await?;
+        for (id, coin_type) in &rows {
+            let coin = objects
+                .get(id)
+                .and_then(Object::as_coin_maybe)
+                .ok_or_else(|| internal_error!("Failed to find latest version of coin {id}"))?;
+
+            let coin_type: TypeTag =
+                bcs::from_bytes(coin_type).context("Failed to deserialize coin type")?;
+            let coin_type = coin_type.to_canonical_string(/* with_prefix */ true);
+
+            let balance = balances
+                .entry(coin_type.clone())
+                .or_insert_with(|| Balance::zero(coin_type));
+            balance.coin_object_count += 1;
+            balance.total_balance += coin.balance.value() as u128;
+        }
+
+        match rows.last() {
+            Some((id, _)) if !is_last_page => after = Some(*id),
+            _ => break,
+        }
+    }
+
+    Ok(balances.into_values().collect())
+}
@@ -0,0 +1,380 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use diesel::{
+    dsl::{exists, not},
+    ExpressionMethods, QueryDsl,
+};
+use futures::future;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sui_indexer_alt_schema::{
+    objects::{StoredDynamicFieldKind, StoredObjDynamicField},
+    schema::obj_dynamic_fields,
+};
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{
+    DynamicFieldInfo, Page, SuiMoveValue, SuiObjectDataOptions, SuiObjectResponse,
+};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::{
+    base_types::ObjectID,
+    dynamic_field::{
+        derive_dynamic_field_id, visitor as DFV, DynamicFieldInfo as DFInfo, DynamicFieldName,
+    },
+    error::SuiObjectResponseError,
+    object::{bounded_visitor::BoundedVisitor, Object},
+    TypeTag,
+};
+
+use crate::{
+    context::Context,
+    error::{internal_error, invalid_params, rpc_bail, InternalContext, RpcError},
+    paginate::{Cursor, Page as PageParams},
+};
+
+use super::{
+    objects::response::{latest_objects, live_objects},
+    rpc_module::RpcModule,
+};
+
+#[open_rpc(namespace = "suix", tag = "Dynamic Fields API")]
+#[rpc(server, namespace = "suix")]
+trait DynamicFieldsApi {
+    /// Return the dynamic fields currently owned by an object, as a paginated list ordered by the
+    /// ID of the field.
+    ///
+    /// If a cursor is provided, the query will start from the field after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first field.
+    ///
+    /// The size of each page is controlled by the `limit` parameter.
+    #[method(name = "getDynamicFields")]
+    async fn get_dynamic_fields(
+        &self,
+        /// The ID of the parent object.
+        parent_object_id: ObjectID,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of dynamic fields to return per page.
+        limit: Option<usize>,
+    ) -> RpcResult<Page<DynamicFieldInfo, String>>;
+
+    /// Fetch the value of a dynamic field by its parent object's ID and its name. For dynamic
+    /// fields this is the `0x2::dynamic_field::Field` object, and for dynamic object fields, it is
+    /// the object stored in the field.
+    #[method(name = "getDynamicFieldObject")]
+    async fn get_dynamic_field_object(
+        &self,
+        /// The ID of the parent object.
+        parent_object_id: ObjectID,
+        /// The name of the dynamic field.
+        name: DynamicFieldName,
+    ) -> RpcResult<SuiObjectResponse>;
+}
+
+pub(crate) struct DynamicFields(pub Context, pub DynamicFieldsConfig);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DynamicFieldsConfig {
+    /// The default page size limit when querying dynamic fields, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying dynamic fields. Requesting a page larger
+    /// than this is a user error.
+    pub max_page_size: usize,
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+
+    #[error("Invalid dynamic field name {0}: {1}")]
+    BadName(DynamicFieldName, anyhow::Error),
+}
+
+#[async_trait::async_trait]
+impl DynamicFieldsApiServer for DynamicFields {
+    async fn get_dynamic_fields(
+        &self,
+        parent_object_id: ObjectID,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<Page<DynamicFieldInfo, String>> {
+        let Self(ctx, config) = self;
+        Ok(
+            dynamic_fields_response(ctx, config, parent_object_id, cursor, limit)
+                .await
+                .with_internal_context(|| {
+                    format!("Failed to get dynamic fields of {parent_object_id}")
+                })?,
+        )
+    }
+
+    async fn get_dynamic_field_object(
+        &self,
+        parent_object_id: ObjectID,
+        name: DynamicFieldName,
+    ) -> RpcResult<SuiObjectResponse> {
+        let Self(ctx, _) = self;
+        Ok(dynamic_field_object_response(ctx, parent_object_id, name)
+            .await
+            .with_internal_context(|| {
+                format!("Failed to get dynamic field of {parent_object_id}")
+            })?)
+    }
+}
+
+impl RpcModule for DynamicFields {
+    fn schema(&self) -> Module {
+        DynamicFieldsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl Default for DynamicFieldsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+/// Load data and generate response for `getDynamicFields`.
+async fn dynamic_fields_response(
+    ctx: &Context,
+    config: &DynamicFieldsConfig,
+    parent_object_id: ObjectID,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<Page<DynamicFieldInfo, String>, RpcError<Error>> {
+    use obj_dynamic_fields::dsl as d;
+
+    let page: PageParams<ObjectID> = PageParams::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    let newer = diesel::alias!(obj_dynamic_fields as newer);
+    let mut query = d::obj_dynamic_fields
+        .filter(d::parent_id.eq(parent_object_id.to_vec()))
+        .filter(not(exists(
+            newer
+                .filter(newer.field(d::object_id).eq(d::object_id))
+                .filter(newer.field(d::cp_sequence_number).gt(d::cp_sequence_number)),
+        )))
+        .order(d::object_id.asc())
+        .limit(page.limit + 1)
+        .into_boxed();
+
+    if let Some(Cursor(id)) = page.cursor {
+        query = query.filter(d::object_id.gt(id.to_vec()));
+    }
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let mut rows: Vec<StoredObjDynamicField> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch dynamic fields")?;
+
+    let has_next_page = rows.len() > page.limit as usize;
+    if has_next_page {
+        rows.truncate(page.limit as usize);
+    }
+
+    let mut field_ids = Vec::with_capacity(rows.len());
+    let mut value_ids = vec![];
+    for row in &rows {
+        let id = ObjectID::from_bytes(&row.object_id).context("Failed to deserialize field ID")?;
+        field_ids.push(id);
+
+        if row.df_kind == Some(StoredDynamicFieldKind::DynamicObject) {
+            let value_id = row
+                .value_id
+                .as_ref()
+                .with_context(|| format!("No value ID for dynamic object field {id}"))?;
+            value_ids
+                .push(ObjectID::from_bytes(value_id).context("Failed to deserialize value ID")?);
+        }
+    }
+
+    let next_cursor = field_ids
+        .last()
+        .map(|last| Cursor(*last).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    // Fetch the fields and the objects stored in dynamic object fields together.
+    let objects =
+        latest_objects::<Error>(ctx, field_ids.iter().chain(value_ids.iter()).copied()).await?;
+
+    let infos = field_ids
+        .iter()
+        .map(|id| dynamic_field_info(ctx, *id, &objects));
+
+    let data = future::join_all(infos)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Page {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Build the response for the dynamic field with ID `id`, given the latest versions of the fields
+/// and the objects stored in dynamic object fields, by ID.
+async fn dynamic_field_info(
+    ctx: &Context,
+    id: ObjectID,
+    objects: &HashMap<ObjectID, Object>,
+) -> Result<DynamicFieldInfo, RpcError<Error>> {
+    let field = objects
+        .get(&id)
+        .ok_or_else(|| internal_error!("Failed to find latest version of field {id}"))?;
+
+    let move_object = field
+        .data
+        .try_as_move()
+        .with_context(|| format!("Dynamic field {id} is not a Move object"))?;
+
+    let type_: TypeTag = move_object.type_().clone().into();
+    let layout = ctx
+        .package_resolver()
+        .type_layout(type_.clone())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to resolve layout for {}",
+                type_.to_canonical_display(/* with_prefix */ true)
+            )
+        })?;
+
+    let df = DFV::FieldVisitor::deserialize(move_object.contents(), &layout)
+        .with_context(|| format!("Failed to deserialize dynamic field {id}"))?;
+
+    let name_value = BoundedVisitor::deserialize_value(df.name_bytes, df.name_layout)
+        .with_context(|| format!("Failed to deserialize name of dynamic field {id}"))?;
+
+    let name = DynamicFieldName {
+        type_: df.name_layout.into(),
+        value: SuiMoveValue::from(name_value).to_json_value(),
+    };
+
+    let value_metadata = df
+        .value_metadata()
+        .with_context(|| format!("Failed to extract value of dynamic field {id}"))?;
+
+    let (object_type, object): (TypeTag, &Object) = match value_metadata {
+        DFV::ValueMetadata::DynamicField(object_type) => (object_type, field),
+        DFV::ValueMetadata::DynamicObjectField(value_id) => {
+            let object = objects.get(&value_id).ok_or_else(|| {
+                internal_error!("Failed to find latest version of object {value_id} in field {id}")
+            })?;
+
+            let object_type = object
+                .struct_tag()
+                .with_context(|| format!("Object {value_id} in field {id} has no type"))?;
+
+            (object_type.into(), object)
+        }
+    };
+
+    let (object_id, version, digest) = object.compute_object_reference();
+    Ok(DFInfo {
+        name,
+        bcs_name: df.name_bytes.to_owned(),
+        type_: df.kind,
+        object_type: object_type.to_canonical_string(/* with_prefix */ true),
+        object_id,
+        version,
+        digest,
+    }
+    .into())
+}
+
+/// Load data and generate response for `getDynamicFieldObject`.
+async fn dynamic_field_object_response(
+    ctx: &Context,
+    parent_object_id: ObjectID,
+    name: DynamicFieldName,
+) -> Result<SuiObjectResponse, RpcError<Error>> {
+    use obj_dynamic_fields::dsl as d;
+
+    let layout = ctx
+        .package_resolver()
+        .type_layout(name.type_.clone())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to resolve layout for {}",
+                name.type_.to_canonical_display(/* with_prefix */ true)
+            )
+        })?;
+
+    let bytes = match SuiJsonValue::new(name.value.clone()).and_then(|v| v.to_bcs_bytes(&layout)) {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(invalid_params(Error::BadName(name, e))),
+    };
+
+    let wrapper: TypeTag = DFInfo::dynamic_object_field_wrapper(name.type_.clone()).into();
+    let df_id = derive_dynamic_field_id(parent_object_id, &name.type_, &bytes)
+        .context("Failed to derive dynamic field ID")?;
+    let dof_id = derive_dynamic_field_id(parent_object_id, &wrapper, &bytes)
+        .context("Failed to derive dynamic object field ID")?;
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let rows: Vec<StoredObjDynamicField> = conn
+        .results(
+            d::obj_dynamic_fields
+                .filter(d::object_id.eq_any([df_id.to_vec(), dof_id.to_vec()]))
+                .distinct_on(d::object_id)
+                .order((d::object_id, d::cp_sequence_number.desc())),
+        )
+        .await
+        .context("Failed to fetch dynamic field")?;
+
+    // Deleted and wrapped fields have a final record without a kind.
+    let Some(row) = rows.iter().find(|r| r.df_kind.is_some()) else {
+        return Ok(SuiObjectResponse::new_with_error(
+            SuiObjectResponseError::DynamicFieldNotFound { parent_object_id },
+        ));
+    };
+
+    let object_id = match (row.df_kind, &row.value_id) {
+        (Some(StoredDynamicFieldKind::DynamicObject), Some(value_id)) => {
+            ObjectID::from_bytes(value_id).context("Failed to deserialize value ID")?
+        }
+        (Some(StoredDynamicFieldKind::DynamicObject), None) => {
+            rpc_bail!("No value ID for dynamic object field {dof_id}")
+        }
+        _ => df_id,
+    };
+
+    let options = SuiObjectDataOptions::full_content();
+    let mut responses = live_objects::<Error>(ctx, &[object_id], &options).await?;
+    Ok(responses.pop().expect("One response per requested object"))
+}
@@ -0,0 +1,361 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use diesel::{ExpressionMethods, QueryDsl};
+use futures::future;
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use sui_indexer_alt_schema::schema::{ev_emit_mod, ev_struct_inst, tx_digests};
+use sui_json_rpc_types::{EventFilter, Page as PageResponse, SuiEvent};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+    event::Event,
+    Identifier,
+};
+
+use crate::{
+    data::transactions::TransactionKey,
+    error::{invalid_params, RpcError},
+    paginate::{Cursor, Page},
+};
+
+use super::{error::Error, response::event, Context, EventsConfig};
+
+/// Fetch a page of events that satisfy the given `filter` and pagination parameters. Events are
+/// paginated by the sequence number of the transaction that emitted them, and then by their
+/// position in that transaction's events.
+///
+/// Events are found in two steps: First, the indices are used to find transactions that emitted
+/// at least one matching event, and then those transactions' events are loaded and filtered.
+pub(super) async fn events(
+    ctx: &Context,
+    config: &EventsConfig,
+    filter: &EventFilter,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<PageResponse<SuiEvent, String>, RpcError<Error>> {
+    let page: Page<(u64, u64)> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        descending_order,
+    )?;
+
+    use EventFilter as F;
+    let tx_sequence_numbers = match filter {
+        F::All(_) => emit_module_transactions(ctx, &page, None, None).await?,
+
+        F::Sender(sender) => emit_module_transactions(ctx, &page, Some(sender), None).await?,
+
+        F::MoveModule { package, module } => {
+            emit_module_transactions(ctx, &page, None, Some((package, module))).await?
+        }
+
+        F::MoveEventType(tag) => {
+            let package = ObjectID::from(tag.address);
+            struct_transactions(ctx, &page, &package, &tag.module, Some(tag)).await?
+        }
+
+        F::MoveEventModule { package, module } => {
+            struct_transactions(ctx, &page, package, module, None).await?
+        }
+
+        F::Transaction(digest) => digest_transactions(ctx, &page, digest).await?,
+
+        F::Any(_) => return unsupported("Any filter is not supported"),
+
+        F::TimeRange { .. } => return unsupported("TimeRange filter is not supported"),
+    };
+
+    let digests = transaction_digests(ctx, &tx_sequence_numbers).await?;
+    let stored_txs = ctx
+        .loader()
+        .load_many(digests.iter().map(|(_, d)| TransactionKey(*d)))
+        .await
+        .context("Failed to fetch transactions from store")?;
+
+    // Every transaction returned by the index contains at least one matching event, apart from
+    // possibly the one pointed to by the cursor, so the `limit + 2` transactions fetched contain
+    // enough events to fill the page and detect whether there is a next page.
+    let mut matched = vec![];
+    'txs: for (tx, digest) in digests {
+        let stored_tx = stored_txs
+            .get(&TransactionKey(digest))
+            .with_context(|| format!("Failed to find transaction {digest}"))?;
+
+        let events: Vec<Event> = bcs::from_bytes(&stored_tx.events)
+            .with_context(|| format!("Failed to deserialize Events for transaction {digest}"))?;
+
+        let mut events: Vec<_> = events.into_iter().enumerate().collect();
+        if page.descending {
+            events.reverse();
+        }
+
+        for (ix, ev) in events {
+            let position = (tx, ix as u64);
+            if let Some(Cursor(cursor)) = page.cursor {
+                if (page.descending && position >= cursor)
+                    || (!page.descending && position <= cursor)
+                {
+                    continue;
+                }
+            }
+
+            if !matches(filter, &ev) {
+                continue;
+            }
+
+            matched.push((position, digest, stored_tx.timestamp_ms as u64, ev));
+            if matched.len() > page.limit as usize {
+                break 'txs;
+            }
+        }
+    }
+
+    let has_next_page = matched.len() > page.limit as usize;
+    if has_next_page {
+        matched.truncate(page.limit as usize);
+    }
+
+    let next_cursor = matched
+        .last()
+        .map(|(position, ..)| Cursor(*position).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let data = future::join_all(
+        matched
+            .into_iter()
+            .map(|((_, ix), digest, timestamp_ms, ev)| {
+                event::<Error>(ctx, digest, ix as usize, timestamp_ms, ev)
+            }),
+    )
+    .await
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Fetch the sequence numbers of transactions that emitted events from the given `module`, sent by
+/// the given `sender` (or any transaction that emitted events, if neither is provided). Fetches two
+/// more results than were requested: One to account for the transaction the cursor points into,
+/// and another to detect a next page.
+async fn emit_module_transactions(
+    ctx: &Context,
+    page: &Page<(u64, u64)>,
+    sender: Option<&SuiAddress>,
+    module: Option<(&ObjectID, &Identifier)>,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    use ev_emit_mod::dsl as e;
+
+    let mut query = e::ev_emit_mod
+        .select(e::tx_sequence_number)
+        .distinct()
+        .limit(page.limit + 2)
+        .into_boxed();
+
+    if let Some(sender) = sender {
+        query = query.filter(e::sender.eq(sender.to_vec()));
+    }
+
+    if let Some((package, module)) = module {
+        query = query
+            .filter(e::package.eq(package.to_vec()))
+            .filter(e::module.eq(module.to_string()));
+    }
+
+    if let Some(Cursor((tx, _))) = page.cursor {
+        if page.descending {
+            query = query.filter(e::tx_sequence_number.le(tx as i64));
+        } else {
+            query = query.filter(e::tx_sequence_number.ge(tx as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(e::tx_sequence_number.desc());
+    } else {
+        query = query.order(e::tx_sequence_number.asc());
+    }
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let txs: Vec<i64> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch matching transactions")?;
+
+    Ok(txs.into_iter().map(|tx| tx as u64).collect())
+}
+
+/// Fetch the sequence numbers of transactions that emitted events whose types are defined in
+/// `package::module`, optionally limited to events of exactly type `tag`. Fetches two more results
+/// than were requested, like `emit_module_transactions`.
+async fn struct_transactions(
+    ctx: &Context,
+    page: &Page<(u64, u64)>,
+    package: &ObjectID,
+    module: &Identifier,
+    tag: Option<&StructTag>,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    use ev_struct_inst::dsl as e;
+
+    let mut query = e::ev_struct_inst
+        .select(e::tx_sequence_number)
+        .distinct()
+        .filter(e::package.eq(package.to_vec()))
+        .filter(e::module.eq(module.to_string()))
+        .limit(page.limit + 2)
+        .into_boxed();
+
+    if let Some(tag) = tag {
+        let instantiation =
+            bcs::to_bytes(&tag.type_params).context("Failed to serialize type parameters")?;
+
+        query = query
+            .filter(e::name.eq(tag.name.to_string()))
+            .filter(e::instantiation.eq(instantiation));
+    }
+
+    if let Some(Cursor((tx, _))) = page.cursor {
+        if page.descending {
+            query = query.filter(e::tx_sequence_number.le(tx as i64));
+        } else {
+            query = query.filter(e::tx_sequence_number.ge(tx as i64));
+        }
+    }
+
+    if page.descending {
+        query = query.order(e::tx_sequence_number.desc());
+    } else {
+        query = query.order(e::tx_sequence_number.asc());
+    }
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let txs: Vec<i64> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch matching transactions")?;
+
+    Ok(txs.into_iter().map(|tx| tx as u64).collect())
+}
+
+/// Fetch the sequence number of the transaction with the given `digest`, if it is in the range
+/// described by `page`.
+async fn digest_transactions(
+    ctx: &Context,
+    page: &Page<(u64, u64)>,
+    digest: &TransactionDigest,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    use tx_digests::dsl as d;
+
+    let mut query = d::tx_digests
+        .select(d::tx_sequence_number)
+        .filter(d::tx_digest.eq(digest.inner().to_vec()))
+        .into_boxed();
+
+    if let Some(Cursor((tx, _))) = page.cursor {
+        if page.descending {
+            query = query.filter(d::tx_sequence_number.le(tx as i64));
+        } else {
+            query = query.filter(d::tx_sequence_number.ge(tx as i64));
+        }
+    }
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let txs: Vec<i64> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch transaction sequence number")?;
+
+    Ok(txs.into_iter().map(|tx| tx as u64).collect())
+}
+
+/// Fetch the digests of the transactions with the given sequence numbers, preserving their order.
+async fn transaction_digests(
+    ctx: &Context,
+    tx_sequence_numbers: &[u64],
+) -> Result<Vec<(u64, TransactionDigest)>, RpcError<Error>> {
+    use tx_digests::dsl as d;
+
+    if tx_sequence_numbers.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let query = d::tx_digests
+        .select((d::tx_sequence_number, d::tx_digest))
+        .filter(d::tx_sequence_number.eq_any(tx_sequence_numbers.iter().map(|tx| *tx as i64)));
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let rows: Vec<(i64, Vec<u8>)> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch transaction digests")?;
+
+    let digests: HashMap<_, _> = rows.into_iter().collect();
+    tx_sequence_numbers
+        .iter()
+        .map(|tx| {
+            let bytes = digests
+                .get(&(*tx as i64))
+                .with_context(|| format!("Failed to find digest for transaction {tx}"))?;
+
+            let digest = TransactionDigest::try_from(bytes.as_slice())
+                .context("Failed to deserialize transaction digest")?;
+
+            Ok((*tx, digest))
+        })
+        .collect()
+}
+
+/// Whether `event` satisfies `filter`. Transactions are selected using indices that are coarser
+/// than some filters (e.g. a transaction that emitted an event from a module may have emitted
+/// others from different modules), so events need to be checked individually.
+fn matches(filter: &EventFilter, event: &Event) -> bool {
+    use EventFilter as F;
+    match filter {
+        F::All(_) | F::Transaction(_) => true,
+        F::Sender(sender) => &event.sender == sender,
+        F::MoveModule { package, module } => {
+            &event.package_id == package && &event.transaction_module == module
+        }
+        F::MoveEventType(tag) => &event.type_ == tag,
+        F::MoveEventModule { package, module } => {
+            event.type_.address == AccountAddress::from(*package) && &event.type_.module == module
+        }
+        F::Any(_) | F::TimeRange { .. } => false,
+    }
+}
+
+fn unsupported<T>(msg: &'static str) -> Result<T, RpcError<Error>> {
+    Err(invalid_params(Error::Unsupported(msg)))
+}
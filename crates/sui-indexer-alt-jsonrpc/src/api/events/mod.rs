@@ -0,0 +1,93 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::{EventFilter, Page, SuiEvent};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+
+use crate::{context::Context, error::InternalContext};
+
+use super::rpc_module::RpcModule;
+
+mod error;
+mod filter;
+pub(crate) mod response;
+
+#[open_rpc(namespace = "suix", tag = "Query Events API")]
+#[rpc(server, namespace = "suix")]
+trait QueryEventsApi {
+    /// Query events based on their properties (sender, emitting module, type, or the transaction
+    /// that emitted them). Returns a paginated list of events.
+    ///
+    /// If a cursor is provided, the query will start from the event after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first event that meets the query
+    /// criteria.
+    ///
+    /// The definition of "first" event is changed by the `descending_order` parameter, which is
+    /// optional, and defaults to false, meaning that the oldest event is shown first.
+    ///
+    /// The size of each page is controlled by the `limit` parameter.
+    #[method(name = "queryEvents")]
+    async fn query_events(
+        &self,
+        /// The query criteria.
+        query: EventFilter,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of events to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by sequence on-chain.
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<SuiEvent, String>>;
+}
+
+pub(crate) struct QueryEvents(pub Context, pub EventsConfig);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventsConfig {
+    /// The default page size limit when querying events, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying events. Requesting a page larger than this
+    /// is a user error.
+    pub max_page_size: usize,
+}
+
+#[async_trait::async_trait]
+impl QueryEventsApiServer for QueryEvents {
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<SuiEvent, String>> {
+        let Self(ctx, config) = self;
+        Ok(
+            filter::events(ctx, config, &query, cursor, limit, descending_order)
+                .await
+                .internal_context("Failed to query events")?,
+        )
+    }
+}
+
+impl RpcModule for QueryEvents {
+    fn schema(&self) -> Module {
+        QueryEventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
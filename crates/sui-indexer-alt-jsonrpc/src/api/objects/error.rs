@@ -0,0 +1,14 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[derive(thiserror::Error, Debug)]
+pub(super) enum Error {
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+
+    #[error("Requested {requested} objects, exceeding maximum {max}")]
+    TooManyObjects { requested: usize, max: usize },
+
+    #[error("{0}")]
+    Unsupported(&'static str),
+}
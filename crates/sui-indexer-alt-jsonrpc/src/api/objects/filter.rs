@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::{
+    dsl::{exists, not},
+    ExpressionMethods, QueryDsl,
+};
+use sui_indexer_alt_schema::{objects::StoredOwnerKind, schema::obj_info};
+use sui_json_rpc_types::{Page as PageResponse, SuiObjectDataFilter};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::{
+    error::{invalid_params, RpcError},
+    paginate::{Cursor, Page},
+};
+
+use super::{error::Error, Context, ObjectsConfig};
+
+/// Fetch the IDs for a page of objects owned by `owner` that satisfy the given `filter` and
+/// pagination parameters. Returns the IDs and a cursor pointing to the last result (if there are
+/// any results).
+pub(super) async fn owned_objects(
+    ctx: &Context,
+    config: &ObjectsConfig,
+    owner: SuiAddress,
+    filter: &Option<SuiObjectDataFilter>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<PageResponse<ObjectID, String>, RpcError<Error>> {
+    use obj_info::dsl as i;
+
+    let page: Page<ObjectID> = Page::from_params(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    // An object is currently owned by `owner` if its latest record in `obj_info` says so. Records
+    // for objects that have since been modified, deleted or wrapped are only removed once they
+    // are pruned, so they need to be filtered out explicitly.
+    let newer = diesel::alias!(obj_info as newer);
+    let mut query = i::obj_info
+        .select(i::object_id)
+        .filter(i::owner_kind.eq(StoredOwnerKind::Address))
+        .filter(i::owner_id.eq(owner.to_vec()))
+        .filter(not(exists(
+            newer
+                .filter(newer.field(i::object_id).eq(i::object_id))
+                .filter(newer.field(i::cp_sequence_number).gt(i::cp_sequence_number)),
+        )))
+        .order(i::object_id.asc())
+        .limit(page.limit + 1)
+        .into_boxed();
+
+    if let Some(Cursor(id)) = page.cursor {
+        query = query.filter(i::object_id.gt(id.to_vec()));
+    }
+
+    use SuiObjectDataFilter as F;
+    match filter {
+        None => {}
+
+        Some(F::Package(package)) => {
+            query = query.filter(i::package.eq(package.to_vec()));
+        }
+
+        Some(F::MoveModule { package, module }) => {
+            query = query
+                .filter(i::package.eq(package.to_vec()))
+                .filter(i::module.eq(module.to_string()));
+        }
+
+        Some(F::StructType(tag)) => {
+            query = query
+                .filter(i::package.eq(tag.address.to_vec()))
+                .filter(i::module.eq(tag.module.to_string()))
+                .filter(i::name.eq(tag.name.to_string()));
+
+            // A type without type parameters matches all its instantiations.
+            if !tag.type_params.is_empty() {
+                let instantiation = bcs::to_bytes(&tag.type_params)
+                    .context("Failed to serialize type parameters")?;
+                query = query.filter(i::instantiation.eq(instantiation));
+            }
+        }
+
+        Some(F::MatchAll(_) | F::MatchAny(_) | F::MatchNone(_)) => {
+            return unsupported("Compound filters are not supported")
+        }
+
+        Some(
+            F::AddressOwner(_)
+            | F::ObjectOwner(_)
+            | F::ObjectId(_)
+            | F::ObjectIds(_)
+            | F::Version(_),
+        ) => {
+            return unsupported(
+                "Only Package, MoveModule and StructType filters are supported for owned objects",
+            )
+        }
+    }
+
+    let mut conn = ctx
+        .reader()
+        .connect()
+        .await
+        .context("Failed to connect to database")?;
+
+    let mut ids: Vec<Vec<u8>> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch matching object IDs")?;
+
+    let has_next_page = ids.len() > page.limit as usize;
+    if has_next_page {
+        ids.truncate(page.limit as usize);
+    }
+
+    let ids = ids
+        .iter()
+        .map(|id| ObjectID::from_bytes(id))
+        .collect::<Result<Vec<ObjectID>, _>>()
+        .context("Failed to deserialize object IDs")?;
+
+    let cursor = ids
+        .last()
+        .map(|last| Cursor(*last).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    Ok(PageResponse {
+        data: ids,
+        next_cursor: cursor,
+        has_next_page,
+    })
+}
+
+fn unsupported<T>(msg: &'static str) -> Result<T, RpcError<Error>> {
+    Err(invalid_params(Error::Unsupported(msg)))
+}
@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::{Page, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::{
+    context::Context,
+    error::{invalid_params, InternalContext},
+};
+
+use self::error::Error;
+
+use super::rpc_module::RpcModule;
+
+mod error;
+mod filter;
+pub(crate) mod response;
+
+#[open_rpc(namespace = "sui", tag = "Objects API")]
+#[rpc(server, namespace = "sui")]
+trait ObjectsApi {
+    /// Fetch the latest version of an object by its ID. If the object has been deleted or wrapped,
+    /// the response describes the last version at which it was live.
+    #[method(name = "getObject")]
+    async fn get_object(
+        &self,
+        /// The ID of the queried object.
+        object_id: ObjectID,
+        /// Options controlling the output format.
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse>;
+
+    /// Fetch the latest versions of a list of objects by their IDs. Responses are returned in the
+    /// same order as the IDs they were requested with.
+    #[method(name = "multiGetObjects")]
+    async fn multi_get_objects(
+        &self,
+        /// The IDs of the queried objects.
+        object_ids: Vec<ObjectID>,
+        /// Options controlling the output format.
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>>;
+}
+
+#[open_rpc(namespace = "suix", tag = "Query Objects API")]
+#[rpc(server, namespace = "suix")]
+trait QueryObjectsApi {
+    /// Query the objects currently owned by an address, optionally filtered by their type.
+    /// Returns a paginated list of objects, ordered by object ID.
+    ///
+    /// If a cursor is provided, the query will start from the object after the one pointed to by
+    /// this cursor, otherwise pagination starts from the first object that meets the query
+    /// criteria.
+    ///
+    /// The size of each page is controlled by the `limit` parameter.
+    #[method(name = "getOwnedObjects")]
+    async fn get_owned_objects(
+        &self,
+        /// The owner's Sui address.
+        address: SuiAddress,
+        /// The query criteria, and the output options.
+        query: Option<SuiObjectResponseQuery>,
+        /// Cursor to start paginating from.
+        cursor: Option<String>,
+        /// Maximum number of objects to return per page.
+        limit: Option<usize>,
+    ) -> RpcResult<Page<SuiObjectResponse, String>>;
+}
+
+pub(crate) struct Objects(pub Context, pub ObjectsConfig);
+
+pub(crate) struct QueryObjects(pub Context, pub ObjectsConfig);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ObjectsConfig {
+    /// The maximum number of objects that can be fetched in a single multi-get request.
+    pub max_multi_get_objects: usize,
+
+    /// The default page size limit when querying owned objects, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying owned objects. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[async_trait::async_trait]
+impl ObjectsApiServer for Objects {
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse> {
+        let Self(ctx, _) = self;
+        let options = options.unwrap_or_default();
+
+        let mut responses = response::live_objects::<Error>(ctx, &[object_id], &options)
+            .await
+            .with_internal_context(|| format!("Failed to get object {object_id}"))?;
+
+        Ok(responses.pop().expect("One response per requested object"))
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>> {
+        let Self(ctx, config) = self;
+        if object_ids.len() > config.max_multi_get_objects {
+            return Err(invalid_params(Error::TooManyObjects {
+                requested: object_ids.len(),
+                max: config.max_multi_get_objects,
+            })
+            .into());
+        }
+
+        let options = options.unwrap_or_default();
+        Ok(response::live_objects::<Error>(ctx, &object_ids, &options)
+            .await
+            .internal_context("Failed to get objects")?)
+    }
+}
+
+#[async_trait::async_trait]
+impl QueryObjectsApiServer for QueryObjects {
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<Page<SuiObjectResponse, String>> {
+        let Self(ctx, config) = self;
+        let query = query.unwrap_or_default();
+
+        let Page {
+            data: object_ids,
+            next_cursor,
+            has_next_page,
+        } = filter::owned_objects(ctx, config, address, &query.filter, cursor, limit).await?;
+
+        let options = query.options.unwrap_or_default();
+        let data = response::live_objects::<Error>(ctx, &object_ids, &options)
+            .await
+            .with_internal_context(|| format!("Failed to get objects owned by {address}"))?;
+
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for Objects {
+    fn schema(&self) -> Module {
+        ObjectsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl RpcModule for QueryObjects {
+    fn schema(&self) -> Module {
+        QueryObjectsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl Default for ObjectsConfig {
+    fn default() -> Self {
+        Self {
+            max_multi_get_objects: 50,
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
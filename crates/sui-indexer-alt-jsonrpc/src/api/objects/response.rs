@@ -0,0 +1,191 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use futures::future;
+use move_core_types::annotated_value::MoveTypeLayout;
+use sui_indexer_alt_schema::objects::{StoredObjInfo, StoredObjVersion};
+use sui_json_rpc_types::{SuiObjectData, SuiObjectDataOptions, SuiObjectResponse};
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    digests::ObjectDigest,
+    error::SuiObjectResponseError,
+    object::Object,
+};
+use tokio::join;
+
+use crate::{
+    context::Context,
+    data::{
+        object_info::LatestObjectInfoKey, object_versions::LatestObjectVersionKey,
+        objects::VersionedObjectKey,
+    },
+    error::{rpc_bail, RpcError},
+};
+
+/// Fetch the necessary data from the stores in `ctx` and transform it to build responses for the
+/// latest versions of the objects identified by `object_ids`, according to the response `options`.
+/// Responses are returned in the same order as `object_ids`.
+pub(crate) async fn live_objects<E: std::error::Error>(
+    ctx: &Context,
+    object_ids: &[ObjectID],
+    options: &SuiObjectDataOptions,
+) -> Result<Vec<SuiObjectResponse>, RpcError<E>> {
+    let info_keys = object_ids.iter().map(|id| LatestObjectInfoKey(*id));
+    let version_keys = object_ids.iter().map(|id| LatestObjectVersionKey(*id));
+
+    let (stored_info, stored_versions) = join!(
+        ctx.loader().load_many(info_keys),
+        ctx.loader().load_many(version_keys),
+    );
+
+    let stored_info = stored_info.context("Failed to fetch object info from store")?;
+    let stored_versions = stored_versions.context("Failed to fetch object versions from store")?;
+
+    let content_keys = object_ids.iter().filter_map(|id| {
+        let stored = stored_versions.get(&LatestObjectVersionKey(*id))?;
+        Some(VersionedObjectKey(*id, stored.object_version as u64))
+    });
+
+    let stored_objects = ctx
+        .loader()
+        .load_many(content_keys)
+        .await
+        .context("Failed to fetch object contents from store")?;
+
+    let responses = object_ids.iter().map(|id| {
+        let info = stored_info.get(&LatestObjectInfoKey(*id));
+        let version = stored_versions.get(&LatestObjectVersionKey(*id));
+        let object = version
+            .and_then(|v| stored_objects.get(&VersionedObjectKey(*id, v.object_version as u64)));
+
+        live_object(
+            ctx,
+            *id,
+            info,
+            version,
+            object.and_then(|o| o.serialized_object.as_deref()),
+            options,
+        )
+    });
+
+    future::join_all(responses).await.into_iter().collect()
+}
+
+/// Fetch the contents of the latest versions of the objects with the given IDs. Objects whose
+/// latest versions could not be found are omitted from the result.
+pub(crate) async fn latest_objects<E: std::error::Error>(
+    ctx: &Context,
+    ids: impl IntoIterator<Item = ObjectID>,
+) -> Result<HashMap<ObjectID, Object>, RpcError<E>> {
+    let versions = ctx
+        .loader()
+        .load_many(ids.into_iter().map(LatestObjectVersionKey))
+        .await
+        .context("Failed to fetch latest object versions")?;
+
+    let stored_objects = ctx
+        .loader()
+        .load_many(versions.iter().map(|(LatestObjectVersionKey(id), stored)| {
+            VersionedObjectKey(*id, stored.object_version as u64)
+        }))
+        .await
+        .context("Failed to fetch object contents")?;
+
+    let mut objects = HashMap::with_capacity(stored_objects.len());
+    for (VersionedObjectKey(id, version), stored) in stored_objects {
+        let bytes = stored
+            .serialized_object
+            .with_context(|| format!("No content for object {id} at version {version}"))?;
+
+        let object: Object = bcs::from_bytes(&bytes)
+            .with_context(|| format!("Failed to deserialize object {id} at version {version}"))?;
+
+        objects.insert(id, object);
+    }
+
+    Ok(objects)
+}
+
+/// Build the response for a single object, given its latest info and version records, and the
+/// contents of its latest version.
+async fn live_object<E: std::error::Error>(
+    ctx: &Context,
+    object_id: ObjectID,
+    info: Option<&StoredObjInfo>,
+    version: Option<&StoredObjVersion>,
+    bytes: Option<&[u8]>,
+    options: &SuiObjectDataOptions,
+) -> Result<SuiObjectResponse, RpcError<E>> {
+    let Some(version) = version else {
+        return Ok(SuiObjectResponse::new_with_error(
+            SuiObjectResponseError::NotExists { object_id },
+        ));
+    };
+
+    // Deleted and wrapped objects have a final record in `obj_info` without an owner, and once
+    // that record has been pruned, they have no record at all. `obj_versions` does not track
+    // deletions, so the version reported is the last one the object was live at.
+    if info.map_or(true, |i| i.owner_kind.is_none()) {
+        let digest = ObjectDigest::try_from(version.object_digest.as_slice())
+            .context("Failed to deserialize object digest")?;
+
+        return Ok(SuiObjectResponse::new_with_error(
+            SuiObjectResponseError::Deleted {
+                object_id,
+                version: SequenceNumber::from_u64(version.object_version as u64),
+                digest,
+            },
+        ));
+    }
+
+    let v = version.object_version;
+    let Some(bytes) = bytes else {
+        rpc_bail!("No content for object {object_id} at version {v}");
+    };
+
+    let object: Object = bcs::from_bytes(bytes)
+        .with_context(|| format!("Failed to deserialize object {object_id} at version {v}"))?;
+
+    Ok(SuiObjectResponse::new_with_data(
+        object_data(ctx, object, options).await?,
+    ))
+}
+
+/// Convert an object into its response form. The object's type layout is only resolved if its
+/// contents were requested. Display metadata is not supported and is never included.
+async fn object_data<E: std::error::Error>(
+    ctx: &Context,
+    object: Object,
+    options: &SuiObjectDataOptions,
+) -> Result<SuiObjectData, RpcError<E>> {
+    let layout = match object.struct_tag() {
+        Some(tag) if options.show_content || options.show_bcs => {
+            match ctx
+                .package_resolver()
+                .type_layout(tag.clone().into())
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to resolve layout for {}",
+                        tag.to_canonical_display(/* with_prefix */ true)
+                    )
+                })? {
+                MoveTypeLayout::Struct(s) => Some(*s),
+                _ => rpc_bail!(
+                    "Object {} is not a struct: {}",
+                    object.id(),
+                    tag.to_canonical_string(/* with_prefix */ true)
+                ),
+            }
+        }
+        _ => None,
+    };
+
+    let object_ref = object.compute_object_reference();
+    Ok((object_ref, object, layout, options.clone())
+        .try_into()
+        .context("Failed to convert object into response")?)
+}
@@ -6,7 +6,10 @@ use std::mem;
 use sui_default_config::DefaultConfig;
 use tracing::warn;
 
-use crate::api::transactions::TransactionsConfig;
+use crate::api::{
+    coins::CoinsConfig, dynamic_fields::DynamicFieldsConfig, events::EventsConfig,
+    objects::ObjectsConfig, transactions::TransactionsConfig,
+};
 
 #[DefaultConfig]
 #[derive(Clone, Default, Debug)]
@@ -14,6 +17,18 @@ pub struct RpcConfig {
     /// Configuration for transaction-related RPC methods.
     pub transactions: TransactionsLayer,
 
+    /// Configuration for object-related RPC methods.
+    pub objects: ObjectsLayer,
+
+    /// Configuration for coin-related RPC methods.
+    pub coins: CoinsLayer,
+
+    /// Configuration for event-related RPC methods.
+    pub events: EventsLayer,
+
+    /// Configuration for dynamic field-related RPC methods.
+    pub dynamic_fields: DynamicFieldsLayer,
+
     #[serde(flatten)]
     pub extra: toml::Table,
 }
@@ -28,12 +43,56 @@ pub struct TransactionsLayer {
     pub extra: toml::Table,
 }
 
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct ObjectsLayer {
+    pub max_multi_get_objects: Option<usize>,
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct CoinsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+    pub balances_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct EventsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+pub struct DynamicFieldsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 impl RpcConfig {
     /// Generate an example configuration, suitable for demonstrating the fields available to
     /// configure.
     pub fn example() -> Self {
         Self {
             transactions: TransactionsConfig::default().into(),
+            objects: ObjectsConfig::default().into(),
+            coins: CoinsConfig::default().into(),
+            events: EventsConfig::default().into(),
+            dynamic_fields: DynamicFieldsConfig::default().into(),
             extra: Default::default(),
         }
     }
@@ -64,6 +123,92 @@ impl From<TransactionsConfig> for TransactionsLayer {
     }
 }
 
+impl ObjectsLayer {
+    pub fn finish(self, base: ObjectsConfig) -> ObjectsConfig {
+        check_extra("objects", self.extra);
+        ObjectsConfig {
+            max_multi_get_objects: self
+                .max_multi_get_objects
+                .unwrap_or(base.max_multi_get_objects),
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl CoinsLayer {
+    pub fn finish(self, base: CoinsConfig) -> CoinsConfig {
+        check_extra("coins", self.extra);
+        CoinsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+            balances_page_size: self.balances_page_size.unwrap_or(base.balances_page_size),
+        }
+    }
+}
+
+impl EventsLayer {
+    pub fn finish(self, base: EventsConfig) -> EventsConfig {
+        check_extra("events", self.extra);
+        EventsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl DynamicFieldsLayer {
+    pub fn finish(self, base: DynamicFieldsConfig) -> DynamicFieldsConfig {
+        check_extra("dynamic_fields", self.extra);
+        DynamicFieldsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl From<ObjectsConfig> for ObjectsLayer {
+    fn from(config: ObjectsConfig) -> Self {
+        Self {
+            max_multi_get_objects: Some(config.max_multi_get_objects),
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<CoinsConfig> for CoinsLayer {
+    fn from(config: CoinsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            balances_page_size: Some(config.balances_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<EventsConfig> for EventsLayer {
+    fn from(config: EventsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
+impl From<DynamicFieldsConfig> for DynamicFieldsLayer {
+    fn from(config: DynamicFieldsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            extra: Default::default(),
+        }
+    }
+}
+
 /// Check whether there are any unrecognized extra fields and if so, warn about them.
 fn check_extra(pos: &str, extra: toml::Table) {
     if !extra.is_empty() {
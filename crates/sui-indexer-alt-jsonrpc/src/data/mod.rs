@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod object_info;
 pub(crate) mod object_versions;
 pub(crate) mod objects;
 pub(crate) mod package_resolver;
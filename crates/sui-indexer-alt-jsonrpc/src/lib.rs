@@ -5,6 +5,10 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::Context as _;
+use api::coins::{Coins, CoinsConfig};
+use api::dynamic_fields::{DynamicFields, DynamicFieldsConfig};
+use api::events::{EventsConfig, QueryEvents};
+use api::objects::{Objects, ObjectsConfig, QueryObjects};
 use api::rpc_module::RpcModule;
 use api::transactions::{QueryTransactions, Transactions, TransactionsConfig};
 use config::RpcConfig;
@@ -204,10 +208,18 @@ pub async fn start_rpc(
 ) -> anyhow::Result<JoinHandle<()>> {
     let RpcConfig {
         transactions,
+        objects,
+        coins,
+        events,
+        dynamic_fields,
         extra: _,
     } = rpc_config.finish();
 
     let transactions_config = transactions.finish(TransactionsConfig::default());
+    let objects_config = objects.finish(ObjectsConfig::default());
+    let coins_config = coins.finish(CoinsConfig::default());
+    let events_config = events.finish(EventsConfig::default());
+    let dynamic_fields_config = dynamic_fields.finish(DynamicFieldsConfig::default());
 
     let mut rpc = RpcService::new(rpc_args, registry, cancel.child_token())
         .context("Failed to create RPC service")?;
@@ -220,7 +232,12 @@ pub async fn start_rpc(
         cancel.child_token(),
     );
 
+    rpc.add_module(Coins(context.clone(), coins_config))?;
+    rpc.add_module(DynamicFields(context.clone(), dynamic_fields_config))?;
     rpc.add_module(Governance(context.clone()))?;
+    rpc.add_module(Objects(context.clone(), objects_config.clone()))?;
+    rpc.add_module(QueryEvents(context.clone(), events_config))?;
+    rpc.add_module(QueryObjects(context.clone(), objects_config))?;
     rpc.add_module(QueryTransactions(context.clone(), transactions_config))?;
     rpc.add_module(Transactions(context.clone()))?;
 
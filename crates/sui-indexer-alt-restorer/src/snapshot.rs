@@ -9,8 +9,6 @@ use std::sync::Arc;
 use anyhow::Error;
 use diesel_async::RunQueryDsl;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use object_store::path::Path;
-use tokio::sync::Mutex;
 use tracing::{debug, info};
 
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
@@ -20,10 +18,7 @@ use sui_indexer_alt_framework::task::TrySpawnStreamExt;
 use sui_indexer_alt_schema::objects::StoredObjInfo;
 use sui_indexer_alt_schema::schema::obj_info;
 use sui_pg_db::Db;
-use sui_snapshot::{
-    reader::{download_bytes, LiveObjectIter, StateSnapshotReaderV1},
-    FileMetadata,
-};
+use sui_snapshot::reader::{download_bytes, LiveObjectIter, SnapshotLayer, StateSnapshotReaderV1};
 use sui_storage::object_store::ObjectStoreGetExt;
 
 use crate::Args;
@@ -82,22 +77,13 @@ impl SnapshotRestorer {
             epoch = self.restore_args.start_epoch,
             "Starting snapshot restore"
         );
-        let (sha3_digests, num_part_files) = self.snapshot_reader.compute_checksum().await?;
-        let (input_files, epoch_dir, remote_object_store, _concurrency) =
+        let (layers, remote_object_store, _concurrency) =
             self.snapshot_reader.export_metadata().await?;
-        let owned_input_files: Vec<(u32, (u32, FileMetadata))> = input_files
-            .into_iter()
-            .map(|(bucket, (part_num, metadata))| (*bucket, (part_num, metadata.clone())))
-            .collect();
         info!("Start snapshot restore.");
-        self.restore_object_infos(
-            owned_input_files,
-            epoch_dir,
-            remote_object_store,
-            sha3_digests,
-            num_part_files,
-        )
-        .await?;
+        for layer in layers {
+            self.restore_object_infos(layer, remote_object_store.clone())
+                .await?;
+        }
         info!(
             epoch = self.restore_args.start_epoch,
             "Finished snapshot restore"
@@ -107,14 +93,13 @@ impl SnapshotRestorer {
 
     async fn restore_object_infos(
         &self,
-        input_files: Vec<(u32, (u32, FileMetadata))>,
-        epoch_dir: Path,
+        mut layer: SnapshotLayer,
         remote_object_store: Arc<dyn ObjectStoreGetExt>,
-        sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
-        num_part_files: usize,
     ) -> anyhow::Result<()> {
+        let input_files = std::mem::take(&mut layer.input_files);
+        let layer = Arc::new(layer);
         let move_object_progress_bar = Arc::new(self.snapshot_reader.get_multi_progress().add(
-            ProgressBar::new(num_part_files as u64).with_style(
+            ProgressBar::new(input_files.len() as u64).with_style(
                 ProgressStyle::with_template(
                     "[{elapsed_precise}] {wide_bar} {pos} out of {len} move object files restored ({msg})",
                 )
@@ -126,9 +111,8 @@ impl SnapshotRestorer {
             .try_for_each_spawned(
                 self.restore_args.concurrency,
                 |(bucket, (part_num, file_metadata))| {
-                    let epoch_dir = epoch_dir.clone();
+                    let layer = layer.clone();
                     let remote_object_store = remote_object_store.clone();
-                    let sha3_digests = sha3_digests.clone();
                     let bar = move_object_progress_bar.clone();
                     let db = self.db.clone();
                     let next_cp = self.next_checkpoint_after_epoch;
@@ -143,8 +127,8 @@ impl SnapshotRestorer {
                         let (bytes, _) = download_bytes(
                             remote_object_store,
                             &file_metadata,
-                            epoch_dir,
-                            sha3_digests,
+                            layer.epoch_dir.clone(),
+                            layer.sha3_digests.clone(),
                             &&bucket,
                             &part_num,
                             Some(512), // max_timeout_secs
@@ -157,10 +141,12 @@ impl SnapshotRestorer {
                         );
                         let object_infos = LiveObjectIter::new(&file_metadata, bytes.clone())?
                             .filter_map(|object| match object {
-                                LiveObject::Normal(obj) => {
+                                LiveObject::Normal(obj)
+                                    if layer.is_live(&obj.compute_object_reference()) =>
+                                {
                                     Some(StoredObjInfo::from_object(&obj, next_cp as i64))
                                 }
-                                LiveObject::Wrapped(_) => None,
+                                LiveObject::Normal(_) | LiveObject::Wrapped(_) => None,
                             })
                             .collect::<Result<Vec<_>, _>>()?;
                         let num_object_infos = object_infos.len();
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS tx_digests_tx_digest;
+DROP INDEX IF EXISTS ev_emit_mod_sender_tx_sequence_number;
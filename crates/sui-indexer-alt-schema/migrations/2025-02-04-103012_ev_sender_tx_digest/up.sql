@@ -0,0 +1,7 @@
+-- Support filtering events by sender alone, without a package or module.
+CREATE INDEX IF NOT EXISTS ev_emit_mod_sender_tx_sequence_number
+ON ev_emit_mod (sender, tx_sequence_number);
+
+-- Support looking up a transaction's sequence number from its digest.
+CREATE INDEX IF NOT EXISTS tx_digests_tx_digest
+ON tx_digests (tx_digest);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS obj_dynamic_fields;
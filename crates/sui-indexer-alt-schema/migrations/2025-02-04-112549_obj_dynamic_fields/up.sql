@@ -0,0 +1,27 @@
+-- A table of dynamic field objects (`0x2::dynamic_field::Field` instances),
+-- keyed on object ID and checkpoint sequence number. A row is inserted when a
+-- field is created or unwrapped, and a record with all fields except the
+-- primary key set to `NULL` is inserted when it is deleted or wrapped, which
+-- we need for consistency queries.
+--
+-- A field's parent and kind cannot change while it is live, so there is no
+-- need to record mutations.
+CREATE TABLE IF NOT EXISTS obj_dynamic_fields
+(
+    object_id                   BYTEA         NOT NULL,
+    cp_sequence_number          BIGINT        NOT NULL,
+    -- The ID of the object that owns this field.
+    parent_id                   BYTEA,
+    -- An enum describing the kind of dynamic field:
+    --
+    --   Dynamic field = 0,
+    --   Dynamic object field = 1.
+    df_kind                     SMALLINT,
+    -- For dynamic object fields, the ID of the object stored in the field.
+    -- NULL for dynamic fields.
+    value_id                    BYTEA,
+    PRIMARY KEY (object_id, cp_sequence_number)
+);
+
+CREATE INDEX IF NOT EXISTS obj_dynamic_fields_parent
+ON obj_dynamic_fields (parent_id, object_id, cp_sequence_number DESC);
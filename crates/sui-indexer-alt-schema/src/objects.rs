@@ -8,9 +8,14 @@ use diesel::{
 };
 
 use sui_field_count::FieldCount;
-use sui_types::object::{Object, Owner};
+use sui_types::{
+    base_types::ObjectID,
+    dynamic_field::DynamicFieldInfo,
+    object::{Object, Owner},
+    TypeTag,
+};
 
-use crate::schema::{coin_balance_buckets, kv_objects, obj_info, obj_versions};
+use crate::schema::{coin_balance_buckets, kv_objects, obj_dynamic_fields, obj_info, obj_versions};
 
 #[derive(Insertable, Debug, Clone, FieldCount, Queryable)]
 #[diesel(table_name = kv_objects, primary_key(object_id, object_version))]
@@ -48,6 +53,14 @@ pub enum StoredCoinOwnerKind {
     Consensus = 1,
 }
 
+#[derive(AsExpression, FromSqlRow, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[diesel(sql_type = SmallInt)]
+#[repr(i16)]
+pub enum StoredDynamicFieldKind {
+    DynamicField = 0,
+    DynamicObject = 1,
+}
+
 #[derive(Insertable, Debug, Clone, FieldCount, Queryable)]
 #[diesel(table_name = obj_info, primary_key(object_id, cp_sequence_number))]
 #[diesel(treat_none_as_default_value = false)]
@@ -74,6 +87,17 @@ pub struct StoredCoinBalanceBucket {
     pub coin_balance_bucket: Option<i16>,
 }
 
+#[derive(Insertable, Queryable, Debug, Clone, FieldCount, Eq, PartialEq)]
+#[diesel(table_name = obj_dynamic_fields, primary_key(object_id, cp_sequence_number))]
+#[diesel(treat_none_as_default_value = false)]
+pub struct StoredObjDynamicField {
+    pub object_id: Vec<u8>,
+    pub cp_sequence_number: i64,
+    pub parent_id: Option<Vec<u8>>,
+    pub df_kind: Option<StoredDynamicFieldKind>,
+    pub value_id: Option<Vec<u8>>,
+}
+
 impl StoredObjInfo {
     pub fn from_object(object: &Object, cp_sequence_number: i64) -> anyhow::Result<Self> {
         let type_ = object.type_();
@@ -117,6 +141,61 @@ impl StoredObjInfo {
     }
 }
 
+impl StoredObjDynamicField {
+    /// Returns the record for `object` if it is a dynamic field (an instance of
+    /// `0x2::dynamic_field::Field`) owned by another object, and `None` otherwise.
+    pub fn from_object(object: &Object, cp_sequence_number: i64) -> anyhow::Result<Option<Self>> {
+        let Some(move_object) = object.data.try_as_move() else {
+            return Ok(None);
+        };
+
+        if !move_object.type_().is_dynamic_field() {
+            return Ok(None);
+        }
+
+        let Owner::ObjectOwner(parent) = object.owner() else {
+            return Ok(None);
+        };
+
+        let is_dynamic_object = matches!(
+            move_object.type_().type_params().first(),
+            Some(TypeTag::Struct(name)) if DynamicFieldInfo::is_dynamic_object_field_wrapper(name),
+        );
+
+        // The value of a dynamic object field is the ID of the object it wraps, and it is the
+        // last field in the `Field` struct.
+        let value_id = if is_dynamic_object {
+            let contents = move_object.contents();
+            let bytes = contents
+                .len()
+                .checked_sub(ObjectID::LENGTH)
+                .map(|start| &contents[start..])
+                .with_context(|| {
+                    format!(
+                        "Dynamic object field {} is too small to contain a value ID",
+                        object.id().to_canonical_display(/* with_prefix */ true),
+                    )
+                })?;
+
+            Some(bytes.to_vec())
+        } else {
+            None
+        };
+
+        Ok(Some(Self {
+            object_id: object.id().to_vec(),
+            cp_sequence_number,
+            parent_id: Some(parent.to_vec()),
+            df_kind: Some(if is_dynamic_object {
+                StoredDynamicFieldKind::DynamicObject
+            } else {
+                StoredDynamicFieldKind::DynamicField
+            }),
+            value_id,
+        }))
+    }
+}
+
 impl<DB: Backend> serialize::ToSql<SmallInt, DB> for StoredOwnerKind
 where
     i16: serialize::ToSql<SmallInt, DB>,
@@ -170,3 +249,28 @@ where
         })
     }
 }
+
+impl<DB: Backend> serialize::ToSql<SmallInt, DB> for StoredDynamicFieldKind
+where
+    i16: serialize::ToSql<SmallInt, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut serialize::Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            StoredDynamicFieldKind::DynamicField => 0.to_sql(out),
+            StoredDynamicFieldKind::DynamicObject => 1.to_sql(out),
+        }
+    }
+}
+
+impl<DB: Backend> deserialize::FromSql<SmallInt, DB> for StoredDynamicFieldKind
+where
+    i16: deserialize::FromSql<SmallInt, DB>,
+{
+    fn from_sql(raw: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        Ok(match i16::from_sql(raw)? {
+            0 => StoredDynamicFieldKind::DynamicField,
+            1 => StoredDynamicFieldKind::DynamicObject,
+            o => return Err(format!("Unexpected StoredDynamicFieldKind: {o}").into()),
+        })
+    }
+}
@@ -116,6 +116,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    obj_dynamic_fields (object_id, cp_sequence_number) {
+        object_id -> Bytea,
+        cp_sequence_number -> Int8,
+        parent_id -> Nullable<Bytea>,
+        df_kind -> Nullable<Int2>,
+        value_id -> Nullable<Bytea>,
+    }
+}
+
 diesel::table! {
     obj_info (object_id, cp_sequence_number) {
         object_id -> Bytea,
@@ -216,6 +226,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     kv_objects,
     kv_protocol_configs,
     kv_transactions,
+    obj_dynamic_fields,
     obj_info,
     obj_versions,
     sum_displays,
@@ -58,6 +58,30 @@ pub enum Command {
         skip_migrations: bool,
     },
 
+    /// List the checkpoints that pipelines have given up on indexing and skipped over, as allowed
+    /// by their failure policy.
+    ListDeadLetters {
+        /// Only list dead letters for this pipeline.
+        #[arg(long)]
+        pipeline: Option<String>,
+    },
+
+    /// Index dead-lettered checkpoints again for a concurrent pipeline (e.g. after fixing the
+    /// issue that caused them to fail), and clear their dead letters once they succeed.
+    ReplayDeadLetters {
+        #[command(flatten)]
+        client_args: ClientArgs,
+
+        /// The pipeline to replay checkpoints for.
+        #[arg(long)]
+        pipeline: String,
+
+        /// Checkpoints to replay. If none are provided, all the pipeline's dead letters are
+        /// replayed.
+        #[arg(long, action = clap::ArgAction::Append)]
+        checkpoint: Vec<u64>,
+    },
+
     /// Run the benchmark. It will load ingestion data from the given path and run the pipelines.
     /// The first and last checkpoint will be determined automatically based on the ingestion data.
     /// Note that the indexer will not be bootstrapped from genesis, and hence will
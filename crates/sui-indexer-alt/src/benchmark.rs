@@ -49,8 +49,8 @@ pub async fn run_benchmark(
     };
 
     let client_args = ClientArgs {
-        remote_store_url: None,
         local_ingestion_path: Some(ingestion_path.clone()),
+        ..Default::default()
     };
 
     let cur_time = Instant::now();
@@ -9,7 +9,7 @@ use sui_indexer_alt_framework::{
     pipeline::{
         concurrent::{ConcurrentConfig, PrunerConfig},
         sequential::SequentialConfig,
-        CommitterConfig,
+        CommitterConfig, FailurePolicy,
     },
 };
 use tracing::warn;
@@ -91,6 +91,7 @@ pub struct CommitterLayer {
     write_concurrency: Option<usize>,
     collect_interval_ms: Option<u64>,
     watermark_interval_ms: Option<u64>,
+    failure_policy: Option<FailurePolicy>,
 
     #[serde(flatten)]
     pub extra: toml::Table,
@@ -115,6 +116,7 @@ pub struct PrunerLayer {
 pub struct PipelineLayer {
     // Consistent pipelines
     pub coin_balance_buckets: Option<CommitterLayer>,
+    pub obj_dynamic_fields: Option<CommitterLayer>,
     pub obj_info: Option<CommitterLayer>,
 
     // Sequential pipelines
@@ -224,6 +226,7 @@ impl CommitterLayer {
             watermark_interval_ms: self
                 .watermark_interval_ms
                 .unwrap_or(base.watermark_interval_ms),
+            failure_policy: self.failure_policy.unwrap_or(base.failure_policy),
         }
     }
 }
@@ -247,6 +250,7 @@ impl PipelineLayer {
     pub fn example() -> Self {
         PipelineLayer {
             coin_balance_buckets: Some(Default::default()),
+            obj_dynamic_fields: Some(Default::default()),
             obj_info: Some(Default::default()),
             sum_displays: Some(Default::default()),
             sum_packages: Some(Default::default()),
@@ -337,6 +341,7 @@ impl Merge for CommitterLayer {
             write_concurrency: other.write_concurrency.or(self.write_concurrency),
             collect_interval_ms: other.collect_interval_ms.or(self.collect_interval_ms),
             watermark_interval_ms: other.watermark_interval_ms.or(self.watermark_interval_ms),
+            failure_policy: other.failure_policy.or(self.failure_policy),
             extra: Default::default(),
         }
     }
@@ -369,6 +374,7 @@ impl Merge for PipelineLayer {
         check_extra("pipeline", other.extra);
         PipelineLayer {
             coin_balance_buckets: self.coin_balance_buckets.merge(other.coin_balance_buckets),
+            obj_dynamic_fields: self.obj_dynamic_fields.merge(other.obj_dynamic_fields),
             obj_info: self.obj_info.merge(other.obj_info),
             sum_displays: self.sum_displays.merge(other.sum_displays),
             sum_packages: self.sum_packages.merge(other.sum_packages),
@@ -443,6 +449,7 @@ impl From<CommitterConfig> for CommitterLayer {
             write_concurrency: Some(config.write_concurrency),
             collect_interval_ms: Some(config.collect_interval_ms),
             watermark_interval_ms: Some(config.watermark_interval_ms),
+            failure_policy: Some(config.failure_policy),
             extra: Default::default(),
         }
     }
@@ -496,6 +503,7 @@ mod tests {
                     write_concurrency: Some(10),
                     collect_interval_ms: Some(1000),
                     watermark_interval_ms: None,
+                    failure_policy: None,
                     extra: Default::default(),
                 }),
                 checkpoint_lag: Some(100),
@@ -507,6 +515,7 @@ mod tests {
                     write_concurrency: Some(5),
                     collect_interval_ms: Some(500),
                     watermark_interval_ms: None,
+                    failure_policy: None,
                     extra: Default::default(),
                 }),
                 ..Default::default()
@@ -520,6 +529,7 @@ mod tests {
                     write_concurrency: Some(5),
                     collect_interval_ms: None,
                     watermark_interval_ms: Some(500),
+                    failure_policy: None,
                     extra: Default::default(),
                 }),
                 checkpoint_lag: Some(200),
@@ -530,6 +540,7 @@ mod tests {
                     write_concurrency: Some(10),
                     collect_interval_ms: None,
                     watermark_interval_ms: Some(1000),
+                    failure_policy: None,
                     extra: Default::default(),
                 }),
                 ..Default::default()
@@ -549,6 +560,7 @@ mod tests {
                         write_concurrency: Some(5),
                         collect_interval_ms: Some(1000),
                         watermark_interval_ms: Some(500),
+                        failure_policy: None,
                         extra: _,
                     }),
                     checkpoint_lag: Some(200),
@@ -559,6 +571,7 @@ mod tests {
                         write_concurrency: Some(10),
                         collect_interval_ms: None,
                         watermark_interval_ms: Some(1000),
+                        failure_policy: None,
                         extra: _,
                     }),
                     checkpoint_lag: None,
@@ -569,6 +582,7 @@ mod tests {
                         write_concurrency: Some(5),
                         collect_interval_ms: Some(500),
                         watermark_interval_ms: None,
+                        failure_policy: None,
                         extra: _,
                     }),
                     pruner: None,
@@ -586,6 +600,7 @@ mod tests {
                         write_concurrency: Some(10),
                         collect_interval_ms: Some(1000),
                         watermark_interval_ms: Some(500),
+                        failure_policy: None,
                         extra: _,
                     }),
                     checkpoint_lag: Some(100),
@@ -596,6 +611,7 @@ mod tests {
                         write_concurrency: Some(10),
                         collect_interval_ms: None,
                         watermark_interval_ms: Some(1000),
+                        failure_policy: None,
                         extra: _,
                     }),
                     checkpoint_lag: None,
@@ -606,6 +622,7 @@ mod tests {
                         write_concurrency: Some(5),
                         collect_interval_ms: Some(500),
                         watermark_interval_ms: None,
+                        failure_policy: None,
                         extra: _,
                     }),
                     pruner: None,
@@ -664,6 +681,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finish_committer_failure_policy() {
+        let layer: CommitterLayer = toml::from_str(
+            r#"
+            failure-policy = { kind = "dead-letter", max-attempts = 3 }
+            "#,
+        )
+        .unwrap();
+
+        assert_matches!(
+            layer.finish(CommitterConfig::default()),
+            CommitterConfig {
+                write_concurrency: 5,
+                collect_interval_ms: 500,
+                watermark_interval_ms: 500,
+                failure_policy: FailurePolicy::DeadLetter { max_attempts: 3 },
+            },
+        );
+    }
+
     #[test]
     fn finish_concurrent_unpruned_override() {
         let layer = ConcurrentLayer {
@@ -677,6 +714,7 @@ mod tests {
                 write_concurrency: 5,
                 collect_interval_ms: 50,
                 watermark_interval_ms: 500,
+                failure_policy: FailurePolicy::Halt,
             },
             pruner: Some(PrunerConfig::default()),
         };
@@ -688,6 +726,7 @@ mod tests {
                     write_concurrency: 5,
                     collect_interval_ms: 50,
                     watermark_interval_ms: 500,
+                    failure_policy: FailurePolicy::Halt,
                 },
                 pruner: None,
             },
@@ -707,6 +746,7 @@ mod tests {
                 write_concurrency: 5,
                 collect_interval_ms: 50,
                 watermark_interval_ms: 500,
+                failure_policy: FailurePolicy::Halt,
             },
             pruner: None,
         };
@@ -718,6 +758,7 @@ mod tests {
                     write_concurrency: 5,
                     collect_interval_ms: 50,
                     watermark_interval_ms: 500,
+                    failure_policy: FailurePolicy::Halt,
                 },
                 pruner: None,
             },
@@ -740,6 +781,7 @@ mod tests {
                 write_concurrency: 5,
                 collect_interval_ms: 50,
                 watermark_interval_ms: 500,
+                failure_policy: FailurePolicy::Halt,
             },
             pruner: Some(PrunerConfig {
                 interval_ms: 100,
@@ -757,6 +799,7 @@ mod tests {
                     write_concurrency: 5,
                     collect_interval_ms: 50,
                     watermark_interval_ms: 500,
+                    failure_policy: FailurePolicy::Halt,
                 },
                 pruner: Some(PrunerConfig {
                     interval_ms: 1000,
@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Context, Result};
+use prometheus::Registry;
+use sui_indexer_alt_framework::{
+    handlers::cp_sequence_numbers::CpSequenceNumbers,
+    ingestion::{ClientArgs, IngestionConfig},
+    models::dead_letters::StoredDeadLetter,
+    pipeline::Processor,
+    Indexer, IndexerArgs,
+};
+use sui_indexer_alt_schema::MIGRATIONS;
+use sui_pg_db::{Db, DbArgs};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::{
+    bootstrap::bootstrap,
+    for_each_pipeline,
+    handlers::{
+        coin_balance_buckets::CoinBalanceBuckets, ev_emit_mod::EvEmitMod,
+        ev_struct_inst::EvStructInst, kv_checkpoints::KvCheckpoints, kv_epoch_ends::KvEpochEnds,
+        kv_epoch_starts::KvEpochStarts, kv_feature_flags::KvFeatureFlags, kv_objects::KvObjects,
+        kv_protocol_configs::KvProtocolConfigs, kv_transactions::KvTransactions,
+        obj_dynamic_fields::ObjDynamicFields, obj_info::ObjInfo, obj_versions::ObjVersions,
+        sum_displays::SumDisplays, sum_packages::SumPackages,
+        tx_affected_addresses::TxAffectedAddresses, tx_affected_objects::TxAffectedObjects,
+        tx_balance_changes::TxBalanceChanges, tx_calls::TxCalls, tx_digests::TxDigests,
+        tx_kinds::TxKinds,
+    },
+};
+
+/// Print the checkpoints that pipelines have given up on, optionally limited to just those for
+/// `pipeline`.
+pub async fn list_dead_letters(db_args: DbArgs, pipeline: Option<String>) -> Result<()> {
+    let db = Db::for_read(db_args)
+        .await
+        .context("Failed to connect to database")?;
+
+    let mut conn = db.connect().await?;
+    let letters = StoredDeadLetter::list(&mut conn, pipeline.as_deref())
+        .await
+        .context("Failed to list dead letters")?;
+
+    for StoredDeadLetter {
+        pipeline,
+        checkpoint,
+        stage,
+        error,
+        recorded_at,
+    } in letters
+    {
+        println!("{pipeline}\t{checkpoint}\t{stage}\t{recorded_at}\t{error}");
+    }
+
+    Ok(())
+}
+
+/// Index `checkpoints` again for the concurrent pipeline called `pipeline`, and clear their dead
+/// letters. If no checkpoints are provided, all the pipeline's dead-lettered checkpoints are
+/// replayed.
+///
+/// Sequential pipelines never record dead letters, so they cannot be replayed.
+pub async fn replay_dead_letters(
+    db_args: DbArgs,
+    client_args: ClientArgs,
+    pipeline: String,
+    checkpoints: Vec<u64>,
+    registry: &Registry,
+    cancel: CancellationToken,
+) -> Result<()> {
+    let ingestion = IngestionConfig::default();
+    let retry_interval = ingestion.retry_interval();
+
+    let indexer = Indexer::new(
+        db_args,
+        IndexerArgs::default(),
+        client_args,
+        ingestion,
+        &MIGRATIONS,
+        registry,
+        cancel.clone(),
+    )
+    .await?;
+
+    let checkpoints = if checkpoints.is_empty() {
+        let mut conn = indexer.db().connect().await?;
+        StoredDeadLetter::list(&mut conn, Some(&pipeline))
+            .await
+            .context("Failed to list dead letters")?
+            .into_iter()
+            .map(|letter| letter.checkpoint as u64)
+            .collect()
+    } else {
+        checkpoints
+    };
+
+    info!(pipeline, ?checkpoints, "Replaying checkpoints");
+
+    // One macro per kind of pipeline in `for_each_pipeline`, each of which replays the
+    // checkpoints with the pipeline's handler if its name matches. Genesis is only bootstrapped if
+    // the pipeline being replayed needs it.
+
+    macro_rules! replay_genesis {
+        ($handler:ident, $config:ident) => {
+            if pipeline == <$handler as Processor>::NAME {
+                let genesis = bootstrap(&indexer, retry_interval, cancel.clone()).await?;
+                return indexer.replay(&$handler(genesis), &checkpoints).await;
+            }
+        };
+    }
+
+    macro_rules! replay_concurrent {
+        ($handler:expr, $config:ident) => {{
+            let handler = $handler;
+            if pipeline == name(&handler) {
+                return indexer.replay(&handler, &checkpoints).await;
+            }
+        }};
+    }
+
+    macro_rules! replay_sequential {
+        ($handler:expr, $config:ident) => {
+            if pipeline == name(&$handler) {
+                bail!("Cannot replay checkpoints for sequential pipeline {pipeline}");
+            }
+        };
+    }
+
+    for_each_pipeline!(
+        replay_genesis,
+        replay_concurrent,
+        replay_sequential,
+        replay_concurrent,
+    );
+
+    bail!("Unknown pipeline: {pipeline}")
+}
+
+/// The name of the pipeline that `handler` belongs to.
+fn name<P: Processor>(_: &P) -> &'static str {
+    P::NAME
+}
@@ -54,6 +54,8 @@ impl Processor for EvStructInst {
 
 #[async_trait::async_trait]
 impl Handler for EvStructInst {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
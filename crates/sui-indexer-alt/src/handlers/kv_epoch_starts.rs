@@ -68,6 +68,8 @@ impl Processor for KvEpochStarts {
 
 #[async_trait::async_trait]
 impl Handler for KvEpochStarts {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 1;
 
     async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
@@ -53,6 +53,8 @@ impl Processor for KvObjects {
 
 #[async_trait::async_trait]
 impl Handler for KvObjects {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
@@ -11,6 +11,7 @@ pub(crate) mod kv_feature_flags;
 pub(crate) mod kv_objects;
 pub(crate) mod kv_protocol_configs;
 pub(crate) mod kv_transactions;
+pub(crate) mod obj_dynamic_fields;
 pub(crate) mod obj_info;
 pub(crate) mod obj_versions;
 pub(crate) mod sum_displays;
@@ -0,0 +1,330 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::Result;
+use diesel::sql_query;
+use diesel_async::RunQueryDsl;
+use sui_field_count::FieldCount;
+use sui_indexer_alt_framework::pipeline::{concurrent::Handler, Processor};
+use sui_indexer_alt_schema::{objects::StoredObjDynamicField, schema::obj_dynamic_fields};
+use sui_pg_db as db;
+use sui_types::{base_types::ObjectID, full_checkpoint_content::CheckpointData};
+
+use crate::consistent_pruning::{PruningInfo, PruningLookupTable};
+
+#[derive(Default)]
+pub(crate) struct ObjDynamicFields {
+    pruning_lookup_table: Arc<PruningLookupTable>,
+}
+
+pub(crate) enum ProcessedObjDynamicFieldUpdate {
+    Insert(StoredObjDynamicField),
+    Delete(ObjectID),
+}
+
+pub(crate) struct ProcessedObjDynamicField {
+    pub cp_sequence_number: u64,
+    pub update: ProcessedObjDynamicFieldUpdate,
+}
+
+impl Processor for ObjDynamicFields {
+    const NAME: &'static str = "obj_dynamic_fields";
+    type Value = ProcessedObjDynamicField;
+
+    fn process(&self, checkpoint: &Arc<CheckpointData>) -> Result<Vec<Self::Value>> {
+        let cp_sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let checkpoint_input_objects = checkpoint.checkpoint_input_objects();
+        let latest_live_output_objects = checkpoint
+            .latest_live_output_objects()
+            .into_iter()
+            .map(|o| (o.id(), o))
+            .collect::<BTreeMap<_, _>>();
+        let mut values: BTreeMap<ObjectID, Self::Value> = BTreeMap::new();
+        let mut prune_info = PruningInfo::new();
+        for (object_id, object) in checkpoint_input_objects.iter() {
+            if latest_live_output_objects.contains_key(object_id) {
+                continue;
+            }
+
+            // A dynamic field that was deleted or wrapped in this checkpoint. Record a tombstone
+            // so that queries do not return it.
+            if StoredObjDynamicField::from_object(object, cp_sequence_number as i64)?.is_some() {
+                values.insert(
+                    *object_id,
+                    ProcessedObjDynamicField {
+                        cp_sequence_number,
+                        update: ProcessedObjDynamicFieldUpdate::Delete(*object_id),
+                    },
+                );
+                prune_info.add_deleted_object(*object_id);
+            }
+        }
+        for (object_id, object) in latest_live_output_objects.iter() {
+            // A dynamic field's parent and kind never change while it is live, so only newly
+            // created or unwrapped fields need a new entry.
+            if checkpoint_input_objects.contains_key(object_id) {
+                continue;
+            }
+
+            if let Some(stored) =
+                StoredObjDynamicField::from_object(object, cp_sequence_number as i64)?
+            {
+                values.insert(
+                    *object_id,
+                    ProcessedObjDynamicField {
+                        cp_sequence_number,
+                        update: ProcessedObjDynamicFieldUpdate::Insert(stored),
+                    },
+                );
+            }
+        }
+        self.pruning_lookup_table
+            .insert(cp_sequence_number, prune_info);
+
+        Ok(values.into_values().collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for ObjDynamicFields {
+    type Store = db::Db;
+
+    const PRUNING_REQUIRES_PROCESSED_VALUES: bool = true;
+
+    async fn commit(values: &[Self::Value], conn: &mut db::Connection<'_>) -> Result<usize> {
+        let stored = values
+            .iter()
+            .map(|v| v.into())
+            .collect::<Vec<StoredObjDynamicField>>();
+        Ok(diesel::insert_into(obj_dynamic_fields::table)
+            .values(stored)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?)
+    }
+
+    async fn prune(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut db::Connection<'_>,
+    ) -> Result<usize> {
+        use sui_indexer_alt_schema::schema::obj_dynamic_fields::dsl;
+
+        let to_prune = self
+            .pruning_lookup_table
+            .get_prune_info(from, to_exclusive)?;
+
+        if to_prune.is_empty() {
+            self.pruning_lookup_table.gc_prune_info(from, to_exclusive);
+            return Ok(0);
+        }
+
+        // For each (object_id, cp_sequence_number_exclusive), delete all entries in
+        // obj_dynamic_fields with cp_sequence_number less than cp_sequence_number_exclusive that
+        // match the object_id.
+
+        let values = to_prune
+            .iter()
+            .map(|(object_id, seq_number)| {
+                let object_id_hex = hex::encode(object_id);
+                format!("('\\x{}'::BYTEA, {}::BIGINT)", object_id_hex, seq_number)
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = format!(
+            "
+            WITH to_prune_data (object_id, cp_sequence_number_exclusive) AS (
+                VALUES {}
+            )
+            DELETE FROM obj_dynamic_fields
+            USING to_prune_data
+            WHERE obj_dynamic_fields.{:?} = to_prune_data.object_id
+              AND obj_dynamic_fields.{:?} < to_prune_data.cp_sequence_number_exclusive
+            ",
+            values,
+            dsl::object_id,
+            dsl::cp_sequence_number,
+        );
+        let rows_deleted = sql_query(query).execute(conn).await?;
+        self.pruning_lookup_table.gc_prune_info(from, to_exclusive);
+        Ok(rows_deleted)
+    }
+}
+
+impl FieldCount for ProcessedObjDynamicField {
+    const FIELD_COUNT: usize = StoredObjDynamicField::FIELD_COUNT;
+}
+
+impl From<&ProcessedObjDynamicField> for StoredObjDynamicField {
+    fn from(value: &ProcessedObjDynamicField) -> Self {
+        match &value.update {
+            ProcessedObjDynamicFieldUpdate::Insert(stored) => stored.clone(),
+            ProcessedObjDynamicFieldUpdate::Delete(object_id) => StoredObjDynamicField {
+                object_id: object_id.to_vec(),
+                cp_sequence_number: value.cp_sequence_number as i64,
+                parent_id: None,
+                df_kind: None,
+                value_id: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_indexer_alt_framework::Indexer;
+    use sui_indexer_alt_schema::{objects::StoredDynamicFieldKind, MIGRATIONS};
+    use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
+
+    use super::*;
+
+    // A helper function to return all entries in the obj_dynamic_fields table sorted by object_id
+    // and cp_sequence_number.
+    async fn get_all_obj_dynamic_fields(
+        conn: &mut db::Connection<'_>,
+    ) -> Result<Vec<StoredObjDynamicField>> {
+        use diesel::QueryDsl;
+        use sui_indexer_alt_schema::schema::obj_dynamic_fields::dsl;
+
+        Ok(obj_dynamic_fields::table
+            .order_by((dsl::object_id, dsl::cp_sequence_number))
+            .load(conn)
+            .await?)
+    }
+
+    #[tokio::test]
+    async fn test_process_ignores_other_objects() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+        let obj_dynamic_fields = ObjDynamicFields::default();
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_owned_object(0)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        assert!(result.is_empty());
+
+        builder = builder
+            .start_transaction(0)
+            .delete_object(0)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        assert!(result.is_empty());
+
+        let rows_pruned = obj_dynamic_fields.prune(0, 2, &mut conn).await.unwrap();
+        assert_eq!(rows_pruned, 0);
+    }
+
+    #[tokio::test]
+    async fn test_process_dynamic_field() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+        let obj_dynamic_fields = ObjDynamicFields::default();
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_dynamic_field(1, 0, 42, 43)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(matches!(
+            result[0].update,
+            ProcessedObjDynamicFieldUpdate::Insert(_)
+        ));
+        let rows_inserted = ObjDynamicFields::commit(&result, &mut conn).await.unwrap();
+        assert_eq!(rows_inserted, 1);
+
+        let all = get_all_obj_dynamic_fields(&mut conn).await.unwrap();
+        let field = TestCheckpointDataBuilder::derive_object_id(1);
+        let parent = TestCheckpointDataBuilder::derive_object_id(0);
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].object_id, field.to_vec());
+        assert_eq!(all[0].cp_sequence_number, 0);
+        assert_eq!(all[0].parent_id, Some(parent.to_vec()));
+        assert_eq!(all[0].df_kind, Some(StoredDynamicFieldKind::DynamicField));
+        assert_eq!(all[0].value_id, None);
+
+        // Mutating the field does not change its entry.
+        builder = builder
+            .start_transaction(0)
+            .mutate_object(1)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        assert!(result.is_empty());
+
+        builder = builder
+            .start_transaction(0)
+            .delete_object(1)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(matches!(
+            result[0].update,
+            ProcessedObjDynamicFieldUpdate::Delete(_)
+        ));
+        let rows_inserted = ObjDynamicFields::commit(&result, &mut conn).await.unwrap();
+        assert_eq!(rows_inserted, 1);
+
+        let all = get_all_obj_dynamic_fields(&mut conn).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].cp_sequence_number, 2);
+        assert_eq!(all[1].parent_id, None);
+        assert_eq!(all[1].df_kind, None);
+
+        let rows_pruned = obj_dynamic_fields.prune(0, 3, &mut conn).await.unwrap();
+        // The field is deleted, so both the creation entry and the tombstone are pruned.
+        assert_eq!(rows_pruned, 2);
+
+        let all = get_all_obj_dynamic_fields(&mut conn).await.unwrap();
+        assert!(all.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_dynamic_object_field() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+        let obj_dynamic_fields = ObjDynamicFields::default();
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_dynamic_object_field(1, 0, 42, 2)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        let rows_inserted = ObjDynamicFields::commit(&result, &mut conn).await.unwrap();
+        assert_eq!(rows_inserted, 1);
+
+        let all = get_all_obj_dynamic_fields(&mut conn).await.unwrap();
+        let child = TestCheckpointDataBuilder::derive_object_id(2);
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].df_kind, Some(StoredDynamicFieldKind::DynamicObject));
+        assert_eq!(all[0].value_id, Some(child.to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_process_create_and_delete() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.db().connect().await.unwrap();
+        let obj_dynamic_fields = ObjDynamicFields::default();
+        // A field that is created and deleted in the same checkpoint leaves no trace.
+        let mut builder = TestCheckpointDataBuilder::new(0)
+            .start_transaction(0)
+            .create_dynamic_field(1, 0, 42, 43)
+            .finish_transaction()
+            .start_transaction(0)
+            .delete_object(1)
+            .finish_transaction();
+        let checkpoint = builder.build_checkpoint();
+        let result = obj_dynamic_fields.process(&Arc::new(checkpoint)).unwrap();
+        assert!(result.is_empty());
+
+        let rows_pruned = obj_dynamic_fields.prune(0, 1, &mut conn).await.unwrap();
+        assert_eq!(rows_pruned, 0);
+    }
+}
@@ -54,6 +54,7 @@ impl Processor for SumPackages {
 
 #[async_trait::async_trait]
 impl Handler for SumPackages {
+    type Store = db::Db;
     type Batch = BTreeMap<Vec<u8>, StoredPackage>;
 
     fn batch(batch: &mut Self::Batch, values: Vec<Self::Value>) {
@@ -64,6 +64,8 @@ impl Processor for TxAffectedAddresses {
 
 #[async_trait::async_trait]
 impl Handler for TxAffectedAddresses {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
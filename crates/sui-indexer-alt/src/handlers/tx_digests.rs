@@ -44,6 +44,8 @@ impl Processor for TxDigests {
 
 #[async_trait::async_trait]
 impl Handler for TxDigests {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
@@ -55,6 +55,8 @@ impl Processor for TxKinds {
 
 #[async_trait::async_trait]
 impl Handler for TxKinds {
+    type Store = db::Db;
+
     const MIN_EAGER_ROWS: usize = 100;
     const MAX_PENDING_ROWS: usize = 10000;
 
@@ -3,16 +3,17 @@
 
 use anyhow::Context;
 use bootstrap::bootstrap;
-use config::{IndexerConfig, PipelineLayer};
+use config::IndexerConfig;
 use handlers::coin_balance_buckets::CoinBalanceBuckets;
 use handlers::{
     ev_emit_mod::EvEmitMod, ev_struct_inst::EvStructInst, kv_checkpoints::KvCheckpoints,
     kv_epoch_ends::KvEpochEnds, kv_epoch_starts::KvEpochStarts, kv_feature_flags::KvFeatureFlags,
     kv_objects::KvObjects, kv_protocol_configs::KvProtocolConfigs, kv_transactions::KvTransactions,
-    obj_info::ObjInfo, obj_versions::ObjVersions, sum_displays::SumDisplays,
-    sum_packages::SumPackages, tx_affected_addresses::TxAffectedAddresses,
-    tx_affected_objects::TxAffectedObjects, tx_balance_changes::TxBalanceChanges,
-    tx_calls::TxCalls, tx_digests::TxDigests, tx_kinds::TxKinds,
+    obj_dynamic_fields::ObjDynamicFields, obj_info::ObjInfo, obj_versions::ObjVersions,
+    sum_displays::SumDisplays, sum_packages::SumPackages,
+    tx_affected_addresses::TxAffectedAddresses, tx_affected_objects::TxAffectedObjects,
+    tx_balance_changes::TxBalanceChanges, tx_calls::TxCalls, tx_digests::TxDigests,
+    tx_kinds::TxKinds,
 };
 use prometheus::Registry;
 use sui_indexer_alt_framework::handlers::cp_sequence_numbers::CpSequenceNumbers;
@@ -34,8 +35,57 @@ pub mod benchmark;
 pub(crate) mod bootstrap;
 pub mod config;
 pub(crate) mod consistent_pruning;
+pub mod dead_letters;
 pub(crate) mod handlers;
 
+/// The single source of truth for the pipelines this indexer runs. Invokes one of four macros for
+/// each pipeline, depending on its kind, passing the pipeline's handler and the name of its field
+/// in [config::PipelineLayer]:
+///
+///  - `$genesis!(Handler, field)` for concurrent pipelines that need genesis information, where
+///    the handler is constructed as `Handler(genesis)`.
+///  - `$consistent!(handler, field)` for consistent pipelines.
+///  - `$sequential!(handler, field)` for sequential pipelines.
+///  - `$concurrent!(handler, field)` for the remaining concurrent pipelines.
+///
+/// Both pipeline registration and dead letter replay are derived from this list, so that a new
+/// pipeline only needs to be added here.
+macro_rules! for_each_pipeline {
+    ($genesis:ident, $consistent:ident, $sequential:ident, $concurrent:ident $(,)?) => {
+        // Pipelines that rely on genesis information
+        $genesis!(KvFeatureFlags, kv_feature_flags);
+        $genesis!(KvProtocolConfigs, kv_protocol_configs);
+
+        // Consistent pipelines
+        $consistent!(CoinBalanceBuckets::default(), coin_balance_buckets);
+        $consistent!(ObjDynamicFields::default(), obj_dynamic_fields);
+        $consistent!(ObjInfo::default(), obj_info);
+
+        // Summary tables (without write-ahead log)
+        $sequential!(SumDisplays, sum_displays);
+        $sequential!(SumPackages, sum_packages);
+
+        // Unpruned concurrent pipelines
+        $concurrent!(CpSequenceNumbers, cp_sequence_numbers);
+        $concurrent!(EvEmitMod, ev_emit_mod);
+        $concurrent!(EvStructInst, ev_struct_inst);
+        $concurrent!(KvCheckpoints, kv_checkpoints);
+        $concurrent!(KvEpochEnds, kv_epoch_ends);
+        $concurrent!(KvEpochStarts, kv_epoch_starts);
+        $concurrent!(KvObjects, kv_objects);
+        $concurrent!(KvTransactions, kv_transactions);
+        $concurrent!(ObjVersions, obj_versions);
+        $concurrent!(TxAffectedAddresses, tx_affected_addresses);
+        $concurrent!(TxAffectedObjects, tx_affected_objects);
+        $concurrent!(TxBalanceChanges, tx_balance_changes);
+        $concurrent!(TxCalls, tx_calls);
+        $concurrent!(TxDigests, tx_digests);
+        $concurrent!(TxKinds, tx_kinds);
+    };
+}
+
+pub(crate) use for_each_pipeline;
+
 pub async fn start_indexer(
     db_args: DbArgs,
     indexer_args: IndexerArgs,
@@ -58,30 +108,7 @@ pub async fn start_indexer(
         extra: _,
     } = indexer_config.finish();
 
-    let PipelineLayer {
-        sum_displays,
-        sum_packages,
-        coin_balance_buckets,
-        cp_sequence_numbers,
-        ev_emit_mod,
-        ev_struct_inst,
-        kv_checkpoints,
-        kv_epoch_ends,
-        kv_epoch_starts,
-        kv_feature_flags,
-        kv_objects,
-        kv_protocol_configs,
-        kv_transactions,
-        obj_info,
-        obj_versions,
-        tx_affected_addresses,
-        tx_affected_objects,
-        tx_balance_changes,
-        tx_calls,
-        tx_digests,
-        tx_kinds,
-        extra: _,
-    } = pipeline.finish();
+    let pipeline = pipeline.finish();
 
     let ingestion = ingestion.finish(IngestionConfig::default());
     let consistency = consistency.finish(PrunerConfig::default());
@@ -101,6 +128,12 @@ pub async fn start_indexer(
     )
     .await?;
 
+    let genesis = if with_genesis {
+        Some(bootstrap(&indexer, retry_interval, cancel.clone()).await?)
+    } else {
+        None
+    };
+
     // These macros are responsible for registering pipelines with the indexer. It is responsible
     // for:
     //
@@ -110,15 +143,16 @@ pub async fn start_indexer(
     //  - Registering the pipeline with the indexer.
     //
     // There are three kinds of pipeline, each with their own macro: `add_concurrent`,
-    // `add_sequential`, and `add_consistent`. `add_concurrent` and `add_sequential` map directly
+    // `add_sequential`, and `add_consistent` (plus `add_genesis`, for concurrent pipelines that
+    // are only registered when bootstrapping from genesis). `add_concurrent` and `add_sequential` map directly
     // to `Indexer::concurrent_pipeline` and `Indexer::sequential_pipeline` respectively while
     // `add_consistent` is a special case that generates both a sequential "summary" pipeline and a
     // `concurrent` "write-ahead log" pipeline, with their configuration based on the supplied
     // ConsistencyConfig.
 
     macro_rules! add_consistent {
-        ($handler:expr, $config:expr) => {
-            if let Some(layer) = $config {
+        ($handler:expr, $config:ident) => {
+            if let Some(layer) = pipeline.$config {
                 indexer
                     .concurrent_pipeline(
                         $handler,
@@ -133,8 +167,8 @@ pub async fn start_indexer(
     }
 
     macro_rules! add_concurrent {
-        ($handler:expr, $config:expr) => {
-            if let Some(layer) = $config {
+        ($handler:expr, $config:ident) => {
+            if let Some(layer) = pipeline.$config {
                 indexer
                     .concurrent_pipeline(
                         $handler,
@@ -149,8 +183,8 @@ pub async fn start_indexer(
     }
 
     macro_rules! add_sequential {
-        ($handler:expr, $config:expr) => {
-            if let Some(layer) = $config {
+        ($handler:expr, $config:ident) => {
+            if let Some(layer) = pipeline.$config {
                 indexer
                     .sequential_pipeline(
                         $handler,
@@ -164,38 +198,15 @@ pub async fn start_indexer(
         };
     }
 
-    if with_genesis {
-        let genesis = bootstrap(&indexer, retry_interval, cancel.clone()).await?;
-
-        // Pipelines that rely on genesis information
-        add_concurrent!(KvFeatureFlags(genesis.clone()), kv_feature_flags);
-        add_concurrent!(KvProtocolConfigs(genesis.clone()), kv_protocol_configs);
+    macro_rules! add_genesis {
+        ($handler:ident, $config:ident) => {
+            if let Some(genesis) = &genesis {
+                add_concurrent!($handler(genesis.clone()), $config);
+            }
+        };
     }
 
-    // Consistent pipelines
-    add_consistent!(CoinBalanceBuckets::default(), coin_balance_buckets);
-    add_consistent!(ObjInfo::default(), obj_info);
-
-    // Summary tables (without write-ahead log)
-    add_sequential!(SumDisplays, sum_displays);
-    add_sequential!(SumPackages, sum_packages);
-
-    // Unpruned concurrent pipelines
-    add_concurrent!(CpSequenceNumbers, cp_sequence_numbers);
-    add_concurrent!(EvEmitMod, ev_emit_mod);
-    add_concurrent!(EvStructInst, ev_struct_inst);
-    add_concurrent!(KvCheckpoints, kv_checkpoints);
-    add_concurrent!(KvEpochEnds, kv_epoch_ends);
-    add_concurrent!(KvEpochStarts, kv_epoch_starts);
-    add_concurrent!(KvObjects, kv_objects);
-    add_concurrent!(KvTransactions, kv_transactions);
-    add_concurrent!(ObjVersions, obj_versions);
-    add_concurrent!(TxAffectedAddresses, tx_affected_addresses);
-    add_concurrent!(TxAffectedObjects, tx_affected_objects);
-    add_concurrent!(TxBalanceChanges, tx_balance_changes);
-    add_concurrent!(TxCalls, tx_calls);
-    add_concurrent!(TxDigests, tx_digests);
-    add_concurrent!(TxKinds, tx_kinds);
+    for_each_pipeline!(add_genesis, add_consistent, add_sequential, add_concurrent);
 
     indexer.run().await.context("Failed to start indexer")
 }
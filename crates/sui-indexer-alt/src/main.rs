@@ -12,6 +12,7 @@ use sui_indexer_alt::args::Args;
 use sui_indexer_alt::args::Command;
 use sui_indexer_alt::config::IndexerConfig;
 use sui_indexer_alt::config::Merge;
+use sui_indexer_alt::dead_letters::{list_dead_letters, replay_dead_letters};
 use sui_indexer_alt::start_indexer;
 use sui_indexer_alt_framework::Indexer;
 use sui_indexer_alt_metrics::MetricsService;
@@ -104,6 +105,29 @@ async fn main() -> Result<()> {
             .await?;
         }
 
+        Command::ListDeadLetters { pipeline } => {
+            list_dead_letters(args.db_args, pipeline).await?;
+        }
+
+        Command::ReplayDeadLetters {
+            client_args,
+            pipeline,
+            checkpoint,
+        } => {
+            let registry = Registry::new_custom(Some("indexer_alt".into()), None)
+                .context("Failed to create Prometheus registry.")?;
+
+            replay_dead_letters(
+                args.db_args,
+                client_args,
+                pipeline,
+                checkpoint,
+                &registry,
+                CancellationToken::new(),
+            )
+            .await?;
+        }
+
         #[cfg(feature = "benchmark")]
         Command::Benchmark {
             benchmark_args,
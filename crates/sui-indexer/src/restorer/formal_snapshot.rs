@@ -15,8 +15,7 @@ use tracing::info;
 
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::authority_store_tables::LiveObject;
-use sui_snapshot::reader::{download_bytes, LiveObjectIter, StateSnapshotReaderV1};
-use sui_snapshot::FileMetadata;
+use sui_snapshot::reader::{download_bytes, LiveObjectIter, SnapshotLayer, StateSnapshotReaderV1};
 use sui_storage::object_store::util::get;
 use sui_storage::object_store::ObjectStoreGetExt;
 use sui_types::accumulator::Accumulator;
@@ -85,23 +84,12 @@ impl IndexerFormalSnapshotRestorer {
     }
 
     pub async fn restore(&mut self) -> Result<(), IndexerError> {
-        let (sha3_digests, num_part_files) = self.reader.compute_checksum().await?;
-        let (_abort_handle, abort_registration) = AbortHandle::new_pair();
-        let (input_files, epoch_dir, remote_object_store, _concurrency) =
-            self.reader.export_metadata().await?;
-        let owned_input_files: Vec<(u32, (u32, FileMetadata))> = input_files
-            .into_iter()
-            .map(|(bucket, (part_num, metadata))| (*bucket, (part_num, metadata.clone())))
-            .collect();
-        self.restore_move_objects(
-            abort_registration,
-            owned_input_files,
-            epoch_dir,
-            remote_object_store,
-            sha3_digests,
-            num_part_files,
-        )
-        .await?;
+        let (layers, remote_object_store, _concurrency) = self.reader.export_metadata().await?;
+        for layer in layers {
+            let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+            self.restore_move_objects(abort_registration, layer, remote_object_store.clone())
+                .await?;
+        }
         info!("Finished restoring move objects");
         self.restore_display_table().await?;
         info!("Finished restoring display table");
@@ -113,14 +101,13 @@ impl IndexerFormalSnapshotRestorer {
     async fn restore_move_objects(
         &self,
         abort_registration: AbortRegistration,
-        input_files: Vec<(u32, (u32, FileMetadata))>,
-        epoch_dir: Path,
+        mut layer: SnapshotLayer,
         remote_object_store: Arc<dyn ObjectStoreGetExt>,
-        sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
-        num_part_files: usize,
     ) -> std::result::Result<(), anyhow::Error> {
+        let input_files = std::mem::take(&mut layer.input_files);
+        let layer = Arc::new(layer);
         let move_object_progress_bar = Arc::new(self.reader.get_multi_progress().add(
-            ProgressBar::new(num_part_files as u64).with_style(
+            ProgressBar::new(input_files.len() as u64).with_style(
                 ProgressStyle::with_template(
                     "[{elapsed_precise}] {wide_bar} {pos} out of {len} move object files restored ({msg})",
                 )
@@ -137,9 +124,10 @@ impl IndexerFormalSnapshotRestorer {
 
                 for (bucket, (part_num, file_metadata)) in input_files.into_iter() {
                     let sema_limit_clone = sema_limit.clone();
-                    let epoch_dir_clone = epoch_dir.clone();
+                    let layer_clone = layer.clone();
+                    let epoch_dir_clone = layer.epoch_dir.clone();
                     let remote_object_store_clone = remote_object_store.clone();
-                    let sha3_digests_clone = sha3_digests.clone();
+                    let sha3_digests_clone = layer.sha3_digests.clone();
                     let store_clone = self.store.clone();
                     let bar_clone = move_object_progress_bar.clone();
                     let restore_config = self.restore_config.clone();
@@ -166,14 +154,17 @@ impl IndexerFormalSnapshotRestorer {
                             LiveObjectIter::new(&file_metadata, bytes.clone()).map(|obj_iter| {
                                 for object in obj_iter {
                                     match object {
-                                        LiveObject::Normal(obj) => {
+                                        LiveObject::Normal(obj)
+                                            if layer_clone
+                                                .is_live(&obj.compute_object_reference()) =>
+                                        {
                                             // TODO: placeholder values for df_info and checkpoint_seq_num,
                                             // will clean it up when the column cleanup is done.
                                             let indexed_object =
                                                 IndexedObject::from_object(0, obj, None);
                                             move_objects.push(indexed_object);
                                         }
-                                        LiveObject::Wrapped(_) => {}
+                                        LiveObject::Normal(_) | LiveObject::Wrapped(_) => {}
                                     }
                                 }
                             });
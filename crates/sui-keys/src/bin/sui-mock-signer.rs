@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A mock external signer, for testing `ExternalKeystore` locally. It signs with the keys in a
+//! file-based keystore, whose path is passed as its only argument, e.g. configuring the keystore
+//! in `client.yaml` as:
+//!
+//! ```yaml
+//! keystore:
+//!   External:
+//!     command: sui-mock-signer
+//!     args: ["/path/to/sui.keystore"]
+//! ```
+//!
+//! It is only built with the `test-utils` feature, and should never be used with real keys.
+
+use std::io;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use sui_keys::external_signer::{SignerKey, SignerRequest, SignerResponse};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+
+fn main() -> Result<(), anyhow::Error> {
+    let path = std::env::args()
+        .nth(1)
+        .context("Usage: sui-mock-signer <KEYSTORE_PATH>")?;
+
+    let keystore = FileBasedKeystore::new(&PathBuf::from(path))?;
+    let request: SignerRequest =
+        serde_json::from_reader(io::stdin().lock()).context("Invalid request")?;
+
+    let response = match request {
+        SignerRequest::Keys => SignerResponse::Keys {
+            keys: keystore
+                .aliases()
+                .into_iter()
+                .map(|alias| SignerKey {
+                    public_key: alias.public_key_base64.clone(),
+                    alias: Some(alias.alias.clone()),
+                })
+                .collect(),
+        },
+
+        SignerRequest::Sign { address, msg } => {
+            let signature = Base64::decode(&msg)
+                .map_err(|e| anyhow!("Invalid message: {e}"))
+                .and_then(|msg| Ok(keystore.sign_hashed(&address, &msg)?));
+
+            match signature {
+                Ok(signature) => SignerResponse::Signature {
+                    signature: Base64::encode(signature.as_ref()),
+                },
+                Err(e) => SignerResponse::Error {
+                    error: e.to_string(),
+                },
+            }
+        }
+    };
+
+    serde_json::to_writer(io::stdout().lock(), &response)?;
+    Ok(())
+}
@@ -0,0 +1,344 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zeroize::Zeroizing;
+
+/// The version of the encrypted keystore format written by this module.
+pub const ENCRYPTED_KEYSTORE_VERSION: u32 = 1;
+
+/// The environment variable that a keystore's passphrase can be supplied through, for
+/// non-interactive use.
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "SUI_KEYSTORE_PASSPHRASE";
+
+/// The directory, next to the keystore, that unlock caches are kept in.
+pub const UNLOCK_CACHE_DIR: &str = ".unlock";
+
+const UNLOCK_CACHE_EXTENSION: &str = "unlock";
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const KEY_LENGTH: usize = 32;
+
+/// Parameters for deriving the encryption key from the passphrase, using scrypt.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScryptParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+/// The header of an encrypted keystore file, which is authenticated (but not encrypted) along with
+/// its contents.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedKeystoreHeader {
+    /// Version of the format, to allow it to evolve.
+    pub version: u32,
+    /// How the encryption key was derived from the passphrase.
+    pub kdf: ScryptParams,
+    /// Base64 encoded salt for the key derivation.
+    pub salt: String,
+}
+
+/// The contents of an encrypted keystore file: The legacy keystore format (a JSON array of Base64
+/// encoded private keys), encrypted with AES-256-GCM, using a key derived from a passphrase.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedKeystore {
+    #[serde(flatten)]
+    pub header: EncryptedKeystoreHeader,
+    /// Base64 encoded nonce used to encrypt the keys.
+    pub nonce: String,
+    /// Base64 encoded encryption of the keys, including the authentication tag.
+    pub ciphertext: String,
+}
+
+/// A key derived from a keystore's passphrase, that can be used to decrypt its current contents,
+/// and to encrypt new contents.
+#[derive(Clone)]
+pub struct KeystoreKey {
+    header: EncryptedKeystoreHeader,
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+/// A key that has been cached to unlock a keystore without its passphrase, until it expires.
+#[derive(Serialize, Deserialize)]
+struct UnlockCache {
+    expires_at_ms: u64,
+    header: EncryptedKeystoreHeader,
+    key: String,
+}
+
+impl Default for ScryptParams {
+    /// Parameters that take a fraction of a second to derive a key with, and use 64MiB of memory.
+    fn default() -> Self {
+        Self {
+            log_n: 16,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KeystoreKey {
+    /// Derive a key for a new encrypted keystore from `passphrase`, with a fresh salt.
+    pub fn generate(passphrase: &str, kdf: ScryptParams) -> Result<Self, anyhow::Error> {
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(
+            passphrase,
+            EncryptedKeystoreHeader {
+                version: ENCRYPTED_KEYSTORE_VERSION,
+                kdf,
+                salt: Base64::encode(salt),
+            },
+        )
+    }
+
+    /// Derive the key for the keystore described by `header` from `passphrase`. This succeeds even
+    /// if the passphrase is wrong -- that is only detected when decrypting.
+    pub fn derive(
+        passphrase: &str,
+        header: EncryptedKeystoreHeader,
+    ) -> Result<Self, anyhow::Error> {
+        ensure!(
+            header.version == ENCRYPTED_KEYSTORE_VERSION,
+            "Unsupported encrypted keystore version: {}",
+            header.version
+        );
+
+        let salt = Base64::decode(&header.salt).map_err(|e| anyhow!("Invalid salt: {e}"))?;
+        let ScryptParams { log_n, r, p } = header.kdf;
+        let params = scrypt::Params::new(log_n, r, p)
+            .map_err(|e| anyhow!("Invalid key derivation parameters: {e}"))?;
+
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut())
+            .map_err(|e| anyhow!("Failed to derive keystore key: {e}"))?;
+
+        Ok(Self { header, key })
+    }
+
+    pub fn header(&self) -> &EncryptedKeystoreHeader {
+        &self.header
+    }
+
+    /// Encrypt `plaintext` with this key, and a fresh nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptedKeystore, anyhow::Error> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let aad = serde_json::to_vec(&self.header)?;
+        let ciphertext = self
+            .cipher()?
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("Failed to encrypt keystore"))?;
+
+        Ok(EncryptedKeystore {
+            header: self.header.clone(),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the contents of `keystore`. Fails if this key was not derived for this keystore, or
+    /// from the right passphrase, or if the keystore has been tampered with.
+    pub fn decrypt(
+        &self,
+        keystore: &EncryptedKeystore,
+    ) -> Result<Zeroizing<Vec<u8>>, anyhow::Error> {
+        ensure!(
+            keystore.header == self.header,
+            "Keystore was not encrypted with this key"
+        );
+
+        let nonce = Base64::decode(&keystore.nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid nonce length");
+
+        let ciphertext =
+            Base64::decode(&keystore.ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+
+        let aad = serde_json::to_vec(&self.header)?;
+        let plaintext = self
+            .cipher()?
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("Incorrect passphrase, or the keystore has been tampered with"))?;
+
+        Ok(Zeroizing::new(plaintext))
+    }
+
+    fn cipher(&self) -> Result<Aes256Gcm, anyhow::Error> {
+        Aes256Gcm::new_from_slice(self.key.as_ref()).map_err(|_| anyhow!("Invalid keystore key"))
+    }
+}
+
+/// Where the unlock cache for the keystore at `keystore_path` lives: in a directory next to the
+/// keystore (normally the sui config directory, which belongs to the user), under a name derived
+/// from the keystore's path.
+fn unlock_cache_path(keystore_path: &Path) -> PathBuf {
+    let path = fs::canonicalize(keystore_path).unwrap_or_else(|_| keystore_path.to_path_buf());
+    let mut hasher = Blake2b256::default();
+    hasher.update(path.to_string_lossy().as_bytes());
+    let digest = Hex::encode(hasher.finalize().digest);
+    path.parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(UNLOCK_CACHE_DIR)
+        .join(format!("{}.{UNLOCK_CACHE_EXTENSION}", &digest[..16]))
+}
+
+/// Cache `key` so that the keystore at `keystore_path` can be unlocked without its passphrase for
+/// the next `timeout`. The cache's directory is only accessible by the current user, and the cache
+/// is only readable by them. Any other expired caches in the same directory are removed.
+pub fn write_unlock_cache(
+    keystore_path: &Path,
+    key: &KeystoreKey,
+    timeout: Duration,
+) -> Result<(), anyhow::Error> {
+    let expires_at = SystemTime::now() + timeout;
+    let cache = UnlockCache {
+        expires_at_ms: expires_at.duration_since(UNIX_EPOCH)?.as_millis() as u64,
+        header: key.header.clone(),
+        key: Base64::encode(key.key.as_ref()),
+    };
+
+    let path = unlock_cache_path(keystore_path);
+    let dir = path
+        .parent()
+        .expect("Unlock cache is always in a directory");
+    create_private_dir(dir)
+        .with_context(|| format!("Cannot create unlock cache directory: {}", dir.display()))?;
+    remove_expired_unlock_caches(dir);
+    let _ = fs::remove_file(&path);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(&path)
+        .with_context(|| format!("Cannot create unlock cache: {}", path.display()))?;
+    file.write_all(&Zeroizing::new(serde_json::to_vec(&cache)?))?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// The cached key for the keystore at `keystore_path`, if there is one and it has not expired.
+/// Caches that are not private to the keystore's owner are ignored, and expired caches are
+/// removed.
+pub fn read_unlock_cache(keystore_path: &Path) -> Option<KeystoreKey> {
+    let path = unlock_cache_path(keystore_path);
+    if !is_private_to_owner_of(&path, keystore_path) {
+        return None;
+    }
+
+    let contents = Zeroizing::new(fs::read(&path).ok()?);
+    let Some(cache) = parse_unlock_cache(&contents) else {
+        let _ = fs::remove_file(&path);
+        return None;
+    };
+
+    let bytes = Zeroizing::new(Base64::decode(&cache.key).ok()?);
+    let key: [u8; KEY_LENGTH] = bytes.as_slice().try_into().ok()?;
+    Some(KeystoreKey {
+        header: cache.header,
+        key: Zeroizing::new(key),
+    })
+}
+
+/// Forget any cached key for the keystore at `keystore_path`.
+pub fn clear_unlock_cache(keystore_path: &Path) -> Result<(), anyhow::Error> {
+    let path = unlock_cache_path(keystore_path);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Cannot remove unlock cache: {}", path.display())),
+    }
+}
+
+/// Deserialize an unlock cache, returning `None` if it is malformed or has expired.
+fn parse_unlock_cache(contents: &[u8]) -> Option<UnlockCache> {
+    let cache: UnlockCache = serde_json::from_slice(contents).ok()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as u64;
+    (now < cache.expires_at_ms).then_some(cache)
+}
+
+/// Best-effort removal of the unlock caches in `dir` that have expired, or cannot be read.
+fn remove_expired_unlock_caches(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension() != Some(OsStr::new(UNLOCK_CACHE_EXTENSION)) {
+            continue;
+        }
+
+        let expired = fs::read(&path)
+            .map(|contents| parse_unlock_cache(&Zeroizing::new(contents)).is_none())
+            .unwrap_or(true);
+        if expired {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+/// Create `dir` (and its parents) if it does not exist, and make sure that only its owner can
+/// access it.
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+/// Whether the file at `path` exists, is owned by the same user as the file at `owner`, and is
+/// not accessible to anyone else.
+#[cfg(unix)]
+fn is_private_to_owner_of(path: &Path, owner: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let (Ok(meta), Ok(owner)) = (fs::symlink_metadata(path), fs::metadata(owner)) else {
+        return false;
+    };
+
+    meta.is_file() && meta.uid() == owner.uid() && meta.mode() & 0o077 == 0
+}
+
+/// Whether the file at `path` exists. Ownership is not checked on platforms without unix
+/// permissions, where the cache is protected by living in the user's config directory.
+#[cfg(not(unix))]
+fn is_private_to_owner_of(path: &Path, _owner: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|meta| meta.is_file())
+}
@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::keystore::{validate_alias, AccountKeystore, Alias};
+use crate::random_names::random_name;
+use anyhow::{anyhow, bail, ensure, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{
+    DefaultHash, EncodeDecodeBase64, PublicKey, Signature, SuiKeyPair, SuiSignature,
+};
+
+/// How to invoke an external signer: the program to run, and the arguments to run it with.
+///
+/// Much like a git credential helper, the signer is run once per request. The request is written
+/// to its stdin as a single JSON object (a [SignerRequest]), after which stdin is closed, and the
+/// signer is expected to write a single JSON object in response (a [SignerResponse]) to its stdout
+/// and exit successfully. Anything the signer writes to stderr is shown to the user, so it can be
+/// used to prompt for confirmation, e.g. on a hardware device.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSigner {
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+/// A request sent to an external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the keys that the signer can sign with.
+    Keys,
+    /// Sign `msg` (Base64 encoded) with the key for `address`. The message is signed as-is: it is
+    /// already the digest of the intent message being signed.
+    Sign { address: SuiAddress, msg: String },
+}
+
+/// A response from an external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SignerResponse {
+    /// The keys that the signer holds, in response to [SignerRequest::Keys].
+    Keys { keys: Vec<SignerKey> },
+    /// Base64 encoding of `flag || signature || pubkey`, in response to [SignerRequest::Sign].
+    Signature { signature: String },
+    /// The signer failed to handle the request.
+    Error { error: String },
+}
+
+/// A key held by an external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignerKey {
+    /// Base64 encoding of `flag || pubkey`.
+    pub public_key: String,
+    /// The alias to use for this key. Keys without an alias are given a random one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+/// A keystore whose private keys are held by an external signer process, so they never need to be
+/// written to disk. The keystore only knows the public keys the signer reported when the keystore
+/// was created, and delegates signing to the signer (see [ExternalSigner] for the protocol).
+///
+/// Keys cannot be added to or exported from this keystore, and aliases only last for as long as
+/// the keystore is in memory.
+pub struct ExternalKeystore {
+    signer: ExternalSigner,
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl ExternalSigner {
+    pub fn new(command: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            command: command.into(),
+            args,
+        }
+    }
+
+    /// Run the signer, send it `request` and wait for its response. Fails if the signer could not
+    /// be run, it did not exit successfully, or it responded with an error.
+    pub fn request(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Cannot run external signer: {}", self.command))?;
+
+        {
+            // Dropping stdin closes it, to signal the end of the request.
+            let mut stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| anyhow!("Cannot write to external signer: {}", self.command))?;
+            serde_json::to_writer(&mut stdin, request)?;
+            stdin.write_all(b"\n")?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("Cannot read from external signer: {}", self.command))?;
+
+        ensure!(
+            output.status.success(),
+            "External signer {} failed: {}",
+            self.command,
+            output.status
+        );
+
+        let response: SignerResponse = serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("Invalid response from external signer: {}", self.command))?;
+
+        if let SignerResponse::Error { error } = response {
+            bail!("External signer {} failed: {error}", self.command);
+        }
+
+        Ok(response)
+    }
+}
+
+impl ExternalKeystore {
+    /// Create a keystore for the keys held by `signer`, by asking it which keys it holds.
+    pub fn new(signer: ExternalSigner) -> Result<Self, anyhow::Error> {
+        let SignerResponse::Keys { keys: signer_keys } = signer.request(&SignerRequest::Keys)?
+        else {
+            bail!(
+                "Unexpected response from external signer {} when listing keys",
+                signer.command
+            );
+        };
+
+        let mut keys = BTreeMap::new();
+        let mut aliases = BTreeMap::new();
+        let mut names = HashSet::new();
+        for SignerKey { public_key, alias } in signer_keys {
+            let public_key = PublicKey::decode_base64(&public_key).map_err(|e| {
+                anyhow!(
+                    "Invalid public key from external signer {}: {e}",
+                    signer.command
+                )
+            })?;
+
+            let alias = match alias {
+                Some(alias) => validate_alias(&alias)?,
+                None => random_name(&names),
+            };
+
+            ensure!(
+                names.insert(alias.clone()),
+                "Alias {alias} is used by more than one key in external signer {}",
+                signer.command
+            );
+
+            let address = SuiAddress::from(&public_key);
+            aliases.insert(
+                address,
+                Alias {
+                    alias,
+                    public_key_base64: public_key.encode_base64(),
+                },
+            );
+            keys.insert(address, public_key);
+        }
+
+        Ok(Self {
+            signer,
+            keys,
+            aliases,
+        })
+    }
+
+    pub fn signer(&self) -> &ExternalSigner {
+        &self.signer
+    }
+
+    /// Ask the signer to sign `msg` with the key for `address`, and check that it used that key.
+    fn sign_with_signer(
+        &self,
+        address: &SuiAddress,
+        msg: &[u8],
+    ) -> Result<Signature, anyhow::Error> {
+        let public_key = self
+            .keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))?;
+
+        let SignerResponse::Signature { signature } =
+            self.signer.request(&SignerRequest::Sign {
+                address: *address,
+                msg: Base64::encode(msg),
+            })?
+        else {
+            bail!(
+                "Unexpected response from external signer {} when signing",
+                self.signer.command
+            );
+        };
+
+        let signature = Base64::decode(&signature)
+            .map_err(|e| anyhow!("Invalid signature from external signer: {e}"))
+            .and_then(|bytes| {
+                Signature::from_bytes(&bytes)
+                    .map_err(|e| anyhow!("Invalid signature from external signer: {e}"))
+            })?;
+
+        ensure!(
+            signature.scheme() == public_key.scheme()
+                && signature.public_key_bytes() == public_key.as_ref(),
+            "External signer {} signed with the wrong key for address: [{address}]",
+            self.signer.command
+        );
+
+        Ok(signature)
+    }
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.signer.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        ExternalKeystore::new(ExternalSigner::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.sign_with_signer(address, msg)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        // Hash the intent message in the same way as `Signature::new_secure`, so that the signer
+        // only ever needs to sign digests.
+        let mut hasher = DefaultHash::default();
+        hasher.update(
+            bcs::to_bytes(&IntentMessage::new(intent, msg))
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        );
+
+        self.sign_hashed(address, &hasher.finalize().digest)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!(
+            "Cannot add keys to an external signer's keystore, add them to the signer ({}) instead",
+            self.signer.command
+        )
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        ensure!(
+            self.keys.contains_key(address),
+            "Cannot find key for address: [{address}]"
+        );
+
+        bail!("Key for address [{address}] is held by an external signer and cannot be accessed")
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        self.aliases.iter().collect::<Vec<_>>()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.addresses_with_alias()
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    /// Updates an old alias to the new alias, in memory only. If the new_alias is None, it will
+    /// generate a new random alias.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        self.update_alias_value(old_alias, new_alias)
+    }
+}
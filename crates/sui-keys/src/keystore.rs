@@ -1,6 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::encryption::{
+    clear_unlock_cache, read_unlock_cache, write_unlock_cache, EncryptedKeystore, KeystoreKey,
+    ScryptParams, KEYSTORE_PASSPHRASE_ENV,
+};
+use crate::external_signer::ExternalKeystore;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
@@ -17,17 +22,20 @@ use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
 };
+use zeroize::Zeroizing;
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -141,6 +149,11 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Keystore Signer : {:?}", external.signer())?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -151,11 +164,33 @@ pub struct Alias {
     pub public_key_base64: String,
 }
 
+/// A keystore backed by a file. The file either holds a JSON array of Base64 encoded private keys
+/// (the legacy format), or that same array encrypted with a key derived from a passphrase (see
+/// [EncryptedKeystore]).
+///
+/// An encrypted keystore is unlocked on load if its passphrase is available through the
+/// `SUI_KEYSTORE_PASSPHRASE` environment variable, or if it was recently unlocked with
+/// `sui keytool unlock`. Otherwise (including if the passphrase is wrong, or the unlock is stale)
+/// it is loaded locked: its addresses and aliases are available (from the aliases file), but it
+/// cannot sign, or be modified, until it is unlocked.
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     aliases: BTreeMap<SuiAddress, Alias>,
     path: Option<PathBuf>,
+    /// The contents of the keystore file, as of when it was last read or written, if it is
+    /// encrypted.
+    encrypted: Option<EncryptedKeystore>,
+    /// The key to encrypt the keystore with, if it is encrypted and unlocked.
+    encryption: Option<KeystoreKey>,
+}
+
+/// The formats that a keystore file can be in.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeystoreFile {
+    Legacy(Vec<String>),
+    Encrypted(EncryptedKeystore),
 }
 
 impl Serialize for FileBasedKeystore {
@@ -188,9 +223,8 @@ impl AccountKeystore for FileBasedKeystore {
     fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
         Ok(Signature::new_hashed(
             msg,
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.key_pair(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
         ))
     }
     fn sign_secure<T>(
@@ -204,13 +238,13 @@ impl AccountKeystore for FileBasedKeystore {
     {
         Ok(Signature::new_secure(
             &IntentMessage::new(intent, msg),
-            self.keys.get(address).ok_or_else(|| {
-                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
-            })?,
+            self.key_pair(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
         ))
     }
 
     fn add_key(&mut self, alias: Option<String>, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        self.ensure_unlocked()?;
         let address: SuiAddress = (&keypair.public()).into();
         let alias = self.create_alias(alias)?;
         self.aliases.insert(
@@ -239,8 +273,17 @@ impl AccountKeystore for FileBasedKeystore {
         self.aliases.values_mut().collect()
     }
 
+    /// The public keys in the keystore. If the keystore is locked, these are read from its aliases
+    /// file instead.
     fn keys(&self) -> Vec<PublicKey> {
-        self.keys.values().map(|key| key.public()).collect()
+        if self.is_locked() {
+            self.aliases
+                .values()
+                .filter_map(|alias| PublicKey::decode_base64(&alias.public_key_base64).ok())
+                .collect()
+        } else {
+            self.keys.values().map(|key| key.public()).collect()
+        }
     }
 
     /// This function returns an error if the provided alias already exists. If the alias
@@ -280,10 +323,7 @@ impl AccountKeystore for FileBasedKeystore {
     }
 
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
-        match self.keys.get(address) {
-            Some(key) => Ok(key),
-            None => Err(anyhow!("Cannot find key for address: [{address}]")),
-        }
+        self.key_pair(address)
     }
 
     /// Updates an old alias to the new alias and saves it to the alias file.
@@ -301,24 +341,38 @@ impl AccountKeystore for FileBasedKeystore {
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
+        let (keys, encrypted, encryption) = if path.exists() {
             let reader =
                 BufReader::new(File::open(path).with_context(|| {
                     format!("Cannot open the keystore file: {}", path.display())
                 })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
+            let file: KeystoreFile = serde_json::from_reader(reader).with_context(|| {
                 format!("Cannot deserialize the keystore file: {}", path.display(),)
             })?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (SuiAddress::from(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
+
+            match file {
+                KeystoreFile::Legacy(kp_strings) => (decode_keys(&kp_strings, path)?, None, None),
+                KeystoreFile::Encrypted(encrypted) => {
+                    // A wrong passphrase, or a stale unlock cache, leaves the keystore locked
+                    // rather than failing to load it.
+                    let key = if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+                        KeystoreKey::derive(&passphrase, encrypted.header.clone()).ok()
+                    } else {
+                        read_unlock_cache(path).filter(|key| key.header() == &encrypted.header)
+                    };
+
+                    let keys = key
+                        .as_ref()
+                        .and_then(|key| decrypt_keys(key, &encrypted, path).ok());
+
+                    match keys {
+                        Some(keys) => (keys, Some(encrypted), key),
+                        None => (BTreeMap::new(), Some(encrypted), None),
+                    }
+                }
+            }
         } else {
-            BTreeMap::new()
+            (BTreeMap::new(), None, None)
         };
 
         // check aliases
@@ -387,6 +441,8 @@ impl FileBasedKeystore {
             keys,
             aliases,
             path: Some(path.to_path_buf()),
+            encrypted,
+            encryption,
         })
     }
 
@@ -415,18 +471,106 @@ impl FileBasedKeystore {
     /// Keys saved as Base64 with 33 bytes `flag || privkey` ($BASE64_STR).
     /// To see Bech32 format encoding, use `sui keytool export $SUI_ADDRESS` where
     /// $SUI_ADDRESS can be found with `sui keytool list`. Or use `sui keytool convert $BASE64_STR`
+    ///
+    /// If the keystore is encrypted, this list is encrypted before it is saved.
     pub fn save_keystore(&self) -> Result<(), anyhow::Error> {
+        self.write_keystore()?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        self.save_aliases()?;
+        self.save_keystore()?;
+        Ok(())
+    }
+
+    /// Whether the keystore file is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted.is_some()
+    }
+
+    /// Whether the keystore is encrypted and has not been unlocked, so its private keys are not
+    /// available.
+    pub fn is_locked(&self) -> bool {
+        self.encrypted.is_some() && self.encryption.is_none()
+    }
+
+    /// Encrypt a legacy keystore with a key derived from `passphrase`, and save it in the
+    /// encrypted format.
+    pub fn encrypt(&mut self, passphrase: &str, kdf: ScryptParams) -> Result<(), anyhow::Error> {
+        ensure!(!self.is_encrypted(), "Keystore is already encrypted");
+        self.encryption = Some(KeystoreKey::generate(passphrase, kdf)?);
+        self.encrypted = self.write_keystore()?;
+        Ok(())
+    }
+
+    /// Unlock an encrypted keystore with its `passphrase`. If the keystore is already unlocked,
+    /// this only checks the passphrase.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), anyhow::Error> {
+        let Some(encrypted) = &self.encrypted else {
+            bail!("Keystore is not encrypted");
+        };
+
+        let key = KeystoreKey::derive(passphrase, encrypted.header.clone())?;
+        let keys = decrypt_keys(
+            &key,
+            encrypted,
+            self.path.as_deref().unwrap_or(Path::new("")),
+        )?;
+
+        if self.encryption.is_none() {
+            self.keys = keys;
+            self.encryption = Some(key);
+        }
+
+        Ok(())
+    }
+
+    /// Re-encrypt the keystore with a key derived from `new_passphrase`, after checking
+    /// `old_passphrase`. Any cached unlock for the old passphrase is forgotten.
+    pub fn rotate_passphrase(
+        &mut self,
+        old_passphrase: &str,
+        new_passphrase: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.unlock(old_passphrase)?;
+
+        let kdf = self
+            .encryption
+            .as_ref()
+            .map_or_else(ScryptParams::default, |key| key.header().kdf);
+
+        self.encryption = Some(KeystoreKey::generate(new_passphrase, kdf)?);
+        self.encrypted = self.write_keystore()?;
+
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
-                    .values()
-                    .map(|k| k.encode_base64())
-                    .collect::<Vec<_>>(),
-            )
-            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            fs::write(path, store)?;
+            clear_unlock_cache(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Remember the key for this (unlocked) keystore for `timeout`, so that it is unlocked when it
+    /// is next loaded within that time, without its passphrase.
+    pub fn cache_unlock(&self, timeout: Duration) -> Result<(), anyhow::Error> {
+        let (Some(path), Some(key)) = (&self.path, &self.encryption) else {
+            bail!("Only an unlocked, encrypted keystore can stay unlocked");
+        };
+
+        write_unlock_cache(path, key, timeout)
+    }
+
+    /// Forget the keystore's private keys, and any cached unlock for it, so that its passphrase is
+    /// needed to use it again.
+    pub fn lock(&mut self) -> Result<(), anyhow::Error> {
+        ensure!(self.is_encrypted(), "Keystore is not encrypted");
+
+        if let Some(path) = &self.path {
+            clear_unlock_cache(path)?;
         }
+
+        self.keys.clear();
+        self.encryption = None;
         Ok(())
     }
 
@@ -439,6 +583,115 @@ impl FileBasedKeystore {
     pub fn key_pairs(&self) -> Vec<&SuiKeyPair> {
         self.keys.values().collect()
     }
+
+    fn ensure_unlocked(&self) -> Result<(), anyhow::Error> {
+        ensure!(
+            !self.is_locked(),
+            "Keystore is locked, unlock it with `sui keytool unlock`"
+        );
+        Ok(())
+    }
+
+    fn key_pair(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        self.ensure_unlocked()?;
+        self.keys
+            .get(address)
+            .ok_or_else(|| anyhow!("Cannot find key for address: [{address}]"))
+    }
+
+    /// Write the keys to the keystore file, encrypting them if the keystore is encrypted, and
+    /// return the encrypted contents that were written, if any.
+    fn write_keystore(&self) -> Result<Option<EncryptedKeystore>, anyhow::Error> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+
+        self.ensure_unlocked()?;
+        let store = Zeroizing::new(
+            serde_json::to_string_pretty(
+                &self
+                    .keys
+                    .values()
+                    .map(|k| k.encode_base64())
+                    .collect::<Vec<_>>(),
+            )
+            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?,
+        );
+
+        let Some(key) = &self.encryption else {
+            write_atomically(path, store.as_bytes())?;
+            return Ok(None);
+        };
+
+        let encrypted = key.encrypt(store.as_bytes())?;
+        let contents = serde_json::to_string_pretty(&encrypted)
+            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
+        write_atomically(path, contents.as_bytes())?;
+        Ok(Some(encrypted))
+    }
+}
+
+/// Replace the contents of the file at `path` with `contents`, so that it is never left partially
+/// written: The contents are written to a temporary file in the same directory, flushed to disk,
+/// and then renamed over `path`.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    use std::io::Write as _;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let write = || -> std::io::Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    };
+
+    write().map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        anyhow!(e).context(format!(
+            "Cannot write the keystore file: {}",
+            path.display()
+        ))
+    })
+}
+
+fn decode_keys(
+    kp_strings: &[String],
+    path: &Path,
+) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+    kp_strings
+        .iter()
+        .map(|kpstr| {
+            let key = SuiKeyPair::decode_base64(kpstr);
+            key.map(|k| (SuiAddress::from(&k.public()), k))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))
+}
+
+fn decrypt_keys(
+    key: &KeystoreKey,
+    encrypted: &EncryptedKeystore,
+    path: &Path,
+) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+    let plaintext = key
+        .decrypt(encrypted)
+        .with_context(|| format!("Cannot decrypt the keystore file: {}", path.display()))?;
+    let kp_strings: Zeroizing<Vec<String>> =
+        Zeroizing::new(serde_json::from_slice(&plaintext).with_context(|| {
+            format!("Cannot deserialize the keystore file: {}", path.display())
+        })?);
+    decode_keys(&kp_strings, path)
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -593,7 +846,7 @@ impl InMemKeystore {
     }
 }
 
-fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
+pub(crate) fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
     let alias = alias.trim();
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod encryption;
+pub mod external_signer;
 pub mod key_derive;
 pub mod keypair_file;
 pub mod keystore;
@@ -2,13 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::fs;
+use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
+use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::EncodeDecodeBase64;
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
 
+use shared_crypto::intent::Intent;
+use sui_keys::encryption::{EncryptedKeystore, KeystoreKey, ScryptParams, UNLOCK_CACHE_DIR};
+use sui_keys::external_signer::{ExternalKeystore, ExternalSigner};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
 use sui_types::{
@@ -279,3 +285,283 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+fn mock_signer(keystore_path: &Path) -> ExternalSigner {
+    ExternalSigner::new(
+        env!("CARGO_BIN_EXE_sui-mock-signer"),
+        vec![keystore_path.display().to_string()],
+    )
+}
+
+#[test]
+fn external_keystore_sign_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("my_signer_key".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let external = Keystore::from(ExternalKeystore::new(mock_signer(&keystore_path)).unwrap());
+    assert_eq!(vec![address], external.addresses());
+    assert_eq!(vec!["my_signer_key"], external.alias_names());
+
+    // Ed25519 signatures are deterministic, so signing with the external signer should produce
+    // the same signature as signing with the keystore directly.
+    let msg = "hello".to_string();
+    let intent = Intent::sui_transaction();
+    assert_eq!(
+        keystore
+            .sign_secure(&address, &msg, intent.clone())
+            .unwrap(),
+        external
+            .sign_secure(&address, &msg, intent.clone())
+            .unwrap(),
+    );
+
+    let unknown = SuiAddress::random_for_testing_only();
+    assert!(external.sign_secure(&unknown, &msg, intent).is_err());
+}
+
+#[test]
+fn external_keystore_keys_stay_with_signer_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    let mut external = Keystore::from(ExternalKeystore::new(mock_signer(&keystore_path)).unwrap());
+    assert!(external.get_key(&address).is_err());
+    assert!(external
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+    assert_eq!(vec![address], external.addresses());
+}
+
+#[test]
+fn external_keystore_serde_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    // Only the signer's configuration is serialized, and the keys are fetched from the signer
+    // again on deserialization.
+    let external = Keystore::from(ExternalKeystore::new(mock_signer(&keystore_path)).unwrap());
+    let serialized = serde_json::to_string(&external).unwrap();
+    let deserialized: Keystore = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(external.addresses(), deserialized.addresses());
+    assert_eq!(external.alias_names(), deserialized.alias_names());
+}
+
+/// Cheap key derivation parameters, to keep tests fast.
+const TEST_KDF: ScryptParams = ScryptParams {
+    log_n: 4,
+    r: 8,
+    p: 1,
+};
+
+#[test]
+fn keystore_key_encrypt_decrypt_test() {
+    let key = KeystoreKey::generate("correct horse", TEST_KDF).unwrap();
+    let encrypted = key.encrypt(b"[\"secret\"]").unwrap();
+    assert_eq!(
+        b"[\"secret\"]".as_slice(),
+        key.decrypt(&encrypted).unwrap().as_slice()
+    );
+
+    // The same passphrase derives the same key.
+    let derived = KeystoreKey::derive("correct horse", encrypted.header.clone()).unwrap();
+    assert_eq!(
+        b"[\"secret\"]".as_slice(),
+        derived.decrypt(&encrypted).unwrap().as_slice()
+    );
+
+    // A different passphrase does not.
+    let wrong = KeystoreKey::derive("battery staple", encrypted.header.clone()).unwrap();
+    assert!(wrong.decrypt(&encrypted).is_err());
+
+    // Tampering with the ciphertext is detected.
+    let mut tampered = encrypted.clone();
+    let mut ciphertext = Base64::decode(&tampered.ciphertext).unwrap();
+    ciphertext[0] ^= 1;
+    tampered.ciphertext = Base64::encode(ciphertext);
+    assert!(key.decrypt(&tampered).is_err());
+}
+
+#[test]
+fn encrypted_keystore_migration_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    let private_key = keystore.get_key(&address).unwrap().encode_base64();
+    assert!(!keystore.is_encrypted());
+
+    keystore.encrypt("correct horse", TEST_KDF).unwrap();
+    assert!(keystore.is_encrypted());
+    assert!(!keystore.is_locked());
+
+    // The private key is no longer stored in plaintext.
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    assert!(!contents.contains(&private_key));
+    let encrypted: EncryptedKeystore = serde_json::from_str(&contents).unwrap();
+    assert_eq!(TEST_KDF, encrypted.header.kdf);
+
+    // Loading the keystore again leaves it locked: addresses are available but keys are not.
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+    assert_eq!(vec![address], keystore.addresses());
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .sign_secure(&address, b"hello", Intent::sui_transaction())
+        .is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+
+    assert!(keystore.unlock("battery staple").is_err());
+    assert!(keystore.is_locked());
+
+    keystore.unlock("correct horse").unwrap();
+    assert!(!keystore.is_locked());
+    assert_eq!(
+        private_key,
+        keystore.get_key(&address).unwrap().encode_base64()
+    );
+    keystore
+        .sign_secure(&address, b"hello", Intent::sui_transaction())
+        .unwrap();
+
+    // Keys added while unlocked are saved encrypted.
+    let (added, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore.unlock("correct horse").unwrap();
+    assert!(keystore.get_key(&added).is_ok());
+}
+
+#[test]
+fn encrypted_keystore_rotate_passphrase_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    keystore.encrypt("correct horse", TEST_KDF).unwrap();
+
+    assert!(keystore
+        .rotate_passphrase("battery staple", "battery staple")
+        .is_err());
+    keystore
+        .rotate_passphrase("correct horse", "battery staple")
+        .unwrap();
+
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.unlock("correct horse").is_err());
+    keystore.unlock("battery staple").unwrap();
+    assert!(keystore.get_key(&address).is_ok());
+}
+
+#[test]
+fn encrypted_keystore_unlock_cache_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+
+    // Only encrypted keystores can be unlocked.
+    assert!(keystore.cache_unlock(Duration::from_secs(60)).is_err());
+    keystore.encrypt("correct horse", TEST_KDF).unwrap();
+
+    // While the cache is fresh, the keystore loads unlocked.
+    keystore.cache_unlock(Duration::from_secs(60)).unwrap();
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(!keystore.is_locked());
+    assert!(keystore.get_key(&address).is_ok());
+
+    // Locking the keystore forgets the cache.
+    keystore.lock().unwrap();
+    assert!(keystore.is_locked());
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+
+    // Expired caches are ignored.
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore.unlock("correct horse").unwrap();
+    keystore.cache_unlock(Duration::ZERO).unwrap();
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+
+    // ...and removed.
+    let cache_dir = temp_dir.path().join(UNLOCK_CACHE_DIR);
+    assert_eq!(0, fs::read_dir(&cache_dir).unwrap().count());
+}
+
+#[test]
+fn encrypted_keystore_stale_unlock_cache_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .unwrap();
+    keystore.encrypt("correct horse", TEST_KDF).unwrap();
+    keystore.cache_unlock(Duration::from_secs(60)).unwrap();
+
+    // The cache is only accessible by its owner.
+    let cache_dir = temp_dir.path().join(UNLOCK_CACHE_DIR);
+    let cache_path = fs::read_dir(&cache_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let dir_mode = fs::metadata(&cache_dir).unwrap().permissions().mode();
+        let file_mode = fs::metadata(&cache_path).unwrap().permissions().mode();
+        assert_eq!(0o700, dir_mode & 0o777);
+        assert_eq!(0o600, file_mode & 0o777);
+    }
+
+    // Re-encrypting the keystore under the same header, but a different passphrase, makes the
+    // cache stale: the keystore loads locked instead of failing.
+    let contents = fs::read_to_string(&keystore_path).unwrap();
+    let encrypted: EncryptedKeystore = serde_json::from_str(&contents).unwrap();
+    let other = KeystoreKey::derive("battery staple", encrypted.header).unwrap();
+    let reencrypted = other.encrypt(b"[]").unwrap();
+    fs::write(&keystore_path, serde_json::to_string(&reencrypted).unwrap()).unwrap();
+
+    let keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    assert!(keystore.is_locked());
+    assert_eq!(vec![address], keystore.addresses());
+
+    // A cache that others can read is ignored.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::write(&keystore_path, contents).unwrap();
+        assert!(!FileBasedKeystore::new(&keystore_path).unwrap().is_locked());
+
+        fs::set_permissions(&cache_path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(FileBasedKeystore::new(&keystore_path).unwrap().is_locked());
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure};
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use sui_rpc_api::CheckpointData;
+use sui_types::{
+    base_types::ObjectRef,
+    committee::Committee,
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    event::{Event, EventID},
+    message_envelope::Message,
+    messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
+    },
+    object::Object,
+};
+
+/// The version of the serialized format written by `BatchProof::to_bytes`.
+pub const BATCH_PROOF_VERSION: u8 = 1;
+
+/// Define aspects of Sui state, spread over any number of transactions and checkpoints, that need
+/// to be certified by a batch proof.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct BatchProofTarget {
+    /// Objects that need to be certified.
+    pub objects: Vec<(ObjectRef, Object)>,
+
+    /// Events that need to be certified.
+    pub events: Vec<(EventID, Event)>,
+}
+
+impl BatchProofTarget {
+    /// Create a new empty batch proof target.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an object to be certified by object reference and content.
+    pub fn add_object(mut self, object_ref: ObjectRef, object: Object) -> Self {
+        self.objects.push((object_ref, object));
+        self
+    }
+
+    /// Add an event to be certified by event ID and content.
+    pub fn add_event(mut self, event_id: EventID, event: Event) -> Self {
+        self.events.push((event_id, event));
+        self
+    }
+
+    /// Add every event of type `type_` emitted in `checkpoints` to be certified.
+    ///
+    /// Note that a verified proof certifies that each of these events is authentic, not that they
+    /// are all the events of this type in the range: a proof constructed from a subset of them
+    /// verifies just as well.
+    pub fn add_events_of_type(mut self, type_: &StructTag, checkpoints: &[CheckpointData]) -> Self {
+        for tx in checkpoints.iter().flat_map(|c| &c.transactions) {
+            let Some(events) = &tx.events else {
+                continue;
+            };
+
+            let digest = *tx.effects.transaction_digest();
+            for (seq, event) in events.data.iter().enumerate() {
+                if &event.type_ == type_ {
+                    self.events
+                        .push((EventID::from((digest, seq as u64)), event.clone()));
+                }
+            }
+        }
+        self
+    }
+
+    /// The transactions that the targets were produced by.
+    fn transactions(&self) -> BTreeSet<TransactionDigest> {
+        let object_tx = self.objects.iter().map(|(_, o)| o.previous_transaction);
+        let event_tx = self.events.iter().map(|(eid, _)| eid.tx_digest);
+        object_tx.chain(event_tx).collect()
+    }
+}
+
+/// Evidence for the transactions of one checkpoint that the targets of a batch proof refer to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointContentsProof {
+    /// The checkpoint these transactions belong to.
+    pub sequence_number: CheckpointSequenceNumber,
+
+    /// The contents of that checkpoint.
+    pub checkpoint_contents: CheckpointContents,
+
+    /// The effects, and events if any, of each transaction referred to by a target. Transactions
+    /// themselves are not needed, as effects are authenticated by their digest in the contents.
+    pub transactions: Vec<(TransactionEffects, Option<TransactionEvents>)>,
+}
+
+/// A proof for targets across a range of checkpoints. Only the last checkpoint in the range carries
+/// a certificate; the summaries of the checkpoints before it are authenticated by the chain of
+/// `previous_digest`s leading to it, so the proof needs a single signature however long the range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchProof {
+    /// Targets of the proof are objects and events that need to be certified.
+    pub targets: BatchProofTarget,
+
+    /// Summaries of the checkpoints in the range, in order, excluding the last.
+    pub checkpoint_summaries: Vec<CheckpointSummary>,
+
+    /// The certified summary of the last checkpoint in the range.
+    pub certified_summary: CertifiedCheckpointSummary,
+
+    /// Evidence for the transactions the targets refer to, grouped by checkpoint.
+    pub contents_proofs: Vec<CheckpointContentsProof>,
+}
+
+impl BatchProof {
+    /// The range of checkpoints covered by the proof.
+    pub fn checkpoints(&self) -> std::ops::RangeInclusive<CheckpointSequenceNumber> {
+        let last = self.certified_summary.sequence_number;
+        let first = self
+            .checkpoint_summaries
+            .first()
+            .map_or(last, |s| s.sequence_number);
+        first..=last
+    }
+
+    /// Serialize the proof, prefixed by its format version.
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        bcs::to_bytes(&(BATCH_PROOF_VERSION, self))
+            .map_err(|e| anyhow!("Unable to serialize batch proof: {e}"))
+    }
+
+    /// Deserialize a proof written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let (version, proof): (u8, Self) =
+            bcs::from_bytes(bytes).map_err(|e| anyhow!("Unable to parse batch proof: {e}"))?;
+        ensure!(
+            version == BATCH_PROOF_VERSION,
+            "Unsupported batch proof version: {version}"
+        );
+        Ok(proof)
+    }
+}
+
+/// Construct a batch proof for `targets` from `checkpoints`, a contiguous range of checkpoints in
+/// order. The proof is certified by the last checkpoint given, and covers the range from the first
+/// checkpoint that includes a target.
+///
+/// As with `construct_proof`, only minimal checks are performed. Call `verify_batch_proof` on the
+/// result if guaranteed validity is needed.
+pub fn construct_batch_proof(
+    targets: BatchProofTarget,
+    checkpoints: &[CheckpointData],
+) -> anyhow::Result<BatchProof> {
+    let (last, rest) = checkpoints
+        .split_last()
+        .ok_or(anyhow!("At least one checkpoint is required"))?;
+
+    for pair in checkpoints.windows(2) {
+        ensure!(
+            pair[0].checkpoint_summary.sequence_number + 1
+                == pair[1].checkpoint_summary.sequence_number,
+            "Checkpoints must be contiguous and in order"
+        );
+    }
+
+    let mut missing = targets.transactions();
+    let mut contents_proofs = vec![];
+    for data in checkpoints {
+        let transactions: Vec<_> = data
+            .transactions
+            .iter()
+            .filter(|t| missing.remove(t.effects.transaction_digest()))
+            .map(|t| (t.effects.clone(), t.events.clone()))
+            .collect();
+
+        if !transactions.is_empty() {
+            contents_proofs.push(CheckpointContentsProof {
+                sequence_number: data.checkpoint_summary.sequence_number,
+                checkpoint_contents: data.checkpoint_contents.clone(),
+                transactions,
+            });
+        }
+    }
+
+    if let Some(digest) = missing.first() {
+        return Err(anyhow!("Transaction {digest} not found in checkpoint data"));
+    }
+
+    // Summaries before the first checkpoint with any evidence in it are not needed.
+    let first = contents_proofs
+        .first()
+        .map_or(last.checkpoint_summary.sequence_number, |c| {
+            c.sequence_number
+        });
+
+    Ok(BatchProof {
+        targets,
+        checkpoint_summaries: rest
+            .iter()
+            .map(|c| c.checkpoint_summary.data())
+            .filter(|s| s.sequence_number >= first)
+            .cloned()
+            .collect(),
+        certified_summary: last.checkpoint_summary.clone(),
+        contents_proofs,
+    })
+}
+
+/// Verify a batch proof against the committee of the epoch of its last checkpoint.
+///
+/// If the result is `Ok(())` then every target of the proof, and every checkpoint summary in it,
+/// can be trusted as authentic.
+pub fn verify_batch_proof(committee: &Committee, proof: &BatchProof) -> anyhow::Result<()> {
+    // Verify the last checkpoint's certificate, and each summary before it through the digest of
+    // the summary that follows it.
+    proof
+        .certified_summary
+        .verify_authority_signatures(committee)?;
+
+    let mut summaries = BTreeMap::new();
+    let mut next = proof.certified_summary.data();
+    summaries.insert(next.sequence_number, next);
+    for summary in proof.checkpoint_summaries.iter().rev() {
+        ensure!(
+            summary.sequence_number + 1 == next.sequence_number
+                && next.previous_digest == Some(summary.digest()),
+            "Checkpoint {} is not the predecessor of checkpoint {}",
+            summary.sequence_number,
+            next.sequence_number
+        );
+        summaries.insert(summary.sequence_number, summary);
+        next = summary;
+    }
+
+    // Check the evidence for each transaction is in the contents of its checkpoint.
+    let mut transactions = BTreeMap::new();
+    for contents_proof in &proof.contents_proofs {
+        let summary = summaries
+            .get(&contents_proof.sequence_number)
+            .ok_or(anyhow!(
+                "Checkpoint {} is not covered by the proof",
+                contents_proof.sequence_number
+            ))?;
+
+        ensure!(
+            contents_proof.checkpoint_contents.digest() == &summary.content_digest,
+            "Contents digest does not match checkpoint {}",
+            contents_proof.sequence_number
+        );
+
+        for (effects, events) in &contents_proof.transactions {
+            let digests = effects.execution_digests();
+            ensure!(
+                contents_proof
+                    .checkpoint_contents
+                    .iter()
+                    .any(|x| x == &digests),
+                "Transaction {} not found in the contents of checkpoint {}",
+                digests.transaction,
+                contents_proof.sequence_number
+            );
+
+            ensure!(
+                effects.events_digest() == events.as_ref().map(|e| e.digest()).as_ref(),
+                "Events digest does not match the execution digest of transaction {}",
+                digests.transaction
+            );
+
+            transactions.insert(digests.transaction, (effects, events));
+        }
+    }
+
+    // Check the targets against the transactions they refer to.
+    for (event_id, event) in &proof.targets.events {
+        let (_, events) = transactions
+            .get(&event_id.tx_digest)
+            .ok_or(anyhow!("Transaction {} is missing", event_id.tx_digest))?;
+
+        let events = events
+            .as_ref()
+            .ok_or(anyhow!("Events of {} are missing", event_id.tx_digest))?;
+
+        ensure!(
+            events.data.get(event_id.event_seq as usize) == Some(event),
+            "Event {:?} does not match",
+            event_id
+        );
+    }
+
+    for (object_ref, object) in &proof.targets.objects {
+        ensure!(
+            object_ref == &object.compute_object_reference(),
+            "Object reference does not match the object"
+        );
+
+        let (effects, _) = transactions
+            .get(&object.previous_transaction)
+            .ok_or(anyhow!(
+                "Transaction {} is missing",
+                object.previous_transaction
+            ))?;
+
+        ensure!(
+            effects
+                .all_changed_objects()
+                .iter()
+                .any(|(changed_ref, _, _)| changed_ref == object_ref),
+            "Object {} not found in the effects of {}",
+            object_ref.0,
+            object.previous_transaction
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,407 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::batch::{verify_batch_proof, BatchProof};
+use crate::construct::construct_proof;
+use crate::proof::{verify_proof, Proof, ProofTarget, TransactionProof};
+
+use anyhow::{anyhow, ensure, Context};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use sui_config::genesis::Genesis;
+use sui_rpc_api::{CheckpointData, CheckpointTransaction};
+use sui_types::{
+    base_types::ObjectID,
+    committee::{Committee, EpochId},
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    event::{Event, EventID},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData},
+    object::Object,
+};
+
+/// A source of (unverified) chain data for a `LightClient`. Nothing returned by a source is
+/// trusted until the light client has checked it against a committee it has verified.
+#[async_trait]
+pub trait CheckpointSource: Send + Sync {
+    /// The sequence number of the latest checkpoint known to the source.
+    async fn latest_checkpoint(&self) -> anyhow::Result<CheckpointSequenceNumber>;
+
+    /// The sequence number of the last checkpoint of `epoch`, which carries the committee for the
+    /// next epoch.
+    async fn last_checkpoint_of_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> anyhow::Result<CheckpointSequenceNumber>;
+
+    /// The full contents of checkpoint `seq`.
+    async fn full_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CheckpointData>;
+
+    /// The summary of checkpoint `seq`. Sources that can fetch summaries without the rest of the
+    /// checkpoint should override this.
+    async fn checkpoint_summary(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CertifiedCheckpointSummary> {
+        Ok(self.full_checkpoint(seq).await?.checkpoint_summary)
+    }
+
+    /// The sequence number of the checkpoint that includes transaction `digest`.
+    async fn transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<CheckpointSequenceNumber>;
+
+    /// The latest version of object `id`.
+    async fn object(&self, id: ObjectID) -> anyhow::Result<Object>;
+}
+
+/// Everything a light client needs to remember between runs: the committee it was told to trust,
+/// and the end-of-epoch checkpoints it has verified since, from which all later committees follow.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrustedState {
+    /// The root of trust. This committee is never verified, so it must come from a trusted source
+    /// (e.g. a genesis blob, or an end-of-epoch checkpoint obtained out of band).
+    pub root: Committee,
+
+    /// The last checkpoint of every epoch since the root's, in order.
+    pub end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+impl TrustedState {
+    /// Trust `committee` as the root.
+    pub fn from_committee(committee: Committee) -> Self {
+        Self {
+            root: committee,
+            end_of_epoch_checkpoints: vec![],
+        }
+    }
+
+    /// Trust the genesis committee as the root.
+    pub fn from_genesis(genesis: &Genesis) -> anyhow::Result<Self> {
+        let committee = genesis
+            .committee()
+            .map_err(|e| anyhow!("Cannot load genesis committee: {e}"))?;
+        Ok(Self::from_committee(committee))
+    }
+
+    /// Trust the committee that end-of-epoch checkpoint `summary` hands over to as the root.
+    pub fn from_checkpoint(summary: &CertifiedCheckpointSummary) -> anyhow::Result<Self> {
+        Ok(Self::from_committee(next_committee(summary)?))
+    }
+
+    /// Read state previously written by `save`.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let bytes = fs::read(path)
+            .with_context(|| format!("Cannot read light client state: {}", path.display()))?;
+        bcs::from_bytes(&bytes).map_err(|e| anyhow!("Unable to parse light client state: {e}"))
+    }
+
+    /// Write the state to `path`, replacing it atomically so that a crash never leaves a partially
+    /// written state behind.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let bytes = bcs::to_bytes(self)
+            .map_err(|e| anyhow!("Unable to serialize light client state: {e}"))?;
+
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = fs::File::create(&tmp_path)
+            .with_context(|| format!("Cannot write light client state: {}", tmp_path.display()))?;
+        writer.write_all(&bytes)?;
+        writer.sync_all()?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Cannot write light client state: {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// A light client that follows the chain's committees from a root of trust, and serves objects,
+/// transactions and events that it has verified against them.
+///
+/// Committees are rotated by verifying each epoch's last checkpoint with the current committee,
+/// and adopting the next epoch's committee that it certifies. If the client was opened with a
+/// path, every rotation is persisted, so that a restarted client does not need to re-sync.
+pub struct LightClient<S> {
+    source: S,
+    state: TrustedState,
+    committees: BTreeMap<EpochId, Committee>,
+    state_path: Option<PathBuf>,
+}
+
+impl<S: CheckpointSource> LightClient<S> {
+    /// Create a light client from `state`, which is re-verified in full. The client's state is not
+    /// persisted.
+    pub fn new(source: S, state: TrustedState) -> anyhow::Result<Self> {
+        let root = state.root.clone();
+        let mut client = Self {
+            source,
+            state: TrustedState::from_committee(root.clone()),
+            committees: BTreeMap::from([(root.epoch, root)]),
+            state_path: None,
+        };
+
+        for summary in state.end_of_epoch_checkpoints {
+            let next = client
+                .verify_rotation(&summary)
+                .context("Invalid light client state")?;
+            client.rotate(summary, next);
+        }
+
+        Ok(client)
+    }
+
+    /// Open a light client whose state is persisted at `path`. If there is no state there yet, the
+    /// client starts from `root`, otherwise the persisted state must have been started from the
+    /// same root of trust.
+    pub fn open(source: S, path: PathBuf, root: TrustedState) -> anyhow::Result<Self> {
+        let state = if path.exists() {
+            let state = TrustedState::load(&path)?;
+            ensure!(
+                state.root == root.root,
+                "Light client state at {} was started from a different root of trust",
+                path.display()
+            );
+            state
+        } else {
+            root.save(&path)?;
+            root
+        };
+
+        let mut client = Self::new(source, state)?;
+        client.state_path = Some(path);
+        Ok(client)
+    }
+
+    /// The source that the client reads chain data from.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The state that the client has verified so far.
+    pub fn state(&self) -> &TrustedState {
+        &self.state
+    }
+
+    /// The committee of the latest epoch the client knows about.
+    pub fn committee(&self) -> &Committee {
+        // Safe to unwrap, as there is always at least the root committee.
+        self.committees.values().next_back().unwrap()
+    }
+
+    /// The verified committee for `epoch`.
+    pub fn committee_for_epoch(&self, epoch: EpochId) -> anyhow::Result<&Committee> {
+        if let Some(committee) = self.committees.get(&epoch) {
+            return Ok(committee);
+        }
+
+        let current = self.committee().epoch;
+        if epoch > current {
+            Err(anyhow!(
+                "No verified committee for epoch {epoch}, the light client has only synced up to \
+                 epoch {current}"
+            ))
+        } else {
+            Err(anyhow!(
+                "No verified committee for epoch {epoch}, which precedes the root of trust (epoch \
+                 {})",
+                self.state.root.epoch
+            ))
+        }
+    }
+
+    /// Verify `summary`, the last checkpoint of the current epoch, and rotate to the committee it
+    /// certifies for the next epoch. The rotation is persisted before it takes effect, so if it
+    /// cannot be persisted, the client stays at the current epoch.
+    pub fn advance(&mut self, summary: CertifiedCheckpointSummary) -> anyhow::Result<&Committee> {
+        let next = self.verify_rotation(&summary)?;
+
+        if let Some(path) = &self.state_path {
+            let mut state = self.state.clone();
+            state.end_of_epoch_checkpoints.push(summary.clone());
+            state.save(path)?;
+        }
+
+        self.rotate(summary, next);
+        Ok(self.committee())
+    }
+
+    /// Walk the end-of-epoch checkpoints between the current committee and the source's latest
+    /// checkpoint, rotating committees along the way. Returns the committee of the latest epoch.
+    pub async fn sync(&mut self) -> anyhow::Result<&Committee> {
+        let latest_seq = self.source.latest_checkpoint().await?;
+        let latest = self.source.checkpoint_summary(latest_seq).await?;
+
+        while self.committee().epoch < latest.epoch() {
+            let epoch = self.committee().epoch;
+            let seq = self.source.last_checkpoint_of_epoch(epoch).await?;
+            let summary = self.source.checkpoint_summary(seq).await?;
+            self.advance(summary)
+                .with_context(|| format!("Cannot rotate committee at end of epoch {epoch}"))?;
+        }
+
+        // The latest checkpoint may itself close the current epoch.
+        if latest.epoch() == self.committee().epoch && latest.end_of_epoch_data.is_some() {
+            self.advance(latest)?;
+        }
+
+        Ok(self.committee())
+    }
+
+    /// Fetch checkpoint `seq`, and verify its summary and contents.
+    pub async fn get_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CheckpointData> {
+        let data = self.source.full_checkpoint(seq).await?;
+        let committee = self.committee_for_epoch(data.checkpoint_summary.epoch())?;
+        data.checkpoint_summary
+            .verify_with_contents(committee, Some(&data.checkpoint_contents))?;
+        Ok(data)
+    }
+
+    /// Fetch the effects and events of transaction `digest`, verified to be part of a certified
+    /// checkpoint.
+    pub async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<(TransactionEffects, Option<TransactionEvents>)> {
+        let seq = self.source.transaction_checkpoint(digest).await?;
+        let data = self.source.full_checkpoint(seq).await?;
+
+        let CheckpointTransaction {
+            transaction,
+            effects,
+            events,
+            ..
+        } = data
+            .transactions
+            .iter()
+            .find(|t| t.effects.transaction_digest() == &digest)
+            .ok_or(anyhow!("Transaction not found in checkpoint data"))?
+            .clone();
+
+        let proof = Proof {
+            targets: ProofTarget::new(),
+            checkpoint_summary: data.checkpoint_summary,
+            contents_proof: Some(TransactionProof {
+                checkpoint_contents: data.checkpoint_contents,
+                transaction,
+                effects,
+                events,
+            }),
+        };
+        self.verify(&proof)?;
+
+        // Safe to unwrap, as the proof was constructed with contents.
+        let TransactionProof {
+            effects, events, ..
+        } = proof.contents_proof.unwrap();
+        Ok((effects, events))
+    }
+
+    /// Fetch the events emitted by transaction `digest`, verified to be part of a certified
+    /// checkpoint.
+    pub async fn get_events(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<Vec<(EventID, Event)>> {
+        let (_, events) = self.get_transaction(digest).await?;
+        Ok(events
+            .map(|events| events.data)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(seq, event)| (EventID::from((digest, seq as u64)), event))
+            .collect())
+    }
+
+    /// Fetch object `id`, and verify that its contents were written by a transaction in a
+    /// certified checkpoint.
+    pub async fn get_object(&self, id: ObjectID) -> anyhow::Result<Object> {
+        let object = self.source.object(id).await?;
+        ensure!(
+            object.id() == id,
+            "Source returned object {} for {id}",
+            object.id()
+        );
+
+        let seq = self
+            .source
+            .transaction_checkpoint(object.previous_transaction)
+            .await?;
+        let data = self.source.full_checkpoint(seq).await?;
+
+        let target = ProofTarget::new().add_object(object.compute_object_reference(), object);
+        let proof = construct_proof(target, &data)?;
+        self.verify(&proof)?;
+
+        let (_, object) = proof.targets.objects.into_iter().next().unwrap();
+        Ok(object)
+    }
+
+    /// Verify `proof` against the committee of the epoch it belongs to.
+    pub fn verify(&self, proof: &Proof) -> anyhow::Result<()> {
+        let committee = self.committee_for_epoch(proof.checkpoint_summary.epoch())?;
+        verify_proof(committee, proof)
+    }
+
+    /// Verify batch `proof` against the committee of the epoch its last checkpoint belongs to.
+    pub fn verify_batch(&self, proof: &BatchProof) -> anyhow::Result<()> {
+        let committee = self.committee_for_epoch(proof.certified_summary.epoch())?;
+        verify_batch_proof(committee, proof)
+    }
+
+    /// Check that `summary` is the last checkpoint of the current epoch, certified by its
+    /// committee, and return the committee it hands over to.
+    fn verify_rotation(&self, summary: &CertifiedCheckpointSummary) -> anyhow::Result<Committee> {
+        let committee = self.committee();
+        ensure!(
+            summary.epoch() == committee.epoch,
+            "Expected a checkpoint from epoch {}, got one from epoch {}",
+            committee.epoch,
+            summary.epoch()
+        );
+
+        summary.verify_with_contents(committee, None)?;
+        next_committee(summary)
+    }
+
+    /// Rotate to `next`, the committee handed over to by `summary`, which has been verified by
+    /// `verify_rotation`.
+    fn rotate(&mut self, summary: CertifiedCheckpointSummary, next: Committee) {
+        self.committees.insert(next.epoch, next);
+        self.state.end_of_epoch_checkpoints.push(summary);
+    }
+}
+
+/// The committee for the epoch after `summary`'s, which must be an end-of-epoch checkpoint.
+fn next_committee(summary: &CertifiedCheckpointSummary) -> anyhow::Result<Committee> {
+    let Some(EndOfEpochData {
+        next_epoch_committee,
+        ..
+    }) = &summary.end_of_epoch_data
+    else {
+        return Err(anyhow!(
+            "Checkpoint {} is not an end-of-epoch checkpoint",
+            summary.sequence_number
+        ));
+    };
+
+    let next_epoch = summary.epoch().checked_add(1).ok_or_else(|| {
+        anyhow!(
+            "Epoch after checkpoint {} overflows",
+            summary.sequence_number
+        )
+    })?;
+
+    Ok(Committee::new(
+        next_epoch,
+        next_epoch_committee.iter().cloned().collect(),
+    ))
+}
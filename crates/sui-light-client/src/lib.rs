@@ -1,11 +1,23 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod batch;
+pub mod client;
 pub mod construct;
 pub mod proof;
+pub mod remote;
 
 #[doc(inline)]
 pub use proof::*;
 
 #[doc(inline)]
 pub use construct::*;
+
+#[doc(inline)]
+pub use batch::*;
+
+#[doc(inline)]
+pub use client::*;
+
+#[doc(inline)]
+pub use remote::*;
@@ -0,0 +1,125 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::CheckpointSource;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use object_store::{parse_url, path::Path, ObjectStore};
+use serde_json::{json, Value};
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponseOptions};
+use sui_rpc_api::CheckpointData;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::{
+    base_types::ObjectID, committee::EpochId, digests::TransactionDigest,
+    messages_checkpoint::CheckpointSequenceNumber, object::Object,
+};
+use url::Url;
+
+/// A `CheckpointSource` backed by a full node (for lookups), a checkpoint bucket (for checkpoint
+/// contents) and a GraphQL service (to find the last checkpoint of each epoch).
+pub struct RemoteCheckpointSource {
+    client: SuiClient,
+    object_store: Box<dyn ObjectStore>,
+    graphql_url: String,
+}
+
+impl RemoteCheckpointSource {
+    pub async fn new(
+        full_node_url: &str,
+        object_store_url: &str,
+        graphql_url: &str,
+    ) -> anyhow::Result<Self> {
+        let client = SuiClientBuilder::default()
+            .build(full_node_url)
+            .await
+            .map_err(|e| anyhow!("Cannot connect to full node: {e}"))?;
+
+        let url =
+            Url::parse(object_store_url).map_err(|_| anyhow!("Cannot parse object store URL"))?;
+        let (object_store, _) = parse_url(&url)?;
+
+        Ok(Self {
+            client,
+            object_store,
+            graphql_url: graphql_url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl CheckpointSource for RemoteCheckpointSource {
+    async fn latest_checkpoint(&self) -> anyhow::Result<CheckpointSequenceNumber> {
+        Ok(self
+            .client
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await?)
+    }
+
+    async fn last_checkpoint_of_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> anyhow::Result<CheckpointSequenceNumber> {
+        let query = json!({
+            "query": "query ($epochID: Int) { epoch(id: $epochID) { checkpoints(last: 1) { nodes { sequenceNumber } } } }",
+            "variables": { "epochID": epoch }
+        });
+
+        let resp = reqwest::Client::new()
+            .post(&self.graphql_url)
+            .header("Content-Type", "application/json")
+            .body(query.to_string())
+            .send()
+            .await
+            .map_err(|e| anyhow!("Cannot connect to graphql: {e}"))?
+            .text()
+            .await?;
+
+        let v: Value = serde_json::from_str(&resp)?;
+        v["data"]["epoch"]["checkpoints"]["nodes"][0]["sequenceNumber"]
+            .as_u64()
+            .ok_or(anyhow!("No last checkpoint for epoch {epoch}"))
+    }
+
+    async fn full_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CheckpointData> {
+        let path = Path::from(format!("{}.chk", seq));
+        let bytes = self
+            .object_store
+            .get(&path)
+            .await
+            .map_err(|e| anyhow!("Cannot get checkpoint {seq} from object store: {e}"))?
+            .bytes()
+            .await?;
+        let (_, data) = bcs::from_bytes::<(u8, CheckpointData)>(&bytes)?;
+        Ok(data)
+    }
+
+    async fn transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<CheckpointSequenceNumber> {
+        self.client
+            .read_api()
+            .get_transaction_with_options(digest, SuiTransactionBlockResponseOptions::new())
+            .await
+            .map_err(|e| anyhow!("Cannot get transaction: {e}"))?
+            .checkpoint
+            .ok_or(anyhow!("Transaction {digest} is not yet in a checkpoint"))
+    }
+
+    async fn object(&self, id: ObjectID) -> anyhow::Result<Object> {
+        let data = self
+            .client
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
+            .await
+            .map_err(|e| anyhow!("Cannot get object: {e}"))?
+            .into_object()
+            .map_err(|e| anyhow!("Cannot get object: {e}"))?;
+        data.try_into()
+    }
+}
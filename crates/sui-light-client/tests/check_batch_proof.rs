@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use common::{checkpoint_chain, read_data};
+
+use sui_light_client::batch::{
+    construct_batch_proof, verify_batch_proof, BatchProof, BatchProofTarget,
+};
+
+use sui_types::{committee::Committee, effects::TransactionEffectsAPI, event::EventID};
+
+use sui_rpc_api::CheckpointData;
+
+/// A target covering every event and the first output object of every transaction.
+fn all_targets(data: &CheckpointData) -> BatchProofTarget {
+    let mut target = BatchProofTarget::new();
+    for tx in &data.transactions {
+        let digest = *tx.effects.transaction_digest();
+        for (seq, event) in tx.events.iter().flat_map(|e| e.data.iter()).enumerate() {
+            target = target.add_event(EventID::from((digest, seq as u64)), event.clone());
+        }
+
+        if let Some(object) = tx.output_objects.first() {
+            target = target.add_object(object.compute_object_reference(), object.clone());
+        }
+    }
+    target
+}
+
+#[test]
+fn test_batch_proof_success() {
+    let (committee, data) = read_data(15918264, 16005062);
+
+    let target = all_targets(&data);
+    assert!(!target.events.is_empty());
+
+    let proof = construct_batch_proof(target, std::slice::from_ref(&data)).unwrap();
+    assert_eq!(proof.checkpoints(), 16005062..=16005062);
+    verify_batch_proof(&committee, &proof).unwrap();
+}
+
+#[test]
+fn test_batch_proof_events_of_type() {
+    let (committee, data) = read_data(15918264, 16005062);
+
+    let event = data
+        .transactions
+        .iter()
+        .find_map(|t| t.events.as_ref()?.data.first())
+        .unwrap();
+
+    let target =
+        BatchProofTarget::new().add_events_of_type(&event.type_, std::slice::from_ref(&data));
+    assert!(target.events.iter().all(|(_, e)| e.type_ == event.type_));
+
+    let proof = construct_batch_proof(target, std::slice::from_ref(&data)).unwrap();
+    verify_batch_proof(&committee, &proof).unwrap();
+}
+
+#[test]
+fn test_batch_proof_roundtrip() {
+    let (committee, data) = read_data(15918264, 16005062);
+
+    let proof = construct_batch_proof(all_targets(&data), std::slice::from_ref(&data)).unwrap();
+    let bytes = proof.to_bytes().unwrap();
+
+    let decoded = BatchProof::from_bytes(&bytes).unwrap();
+    verify_batch_proof(&committee, &decoded).unwrap();
+
+    // Unknown versions are rejected.
+    let mut bad_bytes = bytes.clone();
+    bad_bytes[0] += 1;
+    assert!(BatchProof::from_bytes(&bad_bytes).is_err());
+}
+
+#[test]
+fn test_batch_proof_fail_wrong_committee() {
+    let (_, data) = read_data(15918264, 16005062);
+    let (committee, _) = read_data(16005062, 16005062); // WRONG
+
+    let proof = construct_batch_proof(all_targets(&data), std::slice::from_ref(&data)).unwrap();
+    assert!(verify_batch_proof(&committee, &proof).is_err());
+}
+
+#[test]
+fn test_batch_proof_fail_bad_event() {
+    let (committee, data) = read_data(15918264, 16005062);
+
+    let mut proof = construct_batch_proof(all_targets(&data), std::slice::from_ref(&data)).unwrap();
+    let (event_id, _) = &mut proof.targets.events[0];
+    event_id.event_seq += 1000; // WRONG
+
+    assert!(verify_batch_proof(&committee, &proof).is_err());
+}
+
+#[test]
+fn test_batch_proof_fail_broken_chain() {
+    let (committee, data) = read_data(15918264, 16005062);
+
+    let mut proof = construct_batch_proof(all_targets(&data), std::slice::from_ref(&data)).unwrap();
+
+    // A summary that does not precede the certified checkpoint.
+    let mut summary = proof.certified_summary.data().clone();
+    summary.sequence_number -= 1;
+    proof.checkpoint_summaries.push(summary);
+
+    assert!(verify_batch_proof(&committee, &proof).is_err());
+}
+
+#[test]
+fn test_batch_proof_fail_missing_transaction() {
+    let (_, data) = read_data(15918264, 16005062);
+    let (_, other) = read_data(15918264, 15918264);
+
+    // Targets from a checkpoint that is not part of the range.
+    assert!(construct_batch_proof(all_targets(&other), std::slice::from_ref(&data)).is_err());
+}
+
+#[test]
+fn test_batch_proof_chain_success() {
+    let (committee, checkpoints) = checkpoint_chain(3);
+
+    // Targets in the first and last checkpoints, so the proof has to follow the chain of
+    // `previous_digest`s through the one in the middle.
+    let first = &checkpoints[0].transactions[0].output_objects[0];
+    let last = &checkpoints[2].transactions[0].output_objects[0];
+    let target = BatchProofTarget::new()
+        .add_object(first.compute_object_reference(), first.clone())
+        .add_object(last.compute_object_reference(), last.clone());
+
+    let proof = construct_batch_proof(target, &checkpoints).unwrap();
+    assert_eq!(proof.checkpoints(), 0..=2);
+    assert_eq!(proof.checkpoint_summaries.len(), 2);
+    assert_eq!(proof.contents_proofs.len(), 2);
+    verify_batch_proof(&committee, &proof).unwrap();
+
+    // Summaries before the first one with a target in it are left out.
+    let target = BatchProofTarget::new().add_object(last.compute_object_reference(), last.clone());
+    let proof = construct_batch_proof(target, &checkpoints).unwrap();
+    assert_eq!(proof.checkpoints(), 2..=2);
+    verify_batch_proof(&committee, &proof).unwrap();
+}
+
+#[test]
+fn test_batch_proof_chain_fail_broken_link() {
+    let (committee, checkpoints) = checkpoint_chain(3);
+    let first = &checkpoints[0].transactions[0].output_objects[0];
+    let target =
+        BatchProofTarget::new().add_object(first.compute_object_reference(), first.clone());
+
+    let mut proof = construct_batch_proof(target, &checkpoints).unwrap();
+    verify_batch_proof(&committee, &proof).unwrap();
+
+    // Tampering with a summary in the middle of the chain changes its digest, so it no longer
+    // matches the `previous_digest` of the checkpoint after it.
+    proof.checkpoint_summaries[1].network_total_transactions += 1;
+    assert!(verify_batch_proof(&committee, &proof).is_err());
+}
@@ -1,7 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod common;
+
 use anyhow::anyhow;
+use common::read_data;
 
 use sui_light_client::construct::construct_proof;
 use sui_light_client::proof::{verify_proof, Proof, ProofTarget};
@@ -12,57 +15,9 @@ use sui_types::{committee::Committee, effects::TransactionEffectsAPI, object::Ob
 
 use sui_rpc_api::CheckpointData;
 
-use std::io::Read;
-use std::{fs, path::PathBuf};
-
-async fn read_full_checkpoint(checkpoint_path: &PathBuf) -> anyhow::Result<CheckpointData> {
-    println!("Reading checkpoint from {:?}", checkpoint_path);
-    let mut reader = fs::File::open(checkpoint_path.clone())?;
-    let mut buffer = Vec::new();
-    reader.read_to_end(&mut buffer)?;
-    let (_, data): (u8, CheckpointData) =
-        bcs::from_bytes(&buffer).map_err(|e| anyhow!("Unable to parse checkpoint file: {}", e))?;
-    Ok(data)
-}
-
-async fn read_data(committee_seq: u64, seq: u64) -> (Committee, CheckpointData) {
-    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    d.push(format!("example_config/{}.chk", committee_seq));
-
-    let committee_checkpoint = read_full_checkpoint(&d).await.unwrap();
-
-    let prev_committee = committee_checkpoint
-        .checkpoint_summary
-        .end_of_epoch_data
-        .as_ref()
-        .ok_or(anyhow!("Expected checkpoint to be end-of-epoch"))
-        .unwrap()
-        .next_epoch_committee
-        .iter()
-        .cloned()
-        .collect();
-
-    // Make a committee object using this
-    let committee = Committee::new(
-        committee_checkpoint
-            .checkpoint_summary
-            .epoch()
-            .checked_add(1)
-            .unwrap(),
-        prev_committee,
-    );
-
-    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    d.push(format!("example_config/{}.chk", seq));
-
-    let full_checkpoint = read_full_checkpoint(&d).await.unwrap();
-
-    (committee, full_checkpoint)
-}
-
 #[tokio::test]
 async fn check_can_read_test_data() {
-    let (_committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (_committee, full_checkpoint) = read_data(15918264, 16005062);
     assert!(full_checkpoint
         .checkpoint_summary
         .end_of_epoch_data
@@ -71,7 +26,7 @@ async fn check_can_read_test_data() {
 
 #[tokio::test]
 async fn test_new_committee() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let new_committee_data = full_checkpoint
         .checkpoint_summary
@@ -106,7 +61,7 @@ async fn test_new_committee() {
 // Fail if the new committee does not match the target of the proof
 #[tokio::test]
 async fn test_incorrect_new_committee() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let committee_proof = Proof {
         checkpoint_summary: full_checkpoint.checkpoint_summary.clone(),
@@ -120,7 +75,7 @@ async fn test_incorrect_new_committee() {
 // Fail if the certificate is incorrect even if no proof targets are given
 #[tokio::test]
 async fn test_fail_incorrect_cert() {
-    let (_committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (_committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let new_committee_data = full_checkpoint
         .checkpoint_summary
@@ -158,7 +113,7 @@ async fn test_fail_incorrect_cert() {
 
 #[tokio::test]
 async fn test_object_target_fail_no_data() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let sample_object: Object = full_checkpoint.transactions[0].output_objects[0].clone();
     let sample_ref = sample_object.compute_object_reference();
@@ -174,7 +129,7 @@ async fn test_object_target_fail_no_data() {
 
 #[tokio::test]
 async fn test_object_target_success() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let sample_object: Object = full_checkpoint.transactions[0].output_objects[0].clone();
     let sample_ref = sample_object.compute_object_reference();
@@ -187,7 +142,7 @@ async fn test_object_target_success() {
 
 #[tokio::test]
 async fn test_object_target_fail_wrong_object() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let sample_object: Object = full_checkpoint.transactions[0].output_objects[0].clone();
     let wrong_object: Object = full_checkpoint.transactions[1].output_objects[1].clone();
@@ -208,7 +163,7 @@ async fn test_object_target_fail_wrong_object() {
 
 #[tokio::test]
 async fn test_event_target_fail_no_data() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let sample_event: Event = full_checkpoint.transactions[1]
         .events
@@ -232,7 +187,7 @@ async fn test_event_target_fail_no_data() {
 
 #[tokio::test]
 async fn test_event_target_success() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let sample_event: Event = full_checkpoint.transactions[1]
         .events
@@ -253,7 +208,7 @@ async fn test_event_target_success() {
 
 #[tokio::test]
 async fn test_event_target_fail_bad_event() {
-    let (committee, full_checkpoint) = read_data(15918264, 16005062).await;
+    let (committee, full_checkpoint) = read_data(15918264, 16005062);
 
     let sample_event: Event = full_checkpoint.transactions[1]
         .events
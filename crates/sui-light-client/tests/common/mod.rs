@@ -0,0 +1,69 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fixtures shared by the light client's integration tests.
+
+#![allow(dead_code)]
+
+use sui_rpc_api::CheckpointData;
+use sui_types::{
+    committee::Committee, message_envelope::Message,
+    messages_checkpoint::CertifiedCheckpointSummary,
+    test_checkpoint_data_builder::TestCheckpointDataBuilder,
+};
+
+use std::{fs, path::PathBuf};
+
+/// Read checkpoint `seq` from the example data.
+pub fn read_full_checkpoint(seq: u64) -> CheckpointData {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push(format!("example_config/{}.chk", seq));
+    let bytes = fs::read(d).unwrap();
+    let (_, data): (u8, CheckpointData) = bcs::from_bytes(&bytes).unwrap();
+    data
+}
+
+/// Read checkpoint `seq` from the example data, along with the committee handed over to by
+/// end-of-epoch checkpoint `committee_seq`.
+pub fn read_data(committee_seq: u64, seq: u64) -> (Committee, CheckpointData) {
+    let committee_checkpoint = read_full_checkpoint(committee_seq);
+    let summary = &committee_checkpoint.checkpoint_summary;
+    let next_committee = summary
+        .end_of_epoch_data
+        .as_ref()
+        .expect("Expected checkpoint to be end-of-epoch")
+        .next_epoch_committee
+        .iter()
+        .cloned()
+        .collect();
+
+    let committee = Committee::new(summary.epoch() + 1, next_committee);
+    (committee, read_full_checkpoint(seq))
+}
+
+/// `len` contiguous checkpoints, each with a transaction that creates an object, linked through
+/// their `previous_digest`s, and certified by the test committee that is also returned.
+pub fn checkpoint_chain(len: u64) -> (Committee, Vec<CheckpointData>) {
+    let (committee, keys) = Committee::new_simple_test_committee();
+    let mut builder = TestCheckpointDataBuilder::new(0);
+    let mut previous_digest = None;
+    let mut checkpoints = vec![];
+
+    for i in 0..len {
+        builder = builder
+            .start_transaction(0)
+            .create_owned_object(i)
+            .finish_transaction();
+
+        let mut data = builder.build_checkpoint();
+        let mut summary = data.checkpoint_summary.data().clone();
+        summary.previous_digest = previous_digest;
+        previous_digest = Some(summary.digest());
+
+        data.checkpoint_summary =
+            CertifiedCheckpointSummary::new_from_keypairs_for_testing(summary, &keys, &committee);
+        checkpoints.push(data);
+    }
+
+    (committee, checkpoints)
+}
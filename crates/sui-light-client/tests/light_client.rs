@@ -0,0 +1,229 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use common::read_full_checkpoint;
+
+use sui_light_client::client::{CheckpointSource, LightClient, TrustedState};
+
+use sui_types::{
+    base_types::ObjectID,
+    committee::EpochId,
+    digests::TransactionDigest,
+    effects::TransactionEffectsAPI,
+    messages_checkpoint::{CheckpointSequenceNumber, FullCheckpointContents},
+    object::Object,
+};
+
+use sui_rpc_api::CheckpointData;
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Serves checkpoints from the example data.
+struct TestSource {
+    checkpoints: BTreeMap<CheckpointSequenceNumber, CheckpointData>,
+}
+
+impl TestSource {
+    fn new(seqs: &[u64]) -> Self {
+        Self {
+            checkpoints: seqs
+                .iter()
+                .map(|seq| (*seq, read_full_checkpoint(*seq)))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointSource for TestSource {
+    async fn latest_checkpoint(&self) -> anyhow::Result<CheckpointSequenceNumber> {
+        Ok(*self.checkpoints.keys().next_back().unwrap())
+    }
+
+    async fn last_checkpoint_of_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> anyhow::Result<CheckpointSequenceNumber> {
+        self.checkpoints
+            .iter()
+            .find(|(_, data)| {
+                data.checkpoint_summary.epoch() == epoch
+                    && data.checkpoint_summary.end_of_epoch_data.is_some()
+            })
+            .map(|(seq, _)| *seq)
+            .ok_or(anyhow!("No last checkpoint for epoch {epoch}"))
+    }
+
+    async fn full_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CheckpointData> {
+        self.checkpoints
+            .get(&seq)
+            .cloned()
+            .ok_or(anyhow!("No checkpoint {seq}"))
+    }
+
+    async fn transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<CheckpointSequenceNumber> {
+        self.checkpoints
+            .iter()
+            .find(|(_, data)| {
+                data.transactions
+                    .iter()
+                    .any(|t| t.effects.transaction_digest() == &digest)
+            })
+            .map(|(seq, _)| *seq)
+            .ok_or(anyhow!("No transaction {digest}"))
+    }
+
+    async fn object(&self, id: ObjectID) -> anyhow::Result<Object> {
+        self.checkpoints
+            .values()
+            .flat_map(|data| &data.transactions)
+            .flat_map(|t| &t.output_objects)
+            .find(|o| o.id() == id)
+            .cloned()
+            .ok_or(anyhow!("No object {id}"))
+    }
+}
+
+fn root() -> TrustedState {
+    TrustedState::from_checkpoint(&read_full_checkpoint(15918264).checkpoint_summary).unwrap()
+}
+
+#[tokio::test]
+async fn test_sync_rotates_committee() {
+    let root = root();
+    let root_epoch = root.root.epoch;
+
+    let mut client = LightClient::new(TestSource::new(&[16005062]), root).unwrap();
+    assert_eq!(client.committee().epoch, root_epoch);
+
+    let committee = client.sync().await.unwrap();
+    assert_eq!(committee.epoch, root_epoch + 1);
+    assert_eq!(client.state().end_of_epoch_checkpoints.len(), 1);
+    assert!(client.committee_for_epoch(root_epoch).is_ok());
+    assert!(client.committee_for_epoch(root_epoch + 2).is_err());
+}
+
+#[tokio::test]
+async fn test_advance_rejects_wrong_epoch() {
+    let mut client = LightClient::new(TestSource::new(&[16005062]), root()).unwrap();
+
+    // This checkpoint closes the epoch before the root's.
+    let summary = read_full_checkpoint(15918264).checkpoint_summary;
+    assert!(client.advance(summary).is_err());
+    assert!(client.state().end_of_epoch_checkpoints.is_empty());
+}
+
+#[tokio::test]
+async fn test_state_is_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("light_client.bcs");
+
+    let mut client = LightClient::open(TestSource::new(&[16005062]), path.clone(), root()).unwrap();
+    let synced = client.sync().await.unwrap().clone();
+
+    // A reopened client picks up where the last one left off.
+    let client = LightClient::open(TestSource::new(&[]), path.clone(), root()).unwrap();
+    assert_eq!(client.committee(), &synced);
+
+    // But not if it is asked to trust a different root.
+    let other = TrustedState::from_committee(synced);
+    assert!(LightClient::open(TestSource::new(&[]), path, other).is_err());
+}
+
+#[tokio::test]
+async fn test_advance_fails_if_not_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("light_client.bcs");
+    let mut client = LightClient::open(TestSource::new(&[16005062]), path, root()).unwrap();
+    let root_epoch = client.committee().epoch;
+
+    // Once the state can no longer be written, the client does not rotate.
+    dir.close().unwrap();
+    let summary = read_full_checkpoint(16005062).checkpoint_summary;
+    assert!(client.advance(summary).is_err());
+    assert_eq!(client.committee().epoch, root_epoch);
+    assert!(client.state().end_of_epoch_checkpoints.is_empty());
+}
+
+#[tokio::test]
+async fn test_verified_lookups() {
+    let source = TestSource::new(&[16005062]);
+    let data = read_full_checkpoint(16005062);
+    let client = LightClient::new(source, root()).unwrap();
+
+    let object = data.transactions[0].output_objects[0].clone();
+    assert_eq!(client.get_object(object.id()).await.unwrap(), object);
+
+    let digest = *data.transactions[1].effects.transaction_digest();
+    let (effects, _) = client.get_transaction(digest).await.unwrap();
+    assert_eq!(effects, data.transactions[1].effects);
+
+    let events = client.get_events(digest).await.unwrap();
+    assert_eq!(
+        events[0].1,
+        data.transactions[1].events.as_ref().unwrap().data[0]
+    );
+}
+
+#[tokio::test]
+async fn test_lookup_fails_before_sync() {
+    let source = TestSource::new(&[16005062]);
+    let data = read_full_checkpoint(16005062);
+
+    // Trusting the committee after the checkpoint's, its epoch precedes the root.
+    let root = TrustedState::from_checkpoint(&data.checkpoint_summary).unwrap();
+    let client = LightClient::new(source, root).unwrap();
+
+    let digest = *data.transactions[1].effects.transaction_digest();
+    assert!(client.get_transaction(digest).await.is_err());
+}
+
+#[tokio::test]
+async fn test_transaction_not_found() {
+    let client = LightClient::new(TestSource::new(&[16005062]), root()).unwrap();
+    let digest =
+        TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap();
+    assert!(client.get_transaction(digest).await.is_err());
+}
+
+#[tokio::test]
+async fn test_transaction_bad_contents() {
+    let mut source = TestSource::new(&[16005062]);
+    let data = source.checkpoints.get_mut(&16005062).unwrap();
+    let digest = *data.transactions[1].effects.transaction_digest();
+
+    // Change contents
+    let random_contents = FullCheckpointContents::random_for_testing();
+    data.checkpoint_contents = random_contents.checkpoint_contents();
+
+    let client = LightClient::new(source, root()).unwrap();
+    assert!(client.get_transaction(digest).await.is_err());
+}
+
+#[tokio::test]
+async fn test_transaction_bad_events() {
+    let mut source = TestSource::new(&[16005062]);
+    let data = source.checkpoints.get_mut(&16005062).unwrap();
+    let digest = *data.transactions[1].effects.transaction_digest();
+
+    let event = data.transactions[1].events.as_ref().unwrap().data[0].clone();
+    for t in &mut data.transactions {
+        if let Some(events) = &mut t.events {
+            events.data.push(event.clone());
+        }
+    }
+
+    let client = LightClient::new(source, root()).unwrap();
+    assert!(client.get_transaction(digest).await.is_err());
+}
@@ -1002,6 +1002,9 @@ impl SuiNode {
                 prometheus_registry,
                 checkpoint_store,
                 chain_identifier,
+                config
+                    .state_snapshot_write_config
+                    .full_snapshot_interval_epochs,
             )?;
             Ok(Some(snapshot_uploader.start()))
         } else {
@@ -612,6 +612,11 @@ struct FeatureFlags {
     // If true, enable zstd compression for consensus tonic network.
     #[serde(skip_serializing_if = "is_false")]
     consensus_zstd_compression: bool,
+
+    // If true, consensus detects equivocating and invalid blocks, and reports their authors in
+    // proposed blocks.
+    #[serde(skip_serializing_if = "is_false")]
+    consensus_misbehavior_reports: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -1785,6 +1790,10 @@ impl ProtocolConfig {
     pub fn consensus_zstd_compression(&self) -> bool {
         self.feature_flags.consensus_zstd_compression
     }
+
+    pub fn consensus_misbehavior_reports(&self) -> bool {
+        self.feature_flags.consensus_misbehavior_reports
+    }
 }
 
 #[cfg(not(msim))]
@@ -3362,6 +3371,10 @@ impl ProtocolConfig {
     pub fn set_consensus_linearize_subdag_v2_for_testing(&mut self, val: bool) {
         self.feature_flags.consensus_linearize_subdag_v2 = val;
     }
+
+    pub fn set_consensus_misbehavior_reports_for_testing(&mut self, val: bool) {
+        self.feature_flags.consensus_misbehavior_reports = val;
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
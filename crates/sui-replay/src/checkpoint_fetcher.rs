@@ -0,0 +1,606 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::data_fetcher::DataFetcher;
+use crate::types::ReplayEngineError;
+use async_trait::async_trait;
+use lru::LruCache;
+use parking_lot::RwLock;
+use rand::seq::IteratorRandom;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_json_rpc_types::SuiEvent;
+use sui_json_rpc_types::SuiTransactionBlockResponse;
+use sui_protocol_config::Chain;
+use sui_storage::blob::Blob;
+use sui_types::base_types::{ObjectID, SequenceNumber, VersionNumber};
+use sui_types::digests::{
+    get_mainnet_chain_identifier, get_testnet_chain_identifier, ChainIdentifier, TransactionDigest,
+};
+use sui_types::event::SystemEpochInfoEvent;
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::message_envelope::Message;
+use sui_types::object::Object;
+use sui_types::storage::ObjectStore;
+use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
+use sui_types::transaction::{
+    ChangeEpoch, EndOfEpochTransactionKind, TransactionDataAPI, TransactionKind,
+};
+use tracing::info;
+
+/// File extension of the checkpoint files written by `sui-data-ingestion`.
+const CHECKPOINT_FILE_EXTENSION: &str = "chk";
+
+const CHECKPOINT_CACHE_CAPACITY: Option<NonZeroUsize> = NonZeroUsize::new(100);
+
+/// Where a version of an object was seen in the checkpoint archive.
+#[derive(Clone, Copy, Debug)]
+struct ObjectLocation {
+    checkpoint: u64,
+    epoch: u64,
+}
+
+/// Information about the start of an epoch, needed to execute transactions in it.
+#[derive(Clone, Copy, Debug)]
+pub struct EpochInfo {
+    pub start_timestamp_ms: u64,
+    pub reference_gas_price: u64,
+    pub protocol_version: u64,
+}
+
+/// What is available in the checkpoint archive, built by scanning it once on startup. Object
+/// versions are indexed separately, and lazily (see [ObjectIndex]).
+#[derive(Default)]
+struct ArchiveIndex {
+    checkpoints: BTreeMap<u64, PathBuf>,
+    transactions: HashMap<TransactionDigest, u64>,
+    /// For each system package, the versions it was at, keyed by the first epoch that version
+    /// was in effect for.
+    system_packages: BTreeMap<ObjectID, BTreeMap<u64, SequenceNumber>>,
+    epochs: BTreeMap<u64, EpochInfo>,
+    chain_id: Option<String>,
+}
+
+/// Where versions of objects are in the archive. Built lazily, a checkpoint at a time, from the
+/// newest checkpoint down, as far as lookups need it: Versions of an object only increase from one
+/// checkpoint to the next, so lookups for recent versions only need to index the most recent
+/// checkpoints. Only lookups for versions that are not in the archive at all (e.g. because they
+/// are read from the snapshot) need to index all of it.
+#[derive(Default)]
+struct ObjectIndex {
+    objects: BTreeMap<(ObjectID, SequenceNumber), ObjectLocation>,
+    /// All checkpoints at or above this one have been indexed, if any have.
+    indexed_from: Option<u64>,
+}
+
+/// Fetches data from a directory of checkpoint files (as produced by `sui-data-ingestion`),
+/// optionally backed by the database restored from a formal snapshot, for objects that were last
+/// modified before the first checkpoint in the archive. Does not access the network.
+#[derive(Clone)]
+pub struct CheckpointFetcher {
+    index: Arc<ArchiveIndex>,
+    objects: Arc<RwLock<ObjectIndex>>,
+    snapshot: Option<Arc<AuthorityPerpetualTables>>,
+    snapshot_epoch: Option<(u64, EpochInfo)>,
+    chain_id: String,
+    cache: Arc<RwLock<LruCache<u64, Arc<CheckpointData>>>>,
+}
+
+impl ObjectLocation {
+    fn new(checkpoint: &CheckpointData) -> Self {
+        Self {
+            checkpoint: checkpoint.checkpoint_summary.sequence_number,
+            epoch: checkpoint.checkpoint_summary.epoch,
+        }
+    }
+}
+
+impl ArchiveIndex {
+    fn add_checkpoint(&mut self, path: PathBuf, checkpoint: &CheckpointData) {
+        let location = ObjectLocation::new(checkpoint);
+        self.checkpoints.insert(location.checkpoint, path);
+
+        if location.checkpoint == 0 {
+            let digest = *checkpoint.checkpoint_summary.digest();
+            self.chain_id = Some(ChainIdentifier::from(digest).to_string());
+        }
+
+        for tx in &checkpoint.transactions {
+            self.transactions
+                .insert(*tx.transaction.digest(), location.checkpoint);
+
+            for obj in &tx.input_objects {
+                self.add_system_package(obj, location.epoch);
+            }
+
+            // System packages written in an epoch (by its change epoch transaction) take effect
+            // from the next one.
+            for obj in &tx.output_objects {
+                self.add_system_package(obj, location.epoch + 1);
+            }
+
+            if let Some((epoch, info)) = epoch_info(tx) {
+                self.epochs.insert(epoch, info);
+            }
+        }
+    }
+
+    fn add_system_package(&mut self, obj: &Object, effective_epoch: u64) {
+        if obj.is_system_package() {
+            let versions = self.system_packages.entry(obj.id()).or_default();
+            let from = versions.entry(effective_epoch).or_insert(obj.version());
+            *from = (*from).max(obj.version());
+        }
+    }
+}
+
+impl ObjectIndex {
+    /// Index the objects in `checkpoint`, which must be the next checkpoint below those that have
+    /// already been indexed.
+    fn add_checkpoint(&mut self, checkpoint: &CheckpointData) {
+        let location = ObjectLocation::new(checkpoint);
+        for tx in &checkpoint.transactions {
+            // Checkpoints are indexed from newest to oldest, so overwriting earlier entries keeps
+            // the earliest sighting, which is where the version was created (if the archive covers
+            // it).
+            for obj in tx.input_objects.iter().chain(tx.output_objects.iter()) {
+                self.objects.insert((obj.id(), obj.version()), location);
+            }
+        }
+
+        self.indexed_from = Some(location.checkpoint);
+    }
+
+    fn location(&self, id: ObjectID, version: SequenceNumber) -> Option<ObjectLocation> {
+        self.objects.get(&(id, version)).copied()
+    }
+
+    /// The highest indexed version of `id` that is at most `bound`.
+    fn latest_version(&self, id: ObjectID, bound: SequenceNumber) -> Option<SequenceNumber> {
+        self.objects
+            .range((id, SequenceNumber::MIN)..=(id, bound))
+            .next_back()
+            .map(|((_, version), _)| *version)
+    }
+}
+
+impl CheckpointFetcher {
+    /// Index the checkpoint files in `checkpoints_dir`, and open the formal snapshot database at
+    /// `snapshot_dir`, if provided. `chain` is used to identify the chain when the archive does
+    /// not start from genesis.
+    pub fn new(
+        checkpoints_dir: &Path,
+        snapshot_dir: Option<&Path>,
+        chain: Option<Chain>,
+    ) -> Result<Self, ReplayEngineError> {
+        let mut index = ArchiveIndex::default();
+        for entry in
+            std::fs::read_dir(checkpoints_dir).map_err(|e| ReplayEngineError::GeneralError {
+                err: format!(
+                    "Unable to read checkpoints dir {}: {e}",
+                    checkpoints_dir.display()
+                ),
+            })?
+        {
+            let path = entry
+                .map_err(|e| ReplayEngineError::GeneralError { err: e.to_string() })?
+                .path();
+            if path.extension().and_then(|e| e.to_str()) != Some(CHECKPOINT_FILE_EXTENSION) {
+                continue;
+            }
+
+            let checkpoint = read_checkpoint(&path)?;
+            index.add_checkpoint(path, &checkpoint);
+        }
+
+        info!(
+            "Indexed {} checkpoints with {} transactions from {}",
+            index.checkpoints.len(),
+            index.transactions.len(),
+            checkpoints_dir.display()
+        );
+
+        let snapshot = snapshot_dir.map(|dir| Arc::new(AuthorityPerpetualTables::open(dir, None)));
+        let snapshot_epoch = match &snapshot {
+            Some(tables) => {
+                let state = get_sui_system_state(tables.as_ref() as &dyn ObjectStore)?;
+                Some((
+                    state.epoch(),
+                    EpochInfo {
+                        start_timestamp_ms: state.epoch_start_timestamp_ms(),
+                        reference_gas_price: state.reference_gas_price(),
+                        protocol_version: state.protocol_version(),
+                    },
+                ))
+            }
+            None => None,
+        };
+
+        let chain_id = match (index.chain_id.clone(), chain) {
+            (Some(chain_id), _) => chain_id,
+            (None, Some(Chain::Mainnet)) => get_mainnet_chain_identifier().to_string(),
+            (None, Some(Chain::Testnet)) => get_testnet_chain_identifier().to_string(),
+            (None, _) => {
+                return Err(ReplayEngineError::UnableToGetChainId {
+                    err: "Checkpoint archive does not start from genesis, and no chain was \
+                        specified"
+                        .to_string(),
+                })
+            }
+        };
+
+        Ok(Self {
+            index: Arc::new(index),
+            objects: Arc::new(RwLock::new(ObjectIndex::default())),
+            snapshot,
+            snapshot_epoch,
+            chain_id,
+            cache: Arc::new(RwLock::new(LruCache::new(
+                CHECKPOINT_CACHE_CAPACITY.expect("Cache size must be non zero"),
+            ))),
+        })
+    }
+
+    /// Load a checkpoint from the archive.
+    pub fn get_checkpoint(
+        &self,
+        checkpoint: u64,
+    ) -> Result<Arc<CheckpointData>, ReplayEngineError> {
+        if let Some(data) = self.cache.write().get(&checkpoint) {
+            return Ok(data.clone());
+        }
+
+        let path = self
+            .index
+            .checkpoints
+            .get(&checkpoint)
+            .ok_or(ReplayEngineError::CheckpointNotFound { checkpoint })?;
+        let data = Arc::new(read_checkpoint(path)?);
+        self.cache.write().put(checkpoint, data.clone());
+        Ok(data)
+    }
+
+    /// The transaction with digest `digest`, its effects, and the objects it read and wrote, from
+    /// the checkpoint archive.
+    pub fn get_checkpoint_transaction(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<CheckpointTransaction, ReplayEngineError> {
+        let checkpoint = *self
+            .index
+            .transactions
+            .get(digest)
+            .ok_or(ReplayEngineError::TransactionNotFound { digest: *digest })?;
+
+        self.get_checkpoint(checkpoint)?
+            .transactions
+            .iter()
+            .find(|tx| tx.transaction.digest() == digest)
+            .cloned()
+            .ok_or(ReplayEngineError::TransactionNotFound { digest: *digest })
+    }
+
+    /// Information about the start of `epoch`, from its change epoch transaction in the archive,
+    /// or from the snapshot, if it was taken in that epoch.
+    pub fn epoch_info(&self, epoch: u64) -> Option<EpochInfo> {
+        self.index.epochs.get(&epoch).copied().or_else(|| {
+            self.snapshot_epoch
+                .filter(|(e, _)| *e == epoch)
+                .map(|(_, info)| info)
+        })
+    }
+
+    pub fn protocol_version(&self, epoch: u64) -> Result<u64, ReplayEngineError> {
+        self.epoch_info(epoch)
+            .map(|info| info.protocol_version)
+            .ok_or(ReplayEngineError::ProtocolVersionNotFound { epoch })
+    }
+
+    /// The versions of the system packages in effect at `protocol_version`, based on the last epoch
+    /// at that protocol version. Packages that were not upgraded in the archive are taken from the
+    /// snapshot.
+    pub fn system_package_versions(
+        &self,
+        protocol_version: u64,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+        let epoch = self
+            .index
+            .epochs
+            .iter()
+            .map(|(epoch, info)| (*epoch, info.protocol_version))
+            .chain(
+                self.snapshot_epoch
+                    .map(|(e, info)| (e, info.protocol_version)),
+            )
+            .filter(|(_, version)| *version == protocol_version)
+            .map(|(epoch, _)| epoch)
+            .max()
+            .ok_or(ReplayEngineError::FrameworkObjectVersionTableNotPopulated {
+                protocol_version,
+            })?;
+
+        let mut versions = vec![];
+        for id in sui_framework::BuiltInFramework::all_package_ids() {
+            let archived = self
+                .index
+                .system_packages
+                .get(&id)
+                .and_then(|v| v.range(..=epoch).next_back())
+                .map(|(_, version)| *version);
+
+            let version = match (archived, &self.snapshot) {
+                (Some(version), _) => Some(version),
+                (None, Some(tables)) => tables.get_object_fallible(&id)?.map(|o| o.version()),
+                (None, None) => None,
+            };
+
+            if let Some(version) = version {
+                versions.push((id, version));
+            }
+        }
+
+        Ok(versions)
+    }
+
+    /// Index the objects in the next checkpoint that has not been indexed yet, returning `false`
+    /// if the whole archive has already been indexed.
+    fn index_next_checkpoint(&self) -> Result<bool, ReplayEngineError> {
+        let mut objects = self.objects.write();
+        let next = match objects.indexed_from {
+            Some(from) => self.index.checkpoints.range(..from).next_back(),
+            None => self.index.checkpoints.iter().next_back(),
+        };
+
+        let Some((_, path)) = next else {
+            return Ok(false);
+        };
+
+        // Read the checkpoint directly, rather than through the cache, to avoid evicting the
+        // checkpoints being replayed.
+        objects.add_checkpoint(&read_checkpoint(path)?);
+        Ok(true)
+    }
+
+    /// Where version `version` of `id` is in the archive, indexing more of it if necessary.
+    fn object_location(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<ObjectLocation>, ReplayEngineError> {
+        loop {
+            if let Some(location) = self.objects.read().location(id, version) {
+                return Ok(Some(location));
+            }
+
+            if !self.index_next_checkpoint()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// The highest version of `id` in the archive that is at most `bound`, indexing more of it if
+    /// necessary. Versions in checkpoints that have not been indexed yet are all lower than the
+    /// versions in the checkpoints that have, so the first version found is the latest.
+    fn latest_version(
+        &self,
+        id: ObjectID,
+        bound: SequenceNumber,
+    ) -> Result<Option<SequenceNumber>, ReplayEngineError> {
+        loop {
+            if let Some(version) = self.objects.read().latest_version(id, bound) {
+                return Ok(Some(version));
+            }
+
+            if !self.index_next_checkpoint()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    fn get_archived_object(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        let Some(location) = self.object_location(id, version)? else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .get_checkpoint(location.checkpoint)?
+            .transactions
+            .iter()
+            .flat_map(|tx| tx.input_objects.iter().chain(tx.output_objects.iter()))
+            .find(|o| o.id() == id && o.version() == version)
+            .cloned())
+    }
+
+    fn get_object_at_or_before(
+        &self,
+        id: ObjectID,
+        bound: SequenceNumber,
+    ) -> Result<Option<Object>, ReplayEngineError> {
+        if let Some(version) = self.latest_version(id, bound)? {
+            return self.get_archived_object(id, version);
+        }
+
+        match &self.snapshot {
+            Some(tables) => Ok(tables.find_object_lt_or_eq_version(id, bound)?),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl DataFetcher for CheckpointFetcher {
+    async fn multi_get_versioned(
+        &self,
+        objects: &[(ObjectID, SequenceNumber)],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        let mut resp = vec![];
+        for (id, version) in objects {
+            let obj = match self.get_archived_object(*id, *version)? {
+                Some(obj) => Some(obj),
+                None => match &self.snapshot {
+                    Some(tables) => tables.get_object_by_key_fallible(id, *version)?,
+                    None => None,
+                },
+            };
+
+            resp.push(obj.ok_or(ReplayEngineError::ObjectVersionNotFound {
+                id: *id,
+                version: *version,
+            })?);
+        }
+        Ok(resp)
+    }
+
+    async fn multi_get_latest(
+        &self,
+        objects: &[ObjectID],
+    ) -> Result<Vec<Object>, ReplayEngineError> {
+        let mut resp = vec![];
+        for id in objects {
+            let obj = self
+                .get_object_at_or_before(*id, SequenceNumber::MAX)?
+                .ok_or(ReplayEngineError::ObjectNotExist { id: *id })?;
+            resp.push(obj);
+        }
+        Ok(resp)
+    }
+
+    async fn get_checkpoint_txs(
+        &self,
+        id: u64,
+    ) -> Result<Vec<TransactionDigest>, ReplayEngineError> {
+        Ok(self
+            .get_checkpoint(id)?
+            .transactions
+            .iter()
+            .map(|tx| *tx.transaction.digest())
+            .collect())
+    }
+
+    async fn get_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, ReplayEngineError> {
+        Err(ReplayEngineError::GeneralError {
+            err: format!(
+                "Transaction {tx_digest} can only be read from the checkpoint archive as a \
+                CheckpointTransaction"
+            ),
+        })
+    }
+
+    async fn get_loaded_child_objects(
+        &self,
+        _tx_digest: &TransactionDigest,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>, ReplayEngineError> {
+        // Child objects are read on demand, with `get_child_object`.
+        Ok(vec![])
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, ReplayEngineError> {
+        self.index
+            .checkpoints
+            .keys()
+            .next_back()
+            .copied()
+            .ok_or(ReplayEngineError::GeneralError {
+                err: "Checkpoint archive is empty".to_string(),
+            })
+    }
+
+    async fn fetch_random_transaction(
+        &self,
+        checkpoint_id_start: Option<u64>,
+        checkpoint_id_end: Option<u64>,
+    ) -> Result<TransactionDigest, ReplayEngineError> {
+        let start = checkpoint_id_start.unwrap_or(0);
+        let end = checkpoint_id_end.unwrap_or(u64::MAX);
+        self.index
+            .transactions
+            .iter()
+            .filter(|(_, checkpoint)| (start..=end).contains(*checkpoint))
+            .map(|(digest, _)| *digest)
+            .choose(&mut rand::thread_rng())
+            .ok_or(ReplayEngineError::GeneralError {
+                err: format!("No transactions in checkpoints {start} to {end}"),
+            })
+    }
+
+    async fn get_epoch_start_timestamp_and_rgp(
+        &self,
+        epoch_id: u64,
+    ) -> Result<(u64, u64), ReplayEngineError> {
+        self.epoch_info(epoch_id)
+            .map(|info| (info.start_timestamp_ms, info.reference_gas_price))
+            .ok_or(ReplayEngineError::EventNotFound { epoch: epoch_id })
+    }
+
+    async fn get_epoch_change_events(
+        &self,
+        _reverse: bool,
+    ) -> Result<Vec<SuiEvent>, ReplayEngineError> {
+        Err(ReplayEngineError::UnableToQuerySystemEvents {
+            rpc_err: "Epoch change events are not available from the checkpoint archive"
+                .to_string(),
+        })
+    }
+
+    async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
+        Ok(self.chain_id.clone())
+    }
+
+    async fn get_child_object(
+        &self,
+        object_id: &ObjectID,
+        version_upper_bound: VersionNumber,
+    ) -> Result<Object, ReplayEngineError> {
+        self.get_object_at_or_before(*object_id, version_upper_bound)?
+            .ok_or(ReplayEngineError::ObjectNotExist { id: *object_id })
+    }
+}
+
+fn read_checkpoint(path: &Path) -> Result<CheckpointData, ReplayEngineError> {
+    let bytes = std::fs::read(path).map_err(|e| ReplayEngineError::GeneralError {
+        err: format!("Unable to read checkpoint file {}: {e}", path.display()),
+    })?;
+    Ok(Blob::from_bytes::<CheckpointData>(&bytes)?)
+}
+
+/// If `tx` started an epoch, the epoch it started, and information about it.
+fn epoch_info(tx: &CheckpointTransaction) -> Option<(u64, EpochInfo)> {
+    let change: &ChangeEpoch = match tx.transaction.transaction_data().kind() {
+        TransactionKind::ChangeEpoch(change) => change,
+        TransactionKind::EndOfEpochTransaction(kinds) => kinds.iter().find_map(|k| match k {
+            EndOfEpochTransactionKind::ChangeEpoch(change) => Some(change),
+            _ => None,
+        })?,
+        _ => return None,
+    };
+
+    // The reference gas price for the new epoch is only available from the event (which is not
+    // emitted if the epoch change happened in safe mode).
+    let reference_gas_price = tx
+        .events
+        .iter()
+        .flat_map(|events| events.data.iter())
+        .find(|event| event.is_system_epoch_info_event())
+        .and_then(|event| bcs::from_bytes::<SystemEpochInfoEvent>(&event.contents).ok())
+        .map(|event| event.reference_gas_price)
+        .unwrap_or_default();
+
+    Some((
+        change.epoch,
+        EpochInfo {
+            start_timestamp_ms: change.epoch_start_timestamp_ms,
+            reference_gas_price,
+            protocol_version: change.protocol_version.as_u64(),
+        },
+    ))
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::checkpoint_fetcher::CheckpointFetcher;
 use crate::types::ReplayEngineError;
 use crate::types::EPOCH_CHANGE_STRUCT_TAG;
 use async_trait::async_trait;
@@ -96,13 +97,16 @@ pub(crate) trait DataFetcher {
 pub enum Fetchers {
     Remote(RemoteFetcher),
     NodeStateDump(NodeStateDumpFetcher),
+    Checkpoint(CheckpointFetcher),
 }
 
 impl Fetchers {
     pub fn as_remote(&self) -> &RemoteFetcher {
         match self {
             Fetchers::Remote(q) => q,
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::Checkpoint(_) => {
+                panic!("not a remote fetcher")
+            }
         }
     }
 
@@ -114,16 +118,29 @@ impl Fetchers {
                 q.clear_cache_for_new_task();
                 q
             }
-            Fetchers::NodeStateDump(_) => panic!("not a remote fetcher"),
+            Fetchers::NodeStateDump(_) | Fetchers::Checkpoint(_) => {
+                panic!("not a remote fetcher")
+            }
         }
     }
 
     pub fn as_node_state_dump(&self) -> &NodeStateDumpFetcher {
         match self {
-            Fetchers::Remote(_) => panic!("not a node state dump fetcher"),
+            Fetchers::Remote(_) | Fetchers::Checkpoint(_) => {
+                panic!("not a node state dump fetcher")
+            }
             Fetchers::NodeStateDump(q) => q,
         }
     }
+
+    pub fn as_checkpoint(&self) -> &CheckpointFetcher {
+        match self {
+            Fetchers::Remote(_) | Fetchers::NodeStateDump(_) => {
+                panic!("not a checkpoint fetcher")
+            }
+            Fetchers::Checkpoint(q) => q,
+        }
+    }
 }
 
 #[async_trait]
@@ -136,6 +153,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_versioned(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_versioned(objects).await,
+            Fetchers::Checkpoint(q) => q.multi_get_versioned(objects).await,
         }
     }
 
@@ -146,6 +164,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.multi_get_latest(objects).await,
             Fetchers::NodeStateDump(q) => q.multi_get_latest(objects).await,
+            Fetchers::Checkpoint(q) => q.multi_get_latest(objects).await,
         }
     }
 
@@ -156,6 +175,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_checkpoint_txs(id).await,
             Fetchers::NodeStateDump(q) => q.get_checkpoint_txs(id).await,
+            Fetchers::Checkpoint(q) => q.get_checkpoint_txs(id).await,
         }
     }
 
@@ -166,6 +186,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_transaction(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_transaction(tx_digest).await,
+            Fetchers::Checkpoint(q) => q.get_transaction(tx_digest).await,
         }
     }
 
@@ -176,6 +197,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_loaded_child_objects(tx_digest).await,
             Fetchers::NodeStateDump(q) => q.get_loaded_child_objects(tx_digest).await,
+            Fetchers::Checkpoint(q) => q.get_loaded_child_objects(tx_digest).await,
         }
     }
 
@@ -183,6 +205,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_latest_checkpoint_sequence_number().await,
             Fetchers::NodeStateDump(q) => q.get_latest_checkpoint_sequence_number().await,
+            Fetchers::Checkpoint(q) => q.get_latest_checkpoint_sequence_number().await,
         }
     }
 
@@ -200,6 +223,10 @@ impl DataFetcher for Fetchers {
                 q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
                     .await
             }
+            Fetchers::Checkpoint(q) => {
+                q.fetch_random_transaction(checkpoint_id_start, checkpoint_id_end)
+                    .await
+            }
         }
     }
 
@@ -210,6 +237,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
+            Fetchers::Checkpoint(q) => q.get_epoch_start_timestamp_and_rgp(epoch_id).await,
         }
     }
 
@@ -220,12 +248,14 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_epoch_change_events(reverse).await,
             Fetchers::NodeStateDump(q) => q.get_epoch_change_events(reverse).await,
+            Fetchers::Checkpoint(q) => q.get_epoch_change_events(reverse).await,
         }
     }
     async fn get_chain_id(&self) -> Result<String, ReplayEngineError> {
         match self {
             Fetchers::Remote(q) => q.get_chain_id().await,
             Fetchers::NodeStateDump(q) => q.get_chain_id().await,
+            Fetchers::Checkpoint(q) => q.get_chain_id().await,
         }
     }
     async fn get_child_object(
@@ -236,6 +266,7 @@ impl DataFetcher for Fetchers {
         match self {
             Fetchers::Remote(q) => q.get_child_object(object_id, version_upper_bound).await,
             Fetchers::NodeStateDump(q) => q.get_child_object(object_id, version_upper_bound).await,
+            Fetchers::Checkpoint(q) => q.get_child_object(object_id, version_upper_bound).await,
         }
     }
 }
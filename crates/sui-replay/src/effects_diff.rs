@@ -0,0 +1,514 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A structured, field by field comparison of the effects of a transaction executed on chain
+//! against the effects of replaying it locally, to explain why they diverged.
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
+use sui_json_rpc_types::{OwnedObjectRef, SuiObjectRef, SuiTransactionBlockEffects};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::{ObjectDigest, TransactionDigest};
+use sui_types::event::Event;
+use sui_types::object::{Data, Object, Owner};
+
+/// How to render an [`EffectsDiff`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum DiffFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+/// A value that differs between the on-chain and local execution.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: String,
+    pub on_chain: String,
+    pub local: String,
+}
+
+/// How an object was affected by the transaction.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectChangeKind {
+    Created,
+    Mutated,
+    Unwrapped,
+    Deleted,
+    Wrapped,
+    UnwrappedThenDeleted,
+}
+
+/// What one execution did to an object.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ObjectEffect {
+    pub change: ObjectChangeKind,
+    /// The version of the object before the transaction, if it was an input.
+    pub input_version: Option<SequenceNumber>,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    /// The owner of the object after the transaction, if it still exists.
+    pub owner: Option<Owner>,
+}
+
+/// An object that was affected differently by the on-chain and local executions. `on_chain` or
+/// `local` is `None` if only the other execution touched the object.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ObjectDiff {
+    pub object_id: ObjectID,
+    pub on_chain: Option<ObjectEffect>,
+    pub local: Option<ObjectEffect>,
+    /// Differences in the contents of the object written by each execution, if both are known.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content: Vec<FieldDiff>,
+}
+
+/// An event that differs between the executions, by its position in the transaction's events.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventDiff {
+    pub index: usize,
+    pub fields: Vec<FieldDiff>,
+}
+
+/// All the ways in which the effects of the on-chain and local executions of a transaction differ.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EffectsDiff {
+    pub tx_digest: TransactionDigest,
+    /// Differences in the transaction-wide fields of the effects: status, gas, epoch, etc.
+    pub fields: Vec<FieldDiff>,
+    pub objects: Vec<ObjectDiff>,
+    /// Differences in the events emitted, if the events from both executions are known.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EventDiff>,
+}
+
+impl FieldDiff {
+    fn new(field: impl Into<String>, on_chain: impl Display, local: impl Display) -> Self {
+        Self {
+            field: field.into(),
+            on_chain: on_chain.to_string(),
+            local: local.to_string(),
+        }
+    }
+
+    /// A diff for `field`, if `on_chain` and `local` differ.
+    fn compare<T: PartialEq + Display>(field: &str, on_chain: T, local: T) -> Option<Self> {
+        (on_chain != local).then(|| Self::new(field, on_chain, local))
+    }
+}
+
+impl EffectsDiff {
+    /// Compare the effects of the on-chain and local executions.
+    pub fn new(on_chain: &SuiTransactionBlockEffects, local: &SuiTransactionBlockEffects) -> Self {
+        let (on_chain_gas, local_gas) = (on_chain.gas_cost_summary(), local.gas_cost_summary());
+
+        let fields = [
+            FieldDiff::compare("status", on_chain.status(), local.status()),
+            FieldDiff::compare(
+                "executed_epoch",
+                on_chain.executed_epoch(),
+                local.executed_epoch(),
+            ),
+            FieldDiff::compare(
+                "gas_used.computation_cost",
+                on_chain_gas.computation_cost,
+                local_gas.computation_cost,
+            ),
+            FieldDiff::compare(
+                "gas_used.storage_cost",
+                on_chain_gas.storage_cost,
+                local_gas.storage_cost,
+            ),
+            FieldDiff::compare(
+                "gas_used.storage_rebate",
+                on_chain_gas.storage_rebate,
+                local_gas.storage_rebate,
+            ),
+            FieldDiff::compare(
+                "gas_used.non_refundable_storage_fee",
+                on_chain_gas.non_refundable_storage_fee,
+                local_gas.non_refundable_storage_fee,
+            ),
+            FieldDiff::compare(
+                "gas_object",
+                on_chain.gas_object().reference.object_id,
+                local.gas_object().reference.object_id,
+            ),
+            FieldDiff::compare(
+                "events_digest",
+                display_option(on_chain.events_digest()),
+                display_option(local.events_digest()),
+            ),
+            FieldDiff::compare(
+                "dependencies",
+                display_set(on_chain.dependencies()),
+                display_set(local.dependencies()),
+            ),
+            FieldDiff::compare(
+                "shared_objects",
+                display_set(on_chain.shared_objects().iter().map(display_ref)),
+                display_set(local.shared_objects().iter().map(display_ref)),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let on_chain_objects = object_effects(on_chain);
+        let mut local_objects = object_effects(local);
+        let mut objects = vec![];
+        for (id, on_chain) in on_chain_objects {
+            let local = local_objects.remove(&id);
+            if local.as_ref() != Some(&on_chain) {
+                objects.push(ObjectDiff {
+                    object_id: id,
+                    on_chain: Some(on_chain),
+                    local,
+                    content: vec![],
+                });
+            }
+        }
+        objects.extend(local_objects.into_iter().map(|(id, local)| ObjectDiff {
+            object_id: id,
+            on_chain: None,
+            local: Some(local),
+            content: vec![],
+        }));
+        objects.sort_by_key(|o| o.object_id);
+
+        Self {
+            tx_digest: *on_chain.transaction_digest(),
+            fields,
+            objects,
+            events: vec![],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.objects.is_empty() && self.events.is_empty()
+    }
+
+    /// The objects whose contents are worth comparing: those written by both executions, but that
+    /// ended up with different digests.
+    pub fn objects_to_compare(&self) -> Vec<(ObjectID, SequenceNumber)> {
+        self.objects
+            .iter()
+            .filter_map(|o| {
+                let (on_chain, local) = (o.on_chain.as_ref()?, o.local.as_ref()?);
+                (on_chain.owner.is_some()
+                    && local.owner.is_some()
+                    && on_chain.digest != local.digest)
+                    .then_some((o.object_id, on_chain.version))
+            })
+            .collect()
+    }
+
+    /// Compare the contents of objects written by both executions, as listed by
+    /// `objects_to_compare`.
+    pub fn with_object_contents(
+        mut self,
+        on_chain: &BTreeMap<ObjectID, Object>,
+        local: &BTreeMap<ObjectID, Object>,
+    ) -> Self {
+        for diff in &mut self.objects {
+            if let (Some(on_chain), Some(local)) =
+                (on_chain.get(&diff.object_id), local.get(&diff.object_id))
+            {
+                diff.content = diff_objects(on_chain, local);
+            }
+        }
+        self
+    }
+
+    /// Compare the events emitted by each execution.
+    pub fn with_events(mut self, on_chain: &[Event], local: &[Event]) -> Self {
+        let len = on_chain.len().max(local.len());
+        self.events = (0..len)
+            .filter_map(|index| {
+                let fields = diff_events(on_chain.get(index), local.get(index));
+                (!fields.is_empty()).then_some(EventDiff { index, fields })
+            })
+            .collect();
+        self
+    }
+
+    pub fn render(&self, format: DiffFormat) -> String {
+        match format {
+            DiffFormat::Human => self.to_string(),
+            DiffFormat::Json => {
+                serde_json::to_string_pretty(self).expect("Serializing effects diff cannot fail")
+            }
+        }
+    }
+}
+
+impl Display for EffectsDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Effects for {} match", self.tx_digest);
+        }
+
+        writeln!(
+            f,
+            "Effects for {} diverged (on-chain -> local)",
+            self.tx_digest
+        )?;
+        for field in &self.fields {
+            writeln!(f, "  {field}")?;
+        }
+
+        if !self.objects.is_empty() {
+            writeln!(f, "Objects:")?;
+        }
+        for object in &self.objects {
+            writeln!(
+                f,
+                "  {}: {} -> {}",
+                object.object_id,
+                display_effect(object.on_chain.as_ref()),
+                display_effect(object.local.as_ref()),
+            )?;
+            for field in &object.content {
+                writeln!(f, "    {field}")?;
+            }
+        }
+
+        if !self.events.is_empty() {
+            writeln!(f, "Events:")?;
+        }
+        for event in &self.events {
+            writeln!(f, "  #{}:", event.index)?;
+            for field in &event.fields {
+                writeln!(f, "    {field}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.on_chain, self.local)
+    }
+}
+
+impl Display for ObjectChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ObjectChangeKind::Created => "created",
+            ObjectChangeKind::Mutated => "mutated",
+            ObjectChangeKind::Unwrapped => "unwrapped",
+            ObjectChangeKind::Deleted => "deleted",
+            ObjectChangeKind::Wrapped => "wrapped",
+            ObjectChangeKind::UnwrappedThenDeleted => "unwrapped then deleted",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+fn display_effect(effect: Option<&ObjectEffect>) -> String {
+    let Some(effect) = effect else {
+        return "untouched".to_string();
+    };
+
+    let mut s = effect.change.to_string();
+    if let Some(input) = effect.input_version {
+        s.push_str(&format!(" from v{}", input.value()));
+    }
+    s.push_str(&format!(
+        " at v{} ({})",
+        effect.version.value(),
+        effect.digest
+    ));
+    if let Some(owner) = &effect.owner {
+        s.push_str(&format!(" owned by {owner}"));
+    }
+    s
+}
+
+fn display_option<T: Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "none".to_string(), |v| v.to_string())
+}
+
+fn display_ref(r: &SuiObjectRef) -> String {
+    format!("{}@{}", r.object_id, r.version.value())
+}
+
+/// Render a collection as a sorted list, so that ordering differences do not count as a diff.
+fn display_set<T: Display>(values: impl IntoIterator<Item = T>) -> String {
+    let values: BTreeSet<_> = values.into_iter().map(|v| v.to_string()).collect();
+    format!("[{}]", values.into_iter().collect::<Vec<_>>().join(", "))
+}
+
+/// Everything `effects` did to each object.
+fn object_effects(effects: &SuiTransactionBlockEffects) -> BTreeMap<ObjectID, ObjectEffect> {
+    let inputs: BTreeMap<_, _> = effects.modified_at_versions().into_iter().collect();
+    let mut objects = BTreeMap::new();
+
+    let written = [
+        (ObjectChangeKind::Created, effects.created()),
+        (ObjectChangeKind::Mutated, effects.mutated()),
+        (ObjectChangeKind::Unwrapped, effects.unwrapped()),
+    ];
+    for (change, refs) in written {
+        for OwnedObjectRef { owner, reference } in refs {
+            objects.insert(
+                reference.object_id,
+                ObjectEffect {
+                    change,
+                    input_version: inputs.get(&reference.object_id).copied(),
+                    version: reference.version,
+                    digest: reference.digest,
+                    owner: Some(owner.clone()),
+                },
+            );
+        }
+    }
+
+    let removed = [
+        (ObjectChangeKind::Deleted, effects.deleted()),
+        (ObjectChangeKind::Wrapped, effects.wrapped()),
+        (
+            ObjectChangeKind::UnwrappedThenDeleted,
+            effects.unwrapped_then_deleted(),
+        ),
+    ];
+    for (change, refs) in removed {
+        for reference in refs {
+            objects.insert(
+                reference.object_id,
+                ObjectEffect {
+                    change,
+                    input_version: inputs.get(&reference.object_id).copied(),
+                    version: reference.version,
+                    digest: reference.digest,
+                    owner: None,
+                },
+            );
+        }
+    }
+
+    objects
+}
+
+fn diff_objects(on_chain: &Object, local: &Object) -> Vec<FieldDiff> {
+    let mut diffs: Vec<_> = [
+        FieldDiff::compare("version", on_chain.version(), local.version()),
+        FieldDiff::compare("owner", &on_chain.owner, &local.owner),
+        FieldDiff::compare(
+            "previous_transaction",
+            on_chain.previous_transaction,
+            local.previous_transaction,
+        ),
+        FieldDiff::compare(
+            "storage_rebate",
+            on_chain.storage_rebate,
+            local.storage_rebate,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    match (&on_chain.data, &local.data) {
+        (Data::Move(on_chain), Data::Move(local)) => {
+            diffs.extend(FieldDiff::compare("type", on_chain.type_(), local.type_()));
+            diffs.extend(diff_bytes(
+                "contents",
+                on_chain.contents(),
+                local.contents(),
+            ));
+        }
+
+        (Data::Package(on_chain), Data::Package(local)) => {
+            let (on_chain, local) = (
+                on_chain.serialized_module_map(),
+                local.serialized_module_map(),
+            );
+            let modules: BTreeSet<_> = on_chain.keys().chain(local.keys()).collect();
+            for module in modules {
+                let field = format!("module {module}");
+                match (on_chain.get(module), local.get(module)) {
+                    (Some(a), Some(b)) => diffs.extend(diff_bytes(&field, a, b)),
+                    (a, b) => diffs.push(FieldDiff::new(
+                        field,
+                        if a.is_some() { "present" } else { "missing" },
+                        if b.is_some() { "present" } else { "missing" },
+                    )),
+                }
+            }
+        }
+
+        (on_chain, local) => diffs.push(FieldDiff::new(
+            "kind",
+            data_kind(on_chain),
+            data_kind(local),
+        )),
+    }
+
+    diffs
+}
+
+fn data_kind(data: &Data) -> &'static str {
+    match data {
+        Data::Move(_) => "move object",
+        Data::Package(_) => "package",
+    }
+}
+
+/// Compare two BCS encoded values, showing them from the first byte at which they differ.
+fn diff_bytes(field: &str, on_chain: &[u8], local: &[u8]) -> Option<FieldDiff> {
+    if on_chain == local {
+        return None;
+    }
+
+    let offset = on_chain
+        .iter()
+        .zip(local)
+        .position(|(a, b)| a != b)
+        .unwrap_or(on_chain.len().min(local.len()));
+
+    Some(FieldDiff::new(
+        format!(
+            "{field}[{offset}..] ({} vs {} bytes)",
+            on_chain.len(),
+            local.len()
+        ),
+        hex(&on_chain[offset..]),
+        hex(&local[offset..]),
+    ))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn diff_events(on_chain: Option<&Event>, local: Option<&Event>) -> Vec<FieldDiff> {
+    let (on_chain, local) = match (on_chain, local) {
+        (Some(on_chain), Some(local)) => (on_chain, local),
+        (on_chain, local) => {
+            let describe = |e: Option<&Event>| display_option(e.map(|e| e.type_.to_string()));
+            return vec![FieldDiff::new("event", describe(on_chain), describe(local))];
+        }
+    };
+
+    [
+        FieldDiff::compare("type", &on_chain.type_, &local.type_),
+        FieldDiff::compare("sender", on_chain.sender, local.sender),
+        FieldDiff::compare("package_id", on_chain.package_id, local.package_id),
+        FieldDiff::compare(
+            "transaction_module",
+            &on_chain.transaction_module,
+            &local.transaction_module,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .chain(diff_bytes("contents", &on_chain.contents, &local.contents))
+    .collect()
+}
@@ -16,10 +16,13 @@ use sui_types::message_envelope::Message;
 use tracing::warn;
 use transaction_provider::{FuzzStartPoint, TransactionSource};
 
+use crate::checkpoint_fetcher::CheckpointFetcher;
 use crate::config::get_rpc_url;
+use crate::effects_diff::DiffFormat;
 use crate::replay::ExecutionSandboxState;
 use crate::replay::LocalExec;
 use crate::replay::ProtocolVersionSummary;
+use crate::types::ReplayEngineError;
 use move_vm_config::runtime::get_default_output_filepath;
 use std::env;
 use std::io::BufRead;
@@ -31,9 +34,11 @@ use sui_types::digests::TransactionDigest;
 use tracing::{error, info};
 
 pub mod batch_replay;
+mod checkpoint_fetcher;
 pub mod config;
 mod data_fetcher;
 mod displays;
+pub mod effects_diff;
 pub mod fuzz;
 pub mod fuzz_mutations;
 mod replay;
@@ -45,6 +50,19 @@ pub mod types;
 static DEFAULT_SANDBOX_BASE_PATH: &str =
     concat!(env!("CARGO_MANIFEST_DIR"), "/tests/sandbox_snapshots");
 
+/// Where to read data from when replaying without access to an RPC node.
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct OfflineArgs {
+    /// Replay from the checkpoint files (as written by `sui-data-ingestion`) in this directory,
+    /// instead of fetching data from an RPC node.
+    #[arg(long)]
+    pub checkpoints_dir: Option<PathBuf>,
+    /// The database restored from a formal snapshot (with `sui-tool download-formal-snapshot`),
+    /// to read objects that were last modified before the first checkpoint in `checkpoints-dir`.
+    #[arg(long, requires = "checkpoints_dir")]
+    pub snapshot_dir: Option<PathBuf>,
+}
+
 #[derive(Parser, Clone)]
 #[command(rename_all = "kebab-case")]
 pub enum ReplayToolCommand {
@@ -108,6 +126,11 @@ pub enum ReplayToolCommand {
         /// denied.
         #[arg(long, num_args = 2..)]
         config_objects: Option<Vec<String>>,
+        /// How to report differences between the local and on-chain effects, if they diverge.
+        #[arg(long, value_enum, default_value_t)]
+        diff_format: DiffFormat,
+        #[command(flatten)]
+        offline: OfflineArgs,
     },
 
     /// Replay transactions listed in a file
@@ -169,6 +192,8 @@ pub enum ReplayToolCommand {
         terminate_early: bool,
         #[arg(long, short, default_value = "16")]
         max_tasks: u64,
+        #[command(flatten)]
+        offline: OfflineArgs,
     },
 
     /// Replay all transactions in an epoch
@@ -383,26 +408,50 @@ pub async fn execute_replay_command(
             executor_version,
             protocol_version,
             config_objects,
+            diff_format,
+            offline,
         } => {
             let tx_digest = TransactionDigest::from_str(&tx_digest)?;
             info!("Executing tx: {}", tx_digest);
-            let sandbox_state = LocalExec::replay_with_network_config(
-                get_rpc_url(rpc_url, cfg_path, chain)?,
-                tx_digest,
-                safety,
-                use_authority,
-                executor_version,
-                protocol_version,
-                None,
-                parse_configs_versions(config_objects),
-            )
-            .await?;
+            let mut lx = match checkpoint_fetcher(&offline, chain.as_deref())? {
+                Some(fetcher) => LocalExec::new_for_checkpoints(fetcher).await?,
+                None => {
+                    let rpc_url = get_rpc_url(rpc_url, cfg_path, chain)?;
+                    info!("Using RPC URL: {}", rpc_url);
+                    LocalExec::new_from_fn_url(&rpc_url)
+                        .await?
+                        .init_for_execution()
+                        .await?
+                }
+            };
+            let sandbox_state = lx
+                .execute_transaction(
+                    &tx_digest,
+                    safety,
+                    use_authority,
+                    executor_version,
+                    protocol_version,
+                    None,
+                    parse_configs_versions(config_objects),
+                )
+                .await?;
 
             if show_effects {
                 println!("{}", sandbox_state.local_exec_effects);
             }
 
-            sandbox_state.check_effects()?;
+            if sandbox_state.transaction_info.effects != sandbox_state.local_exec_effects {
+                error!("Replay tool forked {}", tx_digest);
+                let diff = lx.effects_diff(&sandbox_state).await?;
+                println!("{}", diff.render(diff_format));
+                return Err(ReplayEngineError::EffectsForked {
+                    digest: tx_digest,
+                    diff: format!("\n{}", diff),
+                    on_chain: Box::new(sandbox_state.transaction_info.effects),
+                    local: Box::new(sandbox_state.local_exec_effects),
+                }
+                .into());
+            }
 
             println!("Execution finished successfully. Local and on-chain effects match.");
             Some((1u64, 1u64))
@@ -457,9 +506,11 @@ pub async fn execute_replay_command(
             end,
             terminate_early,
             max_tasks,
+            offline,
         } => {
             assert!(start <= end, "Start checkpoint must be <= end checkpoint");
             assert!(max_tasks > 0, "Max tasks must be > 0");
+            let fetcher = checkpoint_fetcher(&offline, chain.as_deref())?;
             let checkpoints_per_task = ((end - start + max_tasks) / max_tasks) as usize;
             let mut handles = vec![];
             info!(
@@ -471,16 +522,21 @@ pub async fn execute_replay_command(
             for (task_count, checkpoints) in range.chunks(checkpoints_per_task).enumerate() {
                 let checkpoints = checkpoints.to_vec();
                 let rpc_url = rpc_url.clone();
+                let fetcher = fetcher.clone();
                 let safety = safety.clone();
                 handles.push(tokio::spawn(async move {
                     info!("Spawning task {task_count} for checkpoints {checkpoints:?}");
                     let time = std::time::Instant::now();
-                    let (succeeded, total) = LocalExec::new_from_fn_url(&rpc_url.expect("Url must be provided"))
-                        .await
-                        .unwrap()
-                        .init_for_execution()
-                        .await
-                        .unwrap()
+                    let mut lx = match fetcher {
+                        Some(fetcher) => LocalExec::new_for_checkpoints(fetcher).await.unwrap(),
+                        None => LocalExec::new_from_fn_url(&rpc_url.expect("Url must be provided"))
+                            .await
+                            .unwrap()
+                            .init_for_execution()
+                            .await
+                            .unwrap(),
+                    };
+                    let (succeeded, total) = lx
                         .execute_all_in_checkpoints(&checkpoints, &safety, terminate_early, use_authority)
                         .await
                         .unwrap();
@@ -544,6 +600,7 @@ pub async fn execute_replay_command(
                     end,
                     terminate_early,
                     max_tasks,
+                    offline: OfflineArgs::default(),
                 },
             )
             .await;
@@ -568,6 +625,28 @@ pub async fn execute_replay_command(
     })
 }
 
+/// A fetcher for the checkpoint archive (and snapshot) in `offline`, if one was given. `chain` is
+/// the name of the chain the archive is from, in case it does not start from genesis.
+fn checkpoint_fetcher(
+    offline: &OfflineArgs,
+    chain: Option<&str>,
+) -> anyhow::Result<Option<CheckpointFetcher>> {
+    let Some(checkpoints_dir) = &offline.checkpoints_dir else {
+        return Ok(None);
+    };
+
+    let chain = chain
+        .map(|c| <Chain as clap::ValueEnum>::from_str(c, true))
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid chain: {e}"))?;
+
+    Ok(Some(CheckpointFetcher::new(
+        checkpoints_dir,
+        offline.snapshot_dir.as_deref(),
+        chain,
+    )?))
+}
+
 pub(crate) fn chain_from_chain_id(chain: &str) -> Chain {
     let mainnet_chain_id = format!("{}", get_mainnet_chain_identifier());
     // TODO: Since testnet periodically resets, we need to ensure that the chain id
@@ -3,6 +3,7 @@
 
 use crate::chain_from_chain_id;
 use crate::{
+    checkpoint_fetcher::CheckpointFetcher,
     data_fetcher::{
         extract_epoch_and_version, DataFetcher, Fetchers, NodeStateDumpFetcher, RemoteFetcher,
     },
@@ -10,6 +11,7 @@ use crate::{
         transaction_displays::{transform_command_results_to_annotated, FullPTB},
         Pretty,
     },
+    effects_diff::EffectsDiff,
     types::*,
 };
 use futures::executor::block_on;
@@ -22,7 +24,6 @@ use move_core_types::{
 };
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
-use similar::{ChangeTag, TextDiff};
 use std::{
     collections::{BTreeMap, HashSet},
     path::PathBuf,
@@ -47,7 +48,9 @@ use sui_types::{
     base_types::{ObjectID, ObjectRef, SequenceNumber, VersionNumber},
     committee::EpochId,
     digests::{ObjectDigest, TransactionDigest},
+    effects::TransactionEffectsAPI,
     error::{ExecutionError, SuiError, SuiResult},
+    event::Event,
     executable_transaction::VerifiedExecutableTransaction,
     gas::SuiGasStatus,
     inner_temporary_store::InnerTemporaryStore,
@@ -99,23 +102,14 @@ impl ExecutionSandboxState {
 
     /// Utility to diff effects in a human readable format
     pub fn diff_effects(&self) -> String {
-        let eff1 = &self.transaction_info.effects;
-        let eff2 = &self.local_exec_effects;
-        let on_chain_str = format!("{:#?}", eff1);
-        let local_chain_str = format!("{:#?}", eff2);
-        let mut res = vec![];
-
-        let diff = TextDiff::from_lines(&on_chain_str, &local_chain_str);
-        for change in diff.iter_all_changes() {
-            let sign = match change.tag() {
-                ChangeTag::Delete => "---",
-                ChangeTag::Insert => "+++",
-                ChangeTag::Equal => "   ",
-            };
-            res.push(format!("{}{}", sign, change));
-        }
+        self.effects_diff().to_string()
+    }
 
-        res.join("")
+    /// Compare the on-chain and local effects field by field. This only covers what is in the
+    /// effects themselves: use `LocalExec::effects_diff` to also compare object contents and
+    /// events.
+    pub fn effects_diff(&self) -> EffectsDiff {
+        EffectsDiff::new(&self.transaction_info.effects, &self.local_exec_effects)
     }
 }
 
@@ -345,6 +339,32 @@ impl LocalExec {
             .await
     }
 
+    /// Replay `tx_digest` entirely from a checkpoint archive (and formal snapshot), without
+    /// accessing the network.
+    pub async fn replay_with_checkpoints(
+        fetcher: CheckpointFetcher,
+        tx_digest: TransactionDigest,
+        expensive_safety_check_config: ExpensiveSafetyCheckConfig,
+        use_authority: bool,
+        executor_version: Option<i64>,
+        protocol_version: Option<i64>,
+        enable_profiler: Option<PathBuf>,
+        config_and_versions: Option<Vec<(ObjectID, SequenceNumber)>>,
+    ) -> Result<ExecutionSandboxState, ReplayEngineError> {
+        LocalExec::new_for_checkpoints(fetcher)
+            .await?
+            .execute_transaction(
+                &tx_digest,
+                expensive_safety_check_config,
+                use_authority,
+                executor_version,
+                protocol_version,
+                enable_profiler,
+                config_and_versions,
+            )
+            .await
+    }
+
     /// This captures the state of the network at a given point in time and populates
     /// prptocol version tables including which system packages to fetch
     /// If this function is called across epoch boundaries, the info might be stale.
@@ -437,6 +457,34 @@ impl LocalExec {
         })
     }
 
+    /// Protocol versions and system package versions are read from the checkpoint archive as they
+    /// are needed, so this does not need `init_for_execution`.
+    pub async fn new_for_checkpoints(
+        fetcher: CheckpointFetcher,
+    ) -> Result<Self, ReplayEngineError> {
+        // Use a throwaway metrics registry for local execution.
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(LimitsMetrics::new(&registry));
+
+        Ok(Self {
+            client: None,
+            protocol_version_epoch_table: BTreeMap::new(),
+            protocol_version_system_package_table: BTreeMap::new(),
+            current_protocol_version: 0,
+            exec_store_events: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            storage: Storage::default(),
+            fetcher: Fetchers::Checkpoint(fetcher),
+            // TODO: make these configurable
+            num_retries_for_timeout: RPC_TIMEOUT_ERR_NUM_RETRIES,
+            sleep_period_for_timeout: RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+            executor_version: None,
+            protocol_version: None,
+            enable_profiler: None,
+            config_and_versions: None,
+        })
+    }
+
     pub async fn multi_download_and_store(
         &mut self,
         objs: &[(ObjectID, SequenceNumber)],
@@ -652,6 +700,79 @@ impl LocalExec {
         }
     }
 
+    /// Compare the on-chain and local effects of `sandbox_state`, including the contents of the
+    /// objects they wrote and the events they emitted, where these can be fetched.
+    pub async fn effects_diff(
+        &self,
+        sandbox_state: &ExecutionSandboxState,
+    ) -> Result<EffectsDiff, ReplayEngineError> {
+        let diff = sandbox_state.effects_diff();
+        let Some(store) = &sandbox_state.local_exec_temporary_store else {
+            return Ok(diff);
+        };
+
+        let mut on_chain_objects = BTreeMap::new();
+        let mut local_objects = BTreeMap::new();
+        for (id, on_chain_version) in diff.objects_to_compare() {
+            let (Some(local), Ok(mut on_chain)) = (
+                store.written.get(&id),
+                self.multi_download(&[(id, on_chain_version)]).await,
+            ) else {
+                continue;
+            };
+
+            local_objects.insert(id, local.clone());
+            if let Some(on_chain) = on_chain.pop() {
+                on_chain_objects.insert(id, on_chain);
+            }
+        }
+
+        // Failing to fetch the on-chain events should not hide the rest of the diff.
+        let diff = diff.with_object_contents(&on_chain_objects, &local_objects);
+        let tx_digest = &sandbox_state.transaction_info.tx_digest;
+        Ok(match self.on_chain_events(tx_digest).await {
+            Ok(Some(events)) => diff.with_events(&events, &store.events.data),
+            Ok(None) => diff,
+            Err(e) => {
+                warn!("Unable to fetch on-chain events for {tx_digest}, not comparing them: {e}");
+                diff
+            }
+        })
+    }
+
+    /// The events emitted by the on-chain execution of `tx_digest`, if they are available.
+    async fn on_chain_events(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<Option<Vec<Event>>, ReplayEngineError> {
+        Ok(match &self.fetcher {
+            Fetchers::Remote(_) => {
+                self.fetcher
+                    .get_transaction(tx_digest)
+                    .await?
+                    .events
+                    .map(|events| {
+                        events
+                            .data
+                            .into_iter()
+                            .map(|e| Event {
+                                package_id: e.package_id,
+                                transaction_module: e.transaction_module,
+                                sender: e.sender,
+                                type_: e.type_,
+                                contents: e.bcs.into_bytes(),
+                            })
+                            .collect()
+                    })
+            }
+            Fetchers::Checkpoint(c) => c
+                .get_checkpoint_transaction(tx_digest)?
+                .events
+                .map(|events| events.data),
+            Fetchers::NodeStateDump(_) => None,
+        })
+    }
+
     pub async fn get_checkpoint_txs(
         &self,
         checkpoint_id: u64,
@@ -874,11 +995,7 @@ impl LocalExec {
         );
         }
 
-        let tx_info = if self.is_remote_replay() {
-            self.resolve_tx_components(tx_digest).await?
-        } else {
-            self.resolve_tx_components_from_dump(tx_digest).await?
-        };
+        let tx_info = self.resolve_tx_info(tx_digest).await?;
         self.execution_engine_execute_with_tx_info_impl(
             &tx_info,
             None,
@@ -1114,6 +1231,8 @@ impl LocalExec {
                 .map(|w| (w.id, w.version, w.digest))
                 .map(|q| (q.0, q.1))
                 .collect()),
+
+            Fetchers::Checkpoint(c) => c.system_package_versions(protocol_version),
         }
     }
 
@@ -1349,6 +1468,10 @@ impl LocalExec {
         match self.protocol_version {
             Some(x) if x < 0 => Ok(ProtocolConfig::get_for_max_version_UNSAFE()),
             Some(v) => Ok(ProtocolConfig::get_for_version((v as u64).into(), chain)),
+            None if matches!(self.fetcher, Fetchers::Checkpoint(_)) => {
+                let version = self.fetcher.as_checkpoint().protocol_version(epoch_id)?;
+                Ok(ProtocolConfig::get_for_version(version.into(), chain))
+            }
             None => self
                 .protocol_version_epoch_table
                 .iter()
@@ -1633,6 +1756,98 @@ impl LocalExec {
         })
     }
 
+    async fn resolve_tx_components_from_checkpoints(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<OnChainTransactionInfo, ReplayEngineError> {
+        let tx = self
+            .fetcher
+            .as_checkpoint()
+            .get_checkpoint_transaction(tx_digest)?;
+
+        let orig_tx = tx.transaction.data().clone();
+        let sender = orig_tx.transaction_data().sender();
+        let epoch_id = tx.effects.executed_epoch();
+        let effects = SuiTransactionBlockEffects::try_from(tx.effects)?;
+        let config_objects = self.add_config_objects_if_needed(effects.status());
+
+        let input_objs = orig_tx
+            .transaction_data()
+            .input_objects()
+            .map_err(|e| ReplayEngineError::UserInputError { err: e })?;
+        let tx_kind_orig = orig_tx.transaction_data().kind();
+
+        // Download the objects at the version right before the execution of this TX
+        let modified_at_versions: Vec<(ObjectID, SequenceNumber)> = effects.modified_at_versions();
+
+        let shared_object_refs: Vec<ObjectRef> = effects
+            .shared_objects()
+            .iter()
+            .map(|so_ref| {
+                if so_ref.digest == ObjectDigest::OBJECT_DIGEST_DELETED {
+                    Err(ReplayEngineError::TransactionNotSupported {
+                        digest: *tx_digest,
+                        reason: "Replay of deleted shared object transactions is not supported \
+                            yet"
+                        .to_string(),
+                    })
+                } else {
+                    Ok(so_ref.to_object_ref())
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let gas_data = orig_tx.transaction_data().gas_data();
+        let gas_object_refs: Vec<_> = gas_data.clone().payment.into_iter().collect();
+        let receiving_objs = orig_tx
+            .transaction_data()
+            .receiving_objects()
+            .into_iter()
+            .map(|(obj_id, version, _)| (obj_id, version))
+            .collect();
+
+        let chain = chain_from_chain_id(self.fetcher.get_chain_id().await?.as_str());
+
+        // Extract the epoch start timestamp
+        let (epoch_start_timestamp, reference_gas_price) = self
+            .get_epoch_start_timestamp_and_rgp(epoch_id, tx_digest)
+            .await?;
+
+        Ok(OnChainTransactionInfo {
+            kind: tx_kind_orig.clone(),
+            sender,
+            modified_at_versions,
+            input_objects: input_objs,
+            shared_object_refs,
+            gas: gas_object_refs,
+            gas_budget: gas_data.budget,
+            gas_price: gas_data.price,
+            executed_epoch: epoch_id,
+            dependencies: effects.dependencies().to_vec(),
+            effects,
+            receiving_objs,
+            config_objects,
+            protocol_version: self.get_protocol_config(epoch_id, chain).await?.version,
+            tx_digest: *tx_digest,
+            epoch_start_timestamp,
+            sender_signed_data: orig_tx.clone(),
+            reference_gas_price,
+            chain,
+        })
+    }
+
+    /// Resolve the information needed to execute `tx_digest`, from wherever this instance gets its
+    /// data.
+    async fn resolve_tx_info(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Result<OnChainTransactionInfo, ReplayEngineError> {
+        match self.fetcher {
+            Fetchers::Remote(_) => self.resolve_tx_components(tx_digest).await,
+            Fetchers::NodeStateDump(_) => self.resolve_tx_components_from_dump(tx_digest).await,
+            Fetchers::Checkpoint(_) => self.resolve_tx_components_from_checkpoints(tx_digest).await,
+        }
+    }
+
     async fn resolve_download_input_objects(
         &mut self,
         tx_info: &OnChainTransactionInfo,
@@ -1648,7 +1863,13 @@ impl LocalExec {
         // correct transaction dependency for a deleted shared object.
         if !deleted_shared_objects.is_empty() {
             for tx_digest in tx_info.dependencies.iter() {
-                let tx_info = self.resolve_tx_components(tx_digest).await?;
+                let tx_info = match self.fetcher {
+                    Fetchers::Checkpoint(_) => {
+                        self.resolve_tx_components_from_checkpoints(tx_digest)
+                            .await?
+                    }
+                    _ => self.resolve_tx_components(tx_digest).await?,
+                };
                 for (obj_id, version, _) in tx_info.shared_object_refs.iter() {
                     deleted_shared_info_map.insert(*obj_id, (tx_info.tx_digest, *version));
                 }
@@ -1,16 +1,27 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::checkpoint_fetcher::CheckpointFetcher;
 use crate::config::ReplayableNetworkConfigSet;
+use crate::data_fetcher::DataFetcher;
+use crate::effects_diff::{DiffFormat, EffectsDiff};
 use crate::types::ReplayEngineError;
 use crate::types::{MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD};
 use crate::LocalExec;
+use std::collections::BTreeMap;
+use std::path::Path;
 use sui_config::node::ExpensiveSafetyCheckConfig;
+use sui_framework::BuiltInFramework;
 use sui_json_rpc_api::QUERY_MAX_RESULT_LIMIT;
-use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockResponseOptions};
+use sui_protocol_config::ProtocolVersion;
 use sui_sdk::{SuiClient, SuiClientBuilder};
-use sui_types::base_types::SuiAddress;
-use sui_types::digests::TransactionDigest;
+use sui_storage::blob::{Blob, BlobEncoding};
+use sui_types::base_types::{SequenceNumber, SuiAddress};
+use sui_types::digests::{ObjectDigest, TransactionDigest};
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::message_envelope::Message;
+use sui_types::test_checkpoint_data_builder::TestCheckpointDataBuilder;
 
 /// Keep searching for non-system TXs in the checkppints for this long
 /// Very unlikely to take this long, but we want to be sure we find one
@@ -172,3 +183,196 @@ async fn execute_replay(url: &str, tx: &TransactionDigest) -> Result<(), ReplayE
 
     Ok(())
 }
+
+/// Checks that objects, transactions and epoch information can be read back from a directory of
+/// checkpoint files, without a snapshot.
+#[tokio::test]
+async fn checkpoint_fetcher_reads_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = TestCheckpointDataBuilder::new(0)
+        .start_transaction(0)
+        .create_owned_object(1)
+        .finish_transaction();
+    let created = builder.build_checkpoint();
+
+    builder = builder
+        .start_transaction(0)
+        .mutate_object(1)
+        .finish_transaction();
+    let mutated = builder.build_checkpoint();
+    let epoch_change = builder.advance_epoch(false);
+
+    write_archive(dir.path(), &[&created, &mutated, &epoch_change]);
+
+    let fetcher = CheckpointFetcher::new(dir.path(), None, None).unwrap();
+    let id = TestCheckpointDataBuilder::derive_object_id(1);
+    let output = |checkpoint: &CheckpointData| {
+        checkpoint.transactions[0]
+            .output_objects
+            .iter()
+            .find(|o| o.id() == id)
+            .cloned()
+            .unwrap()
+    };
+    let (v1, v2) = (output(&created), output(&mutated));
+
+    assert_eq!(
+        fetcher.get_checkpoint_txs(1).await.unwrap(),
+        vec![*mutated.transactions[0].transaction.digest()]
+    );
+    assert_eq!(
+        fetcher
+            .get_latest_checkpoint_sequence_number()
+            .await
+            .unwrap(),
+        2
+    );
+
+    assert_eq!(
+        fetcher
+            .multi_get_versioned(&[(id, v1.version())])
+            .await
+            .unwrap(),
+        vec![v1.clone()]
+    );
+    assert_eq!(fetcher.multi_get_latest(&[id]).await.unwrap(), vec![v2]);
+    assert_eq!(
+        fetcher.get_child_object(&id, v1.version()).await.unwrap(),
+        v1
+    );
+    assert!(matches!(
+        fetcher
+            .multi_get_versioned(&[(id, SequenceNumber::MAX)])
+            .await,
+        Err(ReplayEngineError::ObjectVersionNotFound { .. })
+    ));
+
+    assert_eq!(
+        fetcher.get_epoch_start_timestamp_and_rgp(1).await.unwrap(),
+        (0, 0)
+    );
+    assert_eq!(
+        fetcher.protocol_version(1).unwrap(),
+        ProtocolVersion::MAX.as_u64()
+    );
+    assert!(fetcher.get_chain_id().await.is_ok());
+}
+
+/// Checks that a transaction can be replayed end to end from a directory of checkpoint files,
+/// without access to the network: Its inputs, the system packages, and the epoch it ran in are all
+/// read from the archive.
+#[tokio::test]
+async fn offline_replay_from_checkpoints() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut builder = TestCheckpointDataBuilder::new(0)
+        .start_transaction(0)
+        .create_owned_object(1)
+        .finish_transaction();
+
+    // Publish the system packages at genesis, so they are available from the first epoch on.
+    let mut genesis = builder.build_checkpoint();
+    genesis.transactions[0]
+        .output_objects
+        .extend(BuiltInFramework::genesis_objects());
+    let epoch_change = builder.advance_epoch(false);
+
+    builder = builder
+        .start_transaction(0)
+        .mutate_object(1)
+        .finish_transaction();
+    let mutated = builder.build_checkpoint();
+    write_archive(dir.path(), &[&genesis, &epoch_change, &mutated]);
+
+    let fetcher = CheckpointFetcher::new(dir.path(), None, None).unwrap();
+    let mut exec = LocalExec::new_for_checkpoints(fetcher).await.unwrap();
+    let digest = *mutated.transactions[0].transaction.digest();
+
+    let state = exec
+        .execute_transaction(
+            &digest,
+            ExpensiveSafetyCheckConfig::default(),
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(state.transaction_info.tx_digest, digest);
+    assert_eq!(
+        state.transaction_info.protocol_version,
+        ProtocolVersion::MAX
+    );
+
+    // The checkpoint builder does not charge for gas, so the effects computed locally need not
+    // match those in the archive, but they can still be compared against them.
+    let diff = exec.effects_diff(&state).await.unwrap();
+    assert_eq!(diff.is_empty(), state.check_effects().is_ok());
+}
+
+/// Checks that diverging effects are reported field by field, down to the contents of the objects
+/// that diverged.
+#[test]
+fn effects_diff_reports_divergent_fields_and_objects() {
+    let checkpoint = TestCheckpointDataBuilder::new(0)
+        .start_transaction(0)
+        .create_owned_object(1)
+        .finish_transaction()
+        .build_checkpoint();
+    let tx = &checkpoint.transactions[0];
+    let id = TestCheckpointDataBuilder::derive_object_id(1);
+
+    let on_chain = SuiTransactionBlockEffects::try_from(tx.effects.clone()).unwrap();
+    assert!(EffectsDiff::new(&on_chain, &on_chain).is_empty());
+
+    let mut local = on_chain.clone();
+    let SuiTransactionBlockEffects::V1(effects) = &mut local;
+    effects.gas_used.computation_cost += 1;
+    effects.created[0].reference.digest = ObjectDigest::random();
+
+    let diff = EffectsDiff::new(&on_chain, &local);
+    assert_eq!(diff.fields.len(), 1);
+    assert_eq!(diff.fields[0].field, "gas_used.computation_cost");
+    assert_eq!(diff.objects.len(), 1);
+    assert_eq!(diff.objects[0].object_id, id);
+
+    let to_compare = diff.objects_to_compare();
+    assert_eq!(to_compare.len(), 1);
+    assert_eq!(to_compare[0].0, id);
+
+    let on_chain_object = tx.output_objects.iter().find(|o| o.id() == id).unwrap();
+    let mut local_object = on_chain_object.clone();
+    local_object
+        .data
+        .try_as_move_mut()
+        .unwrap()
+        .set_coin_value_unsafe(42);
+
+    let diff = diff.with_object_contents(
+        &BTreeMap::from([(id, on_chain_object.clone())]),
+        &BTreeMap::from([(id, local_object)]),
+    );
+    assert_eq!(diff.objects[0].content.len(), 1);
+    assert!(diff.objects[0].content[0].field.starts_with("contents["));
+
+    let json = diff.render(DiffFormat::Json);
+    assert_eq!(serde_json::from_str::<EffectsDiff>(&json).unwrap(), diff);
+
+    let human = diff.render(DiffFormat::Human);
+    assert!(human.contains("gas_used.computation_cost"));
+    assert!(human.contains(&id.to_string()));
+}
+
+/// Write `checkpoints` to `dir` in the format `sui-data-ingestion` uses.
+fn write_archive(dir: &Path, checkpoints: &[&CheckpointData]) {
+    for checkpoint in checkpoints {
+        let path = dir.join(format!(
+            "{}.chk",
+            checkpoint.checkpoint_summary.sequence_number
+        ));
+        let blob = Blob::encode(checkpoint, BlobEncoding::Bcs).unwrap();
+        std::fs::write(path, blob.to_bytes()).unwrap();
+    }
+}
@@ -199,6 +199,12 @@ pub enum ReplayEngineError {
 
     #[error("Unable to get chain id: {}", err)]
     UnableToGetChainId { err: String },
+
+    #[error("Checkpoint {checkpoint} not found in checkpoint archive")]
+    CheckpointNotFound { checkpoint: u64 },
+
+    #[error("Transaction {digest} not found in checkpoint archive")]
+    TransactionNotFound { digest: TransactionDigest },
 }
 
 impl From<SuiObjectResponseError> for ReplayEngineError {
@@ -9,12 +9,15 @@ use sdk::BoxError;
 
 pub use reqwest;
 use tap::Pipe;
+use tokio_stream::{Stream, StreamExt};
 use tonic::metadata::MetadataMap;
 
 use crate::proto::node::v2::node_service_client::NodeServiceClient;
 use crate::proto::node::v2::{
     ExecuteTransactionResponse, GetCheckpointResponse, GetFullCheckpointResponse, GetObjectResponse,
 };
+use crate::proto::node::v2alpha::subscription_service_client::SubscriptionServiceClient;
+use crate::proto::node::v2alpha::SubscribeCheckpointsResponse;
 use crate::proto::types::Bcs;
 use crate::proto::TryFromProtoError;
 use crate::types::ExecuteTransactionOptions;
@@ -77,6 +80,14 @@ impl Client {
         NodeServiceClient::with_interceptor(self.channel.clone(), self.auth.clone())
     }
 
+    pub fn raw_subscription_client(
+        &self,
+    ) -> SubscriptionServiceClient<
+        tonic::service::interceptor::InterceptedService<tonic::transport::Channel, AuthInterceptor>,
+    > {
+        SubscriptionServiceClient::with_interceptor(self.channel.clone(), self.auth.clone())
+    }
+
     pub async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
         self.get_checkpoint_internal(None).await
     }
@@ -129,23 +140,7 @@ impl Client {
         let request = crate::proto::node::v2::GetFullCheckpointRequest {
             sequence_number: Some(sequence_number),
             digest: None,
-            options: Some(crate::proto::node::v2::GetFullCheckpointOptions {
-                summary: Some(false),
-                summary_bcs: Some(true),
-                signature: Some(true),
-                contents: Some(false),
-                contents_bcs: Some(true),
-                transaction: Some(false),
-                transaction_bcs: Some(true),
-                effects: Some(false),
-                effects_bcs: Some(true),
-                events: Some(false),
-                events_bcs: Some(true),
-                input_objects: Some(true),
-                output_objects: Some(true),
-                object: Some(false),
-                object_bcs: Some(true),
-            }),
+            options: Some(full_checkpoint_bcs_options()),
         };
 
         let (metadata, response, _extentions) = self
@@ -159,6 +154,36 @@ impl Client {
             .map_err(|e| status_from_error_with_metadata(e, metadata))
     }
 
+    /// Subscribe to the stream of checkpoints being executed by the node, starting from the
+    /// node's current tip. Each item is the checkpoint's sequence number along with its full
+    /// contents.
+    pub async fn subscribe_checkpoints(
+        &self,
+    ) -> Result<impl Stream<Item = Result<(CheckpointSequenceNumber, CheckpointData)>>> {
+        let request = crate::proto::node::v2alpha::SubscribeCheckpointsRequest {
+            options: Some(full_checkpoint_bcs_options()),
+        };
+
+        let stream = self
+            .raw_subscription_client()
+            .max_decoding_message_size(64 * 1024 * 1024)
+            .subscribe_checkpoints(request)
+            .await?
+            .into_inner();
+
+        Ok(stream.map(|response| {
+            let SubscribeCheckpointsResponse { cursor, checkpoint } = response?;
+
+            let checkpoint = checkpoint
+                .ok_or_else(|| TryFromProtoError::missing("checkpoint"))
+                .and_then(checkpoint_data_try_from_proto)
+                .map_err(|e| Status::from_error(e.into()))?;
+
+            let cursor = cursor.unwrap_or(checkpoint.checkpoint_summary.sequence_number);
+            Ok((cursor, checkpoint))
+        }))
+    }
+
     pub async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
         self.get_object_internal(object_id, None).await
     }
@@ -242,6 +267,28 @@ pub struct TransactionExecutionResponse {
     pub balance_changes: Option<Vec<sui_sdk_types::BalanceChange>>,
 }
 
+/// Options requesting every part of a full checkpoint, BCS encoded, so that it can be converted
+/// into a `CheckpointData`.
+fn full_checkpoint_bcs_options() -> crate::proto::node::v2::GetFullCheckpointOptions {
+    crate::proto::node::v2::GetFullCheckpointOptions {
+        summary: Some(false),
+        summary_bcs: Some(true),
+        signature: Some(true),
+        contents: Some(false),
+        contents_bcs: Some(true),
+        transaction: Some(false),
+        transaction_bcs: Some(true),
+        effects: Some(false),
+        effects_bcs: Some(true),
+        events: Some(false),
+        events_bcs: Some(true),
+        input_objects: Some(true),
+        output_objects: Some(true),
+        object: Some(false),
+        object_bcs: Some(true),
+    }
+}
+
 /// Attempts to parse `CertifiedCheckpointSummary` from the bcs fields in `GetCheckpointResponse`
 fn certified_checkpoint_summary_try_from_proto(
     summary_bcs: Option<Bcs>,
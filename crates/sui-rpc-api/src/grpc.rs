@@ -199,6 +199,163 @@ impl crate::proto::node::v2::node_service_server::NodeService for crate::RpcServ
             .map_err(Into::into)
     }
 
+    async fn list_owned_objects(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::ListOwnedObjectsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::ListOwnedObjectsResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let owner = owner_from_proto(request.owner.as_ref())?;
+        let object_type = request
+            .object_type
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid object_type"))?;
+
+        self.list_owned_objects(
+            owner,
+            object_type,
+            request.page_size,
+            request.page_token.as_deref(),
+        )
+        .map(Into::into)
+        .map(tonic::Response::new)
+        .map_err(Into::into)
+    }
+
+    async fn get_balance(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::GetBalanceRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::GetBalanceResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let owner = owner_from_proto(request.owner.as_ref())?;
+        let coin_type = request
+            .coin_type
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid coin_type"))?;
+
+        self.get_balance(owner, coin_type)
+            .map(Into::into)
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_balances(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::ListBalancesRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::ListBalancesResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let owner = owner_from_proto(request.owner.as_ref())?;
+
+        self.list_balances(owner, request.page_size, request.page_token.as_deref())
+            .map(Into::into)
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_dynamic_fields(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::ListDynamicFieldsRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::ListDynamicFieldsResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let parent = request
+            .parent
+            .as_ref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing parent"))?
+            .try_into()
+            .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid parent"))?;
+
+        self.list_dynamic_fields(parent, request.page_size, request.page_token.as_deref())
+            .map(Into::into)
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_package(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::GetPackageRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::GetPackageResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let package_id = package_id_from_proto(request.package_id.as_ref())?;
+
+        self.get_package(package_id)
+            .await
+            .map(|package| crate::proto::node::v2::GetPackageResponse {
+                package: Some(package.into()),
+            })
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_datatype(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::GetDatatypeRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::GetDatatypeResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let package_id = package_id_from_proto(request.package_id.as_ref())?;
+        let module_name = request.module_name.as_deref().ok_or_else(|| {
+            tonic::Status::new(tonic::Code::InvalidArgument, "missing module_name")
+        })?;
+        let name = request
+            .name
+            .as_deref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing name"))?;
+
+        self.get_datatype(package_id, module_name, name)
+            .await
+            .map(|datatype| crate::proto::node::v2::GetDatatypeResponse {
+                datatype: Some(datatype.into()),
+            })
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn get_function(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::GetFunctionRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::GetFunctionResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let package_id = package_id_from_proto(request.package_id.as_ref())?;
+        let module_name = request.module_name.as_deref().ok_or_else(|| {
+            tonic::Status::new(tonic::Code::InvalidArgument, "missing module_name")
+        })?;
+        let name = request
+            .name
+            .as_deref()
+            .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing name"))?;
+
+        self.get_function(package_id, module_name, name)
+            .await
+            .map(|function| crate::proto::node::v2::GetFunctionResponse {
+                function: Some(function.into()),
+            })
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
     async fn execute_transaction(
         &self,
         request: tonic::Request<crate::proto::node::v2::ExecuteTransactionRequest>,
@@ -207,31 +364,7 @@ impl crate::proto::node::v2::node_service_server::NodeService for crate::RpcServ
         tonic::Status,
     > {
         let request = request.into_inner();
-        let transaction = match (request.transaction, request.transaction_bcs) {
-            (Some(_), Some(_)) => {
-                return Err(tonic::Status::new(
-                    tonic::Code::InvalidArgument,
-                    "only one of `transaction` or `transaction_bcs` can be provided",
-                ))
-            }
-            (Some(transaction), None) => (&transaction).try_into().map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::InvalidArgument,
-                    format!("invalid transaction: {e}"),
-                )
-            })?,
-
-            (None, Some(bcs)) => bcs::from_bytes(bcs.bcs()).map_err(|_| {
-                tonic::Status::new(tonic::Code::InvalidArgument, "invalid transaction bcs")
-            })?,
-
-            (None, None) => {
-                return Err(tonic::Status::new(
-                    tonic::Code::InvalidArgument,
-                    "one of `transaction` or `transaction_bcs` must be provided",
-                ))
-            }
-        };
+        let transaction = transaction_from_proto(request.transaction, request.transaction_bcs)?;
         let mut signatures: Vec<sui_sdk_types::UserSignature> = Vec::new();
 
         if let Some(proto_signatures) = request.signatures {
@@ -279,13 +412,85 @@ impl crate::proto::node::v2::node_service_server::NodeService for crate::RpcServ
             .map(tonic::Response::new)
             .map_err(Into::into)
     }
+
+    async fn simulate_transaction(
+        &self,
+        request: tonic::Request<crate::proto::node::v2::SimulateTransactionRequest>,
+    ) -> std::result::Result<
+        tonic::Response<crate::proto::node::v2::SimulateTransactionResponse>,
+        tonic::Status,
+    > {
+        let request = request.into_inner();
+        let transaction = transaction_from_proto(request.transaction, request.transaction_bcs)?;
+        let options = request.options.unwrap_or_default().into();
+
+        self.simulate_transaction_with_options(transaction, &options)
+            .map(Into::into)
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+}
+
+fn package_id_from_proto(
+    package_id: Option<&crate::proto::types::ObjectId>,
+) -> Result<sui_sdk_types::ObjectId, tonic::Status> {
+    package_id
+        .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing package_id"))?
+        .try_into()
+        .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid package_id"))
+}
+
+fn owner_from_proto(
+    owner: Option<&crate::proto::types::Address>,
+) -> Result<sui_sdk_types::Address, tonic::Status> {
+    owner
+        .ok_or_else(|| tonic::Status::new(tonic::Code::InvalidArgument, "missing owner"))?
+        .try_into()
+        .map_err(|_| tonic::Status::new(tonic::Code::InvalidArgument, "invalid owner"))
 }
 
+// Parse a transaction provided as either a `sui.types.Transaction` message or as BCS, requiring
+// that exactly one of the two is set.
+fn transaction_from_proto(
+    transaction: Option<crate::proto::types::Transaction>,
+    transaction_bcs: Option<crate::proto::types::Bcs>,
+) -> Result<sui_sdk_types::Transaction, tonic::Status> {
+    match (transaction, transaction_bcs) {
+        (Some(_), Some(_)) => Err(tonic::Status::new(
+            tonic::Code::InvalidArgument,
+            "only one of `transaction` or `transaction_bcs` can be provided",
+        )),
+        (Some(transaction), None) => (&transaction).try_into().map_err(|e| {
+            tonic::Status::new(
+                tonic::Code::InvalidArgument,
+                format!("invalid transaction: {e}"),
+            )
+        }),
+
+        (None, Some(bcs)) => bcs::from_bytes(bcs.bcs()).map_err(|_| {
+            tonic::Status::new(tonic::Code::InvalidArgument, "invalid transaction bcs")
+        }),
+
+        (None, None) => Err(tonic::Status::new(
+            tonic::Code::InvalidArgument,
+            "one of `transaction` or `transaction_bcs` must be provided",
+        )),
+    }
+}
+
+use crate::proto::node::v2::GetFullCheckpointResponse;
 use crate::proto::node::v2alpha::SubscribeCheckpointsResponse;
+use crate::proto::node::v2alpha::SubscribeEventsResponse;
+use crate::proto::node::v2alpha::SubscribeTransactionsResponse;
+use sui_types::storage::ReadStore;
+
+/// The maximum number of already executed checkpoints a subscription may replay from the store
+/// before switching to newly executed checkpoints.
+const MAX_CHECKPOINT_REPLAY: u64 = 1000;
 
 #[tonic::async_trait]
 impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionService
-    for SubscriptionServiceHandle
+    for crate::RpcService
 {
     /// Server streaming response type for the SubscribeCheckpoints method.
     type SubscribeCheckpointsStream = Pin<
@@ -295,13 +500,30 @@ impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionServi
         >,
     >;
 
+    /// Server streaming response type for the SubscribeTransactions method.
+    type SubscribeTransactionsStream = Pin<
+        Box<
+            dyn tokio_stream::Stream<Item = Result<SubscribeTransactionsResponse, tonic::Status>>
+                + Send,
+        >,
+    >;
+
+    /// Server streaming response type for the SubscribeEvents method.
+    type SubscribeEventsStream = Pin<
+        Box<dyn tokio_stream::Stream<Item = Result<SubscribeEventsResponse, tonic::Status>> + Send>,
+    >;
+
     async fn subscribe_checkpoints(
         &self,
         request: tonic::Request<crate::proto::node::v2alpha::SubscribeCheckpointsRequest>,
     ) -> Result<tonic::Response<Self::SubscribeCheckpointsStream>, tonic::Status> {
         let options = request.into_inner().options.unwrap_or_default();
 
-        let Some(mut receiver) = self.register_subscription().await else {
+        let Some(mut receiver) = self
+            .subscription_service_handle()?
+            .register_subscription()
+            .await
+        else {
             return Err(tonic::Status::unavailable(
                 "too many existing subscriptions",
             ));
@@ -326,6 +548,461 @@ impl crate::proto::node::v2alpha::subscription_service_server::SubscriptionServi
 
         Ok(tonic::Response::new(response))
     }
+
+    async fn subscribe_transactions(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::SubscribeTransactionsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeTransactionsStream>, tonic::Status> {
+        let request = request.into_inner();
+        let filter = request
+            .filter
+            .as_ref()
+            .map(TransactionFilter::try_from)
+            .transpose()?
+            .unwrap_or_default();
+        let options = request.options.unwrap_or_default();
+
+        let start_checkpoint = request.start_checkpoint;
+        if request.start_transaction_index.is_some() && start_checkpoint.is_none() {
+            return Err(tonic::Status::invalid_argument(
+                "start_transaction_index requires start_checkpoint to be set",
+            ));
+        }
+        let start_transaction_index = request.start_transaction_index.unwrap_or(0);
+
+        let checkpoints = self.checkpoint_stream(start_checkpoint).await?;
+
+        let response = Box::pin(async_stream::try_stream! {
+            for await checkpoint in checkpoints {
+                let checkpoint = checkpoint?;
+                let cursor = checkpoint.sequence_number;
+
+                // Transactions before the requested position in the first checkpoint have
+                // already been delivered.
+                let skip = if cursor.is_some() && cursor == start_checkpoint {
+                    start_transaction_index
+                } else {
+                    0
+                };
+
+                for (index, transaction) in checkpoint.transactions.iter().enumerate() {
+                    let index = index as u64;
+                    if index < skip || !filter.matches(transaction)? {
+                        continue;
+                    }
+
+                    let mut transaction = transaction.clone();
+                    apply_transaction_options(&options, &mut transaction);
+
+                    yield SubscribeTransactionsResponse {
+                        cursor,
+                        transaction: Some(transaction),
+                        transaction_index: Some(index),
+                    };
+                }
+            }
+        });
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn subscribe_events(
+        &self,
+        request: tonic::Request<crate::proto::node::v2alpha::SubscribeEventsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeEventsStream>, tonic::Status> {
+        let request = request.into_inner();
+        let filter = request
+            .filter
+            .as_ref()
+            .map(EventFilter::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        let start_checkpoint = request.start_checkpoint;
+        if request.start_transaction_index.is_some() && start_checkpoint.is_none() {
+            return Err(tonic::Status::invalid_argument(
+                "start_transaction_index requires start_checkpoint to be set",
+            ));
+        }
+        if request.start_event_index.is_some() && request.start_transaction_index.is_none() {
+            return Err(tonic::Status::invalid_argument(
+                "start_event_index requires start_transaction_index to be set",
+            ));
+        }
+        let start = (
+            request.start_transaction_index.unwrap_or(0),
+            request.start_event_index.unwrap_or(0),
+        );
+
+        let checkpoints = self.checkpoint_stream(start_checkpoint).await?;
+
+        let response = Box::pin(async_stream::try_stream! {
+            for await checkpoint in checkpoints {
+                let checkpoint = checkpoint?;
+                let cursor = checkpoint.sequence_number;
+
+                // Events before the requested position in the first checkpoint have already been
+                // delivered.
+                let skip = if cursor.is_some() && cursor == start_checkpoint {
+                    start
+                } else {
+                    (0, 0)
+                };
+
+                for (transaction_index, transaction) in checkpoint.transactions.iter().enumerate() {
+                    let transaction_index = transaction_index as u64;
+                    if transaction_index < skip.0 {
+                        continue;
+                    }
+
+                    let events = transaction
+                        .events
+                        .iter()
+                        .flat_map(|events| events.events.iter());
+
+                    for (index, event) in events.enumerate() {
+                        let index = index as u32;
+                        if (transaction_index, index) < skip || !filter.matches(event)? {
+                            continue;
+                        }
+
+                        yield SubscribeEventsResponse {
+                            cursor,
+                            transaction_digest: transaction.digest.clone(),
+                            event_index: Some(index),
+                            event: Some(event.clone()),
+                            transaction_index: Some(transaction_index),
+                        };
+                    }
+                }
+            }
+        });
+
+        Ok(tonic::Response::new(response))
+    }
+}
+
+impl crate::RpcService {
+    fn subscription_service_handle(&self) -> Result<&SubscriptionServiceHandle, tonic::Status> {
+        self.subscription_service_handle
+            .as_ref()
+            .ok_or_else(|| tonic::Status::unimplemented("subscription service is not enabled"))
+    }
+
+    // Stream every checkpoint, in order, starting from `start_checkpoint` if one is provided, or
+    // from the next checkpoint to be executed otherwise.
+    //
+    // The subscription for new checkpoints is registered before any already executed checkpoints
+    // are read from the store, so that no checkpoint is missed in between. The replay is bounded
+    // by `MAX_CHECKPOINT_REPLAY` so that a subscriber can catch up before its channel fills up.
+    // Any gap between the replayed and the received checkpoints is also read from the store.
+    async fn checkpoint_stream(
+        &self,
+        start_checkpoint: Option<u64>,
+    ) -> Result<
+        impl tokio_stream::Stream<Item = Result<Arc<GetFullCheckpointResponse>, tonic::Status>>
+            + Send
+            + 'static,
+        tonic::Status,
+    > {
+        let mut receiver = self
+            .subscription_service_handle()?
+            .register_subscription()
+            .await
+            .ok_or_else(|| tonic::Status::unavailable("too many existing subscriptions"))?;
+
+        let replay = match start_checkpoint {
+            Some(start) => {
+                let store = self.reader.inner();
+                let lowest = store
+                    .get_lowest_available_checkpoint()
+                    .map_err(crate::RpcServiceError::from)?;
+                let latest = store
+                    .get_latest_checkpoint_sequence_number()
+                    .map_err(crate::RpcServiceError::from)?;
+
+                if start < lowest {
+                    return Err(tonic::Status::out_of_range(format!(
+                        "start_checkpoint {start} is below the lowest available checkpoint {lowest}"
+                    )));
+                }
+
+                if latest.saturating_sub(start) > MAX_CHECKPOINT_REPLAY {
+                    return Err(tonic::Status::out_of_range(format!(
+                        "start_checkpoint {start} is more than {MAX_CHECKPOINT_REPLAY} checkpoints \
+                        behind the latest checkpoint {latest}"
+                    )));
+                }
+
+                Some(start..=latest)
+            }
+            None => None,
+        };
+
+        let service = self.clone();
+
+        Ok(async_stream::try_stream! {
+            let mut next = start_checkpoint;
+
+            if let Some(replay) = replay {
+                let start = *replay.start();
+                let latest = *replay.end();
+
+                for sequence_number in replay {
+                    yield service.stored_checkpoint(sequence_number)?;
+                }
+
+                next = Some(start.max(latest + 1));
+            }
+
+            while let Some(checkpoint) = receiver.recv().await {
+                let sequence_number = checkpoint
+                    .sequence_number
+                    .ok_or_else(|| tonic::Status::internal("unable to determine cursor"))?;
+
+                if let Some(next) = next {
+                    if sequence_number < next {
+                        continue;
+                    }
+
+                    for sequence_number in next..sequence_number {
+                        yield service.stored_checkpoint(sequence_number)?;
+                    }
+                }
+
+                next = Some(sequence_number + 1);
+                yield checkpoint;
+            }
+        })
+    }
+
+    // Read a checkpoint from the store, with all of its fields populated, in the same shape that
+    // checkpoints are delivered to subscribers.
+    fn stored_checkpoint(
+        &self,
+        sequence_number: u64,
+    ) -> crate::Result<Arc<GetFullCheckpointResponse>> {
+        self.get_full_checkpoint(
+            crate::service::checkpoints::CheckpointId::SequenceNumber(sequence_number),
+            &crate::proto::node::v2::GetFullCheckpointOptions::all().into(),
+        )
+        .map(GetFullCheckpointResponse::from)
+        .map(Arc::new)
+    }
+}
+
+/// Server-side representation of `sui.node.v2alpha.TransactionFilter`. Unset criteria match every
+/// transaction.
+#[derive(Default)]
+struct TransactionFilter {
+    sender: Option<sui_sdk_types::Address>,
+    affected_address: Option<sui_sdk_types::Address>,
+    affected_object: Option<sui_sdk_types::ObjectId>,
+    package: Option<sui_sdk_types::ObjectId>,
+    module: Option<sui_sdk_types::Identifier>,
+}
+
+impl TransactionFilter {
+    fn matches(
+        &self,
+        transaction: &crate::proto::node::v2::FullCheckpointTransaction,
+    ) -> Result<bool, tonic::Status> {
+        use sui_types::effects::TransactionEffectsAPI;
+        use sui_types::object::Owner;
+        use sui_types::transaction::TransactionDataAPI;
+
+        let data: sui_types::transaction::TransactionData = transaction
+            .transaction_bcs
+            .as_ref()
+            .ok_or_else(|| tonic::Status::internal("missing transaction_bcs"))?
+            .deserialize()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        let sender = sui_sdk_types::Address::from(data.sender());
+        if self.sender.is_some_and(|s| s != sender) {
+            return Ok(false);
+        }
+
+        if let Some(package) = self.package {
+            let package = sui_types::base_types::ObjectID::from(package);
+            let called = data.move_calls().into_iter().any(|(p, m, _)| {
+                *p == package
+                    && self
+                        .module
+                        .as_ref()
+                        .map_or(true, |module| module.as_str() == m)
+            });
+
+            if !called {
+                return Ok(false);
+            }
+        }
+
+        if self.affected_address.is_none() && self.affected_object.is_none() {
+            return Ok(true);
+        }
+
+        let effects: sui_types::effects::TransactionEffects = transaction
+            .effects_bcs
+            .as_ref()
+            .ok_or_else(|| tonic::Status::internal("missing effects_bcs"))?
+            .deserialize()
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        if let Some(affected_address) = self.affected_address {
+            let address = sui_types::base_types::SuiAddress::from(affected_address);
+            let owned_by = |owner: &Owner| match owner {
+                Owner::AddressOwner(a) => *a == address,
+                Owner::ConsensusV2 { authenticator, .. } => {
+                    *authenticator.as_single_owner() == address
+                }
+                _ => false,
+            };
+
+            let affected = sender == affected_address
+                || effects
+                    .all_changed_objects()
+                    .iter()
+                    .any(|(_, owner, _)| owned_by(owner))
+                || effects
+                    .old_object_metadata()
+                    .iter()
+                    .any(|(_, owner)| owned_by(owner));
+
+            if !affected {
+                return Ok(false);
+            }
+        }
+
+        if let Some(object) = self.affected_object {
+            let object = sui_types::base_types::ObjectID::from(object);
+            let affected = effects
+                .all_changed_objects()
+                .iter()
+                .map(|((id, _, _), _, _)| id)
+                .chain(
+                    effects
+                        .all_removed_objects()
+                        .iter()
+                        .map(|((id, _, _), _)| id),
+                )
+                .chain(effects.unwrapped_then_deleted().iter().map(|(id, _, _)| id))
+                .any(|id| *id == object);
+
+            if !affected {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl TryFrom<&crate::proto::node::v2alpha::TransactionFilter> for TransactionFilter {
+    type Error = tonic::Status;
+
+    fn try_from(
+        value: &crate::proto::node::v2alpha::TransactionFilter,
+    ) -> Result<Self, Self::Error> {
+        if value.module.is_some() && value.package.is_none() {
+            return Err(tonic::Status::invalid_argument(
+                "`module` filter requires `package` to be set",
+            ));
+        }
+
+        Ok(Self {
+            sender: value
+                .sender
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid sender"))?,
+            affected_address: value
+                .affected_address
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid affected_address"))?,
+            affected_object: value
+                .affected_object
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid affected_object"))?,
+            package: value
+                .package
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid package"))?,
+            module: value
+                .module
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid module"))?,
+        })
+    }
+}
+
+/// Server-side representation of `sui.node.v2alpha.EventFilter`. Unset criteria match every event.
+#[derive(Default)]
+struct EventFilter {
+    sender: Option<sui_sdk_types::Address>,
+    event_type: Option<sui_sdk_types::StructTag>,
+    package: Option<sui_sdk_types::ObjectId>,
+    module: Option<sui_sdk_types::Identifier>,
+}
+
+impl EventFilter {
+    fn matches(&self, event: &crate::proto::types::Event) -> Result<bool, tonic::Status> {
+        let event = sui_sdk_types::Event::try_from(event)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(self.sender.map_or(true, |s| s == event.sender)
+            && self.event_type.as_ref().map_or(true, |t| *t == event.type_)
+            && self.package.map_or(true, |p| p == event.package_id)
+            && self.module.as_ref().map_or(true, |m| *m == event.module))
+    }
+}
+
+impl TryFrom<&crate::proto::node::v2alpha::EventFilter> for EventFilter {
+    type Error = tonic::Status;
+
+    fn try_from(value: &crate::proto::node::v2alpha::EventFilter) -> Result<Self, Self::Error> {
+        if value.module.is_some() && value.package.is_none() {
+            return Err(tonic::Status::invalid_argument(
+                "`module` filter requires `package` to be set",
+            ));
+        }
+
+        Ok(Self {
+            sender: value
+                .sender
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid sender"))?,
+            event_type: value
+                .event_type
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid event_type"))?,
+            package: value
+                .package
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid package"))?,
+            module: value
+                .module
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|_| tonic::Status::invalid_argument("invalid module"))?,
+        })
+    }
 }
 
 // Go through all of the fields of the checkpoint and apply the provided 'options'.
@@ -353,45 +1030,54 @@ fn apply_checkpont_options(
     }
 
     for transaction in checkpoint.transactions.iter_mut() {
-        if !options.transaction() {
-            transaction.transaction = None;
-        }
-        if !options.transaction_bcs() {
-            transaction.transaction_bcs = None;
-        }
-        if !options.effects() {
-            transaction.effects = None;
-        }
-        if !options.effects_bcs() {
-            transaction.effects_bcs = None;
-        }
-        if !options.events() {
-            transaction.events = None;
-        }
-        if !options.events_bcs() {
-            transaction.events_bcs = None;
-        }
-        if !options.input_objects() {
-            transaction.input_objects = None;
-        }
-        if !options.output_objects() {
-            transaction.output_objects = None;
-        }
-
-        for object in transaction
-            .input_objects
-            .iter_mut()
-            .chain(transaction.output_objects.iter_mut())
-            .flat_map(|objects| objects.objects.iter_mut())
-        {
-            if !options.object() {
-                object.object = None;
-            }
-            if !options.object_bcs() {
-                object.object_bcs = None;
-            }
-        }
+        apply_transaction_options(options, transaction);
     }
 
     checkpoint
 }
+
+// Apply the transaction-level fields of the provided 'options' to a single transaction, under the
+// same assumptions as `apply_checkpont_options`.
+fn apply_transaction_options(
+    options: &crate::proto::node::v2::GetFullCheckpointOptions,
+    transaction: &mut crate::proto::node::v2::FullCheckpointTransaction,
+) {
+    if !options.transaction() {
+        transaction.transaction = None;
+    }
+    if !options.transaction_bcs() {
+        transaction.transaction_bcs = None;
+    }
+    if !options.effects() {
+        transaction.effects = None;
+    }
+    if !options.effects_bcs() {
+        transaction.effects_bcs = None;
+    }
+    if !options.events() {
+        transaction.events = None;
+    }
+    if !options.events_bcs() {
+        transaction.events_bcs = None;
+    }
+    if !options.input_objects() {
+        transaction.input_objects = None;
+    }
+    if !options.output_objects() {
+        transaction.output_objects = None;
+    }
+
+    for object in transaction
+        .input_objects
+        .iter_mut()
+        .chain(transaction.output_objects.iter_mut())
+        .flat_map(|objects| objects.objects.iter_mut())
+    {
+        if !options.object() {
+            object.object = None;
+        }
+        if !options.object_bcs() {
+            object.object_bcs = None;
+        }
+    }
+}
@@ -130,9 +130,8 @@ impl RpcService {
                 .add_service(reflection_v1alpha)
                 .add_service(node_service);
 
-            if let Some(subscription_service_handle) = self.subscription_service_handle.clone() {
-                services = services
-                    .add_service(SubscriptionServiceServer::new(subscription_service_handle));
+            if self.subscription_service_handle.is_some() {
+                services = services.add_service(SubscriptionServiceServer::new(self.clone()));
             }
 
             services.into_router()
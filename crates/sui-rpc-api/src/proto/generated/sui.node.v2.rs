@@ -455,6 +455,587 @@ pub struct ExecuteTransactionOptions {
     #[prost(bool, optional, tag = "8")]
     pub balance_changes: ::core::option::Option<bool>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionRequest {
+    #[prost(message, optional, tag = "1")]
+    pub transaction: ::core::option::Option<super::super::types::Transaction>,
+    #[prost(message, optional, tag = "2")]
+    pub transaction_bcs: ::core::option::Option<super::super::types::Bcs>,
+    #[prost(message, optional, tag = "3")]
+    pub options: ::core::option::Option<SimulateTransactionOptions>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionOptions {
+    /// Include the sui.types.TransactionEffects message in the response.
+    ///
+    /// Defaults to `false` if not included
+    #[prost(bool, optional, tag = "1")]
+    pub effects: ::core::option::Option<bool>,
+    /// Include the TransactionEffects formatted as BCS in the response.
+    ///
+    /// Defaults to `false` if not included
+    #[prost(bool, optional, tag = "2")]
+    pub effects_bcs: ::core::option::Option<bool>,
+    /// Include the sui.types.TransactionEvents message in the response.
+    ///
+    /// Defaults to `false` if not included
+    #[prost(bool, optional, tag = "3")]
+    pub events: ::core::option::Option<bool>,
+    /// Include the TransactionEvents formatted as BCS in the response.
+    ///
+    /// Defaults to `false` if not included
+    #[prost(bool, optional, tag = "4")]
+    pub events_bcs: ::core::option::Option<bool>,
+    /// Include the BalanceChanges in the response.
+    ///
+    /// Defaults to `false` if not included
+    #[prost(bool, optional, tag = "5")]
+    pub balance_changes: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateTransactionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub effects: ::core::option::Option<super::super::types::TransactionEffects>,
+    #[prost(message, optional, tag = "2")]
+    pub effects_bcs: ::core::option::Option<super::super::types::Bcs>,
+    #[prost(message, optional, tag = "3")]
+    pub events: ::core::option::Option<super::super::types::TransactionEvents>,
+    #[prost(message, optional, tag = "4")]
+    pub events_bcs: ::core::option::Option<super::super::types::Bcs>,
+    #[prost(message, optional, tag = "5")]
+    pub balance_changes: ::core::option::Option<BalanceChanges>,
+    /// A gas budget that should be sufficient to execute the transaction, derived
+    /// from the gas used by the simulation with a safety margin added.
+    #[prost(uint64, optional, tag = "6")]
+    pub recommended_gas_budget: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOwnedObjectsRequest {
+    /// The address whose owned objects should be listed.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// Only include objects of this type.
+    ///
+    /// If the type has no type parameters then objects of any instantiation of
+    /// the type are included, e.g. `0x2::coin::Coin` matches every coin.
+    #[prost(message, optional, tag = "2")]
+    pub object_type: ::core::option::Option<super::super::types::StructTag>,
+    /// The maximum number of objects to return. The service may return fewer
+    /// objects than this value. When `object_type` is set, a page may hold fewer
+    /// objects, or none at all, even if more matching objects remain; keep
+    /// paginating until no `next_page_token` is returned.
+    ///
+    /// Defaults to 50 if not included and is capped at 100.
+    #[prost(uint32, optional, tag = "3")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListOwnedObjects` call. Provide
+    /// this to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListOwnedObjects` must
+    /// match the call that provided the page token.
+    #[prost(bytes = "bytes", optional, tag = "4")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOwnedObjectsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub objects: ::prost::alloc::vec::Vec<OwnedObject>,
+    /// A token which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OwnedObject {
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    #[prost(message, optional, tag = "2")]
+    pub object_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(uint64, optional, tag = "3")]
+    pub version: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "4")]
+    pub object_type: ::core::option::Option<super::super::types::StructTag>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceRequest {
+    /// The address whose balance should be returned.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// The type of coin to return the balance of.
+    ///
+    /// Defaults to `0x2::sui::SUI` if not included.
+    #[prost(message, optional, tag = "2")]
+    pub coin_type: ::core::option::Option<super::super::types::TypeTag>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBalanceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub balance: ::core::option::Option<Balance>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesRequest {
+    /// The address whose balances should be listed.
+    #[prost(message, optional, tag = "1")]
+    pub owner: ::core::option::Option<super::super::types::Address>,
+    /// The maximum number of balances to return. The service may return fewer
+    /// balances than this value.
+    ///
+    /// Defaults to 50 if not included and is capped at 100.
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListBalances` call. Provide this
+    /// to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListBalances` must
+    /// match the call that provided the page token.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBalancesResponse {
+    /// The balance of each coin type owned by the address, ordered by coin type.
+    #[prost(message, repeated, tag = "1")]
+    pub balances: ::prost::alloc::vec::Vec<Balance>,
+    /// A token which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Balance {
+    #[prost(message, optional, tag = "1")]
+    pub coin_type: ::core::option::Option<super::super::types::TypeTag>,
+    /// The sum of the balances of all coins of `coin_type` owned by the address.
+    #[prost(uint64, optional, tag = "2")]
+    pub balance: ::core::option::Option<u64>,
+    /// The number of coins of `coin_type` owned by the address.
+    #[prost(uint64, optional, tag = "3")]
+    pub coin_object_count: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListDynamicFieldsRequest {
+    /// The object whose dynamic fields should be listed.
+    #[prost(message, optional, tag = "1")]
+    pub parent: ::core::option::Option<super::super::types::ObjectId>,
+    /// The maximum number of dynamic fields to return. The service may return
+    /// fewer dynamic fields than this value.
+    ///
+    /// Defaults to 50 if not included and is capped at 100.
+    #[prost(uint32, optional, tag = "2")]
+    pub page_size: ::core::option::Option<u32>,
+    /// A page token, received from a previous `ListDynamicFields` call. Provide
+    /// this to retrieve the subsequent page.
+    ///
+    /// When paginating, all other parameters provided to `ListDynamicFields` must
+    /// match the call that provided the page token.
+    #[prost(bytes = "bytes", optional, tag = "3")]
+    pub page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListDynamicFieldsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub dynamic_fields: ::prost::alloc::vec::Vec<DynamicField>,
+    /// A token which can be sent as `page_token` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[prost(bytes = "bytes", optional, tag = "2")]
+    pub next_page_token: ::core::option::Option<::prost::bytes::Bytes>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DynamicField {
+    #[prost(enumeration = "DynamicFieldKind", optional, tag = "1")]
+    pub kind: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "2")]
+    pub parent: ::core::option::Option<super::super::types::ObjectId>,
+    /// The id of the `0x2::dynamic_field::Field` object.
+    #[prost(message, optional, tag = "3")]
+    pub field_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(message, optional, tag = "4")]
+    pub name_type: ::core::option::Option<super::super::types::TypeTag>,
+    /// The BCS serialized value of the name of the field.
+    #[prost(bytes = "bytes", optional, tag = "5")]
+    pub name_value: ::core::option::Option<::prost::bytes::Bytes>,
+    /// The id of the child object when `kind` is `DYNAMIC_FIELD_KIND_OBJECT`.
+    #[prost(message, optional, tag = "6")]
+    pub dynamic_object_id: ::core::option::Option<super::super::types::ObjectId>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPackageRequest {
+    /// The storage id of the package.
+    #[prost(message, optional, tag = "1")]
+    pub package_id: ::core::option::Option<super::super::types::ObjectId>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPackageResponse {
+    #[prost(message, optional, tag = "1")]
+    pub package: ::core::option::Option<Package>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDatatypeRequest {
+    /// The storage id of the package which contains the datatype.
+    #[prost(message, optional, tag = "1")]
+    pub package_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(string, optional, tag = "2")]
+    pub module_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDatatypeResponse {
+    #[prost(message, optional, tag = "1")]
+    pub datatype: ::core::option::Option<DatatypeDescriptor>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetFunctionRequest {
+    /// The storage id of the package which contains the function.
+    #[prost(message, optional, tag = "1")]
+    pub package_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(string, optional, tag = "2")]
+    pub module_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetFunctionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub function: ::core::option::Option<FunctionDescriptor>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Package {
+    /// The id this package is stored at on-chain.
+    #[prost(message, optional, tag = "1")]
+    pub storage_id: ::core::option::Option<super::super::types::ObjectId>,
+    /// The id of the first version of this package, which is the id that types
+    /// and functions from this package are referred to by at runtime.
+    #[prost(message, optional, tag = "2")]
+    pub original_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(uint64, optional, tag = "3")]
+    pub version: ::core::option::Option<u64>,
+    #[prost(message, repeated, tag = "4")]
+    pub modules: ::prost::alloc::vec::Vec<Module>,
+    #[prost(message, repeated, tag = "5")]
+    pub type_origins: ::prost::alloc::vec::Vec<super::super::types::TypeOrigin>,
+    #[prost(message, repeated, tag = "6")]
+    pub linkage: ::prost::alloc::vec::Vec<super::super::types::UpgradeInfo>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Module {
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The datatypes defined in this module, ordered by name.
+    #[prost(message, repeated, tag = "2")]
+    pub datatypes: ::prost::alloc::vec::Vec<DatatypeDescriptor>,
+    /// The functions defined in this module, ordered by name.
+    #[prost(message, repeated, tag = "3")]
+    pub functions: ::prost::alloc::vec::Vec<FunctionDescriptor>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatatypeDescriptor {
+    /// Fully qualified name of the datatype, e.g. `0x2::coin::Coin`, using the id
+    /// of the package which first defined the type.
+    #[prost(string, optional, tag = "1")]
+    pub type_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The storage id of the package which first defined this type.
+    #[prost(message, optional, tag = "2")]
+    pub defining_id: ::core::option::Option<super::super::types::ObjectId>,
+    #[prost(string, optional, tag = "3")]
+    pub module: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "Ability", repeated, tag = "5")]
+    pub abilities: ::prost::alloc::vec::Vec<i32>,
+    #[prost(message, repeated, tag = "6")]
+    pub type_parameters: ::prost::alloc::vec::Vec<TypeParameter>,
+    #[prost(enumeration = "DatatypeKind", optional, tag = "7")]
+    pub kind: ::core::option::Option<i32>,
+    /// The fields of the datatype when `kind` is `DATATYPE_KIND_STRUCT`.
+    #[prost(message, repeated, tag = "8")]
+    pub fields: ::prost::alloc::vec::Vec<FieldDescriptor>,
+    /// The variants of the datatype when `kind` is `DATATYPE_KIND_ENUM`.
+    #[prost(message, repeated, tag = "9")]
+    pub variants: ::prost::alloc::vec::Vec<VariantDescriptor>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TypeParameter {
+    #[prost(enumeration = "Ability", repeated, tag = "1")]
+    pub constraints: ::prost::alloc::vec::Vec<i32>,
+    #[prost(bool, optional, tag = "2")]
+    pub is_phantom: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldDescriptor {
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The position of the field in the datatype's definition.
+    #[prost(uint32, optional, tag = "2")]
+    pub position: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub r#type: ::core::option::Option<OpenSignatureBody>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VariantDescriptor {
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The position of the variant in the enum's definition.
+    #[prost(uint32, optional, tag = "2")]
+    pub position: ::core::option::Option<u32>,
+    #[prost(message, repeated, tag = "3")]
+    pub fields: ::prost::alloc::vec::Vec<FieldDescriptor>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FunctionDescriptor {
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "Visibility", optional, tag = "2")]
+    pub visibility: ::core::option::Option<i32>,
+    #[prost(bool, optional, tag = "3")]
+    pub is_entry: ::core::option::Option<bool>,
+    #[prost(message, repeated, tag = "4")]
+    pub type_parameters: ::prost::alloc::vec::Vec<TypeParameter>,
+    #[prost(message, repeated, tag = "5")]
+    pub parameters: ::prost::alloc::vec::Vec<OpenSignature>,
+    #[prost(message, repeated, tag = "6")]
+    pub returns: ::prost::alloc::vec::Vec<OpenSignature>,
+}
+/// A function parameter or return type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenSignature {
+    /// Set if the type is a reference, otherwise the type is passed by value.
+    #[prost(enumeration = "Reference", optional, tag = "1")]
+    pub reference: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "2")]
+    pub body: ::core::option::Option<OpenSignatureBody>,
+}
+/// A type which may refer to the type parameters of the function or datatype
+/// it appears in.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenSignatureBody {
+    #[prost(enumeration = "OpenSignatureBodyType", optional, tag = "1")]
+    pub r#type: ::core::option::Option<i32>,
+    /// Fully qualified name of the datatype when `type` is
+    /// `OPEN_SIGNATURE_BODY_TYPE_DATATYPE`. Note that the package id is the
+    /// runtime id of the package which defines the type.
+    #[prost(string, optional, tag = "2")]
+    pub type_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// The type parameters of a datatype, or the element type of a vector.
+    #[prost(message, repeated, tag = "3")]
+    pub type_parameter_instantiation: ::prost::alloc::vec::Vec<OpenSignatureBody>,
+    /// The index of the type parameter when `type` is
+    /// `OPEN_SIGNATURE_BODY_TYPE_TYPE_PARAMETER`.
+    #[prost(uint32, optional, tag = "4")]
+    pub type_parameter: ::core::option::Option<u32>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DynamicFieldKind {
+    Unknown = 0,
+    /// A field added with `0x2::dynamic_field`.
+    Field = 1,
+    /// A field added with `0x2::dynamic_object_field`.
+    Object = 2,
+}
+impl DynamicFieldKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "DYNAMIC_FIELD_KIND_UNKNOWN",
+            Self::Field => "DYNAMIC_FIELD_KIND_FIELD",
+            Self::Object => "DYNAMIC_FIELD_KIND_OBJECT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DYNAMIC_FIELD_KIND_UNKNOWN" => Some(Self::Unknown),
+            "DYNAMIC_FIELD_KIND_FIELD" => Some(Self::Field),
+            "DYNAMIC_FIELD_KIND_OBJECT" => Some(Self::Object),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Ability {
+    Unknown = 0,
+    Copy = 1,
+    Drop = 2,
+    Store = 3,
+    Key = 4,
+}
+impl Ability {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "ABILITY_UNKNOWN",
+            Self::Copy => "ABILITY_COPY",
+            Self::Drop => "ABILITY_DROP",
+            Self::Store => "ABILITY_STORE",
+            Self::Key => "ABILITY_KEY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ABILITY_UNKNOWN" => Some(Self::Unknown),
+            "ABILITY_COPY" => Some(Self::Copy),
+            "ABILITY_DROP" => Some(Self::Drop),
+            "ABILITY_STORE" => Some(Self::Store),
+            "ABILITY_KEY" => Some(Self::Key),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DatatypeKind {
+    Unknown = 0,
+    Struct = 1,
+    Enum = 2,
+}
+impl DatatypeKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "DATATYPE_KIND_UNKNOWN",
+            Self::Struct => "DATATYPE_KIND_STRUCT",
+            Self::Enum => "DATATYPE_KIND_ENUM",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DATATYPE_KIND_UNKNOWN" => Some(Self::Unknown),
+            "DATATYPE_KIND_STRUCT" => Some(Self::Struct),
+            "DATATYPE_KIND_ENUM" => Some(Self::Enum),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Visibility {
+    Unknown = 0,
+    Private = 1,
+    Public = 2,
+    Friend = 3,
+}
+impl Visibility {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "VISIBILITY_UNKNOWN",
+            Self::Private => "VISIBILITY_PRIVATE",
+            Self::Public => "VISIBILITY_PUBLIC",
+            Self::Friend => "VISIBILITY_FRIEND",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "VISIBILITY_UNKNOWN" => Some(Self::Unknown),
+            "VISIBILITY_PRIVATE" => Some(Self::Private),
+            "VISIBILITY_PUBLIC" => Some(Self::Public),
+            "VISIBILITY_FRIEND" => Some(Self::Friend),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Reference {
+    Unknown = 0,
+    Immutable = 1,
+    Mutable = 2,
+}
+impl Reference {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "REFERENCE_UNKNOWN",
+            Self::Immutable => "REFERENCE_IMMUTABLE",
+            Self::Mutable => "REFERENCE_MUTABLE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "REFERENCE_UNKNOWN" => Some(Self::Unknown),
+            "REFERENCE_IMMUTABLE" => Some(Self::Immutable),
+            "REFERENCE_MUTABLE" => Some(Self::Mutable),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum OpenSignatureBodyType {
+    Unknown = 0,
+    Address = 1,
+    Bool = 2,
+    U8 = 3,
+    U16 = 4,
+    U32 = 5,
+    U64 = 6,
+    U128 = 7,
+    U256 = 8,
+    Vector = 9,
+    Datatype = 10,
+    TypeParameter = 11,
+}
+impl OpenSignatureBodyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "OPEN_SIGNATURE_BODY_TYPE_UNKNOWN",
+            Self::Address => "OPEN_SIGNATURE_BODY_TYPE_ADDRESS",
+            Self::Bool => "OPEN_SIGNATURE_BODY_TYPE_BOOL",
+            Self::U8 => "OPEN_SIGNATURE_BODY_TYPE_U8",
+            Self::U16 => "OPEN_SIGNATURE_BODY_TYPE_U16",
+            Self::U32 => "OPEN_SIGNATURE_BODY_TYPE_U32",
+            Self::U64 => "OPEN_SIGNATURE_BODY_TYPE_U64",
+            Self::U128 => "OPEN_SIGNATURE_BODY_TYPE_U128",
+            Self::U256 => "OPEN_SIGNATURE_BODY_TYPE_U256",
+            Self::Vector => "OPEN_SIGNATURE_BODY_TYPE_VECTOR",
+            Self::Datatype => "OPEN_SIGNATURE_BODY_TYPE_DATATYPE",
+            Self::TypeParameter => "OPEN_SIGNATURE_BODY_TYPE_TYPE_PARAMETER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OPEN_SIGNATURE_BODY_TYPE_UNKNOWN" => Some(Self::Unknown),
+            "OPEN_SIGNATURE_BODY_TYPE_ADDRESS" => Some(Self::Address),
+            "OPEN_SIGNATURE_BODY_TYPE_BOOL" => Some(Self::Bool),
+            "OPEN_SIGNATURE_BODY_TYPE_U8" => Some(Self::U8),
+            "OPEN_SIGNATURE_BODY_TYPE_U16" => Some(Self::U16),
+            "OPEN_SIGNATURE_BODY_TYPE_U32" => Some(Self::U32),
+            "OPEN_SIGNATURE_BODY_TYPE_U64" => Some(Self::U64),
+            "OPEN_SIGNATURE_BODY_TYPE_U128" => Some(Self::U128),
+            "OPEN_SIGNATURE_BODY_TYPE_U256" => Some(Self::U256),
+            "OPEN_SIGNATURE_BODY_TYPE_VECTOR" => Some(Self::Vector),
+            "OPEN_SIGNATURE_BODY_TYPE_DATATYPE" => Some(Self::Datatype),
+            "OPEN_SIGNATURE_BODY_TYPE_TYPE_PARAMETER" => Some(Self::TypeParameter),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod node_service_client {
     #![allow(
@@ -690,11 +1271,11 @@ pub mod node_service_client {
                 .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetFullCheckpoint"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn execute_transaction(
+        pub async fn list_owned_objects(
             &mut self,
-            request: impl tonic::IntoRequest<super::ExecuteTransactionRequest>,
+            request: impl tonic::IntoRequest<super::ListOwnedObjectsRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ExecuteTransactionResponse>,
+            tonic::Response<super::ListOwnedObjectsResponse>,
             tonic::Status,
         > {
             self.inner
@@ -707,55 +1288,249 @@ pub mod node_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/sui.node.v2.NodeService/ExecuteTransaction",
+                "/sui.node.v2.NodeService/ListOwnedObjects",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(
-                    GrpcMethod::new("sui.node.v2.NodeService", "ExecuteTransaction"),
-                );
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "ListOwnedObjects"));
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod node_service_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value,
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with NodeServiceServer.
-    #[async_trait]
-    pub trait NodeService: std::marker::Send + std::marker::Sync + 'static {
-        async fn get_node_info(
-            &self,
-            request: tonic::Request<super::GetNodeInfoRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::GetNodeInfoResponse>,
-            tonic::Status,
-        >;
-        async fn get_committee(
-            &self,
-            request: tonic::Request<super::GetCommitteeRequest>,
+        pub async fn get_balance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBalanceRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetCommitteeResponse>,
+            tonic::Response<super::GetBalanceResponse>,
             tonic::Status,
-        >;
-        async fn get_object(
-            &self,
-            request: tonic::Request<super::GetObjectRequest>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/GetBalance",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetBalance"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_balances(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBalancesRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetObjectResponse>,
+            tonic::Response<super::ListBalancesResponse>,
             tonic::Status,
-        >;
-        async fn get_transaction(
-            &self,
-            request: tonic::Request<super::GetTransactionRequest>,
-        ) -> std::result::Result<
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/ListBalances",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "ListBalances"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_dynamic_fields(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListDynamicFieldsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListDynamicFieldsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/ListDynamicFields",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "ListDynamicFields"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_package(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPackageRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPackageResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/GetPackage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetPackage"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_datatype(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetDatatypeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDatatypeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/GetDatatype",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetDatatype"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_function(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetFunctionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetFunctionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/GetFunction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sui.node.v2.NodeService", "GetFunction"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn execute_transaction(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExecuteTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExecuteTransactionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/ExecuteTransaction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2.NodeService", "ExecuteTransaction"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn simulate_transaction(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SimulateTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SimulateTransactionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2.NodeService/SimulateTransaction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sui.node.v2.NodeService", "SimulateTransaction"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod node_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with NodeServiceServer.
+    #[async_trait]
+    pub trait NodeService: std::marker::Send + std::marker::Sync + 'static {
+        async fn get_node_info(
+            &self,
+            request: tonic::Request<super::GetNodeInfoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetNodeInfoResponse>,
+            tonic::Status,
+        >;
+        async fn get_committee(
+            &self,
+            request: tonic::Request<super::GetCommitteeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetCommitteeResponse>,
+            tonic::Status,
+        >;
+        async fn get_object(
+            &self,
+            request: tonic::Request<super::GetObjectRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetObjectResponse>,
+            tonic::Status,
+        >;
+        async fn get_transaction(
+            &self,
+            request: tonic::Request<super::GetTransactionRequest>,
+        ) -> std::result::Result<
             tonic::Response<super::GetTransactionResponse>,
             tonic::Status,
         >;
@@ -773,6 +1548,55 @@ pub mod node_service_server {
             tonic::Response<super::GetFullCheckpointResponse>,
             tonic::Status,
         >;
+        async fn list_owned_objects(
+            &self,
+            request: tonic::Request<super::ListOwnedObjectsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListOwnedObjectsResponse>,
+            tonic::Status,
+        >;
+        async fn get_balance(
+            &self,
+            request: tonic::Request<super::GetBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBalanceResponse>,
+            tonic::Status,
+        >;
+        async fn list_balances(
+            &self,
+            request: tonic::Request<super::ListBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListBalancesResponse>,
+            tonic::Status,
+        >;
+        async fn list_dynamic_fields(
+            &self,
+            request: tonic::Request<super::ListDynamicFieldsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListDynamicFieldsResponse>,
+            tonic::Status,
+        >;
+        async fn get_package(
+            &self,
+            request: tonic::Request<super::GetPackageRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPackageResponse>,
+            tonic::Status,
+        >;
+        async fn get_datatype(
+            &self,
+            request: tonic::Request<super::GetDatatypeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDatatypeResponse>,
+            tonic::Status,
+        >;
+        async fn get_function(
+            &self,
+            request: tonic::Request<super::GetFunctionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetFunctionResponse>,
+            tonic::Status,
+        >;
         async fn execute_transaction(
             &self,
             request: tonic::Request<super::ExecuteTransactionRequest>,
@@ -780,6 +1604,13 @@ pub mod node_service_server {
             tonic::Response<super::ExecuteTransactionResponse>,
             tonic::Status,
         >;
+        async fn simulate_transaction(
+            &self,
+            request: tonic::Request<super::SimulateTransactionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SimulateTransactionResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct NodeServiceServer<T> {
@@ -1128,25 +1959,25 @@ pub mod node_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sui.node.v2.NodeService/ExecuteTransaction" => {
+                "/sui.node.v2.NodeService/ListOwnedObjects" => {
                     #[allow(non_camel_case_types)]
-                    struct ExecuteTransactionSvc<T: NodeService>(pub Arc<T>);
+                    struct ListOwnedObjectsSvc<T: NodeService>(pub Arc<T>);
                     impl<
                         T: NodeService,
-                    > tonic::server::UnaryService<super::ExecuteTransactionRequest>
-                    for ExecuteTransactionSvc<T> {
-                        type Response = super::ExecuteTransactionResponse;
+                    > tonic::server::UnaryService<super::ListOwnedObjectsRequest>
+                    for ListOwnedObjectsSvc<T> {
+                        type Response = super::ListOwnedObjectsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ExecuteTransactionRequest>,
+                            request: tonic::Request<super::ListOwnedObjectsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as NodeService>::execute_transaction(&inner, request)
+                                <T as NodeService>::list_owned_objects(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -1158,7 +1989,375 @@ pub mod node_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ExecuteTransactionSvc(inner);
+                        let method = ListOwnedObjectsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/GetBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBalanceSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetBalanceRequest>
+                    for GetBalanceSvc<T> {
+                        type Response = super::GetBalanceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBalanceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_balance(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetBalanceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/ListBalances" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListBalancesSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ListBalancesRequest>
+                    for ListBalancesSvc<T> {
+                        type Response = super::ListBalancesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBalancesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_balances(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListBalancesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/ListDynamicFields" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListDynamicFieldsSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ListDynamicFieldsRequest>
+                    for ListDynamicFieldsSvc<T> {
+                        type Response = super::ListDynamicFieldsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListDynamicFieldsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::list_dynamic_fields(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListDynamicFieldsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/GetPackage" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPackageSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetPackageRequest>
+                    for GetPackageSvc<T> {
+                        type Response = super::GetPackageResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPackageRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_package(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetPackageSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/GetDatatype" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetDatatypeSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetDatatypeRequest>
+                    for GetDatatypeSvc<T> {
+                        type Response = super::GetDatatypeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetDatatypeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_datatype(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetDatatypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/GetFunction" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetFunctionSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::GetFunctionRequest>
+                    for GetFunctionSvc<T> {
+                        type Response = super::GetFunctionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetFunctionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::get_function(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetFunctionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/ExecuteTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExecuteTransactionSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::ExecuteTransactionRequest>
+                    for ExecuteTransactionSvc<T> {
+                        type Response = super::ExecuteTransactionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExecuteTransactionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::execute_transaction(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ExecuteTransactionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2.NodeService/SimulateTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct SimulateTransactionSvc<T: NodeService>(pub Arc<T>);
+                    impl<
+                        T: NodeService,
+                    > tonic::server::UnaryService<super::SimulateTransactionRequest>
+                    for SimulateTransactionSvc<T> {
+                        type Response = super::SimulateTransactionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SimulateTransactionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeService>::simulate_transaction(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SimulateTransactionSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -12,6 +12,121 @@ pub struct SubscribeCheckpointsResponse {
     #[prost(message, optional, tag = "2")]
     pub checkpoint: ::core::option::Option<super::v2::GetFullCheckpointResponse>,
 }
+/// Criteria used to select transactions from the transaction stream. A
+/// transaction must satisfy every criteria that is set to be included in the
+/// stream. An empty filter matches every transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionFilter {
+    /// Only include transactions sent by this address.
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<super::super::types::Address>,
+    /// Only include transactions that sent, or changed the ownership of an object
+    /// owned by, this address.
+    #[prost(message, optional, tag = "2")]
+    pub affected_address: ::core::option::Option<super::super::types::Address>,
+    /// Only include transactions that created, modified, unwrapped, wrapped or
+    /// deleted this object.
+    #[prost(message, optional, tag = "3")]
+    pub affected_object: ::core::option::Option<super::super::types::ObjectId>,
+    /// Only include transactions that call a function in this package.
+    #[prost(message, optional, tag = "4")]
+    pub package: ::core::option::Option<super::super::types::ObjectId>,
+    /// Only include transactions that call a function in this module. Requires
+    /// `package` to be set.
+    #[prost(message, optional, tag = "5")]
+    pub module: ::core::option::Option<super::super::types::Identifier>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeTransactionsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<TransactionFilter>,
+    /// The checkpoint to start streaming from (inclusive). Checkpoints that have
+    /// already been executed are read from the store before the stream switches
+    /// to newly executed checkpoints. If not provided, the stream starts with the
+    /// next checkpoint to be executed. Must not be below the lowest checkpoint
+    /// available on the node, and may not be too far behind the latest executed
+    /// checkpoint.
+    #[prost(uint64, optional, tag = "2")]
+    pub start_checkpoint: ::core::option::Option<u64>,
+    /// Controls which fields of each transaction are included. Checkpoint-level
+    /// options are ignored.
+    #[prost(message, optional, tag = "3")]
+    pub options: ::core::option::Option<super::v2::GetFullCheckpointOptions>,
+    /// The position in `start_checkpoint` of the first transaction to stream
+    /// (inclusive). Transactions before it in `start_checkpoint` are skipped.
+    /// Requires `start_checkpoint` to be set.
+    #[prost(uint64, optional, tag = "4")]
+    pub start_transaction_index: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeTransactionsResponse {
+    /// The sequence number of the checkpoint that includes this transaction.
+    #[prost(uint64, optional, tag = "1")]
+    pub cursor: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "2")]
+    pub transaction: ::core::option::Option<super::v2::FullCheckpointTransaction>,
+    /// The position of this transaction in its checkpoint. Resuming from
+    /// `cursor` and `transaction_index + 1` continues right after this
+    /// transaction.
+    #[prost(uint64, optional, tag = "3")]
+    pub transaction_index: ::core::option::Option<u64>,
+}
+/// Criteria used to select events from the event stream. An event must satisfy
+/// every criteria that is set to be included in the stream. An empty filter
+/// matches every event.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EventFilter {
+    /// Only include events emitted by transactions sent by this address.
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<super::super::types::Address>,
+    /// Only include events of exactly this type.
+    #[prost(message, optional, tag = "2")]
+    pub event_type: ::core::option::Option<super::super::types::StructTag>,
+    /// Only include events emitted by a function in this package.
+    #[prost(message, optional, tag = "3")]
+    pub package: ::core::option::Option<super::super::types::ObjectId>,
+    /// Only include events emitted by a function in this module. Requires
+    /// `package` to be set.
+    #[prost(message, optional, tag = "4")]
+    pub module: ::core::option::Option<super::super::types::Identifier>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeEventsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<EventFilter>,
+    /// The checkpoint to start streaming from (inclusive). See
+    /// `SubscribeTransactionsRequest.start_checkpoint`.
+    #[prost(uint64, optional, tag = "2")]
+    pub start_checkpoint: ::core::option::Option<u64>,
+    /// The position in `start_checkpoint` of the transaction that emitted the
+    /// first event to stream. Requires `start_checkpoint` to be set.
+    #[prost(uint64, optional, tag = "3")]
+    pub start_transaction_index: ::core::option::Option<u64>,
+    /// The position of the first event to stream in the events of the
+    /// transaction at `start_transaction_index` (inclusive). Requires
+    /// `start_transaction_index` to be set.
+    #[prost(uint32, optional, tag = "4")]
+    pub start_event_index: ::core::option::Option<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeEventsResponse {
+    /// The sequence number of the checkpoint that includes this event.
+    #[prost(uint64, optional, tag = "1")]
+    pub cursor: ::core::option::Option<u64>,
+    /// The digest of the transaction that emitted this event.
+    #[prost(message, optional, tag = "2")]
+    pub transaction_digest: ::core::option::Option<super::super::types::Digest>,
+    /// The position of this event in its transaction's events.
+    #[prost(uint32, optional, tag = "3")]
+    pub event_index: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "4")]
+    pub event: ::core::option::Option<super::super::types::Event>,
+    /// The position in its checkpoint of the transaction that emitted this
+    /// event. Resuming from `cursor`, `transaction_index` and `event_index + 1`
+    /// continues right after this event.
+    #[prost(uint64, optional, tag = "5")]
+    pub transaction_index: ::core::option::Option<u64>,
+}
 /// Generated client implementations.
 pub mod subscription_service_client {
     #![allow(
@@ -134,6 +249,68 @@ pub mod subscription_service_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        pub async fn subscribe_transactions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::SubscribeTransactionsResponse>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.SubscriptionService/SubscribeTransactions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.node.v2alpha.SubscriptionService",
+                        "SubscribeTransactions",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn subscribe_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::SubscribeEventsResponse>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.node.v2alpha.SubscriptionService/SubscribeEvents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.node.v2alpha.SubscriptionService",
+                        "SubscribeEvents",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -165,6 +342,38 @@ pub mod subscription_service_server {
             tonic::Response<Self::SubscribeCheckpointsStream>,
             tonic::Status,
         >;
+        /// Server streaming response type for the SubscribeTransactions method.
+        type SubscribeTransactionsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::SubscribeTransactionsResponse,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn subscribe_transactions(
+            &self,
+            request: tonic::Request<super::SubscribeTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeTransactionsStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the SubscribeEvents method.
+        type SubscribeEventsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::SubscribeEventsResponse,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn subscribe_events(
+            &self,
+            request: tonic::Request<super::SubscribeEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeEventsStream>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct SubscriptionServiceServer<T> {
@@ -293,6 +502,108 @@ pub mod subscription_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/sui.node.v2alpha.SubscriptionService/SubscribeTransactions" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeTransactionsSvc<T: SubscriptionService>(pub Arc<T>);
+                    impl<
+                        T: SubscriptionService,
+                    > tonic::server::ServerStreamingService<
+                        super::SubscribeTransactionsRequest,
+                    > for SubscribeTransactionsSvc<T> {
+                        type Response = super::SubscribeTransactionsResponse;
+                        type ResponseStream = T::SubscribeTransactionsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeTransactionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SubscriptionService>::subscribe_transactions(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeTransactionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.node.v2alpha.SubscriptionService/SubscribeEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeEventsSvc<T: SubscriptionService>(pub Arc<T>);
+                    impl<
+                        T: SubscriptionService,
+                    > tonic::server::ServerStreamingService<
+                        super::SubscribeEventsRequest,
+                    > for SubscribeEventsSvc<T> {
+                        type Response = super::SubscribeEventsResponse;
+                        type ResponseStream = T::SubscribeEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SubscriptionService>::subscribe_events(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
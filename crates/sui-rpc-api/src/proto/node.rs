@@ -305,6 +305,605 @@ impl TryFrom<&GetObjectResponse> for crate::types::ObjectResponse {
     }
 }
 
+//
+// OwnedObject
+//
+
+impl From<crate::types::OwnedObject> for OwnedObject {
+    fn from(
+        crate::types::OwnedObject {
+            owner,
+            object_id,
+            version,
+            object_type,
+        }: crate::types::OwnedObject,
+    ) -> Self {
+        Self {
+            owner: Some(owner.into()),
+            object_id: Some(object_id.into()),
+            version: Some(version),
+            object_type: Some(object_type.into()),
+        }
+    }
+}
+
+impl TryFrom<&OwnedObject> for crate::types::OwnedObject {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        OwnedObject {
+            owner,
+            object_id,
+            version,
+            object_type,
+        }: &OwnedObject,
+    ) -> Result<Self, Self::Error> {
+        let owner = owner
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("owner"))?
+            .pipe(TryInto::try_into)?;
+        let object_id = object_id
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("object_id"))?
+            .pipe(TryInto::try_into)?;
+        let version = version.ok_or_else(|| TryFromProtoError::missing("version"))?;
+        let object_type = object_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("object_type"))?
+            .pipe(TryInto::try_into)?;
+
+        Self {
+            owner,
+            object_id,
+            version,
+            object_type,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// ListOwnedObjectsResponse
+//
+
+impl From<crate::types::ListOwnedObjectsResponse> for ListOwnedObjectsResponse {
+    fn from(
+        crate::types::ListOwnedObjectsResponse {
+            objects,
+            next_page_token,
+        }: crate::types::ListOwnedObjectsResponse,
+    ) -> Self {
+        Self {
+            objects: objects.into_iter().map(Into::into).collect(),
+            next_page_token: next_page_token.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<&ListOwnedObjectsResponse> for crate::types::ListOwnedObjectsResponse {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        ListOwnedObjectsResponse {
+            objects,
+            next_page_token,
+        }: &ListOwnedObjectsResponse,
+    ) -> Result<Self, Self::Error> {
+        let objects = objects
+            .iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+        let next_page_token = next_page_token.as_ref().map(|token| token.to_vec());
+
+        Self {
+            objects,
+            next_page_token,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// Balance
+//
+
+impl From<crate::types::Balance> for Balance {
+    fn from(
+        crate::types::Balance {
+            coin_type,
+            balance,
+            coin_object_count,
+        }: crate::types::Balance,
+    ) -> Self {
+        Self {
+            coin_type: Some(coin_type.into()),
+            balance: Some(balance),
+            coin_object_count: Some(coin_object_count),
+        }
+    }
+}
+
+impl TryFrom<&Balance> for crate::types::Balance {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        Balance {
+            coin_type,
+            balance,
+            coin_object_count,
+        }: &Balance,
+    ) -> Result<Self, Self::Error> {
+        let coin_type = coin_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("coin_type"))?
+            .pipe(TryInto::try_into)?;
+        let balance = balance.ok_or_else(|| TryFromProtoError::missing("balance"))?;
+        let coin_object_count =
+            coin_object_count.ok_or_else(|| TryFromProtoError::missing("coin_object_count"))?;
+
+        Self {
+            coin_type,
+            balance,
+            coin_object_count,
+        }
+        .pipe(Ok)
+    }
+}
+
+impl From<crate::types::Balance> for GetBalanceResponse {
+    fn from(value: crate::types::Balance) -> Self {
+        Self {
+            balance: Some(value.into()),
+        }
+    }
+}
+
+//
+// ListBalancesResponse
+//
+
+impl From<crate::types::ListBalancesResponse> for ListBalancesResponse {
+    fn from(
+        crate::types::ListBalancesResponse {
+            balances,
+            next_page_token,
+        }: crate::types::ListBalancesResponse,
+    ) -> Self {
+        Self {
+            balances: balances.into_iter().map(Into::into).collect(),
+            next_page_token: next_page_token.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<&ListBalancesResponse> for crate::types::ListBalancesResponse {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        ListBalancesResponse {
+            balances,
+            next_page_token,
+        }: &ListBalancesResponse,
+    ) -> Result<Self, Self::Error> {
+        let balances = balances
+            .iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+        let next_page_token = next_page_token.as_ref().map(|token| token.to_vec());
+
+        Self {
+            balances,
+            next_page_token,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// DynamicField
+//
+
+impl From<crate::rest::objects::DynamicFieldInfo> for DynamicField {
+    fn from(
+        crate::rest::objects::DynamicFieldInfo {
+            parent,
+            field_id,
+            dynamic_field_type,
+            name_type,
+            name_value,
+            dynamic_object_id,
+        }: crate::rest::objects::DynamicFieldInfo,
+    ) -> Self {
+        use crate::rest::objects::DynamicFieldType;
+
+        let kind = match dynamic_field_type {
+            DynamicFieldType::Field => DynamicFieldKind::Field,
+            DynamicFieldType::Object => DynamicFieldKind::Object,
+        };
+
+        Self {
+            kind: Some(kind.into()),
+            parent: Some(parent.into()),
+            field_id: Some(field_id.into()),
+            name_type: Some(name_type.into()),
+            name_value: Some(name_value.into()),
+            dynamic_object_id: dynamic_object_id.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<&DynamicField> for crate::rest::objects::DynamicFieldInfo {
+    type Error = TryFromProtoError;
+
+    fn try_from(value: &DynamicField) -> Result<Self, Self::Error> {
+        use crate::rest::objects::DynamicFieldType;
+
+        let dynamic_field_type = match value.kind() {
+            DynamicFieldKind::Unknown => {
+                return Err(TryFromProtoError::missing(
+                    "unknown DynamicFieldKind variant",
+                ))
+            }
+            DynamicFieldKind::Field => DynamicFieldType::Field,
+            DynamicFieldKind::Object => DynamicFieldType::Object,
+        };
+        let parent = value
+            .parent
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("parent"))?
+            .pipe(TryInto::try_into)?;
+        let field_id = value
+            .field_id
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("field_id"))?
+            .pipe(TryInto::try_into)?;
+        let name_type = value
+            .name_type
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("name_type"))?
+            .pipe(TryInto::try_into)?;
+        let name_value = value
+            .name_value
+            .as_ref()
+            .ok_or_else(|| TryFromProtoError::missing("name_value"))?
+            .to_vec();
+        let dynamic_object_id = value
+            .dynamic_object_id
+            .as_ref()
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        Self {
+            parent,
+            field_id,
+            dynamic_field_type,
+            name_type,
+            name_value,
+            dynamic_object_id,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// ListDynamicFieldsResponse
+//
+
+impl From<crate::types::ListDynamicFieldsResponse> for ListDynamicFieldsResponse {
+    fn from(
+        crate::types::ListDynamicFieldsResponse {
+            dynamic_fields,
+            next_page_token,
+        }: crate::types::ListDynamicFieldsResponse,
+    ) -> Self {
+        Self {
+            dynamic_fields: dynamic_fields.into_iter().map(Into::into).collect(),
+            next_page_token: next_page_token.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<&ListDynamicFieldsResponse> for crate::types::ListDynamicFieldsResponse {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        ListDynamicFieldsResponse {
+            dynamic_fields,
+            next_page_token,
+        }: &ListDynamicFieldsResponse,
+    ) -> Result<Self, Self::Error> {
+        let dynamic_fields = dynamic_fields
+            .iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+        let next_page_token = next_page_token.as_ref().map(|token| token.to_vec());
+
+        Self {
+            dynamic_fields,
+            next_page_token,
+        }
+        .pipe(Ok)
+    }
+}
+
+//
+// Package
+//
+
+impl From<crate::types::Package> for Package {
+    fn from(
+        crate::types::Package {
+            storage_id,
+            original_id,
+            version,
+            modules,
+            type_origins,
+            linkage,
+        }: crate::types::Package,
+    ) -> Self {
+        let linkage = linkage
+            .into_iter()
+            .map(
+                |(
+                    original_id,
+                    sui_sdk_types::UpgradeInfo {
+                        upgraded_id,
+                        upgraded_version,
+                    },
+                )| super::types::UpgradeInfo {
+                    original_id: Some(original_id.into()),
+                    upgraded_id: Some(upgraded_id.into()),
+                    upgraded_version: Some(upgraded_version),
+                },
+            )
+            .collect();
+
+        Self {
+            storage_id: Some(storage_id.into()),
+            original_id: Some(original_id.into()),
+            version: Some(version),
+            modules: modules.into_iter().map(Into::into).collect(),
+            type_origins: type_origins.into_iter().map(Into::into).collect(),
+            linkage,
+        }
+    }
+}
+
+//
+// Module
+//
+
+impl From<crate::types::Module> for Module {
+    fn from(
+        crate::types::Module {
+            name,
+            datatypes,
+            functions,
+        }: crate::types::Module,
+    ) -> Self {
+        Self {
+            name: Some(name),
+            datatypes: datatypes.into_iter().map(Into::into).collect(),
+            functions: functions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+//
+// DatatypeDescriptor
+//
+
+impl From<crate::types::DatatypeDescriptor> for DatatypeDescriptor {
+    fn from(
+        crate::types::DatatypeDescriptor {
+            type_name,
+            defining_id,
+            module,
+            name,
+            abilities,
+            type_parameters,
+            kind,
+            fields,
+            variants,
+        }: crate::types::DatatypeDescriptor,
+    ) -> Self {
+        Self {
+            type_name: Some(type_name),
+            defining_id: Some(defining_id.into()),
+            module: Some(module),
+            name: Some(name),
+            abilities: abilities
+                .into_iter()
+                .map(|ability| Ability::from(ability).into())
+                .collect(),
+            type_parameters: type_parameters.into_iter().map(Into::into).collect(),
+            kind: Some(DatatypeKind::from(kind).into()),
+            fields: fields.into_iter().map(Into::into).collect(),
+            variants: variants.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::types::DatatypeKind> for DatatypeKind {
+    fn from(value: crate::types::DatatypeKind) -> Self {
+        use crate::types::DatatypeKind::*;
+
+        match value {
+            Struct => Self::Struct,
+            Enum => Self::Enum,
+        }
+    }
+}
+
+impl From<crate::types::Ability> for Ability {
+    fn from(value: crate::types::Ability) -> Self {
+        use crate::types::Ability as A;
+
+        match value {
+            A::Copy => Self::Copy,
+            A::Drop => Self::Drop,
+            A::Store => Self::Store,
+            A::Key => Self::Key,
+        }
+    }
+}
+
+impl From<crate::types::TypeParameter> for TypeParameter {
+    fn from(
+        crate::types::TypeParameter {
+            constraints,
+            is_phantom,
+        }: crate::types::TypeParameter,
+    ) -> Self {
+        Self {
+            constraints: constraints
+                .into_iter()
+                .map(|ability| Ability::from(ability).into())
+                .collect(),
+            is_phantom,
+        }
+    }
+}
+
+impl From<crate::types::FieldDescriptor> for FieldDescriptor {
+    fn from(
+        crate::types::FieldDescriptor {
+            name,
+            position,
+            r#type,
+        }: crate::types::FieldDescriptor,
+    ) -> Self {
+        Self {
+            name: Some(name),
+            position: Some(position),
+            r#type: Some(r#type.into()),
+        }
+    }
+}
+
+impl From<crate::types::VariantDescriptor> for VariantDescriptor {
+    fn from(
+        crate::types::VariantDescriptor {
+            name,
+            position,
+            fields,
+        }: crate::types::VariantDescriptor,
+    ) -> Self {
+        Self {
+            name: Some(name),
+            position: Some(position),
+            fields: fields.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+//
+// FunctionDescriptor
+//
+
+impl From<crate::types::FunctionDescriptor> for FunctionDescriptor {
+    fn from(
+        crate::types::FunctionDescriptor {
+            name,
+            visibility,
+            is_entry,
+            type_parameters,
+            parameters,
+            returns,
+        }: crate::types::FunctionDescriptor,
+    ) -> Self {
+        Self {
+            name: Some(name),
+            visibility: Some(Visibility::from(visibility).into()),
+            is_entry: Some(is_entry),
+            type_parameters: type_parameters.into_iter().map(Into::into).collect(),
+            parameters: parameters.into_iter().map(Into::into).collect(),
+            returns: returns.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::types::Visibility> for Visibility {
+    fn from(value: crate::types::Visibility) -> Self {
+        use crate::types::Visibility::*;
+
+        match value {
+            Private => Self::Private,
+            Public => Self::Public,
+            Friend => Self::Friend,
+        }
+    }
+}
+
+//
+// OpenSignature
+//
+
+impl From<crate::types::OpenSignature> for OpenSignature {
+    fn from(crate::types::OpenSignature { reference, body }: crate::types::OpenSignature) -> Self {
+        Self {
+            reference: reference.map(|reference| Reference::from(reference).into()),
+            body: Some(body.into()),
+        }
+    }
+}
+
+impl From<crate::types::Reference> for Reference {
+    fn from(value: crate::types::Reference) -> Self {
+        use crate::types::Reference::*;
+
+        match value {
+            Immutable => Self::Immutable,
+            Mutable => Self::Mutable,
+        }
+    }
+}
+
+impl From<crate::types::OpenSignatureBody> for OpenSignatureBody {
+    fn from(value: crate::types::OpenSignatureBody) -> Self {
+        use crate::types::OpenSignatureBody as O;
+        use OpenSignatureBodyType as T;
+
+        let simple = |type_: T| Self {
+            r#type: Some(type_.into()),
+            ..Default::default()
+        };
+
+        match value {
+            O::Address => simple(T::Address),
+            O::Bool => simple(T::Bool),
+            O::U8 => simple(T::U8),
+            O::U16 => simple(T::U16),
+            O::U32 => simple(T::U32),
+            O::U64 => simple(T::U64),
+            O::U128 => simple(T::U128),
+            O::U256 => simple(T::U256),
+            O::Vector(element) => Self {
+                r#type: Some(T::Vector.into()),
+                type_parameter_instantiation: vec![(*element).into()],
+                ..Default::default()
+            },
+            O::Datatype {
+                type_name,
+                type_parameter_instantiation,
+            } => Self {
+                r#type: Some(T::Datatype.into()),
+                type_name: Some(type_name),
+                type_parameter_instantiation: type_parameter_instantiation
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                ..Default::default()
+            },
+            O::TypeParameter(index) => Self {
+                r#type: Some(T::TypeParameter.into()),
+                type_parameter: Some(index),
+                ..Default::default()
+            },
+        }
+    }
+}
+
 //
 // GetCheckpointOptions
 //
@@ -682,6 +1281,50 @@ impl From<ExecuteTransactionOptions> for crate::types::ExecuteTransactionOptions
     }
 }
 
+//
+// SimulateTransactionOptions
+//
+
+impl From<crate::types::SimulateTransactionOptions> for SimulateTransactionOptions {
+    fn from(
+        crate::types::SimulateTransactionOptions {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+        }: crate::types::SimulateTransactionOptions,
+    ) -> Self {
+        Self {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+        }
+    }
+}
+
+impl From<SimulateTransactionOptions> for crate::types::SimulateTransactionOptions {
+    fn from(
+        SimulateTransactionOptions {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+        }: SimulateTransactionOptions,
+    ) -> Self {
+        Self {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+        }
+    }
+}
+
 //
 // GetFullCheckpointOptions
 //
@@ -1497,6 +2140,80 @@ impl TryFrom<&ExecuteTransactionResponse> for crate::types::ExecuteTransactionRe
     }
 }
 
+//
+// SimulateTransactionResponse
+//
+
+impl From<crate::types::SimulateTransactionResponse> for SimulateTransactionResponse {
+    fn from(
+        crate::types::SimulateTransactionResponse {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+            recommended_gas_budget,
+        }: crate::types::SimulateTransactionResponse,
+    ) -> Self {
+        let balance_changes = balance_changes.map(|balance_changes| BalanceChanges {
+            balance_changes: balance_changes.into_iter().map(Into::into).collect(),
+        });
+        Self {
+            effects: effects.map(Into::into),
+            effects_bcs: effects_bcs.map(Into::into),
+            events: events.map(Into::into),
+            events_bcs: events_bcs.map(Into::into),
+            balance_changes,
+            recommended_gas_budget: Some(recommended_gas_budget),
+        }
+    }
+}
+
+impl TryFrom<&SimulateTransactionResponse> for crate::types::SimulateTransactionResponse {
+    type Error = TryFromProtoError;
+
+    fn try_from(
+        SimulateTransactionResponse {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+            recommended_gas_budget,
+        }: &SimulateTransactionResponse,
+    ) -> Result<Self, Self::Error> {
+        let effects = effects.as_ref().map(TryInto::try_into).transpose()?;
+        let effects_bcs = effects_bcs.as_ref().map(Into::into);
+
+        let events = events.as_ref().map(TryInto::try_into).transpose()?;
+        let events_bcs = events_bcs.as_ref().map(Into::into);
+
+        let balance_changes = balance_changes
+            .as_ref()
+            .map(|balance_changes| {
+                balance_changes
+                    .balance_changes
+                    .iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?;
+
+        let recommended_gas_budget = recommended_gas_budget
+            .ok_or_else(|| TryFromProtoError::missing("recommended_gas_budget"))?;
+
+        Self {
+            effects,
+            effects_bcs,
+            events,
+            events_bcs,
+            balance_changes,
+            recommended_gas_budget,
+        }
+        .pipe(Ok)
+    }
+}
+
 //
 // EffectsFinality
 //
@@ -74,6 +74,7 @@ async fn simulate_transaction(
 }
 
 /// Response type for the transaction simulation endpoint
+#[serde_with::serde_as]
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct TransactionSimulationResponse {
     pub effects: TransactionEffects,
@@ -81,6 +82,11 @@ pub struct TransactionSimulationResponse {
     pub balance_changes: Option<Vec<BalanceChange>>,
     pub input_objects: Option<Vec<Object>>,
     pub output_objects: Option<Vec<Object>>,
+    /// A gas budget that should be sufficient to execute the transaction, derived from the gas
+    /// used by the simulation.
+    #[serde_as(as = "sui_types::sui_serde::Readable<sui_types::sui_serde::BigInt<u64>, _>")]
+    #[serde(default)]
+    pub recommended_gas_budget: u64,
 }
 
 /// Query parameters for the simulate transaction endpoint
@@ -9,6 +9,9 @@ pub use execution::SimulateTransactionQueryParameters;
 pub use execution::TransactionSimulationResponse;
 
 mod resolve;
+pub(crate) use resolve::estimate_gas_budget_from_gas_cost;
+pub(crate) use resolve::reference_gas_price_and_protocol_config;
+pub(crate) use resolve::select_gas_for_transaction;
 pub use resolve::ResolveTransaction;
 pub use resolve::ResolveTransactionQueryParameters;
 pub use resolve::ResolveTransactionResponse;
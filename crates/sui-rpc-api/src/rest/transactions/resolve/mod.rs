@@ -65,24 +65,7 @@ async fn resolve_transaction(
         .executor
         .as_ref()
         .ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
-    let (reference_gas_price, protocol_config) = {
-        let system_state = state.reader.get_system_state_summary()?;
-
-        let current_protocol_version = state.reader.get_system_state_summary()?.protocol_version;
-
-        let protocol_config = ProtocolConfig::get_for_version_if_supported(
-            current_protocol_version.into(),
-            state.reader.inner().get_chain_identifier()?.chain(),
-        )
-        .ok_or_else(|| {
-            RpcServiceError::new(
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "unable to get current protocol config",
-            )
-        })?;
-
-        (system_state.reference_gas_price, protocol_config)
-    };
+    let (reference_gas_price, protocol_config) = reference_gas_price_and_protocol_config(&state)?;
     let called_packages =
         called_packages(&state.reader, &protocol_config, &unresolved_transaction)?;
     let user_provided_budget = unresolved_transaction
@@ -116,23 +99,11 @@ async fn resolve_transaction(
 
     // If the user didn't provide any gas payment we need to do gas selection now
     if resolved_transaction.gas_data().payment.is_empty() {
-        let input_objects = resolved_transaction
-            .input_objects()
-            .map_err(anyhow::Error::from)?
-            .iter()
-            .flat_map(|obj| match obj {
-                sui_types::transaction::InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => {
-                    Some(*id)
-                }
-                _ => None,
-            })
-            .collect_vec();
-        let gas_coins = select_gas(
+        let gas_coins = select_gas_for_transaction(
             &state.reader,
-            resolved_transaction.gas_data().owner,
+            &protocol_config,
+            &resolved_transaction,
             budget,
-            protocol_config.max_gas_payment_objects(),
-            &input_objects,
         )?;
         resolved_transaction.gas_data_mut().payment = gas_coins;
     }
@@ -156,6 +127,26 @@ async fn resolve_transaction(
     .pipe(Ok)
 }
 
+/// Read the current reference gas price and the protocol config for the current protocol version.
+pub(crate) fn reference_gas_price_and_protocol_config(
+    state: &RpcService,
+) -> Result<(u64, ProtocolConfig)> {
+    let system_state = state.reader.get_system_state_summary()?;
+
+    let protocol_config = ProtocolConfig::get_for_version_if_supported(
+        system_state.protocol_version.into(),
+        state.reader.inner().get_chain_identifier()?.chain(),
+    )
+    .ok_or_else(|| {
+        RpcServiceError::new(
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            "unable to get current protocol config",
+        )
+    })?;
+
+    Ok((system_state.reference_gas_price, protocol_config))
+}
+
 /// Query parameters for the resolve transaction endpoint
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct ResolveTransactionQueryParameters {
@@ -743,7 +734,7 @@ fn find_arg_uses(
 ///     overhead
 ///
 /// This gas estimate is computed similarly as in the TypeScript SDK
-fn estimate_gas_budget_from_gas_cost(
+pub(crate) fn estimate_gas_budget_from_gas_cost(
     gas_cost_summary: &GasCostSummary,
     reference_gas_price: u64,
 ) -> u64 {
@@ -756,6 +747,33 @@ fn estimate_gas_budget_from_gas_cost(
     computation_cost_with_overhead.max(if gas_usage < 0 { 0 } else { gas_usage as u64 })
 }
 
+/// Select gas coins owned by the gas owner of `transaction` to pay for `budget`, skipping any
+/// coins that the transaction already uses as inputs.
+pub(crate) fn select_gas_for_transaction(
+    reader: &StateReader,
+    protocol_config: &ProtocolConfig,
+    transaction: &TransactionData,
+    budget: u64,
+) -> Result<Vec<ObjectRef>> {
+    let input_objects = transaction
+        .input_objects()
+        .map_err(anyhow::Error::from)?
+        .iter()
+        .flat_map(|obj| match obj {
+            sui_types::transaction::InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+            _ => None,
+        })
+        .collect_vec();
+
+    select_gas(
+        reader,
+        transaction.gas_data().owner,
+        budget,
+        protocol_config.max_gas_payment_objects(),
+        &input_objects,
+    )
+}
+
 fn select_gas(
     reader: &StateReader,
     owner: SuiAddress,
@@ -0,0 +1,214 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::pagination::decode_page_token;
+use super::pagination::encode_page_token;
+use super::pagination::invalid_page_token;
+use super::pagination::page_size_or_default;
+use crate::types::Balance;
+use crate::types::ListBalancesResponse;
+use crate::types::ListOwnedObjectsResponse;
+use crate::types::OwnedObject;
+use crate::Result;
+use crate::RpcService;
+use crate::RpcServiceError;
+use sui_sdk_types::Address;
+use sui_sdk_types::ObjectId;
+use sui_sdk_types::StructTag;
+use sui_sdk_types::TypeTag;
+use sui_types::sui_sdk_types_conversions::struct_tag_core_to_sdk;
+use sui_types::sui_sdk_types_conversions::struct_tag_sdk_to_core;
+use sui_types::sui_sdk_types_conversions::type_tag_core_to_sdk;
+use sui_types::sui_sdk_types_conversions::type_tag_sdk_to_core;
+use tap::Pipe;
+
+/// The maximum number of owned objects a single `list_owned_objects` page may scan, so that a
+/// type filter matching few of an account's objects can't make one request scan all of them.
+const MAX_OWNED_OBJECTS_SCANNED_PER_PAGE: usize = 1000;
+
+impl RpcService {
+    pub fn list_owned_objects(
+        &self,
+        owner: Address,
+        object_type: Option<StructTag>,
+        page_size: Option<u32>,
+        page_token: Option<&[u8]>,
+    ) -> Result<ListOwnedObjectsResponse> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+        let page_size = page_size_or_default(page_size);
+        let page_token = page_token
+            .map(decode_page_token::<OwnedObjectsPageToken>)
+            .transpose()?;
+
+        if let Some(token) = &page_token {
+            if token.owner != owner || token.object_type != object_type {
+                return Err(invalid_page_token());
+            }
+        }
+
+        let type_filter = object_type
+            .clone()
+            .map(struct_tag_sdk_to_core)
+            .transpose()?;
+
+        let infos = indexes.account_owned_objects_info_iter(
+            owner.into(),
+            page_token.map(|token| token.object_id.into()),
+        )?;
+
+        // Stop at the first object past a full page, or past the scan limit, and resume from it
+        // on the next page. A page may hold fewer than `page_size` objects if the limit is hit.
+        let mut objects = Vec::with_capacity(page_size);
+        let mut next_object_id = None;
+        for (scanned, info) in infos.enumerate() {
+            if objects.len() == page_size || scanned == MAX_OWNED_OBJECTS_SCANNED_PER_PAGE {
+                next_object_id = Some(ObjectId::from(info.object_id));
+                break;
+            }
+
+            if let Some(type_filter) = &type_filter {
+                if !struct_tag_matches(type_filter, &info.type_.clone().into()) {
+                    continue;
+                }
+            }
+
+            objects.push(OwnedObject {
+                owner: info.owner.into(),
+                object_id: info.object_id.into(),
+                version: info.version.into(),
+                object_type: struct_tag_core_to_sdk(info.type_.into())?,
+            });
+        }
+
+        let next_page_token = next_object_id
+            .map(|object_id| {
+                encode_page_token(&OwnedObjectsPageToken {
+                    owner,
+                    object_type,
+                    object_id,
+                })
+            })
+            .transpose()?;
+
+        Ok(ListOwnedObjectsResponse {
+            objects,
+            next_page_token,
+        })
+    }
+
+    /// Returns the balance of `coin_type` owned by `owner`, defaulting to SUI if no coin type is
+    /// provided.
+    pub fn get_balance(&self, owner: Address, coin_type: Option<TypeTag>) -> Result<Balance> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+
+        let coin_type = match coin_type {
+            Some(coin_type) => coin_type,
+            None => type_tag_core_to_sdk(sui_types::gas_coin::GAS::type_tag())?,
+        };
+        let balance =
+            indexes.get_balance(&owner.into(), &type_tag_sdk_to_core(coin_type.clone())?)?;
+
+        Ok(Balance {
+            coin_type,
+            balance: balance.balance,
+            coin_object_count: balance.coin_object_count,
+        })
+    }
+
+    pub fn list_balances(
+        &self,
+        owner: Address,
+        page_size: Option<u32>,
+        page_token: Option<&[u8]>,
+    ) -> Result<ListBalancesResponse> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+        let page_size = page_size_or_default(page_size);
+        let page_token = page_token
+            .map(decode_page_token::<BalancesPageToken>)
+            .transpose()?;
+
+        if let Some(token) = &page_token {
+            if token.owner != owner {
+                return Err(invalid_page_token());
+            }
+        }
+
+        let cursor = page_token
+            .map(|token| type_tag_sdk_to_core(token.coin_type))
+            .transpose()?;
+
+        let mut balances = indexes
+            .balance_iter(&owner.into(), cursor)?
+            .take(page_size + 1)
+            .map(|(coin_type, balance)| {
+                Balance {
+                    coin_type: type_tag_core_to_sdk(coin_type)?,
+                    balance: balance.balance,
+                    coin_object_count: balance.coin_object_count,
+                }
+                .pipe(Ok)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let next_page_token = if balances.len() > page_size {
+            // SAFETY: We've already verified that balances is greater than page_size, which is
+            // gaurenteed to be >= 1.
+            let next = balances.pop().unwrap();
+            BalancesPageToken {
+                owner,
+                coin_type: next.coin_type,
+            }
+            .pipe(|token| encode_page_token(&token))?
+            .pipe(Some)
+        } else {
+            None
+        };
+
+        Ok(ListBalancesResponse {
+            balances,
+            next_page_token,
+        })
+    }
+}
+
+/// Page tokens include the request parameters that the page was computed for, so that a token
+/// can't be replayed against a different request.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct OwnedObjectsPageToken {
+    owner: Address,
+    object_type: Option<StructTag>,
+    object_id: ObjectId,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BalancesPageToken {
+    owner: Address,
+    coin_type: TypeTag,
+}
+
+/// A filter without any type parameters matches every instantiation of the type, otherwise the
+/// type must match exactly.
+fn struct_tag_matches(
+    filter: &move_core_types::language_storage::StructTag,
+    object_type: &move_core_types::language_storage::StructTag,
+) -> bool {
+    if filter.type_params.is_empty() {
+        filter.address == object_type.address
+            && filter.module == object_type.module
+            && filter.name == object_type.name
+    } else {
+        filter == object_type
+    }
+}
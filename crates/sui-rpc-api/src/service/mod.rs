@@ -1,9 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod accounts;
 pub(crate) mod checkpoints;
 mod committee;
 mod health;
 mod info;
 pub(crate) mod objects;
+mod packages;
+mod pagination;
 pub(crate) mod transactions;
@@ -1,10 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use super::pagination::decode_page_token;
+use super::pagination::encode_page_token;
+use super::pagination::invalid_page_token;
+use super::pagination::page_size_or_default;
+use crate::rest::objects::DynamicFieldInfo;
 use crate::types::GetObjectOptions;
+use crate::types::ListDynamicFieldsResponse;
 use crate::types::ObjectResponse;
 use crate::Result;
 use crate::RpcService;
+use crate::RpcServiceError;
 use sui_sdk_types::ObjectId;
 use sui_sdk_types::Version;
 use tap::Pipe;
@@ -40,6 +47,60 @@ impl RpcService {
         }
         .pipe(Ok)
     }
+
+    pub fn list_dynamic_fields(
+        &self,
+        parent: ObjectId,
+        page_size: Option<u32>,
+        page_token: Option<&[u8]>,
+    ) -> Result<ListDynamicFieldsResponse> {
+        let indexes = self
+            .reader
+            .inner()
+            .indexes()
+            .ok_or_else(RpcServiceError::not_found)?;
+        let page_size = page_size_or_default(page_size);
+        let page_token = page_token
+            .map(decode_page_token::<DynamicFieldsPageToken>)
+            .transpose()?;
+
+        if let Some(token) = &page_token {
+            if token.parent != parent {
+                return Err(invalid_page_token());
+            }
+        }
+
+        let mut dynamic_fields = indexes
+            .dynamic_field_iter(parent.into(), page_token.map(|token| token.field_id.into()))?
+            .take(page_size + 1)
+            .map(DynamicFieldInfo::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let next_page_token = if dynamic_fields.len() > page_size {
+            // SAFETY: We've already verified that dynamic_fields is greater than page_size, which
+            // is gaurenteed to be >= 1.
+            let next = dynamic_fields.pop().unwrap();
+            DynamicFieldsPageToken {
+                parent,
+                field_id: next.field_id,
+            }
+            .pipe(|token| encode_page_token(&token))?
+            .pipe(Some)
+        } else {
+            None
+        };
+
+        Ok(ListDynamicFieldsResponse {
+            dynamic_fields,
+            next_page_token,
+        })
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct DynamicFieldsPageToken {
+    parent: ObjectId,
+    field_id: ObjectId,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,634 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::Ability;
+use crate::types::DatatypeDescriptor;
+use crate::types::DatatypeKind;
+use crate::types::FieldDescriptor;
+use crate::types::FunctionDescriptor;
+use crate::types::Module;
+use crate::types::OpenSignature;
+use crate::types::OpenSignatureBody;
+use crate::types::Package;
+use crate::types::Reference;
+use crate::types::TypeParameter;
+use crate::types::VariantDescriptor;
+use crate::types::Visibility;
+use crate::Result;
+use crate::RpcService;
+use crate::RpcServiceError;
+use axum::http::StatusCode;
+use move_binary_format::file_format::AbilitySet;
+use move_binary_format::file_format::DatatypeTyParameter;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::TypeTag;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use sui_package_resolver::DataDef;
+use sui_package_resolver::DatatypeKey;
+use sui_package_resolver::DatatypeRef;
+use sui_package_resolver::FunctionDef;
+use sui_package_resolver::MoveData;
+use sui_package_resolver::PackageStore;
+use sui_package_resolver::PackageStoreWithLruCache;
+use sui_package_resolver::Resolver;
+use sui_sdk_types::ObjectId;
+use sui_types::base_types::ObjectID;
+use sui_types::move_package::MovePackage;
+use sui_types::storage::RpcStateReader;
+use sui_types::sui_sdk_types_conversions::SdkTypeConversionError;
+use tap::Pipe;
+
+/// The datatypes mentioned by a package, keyed by the runtime id they are referred to by, along
+/// with the number of type parameters each one takes.
+type DatatypeKeys = BTreeMap<DatatypeKey, usize>;
+
+/// Canonical type names, referring to defining ids, for the datatypes mentioned by a package.
+type TypeNames = BTreeMap<DatatypeKey, String>;
+
+impl RpcService {
+    pub async fn get_package(&self, package_id: ObjectId) -> Result<Package> {
+        let package = self.get_move_package(package_id)?;
+        let resolved = resolve_package(&package)?;
+        let runtime_id = AccountAddress::from(package.original_package_id());
+
+        let modules = resolved
+            .modules()
+            .values()
+            .map(module_definitions)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut keys = DatatypeKeys::new();
+        for module in &modules {
+            for (name, data_def) in &module.datatypes {
+                collect_datatype_keys(runtime_id, &module.name, name, data_def, &mut keys);
+            }
+            for (_, function_def) in &module.functions {
+                collect_function_keys(function_def, &mut keys);
+            }
+        }
+        let names = self.canonical_type_names(&package, keys).await?;
+
+        let modules = modules
+            .into_iter()
+            .map(|module| module_descriptor(runtime_id, module, &names))
+            .collect::<Result<_>>()?;
+
+        let type_origins = package
+            .type_origin_table()
+            .iter()
+            .map(|origin| {
+                sui_sdk_types::TypeOrigin {
+                    module_name: sdk_identifier(&origin.module_name)?,
+                    struct_name: sdk_identifier(&origin.datatype_name)?,
+                    package: ObjectId::from(origin.package),
+                }
+                .pipe(Ok)
+            })
+            .collect::<Result<_>>()?;
+
+        let linkage = package
+            .linkage_table()
+            .iter()
+            .map(|(original_id, upgrade_info)| {
+                (
+                    ObjectId::from(*original_id),
+                    sui_sdk_types::UpgradeInfo {
+                        upgraded_id: ObjectId::from(upgrade_info.upgraded_id),
+                        upgraded_version: upgrade_info.upgraded_version.value(),
+                    },
+                )
+            })
+            .collect();
+
+        Package {
+            storage_id: ObjectId::from(package.id()),
+            original_id: ObjectId::from(package.original_package_id()),
+            version: package.version().value(),
+            modules,
+            type_origins,
+            linkage,
+        }
+        .pipe(Ok)
+    }
+
+    pub async fn get_datatype(
+        &self,
+        package_id: ObjectId,
+        module_name: &str,
+        name: &str,
+    ) -> Result<DatatypeDescriptor> {
+        let package = self.get_move_package(package_id)?;
+        let resolved = resolve_package(&package)?;
+        let runtime_id = AccountAddress::from(package.original_package_id());
+        let module = resolved
+            .module(module_name)
+            .map_err(|_| module_not_found(package_id, module_name))?;
+
+        let datatype = module
+            .data_def(name)
+            .map_err(anyhow::Error::from)?
+            .ok_or_else(|| {
+                RpcServiceError::new(
+                    StatusCode::NOT_FOUND,
+                    format!("Datatype {package_id}::{module_name}::{name} not found"),
+                )
+            })?;
+
+        let mut keys = DatatypeKeys::new();
+        collect_datatype_keys(runtime_id, module_name, name, &datatype, &mut keys);
+        let names = self.canonical_type_names(&package, keys).await?;
+
+        datatype_descriptor(runtime_id, module_name, name, datatype, &names)
+    }
+
+    pub async fn get_function(
+        &self,
+        package_id: ObjectId,
+        module_name: &str,
+        name: &str,
+    ) -> Result<FunctionDescriptor> {
+        let package = self.get_move_package(package_id)?;
+        let resolved = resolve_package(&package)?;
+        let module = resolved
+            .module(module_name)
+            .map_err(|_| module_not_found(package_id, module_name))?;
+
+        let function = module
+            .function_def(name)
+            .map_err(anyhow::Error::from)?
+            .ok_or_else(|| {
+                RpcServiceError::new(
+                    StatusCode::NOT_FOUND,
+                    format!("Function {package_id}::{module_name}::{name} not found"),
+                )
+            })?;
+
+        let mut keys = DatatypeKeys::new();
+        collect_function_keys(&function, &mut keys);
+        let names = self.canonical_type_names(&package, keys).await?;
+
+        function_descriptor(name, function, &names)
+    }
+
+    fn get_move_package(&self, package_id: ObjectId) -> Result<MovePackage> {
+        let object = self
+            .reader
+            .inner()
+            .get_object(&package_id.into())
+            .ok_or_else(|| package_not_found(package_id))?;
+
+        object
+            .data
+            .try_as_package()
+            .cloned()
+            .ok_or_else(|| not_a_package(package_id))
+    }
+
+    /// Resolve the defining id of every datatype in `keys`, which refer to packages by the runtime
+    /// ids used in `package`'s bytecode.
+    async fn canonical_type_names(
+        &self,
+        package: &MovePackage,
+        keys: DatatypeKeys,
+    ) -> Result<TypeNames> {
+        let resolver = Resolver::new(PackageStoreWithLruCache::new(RpcPackageStore(
+            self.reader.inner().clone(),
+        )));
+        let runtime_id = AccountAddress::from(package.original_package_id());
+
+        let mut names = TypeNames::new();
+        for (key, arity) in keys {
+            // Datatypes introduced by an upgrade don't exist at the runtime id, so look each one up
+            // at the version of its package that `package` links against. The resolver checks
+            // arity, so the type parameters are filled with placeholders.
+            let storage_id = if key.package == runtime_id {
+                package.id()
+            } else {
+                package
+                    .linkage_table()
+                    .get(&ObjectID::from(key.package))
+                    .map(|upgrade_info| upgrade_info.upgraded_id)
+                    .unwrap_or_else(|| ObjectID::from(key.package))
+            };
+
+            let tag = TypeTag::Struct(Box::new(StructTag {
+                address: storage_id.into(),
+                module: Identifier::new(key.module.as_ref())?,
+                name: Identifier::new(key.name.as_ref())?,
+                type_params: vec![TypeTag::U8; arity],
+            }));
+
+            let defining_id = match resolver
+                .canonical_type(tag)
+                .await
+                .map_err(anyhow::Error::from)?
+            {
+                TypeTag::Struct(tag) => tag.address,
+                tag => return Err(anyhow::anyhow!("unexpected canonical type {tag}").into()),
+            };
+
+            let type_name = format!(
+                "{}::{}::{}",
+                defining_id.to_canonical_string(true),
+                key.module,
+                key.name
+            );
+            names.insert(key, type_name);
+        }
+
+        Ok(names)
+    }
+}
+
+/// Loads packages for the `Resolver` from the node's object store.
+struct RpcPackageStore(Arc<dyn RpcStateReader>);
+
+#[async_trait::async_trait]
+impl PackageStore for RpcPackageStore {
+    async fn fetch(
+        &self,
+        id: AccountAddress,
+    ) -> sui_package_resolver::Result<Arc<sui_package_resolver::Package>> {
+        let Self(reader) = self;
+        let Some(object) = reader.get_object(&id.into()) else {
+            return Err(sui_package_resolver::error::Error::PackageNotFound(id));
+        };
+
+        Ok(Arc::new(sui_package_resolver::Package::read_from_object(
+            &object,
+        )?))
+    }
+}
+
+/// The definitions of a module's datatypes and functions.
+struct ModuleDefinitions {
+    name: String,
+    datatypes: Vec<(String, DataDef)>,
+    functions: Vec<(String, FunctionDef)>,
+}
+
+fn resolve_package(package: &MovePackage) -> Result<sui_package_resolver::Package> {
+    sui_package_resolver::Package::read_from_package(package)
+        .map_err(anyhow::Error::from)
+        .map_err(Into::into)
+}
+
+fn package_not_found(package_id: ObjectId) -> RpcServiceError {
+    RpcServiceError::new(
+        StatusCode::NOT_FOUND,
+        format!("Package {package_id} not found"),
+    )
+}
+
+fn not_a_package(package_id: ObjectId) -> RpcServiceError {
+    RpcServiceError::new(
+        StatusCode::BAD_REQUEST,
+        format!("Object {package_id} is not a package"),
+    )
+}
+
+fn module_not_found(package_id: ObjectId, module_name: &str) -> RpcServiceError {
+    RpcServiceError::new(
+        StatusCode::NOT_FOUND,
+        format!("Module {package_id}::{module_name} not found"),
+    )
+}
+
+fn sdk_identifier(name: &str) -> Result<sui_sdk_types::Identifier> {
+    sui_sdk_types::Identifier::new(name)
+        .map_err(SdkTypeConversionError::from)
+        .map_err(Into::into)
+}
+
+fn module_definitions(module: &sui_package_resolver::Module) -> Result<ModuleDefinitions> {
+    let name = module.name();
+
+    let datatypes = module
+        .datatypes(None, None)
+        .map(|datatype| {
+            // Every name returned by `datatypes` has a definition in the module.
+            let data_def = module
+                .data_def(datatype)
+                .map_err(anyhow::Error::from)?
+                .ok_or_else(|| anyhow::anyhow!("missing definition for {name}::{datatype}"))?;
+            Ok((datatype.to_owned(), data_def))
+        })
+        .collect::<Result<_>>()?;
+
+    let functions = module
+        .functions(None, None)
+        .map(|function| {
+            let function_def = module
+                .function_def(function)
+                .map_err(anyhow::Error::from)?
+                .ok_or_else(|| anyhow::anyhow!("missing definition for {name}::{function}"))?;
+            Ok((function.to_owned(), function_def))
+        })
+        .collect::<Result<_>>()?;
+
+    ModuleDefinitions {
+        name: name.to_owned(),
+        datatypes,
+        functions,
+    }
+    .pipe(Ok)
+}
+
+fn collect_datatype_keys(
+    runtime_id: AccountAddress,
+    module: &str,
+    name: &str,
+    data_def: &DataDef,
+    keys: &mut DatatypeKeys,
+) {
+    keys.insert(
+        datatype_key(runtime_id, module, name),
+        data_def.type_params.len(),
+    );
+
+    match &data_def.data {
+        MoveData::Struct(fields) => {
+            for (_, body) in fields {
+                collect_signature_keys(body, keys);
+            }
+        }
+        MoveData::Enum(variants) => {
+            for variant in variants {
+                for (_, body) in &variant.signatures {
+                    collect_signature_keys(body, keys);
+                }
+            }
+        }
+    }
+}
+
+fn collect_function_keys(function_def: &FunctionDef, keys: &mut DatatypeKeys) {
+    for signature in function_def
+        .parameters
+        .iter()
+        .chain(function_def.return_.iter())
+    {
+        collect_signature_keys(&signature.body, keys);
+    }
+}
+
+fn collect_signature_keys(body: &sui_package_resolver::OpenSignatureBody, keys: &mut DatatypeKeys) {
+    use sui_package_resolver::OpenSignatureBody as O;
+
+    match body {
+        O::Vector(element) => collect_signature_keys(element, keys),
+        O::Datatype(key, type_params) => {
+            keys.insert(key.clone(), type_params.len());
+            for type_param in type_params {
+                collect_signature_keys(type_param, keys);
+            }
+        }
+        O::Address
+        | O::Bool
+        | O::U8
+        | O::U16
+        | O::U32
+        | O::U64
+        | O::U128
+        | O::U256
+        | O::TypeParameter(_) => {}
+    }
+}
+
+fn datatype_key(runtime_id: AccountAddress, module: &str, name: &str) -> DatatypeKey {
+    DatatypeRef {
+        package: runtime_id,
+        module: module.to_owned().into(),
+        name: name.to_owned().into(),
+    }
+}
+
+fn type_name(names: &TypeNames, key: &DatatypeKey) -> Result<String> {
+    names
+        .get(key)
+        .cloned()
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "missing type name for {}::{}::{}",
+                key.package.to_canonical_string(true),
+                key.module,
+                key.name
+            )
+        })
+        .map_err(Into::into)
+}
+
+fn module_descriptor(
+    runtime_id: AccountAddress,
+    module: ModuleDefinitions,
+    names: &TypeNames,
+) -> Result<Module> {
+    let ModuleDefinitions {
+        name,
+        datatypes,
+        functions,
+    } = module;
+
+    let datatypes = datatypes
+        .into_iter()
+        .map(|(datatype, data_def)| {
+            datatype_descriptor(runtime_id, &name, &datatype, data_def, names)
+        })
+        .collect::<Result<_>>()?;
+
+    let functions = functions
+        .into_iter()
+        .map(|(function, function_def)| function_descriptor(&function, function_def, names))
+        .collect::<Result<_>>()?;
+
+    Module {
+        name,
+        datatypes,
+        functions,
+    }
+    .pipe(Ok)
+}
+
+fn datatype_descriptor(
+    runtime_id: AccountAddress,
+    module: &str,
+    name: &str,
+    data_def: DataDef,
+    names: &TypeNames,
+) -> Result<DatatypeDescriptor> {
+    let DataDef {
+        defining_id,
+        abilities,
+        type_params,
+        data,
+    } = data_def;
+
+    let (kind, fields, variants) = match data {
+        MoveData::Struct(fields) => (
+            DatatypeKind::Struct,
+            field_descriptors(fields, names)?,
+            vec![],
+        ),
+        MoveData::Enum(variants) => {
+            let variants = variants
+                .into_iter()
+                .enumerate()
+                .map(|(position, variant)| {
+                    Ok(VariantDescriptor {
+                        name: variant.name,
+                        position: position as u32,
+                        fields: field_descriptors(variant.signatures, names)?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            (DatatypeKind::Enum, vec![], variants)
+        }
+    };
+
+    DatatypeDescriptor {
+        type_name: type_name(names, &datatype_key(runtime_id, module, name))?,
+        defining_id: ObjectId::from(ObjectID::from(defining_id)),
+        module: module.to_owned(),
+        name: name.to_owned(),
+        abilities: abilities_from_set(abilities),
+        type_parameters: type_params.iter().map(datatype_type_parameter).collect(),
+        kind,
+        fields,
+        variants,
+    }
+    .pipe(Ok)
+}
+
+fn field_descriptors(
+    fields: Vec<(String, sui_package_resolver::OpenSignatureBody)>,
+    names: &TypeNames,
+) -> Result<Vec<FieldDescriptor>> {
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(position, (name, body))| {
+            Ok(FieldDescriptor {
+                name,
+                position: position as u32,
+                r#type: open_signature_body(&body, names)?,
+            })
+        })
+        .collect()
+}
+
+fn function_descriptor(
+    name: &str,
+    function_def: FunctionDef,
+    names: &TypeNames,
+) -> Result<FunctionDescriptor> {
+    use move_binary_format::file_format::Visibility as V;
+
+    let FunctionDef {
+        visibility,
+        is_entry,
+        type_params,
+        parameters,
+        return_,
+    } = function_def;
+
+    let visibility = match visibility {
+        V::Private => Visibility::Private,
+        V::Public => Visibility::Public,
+        V::Friend => Visibility::Friend,
+    };
+
+    FunctionDescriptor {
+        name: name.to_owned(),
+        visibility,
+        is_entry,
+        // Function type parameters can't be phantom, so `is_phantom` is left unset.
+        type_parameters: type_params
+            .into_iter()
+            .map(|constraints| TypeParameter {
+                constraints: abilities_from_set(constraints),
+                is_phantom: None,
+            })
+            .collect(),
+        parameters: parameters
+            .iter()
+            .map(|signature| open_signature(signature, names))
+            .collect::<Result<_>>()?,
+        returns: return_
+            .iter()
+            .map(|signature| open_signature(signature, names))
+            .collect::<Result<_>>()?,
+    }
+    .pipe(Ok)
+}
+
+fn datatype_type_parameter(type_param: &DatatypeTyParameter) -> TypeParameter {
+    TypeParameter {
+        constraints: abilities_from_set(type_param.constraints),
+        is_phantom: Some(type_param.is_phantom),
+    }
+}
+
+fn abilities_from_set(abilities: AbilitySet) -> Vec<Ability> {
+    use move_binary_format::file_format::Ability as A;
+
+    abilities
+        .into_iter()
+        .map(|ability| match ability {
+            A::Copy => Ability::Copy,
+            A::Drop => Ability::Drop,
+            A::Store => Ability::Store,
+            A::Key => Ability::Key,
+        })
+        .collect()
+}
+
+fn open_signature(
+    signature: &sui_package_resolver::OpenSignature,
+    names: &TypeNames,
+) -> Result<OpenSignature> {
+    use sui_package_resolver::Reference as R;
+
+    let reference = signature.ref_.map(|reference| match reference {
+        R::Immutable => Reference::Immutable,
+        R::Mutable => Reference::Mutable,
+    });
+
+    OpenSignature {
+        reference,
+        body: open_signature_body(&signature.body, names)?,
+    }
+    .pipe(Ok)
+}
+
+fn open_signature_body(
+    body: &sui_package_resolver::OpenSignatureBody,
+    names: &TypeNames,
+) -> Result<OpenSignatureBody> {
+    use sui_package_resolver::OpenSignatureBody as O;
+
+    match body {
+        O::Address => OpenSignatureBody::Address,
+        O::Bool => OpenSignatureBody::Bool,
+        O::U8 => OpenSignatureBody::U8,
+        O::U16 => OpenSignatureBody::U16,
+        O::U32 => OpenSignatureBody::U32,
+        O::U64 => OpenSignatureBody::U64,
+        O::U128 => OpenSignatureBody::U128,
+        O::U256 => OpenSignatureBody::U256,
+        O::Vector(element) => {
+            OpenSignatureBody::Vector(Box::new(open_signature_body(element, names)?))
+        }
+        O::Datatype(key, type_params) => OpenSignatureBody::Datatype {
+            type_name: type_name(names, key)?,
+            type_parameter_instantiation: type_params
+                .iter()
+                .map(|type_param| open_signature_body(type_param, names))
+                .collect::<Result<_>>()?,
+        },
+        O::TypeParameter(index) => OpenSignatureBody::TypeParameter(u32::from(*index)),
+    }
+    .pipe(Ok)
+}
@@ -0,0 +1,30 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for the page tokens used by paginated gRPC methods.
+//!
+//! A page token is the BCS encoding of a method specific type that records where the next page
+//! starts. Tokens are opaque to clients and are only guaranteed to be valid for the node that
+//! issued them.
+
+use crate::Result;
+use crate::RpcServiceError;
+use axum::http::StatusCode;
+
+pub(super) fn encode_page_token<T: serde::Serialize>(token: &T) -> Result<Vec<u8>> {
+    bcs::to_bytes(token).map_err(Into::into)
+}
+
+pub(super) fn decode_page_token<T: serde::de::DeserializeOwned>(token: &[u8]) -> Result<T> {
+    bcs::from_bytes(token).map_err(|_| invalid_page_token())
+}
+
+pub(super) fn invalid_page_token() -> RpcServiceError {
+    RpcServiceError::new(StatusCode::BAD_REQUEST, "invalid page_token")
+}
+
+pub(super) fn page_size_or_default(page_size: Option<u32>) -> usize {
+    page_size
+        .map(|page_size| (page_size as usize).clamp(1, crate::rest::MAX_PAGE_SIZE))
+        .unwrap_or(crate::rest::DEFAULT_PAGE_SIZE)
+}
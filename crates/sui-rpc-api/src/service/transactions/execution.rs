@@ -1,11 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::rest::transactions::estimate_gas_budget_from_gas_cost;
+use crate::rest::transactions::reference_gas_price_and_protocol_config;
+use crate::rest::transactions::select_gas_for_transaction;
 use crate::rest::transactions::SimulateTransactionQueryParameters;
 use crate::rest::transactions::TransactionSimulationResponse;
 use crate::types::EffectsFinality;
 use crate::types::ExecuteTransactionOptions;
 use crate::types::ExecuteTransactionResponse;
+use crate::types::SimulateTransactionOptions;
+use crate::types::SimulateTransactionResponse;
 use crate::Result;
 use crate::RpcService;
 use crate::RpcServiceError;
@@ -17,6 +22,9 @@ use sui_sdk_types::Owner;
 use sui_sdk_types::SignedTransaction;
 use sui_sdk_types::Transaction;
 use sui_sdk_types::TransactionEffects;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::transaction::TransactionData;
+use sui_types::transaction::TransactionDataAPI;
 use sui_types::transaction_executor::SimulateTransactionResult;
 use tap::Pipe;
 
@@ -129,15 +137,10 @@ impl RpcService {
     }
 
     pub fn simulate_transaction(
-        self,
+        &self,
         parameters: &SimulateTransactionQueryParameters,
         transaction: Transaction,
     ) -> Result<TransactionSimulationResponse> {
-        let executor = self
-            .executor
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
-
         if transaction.gas_payment.objects.is_empty() {
             return Err(RpcServiceError::new(
                 axum::http::StatusCode::BAD_REQUEST,
@@ -145,6 +148,23 @@ impl RpcService {
             ));
         }
 
+        self.simulate(parameters, transaction.try_into()?, false)
+    }
+
+    // Simulate `transaction`, which may only be paid for with a mock gas coin if `allow_mock_gas`
+    // is set and it doesn't provide a gas payment.
+    fn simulate(
+        &self,
+        parameters: &SimulateTransactionQueryParameters,
+        transaction: TransactionData,
+        allow_mock_gas: bool,
+    ) -> Result<TransactionSimulationResponse> {
+        let executor = self
+            .executor
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
+
+        let gas_price = transaction.gas_data().price;
         let SimulateTransactionResult {
             input_objects,
             output_objects,
@@ -152,16 +172,18 @@ impl RpcService {
             effects,
             mock_gas_id,
         } = executor
-            .simulate_transaction(transaction.try_into()?)
+            .simulate_transaction(transaction)
             .map_err(anyhow::Error::from)?;
 
-        if mock_gas_id.is_some() {
+        if mock_gas_id.is_some() && !allow_mock_gas {
             return Err(RpcServiceError::new(
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR,
                 "simulate unexpectedly used a mock gas payment",
             ));
         }
 
+        let recommended_gas_budget =
+            estimate_gas_budget_from_gas_cost(effects.gas_cost_summary(), gas_price);
         let events = events.map(TryInto::try_into).transpose()?;
         let effects = effects.try_into()?;
 
@@ -181,6 +203,77 @@ impl RpcService {
             balance_changes: parameters.balance_changes.then_some(balance_changes),
             input_objects: parameters.input_objects.then_some(input_objects),
             output_objects: parameters.output_objects.then_some(output_objects),
+            recommended_gas_budget,
+        }
+        .pipe(Ok)
+    }
+
+    pub fn simulate_transaction_with_options(
+        &self,
+        transaction: Transaction,
+        options: &SimulateTransactionOptions,
+    ) -> Result<SimulateTransactionResponse> {
+        let parameters = SimulateTransactionQueryParameters {
+            balance_changes: options.include_balance_changes(),
+            ..Default::default()
+        };
+
+        let (reference_gas_price, protocol_config) = reference_gas_price_and_protocol_config(self)?;
+        let mut transaction: TransactionData = transaction.try_into()?;
+
+        if transaction.gas_data().price == 0 {
+            transaction.gas_data_mut().price = reference_gas_price;
+        }
+
+        // Without a budget, simulate with the maximum budget so that the simulation can't run out
+        // of gas, the same way the resolve endpoint does.
+        let budget_provided = transaction.gas_data().budget != 0;
+        if !budget_provided {
+            transaction.gas_data_mut().budget = protocol_config.max_tx_gas();
+        }
+
+        // Without a gas payment, select gas coins the same way the resolve endpoint does: for the
+        // provided budget, or for the recommended budget of a simulation paid for with a mock gas
+        // coin. The transaction is then simulated with the selected coins, so the effects and the
+        // recommended budget reflect the real gas payment.
+        if transaction.gas_data().payment.is_empty() {
+            if !budget_provided {
+                let estimate = self
+                    .simulate(&parameters, transaction.clone(), true)?
+                    .recommended_gas_budget;
+                transaction.gas_data_mut().budget = estimate;
+            }
+            let budget = transaction.gas_data().budget;
+            transaction.gas_data_mut().payment =
+                select_gas_for_transaction(&self.reader, &protocol_config, &transaction, budget)?;
+        }
+
+        let TransactionSimulationResponse {
+            effects,
+            events,
+            balance_changes,
+            recommended_gas_budget,
+            input_objects: _,
+            output_objects: _,
+        } = self.simulate(&parameters, transaction, false)?;
+
+        let effects_bcs = options
+            .include_effects_bcs()
+            .then(|| bcs::to_bytes(&effects))
+            .transpose()?;
+        let events_bcs = options
+            .include_events_bcs()
+            .then(|| events.as_ref().map(bcs::to_bytes))
+            .flatten()
+            .transpose()?;
+
+        SimulateTransactionResponse {
+            effects: options.include_effects().then_some(effects),
+            effects_bcs,
+            events: options.include_events().then_some(events).flatten(),
+            events_bcs,
+            balance_changes,
+            recommended_gas_budget,
         }
         .pipe(Ok)
     }
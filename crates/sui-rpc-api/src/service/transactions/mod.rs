@@ -8,7 +8,7 @@ use crate::RpcService;
 use sui_sdk_types::TransactionDigest;
 use tap::Pipe;
 
-mod execution;
+pub(crate) mod execution;
 
 impl RpcService {
     pub fn get_transaction(
@@ -426,6 +426,271 @@ pub enum EffectsFinality {
     QuorumExecuted,
 }
 
+/// Options for the simulate transaction endpoint
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SimulateTransactionOptions {
+    /// Request `TransactionEffects` be included in the Response.
+    ///
+    /// Defaults to `false` if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects: Option<bool>,
+
+    /// Request `TransactionEffects` encoded as BCS be included in the Response.
+    ///
+    /// Defaults to `false` if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects_bcs: Option<bool>,
+
+    /// Request `TransactionEvents` be included in the Response.
+    ///
+    /// Defaults to `false` if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<bool>,
+
+    /// Request `TransactionEvents` encoded as BCS be included in the Response.
+    ///
+    /// Defaults to `false` if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events_bcs: Option<bool>,
+
+    /// Request `BalanceChanges` be included in the Response.
+    ///
+    /// Defaults to `false` if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_changes: Option<bool>,
+}
+
+impl SimulateTransactionOptions {
+    pub fn include_effects(&self) -> bool {
+        self.effects.unwrap_or(false)
+    }
+
+    pub fn include_effects_bcs(&self) -> bool {
+        self.effects_bcs.unwrap_or(false)
+    }
+
+    pub fn include_events(&self) -> bool {
+        self.events.unwrap_or(false)
+    }
+
+    pub fn include_events_bcs(&self) -> bool {
+        self.events_bcs.unwrap_or(false)
+    }
+
+    pub fn include_balance_changes(&self) -> bool {
+        self.balance_changes.unwrap_or(false)
+    }
+}
+
+/// Response type for the simulate transaction endpoint
+#[serde_with::serde_as]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SimulateTransactionResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects: Option<sui_sdk_types::TransactionEffects>,
+
+    #[serde_as(as = "Option<fastcrypto::encoding::Base64>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects_bcs: Option<Vec<u8>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<sui_sdk_types::TransactionEvents>,
+
+    #[serde_as(as = "Option<fastcrypto::encoding::Base64>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events_bcs: Option<Vec<u8>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_changes: Option<Vec<sui_sdk_types::BalanceChange>>,
+
+    /// A gas budget that should be sufficient to execute the transaction, derived from the gas
+    /// used by the simulation.
+    #[serde_as(as = "sui_types::sui_serde::Readable<sui_types::sui_serde::BigInt<u64>, _>")]
+    pub recommended_gas_budget: u64,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OwnedObject {
+    pub owner: sui_sdk_types::Address,
+    pub object_id: sui_sdk_types::ObjectId,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    pub version: sui_sdk_types::Version,
+    pub object_type: sui_sdk_types::StructTag,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListOwnedObjectsResponse {
+    pub objects: Vec<OwnedObject>,
+
+    /// Token used to fetch the next page, if there is one.
+    #[serde_as(as = "Option<fastcrypto::encoding::Base64>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<Vec<u8>>,
+}
+
+/// The aggregate balance of all coins of a single type owned by an address
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Balance {
+    pub coin_type: sui_sdk_types::TypeTag,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    pub balance: u64,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    pub coin_object_count: u64,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListBalancesResponse {
+    pub balances: Vec<Balance>,
+
+    /// Token used to fetch the next page, if there is one.
+    #[serde_as(as = "Option<fastcrypto::encoding::Base64>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<Vec<u8>>,
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListDynamicFieldsResponse {
+    pub dynamic_fields: Vec<crate::rest::objects::DynamicFieldInfo>,
+
+    /// Token used to fetch the next page, if there is one.
+    #[serde_as(as = "Option<fastcrypto::encoding::Base64>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<Vec<u8>>,
+}
+
+/// A Move package along with the interfaces of its modules
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Package {
+    pub storage_id: sui_sdk_types::ObjectId,
+    pub original_id: sui_sdk_types::ObjectId,
+    #[serde_as(as = "sui_types::sui_serde::BigInt<u64>")]
+    pub version: sui_sdk_types::Version,
+    pub modules: Vec<Module>,
+    pub type_origins: Vec<sui_sdk_types::TypeOrigin>,
+    pub linkage: std::collections::BTreeMap<sui_sdk_types::ObjectId, sui_sdk_types::UpgradeInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Module {
+    pub name: String,
+    pub datatypes: Vec<DatatypeDescriptor>,
+    pub functions: Vec<FunctionDescriptor>,
+}
+
+/// Description of a struct or enum defined in a Move module
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DatatypeDescriptor {
+    /// Fully qualified name of the datatype, referring to its defining package
+    pub type_name: String,
+    pub defining_id: sui_sdk_types::ObjectId,
+    pub module: String,
+    pub name: String,
+    pub abilities: Vec<Ability>,
+    pub type_parameters: Vec<TypeParameter>,
+    pub kind: DatatypeKind,
+    pub fields: Vec<FieldDescriptor>,
+    pub variants: Vec<VariantDescriptor>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatatypeKind {
+    Struct,
+    Enum,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ability {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TypeParameter {
+    pub constraints: Vec<Ability>,
+
+    /// Whether the type parameter is phantom. Only set for the type parameters of datatypes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_phantom: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FieldDescriptor {
+    pub name: String,
+    pub position: u32,
+    pub r#type: OpenSignatureBody,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VariantDescriptor {
+    pub name: String,
+    pub position: u32,
+    pub fields: Vec<FieldDescriptor>,
+}
+
+/// Description of a function defined in a Move module
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FunctionDescriptor {
+    pub name: String,
+    pub visibility: Visibility,
+    pub is_entry: bool,
+    pub type_parameters: Vec<TypeParameter>,
+    pub parameters: Vec<OpenSignature>,
+    pub returns: Vec<OpenSignature>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    Private,
+    Public,
+    Friend,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OpenSignature {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<Reference>,
+    pub body: OpenSignatureBody,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reference {
+    Immutable,
+    Mutable,
+}
+
+/// A type signature which may refer to the type parameters of the enclosing datatype or function
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenSignatureBody {
+    Address,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Vector(Box<OpenSignatureBody>),
+    Datatype {
+        /// Fully qualified name of the datatype, referring to its defining package
+        type_name: String,
+        type_parameter_instantiation: Vec<OpenSignatureBody>,
+    },
+    TypeParameter(u32),
+}
+
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct GetFullCheckpointOptions {
     /// Request `CheckpointSummary` be included in the response
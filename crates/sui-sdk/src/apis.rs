@@ -54,6 +54,11 @@ impl ReadApi {
     pub(crate) fn new(api: Arc<RpcClient>) -> Self {
         Self { api }
     }
+
+    /// A coin read API sharing this API's client.
+    pub(crate) fn coin_read_api(&self) -> CoinReadApi {
+        CoinReadApi::new(self.api.clone())
+    }
     /// Return a paginated response with the objects owned by the given address, or an error upon failure.
     ///
     /// Note that if the address owns more than `QUERY_MAX_RESULT_LIMIT` objects (default is 50),
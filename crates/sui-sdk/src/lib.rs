@@ -83,16 +83,17 @@ use jsonrpsee::rpc_params;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use serde_json::Value;
 
-use move_core_types::language_storage::StructTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 pub use sui_json as json;
 use sui_json_rpc_api::{
     CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER, CLIENT_TARGET_API_VERSION_HEADER,
 };
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
+    CoinPage, ObjectsPage, SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse,
     SuiObjectResponseQuery,
 };
+use sui_transaction_builder::coin_selection::CoinCandidate;
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
@@ -619,4 +620,36 @@ impl DataReader for ReadApi {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
+
+    /// Lists coins a page at a time through the coin read API, which includes their balances,
+    /// rather than reading each coin object.
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Vec<CoinCandidate>, anyhow::Error> {
+        let coin_read_api = self.coin_read_api();
+        let coin_type = Some(coin_type.to_canonical_string(/* with_prefix */ true));
+
+        let mut result = vec![];
+        let mut has_next = true;
+        let mut cursor = None;
+
+        while has_next {
+            let CoinPage {
+                data,
+                next_cursor,
+                has_next_page,
+            } = coin_read_api
+                .get_coins(owner, coin_type.clone(), cursor, None)
+                .await?;
+            result.extend(
+                data.iter()
+                    .map(|coin| CoinCandidate::new(coin.object_ref(), coin.balance)),
+            );
+            cursor = next_cursor;
+            has_next = has_next_page;
+        }
+        Ok(result)
+    }
 }
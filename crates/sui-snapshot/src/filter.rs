@@ -0,0 +1,220 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use arrow_array::{ArrayRef, BinaryArray, RecordBatch, StringArray, UInt64Array};
+use move_core_types::language_storage::StructTag;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::sync::Arc;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::authority::AuthorityStore;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::object::{Object, Owner};
+
+/// Selects the live objects to restore in a partial restore. Objects must match at least one
+/// value of every criterion that is set. Wrapped object tombstones never match.
+#[derive(Clone, Debug, Default)]
+pub struct LiveObjectFilter {
+    types: Vec<StructTag>,
+    packages: Vec<ObjectID>,
+    owners: Vec<SuiAddress>,
+}
+
+impl LiveObjectFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches objects of a Move type. A type without type parameters matches all of its
+    /// instantiations.
+    pub fn with_type(mut self, type_: StructTag) -> Self {
+        self.types.push(type_);
+        self
+    }
+
+    /// Matches packages with this id and objects of types defined in it.
+    pub fn with_package(mut self, package: ObjectID) -> Self {
+        self.packages.push(package);
+        self
+    }
+
+    /// Matches objects owned by this address, either directly or through a single owner
+    /// consensus authenticator, and objects owned by the object with this id.
+    pub fn with_owner(mut self, owner: SuiAddress) -> Self {
+        self.owners.push(owner);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.packages.is_empty() && self.owners.is_empty()
+    }
+
+    pub fn matches(&self, object: &LiveObject) -> bool {
+        match object {
+            LiveObject::Normal(object) => self.matches_object(object),
+            LiveObject::Wrapped(_) => false,
+        }
+    }
+
+    fn matches_object(&self, object: &Object) -> bool {
+        let struct_tag = object.struct_tag();
+        let type_matches = self.types.is_empty()
+            || struct_tag.as_ref().is_some_and(|tag| {
+                self.types.iter().any(|type_| {
+                    type_.address == tag.address
+                        && type_.module == tag.module
+                        && type_.name == tag.name
+                        && (type_.type_params.is_empty() || type_.type_params == tag.type_params)
+                })
+            });
+        let package_matches = self.packages.is_empty()
+            || self.packages.iter().any(|package| {
+                object.is_package() && object.id() == *package
+                    || struct_tag
+                        .as_ref()
+                        .is_some_and(|tag| ObjectID::from(tag.address) == *package)
+            });
+        let owner = match &object.owner {
+            Owner::AddressOwner(address) | Owner::ObjectOwner(address) => Some(*address),
+            Owner::ConsensusV2 { authenticator, .. } => Some(*authenticator.as_single_owner()),
+            Owner::Shared { .. } | Owner::Immutable => None,
+        };
+        let owner_matches =
+            self.owners.is_empty() || owner.is_some_and(|owner| self.owners.contains(&owner));
+        type_matches && package_matches && owner_matches
+    }
+}
+
+/// Destination of the objects selected by a filtered snapshot restore. Objects are only
+/// written after the object file they came from has been verified against its object refs.
+pub trait LiveObjectSink: Send {
+    fn write(&mut self, objects: Vec<Object>) -> Result<()>;
+
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes restored objects into a standalone store, e.g. for running tests against a subset of
+/// the chain state.
+pub struct PerpetualTablesSink<'a> {
+    perpetual_db: &'a AuthorityPerpetualTables,
+    indirect_objects_threshold: usize,
+}
+
+impl<'a> PerpetualTablesSink<'a> {
+    pub fn new(
+        perpetual_db: &'a AuthorityPerpetualTables,
+        indirect_objects_threshold: usize,
+    ) -> Self {
+        Self {
+            perpetual_db,
+            indirect_objects_threshold,
+        }
+    }
+}
+
+impl LiveObjectSink for PerpetualTablesSink<'_> {
+    fn write(&mut self, objects: Vec<Object>) -> Result<()> {
+        AuthorityStore::insert_verified_live_objects(
+            self.perpetual_db,
+            objects.into_iter().map(LiveObject::Normal),
+            self.indirect_objects_threshold,
+        )?;
+        Ok(())
+    }
+}
+
+/// Writes restored objects into a Parquet file with one row per object, for analytics.
+pub struct ParquetSink {
+    writer: Option<ArrowWriter<File>>,
+}
+
+impl ParquetSink {
+    const COLUMNS: [&'static str; 7] = [
+        "object_id",
+        "version",
+        "digest",
+        "type",
+        "owner_type",
+        "owner",
+        "bcs",
+    ];
+
+    pub fn new(path: &std::path::Path) -> Result<Self> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let schema = Self::record_batch(&[])?.schema();
+        let writer = ArrowWriter::try_new(File::create(path)?, schema, Some(properties))?;
+        Ok(Self {
+            writer: Some(writer),
+        })
+    }
+
+    fn record_batch(objects: &[Object]) -> Result<RecordBatch> {
+        let mut object_ids = vec![];
+        let mut versions = vec![];
+        let mut digests = vec![];
+        let mut types = vec![];
+        let mut owner_types = vec![];
+        let mut owners = vec![];
+        let mut contents = vec![];
+        for object in objects {
+            let (object_id, version, digest) = object.compute_object_reference();
+            object_ids.push(object_id.to_string());
+            versions.push(version.value());
+            digests.push(digest.to_string());
+            types.push(object.struct_tag().map(|tag| tag.to_canonical_string(true)));
+            let (owner_type, owner) = match &object.owner {
+                Owner::AddressOwner(address) => ("AddressOwner", Some(address.to_string())),
+                Owner::ObjectOwner(address) => ("ObjectOwner", Some(address.to_string())),
+                Owner::Shared { .. } => ("Shared", None),
+                Owner::Immutable => ("Immutable", None),
+                Owner::ConsensusV2 { authenticator, .. } => (
+                    "ConsensusV2",
+                    Some(authenticator.as_single_owner().to_string()),
+                ),
+            };
+            owner_types.push(owner_type);
+            owners.push(owner);
+            contents.push(bcs::to_bytes(object)?);
+        }
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(object_ids)),
+            Arc::new(UInt64Array::from(versions)),
+            Arc::new(StringArray::from(digests)),
+            Arc::new(StringArray::from(types)),
+            Arc::new(StringArray::from(owner_types)),
+            Arc::new(StringArray::from(owners)),
+            Arc::new(BinaryArray::from_iter_values(contents)),
+        ];
+        Ok(RecordBatch::try_from_iter(
+            Self::COLUMNS.into_iter().zip(columns),
+        )?)
+    }
+}
+
+impl LiveObjectSink for ParquetSink {
+    fn write(&mut self, objects: Vec<Object>) -> Result<()> {
+        if objects.is_empty() {
+            return Ok(());
+        }
+        let batch = Self::record_batch(&objects)?;
+        self.writer
+            .as_mut()
+            .expect("Parquet sink is already finished")
+            .write(&batch)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.take() {
+            writer.close()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::reader::ObjectRefIter;
+use crate::{FileType, Manifest};
+use anyhow::{anyhow, Result};
+use object_store::path::Path;
+use std::iter::Peekable;
+use std::path::PathBuf;
+use sui_storage::object_store::util::{copy_file, get};
+use sui_storage::object_store::{ObjectStoreGetExt, ObjectStorePutExt};
+use sui_types::base_types::ObjectRef;
+
+pub type ObjectRefStream = Box<dyn Iterator<Item = ObjectRef>>;
+
+/// Reads the manifests of the snapshot at `epoch` and of all the snapshots it is based on,
+/// ordered from the full snapshot at the root of the chain up to `epoch`.
+pub async fn read_manifest_chain<S: ObjectStoreGetExt>(
+    store: &S,
+    epoch: u64,
+) -> Result<Vec<Manifest>> {
+    let mut chain = vec![];
+    let mut next_epoch = Some(epoch);
+    while let Some(epoch) = next_epoch {
+        let manifest_path = Path::from(format!("epoch_{}", epoch)).child("MANIFEST");
+        let manifest = Manifest::from_bytes(&get(store, &manifest_path).await?)?;
+        if manifest.epoch() != epoch {
+            return Err(anyhow!("Download manifest is not for epoch: {}", epoch));
+        }
+        next_epoch = manifest.base_epoch();
+        if matches!(next_epoch, Some(base_epoch) if base_epoch >= epoch) {
+            return Err(anyhow!(
+                "Snapshot for epoch {epoch} has invalid base epoch: {next_epoch:?}"
+            ));
+        }
+        chain.push(manifest);
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Downloads the *.ref and *.del files of a snapshot chain into `dir_path` of the local store,
+/// using the same epoch directory layout as the remote store.
+pub async fn download_chain_refs<S: ObjectStoreGetExt, D: ObjectStorePutExt>(
+    remote_store: &S,
+    local_store: &D,
+    dir_path: &Path,
+    chain: &[Manifest],
+) -> Result<()> {
+    for manifest in chain {
+        let epoch_dir = Path::from(format!("epoch_{}", manifest.epoch()));
+        let local_epoch_dir = epoch_dir_in(dir_path, manifest.epoch());
+        for file_metadata in manifest.file_metadata() {
+            if file_metadata.file_type == FileType::Object {
+                continue;
+            }
+            copy_file(
+                &file_metadata.file_path(&epoch_dir),
+                &file_metadata.file_path(&local_epoch_dir),
+                remote_store,
+                local_store,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the object refs which are live at the end of a snapshot chain, sorted by object id.
+/// The *.ref and *.del files of the chain must have been downloaded with `download_chain_refs`.
+pub fn chain_live_refs(
+    root_path: &std::path::Path,
+    dir_path: &Path,
+    chain: &[Manifest],
+) -> Result<ObjectRefStream> {
+    let mut live_refs: ObjectRefStream = Box::new(std::iter::empty());
+    for manifest in chain {
+        let epoch_dir = epoch_dir_in(dir_path, manifest.epoch());
+        let changed = snapshot_refs(root_path, &epoch_dir, manifest, FileType::Reference)?;
+        let deleted = snapshot_refs(root_path, &epoch_dir, manifest, FileType::Deletion)?;
+        live_refs = Box::new(ApplySnapshotRefs::new(live_refs, deleted, changed));
+    }
+    Ok(live_refs)
+}
+
+fn epoch_dir_in(dir_path: &Path, epoch: u64) -> Path {
+    dir_path.child(format!("epoch_{}", epoch))
+}
+
+/// Chains the refs of all files of `file_type` in a snapshot, in partition order.
+fn snapshot_refs(
+    root_path: &std::path::Path,
+    epoch_dir: &Path,
+    manifest: &Manifest,
+    file_type: FileType,
+) -> Result<ObjectRefStream> {
+    let mut files: Vec<_> = manifest
+        .file_metadata()
+        .iter()
+        .filter(|file_metadata| file_metadata.file_type == file_type)
+        .collect();
+    if files
+        .windows(2)
+        .any(|pair| pair[0].bucket_num != pair[1].bucket_num)
+    {
+        return Err(anyhow!(
+            "Incremental snapshots are only supported for snapshots with a single bucket"
+        ));
+    }
+    files.sort_by_key(|file_metadata| file_metadata.part_num);
+    let iters = files
+        .into_iter()
+        .map(|file_metadata| {
+            ObjectRefIter::new(file_metadata, PathBuf::from(root_path), epoch_dir.clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(iters.into_iter().flatten()))
+}
+
+/// Applies the deleted and changed refs of one incremental snapshot to the live refs of its
+/// base. All three streams are sorted by object id.
+struct ApplySnapshotRefs {
+    base: Peekable<ObjectRefStream>,
+    deleted: Peekable<ObjectRefStream>,
+    changed: Peekable<ObjectRefStream>,
+}
+
+impl ApplySnapshotRefs {
+    fn new(base: ObjectRefStream, deleted: ObjectRefStream, changed: ObjectRefStream) -> Self {
+        Self {
+            base: base.peekable(),
+            deleted: deleted.peekable(),
+            changed: changed.peekable(),
+        }
+    }
+}
+
+impl Iterator for ApplySnapshotRefs {
+    type Item = ObjectRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip over base refs which have been deleted
+        while let Some(base_ref) = self.base.peek() {
+            let base_key = (base_ref.0, base_ref.1);
+            while self
+                .deleted
+                .next_if(|deleted| (deleted.0, deleted.1) < base_key)
+                .is_some()
+            {}
+            if self.deleted.next_if_eq(base_ref).is_none() {
+                break;
+            }
+            self.base.next();
+        }
+        // A changed object is never live in the base with the same id, since its previous
+        // version is always listed in the deleted refs
+        match (self.base.peek().copied(), self.changed.peek().copied()) {
+            (Some(base_ref), Some(changed_ref)) if changed_ref.0 < base_ref.0 => {
+                self.changed.next()
+            }
+            (Some(_), _) => self.base.next(),
+            (None, _) => self.changed.next(),
+        }
+    }
+}
@@ -5,12 +5,15 @@
 #[cfg(test)]
 mod tests;
 
+pub mod filter;
+pub mod incremental;
 pub mod reader;
 pub mod uploader;
 mod writer;
 
-use anyhow::Result;
-use fastcrypto::hash::MultisetHash;
+use anyhow::{anyhow, Result};
+use byteorder::{BigEndian, ByteOrder};
+use fastcrypto::hash::{HashFunction, MultisetHash, Sha3_256};
 use indicatif::MultiProgress;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
@@ -73,6 +76,18 @@ use tokio::time::Instant;
 ///       - 1_1.obj
 ///       - ...
 ///
+/// Incremental Snapshots
+/// An incremental snapshot only holds the objects which changed since the snapshot of an earlier
+/// `base_epoch`, which is recorded in its (V2) MANIFEST. Its *.obj and *.ref files are laid out
+/// exactly like those of a full snapshot but only contain objects whose reference is not part of
+/// the live object set at the base epoch. Object references which are live at the base epoch but
+/// not anymore (because the object was mutated, wrapped or deleted) are listed in deletion files
+/// named <bucket_number>_<partition_number>.del, which use the REFERENCE file disk format. A base
+/// may itself be incremental, so restoring an incremental snapshot means restoring the full
+/// snapshot at the root of its chain and applying every incremental snapshot on top of it, in
+/// order. Incremental snapshots require all objects to be in a single bucket, so that the
+/// references of every layer can be merged as sorted streams.
+///
 /// Object File Disk Format
 ///┌──────────────────────────────┐
 ///│  magic(0x00B7EC75) <4 byte>  │
@@ -143,6 +158,7 @@ const FILE_METADATA_BYTES: usize =
 pub enum FileType {
     Object = 0,
     Reference,
+    Deletion,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -163,6 +179,9 @@ impl FileMetadata {
             FileType::Reference => {
                 dir_path.child(&*format!("{}_{}.ref", self.bucket_num, self.part_num))
             }
+            FileType::Deletion => {
+                dir_path.child(&*format!("{}_{}.del", self.bucket_num, self.part_num))
+            }
         }
     }
     pub fn local_file_path(&self, root_path: &std::path::Path, dir_path: &Path) -> Result<PathBuf> {
@@ -178,30 +197,74 @@ pub struct ManifestV1 {
     pub epoch: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ManifestV2 {
+    pub snapshot_version: u8,
+    pub address_length: u64,
+    pub file_metadata: Vec<FileMetadata>,
+    pub epoch: u64,
+    /// Epoch of the snapshot this incremental snapshot applies on top of.
+    pub base_epoch: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum Manifest {
     V1(ManifestV1),
+    V2(ManifestV2),
 }
 
 impl Manifest {
+    /// Parses the contents of a MANIFEST file, verifying its magic and checksum.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < MAGIC_BYTES + SHA3_BYTES {
+            return Err(anyhow!("Manifest file is too short: {} bytes", bytes.len()));
+        }
+        let magic = BigEndian::read_u32(&bytes[..MAGIC_BYTES]);
+        if magic != MANIFEST_FILE_MAGIC {
+            return Err(anyhow!("Unexpected magic byte: {}", magic));
+        }
+        let (content, sha3_digest) = bytes.split_at(bytes.len() - SHA3_BYTES);
+        let mut hasher = Sha3_256::default();
+        hasher.update(content);
+        let computed_digest = hasher.finalize().digest;
+        if computed_digest != sha3_digest {
+            return Err(anyhow!(
+                "Checksum: {:?} don't match: {:?}",
+                computed_digest,
+                sha3_digest
+            ));
+        }
+        Ok(bcs::from_bytes(&content[MAGIC_BYTES..])?)
+    }
     pub fn snapshot_version(&self) -> u8 {
         match self {
             Self::V1(manifest) => manifest.snapshot_version,
+            Self::V2(manifest) => manifest.snapshot_version,
         }
     }
     pub fn address_length(&self) -> u64 {
         match self {
             Self::V1(manifest) => manifest.address_length,
+            Self::V2(manifest) => manifest.address_length,
         }
     }
     pub fn file_metadata(&self) -> &Vec<FileMetadata> {
         match self {
             Self::V1(manifest) => &manifest.file_metadata,
+            Self::V2(manifest) => &manifest.file_metadata,
         }
     }
     pub fn epoch(&self) -> u64 {
         match self {
             Self::V1(manifest) => manifest.epoch,
+            Self::V2(manifest) => manifest.epoch,
+        }
+    }
+    /// Returns the epoch of the base snapshot for incremental snapshots, `None` for full ones.
+    pub fn base_epoch(&self) -> Option<u64> {
+        match self {
+            Self::V1(_) => None,
+            Self::V2(manifest) => Some(manifest.base_epoch),
         }
     }
 }
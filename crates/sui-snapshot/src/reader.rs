@@ -1,24 +1,25 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::filter::{LiveObjectFilter, LiveObjectSink};
 use crate::{
-    FileMetadata, FileType, Manifest, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
-    OBJECT_ID_BYTES, OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES, SHA3_BYTES,
+    FileMetadata, FileType, Manifest, OBJECT_FILE_MAGIC, OBJECT_ID_BYTES, OBJECT_REF_BYTES,
+    REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES,
 };
 use anyhow::{anyhow, Context, Result};
 use byteorder::{BigEndian, ReadBytesExt};
 use bytes::{Buf, Bytes};
 use fastcrypto::hash::MultisetHash;
 use fastcrypto::hash::{HashFunction, Sha3_256};
+use futures::future::BoxFuture;
 use futures::future::{AbortRegistration, Abortable};
 use futures::{StreamExt, TryStreamExt};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use integer_encoding::VarIntReader;
 use object_store::path::Path;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::Read;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
@@ -33,6 +34,7 @@ use sui_storage::object_store::util::{copy_file, copy_files, path_to_filesystem}
 use sui_storage::object_store::{ObjectStoreGetExt, ObjectStoreListExt, ObjectStorePutExt};
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
+use sui_types::object::Object;
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
@@ -42,6 +44,38 @@ use tracing::{error, info};
 pub type SnapshotChecksums = (DigestByBucketAndPartition, Accumulator);
 pub type DigestByBucketAndPartition = BTreeMap<u32, BTreeMap<u32, [u8; 32]>>;
 pub type Sha3DigestType = Arc<Mutex<BTreeMap<u32, BTreeMap<u32, [u8; 32]>>>>;
+
+/// Object refs of a snapshot of a chain which are deleted by the snapshots applied on top of it,
+/// i.e. which are not live anymore at the end of the chain.
+#[derive(Clone, Default)]
+pub struct DeletedRefs(Vec<Arc<HashSet<ObjectRef>>>);
+
+impl DeletedRefs {
+    pub fn contains(&self, object_ref: &ObjectRef) -> bool {
+        self.0.iter().any(|deleted| deleted.contains(object_ref))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|deleted| deleted.is_empty())
+    }
+}
+
+/// Object files of one snapshot of a chain, exported for the indexer restorers. Only the objects
+/// for which `is_live` returns true are live at the end of the chain and should be restored.
+pub struct SnapshotLayer {
+    pub epoch_dir: Path,
+    pub input_files: Vec<(u32, (u32, FileMetadata))>,
+    pub sha3_digests: Sha3DigestType,
+    pub num_part_files: usize,
+    deleted: DeletedRefs,
+}
+
+impl SnapshotLayer {
+    pub fn is_live(&self, object_ref: &ObjectRef) -> bool {
+        !self.deleted.contains(object_ref)
+    }
+}
+
 #[derive(Clone)]
 pub struct StateSnapshotReaderV1 {
     epoch: u64,
@@ -50,6 +84,9 @@ pub struct StateSnapshotReaderV1 {
     local_object_store: Arc<dyn ObjectStorePutExt>,
     ref_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
     object_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    deletion_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    /// Reader for the base snapshot if this is an incremental snapshot
+    base: Option<Box<StateSnapshotReaderV1>>,
     indirect_objects_threshold: usize,
     m: MultiProgress,
     concurrency: usize,
@@ -101,7 +138,7 @@ impl StateSnapshotReaderV1 {
             &manifest_file_path,
         )?)?;
         let snapshot_version = manifest.snapshot_version();
-        if snapshot_version != 1u8 {
+        if !(1u8..=2).contains(&snapshot_version) {
             return Err(anyhow!("Unexpected snapshot version: {}", snapshot_version));
         }
         if manifest.address_length() as usize > ObjectID::LENGTH {
@@ -113,8 +150,29 @@ impl StateSnapshotReaderV1 {
         if manifest.epoch() != epoch {
             return Err(anyhow!("Download manifest is not for epoch: {}", epoch,));
         }
+        let base = match manifest.base_epoch() {
+            Some(base_epoch) if base_epoch >= epoch => {
+                return Err(anyhow!(
+                    "Snapshot for epoch {epoch} has invalid base epoch: {base_epoch}"
+                ));
+            }
+            Some(base_epoch) => Some(Box::new(
+                Self::new_base(
+                    base_epoch,
+                    remote_store_config,
+                    local_store_config,
+                    indirect_objects_threshold,
+                    download_concurrency,
+                    m.clone(),
+                    skip_reset_local_store,
+                )
+                .await?,
+            )),
+            None => None,
+        };
         let mut object_files = BTreeMap::new();
         let mut ref_files = BTreeMap::new();
+        let mut deletion_files = BTreeMap::new();
         for file_metadata in manifest.file_metadata() {
             match file_metadata.file_type {
                 FileType::Object => {
@@ -129,11 +187,18 @@ impl StateSnapshotReaderV1 {
                         .or_insert_with(BTreeMap::new);
                     entry.insert(file_metadata.part_num, file_metadata.clone());
                 }
+                FileType::Deletion => {
+                    let entry = deletion_files
+                        .entry(file_metadata.bucket_num)
+                        .or_insert_with(BTreeMap::new);
+                    entry.insert(file_metadata.part_num, file_metadata.clone());
+                }
             }
         }
         let epoch_dir_path = Path::from(epoch_dir);
         let files: Vec<Path> = ref_files
             .values()
+            .chain(deletion_files.values())
             .flat_map(|entry| {
                 let files: Vec<_> = entry
                     .values()
@@ -186,17 +251,83 @@ impl StateSnapshotReaderV1 {
             local_object_store,
             ref_files,
             object_files,
+            deletion_files,
+            base,
             indirect_objects_threshold,
             m,
             concurrency: download_concurrency.get(),
         })
     }
 
+    /// Boxes the reader of a base snapshot, since `new` is recursive for snapshot chains.
+    #[allow(clippy::too_many_arguments)]
+    fn new_base<'a>(
+        epoch: u64,
+        remote_store_config: &'a ObjectStoreConfig,
+        local_store_config: &'a ObjectStoreConfig,
+        indirect_objects_threshold: usize,
+        download_concurrency: NonZeroUsize,
+        m: MultiProgress,
+        skip_reset_local_store: bool,
+    ) -> BoxFuture<'a, Result<Self>> {
+        Box::pin(Self::new(
+            epoch,
+            remote_store_config,
+            local_store_config,
+            indirect_objects_threshold,
+            download_concurrency,
+            m,
+            skip_reset_local_store,
+        ))
+    }
+
+    /// Returns the epochs of the snapshots this reader restores, starting with the full
+    /// snapshot at the root of the chain.
+    pub fn snapshot_chain(&self) -> Vec<u64> {
+        let mut chain = self
+            .base
+            .as_ref()
+            .map(|base| base.snapshot_chain())
+            .unwrap_or_default();
+        chain.push(self.epoch);
+        chain
+    }
+
     pub async fn read(
         &mut self,
         perpetual_db: &AuthorityPerpetualTables,
         abort_registration: AbortRegistration,
         sender: Option<tokio::sync::mpsc::Sender<(Accumulator, u64)>>,
+    ) -> Result<()> {
+        Abortable::new(self.read_chain(perpetual_db, sender), abort_registration).await?
+    }
+
+    /// Restores every snapshot of the chain ending at this one, from the latest to the full
+    /// snapshot at its root. Only the objects of each snapshot which are still live at the end of
+    /// the chain are restored and accumulated, so objects replaced later in the chain are never
+    /// written, and object files holding none of the live objects are not even downloaded.
+    async fn read_chain(
+        &mut self,
+        perpetual_db: &AuthorityPerpetualTables,
+        sender: Option<tokio::sync::mpsc::Sender<(Accumulator, u64)>>,
+    ) -> Result<()> {
+        let mut deleted = DeletedRefs::default();
+        let mut reader = Some(self);
+        while let Some(current) = reader {
+            current
+                .read_snapshot(perpetual_db, &deleted, sender.clone())
+                .await?;
+            deleted.0.push(Arc::new(current.deleted_refs()?));
+            reader = current.base.as_deref_mut();
+        }
+        Ok(())
+    }
+
+    async fn read_snapshot(
+        &mut self,
+        perpetual_db: &AuthorityPerpetualTables,
+        deleted: &DeletedRefs,
+        sender: Option<tokio::sync::mpsc::Sender<(Accumulator, u64)>>,
     ) -> Result<()> {
         // This computes and stores the sha3 digest of object references in REFERENCE file for each
         // bucket partition. When downloading objects, we will match sha3 digest of object references
@@ -204,9 +335,9 @@ impl StateSnapshotReaderV1 {
         // references and start building state accumulator and fail early if the state root hash
         // doesn't match but we still need to ensure that objects match references exactly.
         let (sha3_digests, num_part_files) = self.compute_checksum().await?;
-        let accum_handle =
-            sender.map(|sender| self.spawn_accumulation_tasks(sender, num_part_files));
-        self.sync_live_objects(perpetual_db, abort_registration, sha3_digests)
+        let accum_handle = sender
+            .map(|sender| self.spawn_accumulation_tasks(sender, num_part_files, deleted.clone()));
+        self.sync_live_objects(perpetual_db, sha3_digests, deleted)
             .await?;
         if let Some(handle) = accum_handle {
             handle.await?;
@@ -287,10 +418,13 @@ impl StateSnapshotReaderV1 {
         Ok((sha3_digests, num_part_files))
     }
 
+    /// Accumulates the object refs of the snapshot which are not in `deleted`, sending one partial
+    /// accumulator and number of objects per REFERENCE file to `sender`.
     fn spawn_accumulation_tasks(
         &self,
         sender: tokio::sync::mpsc::Sender<(Accumulator, u64)>,
         num_part_files: usize,
+        deleted: DeletedRefs,
     ) -> JoinHandle<()> {
         // Spawn accumulation progress bar
         let concurrency = self.concurrency;
@@ -351,6 +485,7 @@ impl StateSnapshotReaderV1 {
                             )
                             .expect("Failed to create object ref iter")
                         }
+                        .filter(|obj_ref| !deleted.contains(obj_ref))
                         .map(|obj_ref| obj_ref.2)
                         .collect::<Vec<ObjectDigest>>();
                         let sender_clone = sender.clone();
@@ -380,24 +515,14 @@ impl StateSnapshotReaderV1 {
     async fn sync_live_objects(
         &self,
         perpetual_db: &AuthorityPerpetualTables,
-        abort_registration: AbortRegistration,
         sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
+        deleted: &DeletedRefs,
     ) -> Result<(), anyhow::Error> {
         let epoch_dir = self.epoch_dir();
         let concurrency = self.concurrency;
         let threshold = self.indirect_objects_threshold;
         let remote_object_store = self.remote_object_store.clone();
-        let input_files: Vec<_> = self
-            .object_files
-            .iter()
-            .flat_map(|(bucket, parts)| {
-                parts
-                    .clone()
-                    .into_iter()
-                    .map(|entry| (bucket, entry))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
+        let input_files = self.live_object_files(deleted)?;
         let obj_progress_bar = self.m.add(
             ProgressBar::new(input_files.len() as u64).with_style(
                 ProgressStyle::with_template(
@@ -410,92 +535,278 @@ impl StateSnapshotReaderV1 {
         let instant = Instant::now();
         let downloaded_bytes = AtomicUsize::new(0);
 
-        let ret = Abortable::new(
-            async move {
-                futures::stream::iter(input_files.iter())
-                    .map(|(bucket, (part_num, file_metadata))| {
-                        let epoch_dir_clone = epoch_dir.clone();
-                        let remote_object_store_clone = remote_object_store.clone();
-                        let sha3_digests_clone = sha3_digests.clone();
-                        async move {
-                            // Download object file with retries
-                            let (bytes, sha3_digest) = download_bytes(
-                                remote_object_store_clone,
-                                file_metadata,
-                                epoch_dir_clone,
-                                sha3_digests_clone,
-                                bucket,
-                                part_num,
-                                None,
-                            )
-                            .await;
-                            Ok::<(Bytes, FileMetadata, [u8; 32]), anyhow::Error>((
-                                bytes,
-                                (*file_metadata).clone(),
-                                sha3_digest,
-                            ))
-                        }
+        let ret = futures::stream::iter(input_files.iter())
+            .map(|(bucket, (part_num, file_metadata))| {
+                let epoch_dir_clone = epoch_dir.clone();
+                let remote_object_store_clone = remote_object_store.clone();
+                let sha3_digests_clone = sha3_digests.clone();
+                async move {
+                    // Download object file with retries
+                    let (bytes, sha3_digest) = download_bytes(
+                        remote_object_store_clone,
+                        file_metadata,
+                        epoch_dir_clone,
+                        sha3_digests_clone,
+                        &bucket,
+                        part_num,
+                        None,
+                    )
+                    .await;
+                    Ok::<(Bytes, FileMetadata, [u8; 32]), anyhow::Error>((
+                        bytes,
+                        (*file_metadata).clone(),
+                        sha3_digest,
+                    ))
+                }
+            })
+            .boxed()
+            .buffer_unordered(concurrency)
+            .try_for_each(|(bytes, file_metadata, sha3_digest)| {
+                let bytes_len = bytes.len();
+                let result: Result<(), anyhow::Error> = if deleted.is_empty() {
+                    LiveObjectIter::new(&file_metadata, bytes).map(|obj_iter| {
+                        AuthorityStore::bulk_insert_live_objects(
+                            perpetual_db,
+                            obj_iter,
+                            threshold,
+                            &sha3_digest,
+                        )
+                        .expect("Failed to insert live objects");
                     })
-                    .boxed()
-                    .buffer_unordered(concurrency)
-                    .try_for_each(|(bytes, file_metadata, sha3_digest)| {
-                        let bytes_len = bytes.len();
-                        let result: Result<(), anyhow::Error> =
-                            LiveObjectIter::new(&file_metadata, bytes).map(|obj_iter| {
-                                AuthorityStore::bulk_insert_live_objects(
-                                    perpetual_db,
-                                    obj_iter,
-                                    threshold,
-                                    &sha3_digest,
-                                )
-                                .expect("Failed to insert live objects");
-                            });
-                        downloaded_bytes.fetch_add(bytes_len, Ordering::Relaxed);
-                        obj_progress_bar_clone.inc(1);
-                        obj_progress_bar_clone.set_message(format!(
-                            "Download speed: {} MiB/s",
-                            downloaded_bytes.load(Ordering::Relaxed) as f64
-                                / (1024 * 1024) as f64
-                                / instant.elapsed().as_secs_f64(),
-                        ));
-                        futures::future::ready(result)
+                } else {
+                    Self::verified_live_objects(&file_metadata, bytes, &sha3_digest, |object| {
+                        !deleted.contains(&object.object_reference())
+                    })
+                    .map(|objects| {
+                        AuthorityStore::insert_verified_live_objects(
+                            perpetual_db,
+                            objects.into_iter(),
+                            threshold,
+                        )
+                        .expect("Failed to insert live objects");
                     })
-                    .await
+                };
+                downloaded_bytes.fetch_add(bytes_len, Ordering::Relaxed);
+                obj_progress_bar_clone.inc(1);
+                obj_progress_bar_clone.set_message(format!(
+                    "Download speed: {} MiB/s",
+                    downloaded_bytes.load(Ordering::Relaxed) as f64
+                        / (1024 * 1024) as f64
+                        / instant.elapsed().as_secs_f64(),
+                ));
+                futures::future::ready(result)
+            })
+            .await;
+        obj_progress_bar.finish_with_message("Objects download complete");
+        ret
+    }
+
+    /// Restores only the live objects matching `filter` into `sink` and returns their number.
+    /// Every object file holding objects live at the end of the snapshot chain is still
+    /// downloaded and verified against the object refs of its snapshot, and accumulator partials
+    /// over all live object refs are sent to `sender`, so that the restored subset can be proven
+    /// to be part of the state committed to by the root state hash.
+    pub async fn read_filtered(
+        &mut self,
+        filter: &LiveObjectFilter,
+        sink: &mut dyn LiveObjectSink,
+        abort_registration: AbortRegistration,
+        sender: Option<tokio::sync::mpsc::Sender<(Accumulator, u64)>>,
+    ) -> Result<u64> {
+        Abortable::new(
+            async move {
+                let mut num_objects = 0;
+                let mut deleted = DeletedRefs::default();
+                let mut reader = Some(self);
+                while let Some(current) = reader {
+                    let (sha3_digests, num_part_files) = current.compute_checksum().await?;
+                    let accum_handle = sender.clone().map(|sender| {
+                        current.spawn_accumulation_tasks(sender, num_part_files, deleted.clone())
+                    });
+                    num_objects += current
+                        .sync_filtered_objects(filter, sink, sha3_digests, &deleted)
+                        .await?;
+                    if let Some(handle) = accum_handle {
+                        handle.await?;
+                    }
+                    deleted.0.push(Arc::new(current.deleted_refs()?));
+                    reader = current.base.as_deref_mut();
+                }
+                sink.finish()?;
+                Ok(num_objects)
             },
             abort_registration,
         )
-        .await?;
-        obj_progress_bar.finish_with_message("Objects download complete");
-        ret
+        .await?
     }
 
-    // NOTE: export these metadata for indexer restorer
-    pub async fn export_metadata(
+    async fn sync_filtered_objects(
         &self,
-    ) -> Result<
-        (
-            Vec<(&u32, (u32, FileMetadata))>,
-            Path,
-            Arc<dyn ObjectStoreGetExt>,
-            usize,
-        ),
-        anyhow::Error,
-    > {
+        filter: &LiveObjectFilter,
+        sink: &mut dyn LiveObjectSink,
+        sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
+        deleted: &DeletedRefs,
+    ) -> Result<u64> {
         let epoch_dir = self.epoch_dir();
-        let concurrency = self.concurrency;
         let remote_object_store = self.remote_object_store.clone();
-        let input_files: Vec<(&u32, (u32, FileMetadata))> = self
-            .object_files
-            .iter()
-            .flat_map(|(bucket, parts)| {
-                parts
-                    .clone()
-                    .into_iter()
-                    .map(|entry| (bucket, entry))
-                    .collect::<Vec<_>>()
+        let input_files = self.live_object_files(deleted)?;
+        let obj_progress_bar = self.m.add(
+            ProgressBar::new(input_files.len() as u64).with_style(
+                ProgressStyle::with_template(
+                    "[{elapsed_precise}] {wide_bar} {pos} out of {len} .obj files filtered ({msg})",
+                )
+                .unwrap(),
+            ),
+        );
+        let mut num_objects = 0u64;
+        futures::stream::iter(input_files.iter())
+            .map(|(bucket, (part_num, file_metadata))| {
+                let epoch_dir_clone = epoch_dir.clone();
+                let remote_object_store_clone = remote_object_store.clone();
+                let sha3_digests_clone = sha3_digests.clone();
+                async move {
+                    // Download object file with retries
+                    let (bytes, sha3_digest) = download_bytes(
+                        remote_object_store_clone,
+                        file_metadata,
+                        epoch_dir_clone,
+                        sha3_digests_clone,
+                        &bucket,
+                        part_num,
+                        None,
+                    )
+                    .await;
+                    Ok::<(Bytes, FileMetadata, [u8; 32]), anyhow::Error>((
+                        bytes,
+                        (*file_metadata).clone(),
+                        sha3_digest,
+                    ))
+                }
             })
-            .collect();
-        Ok((input_files, epoch_dir, remote_object_store, concurrency))
+            .boxed()
+            .buffer_unordered(self.concurrency)
+            .try_for_each(|(bytes, file_metadata, sha3_digest)| {
+                let result =
+                    Self::filter_live_objects(&file_metadata, bytes, filter, deleted, &sha3_digest)
+                        .and_then(|objects| {
+                            num_objects += objects.len() as u64;
+                            sink.write(objects)
+                        });
+                obj_progress_bar.inc(1);
+                obj_progress_bar.set_message(format!("Objects restored: {}", num_objects));
+                futures::future::ready(result)
+            })
+            .await?;
+        obj_progress_bar.finish_with_message("Objects filtering complete");
+        Ok(num_objects)
+    }
+
+    /// Verifies the objects of an object file against the sha3 digest of its object refs and
+    /// returns those matching `filter` which are not in `deleted`.
+    fn filter_live_objects(
+        file_metadata: &FileMetadata,
+        bytes: Bytes,
+        filter: &LiveObjectFilter,
+        deleted: &DeletedRefs,
+        expected_sha3_digest: &[u8; 32],
+    ) -> Result<Vec<Object>> {
+        let objects =
+            Self::verified_live_objects(file_metadata, bytes, expected_sha3_digest, |object| {
+                filter.matches(object) && !deleted.contains(&object.object_reference())
+            })?;
+        Ok(objects
+            .into_iter()
+            .filter_map(|object| match object {
+                LiveObject::Normal(object) => Some(object),
+                LiveObject::Wrapped(_) => None,
+            })
+            .collect())
+    }
+
+    /// Verifies all the objects of an object file against the sha3 digest of its object refs and
+    /// returns those for which `keep` returns true.
+    fn verified_live_objects(
+        file_metadata: &FileMetadata,
+        bytes: Bytes,
+        expected_sha3_digest: &[u8; 32],
+        keep: impl Fn(&LiveObject) -> bool,
+    ) -> Result<Vec<LiveObject>> {
+        let mut hasher = Sha3_256::default();
+        let mut objects = vec![];
+        for object in LiveObjectIter::new(file_metadata, bytes)? {
+            hasher.update(object.object_reference().2.inner());
+            if keep(&object) {
+                objects.push(object);
+            }
+        }
+        let sha3_digest = hasher.finalize().digest;
+        if sha3_digest != *expected_sha3_digest {
+            return Err(anyhow!(
+                "Sha does not match for bucket: {}, part: {}! expected: {:?}, actual: {:?}",
+                file_metadata.bucket_num,
+                file_metadata.part_num,
+                expected_sha3_digest,
+                sha3_digest
+            ));
+        }
+        Ok(objects)
+    }
+
+    /// Returns the object refs deleted by this snapshot, which are empty for a full snapshot.
+    fn deleted_refs(&self) -> Result<HashSet<ObjectRef>> {
+        let mut deleted = HashSet::new();
+        for (bucket, parts) in &self.deletion_files {
+            for part in parts.keys() {
+                deleted.extend(self.deletion_iter(*bucket, *part)?);
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Returns the object files of this snapshot, skipping those whose objects are all in
+    /// `deleted` as none of them would be restored.
+    fn live_object_files(&self, deleted: &DeletedRefs) -> Result<Vec<(u32, (u32, FileMetadata))>> {
+        let mut input_files = vec![];
+        for (bucket, parts) in &self.object_files {
+            for (part, file_metadata) in parts {
+                if !deleted.is_empty()
+                    && self
+                        .ref_iter(*bucket, *part)?
+                        .all(|object_ref| deleted.contains(&object_ref))
+                {
+                    continue;
+                }
+                input_files.push((*bucket, (*part, file_metadata.clone())));
+            }
+        }
+        Ok(input_files)
+    }
+
+    // NOTE: export these metadata for indexer restorer
+    /// Returns the object files and checksums of every snapshot of the chain ending at this one,
+    /// starting with this snapshot. The indexer restorers should only restore the objects of a
+    /// layer for which `SnapshotLayer::is_live` returns true.
+    pub async fn export_metadata(
+        &mut self,
+    ) -> Result<(Vec<SnapshotLayer>, Arc<dyn ObjectStoreGetExt>, usize), anyhow::Error> {
+        let remote_object_store = self.remote_object_store.clone();
+        let concurrency = self.concurrency;
+        let mut layers = vec![];
+        let mut deleted = DeletedRefs::default();
+        let mut reader = Some(self);
+        while let Some(current) = reader {
+            let (sha3_digests, num_part_files) = current.compute_checksum().await?;
+            layers.push(SnapshotLayer {
+                epoch_dir: current.epoch_dir(),
+                input_files: current.live_object_files(&deleted)?,
+                sha3_digests,
+                num_part_files,
+                deleted: deleted.clone(),
+            });
+            deleted.0.push(Arc::new(current.deleted_refs()?));
+            reader = current.base.as_deref_mut();
+        }
+        Ok((layers, remote_object_store, concurrency))
     }
 
     pub fn ref_iter(&self, bucket_num: u32, part_num: u32) -> Result<ObjectRefIter> {
@@ -514,6 +825,22 @@ impl StateSnapshotReaderV1 {
         )
     }
 
+    pub fn deletion_iter(&self, bucket_num: u32, part_num: u32) -> Result<ObjectRefIter> {
+        let file_metadata = self
+            .deletion_files
+            .get(&bucket_num)
+            .context(format!("No deletion files found for bucket: {bucket_num}"))?
+            .get(&part_num)
+            .context(format!(
+                "No deletion files found for bucket: {bucket_num}, part: {part_num}"
+            ))?;
+        ObjectRefIter::new(
+            file_metadata,
+            self.local_staging_dir_root.clone(),
+            self.epoch_dir(),
+        )
+    }
+
     fn buckets(&self) -> Result<Vec<u32>> {
         Ok(self.ref_files.keys().copied().collect())
     }
@@ -523,34 +850,7 @@ impl StateSnapshotReaderV1 {
     }
 
     fn read_manifest(path: PathBuf) -> anyhow::Result<Manifest> {
-        let manifest_file = File::open(path)?;
-        let manifest_file_size = manifest_file.metadata()?.len() as usize;
-        let mut manifest_reader = BufReader::new(manifest_file);
-        manifest_reader.rewind()?;
-        let magic = manifest_reader.read_u32::<BigEndian>()?;
-        if magic != MANIFEST_FILE_MAGIC {
-            return Err(anyhow!("Unexpected magic byte: {}", magic));
-        }
-        manifest_reader.seek(SeekFrom::End(-(SHA3_BYTES as i64)))?;
-        let mut sha3_digest = [0u8; SHA3_BYTES];
-        manifest_reader.read_exact(&mut sha3_digest)?;
-        manifest_reader.rewind()?;
-        let mut content_buf = vec![0u8; manifest_file_size - SHA3_BYTES];
-        manifest_reader.read_exact(&mut content_buf)?;
-        let mut hasher = Sha3_256::default();
-        hasher.update(&content_buf);
-        let computed_digest = hasher.finalize().digest;
-        if computed_digest != sha3_digest {
-            return Err(anyhow!(
-                "Checksum: {:?} don't match: {:?}",
-                computed_digest,
-                sha3_digest
-            ));
-        }
-        manifest_reader.rewind()?;
-        manifest_reader.seek(SeekFrom::Start(MAGIC_BYTES as u64))?;
-        let manifest = bcs::from_bytes(&content_buf[MAGIC_BYTES..])?;
-        Ok(manifest)
+        Manifest::from_bytes(&fs::read(path)?)
     }
 
     pub fn get_multi_progress(&self) -> MultiProgress {
@@ -1,23 +1,29 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::filter::{LiveObjectFilter, LiveObjectSink, ParquetSink, PerpetualTablesSink};
 use crate::reader::StateSnapshotReaderV1;
 use crate::writer::StateSnapshotWriterV1;
 use crate::FileCompression;
 use fastcrypto::hash::MultisetHash;
 use futures::future::AbortHandle;
 use indicatif::MultiProgress;
+use move_core_types::language_storage::StructTag;
+use parquet::file::reader::{FileReader, SerializedFileReader};
 use std::collections::HashSet;
 use std::num::NonZeroUsize;
+use std::str::FromStr;
 use std::sync::Arc;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
-use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::authority::AuthorityStore;
 use sui_core::state_accumulator::StateAccumulator;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::accumulator::Accumulator;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+use sui_types::gas_coin::GasCoin;
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
-use sui_types::object::Object;
+use sui_types::object::{Object, Owner};
 use tempfile::tempdir;
 
 fn temp_dir() -> std::path::PathBuf {
@@ -183,3 +189,284 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
     )?;
     Ok(())
 }
+
+async fn write_epoch_snapshot(
+    local_store_config: &ObjectStoreConfig,
+    remote_store_config: &ObjectStoreConfig,
+    perpetual_db: &Arc<AuthorityPerpetualTables>,
+    epoch: u64,
+    base_epoch: Option<u64>,
+) -> Result<(), anyhow::Error> {
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        local_store_config,
+        remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let root_accumulator =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(perpetual_db, true).digest());
+    match base_epoch {
+        Some(base_epoch) => {
+            snapshot_writer
+                .write_incremental_internal(
+                    epoch,
+                    base_epoch,
+                    true,
+                    perpetual_db.clone(),
+                    root_accumulator,
+                )
+                .await
+        }
+        None => {
+            snapshot_writer
+                .write_internal(epoch, true, perpetual_db.clone(), root_accumulator)
+                .await
+        }
+    }
+}
+
+fn remove_objects(db: &AuthorityPerpetualTables, ids: &[ObjectID]) -> Result<(), anyhow::Error> {
+    let refs = ids
+        .iter()
+        .map(|id| Object::immutable_with_id_for_testing(*id).compute_object_reference());
+    AuthorityStore::bulk_remove_live_objects(db, refs, usize::MAX)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_incremental_snapshot_chain() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let restored_db_path = temp_dir();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir")),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("remote_dir")),
+        ..Default::default()
+    };
+    let ids = ObjectID::in_range(ObjectID::ZERO, 1150)?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None));
+    insert_keys(&perpetual_db, 1000)?;
+    write_epoch_snapshot(
+        &local_store_config,
+        &remote_store_config,
+        &perpetual_db,
+        0,
+        None,
+    )
+    .await?;
+
+    // Delete, mutate and create objects on top of the full snapshot
+    remove_objects(&perpetual_db, &ids[0..100])?;
+    remove_objects(&perpetual_db, &ids[100..200])?;
+    for id in &ids[100..200] {
+        perpetual_db.insert_object_test_only(Object::with_id_owner_version_for_testing(
+            *id,
+            SequenceNumber::from_u64(10),
+            Owner::Immutable,
+        ))?;
+    }
+    for id in &ids[1000..1100] {
+        perpetual_db.insert_object_test_only(Object::immutable_with_id_for_testing(*id))?;
+    }
+    write_epoch_snapshot(
+        &local_store_config,
+        &remote_store_config,
+        &perpetual_db,
+        1,
+        Some(0),
+    )
+    .await?;
+
+    // Chain a second incremental snapshot on top of the first one
+    remove_objects(&perpetual_db, &ids[200..250])?;
+    for id in &ids[1100..1150] {
+        perpetual_db.insert_object_test_only(Object::immutable_with_id_for_testing(*id))?;
+    }
+    write_epoch_snapshot(
+        &local_store_config,
+        &remote_store_config,
+        &perpetual_db,
+        2,
+        Some(1),
+    )
+    .await?;
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir_restore")),
+        ..Default::default()
+    };
+    let mut snapshot_reader = StateSnapshotReaderV1::new(
+        2,
+        &remote_store_config,
+        &local_store_restore_config,
+        usize::MAX,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+        false, // skip_reset_local_store
+    )
+    .await?;
+    assert_eq!(snapshot_reader.snapshot_chain(), vec![0, 1, 2]);
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(1000);
+    snapshot_reader
+        .read(&restored_perpetual_db, abort_registration, Some(sender))
+        .await?;
+    let mut restored_accumulator = Accumulator::default();
+    let mut num_live_objects = 0;
+    while let Some((partial_acc, num_objects)) = receiver.recv().await {
+        restored_accumulator.union(&partial_acc);
+        num_live_objects += num_objects;
+    }
+    assert_eq!(
+        restored_accumulator.digest(),
+        accumulate_live_object_set(&perpetual_db, true).digest()
+    );
+    assert_eq!(
+        num_live_objects,
+        perpetual_db.iter_live_object_set(true).count() as u64
+    );
+    compare_live_objects(&perpetual_db, &restored_perpetual_db, true)?;
+
+    // A filtered restore of the chain only restores the objects live at its end
+    let filtered_db_path = temp_dir();
+    let filtered_perpetual_db = AuthorityPerpetualTables::open(&filtered_db_path, None);
+    let mut sink = PerpetualTablesSink::new(&filtered_perpetual_db, usize::MAX);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(1000);
+    let num_objects = snapshot_reader
+        .read_filtered(
+            &LiveObjectFilter::new(),
+            &mut sink,
+            abort_registration,
+            Some(sender),
+        )
+        .await?;
+    let mut filtered_accumulator = Accumulator::default();
+    while let Some((partial_acc, _)) = receiver.recv().await {
+        filtered_accumulator.union(&partial_acc);
+    }
+    assert_eq!(filtered_accumulator.digest(), restored_accumulator.digest());
+    assert_eq!(
+        num_objects,
+        perpetual_db.iter_live_object_set(false).count() as u64
+    );
+    compare_live_objects(&perpetual_db, &filtered_perpetual_db, false)?;
+    Ok(())
+}
+
+#[test]
+fn test_live_object_filter() {
+    let owner = SuiAddress::random_for_testing_only();
+    let owned = LiveObject::Normal(Object::with_id_owner_for_testing(ObjectID::random(), owner));
+    let immutable = LiveObject::Normal(Object::immutable_for_testing());
+
+    assert!(LiveObjectFilter::new().matches(&owned));
+    assert!(LiveObjectFilter::new().with_owner(owner).matches(&owned));
+    assert!(!LiveObjectFilter::new()
+        .with_owner(owner)
+        .matches(&immutable));
+
+    let coin_type = StructTag::from_str("0x2::coin::Coin").unwrap();
+    assert!(LiveObjectFilter::new().with_type(coin_type).matches(&owned));
+    assert!(LiveObjectFilter::new()
+        .with_type(GasCoin::type_())
+        .with_owner(owner)
+        .matches(&owned));
+    let other_type = StructTag::from_str("0x2::coin::TreasuryCap").unwrap();
+    assert!(!LiveObjectFilter::new()
+        .with_type(other_type)
+        .matches(&owned));
+
+    assert!(LiveObjectFilter::new()
+        .with_package(ObjectID::from_single_byte(2))
+        .matches(&immutable));
+    assert!(!LiveObjectFilter::new()
+        .with_package(ObjectID::from_single_byte(3))
+        .matches(&immutable));
+}
+
+#[tokio::test]
+async fn test_filtered_snapshot_restore() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let restored_db_path = temp_dir();
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("local_dir")),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(temp_dir().join("remote_dir")),
+        ..Default::default()
+    };
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let owner = SuiAddress::random_for_testing_only();
+    let owned_ids = ObjectID::in_range(ObjectID::from_hex_literal("0x10000")?, 100)?;
+    for id in &owned_ids {
+        perpetual_db.insert_object_test_only(Object::with_id_owner_for_testing(*id, owner))?;
+    }
+    let root_accumulator = accumulate_live_object_set(&perpetual_db, true);
+    write_epoch_snapshot(
+        &local_store_config,
+        &remote_store_config,
+        &perpetual_db,
+        0,
+        None,
+    )
+    .await?;
+
+    let filter = LiveObjectFilter::new().with_owner(owner);
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None);
+    let mut sinks: Vec<Box<dyn LiveObjectSink + '_>> = vec![
+        Box::new(PerpetualTablesSink::new(&restored_perpetual_db, usize::MAX)),
+        Box::new(ParquetSink::new(&db_path.join("objects.parquet"))?),
+    ];
+    for sink in sinks.iter_mut() {
+        let local_store_restore_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(temp_dir().join("local_dir_restore")),
+            ..Default::default()
+        };
+        let mut snapshot_reader = StateSnapshotReaderV1::new(
+            0,
+            &remote_store_config,
+            &local_store_restore_config,
+            usize::MAX,
+            NonZeroUsize::new(1).unwrap(),
+            MultiProgress::new(),
+            false, // skip_reset_local_store
+        )
+        .await?;
+        let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(1000);
+        let num_objects = snapshot_reader
+            .read_filtered(&filter, sink.as_mut(), abort_registration, Some(sender))
+            .await?;
+        assert_eq!(num_objects, owned_ids.len() as u64);
+        // The accumulator still covers the whole live object set
+        let mut restored_accumulator = Accumulator::default();
+        while let Some((partial_acc, _)) = receiver.recv().await {
+            restored_accumulator.union(&partial_acc);
+        }
+        assert_eq!(restored_accumulator.digest(), root_accumulator.digest());
+    }
+    drop(sinks);
+
+    let restored_ids: HashSet<_> = restored_perpetual_db
+        .iter_live_object_set(true)
+        .map(|live_object| live_object.object_id())
+        .collect();
+    assert_eq!(restored_ids, owned_ids.into_iter().collect());
+    let parquet_reader =
+        SerializedFileReader::new(std::fs::File::open(db_path.join("objects.parquet"))?)?;
+    assert_eq!(parquet_reader.metadata().file_metadata().num_rows(), 100);
+    Ok(())
+}
@@ -4,6 +4,7 @@
 use crate::writer::StateSnapshotWriterV1;
 use anyhow::Result;
 use bytes::Bytes;
+use object_store::path::Path;
 use object_store::DynObjectStore;
 use prometheus::{
     register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
@@ -18,7 +19,7 @@ use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::db_checkpoint_handler::{STATE_SNAPSHOT_COMPLETED_MARKER, SUCCESS_MARKER};
 use sui_storage::object_store::util::{
-    find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, path_to_filesystem, put,
+    exists, find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, path_to_filesystem, put,
     run_manifest_update_loop,
 };
 use sui_storage::FileCompression;
@@ -70,6 +71,9 @@ pub struct StateSnapshotUploader {
     /// The chain identifier is derived from the genesis checkpoint and used to identify the
     /// network.
     chain_identifier: ChainIdentifier,
+    /// Number of epochs between full state snapshots, with incremental snapshots written for
+    /// the epochs in between. Every snapshot is a full snapshot if unset.
+    full_snapshot_interval_epochs: Option<u64>,
 }
 
 impl StateSnapshotUploader {
@@ -81,6 +85,7 @@ impl StateSnapshotUploader {
         registry: &Registry,
         checkpoint_store: Arc<CheckpointStore>,
         chain_identifier: ChainIdentifier,
+        full_snapshot_interval_epochs: Option<u64>,
     ) -> Result<Arc<Self>> {
         let db_checkpoint_store_config = ObjectStoreConfig {
             object_store: Some(ObjectStoreType::File),
@@ -102,6 +107,7 @@ impl StateSnapshotUploader {
             interval: Duration::from_secs(interval_s),
             metrics: StateSnapshotUploaderMetrics::new(registry),
             chain_identifier,
+            full_snapshot_interval_epochs,
         }))
     }
 
@@ -145,9 +151,25 @@ impl StateSnapshotUploader {
                     .last()
                     .expect("Expected at least one commitment")
                     .clone();
-                state_snapshot_writer
-                    .write(*epoch, db, state_hash_commitment, self.chain_identifier)
-                    .await?;
+                match self.incremental_base_epoch(*epoch).await {
+                    Some(base_epoch) => {
+                        info!("Writing incremental state snapshot on top of epoch: {base_epoch}");
+                        state_snapshot_writer
+                            .write_incremental(
+                                *epoch,
+                                base_epoch,
+                                db,
+                                state_hash_commitment,
+                                self.chain_identifier,
+                            )
+                            .await?
+                    }
+                    None => {
+                        state_snapshot_writer
+                            .write(*epoch, db, state_hash_commitment, self.chain_identifier)
+                            .await?
+                    }
+                }
                 info!("State snapshot creation successful for epoch: {}", *epoch);
                 // Drop marker in the output directory that upload completed successfully
                 let bytes = Bytes::from_static(b"success");
@@ -179,6 +201,21 @@ impl StateSnapshotUploader {
         Ok(())
     }
 
+    /// Returns the epoch the snapshot of `epoch` should be an incremental snapshot on top of, i.e.
+    /// the previous epoch if `epoch` is not due for a full snapshot and the snapshot of the
+    /// previous epoch has been uploaded successfully.
+    async fn incremental_base_epoch(&self, epoch: u64) -> Option<u64> {
+        let interval = self.full_snapshot_interval_epochs.filter(|n| *n > 1)?;
+        if epoch % interval == 0 {
+            return None;
+        }
+        let base_epoch = epoch - 1;
+        let base_success_marker = Path::from(format!("epoch_{}", base_epoch)).child(SUCCESS_MARKER);
+        exists(&self.snapshot_store, &base_success_marker)
+            .await
+            .then_some(base_epoch)
+    }
+
     async fn run_upload_loop(
         self: Arc<Self>,
         mut recv: tokio::sync::broadcast::Receiver<()>,
@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(dead_code)]
 
+use crate::incremental::{chain_live_refs, download_chain_refs, read_manifest_chain};
 use crate::{
     compute_sha3_checksum, create_file_metadata, FileCompression, FileMetadata, FileType, Manifest,
-    ManifestV1, FILE_MAX_BYTES, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
+    ManifestV1, ManifestV2, FILE_MAX_BYTES, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
     OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES,
 };
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use byteorder::{BigEndian, ByteOrder};
 use fastcrypto::hash::MultisetHash;
 use futures::StreamExt;
@@ -102,17 +103,7 @@ impl LiveObjectSetWriterV1 {
         Ok((n, f))
     }
     fn ref_file(dir_path: PathBuf, bucket_num: u32, part_num: u32) -> Result<File> {
-        let ref_path = dir_path.join(format!("{bucket_num}_{part_num}.ref"));
-        let ref_tmp_path = dir_path.join(format!("{bucket_num}_{part_num}.ref.tmp"));
-        let mut f = File::create(ref_tmp_path.clone())?;
-        f.rewind()?;
-        let mut metab = [0u8; MAGIC_BYTES];
-        BigEndian::write_u32(&mut metab, REFERENCE_FILE_MAGIC);
-        let n = f.write(&metab)?;
-        drop(f);
-        fs::rename(ref_tmp_path, ref_path.clone())?;
-        let mut f = OpenOptions::new().append(true).open(ref_path)?;
-        f.seek(SeekFrom::Start(n as u64))?;
+        let (_, f) = create_ref_file(dir_path, &format!("{bucket_num}_{part_num}.ref"))?;
         Ok(f)
     }
     fn finalize(&mut self) -> Result<()> {
@@ -193,19 +184,123 @@ impl LiveObjectSetWriterV1 {
         Ok(())
     }
     fn write_object_ref(&mut self, object_ref: &ObjectRef) -> Result<()> {
-        let mut buf = [0u8; OBJECT_REF_BYTES];
-        buf[0..ObjectID::LENGTH].copy_from_slice(object_ref.0.as_ref());
-        BigEndian::write_u64(
-            &mut buf[ObjectID::LENGTH..OBJECT_REF_BYTES],
-            object_ref.1.value(),
-        );
-        buf[ObjectID::LENGTH + SEQUENCE_NUM_BYTES..OBJECT_REF_BYTES]
-            .copy_from_slice(object_ref.2.as_ref());
-        self.ref_wbuf.write_all(&buf)?;
+        self.ref_wbuf.write_all(&serialize_object_ref(object_ref))?;
+        Ok(())
+    }
+}
+
+/// DeletedObjectRefWriterV1 writes the refs of objects which were live at the base of an
+/// incremental snapshot but not anymore. It creates multiple *.del files
+struct DeletedObjectRefWriterV1 {
+    dir_path: PathBuf,
+    bucket_num: u32,
+    current_part_num: u32,
+    wbuf: BufWriter<File>,
+    n: usize,
+    files: Vec<FileMetadata>,
+    sender: Option<Sender<FileMetadata>>,
+    file_compression: FileCompression,
+}
+
+impl DeletedObjectRefWriterV1 {
+    fn new(
+        dir_path: PathBuf,
+        bucket_num: u32,
+        file_compression: FileCompression,
+        sender: Sender<FileMetadata>,
+    ) -> Result<Self> {
+        let part_num = 1;
+        let (n, f) = create_ref_file(dir_path.clone(), &Self::file_name(bucket_num, part_num))?;
+        Ok(DeletedObjectRefWriterV1 {
+            dir_path,
+            bucket_num,
+            current_part_num: part_num,
+            wbuf: BufWriter::new(f),
+            n,
+            files: vec![],
+            sender: Some(sender),
+            file_compression,
+        })
+    }
+    pub fn write(&mut self, object_ref: &ObjectRef) -> Result<()> {
+        if self.n + OBJECT_REF_BYTES > FILE_MAX_BYTES {
+            self.cut()?;
+        }
+        self.wbuf.write_all(&serialize_object_ref(object_ref))?;
+        self.n += OBJECT_REF_BYTES;
+        Ok(())
+    }
+    pub fn done(mut self) -> Result<Vec<FileMetadata>> {
+        self.finalize()?;
+        self.sender = None;
+        Ok(self.files.clone())
+    }
+    fn file_name(bucket_num: u32, part_num: u32) -> String {
+        format!("{bucket_num}_{part_num}.del")
+    }
+    fn finalize(&mut self) -> Result<()> {
+        self.wbuf.flush()?;
+        self.wbuf.get_ref().sync_data()?;
+        let off = self.wbuf.get_ref().stream_position()?;
+        self.wbuf.get_ref().set_len(off)?;
+        let file_path = self
+            .dir_path
+            .join(Self::file_name(self.bucket_num, self.current_part_num));
+        let file_metadata = create_file_metadata(
+            &file_path,
+            self.file_compression,
+            FileType::Deletion,
+            self.bucket_num,
+            self.current_part_num,
+        )?;
+        self.files.push(file_metadata.clone());
+        if let Some(sender) = &self.sender {
+            sender.blocking_send(file_metadata)?;
+        }
+        Ok(())
+    }
+    fn cut(&mut self) -> Result<()> {
+        self.finalize()?;
+        self.current_part_num += 1;
+        let (n, f) = create_ref_file(
+            self.dir_path.clone(),
+            &Self::file_name(self.bucket_num, self.current_part_num),
+        )?;
+        self.n = n;
+        self.wbuf = BufWriter::new(f);
         Ok(())
     }
 }
 
+/// Creates a file in the REFERENCE file format and returns it opened for appending object refs
+/// along with the number of header bytes written
+fn create_ref_file(dir_path: PathBuf, file_name: &str) -> Result<(usize, File)> {
+    let ref_path = dir_path.join(file_name);
+    let ref_tmp_path = dir_path.join(format!("{file_name}.tmp"));
+    let mut f = File::create(ref_tmp_path.clone())?;
+    f.rewind()?;
+    let mut metab = [0u8; MAGIC_BYTES];
+    BigEndian::write_u32(&mut metab, REFERENCE_FILE_MAGIC);
+    let n = f.write(&metab)?;
+    drop(f);
+    fs::rename(ref_tmp_path, ref_path.clone())?;
+    let mut f = OpenOptions::new().append(true).open(ref_path)?;
+    f.seek(SeekFrom::Start(n as u64))?;
+    Ok((n, f))
+}
+
+fn serialize_object_ref(object_ref: &ObjectRef) -> [u8; OBJECT_REF_BYTES] {
+    let mut buf = [0u8; OBJECT_REF_BYTES];
+    buf[0..ObjectID::LENGTH].copy_from_slice(object_ref.0.as_ref());
+    BigEndian::write_u64(
+        &mut buf[ObjectID::LENGTH..OBJECT_REF_BYTES],
+        object_ref.1.value(),
+    );
+    buf[ObjectID::LENGTH + SEQUENCE_NUM_BYTES..OBJECT_REF_BYTES]
+        .copy_from_slice(object_ref.2.as_ref());
+    buf
+}
+
 /// StateSnapshotWriterV1 writes snapshot files to a local staging dir and simultaneously uploads them
 /// to a remote object store
 pub struct StateSnapshotWriterV1 {
@@ -262,14 +357,8 @@ impl StateSnapshotWriterV1 {
         root_state_hash: ECMHLiveObjectSetDigest,
         chain_identifier: ChainIdentifier,
     ) -> Result<()> {
-        let system_state_object = get_sui_system_state(&perpetual_db)?;
-
-        let protocol_version = system_state_object.protocol_version();
-        let protocol_config = ProtocolConfig::get_for_version(
-            ProtocolVersion::new(protocol_version),
-            chain_identifier.chain(),
-        );
-        let include_wrapped_tombstone = !protocol_config.simplified_unwrap_then_delete();
+        let include_wrapped_tombstone =
+            Self::include_wrapped_tombstone(&perpetual_db, chain_identifier)?;
         self.write_internal(
             epoch,
             include_wrapped_tombstone,
@@ -279,14 +368,100 @@ impl StateSnapshotWriterV1 {
         .await
     }
 
+    /// Writes an incremental snapshot which only holds the objects changed since the snapshot
+    /// of `base_epoch`, which must already exist in the remote store.
+    pub async fn write_incremental(
+        self,
+        epoch: u64,
+        base_epoch: u64,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+        chain_identifier: ChainIdentifier,
+    ) -> Result<()> {
+        let include_wrapped_tombstone =
+            Self::include_wrapped_tombstone(&perpetual_db, chain_identifier)?;
+        self.write_incremental_internal(
+            epoch,
+            base_epoch,
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
     pub(crate) async fn write_internal(
+        self,
+        epoch: u64,
+        include_wrapped_tombstone: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        self.write_snapshot(
+            epoch,
+            None,
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    pub(crate) async fn write_incremental_internal(
+        self,
+        epoch: u64,
+        base_epoch: u64,
+        include_wrapped_tombstone: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        if base_epoch >= epoch {
+            return Err(anyhow!(
+                "Base epoch {base_epoch} of incremental snapshot must be before epoch {epoch}"
+            ));
+        }
+        self.write_snapshot(
+            epoch,
+            Some(base_epoch),
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    fn include_wrapped_tombstone(
+        perpetual_db: &AuthorityPerpetualTables,
+        chain_identifier: ChainIdentifier,
+    ) -> Result<bool> {
+        let system_state_object = get_sui_system_state(perpetual_db)?;
+
+        let protocol_version = system_state_object.protocol_version();
+        let protocol_config = ProtocolConfig::get_for_version(
+            ProtocolVersion::new(protocol_version),
+            chain_identifier.chain(),
+        );
+        Ok(!protocol_config.simplified_unwrap_then_delete())
+    }
+
+    async fn write_snapshot(
         mut self,
         epoch: u64,
+        base_epoch: Option<u64>,
         include_wrapped_tombstone: bool,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         root_state_hash: ECMHLiveObjectSetDigest,
     ) -> Result<()> {
         self.setup_epoch_dir(epoch).await?;
+        // Fetch the object refs of the base snapshot chain to diff the live object set against
+        let base = match base_epoch {
+            Some(base_epoch) => Some(self.setup_base_dir(epoch, base_epoch).await?),
+            None => None,
+        };
+        let base_dir_path = base
+            .as_ref()
+            .map(|(base_dir, _)| path_to_filesystem(self.local_staging_dir.clone(), base_dir))
+            .transpose()?;
 
         let manifest_file_path = self.epoch_dir(epoch).child("MANIFEST");
         let local_staging_dir = self.local_staging_dir.clone();
@@ -303,6 +478,7 @@ impl StateSnapshotWriterV1 {
                 Self::bucket_func,
                 include_wrapped_tombstone,
                 root_state_hash,
+                base,
             )
         });
         write_handler.await?.context(format!(
@@ -314,6 +490,9 @@ impl StateSnapshotWriterV1 {
             "Failed to upload state snapshot for epoch: {}",
             &epoch
         ))?;
+        if let Some(base_dir_path) = base_dir_path.filter(|path| path.exists()) {
+            fs::remove_dir_all(base_dir_path)?;
+        }
 
         Self::sync_file_to_remote(
             local_staging_dir,
@@ -372,6 +551,7 @@ impl StateSnapshotWriterV1 {
         bucket_func: F,
         include_wrapped_tombstone: bool,
         root_state_hash: ECMHLiveObjectSetDigest,
+        base: Option<(Path, Vec<Manifest>)>,
     ) -> Result<()>
     where
         F: Fn(&LiveObject) -> u32,
@@ -379,9 +559,45 @@ impl StateSnapshotWriterV1 {
         let mut object_writers: HashMap<u32, LiveObjectSetWriterV1> = HashMap::new();
         let local_staging_dir_path =
             path_to_filesystem(self.local_staging_dir.clone(), &self.epoch_dir(epoch))?;
+        let base_epoch = base.as_ref().map(|(_, chain)| {
+            chain
+                .last()
+                .expect("Snapshot chain cannot be empty")
+                .epoch()
+        });
+        // Incremental snapshots hold all refs in one bucket, see `chain_live_refs`
+        let mut base = match base {
+            Some((base_dir, chain)) => Some((
+                chain_live_refs(&self.local_staging_dir, &base_dir, &chain)?.peekable(),
+                DeletedObjectRefWriterV1::new(
+                    local_staging_dir_path.clone(),
+                    1,
+                    self.file_compression,
+                    sender.clone(),
+                )?,
+            )),
+            None => None,
+        };
         let mut acc = Accumulator::default();
         for object in perpetual_db.iter_live_object_set(include_wrapped_tombstone) {
             StateAccumulator::accumulate_live_object(&mut acc, &object);
+            // Both the live object set and the base refs are sorted by object id, so objects
+            // which are unchanged since the base can be skipped with a merge join
+            if let Some((base_refs, deletion_writer)) = &mut base {
+                let object_ref = object.object_reference();
+                let mut unchanged = false;
+                while let Some(base_ref) = base_refs.next_if(|base_ref| base_ref.0 <= object_ref.0)
+                {
+                    if base_ref == object_ref {
+                        unchanged = true;
+                    } else {
+                        deletion_writer.write(&base_ref)?;
+                    }
+                }
+                if unchanged {
+                    continue;
+                }
+            }
             let bucket_num = bucket_func(&object);
             if let Vacant(entry) = object_writers.entry(bucket_num) {
                 entry.insert(LiveObjectSetWriterV1::new(
@@ -405,19 +621,39 @@ impl StateSnapshotWriterV1 {
         for (_, writer) in object_writers.into_iter() {
             files.extend(writer.done()?);
         }
-        self.write_manifest(epoch, files)?;
+        if let Some((base_refs, mut deletion_writer)) = base {
+            for base_ref in base_refs {
+                deletion_writer.write(&base_ref)?;
+            }
+            files.extend(deletion_writer.done()?);
+        }
+        self.write_manifest(epoch, base_epoch, files)?;
         Ok(())
     }
 
-    fn write_manifest(&mut self, epoch: u64, file_metadata: Vec<FileMetadata>) -> Result<()> {
+    fn write_manifest(
+        &mut self,
+        epoch: u64,
+        base_epoch: Option<u64>,
+        file_metadata: Vec<FileMetadata>,
+    ) -> Result<()> {
         let (f, manifest_file_path) = self.manifest_file(epoch)?;
         let mut wbuf = BufWriter::new(f);
-        let manifest: Manifest = Manifest::V1(ManifestV1 {
-            snapshot_version: 1,
-            address_length: ObjectID::LENGTH as u64,
-            file_metadata,
-            epoch,
-        });
+        let manifest: Manifest = match base_epoch {
+            None => Manifest::V1(ManifestV1 {
+                snapshot_version: 1,
+                address_length: ObjectID::LENGTH as u64,
+                file_metadata,
+                epoch,
+            }),
+            Some(base_epoch) => Manifest::V2(ManifestV2 {
+                snapshot_version: 2,
+                address_length: ObjectID::LENGTH as u64,
+                file_metadata,
+                epoch,
+                base_epoch,
+            }),
+        };
         let serialized_manifest = bcs::to_bytes(&manifest)?;
         wbuf.write_all(&serialized_manifest)?;
         wbuf.flush()?;
@@ -481,6 +717,25 @@ impl StateSnapshotWriterV1 {
         Ok(())
     }
 
+    /// Downloads the object refs of the snapshot chain ending at `base_epoch` into a local staging
+    /// dir for the incremental snapshot of `epoch`
+    async fn setup_base_dir(&self, epoch: u64, base_epoch: u64) -> Result<(Path, Vec<Manifest>)> {
+        let base_dir = Path::from(format!("base_of_epoch_{}", epoch));
+        let local_base_dir_path = path_to_filesystem(self.local_staging_dir.clone(), &base_dir)?;
+        if local_base_dir_path.exists() {
+            fs::remove_dir_all(&local_base_dir_path)?;
+        }
+        let chain = read_manifest_chain(&self.remote_object_store, base_epoch).await?;
+        download_chain_refs(
+            &self.remote_object_store,
+            &self.local_staging_store,
+            &base_dir,
+            &chain,
+        )
+        .await?;
+        Ok((base_dir, chain))
+    }
+
     async fn sync_file_to_remote(
         local_path: PathBuf,
         path: Path,
@@ -0,0 +1,329 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streams column family backups of typed-store databases to and from an object store.
+//!
+//! Backup files are content addressed, so they are shared by all backups under a prefix:
+//! ```text
+//! <prefix>/files/<column family>-<digest>.sst
+//! <prefix>/manifests/<backup name>
+//! ```
+//! An incremental backup only uploads the files missing from its parent backup.
+
+use crate::object_store::util::{get, put};
+use crate::object_store::ObjectStoreGetExt;
+use anyhow::{anyhow, bail, Context, Result};
+use bytes::Bytes;
+use futures::StreamExt;
+use object_store::buffered::BufWriter;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+use typed_store::rocks::backup::{
+    verify_backup, verify_backup_files, BackupFile, BackupManifest, BackupOptions,
+};
+use typed_store::rocks::{open_cf, MetricConf, RocksDB};
+
+const FILES_DIR_NAME: &str = "files";
+const MANIFESTS_DIR_NAME: &str = "manifests";
+
+fn file_path(prefix: &Path, file_name: &str) -> Path {
+    prefix.child(FILES_DIR_NAME).child(file_name)
+}
+
+fn manifest_path(prefix: &Path, backup_name: &str) -> Path {
+    prefix.child(MANIFESTS_DIR_NAME).child(backup_name)
+}
+
+/// Reads the manifest of backup `backup_name` from the object store.
+pub async fn read_manifest<S: ObjectStoreGetExt>(
+    store: &S,
+    prefix: &Path,
+    backup_name: &str,
+) -> Result<BackupManifest> {
+    let bytes = get(store, &manifest_path(prefix, backup_name)).await?;
+    BackupManifest::from_bytes(&bytes).map_err(|e| anyhow!("Invalid backup manifest: {e}"))
+}
+
+/// Uploads the files of `manifest` from `local_dir` which are not part of `parent`, then the
+/// manifest itself. The manifest is written last, so a backup is only visible once complete.
+/// Files are streamed from disk, so they are never held in memory whole.
+pub async fn upload_backup(
+    store: &Arc<DynObjectStore>,
+    prefix: &Path,
+    backup_name: &str,
+    local_dir: &std::path::Path,
+    manifest: &BackupManifest,
+    parent: Option<&BackupManifest>,
+) -> Result<()> {
+    let new_files = manifest.new_files(parent);
+    for file in &new_files {
+        let mut reader = tokio::fs::File::open(local_dir.join(&file.name))
+            .await
+            .with_context(|| format!("Failed to open backup file {}", file.name))?;
+        let mut writer = BufWriter::new(store.clone(), file_path(prefix, &file.name));
+        tokio::io::copy(&mut reader, &mut writer)
+            .await
+            .with_context(|| format!("Failed to upload backup file {}", file.name))?;
+        writer.shutdown().await?;
+    }
+    put(
+        store,
+        &manifest_path(prefix, backup_name),
+        Bytes::from(manifest.to_bytes()),
+    )
+    .await?;
+    info!(
+        "Uploaded backup {backup_name} with {} new files",
+        new_files.len()
+    );
+    Ok(())
+}
+
+/// Downloads backup `backup_name` into `local_dir` and verifies the sizes and checksums of all
+/// its files. Files already present in `local_dir` are only downloaded again if they do not
+/// match the manifest.
+pub async fn download_backup(
+    store: &Arc<DynObjectStore>,
+    prefix: &Path,
+    backup_name: &str,
+    local_dir: &std::path::Path,
+) -> Result<BackupManifest> {
+    let manifest = read_manifest(store, prefix, backup_name).await?;
+    fs::create_dir_all(local_dir)?;
+    for file in manifest.files() {
+        if verify_backup_files([file], local_dir).is_ok() {
+            continue;
+        }
+        download_file(store, prefix, file, local_dir).await?;
+    }
+    verify_backup(&manifest, local_dir)?;
+    Ok(manifest)
+}
+
+/// Streams backup file `file` into `local_dir`. The file is written under a temporary name and
+/// renamed once complete, so an interrupted download never leaves a partial file behind.
+async fn download_file(
+    store: &Arc<DynObjectStore>,
+    prefix: &Path,
+    file: &BackupFile,
+    local_dir: &std::path::Path,
+) -> Result<()> {
+    let partial_path = local_dir.join(format!("{}.partial", file.name));
+    let mut writer = tokio::fs::File::create(&partial_path).await?;
+    let mut stream = store
+        .get(&file_path(prefix, &file.name))
+        .await
+        .with_context(|| format!("Failed to get backup file {}", file.name))?
+        .into_stream();
+    while let Some(bytes) = stream.next().await {
+        writer.write_all(&bytes?).await?;
+    }
+    writer.sync_all().await?;
+    fs::rename(&partial_path, local_dir.join(&file.name))?;
+    Ok(())
+}
+
+/// Backs up `column_families` of `db` into the object store as `backup_name`, incrementally on
+/// top of backup `parent_name` when given. Backup files are staged in `local_dir`.
+#[allow(clippy::too_many_arguments)]
+pub async fn backup_to_object_store(
+    db: Arc<RocksDB>,
+    column_families: Vec<String>,
+    store: &Arc<DynObjectStore>,
+    prefix: &Path,
+    backup_name: &str,
+    parent_name: Option<&str>,
+    local_dir: PathBuf,
+    options: BackupOptions,
+) -> Result<BackupManifest> {
+    let parent = match parent_name {
+        Some(name) => Some(read_manifest(store, prefix, name).await?),
+        None => None,
+    };
+    let (manifest, parent) = {
+        let local_dir = local_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            let column_families: Vec<_> = column_families.iter().map(String::as_str).collect();
+            db.backup_column_families(&column_families, &local_dir, parent.as_ref(), &options)
+                .map(|manifest| (manifest, parent))
+        })
+        .await??
+    };
+    // Files reused from the parent are not staged locally.
+    verify_backup_files(manifest.new_files(parent.as_ref()), &local_dir)?;
+    upload_backup(
+        store,
+        prefix,
+        backup_name,
+        &local_dir,
+        &manifest,
+        parent.as_ref(),
+    )
+    .await?;
+    Ok(manifest)
+}
+
+/// Downloads backup `backup_name` into `local_dir`, verifies it, and restores its column
+/// families into a new database at `db_path`.
+///
+/// The database is restored next to `db_path` and only moved to `db_path` once every column
+/// family is restored, so a failed restore never leaves a partially restored database behind.
+pub async fn restore_from_object_store(
+    store: &Arc<DynObjectStore>,
+    prefix: &Path,
+    backup_name: &str,
+    db_path: PathBuf,
+    local_dir: PathBuf,
+) -> Result<BackupManifest> {
+    if db_path.exists() {
+        bail!(
+            "Cannot restore backup into existing database {}",
+            db_path.display()
+        );
+    }
+    let manifest = download_backup(store, prefix, backup_name, &local_dir).await?;
+
+    let mut staging_path = db_path.clone().into_os_string();
+    staging_path.push(".restoring");
+    let staging_path = PathBuf::from(staging_path);
+    if staging_path.exists() {
+        fs::remove_dir_all(&staging_path)?;
+    }
+    let result = {
+        let manifest = manifest.clone();
+        let staging_path = staging_path.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let column_families: Vec<_> = manifest
+                .column_families
+                .keys()
+                .map(String::as_str)
+                .collect();
+            let db = open_cf(&staging_path, None, MetricConf::default(), &column_families)?;
+            db.restore_column_families(&manifest, &local_dir)?;
+            Ok(())
+        })
+        .await?
+    };
+    if let Err(e) = result {
+        fs::remove_dir_all(&staging_path).ok();
+        return Err(e);
+    }
+    fs::rename(&staging_path, &db_path)?;
+    info!("Restored backup {backup_name} into {}", db_path.display());
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+    use tempfile::TempDir;
+    use typed_store::rocksdb::WriteOptions;
+
+    #[tokio::test]
+    pub async fn test_backup_and_restore_through_object_store() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let remote = dir.path().join("remote");
+        fs::create_dir(&remote)?;
+        let store = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(remote),
+            ..Default::default()
+        }
+        .make()?;
+        let prefix = Path::from("db");
+        let options = BackupOptions {
+            min_chunk_size: 256,
+            max_chunk_size: 4096,
+            boundary_modulus: 8,
+        };
+
+        let db = open_cf(
+            dir.path().join("db"),
+            None,
+            MetricConf::default(),
+            &["table"],
+        )?;
+        let cf = db.cf_handle("table").unwrap();
+        for i in 0u32..1000 {
+            db.put_cf(
+                &cf,
+                i.to_be_bytes(),
+                i.to_le_bytes(),
+                &WriteOptions::default(),
+            )?;
+        }
+        drop(cf);
+        let full = backup_to_object_store(
+            db.clone(),
+            vec!["table".to_string()],
+            &store,
+            &prefix,
+            "full",
+            None,
+            dir.path().join("staging-full"),
+            options.clone(),
+        )
+        .await?;
+
+        let cf = db.cf_handle("table").unwrap();
+        db.put_cf(&cf, 5000u32.to_be_bytes(), b"new", &WriteOptions::default())?;
+        drop(cf);
+        let incremental = backup_to_object_store(
+            db.clone(),
+            vec!["table".to_string()],
+            &store,
+            &prefix,
+            "incremental",
+            Some("full"),
+            dir.path().join("staging-incremental"),
+            options.clone(),
+        )
+        .await?;
+        assert!(incremental.new_files(Some(&full)).len() < full.files().count());
+
+        // Files already downloaded are fetched again when they do not match the manifest.
+        let download_dir = dir.path().join("download");
+        download_backup(&store, &prefix, "full", &download_dir).await?;
+        let corrupted = download_dir.join(&full.files().next().unwrap().name);
+        fs::write(&corrupted, b"corrupted")?;
+        download_backup(&store, &prefix, "full", &download_dir).await?;
+
+        let restored_path = dir.path().join("restored");
+        restore_from_object_store(
+            &store,
+            &prefix,
+            "incremental",
+            restored_path.clone(),
+            download_dir.clone(),
+        )
+        .await?;
+        assert!(restore_from_object_store(
+            &store,
+            &prefix,
+            "incremental",
+            restored_path.clone(),
+            download_dir,
+        )
+        .await
+        .is_err());
+
+        // Backup files are named after the digest of their entries, so a backup of the restored
+        // database has the same files as the original backup only if all entries were restored.
+        let restored = open_cf(restored_path, None, MetricConf::default(), &["table"])?;
+        let manifest = restored.backup_column_families(
+            &["table"],
+            &dir.path().join("staging-restored"),
+            None,
+            &options,
+        )?;
+        let names = |m: &BackupManifest| m.files().map(|f| f.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&manifest), names(&incremental));
+        Ok(())
+    }
+}
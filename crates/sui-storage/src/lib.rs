@@ -27,6 +27,7 @@ use sui_types::storage::WriteStore;
 use tracing::debug;
 
 pub mod blob;
+pub mod db_backup;
 pub mod http_key_value_store;
 pub mod key_value_store;
 pub mod key_value_store_metrics;
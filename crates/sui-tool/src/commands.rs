@@ -3,21 +3,26 @@
 
 use crate::{
     check_completed_snapshot,
+    consensus_dag::{execute_consensus_dag_command, ConsensusDagCommand},
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
-    download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
-    get_latest_available_epoch, get_object, get_transaction_block, make_clients,
-    restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum, ConciseObjectOutput,
-    GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
+    download_db_snapshot, download_filtered_formal_snapshot, download_formal_snapshot,
+    dump_checkpoints_from_archive, get_latest_available_epoch, get_object, get_transaction_block,
+    make_clients, restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum,
+    ConciseObjectOutput, FilteredSnapshotOutput, GroupedObjectOutput, SnapshotVerifyMode,
+    VerboseObjectOutput,
 };
 use anyhow::Result;
 use futures::{future::join_all, StreamExt};
+use move_core_types::language_storage::StructTag;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::{collections::BTreeMap, env, sync::Arc};
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
 use sui_protocol_config::Chain;
 use sui_replay::{execute_replay_command, ReplayToolCommand};
 use sui_sdk::{rpc_types::SuiTransactionBlockResponseOptions, SuiClient, SuiClientBuilder};
+use sui_snapshot::filter::LiveObjectFilter;
 use telemetry_subscribers::TracingHandle;
 
 use sui_types::{
@@ -130,6 +135,16 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Tool to inspect the consensus DAG of a validator, read-only.
+    #[command(name = "consensus-dag")]
+    ConsensusDag {
+        /// Path of the consensus DB of an epoch
+        #[arg(long = "db-path")]
+        db_path: String,
+        #[command(subcommand)]
+        cmd: ConsensusDagCommand,
+    },
+
     /// Tool to verify the archive store
     #[command(name = "verify-archive")]
     VerifyArchive {
@@ -376,6 +391,22 @@ pub enum ToolCommand {
         /// downloaded, and (if --verify is provided) will be verified via committee signature.
         #[clap(long = "all-checkpoints")]
         all_checkpoints: bool,
+
+        /// Only restore live objects of this Move type, e.g. `0x2::coin::Coin`. A type without
+        /// type parameters matches all of its instantiations. May be repeated.
+        #[clap(long = "object-type")]
+        object_types: Vec<String>,
+        /// Only restore packages with this id and objects of types defined in them.
+        /// May be repeated.
+        #[clap(long = "package")]
+        packages: Vec<ObjectID>,
+        /// Only restore live objects owned by this address. May be repeated.
+        #[clap(long = "owner")]
+        owners: Vec<SuiAddress>,
+        /// Where to write the objects of a filtered restore, i.e. if any of `--object-type`,
+        /// `--package` or `--owner` is set. Filtered restores do not produce a node db.
+        #[clap(long = "filtered-output", default_value = "store")]
+        filtered_output: FilteredSnapshotOutput,
     },
 
     #[clap(name = "replay")]
@@ -583,6 +614,9 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::ConsensusDag { db_path, cmd } => {
+                execute_consensus_dag_command(&PathBuf::from(db_path), cmd)?;
+            }
             ToolCommand::DumpPackages {
                 rpc_url,
                 output_dir,
@@ -669,6 +703,10 @@ impl ToolCommand {
                 latest,
                 verbose,
                 all_checkpoints,
+                object_types,
+                packages,
+                owners,
+                filtered_output,
             } => {
                 if !verbose {
                     tracing_handle
@@ -868,18 +906,44 @@ impl ToolCommand {
                 }
 
                 let verify = verify.unwrap_or_default();
-                download_formal_snapshot(
-                    &path,
-                    epoch_to_download,
-                    &genesis,
-                    snapshot_store_config,
-                    archive_store_config,
-                    num_parallel_downloads,
-                    network,
-                    verify,
-                    all_checkpoints,
-                )
-                .await?;
+                let mut filter = LiveObjectFilter::new();
+                for object_type in object_types {
+                    filter = filter.with_type(StructTag::from_str(&object_type)?);
+                }
+                for package in packages {
+                    filter = filter.with_package(package);
+                }
+                for owner in owners {
+                    filter = filter.with_owner(owner);
+                }
+                if filter.is_empty() {
+                    download_formal_snapshot(
+                        &path,
+                        epoch_to_download,
+                        &genesis,
+                        snapshot_store_config,
+                        archive_store_config,
+                        num_parallel_downloads,
+                        network,
+                        verify,
+                        all_checkpoints,
+                    )
+                    .await?;
+                } else {
+                    download_filtered_formal_snapshot(
+                        &path,
+                        epoch_to_download,
+                        &genesis,
+                        snapshot_store_config,
+                        archive_store_config,
+                        num_parallel_downloads,
+                        verify,
+                        all_checkpoints,
+                        filter,
+                        filtered_output,
+                    )
+                    .await?;
+                }
             }
             ToolCommand::DownloadDBSnapshot {
                 epoch,
@@ -0,0 +1,185 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use clap::Parser;
+use consensus_core::{BlockAPI, CommitIndex, DagInspector, Round, VerifiedBlock};
+use itertools::Itertools;
+
+/// Number of rounds or commits shown when no range is given.
+const DEFAULT_WINDOW: u32 = 10;
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub enum ConsensusDagCommand {
+    /// List the blocks proposed in each round.
+    ListRounds(RoundRangeOptions),
+    /// List the blocks proposed by an authority.
+    ListBlocks(ListBlocksOptions),
+    /// Print the causal history of the blocks at a slot.
+    Ancestors(AncestorsOptions),
+    /// List commits with their leaders.
+    ListCommits(ListCommitsOptions),
+    /// Export the DAG in the text format used by consensus unit tests.
+    ExportDag(ExportDagOptions),
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct RoundRangeOptions {
+    /// First round to show. Defaults to 10 rounds before the last.
+    #[arg(long)]
+    from: Option<Round>,
+    /// Last round to show. Defaults to the last round in the store.
+    #[arg(long)]
+    to: Option<Round>,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct ListBlocksOptions {
+    /// Index of the authority in the committee.
+    #[arg(long)]
+    author: u32,
+    #[command(flatten)]
+    rounds: RoundRangeOptions,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct AncestorsOptions {
+    #[arg(long)]
+    round: Round,
+    /// Index of the authority in the committee.
+    #[arg(long)]
+    author: u32,
+    /// Number of rounds of history to follow.
+    #[arg(long, default_value_t = 3)]
+    depth: Round,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct ListCommitsOptions {
+    /// First commit to show. Defaults to 10 commits before the last.
+    #[arg(long)]
+    from: Option<CommitIndex>,
+    /// Last commit to show. Defaults to the last commit in the store.
+    #[arg(long)]
+    to: Option<CommitIndex>,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct ExportDagOptions {
+    #[command(flatten)]
+    rounds: RoundRangeOptions,
+    /// Size of the committee. Inferred from the blocks if not given.
+    #[arg(long)]
+    committee_size: Option<usize>,
+    /// File to write the DAG to. Printed to stdout if not given.
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+/// Runs `cmd` against the consensus store at `db_path`, which is opened read-only and may belong
+/// to a running validator.
+pub fn execute_consensus_dag_command(
+    db_path: &Path,
+    cmd: ConsensusDagCommand,
+) -> anyhow::Result<()> {
+    let secondary = tempfile::tempdir()?;
+    let inspector = DagInspector::open(db_path, secondary.path())?;
+
+    match cmd {
+        ConsensusDagCommand::ListRounds(rounds) => {
+            let rounds = round_range(&inspector, &rounds)?;
+            let mut by_round: BTreeMap<Round, Vec<VerifiedBlock>> = BTreeMap::new();
+            for block in inspector.blocks(rounds.clone())? {
+                by_round.entry(block.round()).or_default().push(block);
+            }
+            for round in rounds {
+                let blocks = by_round.remove(&round).unwrap_or_default();
+                let authors = blocks.iter().map(|b| b.author()).dedup().collect_vec();
+                println!(
+                    "Round {round}: {} blocks from {} authorities [{}]",
+                    blocks.len(),
+                    authors.len(),
+                    authors.iter().join(", ")
+                );
+                if blocks.len() > authors.len() {
+                    println!("  Multiple blocks in a slot, authorities may have equivocated");
+                }
+            }
+        }
+        ConsensusDagCommand::ListBlocks(options) => {
+            let rounds = round_range(&inspector, &options.rounds)?;
+            for block in inspector.blocks_by_author(options.author, rounds)? {
+                print_block(&block);
+            }
+        }
+        ConsensusDagCommand::Ancestors(options) => {
+            let blocks = inspector.ancestors(options.round, options.author, options.depth)?;
+            if blocks.is_empty() {
+                println!(
+                    "No block from authority {} at round {}",
+                    options.author, options.round
+                );
+            }
+            for block in blocks.iter().rev() {
+                println!("{block} -> [{}]", block.ancestors().iter().join(", "));
+            }
+        }
+        ConsensusDagCommand::ListCommits(options) => {
+            let last = inspector
+                .last_commit_index()?
+                .ok_or(anyhow!("No commits in the store"))?;
+            let to = options.to.unwrap_or(last);
+            let from = options.from.unwrap_or(to.saturating_sub(DEFAULT_WINDOW));
+            for commit in inspector.commits(from..=to)? {
+                println!(
+                    "Commit {} ({}): leader {}, timestamp {}, {} blocks",
+                    commit.index,
+                    commit.digest,
+                    commit.leader,
+                    commit.timestamp_ms,
+                    commit.blocks.len()
+                );
+            }
+        }
+        ConsensusDagCommand::ExportDag(options) => {
+            let rounds = round_range(&inspector, &options.rounds)?;
+            let dag = inspector.export_dag(rounds, options.committee_size)?;
+            match options.output {
+                Some(path) => std::fs::write(path, dag)?,
+                None => print!("{dag}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn round_range(
+    inspector: &DagInspector,
+    options: &RoundRangeOptions,
+) -> anyhow::Result<RangeInclusive<Round>> {
+    let last = inspector
+        .last_round()?
+        .ok_or(anyhow!("No blocks in the store"))?;
+    let to = options.to.unwrap_or(last).min(last);
+    let from = options.from.unwrap_or(to.saturating_sub(DEFAULT_WINDOW));
+    Ok(from..=to)
+}
+
+fn print_block(block: &VerifiedBlock) {
+    println!(
+        "{block}: timestamp {}, {} ancestors, {} transactions",
+        block.timestamp_ms(),
+        block.ancestors().len(),
+        block.transactions().len()
+    );
+}
@@ -7,14 +7,16 @@ use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints,
 use anyhow::{anyhow, bail};
 use clap::Parser;
 use std::path::{Path, PathBuf};
+use sui_config::object_storage_config::ObjectStoreConfig;
 use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::checkpoints::CheckpointStore;
+use sui_storage::db_backup::{backup_to_object_store, restore_from_object_store};
 use sui_types::base_types::{EpochId, ObjectID};
 use sui_types::digests::{CheckpointContentsDigest, TransactionDigest};
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber};
-use typed_store::rocks::MetricConf;
+use typed_store::rocks::{open_cf, MetricConf};
 pub mod db_dump;
 mod index_search;
 
@@ -40,6 +42,8 @@ pub enum DbToolCommand {
     PruneObjects,
     PruneCheckpoints,
     SetCheckpointWatermark(SetCheckpointWatermarkOptions),
+    Backup(BackupOptions),
+    Restore(RestoreOptions),
 }
 
 #[derive(Parser)]
@@ -176,6 +180,44 @@ pub struct SetCheckpointWatermarkOptions {
     highest_synced: Option<CheckpointSequenceNumber>,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct BackupOptions {
+    /// The name of the backup in the object store
+    #[arg(long)]
+    backup_name: String,
+    /// The name of a previous backup to back up incrementally on top of
+    #[arg(long)]
+    parent: Option<String>,
+    /// The prefix of backups in the object store
+    #[arg(long, default_value = "db-backups")]
+    prefix: String,
+    /// The tables to back up. Defaults to all tables of the database
+    #[arg(long = "table-name", short = 't')]
+    table_names: Vec<String>,
+    /// The local directory to stage backup files in
+    #[arg(long)]
+    staging_dir: PathBuf,
+    #[command(flatten)]
+    object_store_config: ObjectStoreConfig,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct RestoreOptions {
+    /// The name of the backup in the object store
+    #[arg(long)]
+    backup_name: String,
+    /// The prefix of backups in the object store
+    #[arg(long, default_value = "db-backups")]
+    prefix: String,
+    /// The local directory to download backup files to
+    #[arg(long)]
+    download_dir: PathBuf,
+    #[command(flatten)]
+    object_store_config: ObjectStoreConfig,
+}
+
 pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
@@ -232,9 +274,56 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             Ok(())
         }
         DbToolCommand::SetCheckpointWatermark(d) => set_checkpoint_watermark(&db_path, d),
+        DbToolCommand::Backup(d) => backup_db(db_path, d).await,
+        DbToolCommand::Restore(d) => restore_db(db_path, d).await,
     }
 }
 
+/// Backs up tables of the database at `db_path`, which can be any typed-store database such as
+/// the perpetual authority store or the consensus store, to an object store.
+pub async fn backup_db(db_path: PathBuf, opt: BackupOptions) -> anyhow::Result<()> {
+    let table_names = if opt.table_names.is_empty() {
+        list_tables(db_path.clone())?
+    } else {
+        opt.table_names
+    };
+    let tables: Vec<_> = table_names.iter().map(String::as_str).collect();
+    let db = open_cf(&db_path, None, MetricConf::default(), &tables)?;
+    let store = opt.object_store_config.make()?;
+    let manifest = backup_to_object_store(
+        db,
+        table_names,
+        &store,
+        &object_store::path::Path::from(opt.prefix),
+        &opt.backup_name,
+        opt.parent.as_deref(),
+        opt.staging_dir,
+        Default::default(),
+    )
+    .await?;
+    println!(
+        "Backed up {} tables into {} files",
+        manifest.column_families.len(),
+        manifest.files().count()
+    );
+    Ok(())
+}
+
+/// Restores a backup from an object store into a new database at `db_path`.
+pub async fn restore_db(db_path: PathBuf, opt: RestoreOptions) -> anyhow::Result<()> {
+    let store = opt.object_store_config.make()?;
+    let manifest = restore_from_object_store(
+        &store,
+        &object_store::path::Path::from(opt.prefix),
+        &opt.backup_name,
+        db_path,
+        opt.download_dir,
+    )
+    .await?;
+    println!("Restored {} tables", manifest.column_families.len());
+    Ok(())
+}
+
 pub fn print_db_all_tables(db_path: PathBuf) -> anyhow::Result<()> {
     list_tables(db_path)?.iter().for_each(|t| println!("{}", t));
     Ok(())
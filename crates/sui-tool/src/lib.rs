@@ -54,12 +54,15 @@ use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::storage::RocksDbStore;
+use sui_snapshot::filter::{LiveObjectFilter, LiveObjectSink, ParquetSink, PerpetualTablesSink};
 use sui_snapshot::reader::StateSnapshotReaderV1;
 use sui_snapshot::setup_db_state;
 use sui_storage::object_store::util::{copy_file, exists, get_path};
 use sui_storage::object_store::ObjectStoreGetExt;
 use sui_storage::verify_checkpoint_range;
-use sui_types::messages_checkpoint::{CheckpointCommitment, ECMHLiveObjectSetDigest};
+use sui_types::messages_checkpoint::{
+    CheckpointCommitment, ECMHLiveObjectSetDigest, VerifiedCheckpoint,
+};
 use sui_types::messages_grpc::{
     ObjectInfoRequest, ObjectInfoRequestKind, ObjectInfoResponse, TransactionInfoRequest,
     TransactionStatus,
@@ -70,6 +73,7 @@ use tracing::info;
 use typed_store::rocks::MetricConf;
 
 pub mod commands;
+pub mod consensus_dag;
 pub mod db_tool;
 
 #[derive(
@@ -90,6 +94,15 @@ pub enum SnapshotVerifyMode {
     Strict,
 }
 
+#[derive(Clone, Debug, PartialEq, Copy, Eq, ValueEnum, Default)]
+pub enum FilteredSnapshotOutput {
+    /// Restore the selected objects into a standalone object store.
+    #[default]
+    Store,
+    /// Write the selected objects into a Parquet file, one row per object.
+    Parquet,
+}
+
 // This functions requires at least one of genesis or fullnode_rpc to be `Some`.
 async fn make_clients(
     sui_client: &Arc<SuiClient>,
@@ -817,6 +830,62 @@ pub async fn check_completed_snapshot(
     }
 }
 
+/// Checks the accumulator computed from the object refs of a formal snapshot against the root
+/// state hash committed to by the last checkpoint of `epoch`.
+fn verify_snapshot_root_state_hash(
+    m: &MultiProgress,
+    last_checkpoint: &VerifiedCheckpoint,
+    epoch: EpochId,
+    root_accumulator: &Accumulator,
+) -> Result<(), anyhow::Error> {
+    if last_checkpoint.epoch() != epoch {
+        return Err(anyhow!(
+            "Expected highest verified checkpoint ({}) to be for epoch {} but was for epoch {}",
+            last_checkpoint.sequence_number,
+            epoch,
+            last_checkpoint.epoch()
+        ));
+    }
+    let commitment = last_checkpoint
+        .end_of_epoch_data
+        .as_ref()
+        .ok_or_else(|| anyhow!("Expected highest verified checkpoint to have end of epoch data"))?
+        .epoch_commitments
+        .last()
+        .ok_or_else(|| {
+            anyhow!(
+                "End of epoch has no commitments. This likely means that the epoch \
+                you are attempting to restore from does not support end of epoch state \
+                digest commitment. If restoring from mainnet, `--epoch` must be > 20, \
+                and for testnet, `--epoch` must be > 12."
+            )
+        })?;
+    match commitment {
+        CheckpointCommitment::ECMHLiveObjectSetDigest(consensus_digest) => {
+            let local_digest: ECMHLiveObjectSetDigest = root_accumulator.digest().into();
+            if *consensus_digest != local_digest {
+                return Err(anyhow!(
+                    "End of epoch {} root state digest {} does not match \
+                    local root state hash {} computed from snapshot data",
+                    epoch,
+                    consensus_digest.digest,
+                    local_digest.digest,
+                ));
+            }
+            let progress_bar = m.add(
+                ProgressBar::new(1).with_style(
+                    ProgressStyle::with_template(
+                        "[{elapsed_precise}] {wide_bar} Verifying snapshot contents against root state hash ({msg})",
+                    )
+                    .unwrap(),
+                ),
+            );
+            progress_bar.finish_with_message("Verification complete");
+        }
+    };
+    Ok(())
+}
+
 pub async fn download_formal_snapshot(
     path: &Path,
     epoch: EpochId,
@@ -917,46 +986,7 @@ pub async fn download_formal_snapshot(
 
     // Perform snapshot state verification
     if verify != SnapshotVerifyMode::None {
-        assert_eq!(
-            last_checkpoint.epoch(),
-            epoch,
-            "Expected highest verified checkpoint ({}) to be for epoch {} but was for epoch {}",
-            last_checkpoint.sequence_number,
-            epoch,
-            last_checkpoint.epoch()
-        );
-        let commitment = last_checkpoint
-            .end_of_epoch_data
-            .as_ref()
-            .expect("Expected highest verified checkpoint to have end of epoch data")
-            .epoch_commitments
-            .last()
-            .expect(
-                "End of epoch has no commitments. This likely means that the epoch \
-                you are attempting to restore from does not support end of epoch state \
-                digest commitment. If restoring from mainnet, `--epoch` must be > 20, \
-                and for testnet, `--epoch` must be > 12.",
-            );
-        match commitment {
-            CheckpointCommitment::ECMHLiveObjectSetDigest(consensus_digest) => {
-                let local_digest: ECMHLiveObjectSetDigest = root_accumulator.digest().into();
-                assert_eq!(
-                    *consensus_digest, local_digest,
-                    "End of epoch {} root state digest {} does not match \
-                    local root state hash {} computed from snapshot data",
-                    epoch, consensus_digest.digest, local_digest.digest,
-                );
-                let progress_bar = m.add(
-                    ProgressBar::new(1).with_style(
-                        ProgressStyle::with_template(
-                            "[{elapsed_precise}] {wide_bar} Verifying snapshot contents against root state hash ({msg})",
-                        )
-                        .unwrap(),
-                    ),
-                );
-                progress_bar.finish_with_message("Verification complete");
-            }
-        };
+        verify_snapshot_root_state_hash(&m, &last_checkpoint, epoch, &root_accumulator)?;
     } else {
         m.println(
             "WARNING: Skipping snapshot verification! \
@@ -1002,6 +1032,185 @@ pub async fn download_formal_snapshot(
     Ok(())
 }
 
+/// Restores only the live objects of a formal snapshot which match `filter`, into a standalone
+/// store or a Parquet file under `path`. All object refs of the snapshot are still accumulated and
+/// checked against the end of epoch root state hash, which proves that the restored subset is
+/// part of the certified state. The objects are restored into a staging directory which only
+/// replaces `path/filtered` once they have been verified.
+#[allow(clippy::too_many_arguments)]
+pub async fn download_filtered_formal_snapshot(
+    path: &Path,
+    epoch: EpochId,
+    genesis: &Path,
+    snapshot_store_config: ObjectStoreConfig,
+    archive_store_config: ObjectStoreConfig,
+    num_parallel_downloads: usize,
+    verify: SnapshotVerifyMode,
+    all_checkpoints: bool,
+    filter: LiveObjectFilter,
+    output: FilteredSnapshotOutput,
+) -> Result<(), anyhow::Error> {
+    let m = MultiProgress::new();
+    m.println(format!(
+        "Beginning filtered formal snapshot restore to end of epoch {}, output: {:?}, verification mode: {:?}",
+        epoch, output, verify,
+    ))?;
+    let staging_path = path.join("staging");
+    let staging_output_path = path.join("filtered.staging");
+    let output_path = path.join("filtered");
+    let snapshot_dir = path.join("snapshot");
+    for dir in [&staging_path, &staging_output_path, &snapshot_dir] {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+    }
+    fs::create_dir_all(&staging_output_path)?;
+    let result = restore_filtered_objects(
+        &m,
+        &staging_path,
+        &staging_output_path,
+        &snapshot_dir,
+        epoch,
+        genesis,
+        snapshot_store_config,
+        archive_store_config,
+        num_parallel_downloads,
+        verify,
+        all_checkpoints,
+        filter,
+        output,
+    )
+    .await;
+    for dir in [&staging_path, &snapshot_dir] {
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+    }
+    let num_objects = match result {
+        Ok(num_objects) => num_objects,
+        Err(err) => {
+            fs::remove_dir_all(&staging_output_path)?;
+            return Err(err);
+        }
+    };
+    if output_path.exists() {
+        fs::remove_dir_all(&output_path)?;
+    }
+    fs::rename(&staging_output_path, &output_path)?;
+    println!(
+        "Successfully restored {} objects from snapshot at end of epoch {} to {}",
+        num_objects,
+        epoch,
+        output_path.display()
+    );
+    Ok(())
+}
+
+/// Restores the live objects matching `filter` into `output_path` and verifies them against the
+/// root state hash, returning the number of restored objects.
+#[allow(clippy::too_many_arguments)]
+async fn restore_filtered_objects(
+    m: &MultiProgress,
+    staging_path: &Path,
+    output_path: &Path,
+    snapshot_dir: &Path,
+    epoch: EpochId,
+    genesis: &Path,
+    snapshot_store_config: ObjectStoreConfig,
+    archive_store_config: ObjectStoreConfig,
+    num_parallel_downloads: usize,
+    verify: SnapshotVerifyMode,
+    all_checkpoints: bool,
+    filter: LiveObjectFilter,
+    output: FilteredSnapshotOutput,
+) -> Result<u64, anyhow::Error> {
+    // Checkpoint summaries are synced into a scratch store to verify the root state hash
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(
+        &staging_path.join("store"),
+        None,
+    ));
+    let genesis = Genesis::load(genesis)?;
+    let genesis_committee = genesis.committee()?;
+    let committee_store = Arc::new(CommitteeStore::new(
+        staging_path.join("epochs"),
+        &genesis_committee,
+        None,
+    ));
+    let checkpoint_store = Arc::new(CheckpointStore::open_tables_read_write(
+        staging_path.join("checkpoints"),
+        MetricConf::default(),
+        None,
+        None,
+    ));
+    let summaries_handle = start_summary_sync(
+        perpetual_db,
+        committee_store,
+        checkpoint_store.clone(),
+        m.clone(),
+        genesis,
+        archive_store_config,
+        epoch,
+        num_parallel_downloads,
+        verify != SnapshotVerifyMode::None,
+        all_checkpoints,
+    );
+
+    let output_db;
+    let mut sink: Box<dyn LiveObjectSink + '_> = match output {
+        FilteredSnapshotOutput::Store => {
+            output_db = AuthorityPerpetualTables::open(&output_path.join("store"), None);
+            Box::new(PerpetualTablesSink::new(&output_db, usize::MAX))
+        }
+        FilteredSnapshotOutput::Parquet => {
+            Box::new(ParquetSink::new(&output_path.join("objects.parquet"))?)
+        }
+    };
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(snapshot_dir.to_path_buf()),
+        ..Default::default()
+    };
+    let mut reader = StateSnapshotReaderV1::new(
+        epoch,
+        &snapshot_store_config,
+        &local_store_config,
+        usize::MAX,
+        NonZeroUsize::new(num_parallel_downloads).unwrap(),
+        m.clone(),
+        false, // skip_reset_local_store
+    )
+    .await?;
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    let (sender, mut receiver) = mpsc::channel(num_parallel_downloads);
+    let accumulate = async move {
+        let mut root_accumulator = Accumulator::default();
+        while let Some((partial_acc, _)) = receiver.recv().await {
+            root_accumulator.union(&partial_acc);
+        }
+        root_accumulator
+    };
+    let (num_objects, root_accumulator) = tokio::join!(
+        reader.read_filtered(&filter, sink.as_mut(), abort_registration, Some(sender)),
+        accumulate
+    );
+    let num_objects = num_objects?;
+    drop(sink);
+    summaries_handle.await??;
+
+    if verify != SnapshotVerifyMode::None {
+        let last_checkpoint = checkpoint_store
+            .get_highest_verified_checkpoint()?
+            .ok_or_else(|| anyhow!("Expected nonempty checkpoint store"))?;
+        verify_snapshot_root_state_hash(m, &last_checkpoint, epoch, &root_accumulator)?;
+    } else {
+        m.println(
+            "WARNING: Skipping snapshot verification! \
+            The restored objects are not proven to be part of the certified state.",
+        )?;
+    }
+    Ok(num_objects)
+}
+
 pub async fn download_db_snapshot(
     path: &Path,
     epoch: u64,
@@ -0,0 +1,536 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Strategies for choosing which coins to spend when a transaction needs to cover an amount,
+//! either as gas payment or as the input coins of a payment.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::bail;
+use sui_types::base_types::{ObjectID, ObjectRef};
+
+/// A coin that may be selected, along with its balance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoinCandidate {
+    pub object_ref: ObjectRef,
+    pub balance: u64,
+}
+
+impl CoinCandidate {
+    pub fn new(object_ref: ObjectRef, balance: u64) -> Self {
+        Self {
+            object_ref,
+            balance,
+        }
+    }
+
+    pub fn object_id(&self) -> ObjectID {
+        self.object_ref.0
+    }
+}
+
+/// Decides which coins are spent to cover an amount.
+///
+/// Implementations only need to define the order in which coins are preferred; selection then
+/// takes coins in that order until the amount is covered.
+pub trait CoinSelector: Send + Sync {
+    /// Order `coins` from most to least preferred for covering `amount`. Coins that must never be
+    /// spent are dropped from the result.
+    fn prioritize(&self, coins: Vec<CoinCandidate>, amount: u64) -> Vec<CoinCandidate>;
+
+    /// Select coins whose combined balance is at least `amount`.
+    fn select(
+        &self,
+        coins: Vec<CoinCandidate>,
+        amount: u64,
+    ) -> Result<Vec<CoinCandidate>, anyhow::Error> {
+        let mut total = 0u64;
+        let mut selected = vec![];
+        for coin in self.prioritize(coins, amount) {
+            total = total.saturating_add(coin.balance);
+            selected.push(coin);
+            if total >= amount {
+                return Ok(selected);
+            }
+        }
+        bail!("Insufficient balance: the available coins hold {total} in total, but {amount} is required")
+    }
+
+    /// Select a single coin whose balance alone covers `amount`, as required for gas payment.
+    fn select_one(&self, coins: Vec<CoinCandidate>, amount: u64) -> Option<CoinCandidate> {
+        let sufficient = coins
+            .into_iter()
+            .filter(|coin| coin.balance >= amount)
+            .collect();
+        self.prioritize(sufficient, amount).into_iter().next()
+    }
+}
+
+impl<S: CoinSelector + ?Sized> CoinSelector for Arc<S> {
+    fn prioritize(&self, coins: Vec<CoinCandidate>, amount: u64) -> Vec<CoinCandidate> {
+        (**self).prioritize(coins, amount)
+    }
+
+    fn select(
+        &self,
+        coins: Vec<CoinCandidate>,
+        amount: u64,
+    ) -> Result<Vec<CoinCandidate>, anyhow::Error> {
+        (**self).select(coins, amount)
+    }
+
+    fn select_one(&self, coins: Vec<CoinCandidate>, amount: u64) -> Option<CoinCandidate> {
+        (**self).select_one(coins, amount)
+    }
+}
+
+/// Prefers coins in the order they were listed by the data source.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FirstFit;
+
+impl CoinSelector for FirstFit {
+    fn prioritize(&self, coins: Vec<CoinCandidate>, _amount: u64) -> Vec<CoinCandidate> {
+        coins
+    }
+}
+
+/// Prefers the coins with the largest balances, minimizing the number of coins spent.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LargestFirst;
+
+impl CoinSelector for LargestFirst {
+    fn prioritize(&self, mut coins: Vec<CoinCandidate>, _amount: u64) -> Vec<CoinCandidate> {
+        coins.sort_by(|a, b| {
+            b.balance
+                .cmp(&a.balance)
+                .then_with(|| a.object_id().cmp(&b.object_id()))
+        });
+        coins
+    }
+}
+
+/// Prefers the coins with the smallest balances, consolidating dust into the payment.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmallestFirst;
+
+impl CoinSelector for SmallestFirst {
+    fn prioritize(&self, mut coins: Vec<CoinCandidate>, _amount: u64) -> Vec<CoinCandidate> {
+        coins.sort_by(|a, b| {
+            a.balance
+                .cmp(&b.balance)
+                .then_with(|| a.object_id().cmp(&b.object_id()))
+        });
+        coins
+    }
+}
+
+/// Prefers a coin whose balance is exactly the amount, so that no change needs to be split off,
+/// and otherwise falls back to another strategy.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExactMatch<S = LargestFirst> {
+    fallback: S,
+}
+
+impl<S> ExactMatch<S> {
+    pub fn new(fallback: S) -> Self {
+        Self { fallback }
+    }
+}
+
+impl<S: CoinSelector> CoinSelector for ExactMatch<S> {
+    fn prioritize(&self, coins: Vec<CoinCandidate>, amount: u64) -> Vec<CoinCandidate> {
+        let (mut exact, rest): (Vec<_>, Vec<_>) = self
+            .fallback
+            .prioritize(coins, amount)
+            .into_iter()
+            .partition(|coin| coin.balance == amount);
+        exact.extend(rest);
+        exact
+    }
+}
+
+/// Never selects coins that are locked, e.g. because they are already used by an in-flight
+/// transaction, and otherwise defers to another strategy.
+#[derive(Clone, Debug)]
+pub struct AvoidLocked<S> {
+    inner: S,
+    locked: BTreeSet<ObjectID>,
+}
+
+impl<S> AvoidLocked<S> {
+    pub fn new(inner: S, locked: impl IntoIterator<Item = ObjectID>) -> Self {
+        Self {
+            inner,
+            locked: locked.into_iter().collect(),
+        }
+    }
+
+    pub fn lock(&mut self, object_id: ObjectID) {
+        self.locked.insert(object_id);
+    }
+
+    pub fn unlock(&mut self, object_id: &ObjectID) {
+        self.locked.remove(object_id);
+    }
+}
+
+impl<S: CoinSelector> CoinSelector for AvoidLocked<S> {
+    fn prioritize(&self, coins: Vec<CoinCandidate>, amount: u64) -> Vec<CoinCandidate> {
+        let unlocked = coins
+            .into_iter()
+            .filter(|coin| !self.locked.contains(&coin.object_id()))
+            .collect();
+        self.inner.prioritize(unlocked, amount)
+    }
+}
+
+/// The built-in strategies, nameable from configuration and the command line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoinSelectionStrategy {
+    #[default]
+    FirstFit,
+    LargestFirst,
+    SmallestFirst,
+    ExactMatch,
+    AvoidLocked,
+}
+
+impl CoinSelectionStrategy {
+    /// Build the selector for this strategy. `locked` lists coins that are known to be in use, e.g.
+    /// by an in-flight transaction; `AvoidLocked` never selects them, preferring the largest of the
+    /// remaining coins, while the other strategies don't consult it.
+    pub fn selector(self, locked: impl IntoIterator<Item = ObjectID>) -> Arc<dyn CoinSelector> {
+        match self {
+            Self::FirstFit => Arc::new(FirstFit),
+            Self::LargestFirst => Arc::new(LargestFirst),
+            Self::SmallestFirst => Arc::new(SmallestFirst),
+            Self::ExactMatch => Arc::new(ExactMatch::new(LargestFirst)),
+            Self::AvoidLocked => Arc::new(AvoidLocked::new(LargestFirst, locked)),
+        }
+    }
+}
+
+impl fmt::Display for CoinSelectionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::FirstFit => "first-fit",
+            Self::LargestFirst => "largest-first",
+            Self::SmallestFirst => "smallest-first",
+            Self::ExactMatch => "exact-match",
+            Self::AvoidLocked => "avoid-locked",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for CoinSelectionStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "first-fit" => Self::FirstFit,
+            "largest-first" => Self::LargestFirst,
+            "smallest-first" => Self::SmallestFirst,
+            "exact-match" => Self::ExactMatch,
+            "avoid-locked" => Self::AvoidLocked,
+            _ => bail!("Unknown coin selection strategy '{s}', expected one of: first-fit, largest-first, smallest-first, exact-match, avoid-locked"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use async_trait::async_trait;
+    use move_core_types::language_storage::StructTag;
+    use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
+    use sui_types::base_types::{ObjectInfo, SuiAddress};
+    use sui_types::gas_coin::GasCoin;
+    use sui_types::object::{Object, ObjectRead};
+    use sui_types::transaction::TransactionDataAPI;
+
+    use super::*;
+    use crate::{DataReader, TransactionBuilder};
+
+    fn coin(id: u8, balance: u64) -> CoinCandidate {
+        let object_id = ObjectID::from_single_byte(id);
+        CoinCandidate::new(
+            Object::with_id_owner_gas_for_testing(object_id, SuiAddress::ZERO, balance)
+                .compute_object_reference(),
+            balance,
+        )
+    }
+
+    fn ids(coins: &[CoinCandidate]) -> Vec<ObjectID> {
+        coins.iter().map(CoinCandidate::object_id).collect()
+    }
+
+    fn wallet() -> Vec<CoinCandidate> {
+        vec![
+            coin(1, 50),
+            coin(2, 5),
+            coin(3, 100),
+            coin(4, 20),
+            coin(5, 5),
+        ]
+    }
+
+    #[test]
+    fn first_fit_keeps_listing_order() {
+        let selected = FirstFit.select(wallet(), 60).unwrap();
+        assert_eq!(
+            ids(&selected),
+            ids(&[coin(1, 50), coin(2, 5), coin(3, 100)])
+        );
+        assert_eq!(FirstFit.select_one(wallet(), 60), Some(coin(3, 100)));
+    }
+
+    #[test]
+    fn largest_first_minimizes_coin_count() {
+        let selected = LargestFirst.select(wallet(), 120).unwrap();
+        assert_eq!(ids(&selected), ids(&[coin(3, 100), coin(1, 50)]));
+        assert_eq!(LargestFirst.select_one(wallet(), 10), Some(coin(3, 100)));
+    }
+
+    #[test]
+    fn smallest_first_consolidates_dust() {
+        let selected = SmallestFirst.select(wallet(), 25).unwrap();
+        // Ties are broken by object id, so the order is deterministic.
+        assert_eq!(ids(&selected), ids(&[coin(2, 5), coin(5, 5), coin(4, 20)]));
+        assert_eq!(SmallestFirst.select_one(wallet(), 10), Some(coin(4, 20)));
+    }
+
+    #[test]
+    fn exact_match_prefers_exact_balance() {
+        let selector = ExactMatch::new(LargestFirst);
+        let selected = selector.select(wallet(), 20).unwrap();
+        assert_eq!(ids(&selected), ids(&[coin(4, 20)]));
+
+        // Without an exact match, the fallback strategy decides.
+        let selected = selector.select(wallet(), 30).unwrap();
+        assert_eq!(ids(&selected), ids(&[coin(3, 100)]));
+        assert_eq!(
+            ExactMatch::new(SmallestFirst).select_one(wallet(), 30),
+            Some(coin(1, 50))
+        );
+    }
+
+    #[test]
+    fn avoid_locked_skips_locked_coins() {
+        let mut selector = AvoidLocked::new(LargestFirst, [coin(3, 100).object_id()]);
+        let selected = selector.select(wallet(), 60).unwrap();
+        assert_eq!(ids(&selected), ids(&[coin(1, 50), coin(4, 20)]));
+
+        // Locked coins are never selected, even when nothing else is sufficient.
+        assert_eq!(selector.select_one(wallet(), 60), None);
+        assert!(selector.select(wallet(), 150).is_err());
+
+        selector.unlock(&coin(3, 100).object_id());
+        assert_eq!(selector.select_one(wallet(), 60), Some(coin(3, 100)));
+    }
+
+    #[test]
+    fn insufficient_balance() {
+        let err = LargestFirst.select(wallet(), 1_000).unwrap_err();
+        assert!(err.to_string().contains("Insufficient balance"), "{err}");
+        assert_eq!(LargestFirst.select_one(wallet(), 1_000), None);
+    }
+
+    #[test]
+    fn strategy_round_trips_through_str() {
+        for strategy in [
+            CoinSelectionStrategy::FirstFit,
+            CoinSelectionStrategy::LargestFirst,
+            CoinSelectionStrategy::SmallestFirst,
+            CoinSelectionStrategy::ExactMatch,
+            CoinSelectionStrategy::AvoidLocked,
+        ] {
+            assert_eq!(
+                strategy
+                    .to_string()
+                    .parse::<CoinSelectionStrategy>()
+                    .unwrap(),
+                strategy
+            );
+        }
+        assert!("biggest".parse::<CoinSelectionStrategy>().is_err());
+    }
+
+    #[test]
+    fn only_avoid_locked_strategy_consults_locked_coins() {
+        let locked = [coin(3, 100).object_id()];
+        assert_eq!(
+            CoinSelectionStrategy::AvoidLocked
+                .selector(locked)
+                .select_one(wallet(), 10),
+            Some(coin(1, 50))
+        );
+        assert_eq!(
+            CoinSelectionStrategy::LargestFirst
+                .selector(locked)
+                .select_one(wallet(), 10),
+            Some(coin(3, 100))
+        );
+    }
+
+    /// A `DataReader` serving a fixed set of gas coins, listed in insertion order.
+    struct MockDataReader {
+        objects: BTreeMap<ObjectID, Object>,
+        order: Vec<ObjectID>,
+    }
+
+    impl MockDataReader {
+        fn new(owner: SuiAddress, balances: &[(u8, u64)]) -> Self {
+            let mut objects = BTreeMap::new();
+            let mut order = vec![];
+            for (id, balance) in balances {
+                let object_id = ObjectID::from_single_byte(*id);
+                objects.insert(
+                    object_id,
+                    Object::with_id_owner_gas_for_testing(object_id, owner, *balance),
+                );
+                order.push(object_id);
+            }
+            Self { objects, order }
+        }
+    }
+
+    #[async_trait]
+    impl DataReader for MockDataReader {
+        async fn get_owned_objects(
+            &self,
+            address: SuiAddress,
+            object_type: StructTag,
+        ) -> Result<Vec<ObjectInfo>, anyhow::Error> {
+            Ok(self
+                .order
+                .iter()
+                .map(|id| &self.objects[id])
+                .filter(|object| {
+                    object.owner.get_address_owner_address().ok() == Some(address)
+                        && object.struct_tag().as_ref() == Some(&object_type)
+                })
+                .map(|object| ObjectInfo::new(&object.compute_object_reference(), object))
+                .collect())
+        }
+
+        async fn get_object_with_options(
+            &self,
+            object_id: ObjectID,
+            options: SuiObjectDataOptions,
+        ) -> Result<SuiObjectResponse, anyhow::Error> {
+            let read = match self.objects.get(&object_id) {
+                Some(object) => ObjectRead::Exists(
+                    object.compute_object_reference(),
+                    object.clone(),
+                    Some(GasCoin::layout()),
+                ),
+                None => ObjectRead::NotExists(object_id),
+            };
+            (read, options).try_into()
+        }
+
+        async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+            Ok(1_000)
+        }
+    }
+
+    fn builder(owner: SuiAddress) -> TransactionBuilder {
+        TransactionBuilder::new(Arc::new(MockDataReader::new(
+            owner,
+            &[(1, 5_000), (2, 2_000_000), (3, 1_000_000), (4, 40_000_000)],
+        )))
+    }
+
+    #[tokio::test]
+    async fn builder_selects_gas_with_strategy() {
+        let owner = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let gas_budget = 1_000_000;
+
+        let gas_coin = |builder: TransactionBuilder| async move {
+            let data = builder
+                .transfer_object(
+                    owner,
+                    ObjectID::from_single_byte(1),
+                    None,
+                    gas_budget,
+                    recipient,
+                )
+                .await
+                .unwrap();
+            data.gas()[0].0
+        };
+
+        // By default the first sufficient coin is used, skipping the coin being transferred.
+        assert_eq!(
+            gas_coin(builder(owner)).await,
+            ObjectID::from_single_byte(2)
+        );
+        assert_eq!(
+            gas_coin(builder(owner).with_coin_selector(Arc::new(LargestFirst))).await,
+            ObjectID::from_single_byte(4)
+        );
+        assert_eq!(
+            gas_coin(builder(owner).with_coin_selector(Arc::new(SmallestFirst))).await,
+            ObjectID::from_single_byte(3)
+        );
+        assert_eq!(
+            gas_coin(builder(owner).with_coin_selector(Arc::new(AvoidLocked::new(
+                SmallestFirst,
+                [ObjectID::from_single_byte(3)],
+            ))))
+            .await,
+            ObjectID::from_single_byte(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn builder_selects_payment_coins() {
+        let owner = SuiAddress::random_for_testing_only();
+        let sui = GasCoin::type_().type_params[0].clone();
+
+        let selected = builder(owner)
+            .with_coin_selector(Arc::new(SmallestFirst))
+            .select_coins(owner, sui.clone(), 1_500_000, &[])
+            .await
+            .unwrap();
+        let selected: Vec<_> = selected.into_iter().map(|r| r.0).collect();
+        assert_eq!(
+            selected,
+            vec![
+                ObjectID::from_single_byte(1),
+                ObjectID::from_single_byte(3),
+                ObjectID::from_single_byte(2)
+            ]
+        );
+
+        let selected = builder(owner)
+            .with_coin_selector(Arc::new(ExactMatch::new(LargestFirst)))
+            .select_coins(owner, sui.clone(), 1_000_000, &[])
+            .await
+            .unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].0, ObjectID::from_single_byte(3));
+
+        // Excluded coins are not considered, and other owners' coins are not visible.
+        let err = builder(owner)
+            .select_coins(
+                owner,
+                sui.clone(),
+                40_000_000,
+                &[ObjectID::from_single_byte(4)],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Insufficient balance"), "{err}");
+        assert!(builder(owner)
+            .select_coins(SuiAddress::random_for_testing_only(), sui, 1, &[])
+            .await
+            .is_err());
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod coin_selection;
+
 use std::collections::BTreeMap;
 use std::result::Result;
 use std::str::FromStr;
@@ -23,7 +25,7 @@ use sui_json_rpc_types::{
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectInfo, ObjectRef, ObjectType, SuiAddress};
 use sui_types::error::UserInputError;
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::GAS;
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
@@ -34,6 +36,8 @@ use sui_types::transaction::{
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
+use crate::coin_selection::{CoinCandidate, CoinSelector, FirstFit};
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -49,14 +53,58 @@ pub trait DataReader {
     ) -> Result<SuiObjectResponse, anyhow::Error>;
 
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    /// Return the coins of type `Coin<coin_type>` owned by `owner`, along with their balances.
+    ///
+    /// The default implementation reads each owned coin object individually; readers that can
+    /// list coins with their balances directly should override it.
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+    ) -> Result<Vec<CoinCandidate>, anyhow::Error> {
+        let mut coins = vec![];
+        for info in self
+            .get_owned_objects(owner, coin::Coin::type_(coin_type))
+            .await?
+        {
+            let response = self
+                .get_object_with_options(info.object_id, SuiObjectDataOptions::new().with_bcs())
+                .await?;
+            let obj = response.object()?;
+            let coin = coin::Coin::from_bcs_bytes(
+                &obj.bcs
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("bcs field is unexpectedly empty"))?
+                    .try_as_move()
+                    .ok_or_else(|| anyhow!("Cannot parse move object to coin object"))?
+                    .bcs_bytes,
+            )?;
+            coins.push(CoinCandidate::new(obj.object_ref(), coin.value()));
+        }
+        Ok(coins)
+    }
 }
 
 #[derive(Clone)]
-pub struct TransactionBuilder(Arc<dyn DataReader + Sync + Send>);
+pub struct TransactionBuilder {
+    data_reader: Arc<dyn DataReader + Sync + Send>,
+    coin_selector: Arc<dyn CoinSelector>,
+}
 
 impl TransactionBuilder {
     pub fn new(data_reader: Arc<dyn DataReader + Sync + Send>) -> Self {
-        Self(data_reader)
+        Self {
+            data_reader,
+            coin_selector: Arc::new(FirstFit),
+        }
+    }
+
+    /// Use `coin_selector` to choose gas coins and payment coins, instead of picking the first
+    /// sufficient coin.
+    pub fn with_coin_selector(mut self, coin_selector: Arc<dyn CoinSelector>) -> Self {
+        self.coin_selector = coin_selector;
+        self
     }
 
     async fn select_gas(
@@ -73,30 +121,45 @@ impl TransactionBuilder {
         if let Some(gas) = input_gas {
             self.get_object_ref(gas).await
         } else {
-            let gas_objs = self.0.get_owned_objects(signer, GasCoin::type_()).await?;
-
-            for obj in gas_objs {
-                let response = self
-                    .0
-                    .get_object_with_options(obj.object_id, SuiObjectDataOptions::new().with_bcs())
-                    .await?;
-                let obj = response.object()?;
-                let gas: GasCoin = bcs::from_bytes(
-                    &obj.bcs
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("bcs field is unexpectedly empty"))?
-                        .try_as_move()
-                        .ok_or_else(|| anyhow!("Cannot parse move object to gas object"))?
-                        .bcs_bytes,
-                )?;
-                if !input_objects.contains(&obj.object_id) && gas.value() >= gas_budget {
-                    return Ok(obj.object_ref());
-                }
+            let candidates = self
+                .coin_candidates(signer, GAS::type_tag(), &input_objects)
+                .await?;
+            match self.coin_selector.select_one(candidates, gas_budget) {
+                Some(gas) => Ok(gas.object_ref),
+                None => Err(anyhow!("Cannot find gas coin for signer address {signer} with amount sufficient for the required gas budget {gas_budget}. If you are using the pay or transfer commands, you can use pay-sui or transfer-sui commands instead, which will use the only object as gas payment.")),
             }
-            Err(anyhow!("Cannot find gas coin for signer address {signer} with amount sufficient for the required gas budget {gas_budget}. If you are using the pay or transfer commands, you can use pay-sui or transfer-sui commands instead, which will use the only object as gas payment."))
         }
     }
 
+    /// Select coins of type `Coin<coin_type>` owned by `owner` whose combined balance covers
+    /// `amount`, using the builder's coin selector. Coins in `exclude` are never selected.
+    pub async fn select_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        amount: u64,
+        exclude: &[ObjectID],
+    ) -> Result<Vec<ObjectRef>, anyhow::Error> {
+        let candidates = self
+            .coin_candidates(owner, coin_type.clone(), exclude)
+            .await?;
+        let selected = self.coin_selector.select(candidates, amount).map_err(|e| {
+            anyhow!("Cannot select {coin_type} coins owned by {owner} to cover {amount}: {e}")
+        })?;
+        Ok(selected.into_iter().map(|coin| coin.object_ref).collect())
+    }
+
+    async fn coin_candidates(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        exclude: &[ObjectID],
+    ) -> Result<Vec<CoinCandidate>, anyhow::Error> {
+        let mut candidates = self.data_reader.get_coins(owner, coin_type).await?;
+        candidates.retain(|coin| !exclude.contains(&coin.object_id()));
+        Ok(candidates)
+    }
+
     /// Construct the transaction data for a dry run
     pub async fn tx_data_for_dry_run(
         &self,
@@ -123,8 +186,8 @@ impl TransactionBuilder {
     }
 
     /// Construct the transaction data from a transaction kind, and other parameters.
-    /// If the gas_payment list is empty, it will pick the first gas coin that has at least
-    /// the required gas budget that is not in the input coins.
+    /// If the gas_payment list is empty, it will use the builder's coin selector to pick a gas
+    /// coin that has at least the required gas budget and is not in the input coins.
     pub async fn tx_data(
         &self,
         sender: SuiAddress,
@@ -182,7 +245,7 @@ impl TransactionBuilder {
         let mut builder = ProgrammableTransactionBuilder::new();
         self.single_transfer_object(&mut builder, object_id, recipient)
             .await?;
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, vec![object_id], gas_price)
             .await?;
@@ -226,7 +289,7 @@ impl TransactionBuilder {
         amount: Option<u64>,
     ) -> anyhow::Result<TransactionData> {
         let object = self.get_object_ref(sui_object_id).await?;
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         Ok(TransactionData::new_transfer_sui(
             recipient, signer, amount, object, gas_budget, gas_price,
         ))
@@ -260,7 +323,7 @@ impl TransactionBuilder {
         }
 
         let coin_refs = self.input_refs(&input_coins).await?;
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, input_coins, gas_price)
             .await?;
@@ -312,7 +375,7 @@ impl TransactionBuilder {
         let mut coin_refs = self.input_refs(&input_coins).await?;
         // [0] is safe because input_coins is non-empty and coins are of same length as input_coins.
         let gas_object_ref = coin_refs.remove(0);
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         TransactionData::new_pay_sui(
             signer,
             coin_refs,
@@ -346,7 +409,7 @@ impl TransactionBuilder {
         let mut coin_refs = self.input_refs(&input_coins).await?;
         // [0] is safe because input_coins is non-empty and coins are of same length as input_coins.
         let gas_object_ref = coin_refs.remove(0);
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         Ok(TransactionData::new_pay_all_sui(
             signer,
             coin_refs,
@@ -413,7 +476,7 @@ impl TransactionBuilder {
         let gas_price = if let Some(gas_price) = gas_price {
             gas_price
         } else {
-            self.0.get_reference_gas_price().await?
+            self.data_reader.get_reference_gas_price().await?
         };
         let gas = self
             .select_gas(signer, gas, gas_budget, input_objects, gas_price)
@@ -466,7 +529,7 @@ impl TransactionBuilder {
         arg_type: &SignatureToken,
     ) -> Result<ObjectArg, anyhow::Error> {
         let response = self
-            .0
+            .data_reader
             .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
             .await?;
 
@@ -505,7 +568,7 @@ impl TransactionBuilder {
         json_args: Vec<SuiJsonValue>,
     ) -> Result<Vec<Argument>, anyhow::Error> {
         let object = self
-            .0
+            .data_reader
             .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
             .await?
             .into_object()?;
@@ -597,7 +660,7 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(sender, gas, gas_budget, vec![], gas_price)
             .await?;
@@ -621,7 +684,7 @@ impl TransactionBuilder {
         digest: Vec<u8>,
     ) -> Result<TransactionKind, anyhow::Error> {
         let upgrade_capability = self
-            .0
+            .data_reader
             .get_object_with_options(upgrade_capability, SuiObjectDataOptions::new().with_owner())
             .await?
             .into_object()?;
@@ -693,12 +756,12 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(sender, gas, gas_budget, vec![], gas_price)
             .await?;
         let upgrade_cap = self
-            .0
+            .data_reader
             .get_object_with_options(upgrade_capability, SuiObjectDataOptions::new().with_owner())
             .await?
             .into_object()?;
@@ -735,7 +798,7 @@ impl TransactionBuilder {
             );
         }
         let coin = self
-            .0
+            .data_reader
             .get_object_with_options(coin_object_id, SuiObjectDataOptions::bcs_lossless())
             .await?
             .into_object()?;
@@ -779,14 +842,14 @@ impl TransactionBuilder {
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
         let coin = self
-            .0
+            .data_reader
             .get_object_with_options(coin_object_id, SuiObjectDataOptions::bcs_lossless())
             .await?
             .into_object()?;
         let coin_object_ref = coin.object_ref();
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, vec![coin_object_id], gas_price)
             .await?;
@@ -817,14 +880,14 @@ impl TransactionBuilder {
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
         let coin = self
-            .0
+            .data_reader
             .get_object_with_options(coin_object_id, SuiObjectDataOptions::bcs_lossless())
             .await?
             .into_object()?;
         let coin_object_ref = coin.object_ref();
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, vec![coin_object_id], gas_price)
             .await?;
@@ -851,7 +914,7 @@ impl TransactionBuilder {
         coin_to_merge: ObjectID,
     ) -> Result<TransactionKind, anyhow::Error> {
         let coin = self
-            .0
+            .data_reader
             .get_object_with_options(primary_coin, SuiObjectDataOptions::bcs_lossless())
             .await?
             .into_object()?;
@@ -885,7 +948,7 @@ impl TransactionBuilder {
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
         let coin = self
-            .0
+            .data_reader
             .get_object_with_options(primary_coin, SuiObjectDataOptions::bcs_lossless())
             .await?
             .into_object()?;
@@ -893,7 +956,7 @@ impl TransactionBuilder {
         let coin_to_merge_ref = self.get_object_ref(coin_to_merge).await?;
         let coin: Object = coin.try_into()?;
         let type_args = vec![coin.get_move_template_type()?];
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(
                 signer,
@@ -963,7 +1026,7 @@ impl TransactionBuilder {
                 _ => None,
             })
             .collect();
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, inputs, gas_price)
             .await?;
@@ -986,7 +1049,7 @@ impl TransactionBuilder {
         gas: Option<ObjectID>,
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, coins.clone(), gas_price)
             .await?;
@@ -1053,7 +1116,7 @@ impl TransactionBuilder {
         gas_budget: u64,
     ) -> anyhow::Result<TransactionData> {
         let staked_sui = self.get_object_ref(staked_sui).await?;
-        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas_price = self.data_reader.get_reference_gas_price().await?;
         let gas = self
             .select_gas(signer, gas, gas_budget, vec![], gas_price)
             .await?;
@@ -1085,7 +1148,7 @@ impl TransactionBuilder {
         object_id: ObjectID,
     ) -> anyhow::Result<(ObjectRef, ObjectType)> {
         let object = self
-            .0
+            .data_reader
             .get_object_with_options(object_id, SuiObjectDataOptions::new().with_type())
             .await?
             .into_object()?;
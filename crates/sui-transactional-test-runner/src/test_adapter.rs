@@ -1327,7 +1327,12 @@ impl<'a> SuiTestAdapter {
 
                 variables.insert(format!("cursor_{idx}"), base64d);
             } else {
-                variables.insert(format!("cursor_{idx}"), Base64::encode(s));
+                // Other cursors may refer to variables, e.g. the ID of an object in a JSON cursor
+                let mut cursor = s.clone();
+                for (name, value) in &variables {
+                    cursor = cursor.replace(&format!("@{{{name}}}"), value);
+                }
+                variables.insert(format!("cursor_{idx}"), Base64::encode(cursor));
             }
         }
 
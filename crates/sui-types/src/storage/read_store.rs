@@ -630,6 +630,15 @@ pub trait RpcIndexes: Send + Sync {
     ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
 
     fn get_coin_info(&self, coin_type: &StructTag) -> Result<Option<CoinInfo>>;
+
+    fn get_balance(&self, owner: &SuiAddress, coin_type: &TypeTag) -> Result<BalanceInfo>;
+
+    /// Iterate over the balance of each coin type owned by `owner`, ordered by coin type.
+    fn balance_iter(
+        &self,
+        owner: &SuiAddress,
+        cursor: Option<TypeTag>,
+    ) -> Result<Box<dyn Iterator<Item = (TypeTag, BalanceInfo)> + '_>>;
 }
 
 pub struct AccountOwnedObjectInfo {
@@ -675,3 +684,9 @@ pub struct CoinInfo {
     pub coin_metadata_object_id: Option<ObjectID>,
     pub treasury_object_id: Option<ObjectID>,
 }
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct BalanceInfo {
+    pub balance: u64,
+    pub coin_object_count: u64,
+}
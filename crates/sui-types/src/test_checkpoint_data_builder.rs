@@ -18,10 +18,12 @@ use crate::{
     coin::Coin,
     committee::Committee,
     digests::TransactionDigest,
+    dynamic_field::DynamicFieldInfo,
     effects::{TestEffectsBuilder, TransactionEffectsAPI, TransactionEvents},
     event::{Event, SystemEpochInfoEvent},
     full_checkpoint_content::{CheckpointData, CheckpointTransaction},
     gas_coin::GAS,
+    id::ID,
     message_envelope::Message,
     messages_checkpoint::{
         CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, EndOfEpochData,
@@ -230,6 +232,74 @@ impl TestCheckpointDataBuilder {
         self
     }
 
+    /// Create a new dynamic field in the transaction, with a `u64` name and a `u64` value.
+    /// `object_idx` is a convenient representation of the field object's ID.
+    /// `parent_idx` is a convenient representation of the parent object's ID.
+    pub fn create_dynamic_field(
+        self,
+        object_idx: u64,
+        parent_idx: u64,
+        name: u64,
+        value: u64,
+    ) -> Self {
+        let type_ = DynamicFieldInfo::dynamic_field_type(TypeTag::U64, TypeTag::U64);
+        let contents = bcs::to_bytes(&(Self::derive_object_id(object_idx), name, value)).unwrap();
+        self.create_dynamic_field_with_contents(object_idx, parent_idx, type_, contents)
+    }
+
+    /// Create a new dynamic object field in the transaction, with a `u64` name.
+    /// `object_idx` is a convenient representation of the field object's ID.
+    /// `parent_idx` is a convenient representation of the parent object's ID.
+    /// `child_idx` is a convenient representation of the ID of the object stored in the field.
+    pub fn create_dynamic_object_field(
+        self,
+        object_idx: u64,
+        parent_idx: u64,
+        name: u64,
+        child_idx: u64,
+    ) -> Self {
+        let wrapper = DynamicFieldInfo::dynamic_object_field_wrapper(TypeTag::U64);
+        let type_ = DynamicFieldInfo::dynamic_field_type(wrapper.into(), ID::type_().into());
+        let contents = bcs::to_bytes(&(
+            Self::derive_object_id(object_idx),
+            name,
+            Self::derive_object_id(child_idx),
+        ))
+        .unwrap();
+        self.create_dynamic_field_with_contents(object_idx, parent_idx, type_, contents)
+    }
+
+    fn create_dynamic_field_with_contents(
+        mut self,
+        object_idx: u64,
+        parent_idx: u64,
+        type_: StructTag,
+        contents: Vec<u8>,
+    ) -> Self {
+        let tx_builder = self.checkpoint_builder.next_transaction.as_mut().unwrap();
+        let object_id = Self::derive_object_id(object_idx);
+        assert!(
+            !self.live_objects.contains_key(&object_id),
+            "Object already exists: {}. Please use a different object index.",
+            object_id
+        );
+        // SAFETY: `Field` has `key` but not `store`, so it never has public transfer.
+        let move_object = unsafe {
+            MoveObject::new_from_execution_with_limit(
+                type_.into(),
+                false,
+                SequenceNumber::MIN,
+                contents,
+                u64::MAX,
+            )
+        }
+        .unwrap();
+        let owner = Owner::ObjectOwner(Self::derive_object_id(parent_idx).into());
+        let object = Object::new_move(move_object, owner, TransactionDigest::ZERO);
+        tx_builder.created_objects.insert(object_id, object);
+        self
+    }
+
     /// Mutate an existing object in the transaction.
     /// `object_idx` is a convenient representation of the object's ID.
     pub fn mutate_object(mut self, object_idx: u64) -> Self {
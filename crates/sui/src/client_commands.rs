@@ -69,7 +69,7 @@ use sui_types::{
     digests::TransactionDigest,
     error::SuiError,
     gas::GasCostSummary,
-    gas_coin::GasCoin,
+    gas_coin::{GasCoin, GAS},
     message_envelope::Envelope,
     metrics::BytecodeVerifierMetrics,
     move_package::UpgradeCap,
@@ -94,6 +94,7 @@ use tabled::{
     },
 };
 
+use sui_transaction_builder::coin_selection::{CoinSelectionStrategy, CoinSelector};
 use sui_types::digests::ChainIdentifier;
 use tracing::{debug, info};
 
@@ -290,9 +291,22 @@ pub enum SuiClientCommands {
     #[clap(name = "pay")]
     Pay {
         /// The input coins to be used for pay recipients, following the specified amounts.
+        /// If omitted, coins of `--coin-type` owned by the active address are selected to cover
+        /// the amounts, following `--coin-selection`.
         #[clap(long, num_args(1..))]
         input_coins: Vec<ObjectID>,
 
+        /// The type of coin to select as input coins when `--input-coins` is omitted.
+        /// Defaults to SUI.
+        #[clap(long, conflicts_with = "input_coins")]
+        coin_type: Option<TypeTag>,
+
+        /// How input coins are selected when `--input-coins` is omitted: `first-fit`,
+        /// `largest-first`, `smallest-first` (to consolidate small coins), `exact-match` or
+        /// `avoid-locked` (never touching the `--gas` coin).
+        #[clap(long, default_value_t = CoinSelectionStrategy::LargestFirst)]
+        coin_selection: CoinSelectionStrategy,
+
         /// The recipient addresses, must be of same length as amounts.
         /// Aliases of addresses are also accepted as input.
         #[clap(long, num_args(1..))]
@@ -326,9 +340,17 @@ pub enum SuiClientCommands {
     /// The input coins also include the coin for gas payment, so no extra gas coin is required.
     PaySui {
         /// The input coins to be used for pay recipients, including the gas coin.
+        /// If omitted, SUI coins owned by the active address are selected to cover the amounts
+        /// and the gas budget, following `--coin-selection`.
         #[clap(long, num_args(1..))]
         input_coins: Vec<ObjectID>,
 
+        /// How input coins are selected when `--input-coins` is omitted: `first-fit`,
+        /// `largest-first`, `smallest-first` (to consolidate small coins), `exact-match` or
+        /// `avoid-locked`.
+        #[clap(long, default_value_t = CoinSelectionStrategy::LargestFirst)]
+        coin_selection: CoinSelectionStrategy,
+
         /// The recipient addresses, must be of same length as amounts.
         /// Aliases of addresses are also accepted as input.
         #[clap(long, num_args(1..))]
@@ -720,6 +742,8 @@ impl SuiClientCommands {
                     executor_version,
                     protocol_version,
                     config_objects: None,
+                    diff_format: Default::default(),
+                    offline: Default::default(),
                 };
 
                 let rpc = context.config.get_active_env()?.rpc.clone();
@@ -756,6 +780,7 @@ impl SuiClientCommands {
                     end,
                     terminate_early,
                     max_tasks: 16,
+                    offline: Default::default(),
                 };
                 let rpc = context.config.get_active_env()?.rpc.clone();
                 let _command_result =
@@ -1303,14 +1328,12 @@ impl SuiClientCommands {
 
             SuiClientCommands::Pay {
                 input_coins,
+                coin_type,
+                coin_selection,
                 recipients,
                 amounts,
                 opts,
             } => {
-                ensure!(
-                    !input_coins.is_empty(),
-                    "Pay transaction requires a non-empty list of input coins"
-                );
                 ensure!(
                     !recipients.is_empty(),
                     "Pay transaction requires a non-empty list of recipient addresses"
@@ -1328,7 +1351,23 @@ impl SuiClientCommands {
                     .map(|x| get_identity_address(Some(x), context))
                     .collect::<Result<Vec<SuiAddress>, anyhow::Error>>()
                     .map_err(|e| anyhow!("{e}"))?;
-                let signer = context.get_object_owner(&input_coins[0]).await?;
+                let (signer, input_coins) = if input_coins.is_empty() {
+                    let amount = total_amount(&amounts)?;
+                    // The gas coin can't also be an input coin, and is locked for strategies that
+                    // avoid locked coins.
+                    let gas: Vec<_> = opts.gas.into_iter().collect();
+                    select_input_coins(
+                        context,
+                        coin_type.unwrap_or_else(GAS::type_tag),
+                        amount,
+                        &gas,
+                        coin_selection.selector(gas.clone()),
+                    )
+                    .await?
+                } else {
+                    let signer = context.get_object_owner(&input_coins[0]).await?;
+                    (signer, input_coins)
+                };
                 let client = context.get_client().await?;
                 let tx_kind = client
                     .transaction_builder()
@@ -1349,14 +1388,11 @@ impl SuiClientCommands {
 
             SuiClientCommands::PaySui {
                 input_coins,
+                coin_selection,
                 recipients,
                 amounts,
                 opts,
             } => {
-                ensure!(
-                    !input_coins.is_empty(),
-                    "PaySui transaction requires a non-empty list of input coins"
-                );
                 ensure!(
                     !recipients.is_empty(),
                     "PaySui transaction requires a non-empty list of recipient addresses"
@@ -1374,7 +1410,24 @@ impl SuiClientCommands {
                     .map(|x| get_identity_address(Some(x), context))
                     .collect::<Result<Vec<SuiAddress>, anyhow::Error>>()
                     .map_err(|e| anyhow!("{e}"))?;
-                let signer = context.get_object_owner(&input_coins[0]).await?;
+                let (signer, input_coins) = if input_coins.is_empty() {
+                    // The input coins also pay for gas, so they need to cover the gas budget too,
+                    // when it is known up front.
+                    let amount = total_amount(&amounts)?
+                        .checked_add(opts.gas_budget.unwrap_or(0))
+                        .ok_or_else(|| anyhow!("Total amount to pay overflows u64"))?;
+                    select_input_coins(
+                        context,
+                        GAS::type_tag(),
+                        amount,
+                        &[],
+                        coin_selection.selector([]),
+                    )
+                    .await?
+                } else {
+                    let signer = context.get_object_owner(&input_coins[0]).await?;
+                    (signer, input_coins)
+                };
                 let client = context.get_client().await?;
                 let tx_kind = client
                     .transaction_builder()
@@ -2766,6 +2819,33 @@ fn format_balance(
     format!("{whole}.{fractional}{suffix}")
 }
 
+fn total_amount(amounts: &[u64]) -> Result<u64, anyhow::Error> {
+    amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or_else(|| anyhow!("Total amount to pay overflows u64"))
+}
+
+/// Select coins of `coin_type` owned by the active address that cover `amount`, for pay commands
+/// that were not given explicit input coins. Returns the signer along with the selected coins.
+async fn select_input_coins(
+    context: &mut WalletContext,
+    coin_type: TypeTag,
+    amount: u64,
+    exclude: &[ObjectID],
+    coin_selector: Arc<dyn CoinSelector>,
+) -> Result<(SuiAddress, Vec<ObjectID>), anyhow::Error> {
+    let signer = context.active_address()?;
+    let client = context.get_client().await?;
+    let coins = client
+        .transaction_builder()
+        .clone()
+        .with_coin_selector(coin_selector)
+        .select_coins(signer, coin_type, amount, exclude)
+        .await?;
+    Ok((signer, coins.into_iter().map(|(id, _, _)| id).collect()))
+}
+
 /// Helper function to reduce code duplication for executing dry run
 pub async fn execute_dry_run(
     context: &mut WalletContext,
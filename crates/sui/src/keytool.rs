@@ -19,6 +19,7 @@ use fastcrypto_zkp::bn254::zk_login::{fetch_jwks, OIDCProvider, ZkLoginInputs};
 use fastcrypto_zkp::bn254::zk_login::{JwkId, JWK};
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use im::hashmap::HashMap as ImHashMap;
+use inquire::Password;
 use json_to_table::{json_to_table, Orientation};
 use num_bigint::BigUint;
 use rand::rngs::StdRng;
@@ -33,12 +34,14 @@ use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
+use sui_keys::encryption::{ScryptParams, KEYSTORE_PASSPHRASE_ENV};
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{
@@ -147,6 +150,11 @@ pub enum KeyToolCommand {
     /// (Base64 encoded `privkey`). This prints out the account keypair as Base64 encoded `flag || privkey`,
     /// the network keypair, worker keypair, protocol keypair as Base64 encoded `privkey`.
     LoadKeypair { file: PathBuf },
+    /// Encrypt the keystore with a passphrase, prompting for a new passphrase if the keystore is
+    /// not encrypted yet (migrating it from the legacy plaintext format). If it is already
+    /// encrypted, forget any unlock cached by `sui keytool unlock`, so that the passphrase is
+    /// needed to sign with it again.
+    Lock,
     /// To MultiSig Sui Address. Pass in a list of all public keys `flag || pk` in Base64.
     /// See `keytool list` for example public keys.
     MultiSigAddress {
@@ -187,6 +195,9 @@ pub enum KeyToolCommand {
         threshold: ThresholdUnit,
     },
 
+    /// Re-encrypt an encrypted keystore with a new passphrase, prompting for the current and new
+    /// passphrases. Any unlock cached with the old passphrase is forgotten.
+    RotatePassphrase,
     /// Read the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
     /// (Base64 encoded `privkey`). It prints its Base64 encoded public key and the key scheme flag.
@@ -223,6 +234,15 @@ pub enum KeyToolCommand {
     /// outputs the keypair into a file at the current directory where the address is the filename,
     /// and prints out its Sui address, Base64 encoded public key, the key scheme, and the key scheme flag.
     Unpack { keypair: String },
+    /// Unlock an encrypted keystore, prompting for its passphrase. The keystore stays unlocked for
+    /// subsequent commands until the timeout elapses, or it is locked again with
+    /// `sui keytool lock`. The passphrase can also be provided through the
+    /// `SUI_KEYSTORE_PASSPHRASE` environment variable, for non-interactive use.
+    Unlock {
+        /// How long the keystore stays unlocked for, in seconds.
+        #[clap(long, default_value_t = 900)]
+        timeout_secs: u64,
+    },
 
     /// Given the max_epoch, generate an OAuth url, ask user to paste the redirect with id_token, call salt server, then call the prover server,
     /// create a test transaction, use the ephemeral key to sign and execute it by assembling to a serialized zkLogin signature.
@@ -395,6 +415,15 @@ pub struct ConvertOutput {
     scheme: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreLockStatus {
+    encrypted: bool,
+    locked: bool,
+    // How long the keystore stays unlocked for, in seconds, if it was unlocked.
+    unlocked_for_secs: Option<u64>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrivateKeyBase64 {
@@ -464,13 +493,16 @@ pub enum CommandOutput {
     Export(ExportedKey),
     List(Vec<Key>),
     LoadKeypair(KeypairData),
+    Lock(KeystoreLockStatus),
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
     PrivateKeyBase64(PrivateKeyBase64),
+    RotatePassphrase(KeystoreLockStatus),
     Show(Key),
     Sign(SignData),
     SignKMS(SerializedSig),
+    Unlock(KeystoreLockStatus),
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
     ZkLoginSigVerify(ZkLoginSigVerifyResponse),
@@ -729,6 +761,21 @@ impl KeyToolCommand {
                 CommandOutput::LoadKeypair(output)
             }
 
+            KeyToolCommand::Lock => {
+                let keystore = file_keystore(keystore)?;
+                if keystore.is_encrypted() {
+                    keystore.lock()?;
+                } else {
+                    let passphrase = Password::new("New keystore passphrase:")
+                        .with_custom_confirmation_message("Confirm passphrase:")
+                        .prompt()?;
+                    keystore.encrypt(&passphrase, ScryptParams::default())?;
+                    keystore.lock()?;
+                }
+
+                CommandOutput::Lock(lock_status(keystore, None))
+            }
+
             KeyToolCommand::MultiSigAddress {
                 threshold,
                 pks,
@@ -792,6 +839,26 @@ impl KeyToolCommand {
                 )
             }
 
+            KeyToolCommand::RotatePassphrase => {
+                let keystore = file_keystore(keystore)?;
+                if !keystore.is_encrypted() {
+                    return Err(anyhow!(
+                        "Keystore is not encrypted, encrypt it with `sui keytool lock`"
+                    ));
+                }
+
+                let old_passphrase = Password::new("Current keystore passphrase:")
+                    .without_confirmation()
+                    .prompt()?;
+                let new_passphrase = Password::new("New keystore passphrase:")
+                    .with_custom_confirmation_message("Confirm passphrase:")
+                    .prompt()?;
+                keystore.rotate_passphrase(&old_passphrase, &new_passphrase)?;
+                keystore.lock()?;
+
+                CommandOutput::RotatePassphrase(lock_status(keystore, None))
+            }
+
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
@@ -911,6 +978,26 @@ impl KeyToolCommand {
                 })
             }
 
+            KeyToolCommand::Unlock { timeout_secs } => {
+                let keystore = file_keystore(keystore)?;
+                if !keystore.is_encrypted() {
+                    return Err(anyhow!(
+                        "Keystore is not encrypted, encrypt it with `sui keytool lock`"
+                    ));
+                }
+
+                let passphrase = match std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+                    Ok(passphrase) => passphrase,
+                    Err(_) => Password::new("Keystore passphrase:")
+                        .without_confirmation()
+                        .prompt()?,
+                };
+                keystore.unlock(&passphrase)?;
+                keystore.cache_unlock(Duration::from_secs(timeout_secs))?;
+
+                CommandOutput::Unlock(lock_status(keystore, Some(timeout_secs)))
+            }
+
             KeyToolCommand::Unpack { keypair } => {
                 let keypair = SuiKeyPair::decode_base64(&keypair)
                     .map_err(|_| anyhow!("Invalid Base64 encode keypair"))?;
@@ -1400,6 +1487,22 @@ impl Debug for CommandOutput {
     }
 }
 
+/// The file-based keystore, which is the only kind of keystore that can be encrypted.
+fn file_keystore(keystore: &mut Keystore) -> Result<&mut FileBasedKeystore, anyhow::Error> {
+    match keystore {
+        Keystore::File(keystore) => Ok(keystore),
+        _ => Err(anyhow!("Only file-based keystores can be encrypted")),
+    }
+}
+
+fn lock_status(keystore: &FileBasedKeystore, unlocked_for_secs: Option<u64>) -> KeystoreLockStatus {
+    KeystoreLockStatus {
+        encrypted: keystore.is_encrypted(),
+        locked: keystore.is_locked(),
+        unlocked_for_secs,
+    }
+}
+
 /// Converts legacy formatted private key to 33 bytes bech32 encoded private key or vice versa.
 /// It can handle:
 /// 1) Hex encoded 32 byte private key (assumes scheme is Ed25519), this is the legacy wallet format
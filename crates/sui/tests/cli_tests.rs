@@ -19,6 +19,7 @@ use sui::key_identity::{get_identity_address, KeyIdentity};
 use sui::sui_commands::IndexerArgs;
 use sui_sdk::SuiClient;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
+use sui_transaction_builder::coin_selection::CoinSelectionStrategy;
 use sui_types::object::Owner;
 use sui_types::transaction::{
     TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
@@ -3290,6 +3291,8 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
     // === PAY === //
     let pay_dry_run = SuiClientCommands::Pay {
         input_coins: vec![object_id],
+        coin_type: None,
+        coin_selection: CoinSelectionStrategy::LargestFirst,
         recipients: vec![KeyIdentity::Address(SuiAddress::random_for_testing_only())],
         amounts: vec![1],
         opts: OptsWithGas::for_testing_dry_run(None, rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
@@ -3308,6 +3311,8 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
     let gas_coin_id = object_refs.data.last().unwrap().object().unwrap().object_id;
     let pay_dry_run = SuiClientCommands::Pay {
         input_coins: vec![object_id],
+        coin_type: None,
+        coin_selection: CoinSelectionStrategy::LargestFirst,
         recipients: vec![KeyIdentity::Address(SuiAddress::random_for_testing_only())],
         amounts: vec![1],
         opts: OptsWithGas::for_testing_dry_run(
@@ -3323,6 +3328,7 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
     // === PAY SUI === //
     let pay_sui_dry_run = SuiClientCommands::PaySui {
         input_coins: vec![object_id],
+        coin_selection: CoinSelectionStrategy::LargestFirst,
         recipients: vec![KeyIdentity::Address(SuiAddress::random_for_testing_only())],
         amounts: vec![1],
         opts: Opts::for_testing_dry_run(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
@@ -3406,6 +3412,8 @@ async fn test_pay() -> Result<(), anyhow::Error> {
     let context = &mut test_cluster.wallet;
     let pay = SuiClientCommands::Pay {
         input_coins: vec![object_id1, object_id2],
+        coin_type: None,
+        coin_selection: CoinSelectionStrategy::LargestFirst,
         recipients: vec![recipient1.clone(), recipient2.clone()],
         amounts: vec![5000, 10000],
         opts: OptsWithGas::for_testing(Some(object_id1), rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
@@ -3420,6 +3428,8 @@ async fn test_pay() -> Result<(), anyhow::Error> {
     // we expect this to be the gas coin used
     let pay = SuiClientCommands::Pay {
         input_coins: vec![object_id1, object_id2],
+        coin_type: None,
+        coin_selection: CoinSelectionStrategy::LargestFirst,
         recipients: vec![recipient1.clone(), recipient2.clone()],
         amounts: amounts.into(),
         opts: OptsWithGas::for_testing(None, rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
@@ -3501,6 +3511,7 @@ async fn test_pay_sui() -> Result<(), anyhow::Error> {
     let amounts = [1000, 5000];
     let pay_sui = SuiClientCommands::PaySui {
         input_coins: vec![object_id1, object_id2],
+        coin_selection: CoinSelectionStrategy::LargestFirst,
         recipients: vec![recipient1.clone(), recipient2.clone()],
         amounts: amounts.into(),
         opts: Opts::for_testing(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
@@ -3569,6 +3580,56 @@ async fn test_pay_sui() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_pay_sui_selects_input_coins() -> Result<(), anyhow::Error> {
+    let (mut test_cluster, client, rgp, _, recipients, addresses) = test_cluster_helper().await;
+    let context = &mut test_cluster.wallet;
+    let address1 = context.active_address()?;
+    let amounts = [1000, 5000];
+
+    // All genesis coins hold the same balance, so smallest-first falls back to the coin with the
+    // lowest object id, which alone covers the amounts and gas budget.
+    let expected_coin = client
+        .coin_read_api()
+        .get_coins(address1, None, None, None)
+        .await?
+        .data
+        .into_iter()
+        .map(|coin| coin.coin_object_id)
+        .min()
+        .unwrap();
+
+    let pay_sui = SuiClientCommands::PaySui {
+        input_coins: vec![],
+        coin_selection: CoinSelectionStrategy::SmallestFirst,
+        recipients: recipients.to_vec(),
+        amounts: amounts.into(),
+        opts: Opts::for_testing(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+    }
+    .execute(context)
+    .await?;
+
+    let SuiClientCommandResult::TransactionBlock(response) = pay_sui else {
+        panic!("PaySui test failed");
+    };
+    assert!(response.status_ok().unwrap());
+    assert_eq!(
+        response.effects.as_ref().unwrap().gas_object().object_id(),
+        expected_coin
+    );
+    for (address, amount) in addresses.into_iter().zip(amounts) {
+        assert_eq!(
+            client
+                .coin_read_api()
+                .get_balance(address, None)
+                .await?
+                .total_balance,
+            amount as u128
+        );
+    }
+    Ok(())
+}
+
 #[sim_test]
 async fn test_pay_all_sui() -> Result<(), anyhow::Error> {
     let (mut test_cluster, client, rgp, objects, recipients, addresses) =
@@ -21,6 +21,10 @@ const DB_OPTIONS_CUSTOM_FUNCTION: &str = "default_options_override_fn";
 const DB_OPTIONS_RENAME: &str = "rename";
 // Deprecate a column family
 const DB_OPTIONS_DEPRECATE: &str = "deprecated";
+// Function returning the schema migrations of a table
+const DB_OPTIONS_MIGRATIONS: &str = "migrations";
+// Table recording the schema versions of migrated tables
+const DB_OPTIONS_SCHEMA_VERSIONS: &str = "schema_versions";
 
 /// Options can either be simplified form or
 enum GeneralTableOptions {
@@ -45,6 +49,8 @@ fn extract_struct_info(
         .collect();
     let allowed_strs = allowed_strs.join(" or ");
     let mut deprecated_cfs = vec![];
+    let mut migrations = vec![];
+    let mut schema_versions_field = None;
 
     let info = input.fields.iter().map(|f| {
         let attrs: BTreeMap<_, _> = f
@@ -54,6 +60,8 @@ fn extract_struct_info(
                 a.path.is_ident(DB_OPTIONS_CUSTOM_FUNCTION)
                     || a.path.is_ident(DB_OPTIONS_RENAME)
                     || a.path.is_ident(DB_OPTIONS_DEPRECATE)
+                    || a.path.is_ident(DB_OPTIONS_MIGRATIONS)
+                    || a.path.is_ident(DB_OPTIONS_SCHEMA_VERSIONS)
             })
             .map(|a| (a.path.get_ident().unwrap().to_string(), a))
             .collect();
@@ -96,6 +104,15 @@ fn extract_struct_info(
                 if attrs.contains_key(DB_OPTIONS_DEPRECATE) {
                     deprecated_cfs.push(field_name.clone());
                 }
+                if let Some(attr) = attrs.get(DB_OPTIONS_MIGRATIONS) {
+                    migrations.push((field_name.clone(), get_migrations_function(attr).unwrap()));
+                }
+                if attrs.contains_key(DB_OPTIONS_SCHEMA_VERSIONS) {
+                    if schema_versions_field.is_some() {
+                        panic!("Only one table can record schema versions");
+                    }
+                    schema_versions_field = Some(field_name.clone());
+                }
 
                 return ((field_name, cf_name, type_str), (inner_type, options));
             } else {
@@ -122,6 +139,12 @@ fn extract_struct_info(
 
     let (inner_types, options): (Vec<_>, Vec<_>) = inner_types_with_opts.into_iter().unzip();
 
+    if !migrations.is_empty() && schema_versions_field.is_none() {
+        panic!(
+            "Tables with migrations require a table annotated with #[{DB_OPTIONS_SCHEMA_VERSIONS}]"
+        );
+    }
+
     ExtractedStructInfo {
         field_names,
         cf_names,
@@ -129,6 +152,8 @@ fn extract_struct_info(
         derived_table_options: options,
         simple_field_type_name_str: simple_field_type_names.first().unwrap().clone(),
         deprecated_cfs,
+        migrations,
+        schema_versions_field,
     }
 }
 
@@ -164,6 +189,24 @@ fn get_options_override_function(attr: &Attribute) -> syn::Result<String> {
     Ok(fn_name.value())
 }
 
+/// Extracts the function returning the migrations of a table
+/// The function must take no args and return Vec<Box<dyn typed_store::migration::Migration>>
+fn get_migrations_function(attr: &Attribute) -> syn::Result<String> {
+    let meta = attr.parse_meta()?;
+    match meta {
+        Meta::NameValue(syn::MetaNameValue {
+            lit: Lit::Str(fn_name),
+            ..
+        }) => Ok(fn_name.value()),
+        _ => Err(syn::Error::new_spanned(
+            meta,
+            format!(
+                "Expected function name in format `#[{DB_OPTIONS_MIGRATIONS} = {{function_name}}]`"
+            ),
+        )),
+    }
+}
+
 fn extract_generics_names(generics: &Generics) -> Vec<Ident> {
     generics
         .params
@@ -182,9 +225,14 @@ struct ExtractedStructInfo {
     derived_table_options: Vec<GeneralTableOptions>,
     simple_field_type_name_str: String,
     deprecated_cfs: Vec<Ident>,
+    migrations: Vec<(Ident, String)>,
+    schema_versions_field: Option<Ident>,
 }
 
-#[proc_macro_derive(DBMapUtils, attributes(default_options_override_fn, rename))]
+#[proc_macro_derive(
+    DBMapUtils,
+    attributes(default_options_override_fn, rename, migrations, schema_versions)
+)]
 pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemStruct);
     let name = &input.ident;
@@ -206,6 +254,8 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
         derived_table_options,
         simple_field_type_name_str,
         deprecated_cfs,
+        migrations,
+        schema_versions_field,
     } = extract_struct_info(input.clone(), allowed_strs);
 
     let (key_names, value_names): (Vec<_>, Vec<_>) = inner_types
@@ -242,6 +292,50 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
     let secondary_db_map_struct_name: proc_macro2::TokenStream =
         secondary_db_map_struct_name_str.parse().unwrap();
 
+    let migrations_fns = match &schema_versions_field {
+        None => quote! {},
+        Some(schema_versions_field) => {
+            let (migrated_fields, migrations_fn_names): (Vec<_>, Vec<proc_macro2::TokenStream>) =
+                migrations
+                    .iter()
+                    .map(|(field_name, fn_name)| (field_name, fn_name.parse().unwrap()))
+                    .unzip();
+            quote! {
+                /// Migrates the tables with registered migrations to their latest schema version
+                /// This should be called before the tables are used
+                pub fn run_migrations(&self) -> Result<(), typed_store::TypedStoreError> {
+                    #(
+                        typed_store::migration::Migrator::new(
+                            self.#migrated_fields.clone(),
+                            self.#schema_versions_field.clone(),
+                            #migrations_fn_names(),
+                        )?.run()?;
+                    )*
+                    Ok(())
+                }
+
+                /// Migrates the tables with registered migrations to their latest schema version
+                /// in the background, returning the progress and task of each migrated table
+                /// Until a table is migrated, writers must wait for the entries they write with
+                /// `MigrationProgress::wait_until_migrated`
+                pub fn spawn_migrations(&self) -> Result<Vec<(
+                    &'static str,
+                    std::sync::Arc<typed_store::migration::MigrationProgress>,
+                    typed_store::migration::MigrationTask,
+                )>, typed_store::TypedStoreError> {
+                    Ok(vec![#({
+                        let migrator = typed_store::migration::Migrator::new(
+                            self.#migrated_fields.clone(),
+                            self.#schema_versions_field.clone(),
+                            #migrations_fn_names(),
+                        )?;
+                        (stringify!(#migrated_fields), migrator.progress(), migrator.spawn())
+                    },)*])
+                }
+            }
+        }
+    };
+
     TokenStream::from(quote! {
 
         // <----------- This section generates the configurator struct -------------->
@@ -434,6 +528,8 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 )*].into_iter().collect()
             }
 
+            #migrations_fns
+
             /// This opens the DB in read only mode and returns a struct which exposes debug features
             pub fn get_read_only_handle (
                 primary_path: std::path::PathBuf,
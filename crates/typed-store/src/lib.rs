@@ -14,6 +14,7 @@ pub use rocksdb;
 pub mod traits;
 pub use traits::Map;
 pub mod metrics;
+pub mod migration;
 pub mod rocks;
 pub use typed_store_error::TypedStoreError;
 pub mod sally;
@@ -144,6 +145,38 @@ pub type StoreError = typed_store_error::TypedStoreError;
 /// 5. Other convenience features
 ///     `Tables::describe_tables` is used to get a list of the table names and key-value types as string in a BTreeMap
 ///
+/// 6. Schema migrations
+///     Tables annotated with `#[migrations = "fn_name"]` are migrated by the generated `run_migrations` and
+///     `spawn_migrations` functions, where `fn_name` returns all the `typed_store::migration::Migration`s of the
+///     table in order. Schema versions are recorded in the `DBMap<String, SchemaVersion>` annotated with `#[schema_versions]`
+///     While `spawn_migrations` runs, writers must wait for the entries they write with `MigrationProgress::wait_until_migrated`
+///```
+/// use typed_store::migration::{migrate_values, Migration, SchemaVersion};
+/// use typed_store::rocks::DBMap;
+/// use typed_store::rocks::MetricConf;
+/// use typed_store::DBMapUtils;
+/// use typed_store::traits::TypedStoreDebug;
+/// use typed_store::traits::TableSummary;
+///
+/// fn balance_migrations() -> Vec<Box<dyn Migration>> {
+///     // Balances used to be stored as u32
+///     vec![migrate_values(|_: String, balance: u32| Some(balance as u64))]
+/// }
+/// #[derive(DBMapUtils)]
+/// struct Tables {
+///     #[migrations = "balance_migrations"]
+///     balances: DBMap<String, u64>,
+///     #[schema_versions]
+///     schema_versions: DBMap<String, SchemaVersion>,
+/// }
+/// #[tokio::main]
+/// async fn main() {
+/// let primary_path = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+/// let tables = Tables::open_tables_read_write(primary_path, MetricConf::default(), None, None);
+/// tables.run_migrations().expect("Failed to migrate tables");
+/// }
+/// ```
+///
 /// // Bad usage example
 /// // Structs fields most only be of type Store<K, V> or DMBap<K, V>
 /// // This will fail to compile with error `All struct members must be of type Store<K, V> or DMBap<K, V>`
@@ -0,0 +1,528 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versioned schema migrations of tables.
+//!
+//! The schema version of each migrated table is recorded in a `DBMap<String, SchemaVersion>`
+//! table of the same database, keyed by column family name. Migrations registered for a table are
+//! numbered from 1 in order, and a table at version `n` has had the first `n` migrations applied.
+//! Migrations are applied in key order, one batch at a time. Each batch is written atomically with
+//! the key the migration resumes from, so a migration interrupted by a crash resumes where it
+//! stopped.
+//!
+//! Tables should be migrated before they are written to by code expecting the latest schema.
+//! Migrations running in the background are only safe when readers of the table tolerate both
+//! the old and the new schema in the meantime, and when writers only write entries already at the
+//! latest schema version: an entry written with the new schema ahead of the migration would be
+//! migrated again and corrupted. Writers wait for the entries they write to be migrated with
+//! [`MigrationProgress::wait_until_migrated`].
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use bincode::Options;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::info;
+
+use crate::rocks::errors::{
+    typed_store_err_from_bcs_err, typed_store_err_from_bincode_err, typed_store_err_from_rocks_err,
+};
+use crate::rocks::{be_fix_int_ser, DBMap};
+use crate::test_db::{TestDB, TestDBWriteBatch, WriteBatchOp};
+use crate::{Map, TypedStoreError};
+
+/// Number of entries migrated per batch by default.
+pub const DEFAULT_MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// Task migrating a table in the background.
+pub type MigrationTask = tokio::task::JoinHandle<Result<(), TypedStoreError>>;
+
+/// Schema version of a table.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaVersion {
+    /// Number of migrations applied to the table.
+    pub version: u32,
+    /// Raw key the migration to `version + 1` resumes from, if it is in progress.
+    pub next_key: Option<Vec<u8>>,
+}
+
+/// Migrates a table entry from one schema version to the next.
+pub trait Migration: Send + Sync {
+    /// Returns the new value of the entry with raw `key` and `value`, or `None` to delete it.
+    fn migrate(&self, key: &[u8], value: &[u8]) -> Result<Option<Vec<u8>>, TypedStoreError>;
+}
+
+/// A [`Migration`] converting values of type `Old` into values of type `New`.
+pub struct ValueMigration<K, Old, New, F> {
+    f: F,
+    _phantom: PhantomData<fn(K, Old) -> New>,
+}
+
+/// Creates a migration converting the values of a `DBMap<K, Old>` into the values of a
+/// `DBMap<K, New>`. Entries for which `f` returns `None` are deleted.
+pub fn migrate_values<K, Old, New, F>(f: F) -> Box<dyn Migration>
+where
+    K: DeserializeOwned + 'static,
+    Old: DeserializeOwned + 'static,
+    New: Serialize + 'static,
+    F: Fn(K, Old) -> Option<New> + Send + Sync + 'static,
+{
+    Box::new(ValueMigration {
+        f,
+        _phantom: PhantomData,
+    })
+}
+
+impl<K, Old, New, F> Migration for ValueMigration<K, Old, New, F>
+where
+    K: DeserializeOwned,
+    Old: DeserializeOwned,
+    New: Serialize,
+    F: Fn(K, Old) -> Option<New> + Send + Sync,
+{
+    fn migrate(&self, key: &[u8], value: &[u8]) -> Result<Option<Vec<u8>>, TypedStoreError> {
+        let config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+        let key: K = config
+            .deserialize(key)
+            .map_err(typed_store_err_from_bincode_err)?;
+        let value: Old = bcs::from_bytes(value).map_err(typed_store_err_from_bcs_err)?;
+        (self.f)(key, value)
+            .map(|new| bcs::to_bytes(&new).map_err(typed_store_err_from_bcs_err))
+            .transpose()
+    }
+}
+
+/// A table which migrations can be applied to.
+pub trait MigratableTable: Send + Sync {
+    /// Table recording the schema versions of tables.
+    type Versions: for<'a> Map<'a, String, SchemaVersion, Error = TypedStoreError> + Send + Sync;
+
+    /// Name of the table, under which its schema version is recorded.
+    fn table_name(&self) -> String;
+
+    /// Returns up to `limit` raw entries in key order, starting from `start` when given.
+    fn raw_entries(
+        &self,
+        start: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, TypedStoreError>;
+
+    /// Atomically writes migrated entries, deletes entries, and records the schema version.
+    fn write_migrated(
+        &self,
+        versions: &Self::Versions,
+        puts: Vec<(Vec<u8>, Vec<u8>)>,
+        deletes: Vec<Vec<u8>>,
+        version: &SchemaVersion,
+    ) -> Result<(), TypedStoreError>;
+}
+
+impl<K, V> MigratableTable for DBMap<K, V> {
+    type Versions = DBMap<String, SchemaVersion>;
+
+    fn table_name(&self) -> String {
+        self.cf_name().to_string()
+    }
+
+    fn raw_entries(
+        &self,
+        start: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, TypedStoreError> {
+        let mut iter = self
+            .rocksdb
+            .raw_iterator_cf(&self.cf(), self.opts.readopts());
+        match start {
+            Some(key) => iter.seek(key),
+            None => iter.seek_to_first(),
+        }
+        let mut entries = vec![];
+        while entries.len() < limit {
+            let Some((key, value)) = iter.key().zip(iter.value()) else {
+                break;
+            };
+            entries.push((key.to_vec(), value.to_vec()));
+            iter.next();
+        }
+        iter.status().map_err(typed_store_err_from_rocks_err)?;
+        Ok(entries)
+    }
+
+    fn write_migrated(
+        &self,
+        versions: &Self::Versions,
+        puts: Vec<(Vec<u8>, Vec<u8>)>,
+        deletes: Vec<Vec<u8>>,
+        version: &SchemaVersion,
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = self.batch();
+        batch
+            .insert_raw_batch(self, puts)?
+            .delete_raw_batch(self, deletes)?
+            .insert_batch(versions, [(self.table_name(), version)])?;
+        batch.write()
+    }
+}
+
+impl<K, V> MigratableTable for TestDB<K, V> {
+    type Versions = TestDB<String, SchemaVersion>;
+
+    fn table_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn raw_entries(
+        &self,
+        start: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, TypedStoreError> {
+        let rows = self.rows.read().unwrap();
+        let iter: Box<dyn Iterator<Item = _>> = match start {
+            Some(key) => Box::new(rows.range(key.to_vec()..)),
+            None => Box::new(rows.iter()),
+        };
+        Ok(iter
+            .take(limit)
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect())
+    }
+
+    fn write_migrated(
+        &self,
+        versions: &Self::Versions,
+        puts: Vec<(Vec<u8>, Vec<u8>)>,
+        deletes: Vec<Vec<u8>>,
+        version: &SchemaVersion,
+    ) -> Result<(), TypedStoreError> {
+        let mut batch = TestDBWriteBatch::default();
+        batch.ops.push_back(WriteBatchOp::InsertBatch((
+            self.rows.clone(),
+            self.name.clone(),
+            puts,
+        )));
+        batch.ops.push_back(WriteBatchOp::DeleteBatch((
+            self.rows.clone(),
+            self.name.clone(),
+            deletes,
+        )));
+        batch.insert_batch(versions, [(self.table_name(), version)])?;
+        batch.write()
+    }
+}
+
+/// Progress of the migrations of a table.
+#[derive(Debug)]
+pub struct MigrationProgress {
+    /// Version the table is migrated to.
+    pub target_version: u32,
+    /// Recorded schema version of the table.
+    current: watch::Sender<SchemaVersion>,
+    migrated_entries: AtomicU64,
+}
+
+impl MigrationProgress {
+    /// Current schema version of the table.
+    pub fn version(&self) -> u32 {
+        self.current.borrow().version
+    }
+
+    /// Number of entries processed by the migrations so far.
+    pub fn migrated_entries(&self) -> u64 {
+        self.migrated_entries.load(Ordering::Relaxed)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.version() >= self.target_version
+    }
+
+    /// Returns true if the entry with `key` is at the latest schema version, and can be written
+    /// with the latest schema.
+    pub fn is_migrated<K: Serialize>(&self, key: &K) -> Result<bool, TypedStoreError> {
+        let key = be_fix_int_ser(key)?;
+        Ok(is_migrated(
+            &self.current.borrow(),
+            self.target_version,
+            &key,
+        ))
+    }
+
+    /// Waits until the entry with `key` is at the latest schema version, and can be written with
+    /// the latest schema. Never returns if the migration of the table fails before reaching it.
+    pub async fn wait_until_migrated<K: Serialize>(&self, key: &K) -> Result<(), TypedStoreError> {
+        let key = be_fix_int_ser(key)?;
+        let mut current = self.current.subscribe();
+        current
+            .wait_for(|current| is_migrated(current, self.target_version, &key))
+            .await
+            .expect("Sender is owned by the progress");
+        Ok(())
+    }
+}
+
+/// Returns true if the entry with raw `key` of a table at schema version `current` is at
+/// `target_version`.
+fn is_migrated(current: &SchemaVersion, target_version: u32, key: &[u8]) -> bool {
+    if current.version >= target_version {
+        return true;
+    }
+    // Entries before the key the last migration resumes from are already migrated.
+    current.version + 1 == target_version
+        && current
+            .next_key
+            .as_deref()
+            .is_some_and(|next_key| key < next_key)
+}
+
+/// Applies the registered migrations of a table which have not been applied yet.
+pub struct Migrator<T: MigratableTable> {
+    table: T,
+    versions: T::Versions,
+    migrations: Vec<Box<dyn Migration>>,
+    batch_size: usize,
+    progress: Arc<MigrationProgress>,
+}
+
+impl<T: MigratableTable> Migrator<T> {
+    /// Creates a migrator of `table`, whose schema version is recorded in `versions`.
+    /// `migrations` are all the migrations ever registered for the table, in order.
+    pub fn new(
+        table: T,
+        versions: T::Versions,
+        migrations: Vec<Box<dyn Migration>>,
+    ) -> Result<Self, TypedStoreError> {
+        let progress = Arc::new(MigrationProgress {
+            target_version: migrations.len() as u32,
+            current: watch::Sender::new(SchemaVersion::default()),
+            migrated_entries: AtomicU64::new(0),
+        });
+        let migrator = Self {
+            table,
+            versions,
+            migrations,
+            batch_size: DEFAULT_MIGRATION_BATCH_SIZE,
+            progress,
+        };
+        let current = migrator.schema_version()?;
+        migrator.progress.current.send_replace(current);
+        Ok(migrator)
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn progress(&self) -> Arc<MigrationProgress> {
+        self.progress.clone()
+    }
+
+    /// Returns the recorded schema version of the table.
+    pub fn schema_version(&self) -> Result<SchemaVersion, TypedStoreError> {
+        let version = self
+            .versions
+            .get(&self.table.table_name())?
+            .unwrap_or_default();
+        if version.version > self.progress.target_version {
+            return Err(TypedStoreError::SerializationError(format!(
+                "table {} is at schema version {}, newer than the latest known version {}",
+                self.table.table_name(),
+                version.version,
+                self.progress.target_version
+            )));
+        }
+        Ok(version)
+    }
+
+    /// Migrates one batch of entries. Returns true once the table is at the latest version.
+    pub fn step(&self) -> Result<bool, TypedStoreError> {
+        let current = self.schema_version()?;
+        self.progress.current.send_replace(current.clone());
+        if self.progress.is_done() {
+            return Ok(true);
+        }
+
+        let migration = &self.migrations[current.version as usize];
+        let entries = self
+            .table
+            .raw_entries(current.next_key.as_deref(), self.batch_size)?;
+        let mut puts = vec![];
+        let mut deletes = vec![];
+        for (key, value) in &entries {
+            match migration.migrate(key, value)? {
+                Some(value) => puts.push((key.clone(), value)),
+                None => deletes.push(key.clone()),
+            }
+        }
+        let next = match entries.last() {
+            Some((last, _)) if entries.len() == self.batch_size => {
+                // The smallest key greater than the last migrated key.
+                let mut next_key = last.clone();
+                next_key.push(0);
+                SchemaVersion {
+                    version: current.version,
+                    next_key: Some(next_key),
+                }
+            }
+            _ => SchemaVersion {
+                version: current.version + 1,
+                next_key: None,
+            },
+        };
+        self.table
+            .write_migrated(&self.versions, puts, deletes, &next)?;
+
+        self.progress
+            .migrated_entries
+            .fetch_add(entries.len() as u64, Ordering::Relaxed);
+        self.progress.current.send_replace(next.clone());
+        if next.version > current.version {
+            info!(
+                "Migrated table {} to schema version {}",
+                self.table.table_name(),
+                next.version
+            );
+        }
+        Ok(self.progress.is_done())
+    }
+
+    /// Migrates the table to the latest version.
+    pub fn run(&self) -> Result<(), TypedStoreError> {
+        while !self.step()? {}
+        Ok(())
+    }
+
+    /// Migrates the table to the latest version in the background.
+    pub fn spawn(self) -> MigrationTask
+    where
+        T: 'static,
+    {
+        tokio::task::spawn_blocking(move || self.run())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct ValueV1 {
+        amount: u32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct ValueV2 {
+        amount: u64,
+        label: String,
+    }
+
+    fn migrations() -> Vec<Box<dyn Migration>> {
+        vec![
+            // Drops odd keys.
+            migrate_values(|key: u64, v: ValueV1| (key % 2 == 0).then_some(v)),
+            migrate_values(|key: u64, v: ValueV1| {
+                Some(ValueV2 {
+                    amount: v.amount as u64,
+                    label: format!("key-{key}"),
+                })
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_migrate_test_db() {
+        let table = TestDB::<u64, ValueV1>::open();
+        let versions = TestDB::<String, SchemaVersion>::open();
+        for key in 0..25 {
+            table.insert(&key, &ValueV1 { amount: 7 }).unwrap();
+        }
+
+        let migrator = Migrator::new(table.clone(), versions.clone(), migrations())
+            .unwrap()
+            .with_batch_size(10);
+        let progress = migrator.progress();
+        // Three batches for the first migration.
+        assert!(!migrator.step().unwrap());
+        assert_eq!(
+            migrator.schema_version().unwrap().next_key,
+            Some({
+                let mut key = crate::rocks::be_fix_int_ser(&9u64).unwrap();
+                key.push(0);
+                key
+            })
+        );
+        migrator.step().unwrap();
+        migrator.step().unwrap();
+        assert_eq!(progress.version(), 1);
+        assert_eq!(progress.migrated_entries(), 25);
+
+        // A new migrator resumes from the recorded version.
+        let migrator = Migrator::new(table.clone(), versions.clone(), migrations())
+            .unwrap()
+            .with_batch_size(10);
+        let progress = migrator.progress();
+        assert_eq!(progress.version(), 1);
+
+        // Only entries before the key the last migration resumes from can be written with the
+        // latest schema.
+        migrator.step().unwrap();
+        assert!(progress.is_migrated(&8u64).unwrap());
+        assert!(!progress.is_migrated(&20u64).unwrap());
+        progress.wait_until_migrated(&8u64).await.unwrap();
+
+        migrator.run().unwrap();
+        assert!(progress.is_migrated(&20u64).unwrap());
+        assert!(migrator.progress().is_done());
+        assert_eq!(
+            migrator.schema_version().unwrap(),
+            SchemaVersion {
+                version: 2,
+                next_key: None
+            }
+        );
+
+        let config = bincode::DefaultOptions::new()
+            .with_big_endian()
+            .with_fixint_encoding();
+        let entries: Vec<(u64, ValueV2)> = table
+            .rows
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (config.deserialize(k).unwrap(), bcs::from_bytes(v).unwrap()))
+            .collect();
+        assert_eq!(entries.len(), 13);
+        assert_eq!(
+            entries[1],
+            (
+                2,
+                ValueV2 {
+                    amount: 7,
+                    label: "key-2".to_string()
+                }
+            )
+        );
+
+        // Migrating again is a no-op.
+        assert!(migrator.step().unwrap());
+    }
+
+    #[test]
+    fn test_reject_unknown_schema_version() {
+        let table = TestDB::<u64, ValueV1>::open();
+        let versions = TestDB::<String, SchemaVersion>::open();
+        versions
+            .insert(
+                &table.name,
+                &SchemaVersion {
+                    version: 3,
+                    next_key: None,
+                },
+            )
+            .unwrap();
+        assert!(Migrator::new(table, versions, migrations()).is_err());
+    }
+}
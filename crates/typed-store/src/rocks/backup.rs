@@ -0,0 +1,495 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Backup and restore of selected column families.
+//!
+//! A backup exports column families from a single consistent snapshot into SST files, which can
+//! later be ingested into a database with the same column families. Each column family is split
+//! into chunks at content defined boundaries, and every chunk is named after the digest of its
+//! content. Unchanged ranges of a column family therefore produce the same files across backups,
+//! so an incremental backup only needs to write and ship the chunks missing from its parent.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    hash::Hasher,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use fastcrypto::{
+    encoding::{Encoding, Hex},
+    hash::{HashFunction, Sha3_256},
+};
+use rocksdb::{IngestExternalFileOptions, ReadOptions, SstFileWriter};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use twox_hash::XxHash64;
+
+use super::{errors::typed_store_err_from_rocks_err, RocksDB, RocksDBSnapshot};
+use crate::TypedStoreError;
+
+const MANIFEST_VERSION: u32 = 1;
+
+/// Options of a column family backup.
+#[derive(Clone, Debug)]
+pub struct BackupOptions {
+    /// Chunks are not cut before reaching this size, in bytes of keys and values.
+    pub min_chunk_size: usize,
+    /// Chunks are always cut after reaching this size, in bytes of keys and values.
+    pub max_chunk_size: usize,
+    /// Past `min_chunk_size`, a chunk is cut after keys whose hash is a multiple of this value.
+    pub boundary_modulus: u64,
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        Self {
+            min_chunk_size: 16 << 20,
+            max_chunk_size: 256 << 20,
+            boundary_modulus: 1 << 16,
+        }
+    }
+}
+
+/// Describes the files making up a backup of column families.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    /// Files of each column family, in key order.
+    pub column_families: BTreeMap<String, Vec<BackupFile>>,
+}
+
+/// An SST file holding a chunk of a column family.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Name of the file, derived from the digest of the entries it holds.
+    pub name: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Number of entries in the file.
+    pub num_entries: u64,
+    /// Hex encoded SHA3-256 digest of the file.
+    pub checksum: String,
+}
+
+impl BackupManifest {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TypedStoreError> {
+        let manifest: Self = bcs::from_bytes(bytes)
+            .map_err(|e| TypedStoreError::SerializationError(e.to_string()))?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(TypedStoreError::SerializationError(format!(
+                "unsupported backup manifest version {}",
+                manifest.version
+            )));
+        }
+        Ok(manifest)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Serializing a backup manifest should not fail")
+    }
+
+    /// Returns all files of the backup.
+    pub fn files(&self) -> impl Iterator<Item = &BackupFile> {
+        self.column_families.values().flatten()
+    }
+
+    /// Returns the files of the backup which are not part of `parent`.
+    pub fn new_files<'a>(&'a self, parent: Option<&BackupManifest>) -> Vec<&'a BackupFile> {
+        let existing: HashMap<_, _> = parent
+            .into_iter()
+            .flat_map(|p| p.files())
+            .map(|f| (f.name.as_str(), f))
+            .collect();
+        self.files()
+            .filter(|f| !existing.contains_key(f.name.as_str()))
+            .collect()
+    }
+}
+
+impl RocksDB {
+    /// Exports `column_families` from a single consistent snapshot of the database into SST files
+    /// under `dir`, and returns the manifest of the backup.
+    ///
+    /// When `parent` is given, chunks whose content is already part of it are not kept in `dir`,
+    /// and only the files returned by [`BackupManifest::new_files`] need to be shipped along
+    /// with the parent's files.
+    pub fn backup_column_families(
+        &self,
+        column_families: &[&str],
+        dir: &Path,
+        parent: Option<&BackupManifest>,
+        options: &BackupOptions,
+    ) -> Result<BackupManifest, TypedStoreError> {
+        fs::create_dir_all(dir).map_err(io_error)?;
+        let parent_files: HashMap<_, _> = parent
+            .into_iter()
+            .flat_map(|p| p.files())
+            .map(|f| (f.name.as_str(), f))
+            .collect();
+
+        let snapshot = self.snapshot();
+        let mut manifest = BackupManifest {
+            version: MANIFEST_VERSION,
+            column_families: BTreeMap::new(),
+        };
+        for cf_name in column_families {
+            let files =
+                self.backup_column_family(cf_name, &snapshot, dir, &parent_files, options)?;
+            info!(
+                "Backed up column family {cf_name} into {} files",
+                files.len()
+            );
+            manifest.column_families.insert(cf_name.to_string(), files);
+        }
+        Ok(manifest)
+    }
+
+    fn backup_column_family(
+        &self,
+        cf_name: &str,
+        snapshot: &RocksDBSnapshot<'_>,
+        dir: &Path,
+        parent_files: &HashMap<&str, &BackupFile>,
+        options: &BackupOptions,
+    ) -> Result<Vec<BackupFile>, TypedStoreError> {
+        let cf = self
+            .cf_handle(cf_name)
+            .ok_or_else(|| TypedStoreError::UnregisteredColumn(cf_name.to_string()))?;
+        let mut readopts = ReadOptions::default();
+        snapshot.set_in(&mut readopts);
+        let mut iter = self.raw_iterator_cf(&cf, readopts);
+        iter.seek_to_first();
+
+        let sst_options = rocksdb::Options::default();
+        let mut files = vec![];
+        let mut chunk: Option<ChunkWriter<'_>> = None;
+        while let Some((key, value)) = iter.key().zip(iter.value()) {
+            let writer = match &mut chunk {
+                Some(writer) => writer,
+                None => chunk.insert(ChunkWriter::new(
+                    &sst_options,
+                    dir.join(format!("{cf_name}-{}.tmp", files.len())),
+                )?),
+            };
+            writer.put(key, value)?;
+            if writer.size >= options.max_chunk_size
+                || (writer.size >= options.min_chunk_size && is_boundary(key, options))
+            {
+                let writer = chunk.take().expect("Chunk writer should exist");
+                files.push(writer.finish(cf_name, dir, parent_files)?);
+            }
+            iter.next();
+        }
+        iter.status().map_err(typed_store_err_from_rocks_err)?;
+        if let Some(writer) = chunk {
+            files.push(writer.finish(cf_name, dir, parent_files)?);
+        }
+        Ok(files)
+    }
+
+    /// Ingests the backup files in `dir` into the column families of `manifest`, which must all
+    /// be empty. The backup should be verified with [`verify_backup`] before it is restored.
+    ///
+    /// Column families are ingested one at a time, so a failed restore can leave some of them
+    /// restored. Restores should target a new database, which is only used once this returns.
+    pub fn restore_column_families(
+        &self,
+        manifest: &BackupManifest,
+        dir: &Path,
+    ) -> Result<(), TypedStoreError> {
+        let Self::DBWithThreadMode(db) = self else {
+            return Err(TypedStoreError::RocksDBError(
+                "restoring column families is not supported on transactional databases".into(),
+            ));
+        };
+        // Check every column family before ingesting anything.
+        for cf_name in manifest.column_families.keys() {
+            let cf = self
+                .cf_handle(cf_name)
+                .ok_or_else(|| TypedStoreError::UnregisteredColumn(cf_name.to_string()))?;
+            let mut iter = self.raw_iterator_cf(&cf, ReadOptions::default());
+            iter.seek_to_first();
+            if iter.valid() {
+                return Err(TypedStoreError::RocksDBError(format!(
+                    "cannot restore into non-empty column family {cf_name}"
+                )));
+            }
+            iter.status().map_err(typed_store_err_from_rocks_err)?;
+        }
+
+        for (cf_name, files) in &manifest.column_families {
+            if files.is_empty() {
+                continue;
+            }
+            let cf = self
+                .cf_handle(cf_name)
+                .ok_or_else(|| TypedStoreError::UnregisteredColumn(cf_name.to_string()))?;
+            let mut ingest_options = IngestExternalFileOptions::default();
+            ingest_options.set_move_files(false);
+            let paths: Vec<PathBuf> = files.iter().map(|f| dir.join(&f.name)).collect();
+            db.underlying
+                .ingest_external_file_cf_opts(&cf, &ingest_options, paths)
+                .map_err(typed_store_err_from_rocks_err)?;
+            info!(
+                "Restored column family {cf_name} from {} files",
+                files.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Checks that every file of `manifest` is present in `dir` with the expected size and checksum.
+pub fn verify_backup(manifest: &BackupManifest, dir: &Path) -> Result<(), TypedStoreError> {
+    verify_backup_files(manifest.files(), dir)
+}
+
+/// Checks that each of `files` is present in `dir` with the expected size and checksum.
+pub fn verify_backup_files<'a>(
+    files: impl IntoIterator<Item = &'a BackupFile>,
+    dir: &Path,
+) -> Result<(), TypedStoreError> {
+    for file in files {
+        let path = dir.join(&file.name);
+        let size = fs::metadata(&path).map_err(io_error)?.len();
+        if size != file.size {
+            return Err(TypedStoreError::RocksDBError(format!(
+                "backup file {} has size {size}, expected {}",
+                file.name, file.size
+            )));
+        }
+        let checksum = file_checksum(&path)?;
+        if checksum != file.checksum {
+            return Err(TypedStoreError::RocksDBError(format!(
+                "backup file {} has checksum {checksum}, expected {}",
+                file.name, file.checksum
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Computes the hex encoded SHA3-256 digest of the file at `path`.
+pub fn file_checksum(path: &Path) -> Result<String, TypedStoreError> {
+    let mut file = fs::File::open(path).map_err(io_error)?;
+    let mut hasher = Sha3_256::default();
+    let mut buf = vec![0; 1 << 20];
+    loop {
+        let n = file.read(&mut buf).map_err(io_error)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(Hex::encode(hasher.finalize().digest))
+}
+
+/// Writes a chunk of a column family into a temporary SST file, tracking the digest of its
+/// entries.
+struct ChunkWriter<'a> {
+    writer: SstFileWriter<'a>,
+    path: PathBuf,
+    hasher: Sha3_256,
+    size: usize,
+    num_entries: u64,
+}
+
+impl<'a> ChunkWriter<'a> {
+    fn new(options: &'a rocksdb::Options, path: PathBuf) -> Result<Self, TypedStoreError> {
+        let writer = SstFileWriter::create(options);
+        writer.open(&path).map_err(typed_store_err_from_rocks_err)?;
+        Ok(Self {
+            writer,
+            path,
+            hasher: Sha3_256::default(),
+            size: 0,
+            num_entries: 0,
+        })
+    }
+
+    fn put(&mut self, key: &[u8], value: &[u8]) -> Result<(), TypedStoreError> {
+        self.writer
+            .put(key, value)
+            .map_err(typed_store_err_from_rocks_err)?;
+        self.hasher.update((key.len() as u64).to_le_bytes());
+        self.hasher.update(key);
+        self.hasher.update((value.len() as u64).to_le_bytes());
+        self.hasher.update(value);
+        self.size += key.len() + value.len();
+        self.num_entries += 1;
+        Ok(())
+    }
+
+    /// Finishes the SST file and renames it after the digest of its entries. If the parent
+    /// backup already has a file with the same entries, the new file is dropped and the parent's
+    /// file is reused.
+    fn finish(
+        mut self,
+        cf_name: &str,
+        dir: &Path,
+        parent_files: &HashMap<&str, &BackupFile>,
+    ) -> Result<BackupFile, TypedStoreError> {
+        self.writer
+            .finish()
+            .map_err(typed_store_err_from_rocks_err)?;
+        let name = format!(
+            "{cf_name}-{}.sst",
+            Hex::encode(self.hasher.finalize().digest)
+        );
+        if let Some(file) = parent_files.get(name.as_str()) {
+            fs::remove_file(&self.path).map_err(io_error)?;
+            return Ok((*file).clone());
+        }
+        let path = dir.join(&name);
+        fs::rename(&self.path, &path).map_err(io_error)?;
+        Ok(BackupFile {
+            size: fs::metadata(&path).map_err(io_error)?.len(),
+            checksum: file_checksum(&path)?,
+            num_entries: self.num_entries,
+            name,
+        })
+    }
+}
+
+fn is_boundary(key: &[u8], options: &BackupOptions) -> bool {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(key);
+    hasher.finish() % options.boundary_modulus.max(1) == 0
+}
+
+fn io_error(e: std::io::Error) -> TypedStoreError {
+    TypedStoreError::RocksDBError(format!("io error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rocks::{open_cf, MetricConf};
+    use rocksdb::WriteOptions;
+
+    const CFS: [&str; 3] = ["objects", "effects", "unrelated"];
+
+    fn small_chunks() -> BackupOptions {
+        BackupOptions {
+            min_chunk_size: 256,
+            max_chunk_size: 4096,
+            boundary_modulus: 8,
+        }
+    }
+
+    fn put(db: &RocksDB, cf: &str, range: std::ops::Range<u32>, tag: &str) {
+        let cf = db.cf_handle(cf).unwrap();
+        for i in range {
+            db.put_cf(
+                &cf,
+                i.to_be_bytes(),
+                format!("{tag}-{i}"),
+                &WriteOptions::default(),
+            )
+            .unwrap();
+        }
+    }
+
+    fn read_all(db: &RocksDB, cf: &str) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let cf = db.cf_handle(cf).unwrap();
+        let mut iter = db.raw_iterator_cf(&cf, ReadOptions::default());
+        iter.seek_to_first();
+        let mut entries = vec![];
+        while let Some((k, v)) = iter.key().zip(iter.value()) {
+            entries.push((k.to_vec(), v.to_vec()));
+            iter.next();
+        }
+        entries
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_restore_column_families() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_cf(dir.path().join("db"), None, MetricConf::default(), &CFS).unwrap();
+        put(&db, "objects", 0..1000, "v1");
+        put(&db, "effects", 0..10, "v1");
+        put(&db, "unrelated", 0..10, "v1");
+
+        let backup_dir = dir.path().join("backup");
+        let options = small_chunks();
+        let full = db
+            .backup_column_families(&CFS[..2], &backup_dir, None, &options)
+            .unwrap();
+        assert!(full.column_families["objects"].len() > 1);
+        assert_eq!(full.new_files(None).len(), full.files().count());
+        assert_eq!(BackupManifest::from_bytes(&full.to_bytes()).unwrap(), full);
+        verify_backup(&full, &backup_dir).unwrap();
+
+        // Only chunks covering the modified keys are new in an incremental backup.
+        put(&db, "objects", 990..1010, "v2");
+        let incremental_dir = dir.path().join("incremental");
+        let incremental = db
+            .backup_column_families(&CFS[..2], &incremental_dir, Some(&full), &options)
+            .unwrap();
+        let new_files = incremental.new_files(Some(&full));
+        assert!(!new_files.is_empty());
+        assert!(new_files.len() < full.column_families["objects"].len());
+        assert_eq!(
+            fs::read_dir(&incremental_dir).unwrap().count(),
+            new_files.len()
+        );
+
+        // Restore into another database, combining the files of both backups.
+        for entry in fs::read_dir(&incremental_dir).unwrap() {
+            let entry = entry.unwrap();
+            fs::copy(entry.path(), backup_dir.join(entry.file_name())).unwrap();
+        }
+        verify_backup(&incremental, &backup_dir).unwrap();
+        let restored = open_cf(
+            dir.path().join("restored"),
+            None,
+            MetricConf::default(),
+            &CFS,
+        )
+        .unwrap();
+        restored
+            .restore_column_families(&incremental, &backup_dir)
+            .unwrap();
+        for cf in &CFS[..2] {
+            assert_eq!(read_all(&restored, cf), read_all(&db, cf));
+        }
+        assert!(read_all(&restored, "unrelated").is_empty());
+
+        // Restoring over existing entries is rejected before any column family is modified.
+        let existing = open_cf(
+            dir.path().join("existing"),
+            None,
+            MetricConf::default(),
+            &CFS,
+        )
+        .unwrap();
+        put(&existing, "effects", 5000..5010, "stale");
+        assert!(existing
+            .restore_column_families(&incremental, &backup_dir)
+            .is_err());
+        assert!(read_all(&existing, "objects").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_backup_detects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_cf(dir.path().join("db"), None, MetricConf::default(), &CFS).unwrap();
+        put(&db, "objects", 0..100, "v1");
+
+        let backup_dir = dir.path().join("backup");
+        let manifest = db
+            .backup_column_families(&["objects"], &backup_dir, None, &small_chunks())
+            .unwrap();
+        let file = manifest.files().next().unwrap();
+        let path = backup_dir.join(&file.name);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[0] ^= 0xff;
+        fs::write(&path, bytes).unwrap();
+        assert!(verify_backup(&manifest, &backup_dir).is_err());
+
+        fs::remove_file(&path).unwrap();
+        assert!(verify_backup(&manifest, &backup_dir).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+pub mod backup;
 pub mod errors;
 pub(crate) mod iter;
 pub(crate) mod keys;
@@ -599,6 +600,14 @@ pub enum RocksDBSnapshot<'a> {
 }
 
 impl<'a> RocksDBSnapshot<'a> {
+    /// Makes reads with `readopts` observe this snapshot.
+    pub fn set_in(&self, readopts: &mut ReadOptions) {
+        match self {
+            Self::DBWithThreadMode(s) => readopts.set_snapshot(s),
+            Self::OptimisticTransactionDB(s) => readopts.set_snapshot(s),
+        }
+    }
+
     pub fn multi_get_cf_opt<'b: 'a, K, I, W>(
         &'a self,
         keys: I,
@@ -1543,6 +1552,36 @@ impl DBBatch {
         Ok(self)
     }
 
+    /// inserts a range of already serialized (key, value) pairs given as an iterator
+    pub fn insert_raw_batch<K, V>(
+        &mut self,
+        db: &DBMap<K, V>,
+        new_vals: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+    ) -> Result<&mut Self, TypedStoreError> {
+        if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
+            return Err(TypedStoreError::CrossDBBatch);
+        }
+        for (k_buf, v_buf) in new_vals {
+            self.batch.put_cf(&db.cf(), k_buf, v_buf);
+        }
+        Ok(self)
+    }
+
+    /// deletes a set of already serialized keys given as an iterator
+    pub fn delete_raw_batch<K, V>(
+        &mut self,
+        db: &DBMap<K, V>,
+        purged_keys: impl IntoIterator<Item = Vec<u8>>,
+    ) -> Result<&mut Self, TypedStoreError> {
+        if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
+            return Err(TypedStoreError::CrossDBBatch);
+        }
+        for k_buf in purged_keys {
+            self.batch.delete_cf(&db.cf(), k_buf);
+        }
+        Ok(self)
+    }
+
     /// merges a range of (key, value) pairs given as an iterator
     pub fn merge_batch<J: Borrow<K>, K: Serialize, U: Borrow<V>, V: Serialize>(
         &mut self,
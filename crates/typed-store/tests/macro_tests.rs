@@ -11,6 +11,7 @@ use std::fmt::Debug;
 use std::sync::Mutex;
 use std::time::Duration;
 use typed_store::metrics::SamplingInterval;
+use typed_store::migration::{migrate_values, Migration, SchemaVersion};
 use typed_store::rocks::list_tables;
 use typed_store::rocks::DBMap;
 use typed_store::rocks::RocksDBAccessType;
@@ -228,6 +229,77 @@ async fn deprecate_test() {
     }
 }
 
+#[derive(DBMapUtils)]
+struct MigratedTables {
+    table1: DBMap<String, String>,
+    #[migrations = "table2_migrations"]
+    table2: DBMap<i32, u64>,
+    #[schema_versions]
+    schema_versions: DBMap<String, SchemaVersion>,
+}
+
+fn table2_migrations() -> Vec<Box<dyn Migration>> {
+    vec![migrate_values(|_: i32, value: String| {
+        Some(value.len() as u64)
+    })]
+}
+
+#[tokio::test]
+async fn migration_test() {
+    let dbdir = temp_dir();
+    {
+        let original_db =
+            Tables::open_tables_read_write(dbdir.clone(), MetricConf::default(), None, None);
+        for i in 0..10 {
+            original_db
+                .table2
+                .insert(&i, &"x".repeat(i as usize))
+                .unwrap();
+        }
+    }
+    for _ in 0..2 {
+        let db = MigratedTables::open_tables_read_write(
+            dbdir.clone(),
+            MetricConf::default(),
+            None,
+            None,
+        );
+        db.run_migrations().unwrap();
+        for i in 0..10 {
+            assert_eq!(db.table2.get(&i), Ok(Some(i as u64)));
+        }
+        assert_eq!(
+            db.schema_versions.get(&"table2".to_string()).unwrap(),
+            Some(SchemaVersion {
+                version: 1,
+                next_key: None
+            })
+        );
+    }
+}
+
+#[tokio::test]
+async fn background_migration_test() {
+    let dbdir = temp_dir();
+    {
+        let original_db =
+            Tables::open_tables_read_write(dbdir.clone(), MetricConf::default(), None, None);
+        original_db.table2.insert(&1, &"abc".to_string()).unwrap();
+    }
+    let db = MigratedTables::open_tables_read_write(dbdir, MetricConf::default(), None, None);
+    for (table, progress, task) in db.spawn_migrations().unwrap() {
+        assert_eq!(table, "table2");
+        // A writer waits for the entry to be migrated before writing it with the new schema.
+        progress.wait_until_migrated(&2).await.unwrap();
+        db.table2.insert(&2, &10).unwrap();
+        task.await.unwrap().unwrap();
+        assert!(progress.is_done());
+        assert_eq!(progress.migrated_entries(), 1);
+    }
+    assert_eq!(db.table2.get(&1), Ok(Some(3)));
+    assert_eq!(db.table2.get(&2), Ok(Some(10)));
+}
+
 #[derive(SallyDB)]
 pub struct SallyDBExample {
     col1: SallyColumn<String, String>,